@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rkyv_fuzz::Schema;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = rkyv::fuzz::corrupt::<Schema>(data);
+});
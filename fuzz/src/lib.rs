@@ -0,0 +1,37 @@
+//! A schema that exercises most of the archived types supported by rkyv.
+//!
+//! The fuzz targets check this schema against the validator. To fuzz your own types, derive
+//! `Arbitrary` for them and call the entry points in `rkyv::fuzz` from your own fuzz targets.
+
+use arbitrary::Arbitrary;
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
+
+#[derive(Arbitrary, Archive, Serialize, Deserialize, Debug, PartialEq)]
+#[archive_attr(derive(CheckBytes))]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Int(i64),
+    Char(char),
+    String(String),
+    Bytes(Box<[u8]>),
+    List(Vec<u32>),
+    Pair(u16, Option<String>),
+    Record { id: u128, shared: Rc<String> },
+}
+
+#[derive(Arbitrary, Archive, Serialize, Deserialize, Debug, PartialEq)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Schema {
+    pub name: String,
+    pub values: Vec<Value>,
+    pub tags: HashMap<String, u32>,
+    pub sorted: BTreeMap<u8, Vec<String>>,
+    pub fixed: [u16; 4],
+    pub result: Result<u64, String>,
+}
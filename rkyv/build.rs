@@ -1,4 +1,4 @@
-use std::env;
+use std::{env, process::Command};
 
 fn main() {
     let mut has_atomic32 = true;
@@ -36,4 +36,17 @@ fn main() {
     if has_atomic32 {
         println!("cargo:rustc-cfg=has_atomics");
     }
+
+    // The diagnostic namespace was stabilized in Rust 1.78
+    println!("cargo:rustc-check-cfg=cfg(has_diagnostic_namespace)");
+    if matches!(rustc_minor_version(), Some(minor) if minor >= 78) {
+        println!("cargo:rustc-cfg=has_diagnostic_namespace");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split('.').nth(1)?.parse().ok()
 }
@@ -0,0 +1,512 @@
+//! Arenas that refer to their elements with typed indices.
+//!
+//! An [`Arena`] stores its elements in a single vector and hands out [`ArenaIndex`] handles to
+//! them. Archiving an index only stores its position, so structures that refer to other elements
+//! through indices (like ASTs, scene graphs, and other graphs that may have cycles or shared
+//! nodes) can be archived without using `Rc` or relative pointers.
+//!
+//! Indices are typed by the unarchived element type, so types that contain indices to themselves
+//! don't need `#[omit_bounds]`.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     archived_root,
+//!     arena::{Arena, ArenaIndex},
+//!     Archive,
+//!     Deserialize,
+//!     Infallible,
+//!     Serialize,
+//! };
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Node {
+//!     name: String,
+//!     edges: Vec<ArenaIndex<Node>>,
+//! }
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Graph {
+//!     nodes: Arena<Node>,
+//!     root: ArenaIndex<Node>,
+//! }
+//!
+//! let mut nodes = Arena::new();
+//! let a = nodes.push(Node { name: "a".to_string(), edges: Vec::new() });
+//! let b = nodes.push(Node { name: "b".to_string(), edges: vec![a] });
+//! // Cycles are just indices
+//! nodes[a].edges.push(b);
+//!
+//! let graph = Graph { nodes, root: a };
+//! let bytes = rkyv::to_bytes::<_, 256>(&graph).unwrap();
+//! let archived = unsafe { archived_root::<Graph>(&bytes) };
+//!
+//! let root = &archived.nodes[archived.root];
+//! let next = &archived.nodes[root.edges[0]];
+//! assert_eq!(next.name, "b");
+//! assert_eq!(archived.nodes[next.edges[0]].name, "a");
+//!
+//! let deserialized: Graph = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized.nodes[deserialized.root].name, "a");
+//! ```
+
+use crate::{vec::ArchivedVec, Archive, Archived, Deserialize, Fallible, Serialize};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+use core::{
+    cmp, fmt, hash,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    pin::Pin,
+};
+
+/// A typed index of an element in an [`Arena`].
+///
+/// See the [module-level documentation](self) for more information.
+pub struct ArenaIndex<T> {
+    index: usize,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> ArenaIndex<T> {
+    /// Creates an arena index from the position of an element.
+    #[inline]
+    pub const fn new(index: usize) -> Self {
+        Self {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the position of the element in its arena.
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.index
+    }
+}
+
+impl<T> Clone for ArenaIndex<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArenaIndex<T> {}
+
+impl<T> fmt::Debug for ArenaIndex<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArenaIndex").field(&self.index).finish()
+    }
+}
+
+impl<T> PartialEq for ArenaIndex<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for ArenaIndex<T> {}
+
+impl<T> PartialOrd for ArenaIndex<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for ArenaIndex<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T> hash::Hash for ArenaIndex<T> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state)
+    }
+}
+
+/// An archived [`ArenaIndex`].
+///
+/// Like `ArenaIndex`, this is typed by the unarchived element type.
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedArenaIndex<T> {
+    index: Archived<usize>,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> ArchivedArenaIndex<T> {
+    /// Returns the position of the element in its arena.
+    #[inline]
+    pub fn index(self) -> usize {
+        from_archived!(self.index) as usize
+    }
+}
+
+impl<T> Clone for ArchivedArenaIndex<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArchivedArenaIndex<T> {}
+
+impl<T> fmt::Debug for ArchivedArenaIndex<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArchivedArenaIndex")
+            .field(&self.index())
+            .finish()
+    }
+}
+
+impl<T> PartialEq for ArchivedArenaIndex<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.index() == other.index()
+    }
+}
+
+impl<T> Eq for ArchivedArenaIndex<T> {}
+
+impl<T> PartialEq<ArenaIndex<T>> for ArchivedArenaIndex<T> {
+    #[inline]
+    fn eq(&self, other: &ArenaIndex<T>) -> bool {
+        self.index() == other.index()
+    }
+}
+
+impl<T> PartialEq<ArchivedArenaIndex<T>> for ArenaIndex<T> {
+    #[inline]
+    fn eq(&self, other: &ArchivedArenaIndex<T>) -> bool {
+        self.index() == other.index()
+    }
+}
+
+impl<T> PartialOrd for ArchivedArenaIndex<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for ArchivedArenaIndex<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
+impl<T> hash::Hash for ArchivedArenaIndex<T> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index().hash(state)
+    }
+}
+
+impl<T> Archive for ArenaIndex<T> {
+    type Archived = ArchivedArenaIndex<T>;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        let (fp, fo) = out_field!(out.index);
+        self.index.resolve(pos + fp, (), fo);
+    }
+}
+
+impl<T, S: Fallible + ?Sized> Serialize<S> for ArenaIndex<T> {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<T, D: Fallible + ?Sized> Deserialize<ArenaIndex<T>, D> for ArchivedArenaIndex<T> {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<ArenaIndex<T>, D::Error> {
+        Ok(ArenaIndex::new(self.index()))
+    }
+}
+
+/// An archived [`Arena`].
+///
+/// Unlike [`ArchivedArenaIndex`], this is typed by the archived element type. Indices into the
+/// arena are not checked during validation, so looking up an index that is out of bounds returns
+/// `None` instead.
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedArena<T> {
+    items: ArchivedVec<T>,
+}
+
+impl<T> ArchivedArena<T> {
+    /// Gets the element with the given index, if it exists.
+    #[inline]
+    pub fn get<U: Archive<Archived = T>>(&self, index: ArchivedArenaIndex<U>) -> Option<&T> {
+        self.items.get(index.index())
+    }
+
+    /// Gets the element with the given index, if it exists.
+    #[inline]
+    pub fn get_pin<U: Archive<Archived = T>>(
+        self: Pin<&mut Self>,
+        index: ArchivedArenaIndex<U>,
+    ) -> Option<Pin<&mut T>> {
+        unsafe {
+            self.map_unchecked_mut(|s| &mut s.items)
+                .get_pin(index.index())
+        }
+    }
+
+    /// Returns the number of elements in the arena.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the arena is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Gets the elements of the arena as a slice, in index order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.items.as_slice()
+    }
+
+    /// Returns an iterator over the elements of the arena, in index order.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedArena<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.items.iter()).finish()
+    }
+}
+
+impl<T, U: Archive<Archived = T>> Index<ArchivedArenaIndex<U>> for ArchivedArena<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: ArchivedArenaIndex<U>) -> &T {
+        &self.items[index.index()]
+    }
+}
+
+impl<T, U: Archive<Archived = T>> Index<ArenaIndex<U>> for ArchivedArena<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: ArenaIndex<U>) -> &T {
+        &self.items[index.index()]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArchivedArena<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "validation")]
+impl<T, C: Fallible + ?Sized> bytecheck::CheckBytes<C> for ArchivedArenaIndex<T>
+where
+    Archived<usize>: bytecheck::CheckBytes<C>,
+{
+    type Error = <Archived<usize> as bytecheck::CheckBytes<C>>::Error;
+
+    #[inline]
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        Archived::<usize>::check_bytes(core::ptr::addr_of!((*value).index), context)?;
+        Ok(&*value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::{
+        ser::{ScratchSpace, Serializer},
+        vec::VecResolver,
+    };
+
+    impl<T> Arena<T> {
+        /// Creates a new, empty arena.
+        #[inline]
+        pub const fn new() -> Self {
+            Self { items: Vec::new() }
+        }
+
+        /// Creates a new, empty arena with space for at least `capacity` elements.
+        #[inline]
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                items: Vec::with_capacity(capacity),
+            }
+        }
+
+        /// Adds an element to the arena and returns its index.
+        #[inline]
+        pub fn push(&mut self, value: T) -> ArenaIndex<T> {
+            let index = ArenaIndex::new(self.items.len());
+            self.items.push(value);
+            index
+        }
+
+        /// Gets the element with the given index, if it exists.
+        #[inline]
+        pub fn get(&self, index: ArenaIndex<T>) -> Option<&T> {
+            self.items.get(index.index())
+        }
+
+        /// Gets the element with the given index mutably, if it exists.
+        #[inline]
+        pub fn get_mut(&mut self, index: ArenaIndex<T>) -> Option<&mut T> {
+            self.items.get_mut(index.index())
+        }
+
+        /// Returns the number of elements in the arena.
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.items.len()
+        }
+
+        /// Returns whether the arena is empty.
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.items.is_empty()
+        }
+
+        /// Gets the elements of the arena as a slice, in index order.
+        #[inline]
+        pub fn as_slice(&self) -> &[T] {
+            self.items.as_slice()
+        }
+
+        /// Returns an iterator over the elements of the arena, in index order.
+        #[inline]
+        pub fn iter(&self) -> core::slice::Iter<'_, T> {
+            self.items.iter()
+        }
+
+        /// Consumes the arena and returns its elements, in index order.
+        #[inline]
+        pub fn into_vec(self) -> Vec<T> {
+            self.items
+        }
+    }
+
+    impl<T> Default for Arena<T> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T: Clone> Clone for Arena<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self {
+                items: self.items.clone(),
+            }
+        }
+    }
+
+    impl<T: fmt::Debug> fmt::Debug for Arena<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_list().entries(self.items.iter()).finish()
+        }
+    }
+
+    impl<T> From<Vec<T>> for Arena<T> {
+        #[inline]
+        fn from(items: Vec<T>) -> Self {
+            Self { items }
+        }
+    }
+
+    impl<T> Index<ArenaIndex<T>> for Arena<T> {
+        type Output = T;
+
+        #[inline]
+        fn index(&self, index: ArenaIndex<T>) -> &T {
+            &self.items[index.index()]
+        }
+    }
+
+    impl<T> IndexMut<ArenaIndex<T>> for Arena<T> {
+        #[inline]
+        fn index_mut(&mut self, index: ArenaIndex<T>) -> &mut T {
+            &mut self.items[index.index()]
+        }
+    }
+
+    impl<'a, T> IntoIterator for &'a Arena<T> {
+        type Item = &'a T;
+        type IntoIter = core::slice::Iter<'a, T>;
+
+        #[inline]
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+        }
+    }
+
+    impl<T: Archive> Archive for Arena<T> {
+        type Archived = ArchivedArena<T::Archived>;
+        type Resolver = VecResolver;
+
+        #[inline]
+        unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+            let (fp, fo) = out_field!(out.items);
+            ArchivedVec::resolve_from_slice(self.items.as_slice(), pos + fp, resolver, fo);
+        }
+    }
+
+    impl<T: Serialize<S>, S: ScratchSpace + Serializer + ?Sized> Serialize<S> for Arena<T> {
+        #[inline]
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedVec::<T::Archived>::serialize_from_slice(self.items.as_slice(), serializer)
+        }
+    }
+
+    impl<T: Archive, D: Fallible + ?Sized> Deserialize<Arena<T>, D> for ArchivedArena<T::Archived>
+    where
+        ArchivedVec<T::Archived>: Deserialize<Vec<T>, D>,
+    {
+        #[inline]
+        fn deserialize(&self, deserializer: &mut D) -> Result<Arena<T>, D::Error> {
+            Ok(Arena {
+                items: self.items.deserialize(deserializer)?,
+            })
+        }
+    }
+};
+
+/// A collection of elements that are referred to by typed indices.
+///
+/// See the [module-level documentation](self) for more information.
+#[cfg(feature = "alloc")]
+pub struct Arena<T> {
+    items: Vec<T>,
+}
@@ -199,7 +199,7 @@ pub struct BoxResolver<T> {
 const _: () = {
     use crate::validation::{
         owned::{CheckOwnedPointerError, OwnedPointerError},
-        ArchiveContext, LayoutRaw,
+        ArchiveContext, LayoutRaw, PathSegment,
     };
     use bytecheck::{CheckBytes, Error};
     use ptr_meta::Pointee;
@@ -227,7 +227,10 @@ const _: () = {
             let range = context
                 .push_prefix_subtree(ptr)
                 .map_err(OwnedPointerError::ContextError)?;
-            T::check_bytes(ptr, context).map_err(OwnedPointerError::ValueCheckBytesError)?;
+            T::check_bytes(ptr, context).map_err(|e| {
+                context.record_check_error(&e, PathSegment::Deref, ptr.cast());
+                OwnedPointerError::ValueCheckBytesError(e)
+            })?;
             context
                 .pop_prefix_range(range)
                 .map_err(OwnedPointerError::ContextError)?;
@@ -0,0 +1,328 @@
+//! Generating C headers for archived types.
+//!
+//! Archived types with a C-compatible layout can be read directly by C code, for example from a
+//! memory-mapped archive written by a Rust program. Deriving `Archive` with `#[archive(c_header)]`
+//! lays out the archived struct as `repr(C)` and implements [`CType`] for it, and a [`CHeader`]
+//! collects the definitions of archived types into a header that C code can include:
+//!
+//! ```
+//! use rkyv::{c_header::CHeader, with::NulTerminated, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(c_header)]
+//! struct Point {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(c_header)]
+//! struct Marker {
+//!     #[with(NulTerminated)]
+//!     label: String,
+//!     position: Point,
+//!     color: [u8; 4],
+//! }
+//!
+//! let header = CHeader::new("MARKER_H").add::<ArchivedMarker>().to_string();
+//! assert!(header.contains(
+//!     "typedef struct ArchivedMarker {\n    \
+//!         rkyv_str label;\n    \
+//!         ArchivedPoint position;\n    \
+//!         uint8_t color[4];\n\
+//!     } ArchivedMarker;"
+//! ));
+//! ```
+//!
+//! Each struct in the header is followed by static assertions that its size and alignment in C
+//! match the archived type, so a C compiler will reject a header that doesn't match the archive.
+//! The header also defines helper macros:
+//!
+//! - `RKYV_ROOT(T, bytes, len)`: returns a pointer to the root object of type `T` in an archive
+//! - `RKYV_STR(s)`: returns a `const char *` to the NUL-terminated bytes of an `rkyv_str`
+//! - `RKYV_VEC(T, v)`: returns a `const T *` to the elements of an `rkyv_vec`
+//!
+//! Only types with a stable, C-compatible layout implement [`CType`]:
+//!
+//! - Integers (except 128-bit integers), floats, `bool`, and `char` (as a `uint32_t`). With the
+//!   `archive_le` and `archive_be` features, multibyte primitives only implement `CType` when the
+//!   archive format matches the endianness of the target.
+//! - Arrays of types that implement `CType`.
+//! - [`ArchivedNulTerminatedString`], archived with the [`NulTerminated`](crate::with::NulTerminated)
+//!   wrapper, as an `rkyv_str`.
+//! - [`ArchivedVec`](crate::vec::ArchivedVec) as an `rkyv_vec`, but only with the `strict`
+//!   feature since its layout is otherwise unspecified.
+//! - Archived structs derived with `#[archive(c_header)]`.
+
+use crate::string::ArchivedNulTerminatedString;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt,
+    mem::{align_of, size_of},
+};
+use hashbrown::HashSet;
+
+/// A type that has an equivalent C type.
+pub trait CType {
+    /// Returns the name of the C type.
+    fn c_name() -> String;
+
+    /// Returns a C declaration of a field with the given name and this type.
+    #[inline]
+    fn c_declaration(name: &str) -> String {
+        format!("{} {}", Self::c_name(), name)
+    }
+
+    /// Adds the definitions needed to use this type to the header.
+    ///
+    /// Types that are built into C don't need any definitions.
+    #[inline]
+    fn define(header: &mut CHeader) {
+        let _ = header;
+    }
+}
+
+/// An archived struct that has an equivalent C struct.
+///
+/// This is implemented by `#[derive(Archive)]` for archived structs with `#[archive(c_header)]`,
+/// and every `CStruct` is also a [`CType`].
+pub trait CStruct {
+    /// The name of the C struct.
+    const C_NAME: &'static str;
+
+    /// Adds the definition of the struct to the header, after the definitions of its fields.
+    fn define_struct(header: &mut CHeader);
+}
+
+impl<T: CStruct> CType for T {
+    #[inline]
+    fn c_name() -> String {
+        T::C_NAME.to_string()
+    }
+
+    #[inline]
+    fn define(header: &mut CHeader) {
+        if header.begin_definition(T::C_NAME) {
+            T::define_struct(header);
+        }
+    }
+}
+
+/// A C header with the definitions of archived types.
+///
+/// The header is written with its `Display` implementation.
+pub struct CHeader {
+    guard: String,
+    defined: HashSet<String>,
+    definitions: Vec<String>,
+}
+
+impl CHeader {
+    /// Returns a new empty header with the given include guard.
+    #[inline]
+    pub fn new(guard: &str) -> Self {
+        Self {
+            guard: guard.to_string(),
+            defined: HashSet::new(),
+            definitions: Vec::new(),
+        }
+    }
+
+    /// Adds the definitions of the given type and the types it depends on to the header.
+    #[inline]
+    pub fn add<T: CType + ?Sized>(&mut self) -> &mut Self {
+        T::define(self);
+        self
+    }
+
+    /// Starts defining the type with the given name.
+    ///
+    /// Returns `false` if the type has already been defined or is being defined, in which case it
+    /// shouldn't be defined again.
+    #[inline]
+    pub fn begin_definition(&mut self, name: &str) -> bool {
+        if self.defined.contains(name) {
+            false
+        } else {
+            self.defined.insert(name.to_string());
+            true
+        }
+    }
+
+    /// Adds a definition to the header.
+    ///
+    /// Definitions are written in the order they were added, so the definitions that a type
+    /// depends on should be added first.
+    #[inline]
+    pub fn push_definition(&mut self, definition: String) {
+        self.definitions.push(definition);
+    }
+
+    /// Adds the definition of a struct with the given field declarations to the header, followed by
+    /// assertions that it has the given size and alignment.
+    #[inline]
+    pub fn push_struct(&mut self, name: &str, fields: &[String], size: usize, align: usize) {
+        self.push_definition(struct_definition(name, fields, size, align));
+    }
+}
+
+fn struct_definition(name: &str, fields: &[String], size: usize, align: usize) -> String {
+    let mut definition = format!("typedef struct {} {{\n", name);
+    for field in fields {
+        definition.push_str("    ");
+        definition.push_str(field);
+        definition.push_str(";\n");
+    }
+    definition.push_str(&format!(
+        "}} {name};\n\
+        _Static_assert(sizeof({name}) == {size}, \"unexpected size of {name}\");\n\
+        _Static_assert(_Alignof({name}) == {align}, \"unexpected alignment of {name}\");\n",
+        name = name,
+        size = size,
+        align = align,
+    ));
+    definition
+}
+
+impl fmt::Display for CHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "/* Generated by rkyv. Do not edit. */")?;
+        writeln!(f, "#ifndef {}", self.guard)?;
+        writeln!(f, "#define {}", self.guard)?;
+        writeln!(f)?;
+        writeln!(f, "#include <stdbool.h>")?;
+        writeln!(f, "#include <stdint.h>")?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "/* Returns a pointer to the root object of type T in an archive of len bytes. */"
+        )?;
+        writeln!(
+            f,
+            "#define RKYV_ROOT(T, bytes, len) \
+                ((const T *)((const char *)(bytes) + (len) - sizeof(T)))"
+        )?;
+        for definition in self.definitions.iter() {
+            writeln!(f)?;
+            write!(f, "{}", definition)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "#endif")
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $name:literal,)*) => {
+        $(
+            impl CType for $ty {
+                #[inline]
+                fn c_name() -> String {
+                    $name.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive! {
+    i8 => "int8_t",
+    i16 => "int16_t",
+    i32 => "int32_t",
+    i64 => "int64_t",
+    u8 => "uint8_t",
+    u16 => "uint16_t",
+    u32 => "uint32_t",
+    u64 => "uint64_t",
+    f32 => "float",
+    f64 => "double",
+    bool => "bool",
+    char => "uint32_t",
+}
+
+#[cfg(all(feature = "archive_le", target_endian = "little"))]
+impl<T: CType + rend::Primitive> CType for rend::LittleEndian<T> {
+    #[inline]
+    fn c_name() -> String {
+        T::c_name()
+    }
+}
+
+#[cfg(all(feature = "archive_be", target_endian = "big"))]
+impl<T: CType + rend::Primitive> CType for rend::BigEndian<T> {
+    #[inline]
+    fn c_name() -> String {
+        T::c_name()
+    }
+}
+
+impl<T: CType, const N: usize> CType for [T; N] {
+    #[inline]
+    fn c_name() -> String {
+        format!("{}[{}]", T::c_name(), N)
+    }
+
+    #[inline]
+    fn c_declaration(name: &str) -> String {
+        T::c_declaration(&format!("{}[{}]", name, N))
+    }
+
+    #[inline]
+    fn define(header: &mut CHeader) {
+        T::define(header);
+    }
+}
+
+// Relative pointers and lengths are always archived as native integers of the fixed sizes
+fn rel_ptr_definition<T>(name: &str, doc: &str, helper: &str) -> String {
+    let fields = [
+        format!("int{}_t offset", crate::FixedIsize::BITS),
+        format!("uint{}_t len", crate::FixedUsize::BITS),
+    ];
+    format!(
+        "/* {} */\n{}{}\n",
+        doc,
+        struct_definition(name, &fields, size_of::<T>(), align_of::<T>()),
+        helper,
+    )
+}
+
+impl CType for ArchivedNulTerminatedString {
+    #[inline]
+    fn c_name() -> String {
+        "rkyv_str".to_string()
+    }
+
+    fn define(header: &mut CHeader) {
+        if header.begin_definition("rkyv_str") {
+            header.push_definition(rel_ptr_definition::<Self>(
+                "rkyv_str",
+                "A relative pointer to a NUL-terminated string and its length without the NUL.",
+                "#define RKYV_STR(s) ((const char *)&(s) + (s).offset)",
+            ));
+        }
+    }
+}
+
+#[cfg(feature = "strict")]
+impl<T: CType> CType for crate::vec::ArchivedVec<T> {
+    #[inline]
+    fn c_name() -> String {
+        "rkyv_vec".to_string()
+    }
+
+    fn define(header: &mut CHeader) {
+        if header.begin_definition("rkyv_vec") {
+            header.push_definition(rel_ptr_definition::<Self>(
+                "rkyv_vec",
+                "A relative pointer to an array and its length.",
+                "#define RKYV_VEC(T, v) ((const T *)((const char *)&(v) + (v).offset))",
+            ));
+        }
+        T::define(header);
+    }
+}
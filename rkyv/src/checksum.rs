@@ -0,0 +1,180 @@
+//! Checksums for detecting corrupted archives.
+//!
+//! A [`ChecksumSerializer`](crate::ser::serializers::ChecksumSerializer) computes a CRC-32 of the
+//! bytes it writes and appends it to the end of the archive when it is finished. Before the archive
+//! is accessed or validated, [`verify_checksum`] recomputes the checksum and strips it off. This
+//! catches torn writes and bit rot in archives that are stored in files or memory-mapped, which
+//! validation alone may not detect.
+//!
+//! The checksum is the CRC-32 (IEEE) of the archive bytes, stored as a little-endian `u32`.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     checksum::{verify_checksum, ChecksumError},
+//!     ser::{
+//!         serializers::{AllocSerializer, ChecksumSerializer},
+//!         Serializer,
+//!     },
+//!     archived_root,
+//! };
+//!
+//! let mut serializer = ChecksumSerializer::new(AllocSerializer::<256>::default());
+//! serializer.serialize_value(&vec![1u32, 2, 3]).unwrap();
+//! let mut bytes = serializer.finish().unwrap().into_serializer().into_inner();
+//!
+//! let archive = verify_checksum(&bytes).unwrap();
+//! let archived = unsafe { archived_root::<Vec<u32>>(archive) };
+//! assert_eq!(archived.as_slice(), &[1, 2, 3]);
+//!
+//! bytes[0] ^= 1;
+//! assert!(matches!(
+//!     verify_checksum(&bytes),
+//!     Err(ChecksumError::Mismatch { .. })
+//! ));
+//! ```
+
+use crate::{archived_root, Archive};
+use core::fmt;
+
+/// The length of the checksum appended to the end of an archive.
+pub const CHECKSUM_LEN: usize = 4;
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// An incremental CRC-32 hasher.
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    /// Creates a new hasher.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { state: !0 }
+    }
+
+    /// Adds the given bytes to the checksum.
+    #[inline]
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state =
+                CRC32_TABLE[((self.state ^ byte as u32) & 0xff) as usize] ^ (self.state >> 8);
+        }
+    }
+
+    /// Returns the checksum of the bytes added so far.
+    #[inline]
+    pub const fn finish(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the CRC-32 checksum of the given bytes.
+#[inline]
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut hasher = Crc32::new();
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+/// An error resulting from an archive with an invalid checksum.
+#[derive(Debug)]
+pub enum ChecksumError {
+    /// The buffer was too short to contain a checksum.
+    Truncated {
+        /// The length of the buffer
+        len: usize,
+    },
+    /// The checksum stored in the buffer did not match the checksum of the archive.
+    Mismatch {
+        /// The checksum stored in the buffer
+        expected: u32,
+        /// The checksum of the archive bytes
+        actual: u32,
+    },
+}
+
+impl fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChecksumError::Truncated { len } => write!(
+                f,
+                "buffer of length {} is too short to contain a checksum",
+                len
+            ),
+            ChecksumError::Mismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected {:#010x}, found {:#010x}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChecksumError {}
+
+/// Verifies the checksum at the end of the given bytes and returns the archive without it.
+///
+/// The returned bytes can be accessed or validated like any other archive.
+#[inline]
+pub fn verify_checksum(bytes: &[u8]) -> Result<&[u8], ChecksumError> {
+    if bytes.len() < CHECKSUM_LEN {
+        return Err(ChecksumError::Truncated { len: bytes.len() });
+    }
+
+    let (archive, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+    let expected = u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+    let actual = crc32(archive);
+    if expected == actual {
+        Ok(archive)
+    } else {
+        Err(ChecksumError::Mismatch { expected, actual })
+    }
+}
+
+/// Verifies the checksum at the end of the given bytes and casts the root of the archive.
+///
+/// This is a wrapper for [`archived_root`] that verifies and strips the checksum first.
+///
+/// # Safety
+///
+/// The archive without its checksum must satisfy the safety requirements of [`archived_root`].
+#[inline]
+pub unsafe fn archived_root_checksummed<T: Archive + ?Sized>(
+    bytes: &[u8],
+) -> Result<&T::Archived, ChecksumError> {
+    verify_checksum(bytes).map(|archive| archived_root::<T>(archive))
+}
@@ -0,0 +1,63 @@
+//! An archived version of `Ordering`.
+
+use core::cmp::Ordering;
+
+/// An archived [`Ordering`].
+///
+/// Archived orderings are stored as a single byte and checked for a valid variant during
+/// validation.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedOrdering {
+    /// An ordering where a compared value is less than another
+    Less,
+    /// An ordering where a compared value is equal to another
+    Equal,
+    /// An ordering where a compared value is greater than another
+    Greater,
+}
+
+impl ArchivedOrdering {
+    /// Returns the [`Ordering`] value of this archived ordering.
+    #[inline]
+    pub const fn as_ordering(&self) -> Ordering {
+        match self {
+            ArchivedOrdering::Less => Ordering::Less,
+            ArchivedOrdering::Equal => Ordering::Equal,
+            ArchivedOrdering::Greater => Ordering::Greater,
+        }
+    }
+}
+
+impl From<Ordering> for ArchivedOrdering {
+    #[inline]
+    fn from(value: Ordering) -> Self {
+        match value {
+            Ordering::Less => ArchivedOrdering::Less,
+            Ordering::Equal => ArchivedOrdering::Equal,
+            Ordering::Greater => ArchivedOrdering::Greater,
+        }
+    }
+}
+
+impl From<ArchivedOrdering> for Ordering {
+    #[inline]
+    fn from(value: ArchivedOrdering) -> Self {
+        value.as_ordering()
+    }
+}
+
+impl PartialEq<Ordering> for ArchivedOrdering {
+    #[inline]
+    fn eq(&self, other: &Ordering) -> bool {
+        self.as_ordering() == *other
+    }
+}
+
+impl PartialEq<ArchivedOrdering> for Ordering {
+    #[inline]
+    fn eq(&self, other: &ArchivedOrdering) -> bool {
+        other.eq(self)
+    }
+}
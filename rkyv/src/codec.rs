@@ -0,0 +1,341 @@
+//! A [`tokio_util`] codec for sending archives as framed messages.
+//!
+//! [`ArchiveCodec`] encodes each message as a little-endian `u32` length followed by the bytes of
+//! its archive. Decoded messages are copied out of the read buffer into a [`SubArchive`] so that
+//! they are properly aligned, and their roots can then be accessed without deserializing them.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::codec::ArchiveCodec;
+//! use tokio_util::codec::{Decoder, Encoder};
+//! # use bytes::BytesMut;
+//!
+//! let mut codec = ArchiveCodec::<Vec<u32>, 256>::new();
+//! let mut buf = BytesMut::new();
+//! codec.encode(&vec![1, 2, 3], &mut buf).unwrap();
+//! codec.encode(&vec![4, 5], &mut buf).unwrap();
+//!
+//! let first = codec.decode(&mut buf).unwrap().unwrap();
+//! assert_eq!(first.root().as_slice(), &[1, 2, 3]);
+//! let second = codec.decode(&mut buf).unwrap().unwrap();
+//! assert_eq!(second.root().as_slice(), &[4, 5]);
+//! assert!(codec.decode(&mut buf).unwrap().is_none());
+//! ```
+
+use crate::{
+    check_archived_root, ser::serializers::AllocSerializer, sub_archive::SubArchive, to_bytes,
+    validation::validators::DefaultValidator, AlignedVec, Archive, Fallible, Serialize,
+};
+use bytecheck::CheckBytes;
+use bytes::{Buf, BufMut, BytesMut};
+use core::{fmt, marker::PhantomData};
+use std::{error::Error, io};
+use tokio_util::codec::{Decoder, Encoder};
+
+const LEN_SIZE: usize = 4;
+const DEFAULT_MAX_FRAME_LEN: usize = 8 * 1024 * 1024;
+
+/// An error that can occur while encoding or decoding archived messages.
+#[derive(Debug)]
+pub enum ArchiveCodecError<E> {
+    /// An I/O error occurred.
+    Io(io::Error),
+    /// The message could not be serialized.
+    Serialize(E),
+    /// A frame was longer than the maximum frame length.
+    FrameTooLong {
+        /// The length of the frame
+        len: usize,
+        /// The maximum frame length of the codec
+        max_len: usize,
+    },
+    /// A decoded message failed validation.
+    ///
+    /// The validation error refers to the frame that failed, so it is formatted as a message.
+    Invalid(String),
+}
+
+impl<E: fmt::Display> fmt::Display for ArchiveCodecError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveCodecError::Io(e) => write!(f, "I/O error: {}", e),
+            ArchiveCodecError::Serialize(e) => write!(f, "serialize error: {}", e),
+            ArchiveCodecError::FrameTooLong { len, max_len } => write!(
+                f,
+                "frame of length {} exceeds the maximum frame length of {}",
+                len, max_len
+            ),
+            ArchiveCodecError::Invalid(e) => write!(f, "invalid message: {}", e),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ArchiveCodecError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ArchiveCodecError::Io(e) => Some(e as &dyn Error),
+            ArchiveCodecError::Serialize(e) => Some(e as &dyn Error),
+            ArchiveCodecError::FrameTooLong { .. } | ArchiveCodecError::Invalid(_) => None,
+        }
+    }
+}
+
+impl<E> From<io::Error> for ArchiveCodecError<E> {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        ArchiveCodecError::Io(e)
+    }
+}
+
+/// The error type of an [`ArchiveCodec`] with `N` bytes of scratch space.
+pub type ArchiveCodecErrorFor<const N: usize> =
+    ArchiveCodecError<<AllocSerializer<N> as Fallible>::Error>;
+
+type CheckFn = fn(&[u8]) -> Result<(), String>;
+
+/// A codec that encodes values as length-prefixed archives and decodes them as [`SubArchive`]s.
+///
+/// The const generic parameter `N` specifies the number of bytes to pre-allocate as scratch space
+/// when encoding messages.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct ArchiveCodec<T, const N: usize> {
+    max_frame_len: usize,
+    check: Option<CheckFn>,
+    _phantom: PhantomData<fn(&T) -> SubArchive<T>>,
+}
+
+impl<T, const N: usize> ArchiveCodec<T, N> {
+    /// Creates a new codec that does not validate decoded messages.
+    ///
+    /// # Safety
+    ///
+    /// Every frame decoded by the codec must contain a valid archive with an archived `T` at its
+    /// root. This is only the case if the data being decoded is trusted.
+    #[inline]
+    pub unsafe fn new_unchecked() -> Self {
+        Self {
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            check: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the maximum length of an encoded or decoded frame.
+    ///
+    /// The default maximum frame length is 8 MiB.
+    #[inline]
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Sets the maximum length of an encoded or decoded frame.
+    ///
+    /// Frames longer than this are rejected with [`ArchiveCodecError::FrameTooLong`].
+    #[inline]
+    pub fn set_max_frame_len(&mut self, max_frame_len: usize) {
+        self.max_frame_len = max_frame_len;
+    }
+
+    #[inline]
+    fn check_len<E>(&self, len: usize) -> Result<(), ArchiveCodecError<E>> {
+        if len > self.max_frame_len || len > u32::MAX as usize {
+            Err(ArchiveCodecError::FrameTooLong {
+                len,
+                max_len: self.max_frame_len,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T, const N: usize> ArchiveCodec<T, N>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>>,
+{
+    /// Creates a new codec that validates decoded messages.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            check: Some(|bytes| {
+                check_archived_root::<T>(bytes)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize> Default for ArchiveCodec<T, N>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Clone for ArchiveCodec<T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            max_frame_len: self.max_frame_len,
+            check: self.check,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for ArchiveCodec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveCodec")
+            .field("max_frame_len", &self.max_frame_len)
+            .field("validate", &self.check.is_some())
+            .finish()
+    }
+}
+
+impl<T, const N: usize> Encoder<&T> for ArchiveCodec<T, N>
+where
+    T: Serialize<AllocSerializer<N>>,
+{
+    type Error = ArchiveCodecErrorFor<N>;
+
+    fn encode(&mut self, item: &T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let bytes = to_bytes::<T, N>(item).map_err(ArchiveCodecError::Serialize)?;
+        self.check_len(bytes.len())?;
+        dst.reserve(LEN_SIZE + bytes.len());
+        dst.put_u32_le(bytes.len() as u32);
+        dst.extend_from_slice(&bytes);
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Decoder for ArchiveCodec<T, N> {
+    type Item = SubArchive<T>;
+    type Error = ArchiveCodecErrorFor<N>;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < LEN_SIZE {
+            return Ok(None);
+        }
+
+        let len = u32::from_le_bytes([src[0], src[1], src[2], src[3]]) as usize;
+        self.check_len(len)?;
+        if src.len() < LEN_SIZE + len {
+            src.reserve(LEN_SIZE + len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(LEN_SIZE);
+        let mut bytes = AlignedVec::with_capacity(len);
+        bytes.extend_from_slice(&src[..len]);
+        src.advance(len);
+
+        if let Some(check) = self.check {
+            check(&bytes).map_err(ArchiveCodecError::Invalid)?;
+        }
+        // SAFETY: The bytes were either validated, or the codec was created with `new_unchecked`
+        // and the caller guaranteed that every frame contains a valid archive.
+        Ok(Some(unsafe { SubArchive::from_bytes_unchecked(bytes) }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArchiveCodec, ArchiveCodecError};
+    use crate::{Archive, Serialize};
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[derive(Archive, Serialize)]
+    #[archive(crate = "crate")]
+    #[archive_attr(derive(bytecheck::CheckBytes))]
+    struct Message {
+        id: u32,
+        body: String,
+    }
+
+    #[test]
+    fn partial_frames() {
+        let mut codec = unsafe { ArchiveCodec::<Message, 256>::new_unchecked() };
+        let mut encoded = BytesMut::new();
+        codec
+            .encode(
+                &Message {
+                    id: 7,
+                    body: "a message long enough to be stored out of line".to_string(),
+                },
+                &mut encoded,
+            )
+            .unwrap();
+
+        let mut src = BytesMut::new();
+        for byte in encoded[..encoded.len() - 1].iter() {
+            src.extend_from_slice(&[*byte]);
+            assert!(codec.decode(&mut src).unwrap().is_none());
+        }
+        src.extend_from_slice(&encoded[encoded.len() - 1..]);
+        let message = codec.decode(&mut src).unwrap().unwrap();
+        assert_eq!(message.root().id, 7);
+        assert_eq!(
+            message.root().body,
+            "a message long enough to be stored out of line"
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn max_frame_len() {
+        let mut codec = unsafe { ArchiveCodec::<Message, 256>::new_unchecked() };
+        codec.set_max_frame_len(8);
+        let message = Message {
+            id: 1,
+            body: "too long for the frame".to_string(),
+        };
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            codec.encode(&message, &mut buf),
+            Err(ArchiveCodecError::FrameTooLong { max_len: 8, .. })
+        ));
+
+        buf.extend_from_slice(&100u32.to_le_bytes());
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(ArchiveCodecError::FrameTooLong { len: 100, .. })
+        ));
+    }
+
+    #[test]
+    fn validate_messages() {
+        let mut codec = ArchiveCodec::<Message, 256>::new();
+        let mut buf = BytesMut::new();
+        codec
+            .encode(
+                &Message {
+                    id: 3,
+                    body: "hello".to_string(),
+                },
+                &mut buf,
+            )
+            .unwrap();
+        let message = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(message.root().body, "hello");
+
+        // A frame whose root points outside of the frame
+        buf.extend_from_slice(&8u32.to_le_bytes());
+        buf.extend_from_slice(&[0xff; 8]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(ArchiveCodecError::Invalid(_))
+        ));
+        assert!(buf.is_empty());
+    }
+}
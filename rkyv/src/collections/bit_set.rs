@@ -0,0 +1,258 @@
+//! Archived bit set implementation.
+//!
+//! Bit sets store their bits packed into 32-bit blocks along with the number of bits, so individual
+//! bits can be read directly from the archive.
+
+use crate::{
+    vec::{ArchivedVec, VecResolver},
+    Archived,
+};
+use core::{fmt, iter::FusedIterator};
+
+const BITS_PER_BLOCK: usize = 32;
+
+#[inline]
+fn block_count(len: usize) -> usize {
+    len.div_ceil(BITS_PER_BLOCK)
+}
+
+/// An archived, fixed-length set of bits.
+///
+/// This is the archived counterpart of [`AsBitSet`](crate::with::AsBitSet).
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedBitSet {
+    blocks: ArchivedVec<Archived<u32>>,
+    len: Archived<usize>,
+}
+
+impl ArchivedBitSet {
+    /// Returns the number of bits in the bit set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns whether the bit set contains no bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bit at the given index, or `None` if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len() {
+            return None;
+        }
+        let block = self.blocks.get(index / BITS_PER_BLOCK)?;
+        Some(from_archived!(*block) & (1 << (index % BITS_PER_BLOCK)) != 0)
+    }
+
+    /// Returns the number of bits that are set.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.iter().filter(|bit| *bit).count()
+    }
+
+    /// Returns the raw blocks that the bits are stored in.
+    ///
+    /// Bit `i` is stored in block `i / 32` at bit position `i % 32`. The bits in the last block past
+    /// the length of the bit set are always zero.
+    #[inline]
+    pub fn as_raw_blocks(&self) -> &[Archived<u32>] {
+        self.blocks.as_slice()
+    }
+
+    /// Gets an iterator over the bits of the bit set.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            set: self,
+            index: 0,
+        }
+    }
+
+    /// Resolves an archived bit set from the number of bits it contains.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of bits that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a bit set
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: BitSetResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.blocks);
+        ArchivedVec::resolve_from_len(block_count(len), pos + fp, resolver.0, fo);
+        let (_, fo) = out_field!(out.len);
+        fo.write(to_archived!(len as crate::FixedUsize));
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::ser::{ScratchSpace, Serializer};
+
+    impl ArchivedBitSet {
+        /// Serializes a bit set from a slice of bits.
+        #[inline]
+        pub fn serialize_from_bools<S>(
+            bits: &[bool],
+            serializer: &mut S,
+        ) -> Result<BitSetResolver, S::Error>
+        where
+            S: ScratchSpace + Serializer + ?Sized,
+        {
+            let blocks = bits.chunks(BITS_PER_BLOCK).map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u32, |block, (i, bit)| block | (u32::from(*bit) << i))
+            });
+            Ok(BitSetResolver(ArchivedVec::serialize_from_iter::<
+                u32,
+                _,
+                _,
+                _,
+            >(blocks, serializer)?))
+        }
+    }
+};
+
+impl fmt::Debug for ArchivedBitSet {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Eq for ArchivedBitSet {}
+
+impl PartialEq for ArchivedBitSet {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl PartialEq<[bool]> for ArchivedBitSet {
+    #[inline]
+    fn eq(&self, other: &[bool]) -> bool {
+        self.iter().eq(other.iter().copied())
+    }
+}
+
+impl<'a> IntoIterator for &'a ArchivedBitSet {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the bits of an archived bit set.
+pub struct Iter<'a> {
+    set: &'a ArchivedBitSet,
+    index: usize,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let bit = self.set.get(self.index)?;
+        self.index += 1;
+        Some(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.set.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// The resolver for an [`ArchivedBitSet`].
+pub struct BitSetResolver(VecResolver);
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::ArchiveContext;
+    use bytecheck::{CheckBytes, Error, ErrorBox, StructCheckError};
+    use core::ptr::addr_of;
+
+    /// An error resulting from an archived bit set with the wrong number of blocks for its length.
+    #[derive(Debug)]
+    pub struct BitSetLengthError {
+        /// The number of bits in the bit set
+        pub len: usize,
+        /// The number of blocks in the bit set
+        pub blocks: usize,
+    }
+
+    impl fmt::Display for BitSetLengthError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "bit set of length {} has {} blocks, expected {}",
+                self.len,
+                self.blocks,
+                block_count(self.len),
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for BitSetLengthError {}
+
+    impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedBitSet
+    where
+        C::Error: Error,
+    {
+        type Error = StructCheckError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            let blocks =
+                ArchivedVec::<Archived<u32>>::check_bytes(addr_of!((*value).blocks), context)
+                    .map_err(|e| StructCheckError {
+                        field_name: "blocks",
+                        inner: ErrorBox::new(e),
+                    })?;
+            Archived::<usize>::check_bytes(addr_of!((*value).len), context).map_err(|e| {
+                StructCheckError {
+                    field_name: "len",
+                    inner: ErrorBox::new(e),
+                }
+            })?;
+
+            let value = &*value;
+            let len = value.len();
+            if blocks.len() != block_count(len) {
+                return Err(StructCheckError {
+                    field_name: "len",
+                    inner: ErrorBox::new(BitSetLengthError {
+                        len,
+                        blocks: blocks.len(),
+                    }),
+                });
+            }
+
+            Ok(value)
+        }
+    }
+};
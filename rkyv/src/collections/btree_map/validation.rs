@@ -11,7 +11,7 @@ use crate::{
 };
 use bytecheck::{CheckBytes, Error};
 use core::{
-    alloc::Layout,
+    alloc::{Layout, LayoutError},
     convert::{Infallible, TryFrom},
     fmt,
     hint::unreachable_unchecked,
@@ -248,19 +248,18 @@ const _: () = {
 };
 
 impl<T> LayoutRaw for Node<[T]> {
-    fn layout_raw(value: *const Self) -> Layout {
+    fn layout_raw(value: *const Self) -> Result<Layout, LayoutError> {
         let len = ptr_meta::metadata(value);
         let result = Layout::new::<NodeHeader>()
-            .extend(Layout::array::<T>(len).unwrap())
-            .unwrap()
+            .extend(Layout::array::<T>(len)?)?
             .0;
         #[cfg(not(feature = "strict"))]
         {
-            result
+            Ok(result)
         }
         #[cfg(feature = "strict")]
         {
-            result.pad_to_align()
+            Ok(result.pad_to_align())
         }
     }
 }
@@ -290,7 +289,8 @@ impl NodeHeader {
             Node::layout_raw(raw_node.classify_inner_ptr::<K>())
         } else {
             Node::layout_raw(raw_node.classify_leaf_ptr::<K, V>())
-        };
+        }
+        .map_err(|e| ArchivedBTreeMapError::ContextError(context.wrap_layout_error(e)))?;
 
         context.bounds_check_subtree_ptr_layout(
             (raw_node as *const NodeHeader).cast(),
@@ -483,6 +483,9 @@ const _: () = {
                 ptr::addr_of!((*value).len),
                 context,
             )?) as usize;
+            context
+                .check_collection_len(len)
+                .map_err(ArchivedBTreeMapError::ContextError)?;
 
             if len > 0 {
                 let root_rel_ptr =
@@ -490,8 +493,10 @@ const _: () = {
 
                 // Walk all the inner nodes, claim their memory, and check their contents
                 let mut nodes = VecDeque::new();
+                // Only the header is checked here, the whole node is checked against the subtree
+                // range once its size is known
                 let root_ptr = context
-                    .check_subtree_rel_ptr(root_rel_ptr)
+                    .check_rel_ptr(root_rel_ptr)
                     .map_err(ArchivedBTreeMapError::ContextError)?;
 
                 // Before checking all the nodes, we have to push an additional prefix subtree with
@@ -507,7 +512,8 @@ const _: () = {
                     Node::layout_raw(root.classify_inner_ptr::<K>())
                 } else {
                     Node::layout_raw(root.classify_leaf_ptr::<K, V>())
-                };
+                }
+                .map_err(|e| ArchivedBTreeMapError::ContextError(context.wrap_layout_error(e)))?;
 
                 // Because the layout of the subtree is dynamic, we need to bounds check the layout
                 // declared by the root node.
@@ -538,7 +544,7 @@ const _: () = {
                     let inner = node.classify_inner::<K>();
 
                     let child_ptr = context
-                        .check_subtree_rel_ptr(&inner.header.ptr)
+                        .check_rel_ptr(&inner.header.ptr)
                         .map_err(ArchivedBTreeMapError::ContextError)?;
                     let child = NodeHeader::manual_check_bytes::<K, V, C>(child_ptr, context)?;
                     nodes.push_back((child, depth + 1));
@@ -547,7 +553,7 @@ const _: () = {
                     // be checked when we iterate through the leaf nodes in order and check ordering
                     for entry in inner.tail.iter() {
                         let child_ptr = context
-                            .check_subtree_rel_ptr(&entry.ptr)
+                            .check_rel_ptr(&entry.ptr)
                             .map_err(ArchivedBTreeMapError::ContextError)?;
                         let child = NodeHeader::manual_check_bytes::<K, V, C>(child_ptr, context)?;
                         nodes.push_back((child, depth + 1));
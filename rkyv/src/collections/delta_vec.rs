@@ -0,0 +1,487 @@
+//! Archived delta-encoded integer vector implementation.
+//!
+//! Delta-encoded vectors split their elements into blocks of 64 and store each element as its
+//! difference from the smallest element in its block, packed into just enough bits to hold the
+//! largest difference. Sequences that change slowly, like timestamps and offsets, have small
+//! differences within each block and so can take a fraction of the space of a plain `Vec`.
+//! Every block takes a whole number of words, so any element can still be read in constant time.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, with::AsDelta, Archive, Deserialize, Infallible, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Index {
+//!     #[with(AsDelta)]
+//!     timestamps: Vec<u64>,
+//! }
+//!
+//! let value = Index {
+//!     timestamps: (0..1000).map(|i| 1_600_000_000_000 + i * 15).collect(),
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//! let archived = unsafe { archived_root::<Index>(&bytes) };
+//!
+//! assert_eq!(archived.timestamps.len(), 1000);
+//! assert_eq!(archived.timestamps.get(500), Some(1_600_000_007_500));
+//! assert_eq!(archived.timestamps.binary_search(1_600_000_000_150), Ok(10));
+//!
+//! // Each element only takes up 10 bits instead of 64
+//! assert!(bytes.len() < 1000 * 2);
+//!
+//! let deserialized: Index = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized.timestamps, value.timestamps);
+//! ```
+
+use crate::{
+    vec::{ArchivedVec, VecResolver},
+    Archived,
+};
+use core::{cmp::Ordering, fmt, iter::FusedIterator, marker::PhantomData};
+
+const BLOCK_LEN: usize = 64;
+
+#[inline]
+fn block_count(len: usize) -> usize {
+    len.div_ceil(BLOCK_LEN)
+}
+
+/// An integer type that can be delta-encoded.
+///
+/// Integers are encoded as `u64` keys that sort in the same order as the integers.
+pub trait DeltaInt: Copy + sealed::Sealed {
+    /// Converts the integer to its key.
+    fn to_key(self) -> u64;
+    /// Converts a key back to its integer.
+    fn from_key(key: u64) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_delta_int {
+    (unsigned $($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl DeltaInt for $ty {
+                #[inline]
+                fn to_key(self) -> u64 {
+                    self as u64
+                }
+
+                #[inline]
+                fn from_key(key: u64) -> Self {
+                    key as $ty
+                }
+            }
+        )*
+    };
+    (signed $($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl DeltaInt for $ty {
+                // Flipping the sign bit maps signed integers to unsigned keys in the same order
+                #[inline]
+                fn to_key(self) -> u64 {
+                    (self as i64 as u64) ^ (1 << 63)
+                }
+
+                #[inline]
+                fn from_key(key: u64) -> Self {
+                    (key ^ (1 << 63)) as i64 as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_int!(unsigned u8, u16, u32, u64, usize);
+impl_delta_int!(signed i8, i16, i32, i64, isize);
+
+/// An archived vector of integers that are delta-encoded in blocks.
+///
+/// This is the archived counterpart of [`AsDelta`](crate::with::AsDelta).
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedDeltaVec<T> {
+    // The smallest key in each block
+    bases: ArchivedVec<Archived<u64>>,
+    // The word that each block starts at, plus the total number of words. The number of bits per
+    // element in a block is the same as the number of words in the block.
+    starts: ArchivedVec<Archived<u32>>,
+    words: ArchivedVec<Archived<u64>>,
+    len: Archived<usize>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: DeltaInt> ArchivedDeltaVec<T> {
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns whether the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at the given index, or `None` if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        let block = index / BLOCK_LEN;
+        let base = from_archived!(self.bases[block]);
+        let start = from_archived!(self.starts[block]) as usize;
+        let width = from_archived!(self.starts[block + 1]) as usize - start;
+        if width == 0 {
+            return Some(T::from_key(base));
+        }
+
+        let bit = (index % BLOCK_LEN) * width;
+        let word = start + bit / 64;
+        let shift = bit % 64;
+        let mut delta = from_archived!(self.words[word]) >> shift;
+        if shift + width > 64 {
+            delta |= from_archived!(self.words[word + 1]) << (64 - shift);
+        }
+        if width < 64 {
+            delta &= (1 << width) - 1;
+        }
+        Some(T::from_key(base.wrapping_add(delta)))
+    }
+
+    /// Returns the number of bits that each element in the block at the given index is stored in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block index is out of bounds. There is one block for every 64 elements.
+    #[inline]
+    pub fn block_width(&self, block: usize) -> u32 {
+        from_archived!(self.starts[block + 1]) - from_archived!(self.starts[block])
+    }
+
+    /// Binary searches this sorted vector for the given element.
+    ///
+    /// This behaves like [`slice::binary_search`]: if the element is found, then the index of a
+    /// matching element is returned. Otherwise, the index where it could be inserted while
+    /// maintaining the sorted order is returned.
+    pub fn binary_search(&self, value: T) -> Result<usize, usize> {
+        let key = value.to_key();
+        let mut low = 0;
+        let mut high = self.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.get(mid).unwrap().to_key().cmp(&key) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(low)
+    }
+
+    /// Gets an iterator over the elements of the vector.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vec: self,
+            index: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Resolves an archived delta-encoded vector from the number of elements it contains.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a delta-encoded vector
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: DeltaVecResolver,
+        out: *mut Self,
+    ) {
+        let blocks = block_count(len);
+        let (fp, fo) = out_field!(out.bases);
+        ArchivedVec::resolve_from_len(blocks, pos + fp, resolver.bases, fo);
+        let (fp, fo) = out_field!(out.starts);
+        ArchivedVec::resolve_from_len(blocks + 1, pos + fp, resolver.starts, fo);
+        let (fp, fo) = out_field!(out.words);
+        ArchivedVec::resolve_from_len(resolver.words_len, pos + fp, resolver.words, fo);
+        let (_, fo) = out_field!(out.len);
+        fo.write(to_archived!(len as crate::FixedUsize));
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::ser::{ScratchSpace, Serializer};
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+    use core::convert::TryFrom;
+
+    impl<T: DeltaInt> ArchivedDeltaVec<T> {
+        /// Serializes a delta-encoded vector from a slice of integers.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the encoded elements take up more than `u32::MAX` words.
+        pub fn serialize_from_slice<S>(
+            values: &[T],
+            serializer: &mut S,
+        ) -> Result<DeltaVecResolver, S::Error>
+        where
+            S: ScratchSpace + Serializer + ?Sized,
+        {
+            let mut bases = Vec::with_capacity(block_count(values.len()));
+            let mut starts = vec![0u32];
+            let mut words = Vec::new();
+            for block in values.chunks(BLOCK_LEN) {
+                let base = block.iter().map(|v| v.to_key()).min().unwrap();
+                let max = block.iter().map(|v| v.to_key()).max().unwrap();
+                let width = (64 - (max - base).leading_zeros()) as usize;
+
+                let start = words.len();
+                words.resize(start + width, 0u64);
+                if width != 0 {
+                    for (i, value) in block.iter().enumerate() {
+                        let delta = value.to_key() - base;
+                        let bit = i * width;
+                        let word = start + bit / 64;
+                        let shift = bit % 64;
+                        words[word] |= delta << shift;
+                        if shift + width > 64 {
+                            words[word + 1] |= delta >> (64 - shift);
+                        }
+                    }
+                }
+
+                bases.push(base);
+                starts.push(
+                    u32::try_from(words.len()).expect("too many words to delta-encode vector"),
+                );
+            }
+
+            Ok(DeltaVecResolver {
+                words_len: words.len(),
+                bases: ArchivedVec::serialize_from_slice(bases.as_slice(), serializer)?,
+                starts: ArchivedVec::serialize_from_slice(starts.as_slice(), serializer)?,
+                words: ArchivedVec::serialize_from_slice(words.as_slice(), serializer)?,
+            })
+        }
+    }
+};
+
+impl<T: DeltaInt + fmt::Debug> fmt::Debug for ArchivedDeltaVec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: DeltaInt + Eq> Eq for ArchivedDeltaVec<T> {}
+
+impl<T: DeltaInt + PartialEq> PartialEq for ArchivedDeltaVec<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: DeltaInt + PartialEq> PartialEq<[T]> for ArchivedDeltaVec<T> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        self.iter().eq(other.iter().copied())
+    }
+}
+
+impl<'a, T: DeltaInt> IntoIterator for &'a ArchivedDeltaVec<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of an archived delta-encoded vector.
+pub struct Iter<'a, T> {
+    vec: &'a ArchivedDeltaVec<T>,
+    index: usize,
+    end: usize,
+}
+
+impl<T: DeltaInt> Iterator for Iter<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let value = self.vec.get(self.index)?;
+        self.index += 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: DeltaInt> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        self.vec.get(self.end)
+    }
+}
+
+impl<T: DeltaInt> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: DeltaInt> FusedIterator for Iter<'_, T> {}
+
+/// The resolver for an [`ArchivedDeltaVec`].
+pub struct DeltaVecResolver {
+    words_len: usize,
+    bases: VecResolver,
+    starts: VecResolver,
+    words: VecResolver,
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::ArchiveContext;
+    use bytecheck::{CheckBytes, Error, ErrorBox, StructCheckError};
+    use core::ptr::addr_of;
+
+    /// An error resulting from an archived delta-encoded vector with blocks that don't match its
+    /// length or words.
+    #[derive(Debug)]
+    pub enum DeltaVecLayoutError {
+        /// The number of blocks doesn't match the length of the vector
+        BlockCount {
+            /// The number of elements in the vector
+            len: usize,
+            /// The number of block bases in the vector
+            bases: usize,
+            /// The number of block starts in the vector
+            starts: usize,
+        },
+        /// A block is wider than 64 bits or extends past the end of the words
+        InvalidBlock {
+            /// The index of the invalid block
+            block: usize,
+        },
+    }
+
+    impl fmt::Display for DeltaVecLayoutError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                DeltaVecLayoutError::BlockCount { len, bases, starts } => write!(
+                    f,
+                    "delta-encoded vector of length {} has {} block bases and {} block starts, expected {} and {}",
+                    len,
+                    bases,
+                    starts,
+                    block_count(*len),
+                    block_count(*len) + 1,
+                ),
+                DeltaVecLayoutError::InvalidBlock { block } => {
+                    write!(f, "block {} of delta-encoded vector is invalid", block)
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for DeltaVecLayoutError {}
+
+    impl<T, C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedDeltaVec<T>
+    where
+        C::Error: Error,
+    {
+        type Error = StructCheckError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            let bases =
+                ArchivedVec::<Archived<u64>>::check_bytes(addr_of!((*value).bases), context)
+                    .map_err(|e| StructCheckError {
+                        field_name: "bases",
+                        inner: ErrorBox::new(e),
+                    })?;
+            let starts =
+                ArchivedVec::<Archived<u32>>::check_bytes(addr_of!((*value).starts), context)
+                    .map_err(|e| StructCheckError {
+                        field_name: "starts",
+                        inner: ErrorBox::new(e),
+                    })?;
+            let words =
+                ArchivedVec::<Archived<u64>>::check_bytes(addr_of!((*value).words), context)
+                    .map_err(|e| StructCheckError {
+                        field_name: "words",
+                        inner: ErrorBox::new(e),
+                    })?;
+            let len =
+                Archived::<usize>::check_bytes(addr_of!((*value).len), context).map_err(|e| {
+                    StructCheckError {
+                        field_name: "len",
+                        inner: ErrorBox::new(e),
+                    }
+                })?;
+
+            let len = from_archived!(*len) as usize;
+            if bases.len() != block_count(len) || starts.len() != block_count(len) + 1 {
+                return Err(StructCheckError {
+                    field_name: "len",
+                    inner: ErrorBox::new(DeltaVecLayoutError::BlockCount {
+                        len,
+                        bases: bases.len(),
+                        starts: starts.len(),
+                    }),
+                });
+            }
+
+            // Blocks must be laid out back to back and be at most 64 words (bits per element) wide
+            let invalid_block = |block| StructCheckError {
+                field_name: "starts",
+                inner: ErrorBox::new(DeltaVecLayoutError::InvalidBlock { block }),
+            };
+            if from_archived!(starts[0]) != 0 {
+                return Err(invalid_block(0));
+            }
+            for block in 0..bases.len() {
+                let start = from_archived!(starts[block]) as usize;
+                let end = from_archived!(starts[block + 1]) as usize;
+                if end < start || end - start > 64 || end > words.len() {
+                    return Err(invalid_block(block));
+                }
+            }
+            if from_archived!(starts[bases.len()]) as usize != words.len() {
+                return Err(invalid_block(bases.len().saturating_sub(1)));
+            }
+
+            Ok(&*value)
+        }
+    }
+};
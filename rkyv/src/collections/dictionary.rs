@@ -0,0 +1,369 @@
+//! Archived dictionary-encoded string vector implementation.
+//!
+//! Dictionary-encoded vectors store each unique string once, along with a `u32` code for each
+//! element that indexes into the unique strings. This makes archives of strings with lots of
+//! repeated values (like log levels, hostnames, or categories) much smaller, and the elements can
+//! still be read directly from the archive as `&str`s.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, with::AsDictionary, Archive, Deserialize, Infallible, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Log {
+//!     #[with(AsDictionary)]
+//!     levels: Vec<String>,
+//! }
+//!
+//! let levels = ["info", "info", "warn", "info", "error", "warn"];
+//! let value = Log {
+//!     levels: levels.iter().map(|s| s.to_string()).collect(),
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//! let archived = unsafe { archived_root::<Log>(&bytes) };
+//!
+//! assert_eq!(archived.levels.len(), 6);
+//! assert_eq!(archived.levels.dictionary().len(), 3);
+//! assert_eq!(&archived.levels[4], "error");
+//!
+//! // Searching by code only compares integers
+//! let warn = archived.levels.code_of("warn").unwrap();
+//! let warnings = archived.levels.codes().iter().filter(|c| **c == warn).count();
+//! assert_eq!(warnings, 2);
+//!
+//! let deserialized: Log = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized.levels, levels);
+//! ```
+
+use crate::{
+    string::ArchivedString,
+    vec::{ArchivedVec, VecResolver},
+    Archived,
+};
+use core::{fmt, iter::FusedIterator, ops::Index};
+
+/// An archived vector of strings that stores each unique string once.
+///
+/// This is the archived counterpart of [`AsDictionary`](crate::with::AsDictionary).
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedDictionaryVec {
+    dictionary: ArchivedVec<ArchivedString>,
+    codes: ArchivedVec<Archived<u32>>,
+}
+
+impl ArchivedDictionaryVec {
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Returns whether the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Returns the element at the given index, or `None` if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let code = self.code(index)?;
+        Some(self.dictionary[code as usize].as_str())
+    }
+
+    /// Returns the code of the element at the given index, or `None` if the index is out of
+    /// bounds.
+    #[inline]
+    pub fn code(&self, index: usize) -> Option<u32> {
+        let code = self.codes.get(index)?;
+        Some(from_archived!(*code))
+    }
+
+    /// Returns the code of each element.
+    ///
+    /// Each code is the index of the element in the [`dictionary`](Self::dictionary).
+    #[inline]
+    pub fn codes(&self) -> &[Archived<u32>] {
+        self.codes.as_slice()
+    }
+
+    /// Returns the unique strings of the vector, in the order that they first appear.
+    #[inline]
+    pub fn dictionary(&self) -> &[ArchivedString] {
+        self.dictionary.as_slice()
+    }
+
+    /// Returns the code of the given string, or `None` if the vector doesn't contain it.
+    ///
+    /// This searches the dictionary, which is usually much smaller than the vector itself.
+    #[inline]
+    pub fn code_of(&self, value: &str) -> Option<u32> {
+        self.dictionary
+            .iter()
+            .position(|s| s.as_str() == value)
+            .map(|code| code as u32)
+    }
+
+    /// Gets an iterator over the elements of the vector.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            dictionary: self.dictionary(),
+            codes: self.codes().iter(),
+        }
+    }
+
+    /// Resolves an archived dictionary-encoded vector from the number of elements it contains.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a dictionary-encoded vector
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: DictionaryVecResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.dictionary);
+        ArchivedVec::resolve_from_len(resolver.dictionary_len, pos + fp, resolver.dictionary, fo);
+        let (fp, fo) = out_field!(out.codes);
+        ArchivedVec::resolve_from_len(len, pos + fp, resolver.codes, fo);
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::{
+        ser::{ScratchSpace, Serializer},
+        string::StringResolver,
+        Archive, Fallible, Serialize,
+    };
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+    use core::convert::TryFrom;
+    use hashbrown::{hash_map::Entry, HashMap};
+
+    // Serializes the unique strings without copying them into `String`s first
+    struct StrRef<'a>(&'a str);
+
+    impl Archive for StrRef<'_> {
+        type Archived = ArchivedString;
+        type Resolver = StringResolver;
+
+        #[inline]
+        unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+            ArchivedString::resolve_from_str(self.0, pos, resolver, out);
+        }
+    }
+
+    impl<S: Serializer + ?Sized> Serialize<S> for StrRef<'_> {
+        #[inline]
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedString::serialize_from_str(self.0, serializer)
+        }
+    }
+
+    impl ArchivedDictionaryVec {
+        /// Serializes a dictionary-encoded vector from a slice of strings.
+        ///
+        /// # Panics
+        ///
+        /// Panics if there are more than `u32::MAX` unique strings.
+        #[inline]
+        pub fn serialize_from_strs<T, S>(
+            values: &[T],
+            serializer: &mut S,
+        ) -> Result<DictionaryVecResolver, S::Error>
+        where
+            T: AsRef<str>,
+            S: Fallible + ScratchSpace + Serializer + ?Sized,
+        {
+            let mut dictionary = Vec::new();
+            let mut lookup = HashMap::new();
+            let codes = values
+                .iter()
+                .map(|value| {
+                    let value = value.as_ref();
+                    match lookup.entry(value) {
+                        Entry::Occupied(entry) => *entry.get(),
+                        Entry::Vacant(entry) => {
+                            let code = u32::try_from(dictionary.len())
+                                .expect("too many unique strings to dictionary-encode");
+                            dictionary.push(value);
+                            *entry.insert(code)
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            Ok(DictionaryVecResolver {
+                dictionary_len: dictionary.len(),
+                dictionary: ArchivedVec::serialize_from_iter::<StrRef<'_>, _, _, _>(
+                    dictionary.iter().map(|s| StrRef(s)),
+                    serializer,
+                )?,
+                codes: ArchivedVec::serialize_from_slice(codes.as_slice(), serializer)?,
+            })
+        }
+    }
+};
+
+impl fmt::Debug for ArchivedDictionaryVec {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Eq for ArchivedDictionaryVec {}
+
+impl Index<usize> for ArchivedDictionaryVec {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &str {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl PartialEq for ArchivedDictionaryVec {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: AsRef<str>> PartialEq<[T]> for ArchivedDictionaryVec {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        self.iter().eq(other.iter().map(AsRef::as_ref))
+    }
+}
+
+impl<'a> IntoIterator for &'a ArchivedDictionaryVec {
+    type Item = &'a str;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of an archived dictionary-encoded vector.
+pub struct Iter<'a> {
+    dictionary: &'a [ArchivedString],
+    codes: core::slice::Iter<'a, Archived<u32>>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let code = from_archived!(*self.codes.next()?);
+        Some(self.dictionary[code as usize].as_str())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.codes.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let code = from_archived!(*self.codes.next_back()?);
+        Some(self.dictionary[code as usize].as_str())
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// The resolver for an [`ArchivedDictionaryVec`].
+pub struct DictionaryVecResolver {
+    dictionary_len: usize,
+    dictionary: VecResolver,
+    codes: VecResolver,
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::ArchiveContext;
+    use bytecheck::{CheckBytes, Error, ErrorBox, StructCheckError};
+    use core::ptr::addr_of;
+
+    /// An error resulting from an archived dictionary-encoded vector with a code that is out of
+    /// bounds of its dictionary.
+    #[derive(Debug)]
+    pub struct DictionaryCodeError {
+        /// The index of the element with the invalid code
+        pub index: usize,
+        /// The invalid code
+        pub code: u32,
+        /// The number of strings in the dictionary
+        pub dictionary_len: usize,
+    }
+
+    impl fmt::Display for DictionaryCodeError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "element {} has code {} but the dictionary only has {} strings",
+                self.index, self.code, self.dictionary_len,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for DictionaryCodeError {}
+
+    impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedDictionaryVec
+    where
+        C::Error: Error,
+    {
+        type Error = StructCheckError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            let dictionary =
+                ArchivedVec::<ArchivedString>::check_bytes(addr_of!((*value).dictionary), context)
+                    .map_err(|e| StructCheckError {
+                        field_name: "dictionary",
+                        inner: ErrorBox::new(e),
+                    })?;
+            let codes =
+                ArchivedVec::<Archived<u32>>::check_bytes(addr_of!((*value).codes), context)
+                    .map_err(|e| StructCheckError {
+                        field_name: "codes",
+                        inner: ErrorBox::new(e),
+                    })?;
+
+            for (index, code) in codes.iter().enumerate() {
+                let code = from_archived!(*code);
+                if code as usize >= dictionary.len() {
+                    return Err(StructCheckError {
+                        field_name: "codes",
+                        inner: ErrorBox::new(DictionaryCodeError {
+                            index,
+                            code,
+                            dictionary_len: dictionary.len(),
+                        }),
+                    });
+                }
+            }
+
+            Ok(&*value)
+        }
+    }
+};
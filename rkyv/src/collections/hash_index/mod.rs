@@ -58,9 +58,14 @@ impl ArchivedHashIndex {
     /// Returns the index where a key may be located in the hash index.
     ///
     /// The hash index does not have access to the keys used to build it, so the key at the returned
-    /// index must be checked for equality.
+    /// index must be checked for equality. The returned index is always less than the length of the
+    /// hash index.
     #[inline]
     pub fn index<K: Hash + ?Sized>(&self, k: &K) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+
         let mut hasher = self.hasher();
         k.hash(&mut hasher);
         let displace_index = hasher.finish() % self.len() as u64;
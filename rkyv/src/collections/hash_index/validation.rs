@@ -88,6 +88,9 @@ where
             ptr::addr_of!((*value).len),
             context,
         )?) as usize;
+        context
+            .check_collection_len(len)
+            .map_err(HashIndexError::ContextError)?;
         Layout::array::<Archived<u32>>(len)?;
 
         let displace_rel_ptr =
@@ -108,6 +111,8 @@ where
             .pop_prefix_range(range)
             .map_err(HashIndexError::ContextError)?;
 
+        // Direct displacements are used as entry indices, so they must be in bounds. Seeded
+        // displacements (with the high bit set) are always reduced modulo the length.
         for (i, &d) in displace.iter().enumerate() {
             let d = from_archived!(d);
             if d as usize >= len && d < 0x80_00_00_00 {
@@ -9,7 +9,7 @@ pub mod validation;
 use crate::{
     collections::{
         hash_index::{ArchivedHashIndex, HashIndexResolver},
-        util::Entry,
+        util::{Entry, Equivalent},
     },
     RelPtr,
 };
@@ -18,9 +18,7 @@ use crate::{
     ser::{ScratchSpace, Serializer},
     Serialize,
 };
-use core::{
-    borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData, ops::Index, pin::Pin,
-};
+use core::{fmt, hash::Hash, iter::FusedIterator, marker::PhantomData, ops::Index, pin::Pin};
 
 /// An archived `HashMap`.
 #[cfg_attr(feature = "strict", repr(C))]
@@ -56,12 +54,11 @@ impl<K, V> ArchivedHashMap<K, V> {
     #[inline]
     fn find<Q: ?Sized>(&self, k: &Q) -> Option<usize>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.index.index(k).and_then(|i| {
             let entry = unsafe { self.entry(i) };
-            if entry.key.borrow() == k {
+            if k.equivalent(&entry.key) {
                 Some(i)
             } else {
                 None
@@ -73,8 +70,7 @@ impl<K, V> ArchivedHashMap<K, V> {
     #[inline]
     pub fn get_key_value<Q: ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(k).map(move |index| {
             let entry = unsafe { self.entry(index) };
@@ -86,8 +82,7 @@ impl<K, V> ArchivedHashMap<K, V> {
     #[inline]
     pub fn get_key_value_pin<Q: ?Sized>(self: Pin<&mut Self>, k: &Q) -> Option<(&K, Pin<&mut V>)>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         unsafe {
             let hash_map = self.get_unchecked_mut();
@@ -102,8 +97,7 @@ impl<K, V> ArchivedHashMap<K, V> {
     #[inline]
     pub fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(k).is_some()
     }
@@ -112,8 +106,7 @@ impl<K, V> ArchivedHashMap<K, V> {
     #[inline]
     pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(k)
             .map(|index| unsafe { &self.entry(index).value })
@@ -123,8 +116,7 @@ impl<K, V> ArchivedHashMap<K, V> {
     #[inline]
     pub fn get_pin<Q: ?Sized>(self: Pin<&mut Self>, k: &Q) -> Option<Pin<&mut V>>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         unsafe {
             let hash_map = self.get_unchecked_mut();
@@ -134,6 +126,44 @@ impl<K, V> ArchivedHashMap<K, V> {
         }
     }
 
+    /// Gets a key-value pair by index.
+    ///
+    /// Entries are indexed by their position in the archive, which is the order they're iterated
+    /// in. Indices don't change for a given archive, so they can be used to refer to entries
+    /// without looking their keys up again.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        if index < self.len() {
+            let entry = unsafe { self.entry(index) };
+            Some((&entry.key, &entry.value))
+        } else {
+            None
+        }
+    }
+
+    /// Gets a key and mutable value pair by index.
+    #[inline]
+    pub fn get_index_pin(self: Pin<&mut Self>, index: usize) -> Option<(&K, Pin<&mut V>)> {
+        unsafe {
+            let hash_map = self.get_unchecked_mut();
+            if index < hash_map.len() {
+                let entry = hash_map.entry_mut(index);
+                Some((&entry.key, Pin::new_unchecked(&mut entry.value)))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Gets the index of a key if it exists in the map.
+    #[inline]
+    pub fn get_index_of<Q>(&self, k: &Q) -> Option<usize>
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        self.find(k)
+    }
+
     /// Returns `true` if the map contains no elements.
     #[inline]
     pub const fn is_empty(&self) -> bool {
@@ -278,7 +308,17 @@ impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ArchivedHashMap<K, V> {
 
 impl<K: Hash + Eq, V: Eq> Eq for ArchivedHashMap<K, V> {}
 
-impl<K: Eq + Hash + Borrow<Q>, Q: Eq + Hash + ?Sized, V> Index<&'_ Q> for ArchivedHashMap<K, V> {
+impl<'a, K, V> IntoIterator for &'a ArchivedHashMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Eq + Hash, Q: Hash + Equivalent<K> + ?Sized, V> Index<&'_ Q> for ArchivedHashMap<K, V> {
     type Output = V;
 
     #[inline]
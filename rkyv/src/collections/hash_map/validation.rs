@@ -140,6 +140,8 @@ where
             .pop_prefix_range(range)
             .map_err(HashMapError::ContextError)?;
 
+        // Every entry must be found at its own position. This also guarantees that the keys are
+        // unique.
         for (i, entry) in entries.iter().enumerate() {
             if index.index(&entry.key) != Some(i) {
                 return Err(HashMapError::InvalidKeyPosition { index: i });
@@ -3,13 +3,16 @@
 //! During archiving, hashsets are built into minimal perfect hashsets using
 //! [compress, hash and displace](http://cmph.sourceforge.net/papers/esa09.pdf).
 
-use crate::collections::hash_map::{ArchivedHashMap, HashMapResolver, Keys};
+use crate::collections::{
+    hash_map::{ArchivedHashMap, HashMapResolver, Keys},
+    util::Equivalent,
+};
 #[cfg(feature = "alloc")]
 use crate::{
     ser::{ScratchSpace, Serializer},
     Serialize,
 };
-use core::{borrow::Borrow, fmt, hash::Hash};
+use core::{fmt, hash::Hash};
 
 /// An archived `HashSet`. This is a wrapper around a hash map with the same key and a value of
 /// `()`.
@@ -28,8 +31,7 @@ impl<K> ArchivedHashSet<K> {
     #[inline]
     pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&K>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.0.get_key_value(k).map(|(k, _)| k)
     }
@@ -38,8 +40,7 @@ impl<K> ArchivedHashSet<K> {
     #[inline]
     pub fn contains<Q: ?Sized>(&self, k: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.0.contains_key(k)
     }
@@ -110,6 +111,16 @@ impl<K: fmt::Debug> fmt::Debug for ArchivedHashSet<K> {
     }
 }
 
+impl<'a, K> IntoIterator for &'a ArchivedHashSet<K> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, ()>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 /// The resolver for archived hash sets.
 pub struct HashSetResolver(HashMapResolver);
 
@@ -9,11 +9,11 @@ pub mod validation;
 use crate::{
     collections::{
         hash_index::{ArchivedHashIndex, HashBuilder, HashIndexResolver},
-        util::Entry,
+        util::{Entry, Equivalent},
     },
-    out_field, Archived, RelPtr,
+    Archived, RelPtr,
 };
-use core::{borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData};
+use core::{fmt, hash::Hash, iter::FusedIterator, marker::PhantomData, ops::Index, pin::Pin};
 
 /// An archived `IndexMap`.
 #[cfg_attr(feature = "strict", repr(C))]
@@ -34,16 +34,20 @@ impl<K, V> ArchivedIndexMap<K, V> {
         &*self.entries.as_ptr().add(index)
     }
 
+    #[inline]
+    unsafe fn entry_mut(&mut self, index: usize) -> &mut Entry<K, V> {
+        &mut *self.entries.as_mut_ptr().add(index)
+    }
+
     #[inline]
     fn find<Q: ?Sized>(&self, k: &Q) -> Option<usize>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.index.index(k).and_then(|pivot_index| {
             let index = unsafe { self.pivot(pivot_index) };
             let entry = unsafe { self.entry(index) };
-            if entry.key.borrow() == k {
+            if k.equivalent(&entry.key) {
                 Some(index)
             } else {
                 None
@@ -55,8 +59,7 @@ impl<K, V> ArchivedIndexMap<K, V> {
     #[inline]
     pub fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(k).is_some()
     }
@@ -76,19 +79,31 @@ impl<K, V> ArchivedIndexMap<K, V> {
     #[inline]
     pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(k)
             .map(|index| unsafe { &self.entry(index).value })
     }
 
+    /// Gets the mutable value associated with the given key.
+    #[inline]
+    pub fn get_pin<Q>(self: Pin<&mut Self>, k: &Q) -> Option<Pin<&mut V>>
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        unsafe {
+            let index_map = self.get_unchecked_mut();
+            index_map
+                .find(k)
+                .map(move |index| Pin::new_unchecked(&mut index_map.entry_mut(index).value))
+        }
+    }
+
     /// Gets the index, key, and value associated with the given key.
     #[inline]
     pub fn get_full<Q: ?Sized>(&self, k: &Q) -> Option<(usize, &K, &V)>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(k).map(|index| {
             let entry = unsafe { &self.entry(index) };
@@ -107,12 +122,25 @@ impl<K, V> ArchivedIndexMap<K, V> {
         }
     }
 
+    /// Gets a key and mutable value pair by index.
+    #[inline]
+    pub fn get_index_pin(self: Pin<&mut Self>, index: usize) -> Option<(&K, Pin<&mut V>)> {
+        unsafe {
+            let index_map = self.get_unchecked_mut();
+            if index < index_map.len() {
+                let entry = index_map.entry_mut(index);
+                Some((&entry.key, Pin::new_unchecked(&mut entry.value)))
+            } else {
+                None
+            }
+        }
+    }
+
     /// Gets the index of a key if it exists in the map.
     #[inline]
     pub fn get_index_of<Q: ?Sized>(&self, key: &Q) -> Option<usize>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(key)
     }
@@ -121,8 +149,7 @@ impl<K, V> ArchivedIndexMap<K, V> {
     #[inline]
     pub fn get_key_value<Q: ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.find(k).map(|index| {
             let entry = unsafe { &self.entry(index) };
@@ -155,6 +182,22 @@ impl<K, V> ArchivedIndexMap<K, V> {
         }
     }
 
+    #[inline]
+    fn raw_iter_pin(self: Pin<&mut Self>) -> RawIterPin<'_, K, V> {
+        unsafe {
+            let index_map = self.get_unchecked_mut();
+            RawIterPin::new(index_map.entries.as_mut_ptr().cast(), index_map.len())
+        }
+    }
+
+    /// Returns an iterator over the mutable key-value pairs of the map in order
+    #[inline]
+    pub fn iter_pin(self: Pin<&mut Self>) -> IterPin<'_, K, V> {
+        IterPin {
+            inner: self.raw_iter_pin(),
+        }
+    }
+
     /// Returns an iterator over the keys of the map in order
     #[inline]
     pub fn keys(&self) -> Keys<K, V> {
@@ -188,6 +231,14 @@ impl<K, V> ArchivedIndexMap<K, V> {
         }
     }
 
+    /// Returns an iterator over the mutable values of the map in order.
+    #[inline]
+    pub fn values_pin(self: Pin<&mut Self>) -> ValuesPin<'_, K, V> {
+        ValuesPin {
+            inner: self.raw_iter_pin(),
+        }
+    }
+
     /// Resolves an archived index map from a given length and parameters.
     ///
     /// # Safety
@@ -285,6 +336,24 @@ impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ArchivedIndexMap<K, V> {
     }
 }
 
+impl<K: Eq + Hash, Q: Hash + Equivalent<K> + ?Sized, V> Index<&'_ Q> for ArchivedIndexMap<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).unwrap()
+    }
+}
+
+impl<K, V> Index<usize> for ArchivedIndexMap<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, index: usize) -> &V {
+        self.get_index(index).unwrap().1
+    }
+}
+
 impl<K: PartialEq, V: PartialEq> PartialEq for ArchivedIndexMap<K, V> {
     fn eq(&self, other: &Self) -> bool {
         self.iter().eq(other.iter())
@@ -335,6 +404,50 @@ impl<'a, K, V> Iterator for RawIter<'a, K, V> {
 impl<'a, K, V> ExactSizeIterator for RawIter<'a, K, V> {}
 impl<'a, K, V> FusedIterator for RawIter<'a, K, V> {}
 
+struct RawIterPin<'a, K, V> {
+    current: *mut Entry<K, V>,
+    remaining: usize,
+    _phantom: PhantomData<(&'a K, Pin<&'a mut V>)>,
+}
+
+impl<'a, K, V> RawIterPin<'a, K, V> {
+    #[inline]
+    fn new(pairs: *mut Entry<K, V>, len: usize) -> Self {
+        Self {
+            current: pairs,
+            remaining: len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for RawIterPin<'a, K, V> {
+    type Item = (&'a K, Pin<&'a mut V>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            if self.remaining == 0 {
+                None
+            } else {
+                let result = self.current;
+                self.current = self.current.add(1);
+                self.remaining -= 1;
+                let entry = &mut *result;
+                Some((&entry.key, Pin::new_unchecked(&mut entry.value)))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for RawIterPin<'_, K, V> {}
+impl<K, V> FusedIterator for RawIterPin<'_, K, V> {}
+
 /// An iterator over the key-value pairs of an index map.
 #[repr(transparent)]
 pub struct Iter<'a, K, V> {
@@ -358,6 +471,29 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
 impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
 impl<K, V> FusedIterator for Iter<'_, K, V> {}
 
+/// An iterator over the mutable key-value pairs of an index map.
+#[repr(transparent)]
+pub struct IterPin<'a, K, V> {
+    inner: RawIterPin<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for IterPin<'a, K, V> {
+    type Item = (&'a K, Pin<&'a mut V>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterPin<'_, K, V> {}
+impl<K, V> FusedIterator for IterPin<'_, K, V> {}
+
 /// An iterator over the keys of an index map.
 #[repr(transparent)]
 pub struct Keys<'a, K, V> {
@@ -404,6 +540,29 @@ impl<'a, K, V> Iterator for Values<'a, K, V> {
 impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
 impl<K, V> FusedIterator for Values<'_, K, V> {}
 
+/// An iterator over the mutable values of an index map.
+#[repr(transparent)]
+pub struct ValuesPin<'a, K, V> {
+    inner: RawIterPin<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesPin<'a, K, V> {
+    type Item = Pin<&'a mut V>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for ValuesPin<'_, K, V> {}
+impl<K, V> FusedIterator for ValuesPin<'_, K, V> {}
+
 // Archive implementations
 
 /// The resolver for an `IndexMap`.
@@ -181,6 +181,8 @@ where
             }
         }
 
+        // Every entry must be found at its own position through the pivots. This also guarantees
+        // that the keys are unique.
         for (i, entry) in entries.iter().enumerate() {
             if let Some(pivot_index) = index.index(&entry.key) {
                 let pivot = from_archived!(pivots[pivot_index]) as usize;
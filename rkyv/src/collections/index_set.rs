@@ -3,14 +3,12 @@
 //! During archiving, index sets are built into minimal perfect index sets using
 //! [compress, hash and displace](http://cmph.sourceforge.net/papers/esa09.pdf).
 
-use crate::{
-    collections::{
-        hash_index::HashBuilder,
-        index_map::{ArchivedIndexMap, IndexMapResolver, Keys},
-    },
-    out_field,
+use crate::collections::{
+    hash_index::HashBuilder,
+    index_map::{ArchivedIndexMap, IndexMapResolver, Keys},
+    util::Equivalent,
 };
-use core::{borrow::Borrow, fmt, hash::Hash};
+use core::{fmt, hash::Hash, ops::Index};
 
 /// An archived `IndexSet`.
 #[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
@@ -24,8 +22,7 @@ impl<K> ArchivedIndexSet<K> {
     #[inline]
     pub fn contains<Q: ?Sized>(&self, k: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.inner.contains_key(k)
     }
@@ -40,8 +37,7 @@ impl<K> ArchivedIndexSet<K> {
     #[inline]
     pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&K>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.inner.get_full(k).map(|(_, k, _)| k)
     }
@@ -50,8 +46,7 @@ impl<K> ArchivedIndexSet<K> {
     #[inline]
     pub fn get_full<Q: ?Sized>(&self, k: &Q) -> Option<(usize, &K)>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.inner.get_full(k).map(|(i, k, _)| (i, k))
     }
@@ -66,8 +61,7 @@ impl<K> ArchivedIndexSet<K> {
     #[inline]
     pub fn get_index_of<Q: ?Sized>(&self, key: &Q) -> Option<usize>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K>,
     {
         self.inner.get_index_of(key)
     }
@@ -164,6 +158,15 @@ impl<K: fmt::Debug> fmt::Debug for ArchivedIndexSet<K> {
     }
 }
 
+impl<K> Index<usize> for ArchivedIndexSet<K> {
+    type Output = K;
+
+    #[inline]
+    fn index(&self, index: usize) -> &K {
+        self.get_index(index).unwrap()
+    }
+}
+
 impl<K: PartialEq> PartialEq for ArchivedIndexSet<K> {
     fn eq(&self, other: &Self) -> bool {
         self.iter().eq(other.iter())
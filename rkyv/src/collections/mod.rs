@@ -1,7 +1,10 @@
 //! Archived versions of standard library containers.
 
+pub mod bit_set;
 pub mod btree_map;
 pub mod btree_set;
+pub mod delta_vec;
+pub mod dictionary;
 pub mod hash_index;
 pub mod hash_map;
 pub mod hash_set;
@@ -10,7 +13,10 @@ pub mod index_map;
 pub mod index_set;
 pub mod util;
 
+pub use self::bit_set::ArchivedBitSet;
 pub use self::btree_map::ArchivedBTreeMap;
+pub use self::delta_vec::ArchivedDeltaVec;
+pub use self::dictionary::ArchivedDictionaryVec;
 pub use self::hash_index::ArchivedHashIndex;
 pub use self::hash_map::ArchivedHashMap;
 pub use self::hash_set::ArchivedHashSet;
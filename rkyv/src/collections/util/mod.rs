@@ -4,6 +4,95 @@
 pub mod validation;
 
 use crate::{Archive, Fallible, Serialize};
+use core::borrow::Borrow;
+
+/// Key equivalence for archived hash collections.
+///
+/// Every type that an archived key can be borrowed as is equivalent to that key, so archived
+/// collections can be queried with any type that works with [`Borrow`]. For example, a map with
+/// archived `String` keys can be queried with a `&str`. Types that can't be borrowed from their
+/// archived keys can implement this trait to be used as queries as well, like `char` for archived
+/// `char` keys.
+///
+/// With `archive_le` or `archive_be`, archived multibyte integers can't be borrowed as native
+/// integers. Instead, keys are only equivalent to themselves and to the queries implemented in
+/// this module, which include native integers, `str`, slices, `CStr`, and box and rc pointees.
+///
+/// Implementors must hash the same as the keys they are equivalent to.
+pub trait Equivalent<K: ?Sized> {
+    /// Returns whether this value is equivalent to the given key.
+    fn equivalent(&self, key: &K) -> bool;
+}
+
+#[cfg(not(any(feature = "archive_le", feature = "archive_be")))]
+impl<Q: Eq + ?Sized, K: Borrow<Q> + ?Sized> Equivalent<K> for Q {
+    #[inline]
+    fn equivalent(&self, key: &K) -> bool {
+        self == key.borrow()
+    }
+}
+
+// Endian-aware integers come from `rend`, which could add `Borrow` impls for them, so the blanket
+// impl above would overlap with the integer impls below
+#[cfg(any(feature = "archive_le", feature = "archive_be"))]
+const _: () = {
+    use crate::{
+        boxed::ArchivedBox,
+        rc::ArchivedRc,
+        string::{
+            inline::ArchivedInlineString, nul_terminated::ArchivedNulTerminatedString,
+            ArchivedString,
+        },
+        vec::ArchivedVec,
+        ArchivePointee, Archived,
+    };
+    use core::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroU16, NonZeroU32, NonZeroU64};
+
+    impl<K: Eq + ?Sized> Equivalent<K> for K {
+        #[inline]
+        fn equivalent(&self, key: &K) -> bool {
+            self == key
+        }
+    }
+
+    macro_rules! impl_equivalent_borrow {
+        ($query:ty, $key:ty $(, $($params:tt)*)?) => {
+            impl$(<$($params)*>)? Equivalent<$key> for $query {
+                #[inline]
+                fn equivalent(&self, key: &$key) -> bool {
+                    self == Borrow::<$query>::borrow(key)
+                }
+            }
+        };
+    }
+
+    impl_equivalent_borrow!(str, ArchivedString);
+    impl_equivalent_borrow!(str, ArchivedInlineString<N>, const N: usize);
+    impl_equivalent_borrow!(str, ArchivedNulTerminatedString);
+    impl_equivalent_borrow!([T], ArchivedVec<T>, T: PartialEq);
+    impl_equivalent_borrow!(T, ArchivedBox<T>, T: ArchivePointee + PartialEq + ?Sized);
+    impl_equivalent_borrow!(T, ArchivedRc<T, F>, T: ArchivePointee + PartialEq + ?Sized, F);
+    #[cfg(feature = "std")]
+    impl_equivalent_borrow!(std::ffi::CStr, crate::ffi::ArchivedCString);
+
+    macro_rules! impl_equivalent_endian {
+        ($($ty:ty),* $(,)?) => {
+            $(
+                impl Equivalent<Archived<$ty>> for $ty {
+                    #[inline]
+                    fn equivalent(&self, key: &Archived<$ty>) -> bool {
+                        *self == from_archived!(*key)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_equivalent_endian!(
+        i16, i32, i64, u16, u32, u64, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroU16, NonZeroU32,
+        NonZeroU64,
+    );
+};
 
 /// A simple key-value pair.
 ///
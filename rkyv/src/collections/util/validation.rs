@@ -1,6 +1,9 @@
 //! Validation implementation for utility types.
 
-use crate::{collections::util::Entry, validation::ArchiveContext};
+use crate::{
+    collections::util::Entry,
+    validation::{ArchiveContext, PathSegment},
+};
 use ::bytecheck::CheckBytes;
 use ::core::{fmt, ptr};
 
@@ -49,10 +52,16 @@ where
         value: *const Self,
         context: &mut C,
     ) -> Result<&'a Self, Self::Error> {
-        K::check_bytes(ptr::addr_of!((*value).key), context)
-            .map_err(ArchivedEntryError::KeyCheckError)?;
-        V::check_bytes(ptr::addr_of!((*value).value), context)
-            .map_err(ArchivedEntryError::ValueCheckError)?;
+        let key = ptr::addr_of!((*value).key);
+        K::check_bytes(key, context).map_err(|e| {
+            context.record_check_error(&e, PathSegment::Key, key.cast());
+            ArchivedEntryError::KeyCheckError(e)
+        })?;
+        let value_ptr = ptr::addr_of!((*value).value);
+        V::check_bytes(value_ptr, context).map_err(|e| {
+            context.record_check_error(&e, PathSegment::Value, value_ptr.cast());
+            ArchivedEntryError::ValueCheckError(e)
+        })?;
         Ok(&*value)
     }
 }
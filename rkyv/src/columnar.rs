@@ -0,0 +1,106 @@
+//! Archiving collections of structs as columns.
+//!
+//! A `Vec` of structs is normally archived row by row, with the fields of each struct next to each
+//! other. Structs derived with `#[archive(columnar)]` can also be archived as columns instead: each
+//! field is archived as its own `ArchivedVec` with one element per row. Scanning a single field of
+//! every row then only touches that field's contiguous column.
+//!
+//! The derive generates an `Archived{Name}Columns` struct with one column for each field, and a
+//! `Vec` of the struct can be archived as its columns with the [`AsColumns`] wrapper:
+//!
+//! ```
+//! use rkyv::{archived_root, with::AsColumns, Archive, Deserialize, Infallible, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+//! #[archive(columnar)]
+//! struct Trade {
+//!     price: f64,
+//!     quantity: u32,
+//!     symbol: String,
+//! }
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Trades {
+//!     #[with(AsColumns)]
+//!     trades: Vec<Trade>,
+//! }
+//!
+//! let value = Trades {
+//!     trades: vec![
+//!         Trade { price: 10.5, quantity: 100, symbol: "ABC".to_string() },
+//!         Trade { price: 11.0, quantity: 50, symbol: "XYZ".to_string() },
+//!     ],
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//! let archived = unsafe { archived_root::<Trades>(&bytes) };
+//!
+//! // Each field is a contiguous column
+//! assert_eq!(archived.trades.len(), 2);
+//! assert_eq!(archived.trades.quantity.iter().sum::<u32>(), 150);
+//! assert_eq!(archived.trades.symbol[1], "XYZ");
+//!
+//! // The columns deserialize back into rows
+//! let deserialized: Trades = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized.trades, value.trades);
+//! ```
+//!
+//! The struct still implements `Archive` as usual, so it can be archived row by row elsewhere.
+//! `#[archive_attr(...)]` attributes apply to both the archived struct and its columns, so deriving
+//! `CheckBytes` for the archived struct also makes its columns checkable.
+//!
+//! [`AsColumns`]: crate::with::AsColumns
+
+use crate::Fallible;
+
+/// A type that can be archived as columns of its fields.
+///
+/// This is implemented by `#[derive(Archive)]` for `#[archive(columnar)]` structs and is not meant
+/// to be implemented directly.
+pub trait Columnar: Sized {
+    /// The archived columns of a slice of this type.
+    type ArchivedColumns;
+    /// The resolver for the columns of a slice of this type.
+    type ColumnsResolver;
+
+    /// Returns the number of rows in the given columns.
+    ///
+    /// If the columns have different lengths, this is the number of rows that are present in every
+    /// column.
+    fn columns_len(columns: &Self::ArchivedColumns) -> usize;
+
+    /// Creates the archived columns of the given rows in the output pointer.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing the columns of `rows`
+    unsafe fn resolve_columns(
+        rows: &[Self],
+        pos: usize,
+        resolver: Self::ColumnsResolver,
+        out: *mut Self::ArchivedColumns,
+    );
+}
+
+/// A type whose columns can be serialized.
+pub trait SerializeColumns<S: Fallible + ?Sized>: Columnar {
+    /// Writes the column of each field of the given rows to the serializer.
+    fn serialize_columns(
+        rows: &[Self],
+        serializer: &mut S,
+    ) -> Result<Self::ColumnsResolver, S::Error>;
+}
+
+/// A type that can be deserialized from its archived columns.
+pub trait DeserializeColumns<D: Fallible + ?Sized>: Columnar {
+    /// Deserializes the row at the given index of the columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`columns_len`](Columnar::columns_len).
+    fn deserialize_row(
+        columns: &Self::ArchivedColumns,
+        index: usize,
+        deserializer: &mut D,
+    ) -> Result<Self, D::Error>;
+}
@@ -0,0 +1,316 @@
+//! Compacting archives that have unreachable data.
+//!
+//! Appending values to an archive with [`append_to_bytes`](crate::append_to_bytes) and patching
+//! it with [`patch_box`](crate::patch_box) and [`patch_vec`](crate::patch_vec) leave behind data
+//! that is no longer reachable from any root. [`compact`] walks the values reachable from the
+//! roots of an archive using their [`Schema`], copies them into a fresh buffer, and points their
+//! relative pointers to the copies. The result is laid out like a freshly serialized archive: every
+//! value comes after the data it points to and the last root is at the end, so it can be accessed
+//! with [`archived_root`](crate::archived_root) and passes validation again.
+//!
+//! ```
+//! use rkyv::{
+//!     archived_root, archived_root_mut, compact::compact, patch_vec, Archive, Archived, Serialize,
+//! };
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(schema)]
+//! struct Log {
+//!     name: String,
+//!     lines: Vec<String>,
+//! }
+//!
+//! let value = Log {
+//!     name: "server".to_string(),
+//!     lines: vec!["a line that will be replaced".to_string(); 100],
+//! };
+//! let mut bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//! let root_pos = bytes.len() - core::mem::size_of::<Archived<Log>>();
+//!
+//! patch_vec::<_, _, 256>(
+//!     &mut bytes,
+//!     |bytes| unsafe { archived_root_mut::<Log>(bytes).map_unchecked_mut(|log| &mut log.lines) },
+//!     &["the only line".to_string()],
+//! )
+//! .unwrap();
+//!
+//! let compacted = compact::<Log>(&bytes, root_pos).unwrap();
+//! assert!(compacted.len() < bytes.len());
+//! let archived = unsafe { archived_root::<Log>(&compacted) };
+//! assert_eq!(archived.name, "server");
+//! assert_eq!(archived.lines.as_slice(), ["the only line"]);
+//! ```
+//!
+//! Compaction copies the bytes of each value without checking them, so archives from untrusted
+//! sources should still be validated after they're compacted. Values that are pointed to more than
+//! once are copied once for each pointer. Archives that contain [`Schema::Opaque`] values can't be
+//! compacted, since the pointers inside of them can't be found.
+
+use crate::{
+    rel_ptr::{checked_target, OffsetError},
+    schema::{read_tag, round_up, ArchiveSchema, Schema},
+    string::repr::ArchivedStringRepr,
+    vec::ArchivedVec,
+    AlignedVec, RawRelPtr, RelPtr,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{fmt, mem::size_of};
+
+/// An error that occurred while compacting an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactError {
+    /// A value or the data it points to was out of bounds of the archive
+    OutOfBounds {
+        /// The position of the value
+        pos: usize,
+    },
+    /// An option or enum had a tag that doesn't match any variant
+    InvalidTag {
+        /// The position of the option or enum
+        pos: usize,
+    },
+    /// A value had an opaque schema, so the pointers inside of it couldn't be found
+    Opaque {
+        /// The name of the opaque type
+        name: &'static str,
+        /// The position of the value
+        pos: usize,
+    },
+    /// A relative pointer in the compacted archive couldn't reach its target
+    Offset(OffsetError),
+}
+
+impl fmt::Display for CompactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactError::OutOfBounds { pos } => {
+                write!(f, "value at {:#x} is out of bounds of the archive", pos)
+            }
+            CompactError::InvalidTag { pos } => write!(f, "invalid tag at {:#x}", pos),
+            CompactError::Opaque { name, pos } => {
+                write!(f, "can't compact opaque value of {} at {:#x}", name, pos)
+            }
+            CompactError::Offset(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompactError {}
+
+/// Compacts an archive with a root of `T` at the given position.
+///
+/// The root is at the end of the returned archive.
+#[inline]
+pub fn compact<T: ArchiveSchema>(
+    bytes: &[u8],
+    root_pos: usize,
+) -> Result<AlignedVec, CompactError> {
+    compact_roots(bytes, &[(root_pos, &T::SCHEMA)]).map(|(compacted, _)| compacted)
+}
+
+/// Compacts an archive with roots of the given schemas at the given positions.
+///
+/// The roots are written in the given order, so the last root is at the end of the returned
+/// archive. Returns the compacted archive and the new positions of the roots.
+pub fn compact_roots(
+    bytes: &[u8],
+    roots: &[(usize, &'static Schema)],
+) -> Result<(AlignedVec, Vec<usize>), CompactError> {
+    let mut compactor = Compactor {
+        bytes,
+        out: AlignedVec::new(),
+    };
+    let positions = roots
+        .iter()
+        .map(|&(pos, schema)| compactor.copy(pos, schema, 1))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((compactor.out, positions))
+}
+
+enum PointerKind {
+    RelPtr,
+    String,
+}
+
+// A pointer inside of a value that must be pointed to the copy of its target
+struct Pointer {
+    offset: usize,
+    target: usize,
+    kind: PointerKind,
+}
+
+struct Compactor<'a> {
+    bytes: &'a [u8],
+    out: AlignedVec,
+}
+
+impl Compactor<'_> {
+    /// # Safety
+    ///
+    /// Every bit pattern must be a valid `T`.
+    #[inline]
+    unsafe fn read<T>(&self, pos: usize) -> Result<T, CompactError> {
+        self.check_bounds(pos, size_of::<T>())?;
+        Ok(self.bytes.as_ptr().add(pos).cast::<T>().read_unaligned())
+    }
+
+    #[inline]
+    fn check_bounds(&self, pos: usize, len: usize) -> Result<(), CompactError> {
+        match pos.checked_add(len) {
+            Some(end) if end <= self.bytes.len() => Ok(()),
+            _ => Err(CompactError::OutOfBounds { pos }),
+        }
+    }
+
+    #[inline]
+    fn target(&self, base: usize, offset: isize) -> Result<usize, CompactError> {
+        checked_target(base, offset, self.bytes.len())
+            .map_err(|_| CompactError::OutOfBounds { pos: base })
+    }
+
+    // Copies `len` consecutive values with the given schema and returns the position of the first
+    // one in the compacted archive. The data they point to is copied before them.
+    fn copy(
+        &mut self,
+        pos: usize,
+        schema: &'static Schema,
+        len: usize,
+    ) -> Result<usize, CompactError> {
+        let size = schema.size();
+        let total = size
+            .checked_mul(len)
+            .ok_or(CompactError::OutOfBounds { pos })?;
+        self.check_bounds(pos, total)?;
+
+        let mut pointers = Vec::new();
+        for i in 0..len {
+            self.copy_targets(pos + i * size, i * size, schema, &mut pointers)?;
+        }
+
+        let new_pos = round_up(self.out.len(), schema.align());
+        self.out.resize(new_pos, 0);
+        self.out.extend_from_slice(&self.bytes[pos..pos + total]);
+        for pointer in pointers {
+            let from = new_pos + pointer.offset;
+            // Every pointer was copied into the compacted archive above, and its target was copied
+            // before it
+            unsafe {
+                let out = self.out.as_mut_ptr().add(from);
+                match pointer.kind {
+                    PointerKind::RelPtr => RawRelPtr::try_emplace(from, pointer.target, out.cast())
+                        .map_err(CompactError::Offset)?,
+                    PointerKind::String => {
+                        ArchivedStringRepr::emplace_out_of_line_offset(
+                            from,
+                            pointer.target,
+                            out.cast(),
+                        );
+                    }
+                }
+            }
+        }
+        Ok(new_pos)
+    }
+
+    // Copies the data that the value at `pos` points to, and records the pointers in the value at
+    // `offset` from the start of the values being copied
+    fn copy_targets(
+        &mut self,
+        pos: usize,
+        offset: usize,
+        schema: &'static Schema,
+        pointers: &mut Vec<Pointer>,
+    ) -> Result<(), CompactError> {
+        match *schema {
+            Schema::String => {
+                let repr = unsafe { self.read::<ArchivedStringRepr>(pos)? };
+                if !repr.is_inline() {
+                    let len = repr.len();
+                    let target = self.target(pos, unsafe { repr.out_of_line_offset() })?;
+                    self.check_bounds(target, len)?;
+                    let new_target = self.out.len();
+                    self.out
+                        .extend_from_slice(&self.bytes[target..target + len]);
+                    pointers.push(Pointer {
+                        offset,
+                        target: new_target,
+                        kind: PointerKind::String,
+                    });
+                }
+            }
+            Schema::Array { element, len } => {
+                for i in 0..len {
+                    let element_offset = i * element.size();
+                    self.copy_targets(
+                        pos + element_offset,
+                        offset + element_offset,
+                        element,
+                        pointers,
+                    )?;
+                }
+            }
+            Schema::Vec { element } => {
+                let header = unsafe { self.read::<ArchivedVec<u8>>(pos)? };
+                let rel_ptr = header.rel_ptr();
+                let ptr_offset = rel_ptr.base() as usize - &header as *const _ as usize;
+                let target = self.target(pos + ptr_offset, rel_ptr.offset())?;
+                let new_target = self.copy(target, element, header.len())?;
+                pointers.push(Pointer {
+                    offset: offset + ptr_offset,
+                    target: new_target,
+                    kind: PointerKind::RelPtr,
+                });
+            }
+            Schema::Box { inner } => {
+                // Archived boxes are transparent wrappers around a relative pointer
+                let rel_ptr = unsafe { self.read::<RelPtr<u8>>(pos)? };
+                let target = self.target(pos, rel_ptr.offset())?;
+                let new_target = self.copy(target, inner, 1)?;
+                pointers.push(Pointer {
+                    offset,
+                    target: new_target,
+                    kind: PointerKind::RelPtr,
+                });
+            }
+            Schema::Option { inner } => match unsafe { self.read::<u8>(pos)? } {
+                0 => (),
+                1 => {
+                    let inner_offset = round_up(1, inner.align());
+                    self.copy_targets(pos + inner_offset, offset + inner_offset, inner, pointers)?;
+                }
+                _ => return Err(CompactError::InvalidTag { pos }),
+            },
+            Schema::Struct { fields, .. } => {
+                for field in fields {
+                    self.copy_targets(
+                        pos + field.offset,
+                        offset + field.offset,
+                        field.schema,
+                        pointers,
+                    )?;
+                }
+            }
+            Schema::Enum { tag, variants, .. } => {
+                self.check_bounds(pos, tag.size())?;
+                let value = unsafe { read_tag(self.bytes[pos..].as_ptr(), tag) };
+                let variant = value
+                    .and_then(|value| variants.iter().find(|v| v.tag == value))
+                    .ok_or(CompactError::InvalidTag { pos })?;
+                for field in variant.fields {
+                    self.copy_targets(
+                        pos + field.offset,
+                        offset + field.offset,
+                        field.schema,
+                        pointers,
+                    )?;
+                }
+            }
+            Schema::Opaque { name, .. } => return Err(CompactError::Opaque { name, pos }),
+            // Everything else is plain data
+            _ => (),
+        }
+        Ok(())
+    }
+}
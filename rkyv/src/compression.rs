@@ -0,0 +1,176 @@
+//! Compressed archives using [LZ4](https://lz4.github.io/lz4/).
+//!
+//! Archives are accessed directly from their bytes, so they can't be accessed while they are
+//! compressed. This module provides a writer that compresses an archive as it is serialized and
+//! functions that decompress it into an [`AlignedVec`] so it can be accessed as usual. The archive
+//! is decompressed to exactly the bytes that were serialized, so its root stays at the end of the
+//! buffer. This is useful for archives that are stored for a long time but rarely accessed.
+//!
+//! Compressed archives use the LZ4 frame format, and can be decompressed by any LZ4 implementation
+//! that supports it.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     archived_root,
+//!     compression::{decompress_bytes, to_compressed_bytes},
+//! };
+//!
+//! let value = vec![0u32; 1024];
+//! let compressed = to_compressed_bytes::<_, 256>(&value).unwrap();
+//! assert!(compressed.len() < 1024);
+//!
+//! let bytes = decompress_bytes(&compressed).unwrap();
+//! let archived = unsafe { archived_root::<Vec<u32>>(&bytes) };
+//! assert_eq!(archived.len(), 1024);
+//! ```
+
+use crate::{
+    ser::{
+        serializers::{
+            AllocScratch, CompositeSerializer, CompositeSerializerError, FallbackScratch,
+            HeapScratch, SharedSerializeMap, WriteSerializer,
+        },
+        Serializer,
+    },
+    AlignedVec, Fallible, Serialize,
+};
+use lz4_flex::frame::{FrameDecoder, FrameEncoder};
+use std::io;
+
+/// A writer that compresses the bytes written to it.
+///
+/// Wrap a `CompressWriter` in a [`WriteSerializer`] to compress an archive while it is serialized.
+/// Call [`finish`](CompressWriter::finish) when serialization is complete to write the end of the
+/// compressed data.
+pub struct CompressWriter<W: io::Write> {
+    inner: FrameEncoder<W>,
+}
+
+impl<W: io::Write> CompressWriter<W> {
+    /// Creates a new compressing writer that writes to the given writer.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: FrameEncoder::new(inner),
+        }
+    }
+
+    /// Finishes compressing and returns the underlying writer.
+    #[inline]
+    pub fn finish(self) -> io::Result<W> {
+        Ok(self.inner.finish()?)
+    }
+}
+
+impl<W: io::Write> io::Write for CompressWriter<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A general-purpose serializer that compresses the archive and writes it to a writer.
+///
+/// This is the same as [`AllocSerializer`](crate::ser::serializers::AllocSerializer) except that
+/// it writes to a [`CompressWriter`].
+pub type CompressSerializer<W, const N: usize> = CompositeSerializer<
+    WriteSerializer<CompressWriter<W>>,
+    FallbackScratch<HeapScratch<N>, AllocScratch>,
+    SharedSerializeMap,
+>;
+
+/// Serializes the given value and returns the compressed archive.
+///
+/// This is the compressing equivalent of [`to_bytes`](crate::to_bytes). Use [`decompress_bytes`]
+/// to get the archive back.
+#[inline]
+pub fn to_compressed_bytes<T, const N: usize>(
+    value: &T,
+) -> Result<Vec<u8>, <CompressSerializer<Vec<u8>, N> as Fallible>::Error>
+where
+    T: Serialize<CompressSerializer<Vec<u8>, N>>,
+{
+    let mut serializer = CompressSerializer::<Vec<u8>, N>::new(
+        WriteSerializer::new(CompressWriter::new(Vec::new())),
+        Default::default(),
+        Default::default(),
+    );
+    serializer.serialize_value(value)?;
+    serializer
+        .into_serializer()
+        .into_inner()
+        .finish()
+        .map_err(CompositeSerializerError::SerializerError)
+}
+
+/// Decompresses an archive from the given reader.
+#[inline]
+pub fn decompress_from<R: io::Read>(reader: R) -> io::Result<AlignedVec> {
+    let mut bytes = AlignedVec::new();
+    io::copy(&mut FrameDecoder::new(reader), &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decompresses an archive from the given bytes.
+#[inline]
+pub fn decompress_bytes(bytes: &[u8]) -> io::Result<AlignedVec> {
+    decompress_from(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decompress_bytes, decompress_from, to_compressed_bytes, CompressWriter};
+    use crate::{
+        archived_root,
+        ser::{serializers::WriteSerializer, Serializer},
+        Archive, Deserialize, Infallible, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(crate = "crate")]
+    struct Test {
+        name: String,
+        values: Vec<u64>,
+    }
+
+    #[test]
+    fn compressed_round_trip() {
+        let value = Test {
+            name: "compressed".to_string(),
+            values: (0..256).map(|i| i % 4).collect(),
+        };
+
+        let compressed = to_compressed_bytes::<_, 256>(&value).unwrap();
+        let uncompressed = crate::to_bytes::<_, 256>(&value).unwrap();
+        assert!(compressed.len() < uncompressed.len());
+
+        let bytes = decompress_bytes(&compressed).unwrap();
+        assert_eq!(bytes.as_slice(), uncompressed.as_slice());
+        let archived = unsafe { archived_root::<Test>(&bytes) };
+        assert_eq!(archived.name, "compressed");
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        assert!(decompress_bytes(&compressed[..compressed.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn compress_writer() {
+        let mut serializer = WriteSerializer::new(CompressWriter::new(Vec::new()));
+        let pos = serializer.serialize_value(&[1u32, 2, 3, 4]).unwrap();
+        let end = serializer.pos();
+        let compressed = serializer.into_inner().finish().unwrap();
+
+        let bytes = decompress_from(compressed.as_slice()).unwrap();
+        assert_eq!(bytes.len(), end);
+        let archived = unsafe { crate::archived_value::<[u32; 4]>(&bytes, pos) };
+        assert_eq!(archived, &[1, 2, 3, 4]);
+    }
+}
@@ -34,24 +34,27 @@ const _: () = {
     impl !ArchiveCopy for i16 {}
     impl !ArchiveCopy for i32 {}
     impl !ArchiveCopy for i64 {}
-    impl !ArchiveCopy for i128 {}
     impl !ArchiveCopy for u16 {}
     impl !ArchiveCopy for u32 {}
     impl !ArchiveCopy for u64 {}
-    impl !ArchiveCopy for u128 {}
     impl !ArchiveCopy for f32 {}
     impl !ArchiveCopy for f64 {}
     impl !ArchiveCopy for char {}
     impl !ArchiveCopy for NonZeroI16 {}
     impl !ArchiveCopy for NonZeroI32 {}
     impl !ArchiveCopy for NonZeroI64 {}
-    impl !ArchiveCopy for NonZeroI128 {}
     impl !ArchiveCopy for NonZeroU16 {}
     impl !ArchiveCopy for NonZeroU32 {}
     impl !ArchiveCopy for NonZeroU64 {}
-    impl !ArchiveCopy for NonZeroU128 {}
 };
 
+// 128-bit integers are never ArchiveCopy because they are archived with a fixed alignment which may
+// not match the target
+impl !ArchiveCopy for i128 {}
+impl !ArchiveCopy for u128 {}
+impl !ArchiveCopy for NonZeroI128 {}
+impl !ArchiveCopy for NonZeroU128 {}
+
 // Pointer-sized integers are not ArchiveCopy if the target pointer width does not match the archive
 // pointer width
 #[cfg(any(
@@ -117,22 +120,18 @@ const _: () = {
     unsafe impl ArchiveCopySafe for i16 {}
     unsafe impl ArchiveCopySafe for i32 {}
     unsafe impl ArchiveCopySafe for i64 {}
-    unsafe impl ArchiveCopySafe for i128 {}
     unsafe impl ArchiveCopySafe for u16 {}
     unsafe impl ArchiveCopySafe for u32 {}
     unsafe impl ArchiveCopySafe for u64 {}
-    unsafe impl ArchiveCopySafe for u128 {}
     unsafe impl ArchiveCopySafe for f32 {}
     unsafe impl ArchiveCopySafe for f64 {}
     unsafe impl ArchiveCopySafe for char {}
     unsafe impl ArchiveCopySafe for NonZeroI16 {}
     unsafe impl ArchiveCopySafe for NonZeroI32 {}
     unsafe impl ArchiveCopySafe for NonZeroI64 {}
-    unsafe impl ArchiveCopySafe for NonZeroI128 {}
     unsafe impl ArchiveCopySafe for NonZeroU16 {}
     unsafe impl ArchiveCopySafe for NonZeroU32 {}
     unsafe impl ArchiveCopySafe for NonZeroU64 {}
-    unsafe impl ArchiveCopySafe for NonZeroU128 {}
 };
 
 // Pointer-sized integers are ArchiveCopySafe if the target pointer width matches the archive
@@ -0,0 +1,46 @@
+//! A deserializer that allocates deserialized values in an arena.
+
+use crate::{de::BumpArena, Fallible};
+use bumpalo::Bump;
+
+/// A deserializer that allocates deserialized values in a [`Bump`] arena.
+///
+/// Values deserialized into the `bumpalo` versions of `Box`, `Vec`, and `String` will be allocated
+/// in the arena, so the entire deserialized object graph can be freed at once.
+///
+/// # Examples
+/// ```
+/// use bumpalo::{collections::Vec, Bump};
+/// use rkyv::{archived_root, de::deserializers::BumpDeserializer, Deserialize};
+///
+/// let bytes = rkyv::to_bytes::<_, 256>(&std::vec![1u32, 2, 3]).unwrap();
+/// let archived = unsafe { archived_root::<std::vec::Vec<u32>>(&bytes) };
+///
+/// let bump = Bump::new();
+/// let mut deserializer = BumpDeserializer::new(&bump);
+/// let deserialized: Vec<u32> = archived.deserialize(&mut deserializer).unwrap();
+/// assert_eq!(deserialized.as_slice(), &[1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct BumpDeserializer<'a> {
+    bump: &'a Bump,
+}
+
+impl<'a> BumpDeserializer<'a> {
+    /// Creates a new deserializer that allocates in the given arena.
+    #[inline]
+    pub fn new(bump: &'a Bump) -> Self {
+        Self { bump }
+    }
+}
+
+impl Fallible for BumpDeserializer<'_> {
+    type Error = core::convert::Infallible;
+}
+
+impl<'a> BumpArena<'a> for BumpDeserializer<'a> {
+    #[inline]
+    fn bump(&self) -> &'a Bump {
+        self.bump
+    }
+}
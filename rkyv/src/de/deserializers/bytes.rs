@@ -0,0 +1,38 @@
+//! A deserializer that shares a backing buffer with the values it deserializes.
+
+use crate::{de::BackingBytes, Fallible};
+use bytes::Bytes;
+
+/// A deserializer that holds the shared buffer an archive is stored in.
+///
+/// Values deserialized with the [`SharedBytes`](crate::with::SharedBytes) wrapper will point into
+/// this buffer instead of copying their contents.
+#[derive(Debug)]
+pub struct BytesDeserializer {
+    bytes: Bytes,
+}
+
+impl BytesDeserializer {
+    /// Creates a new deserializer from the buffer containing the archive.
+    #[inline]
+    pub fn new(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+
+    /// Consumes the deserializer and returns the backing buffer.
+    #[inline]
+    pub fn into_inner(self) -> Bytes {
+        self.bytes
+    }
+}
+
+impl Fallible for BytesDeserializer {
+    type Error = core::convert::Infallible;
+}
+
+impl BackingBytes for BytesDeserializer {
+    #[inline]
+    fn backing_bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+}
@@ -2,7 +2,17 @@
 
 #[cfg(feature = "alloc")]
 mod alloc;
+#[cfg(feature = "bumpalo")]
+mod bumpalo;
+#[cfg(feature = "bytes")]
+mod bytes;
 
 #[doc(inline)]
 #[cfg(feature = "alloc")]
 pub use self::alloc::*;
+#[doc(inline)]
+#[cfg(feature = "bumpalo")]
+pub use self::bumpalo::*;
+#[doc(inline)]
+#[cfg(feature = "bytes")]
+pub use self::bytes::*;
@@ -3,11 +3,18 @@
 pub mod deserializers;
 
 #[cfg(feature = "alloc")]
-use crate::{ArchiveUnsized, DeserializeUnsized, Fallible};
+use crate::{ArchiveUnsized, DeserializeUnsized};
+use crate::{Deserialize, Fallible};
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 use ::alloc::boxed::Box;
 #[cfg(feature = "alloc")]
 use ::core::alloc::Layout;
+#[cfg(feature = "arrayvec")]
+use ::core::fmt;
+use ::core::{
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+};
 
 /// A deserializable shared pointer type.
 #[cfg(feature = "alloc")]
@@ -65,3 +72,199 @@ pub trait SharedDeserializeRegistry: Fallible {
         }
     }
 }
+
+/// A deserializer that can provide the shared buffer that an archive is stored in.
+///
+/// This trait is required to deserialize [`Bytes`](::bytes::Bytes) with the
+/// [`SharedBytes`](crate::with::SharedBytes) wrapper.
+#[cfg(feature = "bytes")]
+pub trait BackingBytes: Fallible {
+    /// Returns the buffer containing the archive being deserialized.
+    fn backing_bytes(&self) -> &::bytes::Bytes;
+}
+
+/// A deserializer that can provide an arena to allocate deserialized values in.
+///
+/// This trait is required to deserialize the [`bumpalo`](::bumpalo) versions of `Box`, `Vec`, and
+/// `String`. Every value deserialized into the arena is freed at once when the arena is reset or
+/// dropped.
+#[cfg(feature = "bumpalo")]
+pub trait BumpArena<'a>: Fallible {
+    /// Returns the arena to allocate deserialized values in.
+    fn bump(&self) -> &'a ::bumpalo::Bump;
+}
+
+/// An error resulting from deserializing an archived collection into a type with a fixed capacity
+/// that's too small to hold it.
+///
+/// Deserializing [`ArrayVec`](::arrayvec::ArrayVec) and [`ArrayString`](::arrayvec::ArrayString)
+/// requires `<D as Fallible>::Error: From<CapacityError>`.
+#[cfg(feature = "arrayvec")]
+#[derive(Debug)]
+pub struct CapacityError {
+    /// The length of the archived collection
+    pub len: usize,
+    /// The capacity of the type being deserialized
+    pub capacity: usize,
+}
+
+#[cfg(feature = "arrayvec")]
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "archived length {} exceeds the capacity {}",
+            self.len, self.capacity
+        )
+    }
+}
+
+#[cfg(all(feature = "arrayvec", feature = "std"))]
+impl std::error::Error for CapacityError {}
+
+/// An item yielded by an iterator over an archived collection that can be deserialized.
+///
+/// This is implemented for references to archived values like the items of
+/// [`ArchivedVec`](crate::vec::ArchivedVec), and for pairs of references like the entries of
+/// [`ArchivedHashMap`](crate::collections::ArchivedHashMap).
+pub trait DeserializeItem<T, D: Fallible + ?Sized> {
+    /// Deserializes the item using the given deserializer.
+    fn deserialize_item(self, deserializer: &mut D) -> Result<T, D::Error>;
+}
+
+impl<A: Deserialize<T, D> + ?Sized, T, D: Fallible + ?Sized> DeserializeItem<T, D> for &'_ A {
+    #[inline]
+    fn deserialize_item(self, deserializer: &mut D) -> Result<T, D::Error> {
+        self.deserialize(deserializer)
+    }
+}
+
+impl<K, V, TK, TV, D> DeserializeItem<(TK, TV), D> for (&'_ K, &'_ V)
+where
+    K: Deserialize<TK, D> + ?Sized,
+    V: Deserialize<TV, D> + ?Sized,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_item(self, deserializer: &mut D) -> Result<(TK, TV), D::Error> {
+        Ok((
+            self.0.deserialize(deserializer)?,
+            self.1.deserialize(deserializer)?,
+        ))
+    }
+}
+
+/// Extension methods for deserializing the items of iterators over archived collections.
+///
+/// This is implemented for every iterator, and makes it easy to deserialize only some of the
+/// items of an archived collection without deserializing the whole collection first.
+///
+/// # Examples
+/// ```
+/// use rkyv::{archived_root, de::DeserializeIterator, Infallible};
+/// use std::collections::HashMap;
+///
+/// let value = (0..10).map(|i| i.to_string()).collect::<Vec<_>>();
+/// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+/// let archived = unsafe { archived_root::<Vec<String>>(&bytes) };
+///
+/// let odd = archived
+///     .iter()
+///     .skip(1)
+///     .step_by(2)
+///     .deserialize_collect::<Vec<String>, _, _>(&mut Infallible)
+///     .unwrap();
+/// assert_eq!(odd, ["1", "3", "5", "7", "9"]);
+///
+/// let mut map = HashMap::new();
+/// map.insert("one".to_string(), 1);
+/// map.insert("two".to_string(), 2);
+/// let bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+/// let archived = unsafe { archived_root::<HashMap<String, i32>>(&bytes) };
+///
+/// let small: HashMap<String, i32> = archived
+///     .iter()
+///     .filter(|(_, v)| **v < 2)
+///     .deserialize_collect(&mut Infallible)
+///     .unwrap();
+/// assert_eq!(small.len(), 1);
+/// assert_eq!(small["one"], 1);
+/// ```
+pub trait DeserializeIterator: Iterator + Sized {
+    /// Returns an iterator that deserializes each item of this iterator.
+    #[inline]
+    fn deserialize_each<T, D>(self, deserializer: &mut D) -> DeserializeEach<'_, Self, T, D>
+    where
+        Self::Item: DeserializeItem<T, D>,
+        D: Fallible + ?Sized,
+    {
+        DeserializeEach {
+            iter: self,
+            deserializer,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Deserializes each item of this iterator and collects them into a collection.
+    ///
+    /// Deserialization stops at the first item that fails to deserialize, and its error is
+    /// returned.
+    #[inline]
+    fn deserialize_collect<C, T, D>(self, deserializer: &mut D) -> Result<C, D::Error>
+    where
+        Self::Item: DeserializeItem<T, D>,
+        C: FromIterator<T>,
+        D: Fallible + ?Sized,
+    {
+        self.deserialize_each(deserializer).collect()
+    }
+}
+
+impl<I: Iterator> DeserializeIterator for I {}
+
+/// An iterator that deserializes the items of another iterator.
+///
+/// This is returned by [`DeserializeIterator::deserialize_each`].
+pub struct DeserializeEach<'d, I, T, D: ?Sized> {
+    iter: I,
+    deserializer: &'d mut D,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<I, T, D> Iterator for DeserializeEach<'_, I, T, D>
+where
+    I: Iterator,
+    I::Item: DeserializeItem<T, D>,
+    D: Fallible + ?Sized,
+{
+    type Item = Result<T, D::Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let deserializer = &mut *self.deserializer;
+        self.iter
+            .next()
+            .map(|item| item.deserialize_item(deserializer))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, T, D> ExactSizeIterator for DeserializeEach<'_, I, T, D>
+where
+    I: ExactSizeIterator,
+    I::Item: DeserializeItem<T, D>,
+    D: Fallible + ?Sized,
+{
+}
+
+impl<I, T, D> FusedIterator for DeserializeEach<'_, I, T, D>
+where
+    I: FusedIterator,
+    I::Item: DeserializeItem<T, D>,
+    D: Fallible + ?Sized,
+{
+}
@@ -0,0 +1,213 @@
+//! Comparing archived values field by field.
+//!
+//! [`diff`] walks two archived values of the same type using the [`Schema`] of the type and
+//! returns a [`Difference`] for every place where they disagree, with the path to the value that
+//! differs. This makes it easy to find out why two archives that should be equal aren't, for
+//! example when serialization is nondeterministic or a migration changed more than it should have:
+//!
+//! ```
+//! use rkyv::{diff::diff, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(schema)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(schema)]
+//! struct Path {
+//!     name: String,
+//!     points: Vec<Point>,
+//!     closed: Option<bool>,
+//! }
+//!
+//! let a = Path {
+//!     name: "square".to_string(),
+//!     points: vec![Point { x: 0, y: 0 }, Point { x: 1, y: 0 }],
+//!     closed: Some(true),
+//! };
+//! let b = Path {
+//!     name: "square".to_string(),
+//!     points: vec![Point { x: 0, y: 0 }, Point { x: 1, y: 1 }],
+//!     closed: None,
+//! };
+//! let a = rkyv::to_bytes::<_, 256>(&a).unwrap();
+//! let b = rkyv::to_bytes::<_, 256>(&b).unwrap();
+//!
+//! let differences = diff::<Path>(
+//!     unsafe { rkyv::archived_root::<Path>(&a) },
+//!     unsafe { rkyv::archived_root::<Path>(&b) },
+//! );
+//! let paths = differences.iter().map(|d| d.path.as_str()).collect::<Vec<_>>();
+//! assert_eq!(paths, [".points[1].y", ".closed"]);
+//! assert_eq!(differences[1].to_string(), ".closed: Some(true) != None");
+//! ```
+//!
+//! Paths start with `.` for the root value, followed by `.name` for each field and `[index]` for
+//! each element of an array or vector. Boxes and `Some` options are followed without adding to the
+//! path. The comparison doesn't descend any further into values that differ in kind: enums with
+//! different variants, options where only one is `Some`, and vectors with different lengths are
+//! reported as a single difference.
+//!
+//! Floating-point numbers are compared by their bits, so two `NaN`s with the same bits are equal
+//! and `0.0` and `-0.0` are different. Opaque values are compared by their raw bytes.
+
+use crate::{
+    schema::{ArchiveSchema, ArchivedValue, Schema},
+    Archive,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+/// A place where two archived values differ.
+#[derive(Clone, Debug)]
+pub struct Difference<'a> {
+    /// The path to the values that differ, starting from the root.
+    pub path: String,
+    /// The value in the first archive.
+    pub left: ArchivedValue<'a>,
+    /// The value in the second archive.
+    pub right: ArchivedValue<'a>,
+}
+
+impl fmt::Display for Difference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.left.len(), self.right.len()) {
+            (Some(left), Some(right)) => {
+                write!(f, "{}: {} elements != {} elements", self.path, left, right)
+            }
+            _ => write!(f, "{}: {:?} != {:?}", self.path, self.left, self.right),
+        }
+    }
+}
+
+/// Returns the differences between two archived values of the same type.
+///
+/// The differences are returned in the order the values are walked, which is the order the fields
+/// of structs are declared and the order of the elements of arrays and vectors. If the values are
+/// equal, no differences are returned.
+#[inline]
+pub fn diff<'a, T: Archive + ArchiveSchema + ?Sized>(
+    left: &'a T::Archived,
+    right: &'a T::Archived,
+) -> Vec<Difference<'a>> {
+    unsafe {
+        diff_values(
+            ArchivedValue::new((left as *const T::Archived).cast(), &T::SCHEMA),
+            ArchivedValue::new((right as *const T::Archived).cast(), &T::SCHEMA),
+        )
+    }
+}
+
+/// Returns the differences between two archived values.
+///
+/// If the values have different schemas, they are reported as a single difference at the root.
+pub fn diff_values<'a>(left: ArchivedValue<'a>, right: ArchivedValue<'a>) -> Vec<Difference<'a>> {
+    let mut differences = Vec::new();
+    if left.schema() == right.schema() {
+        walk(left, right, &mut String::new(), &mut differences);
+    } else {
+        differences.push(Difference {
+            path: String::from("."),
+            left,
+            right,
+        });
+    }
+    differences
+}
+
+fn walk<'a>(
+    left: ArchivedValue<'a>,
+    right: ArchivedValue<'a>,
+    path: &mut String,
+    differences: &mut Vec<Difference<'a>>,
+) {
+    let equal = match *left.schema() {
+        Schema::Unit => true,
+        Schema::Bool => left.as_bool() == right.as_bool(),
+        Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 | Schema::I128 => {
+            left.as_i128() == right.as_i128()
+        }
+        Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 | Schema::U128 => {
+            left.as_u128() == right.as_u128()
+        }
+        Schema::F32 | Schema::F64 => {
+            left.as_f64().map(f64::to_bits) == right.as_f64().map(f64::to_bits)
+        }
+        Schema::Char => left.as_char() == right.as_char(),
+        Schema::String => left.as_str() == right.as_str(),
+        Schema::Array { .. } | Schema::Vec { .. } => {
+            let len = left.len().unwrap();
+            if len == right.len().unwrap() {
+                for i in 0..len {
+                    let truncate = path.len();
+                    write!(path, "[{}]", i).unwrap();
+                    walk(
+                        left.get(i).unwrap(),
+                        right.get(i).unwrap(),
+                        path,
+                        differences,
+                    );
+                    path.truncate(truncate);
+                }
+                true
+            } else {
+                false
+            }
+        }
+        Schema::Box { .. } => {
+            walk(
+                left.inner().unwrap(),
+                right.inner().unwrap(),
+                path,
+                differences,
+            );
+            true
+        }
+        Schema::Option { .. } => match (left.inner(), right.inner()) {
+            (None, None) => true,
+            (Some(left), Some(right)) => {
+                walk(left, right, path, differences);
+                true
+            }
+            _ => false,
+        },
+        Schema::Struct { .. } | Schema::Enum { .. } => {
+            let same_variant = match *left.schema() {
+                Schema::Enum { .. } => match (left.variant(), right.variant()) {
+                    (Some(left), Some(right)) => left.tag == right.tag,
+                    _ => false,
+                },
+                _ => true,
+            };
+            if same_variant {
+                for ((name, left), (_, right)) in
+                    left.fields().unwrap().zip(right.fields().unwrap())
+                {
+                    let truncate = path.len();
+                    path.push('.');
+                    path.push_str(name);
+                    walk(left, right, path, differences);
+                    path.truncate(truncate);
+                }
+            }
+            same_variant
+        }
+        Schema::Opaque { size, .. } => unsafe {
+            core::slice::from_raw_parts(left.as_ptr(), size)
+                == core::slice::from_raw_parts(right.as_ptr(), size)
+        },
+    };
+    if !equal {
+        // Paths always start with a `.`, even at the root and for elements of a root array
+        let path = if path.starts_with('.') {
+            path.clone()
+        } else {
+            format!(".{}", path)
+        };
+        differences.push(Difference { path, left, right });
+    }
+}
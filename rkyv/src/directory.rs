@@ -0,0 +1,359 @@
+//! Directories of named roots for archives with multiple roots.
+//!
+//! A single buffer can hold several independently-serialized values. A [`Directory`] records the
+//! position of each of those roots under a name, and is then serialized as the root of the whole
+//! archive. Readers access the [`ArchivedDirectory`] at the root of the buffer and use it to find
+//! each value by name or by its index in the directory.
+//!
+//! Directories are searched linearly, so they work best with a small number of roots.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     archived_root,
+//!     directory::Directory,
+//!     ser::{serializers::AllocSerializer, Serializer},
+//! };
+//!
+//! let mut serializer = AllocSerializer::<256>::default();
+//! let mut directory = Directory::new();
+//! directory
+//!     .serialize_root("header", &"version 1".to_string(), &mut serializer)
+//!     .unwrap();
+//! directory
+//!     .serialize_root("payload", &vec![1u8, 2, 3, 4], &mut serializer)
+//!     .unwrap();
+//! serializer.serialize_value(&directory).unwrap();
+//! let bytes = serializer.into_serializer().into_inner();
+//!
+//! let directory = unsafe { archived_root::<Directory>(&bytes) };
+//! let header = unsafe { directory.root::<String>(&bytes, "header") }.unwrap();
+//! assert_eq!(header, "version 1");
+//! let payload = unsafe { directory.root_at::<Vec<u8>>(&bytes, 1) }.unwrap();
+//! assert_eq!(payload.as_slice(), &[1, 2, 3, 4]);
+//! ```
+
+#[cfg(feature = "alloc")]
+use crate::vec::VecResolver;
+use crate::{archived_value, string::ArchivedString, vec::ArchivedVec, Archive, Archived};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// An archived entry in a directory.
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedDirectoryEntry {
+    name: ArchivedString,
+    pos: Archived<usize>,
+}
+
+impl ArchivedDirectoryEntry {
+    /// Returns the name of the root.
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns the position of the root within the archive.
+    #[inline]
+    pub fn pos(&self) -> usize {
+        from_archived!(self.pos) as usize
+    }
+}
+
+impl fmt::Debug for ArchivedDirectoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedDirectoryEntry")
+            .field("name", &self.name())
+            .field("pos", &self.pos())
+            .finish()
+    }
+}
+
+/// An archived [`Directory`].
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedDirectory {
+    entries: ArchivedVec<ArchivedDirectoryEntry>,
+}
+
+impl ArchivedDirectory {
+    /// Returns the number of roots in the directory.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the directory contains no roots.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entries of the directory in the order they were inserted.
+    #[inline]
+    pub fn entries(&self) -> &[ArchivedDirectoryEntry] {
+        self.entries.as_slice()
+    }
+
+    /// Returns the index of the root with the given name.
+    #[inline]
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|e| e.name() == name)
+    }
+
+    /// Returns the position of the root with the given name.
+    #[inline]
+    pub fn pos(&self, name: &str) -> Option<usize> {
+        self.index_of(name).map(|i| self.entries[i].pos())
+    }
+
+    /// Returns the position of the root at the given index.
+    #[inline]
+    pub fn pos_at(&self, index: usize) -> Option<usize> {
+        self.entries.get(index).map(ArchivedDirectoryEntry::pos)
+    }
+
+    /// Gets the root with the given name.
+    ///
+    /// # Safety
+    ///
+    /// - `bytes` must be the archive that this directory was read from
+    /// - The root with the given name, if any, must be an archived `T`
+    #[inline]
+    pub unsafe fn root<'a, T: Archive + ?Sized>(
+        &self,
+        bytes: &'a [u8],
+        name: &str,
+    ) -> Option<&'a T::Archived> {
+        self.pos(name).map(|pos| archived_value::<T>(bytes, pos))
+    }
+
+    /// Gets the root at the given index.
+    ///
+    /// # Safety
+    ///
+    /// - `bytes` must be the archive that this directory was read from
+    /// - The root at the given index, if any, must be an archived `T`
+    #[inline]
+    pub unsafe fn root_at<'a, T: Archive + ?Sized>(
+        &self,
+        bytes: &'a [u8],
+        index: usize,
+    ) -> Option<&'a T::Archived> {
+        self.pos_at(index)
+            .map(|pos| archived_value::<T>(bytes, pos))
+    }
+}
+
+impl fmt::Debug for ArchivedDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|e| (e.name(), e.pos())))
+            .finish()
+    }
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::{validators::DefaultValidator, CheckTypeError};
+    use bytecheck::CheckBytes;
+
+    type CheckRootResult<'a, T> = Option<
+        Result<
+            &'a <T as Archive>::Archived,
+            CheckTypeError<<T as Archive>::Archived, DefaultValidator<'a>>,
+        >,
+    >;
+
+    impl ArchivedDirectory {
+        /// Checks and gets the root with the given name.
+        ///
+        /// Returns `None` if there is no root with the given name. `bytes` should be the archive
+        /// that this directory was read from.
+        #[inline]
+        pub fn check_root<'a, T: Archive>(
+            &self,
+            bytes: &'a [u8],
+            name: &str,
+        ) -> CheckRootResult<'a, T>
+        where
+            T::Archived: CheckBytes<DefaultValidator<'a>>,
+        {
+            self.pos(name)
+                .map(|pos| crate::check_archived_value::<T>(bytes, pos))
+        }
+
+        /// Checks and gets the root at the given index.
+        ///
+        /// Returns `None` if the index is out of bounds. `bytes` should be the archive that this
+        /// directory was read from.
+        #[inline]
+        pub fn check_root_at<'a, T: Archive>(
+            &self,
+            bytes: &'a [u8],
+            index: usize,
+        ) -> CheckRootResult<'a, T>
+        where
+            T::Archived: CheckBytes<DefaultValidator<'a>>,
+        {
+            self.pos_at(index)
+                .map(|pos| crate::check_archived_value::<T>(bytes, pos))
+        }
+    }
+};
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::{
+        ser::{ScratchSpace, Serializer},
+        string::StringResolver,
+        Deserialize, Fallible, Serialize,
+    };
+
+    impl Directory {
+        /// Creates a new, empty directory.
+        #[inline]
+        pub fn new() -> Self {
+            Self {
+                entries: Vec::new(),
+            }
+        }
+
+        /// Returns the number of roots in the directory.
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        /// Returns whether the directory contains no roots.
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        /// Returns the position of the root with the given name.
+        #[inline]
+        pub fn pos(&self, name: &str) -> Option<usize> {
+            self.entries
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, pos)| *pos)
+        }
+
+        /// Adds a root at the given position to the directory.
+        ///
+        /// If the directory already has a root with the given name, its position is replaced and
+        /// the old position is returned.
+        pub fn insert(&mut self, name: &str, pos: usize) -> Option<usize> {
+            if let Some((_, old)) = self.entries.iter_mut().find(|(n, _)| n == name) {
+                Some(core::mem::replace(old, pos))
+            } else {
+                self.entries.push((name.to_string(), pos));
+                None
+            }
+        }
+
+        /// Serializes a value with the given serializer and adds it to the directory as a root.
+        ///
+        /// Returns the position of the serialized root.
+        #[inline]
+        pub fn serialize_root<T, S>(
+            &mut self,
+            name: &str,
+            value: &T,
+            serializer: &mut S,
+        ) -> Result<usize, S::Error>
+        where
+            T: Serialize<S>,
+            S: Serializer + ?Sized,
+        {
+            let pos = serializer.serialize_value(value)?;
+            self.insert(name, pos);
+            Ok(pos)
+        }
+    }
+
+    impl Archive for Directory {
+        type Archived = ArchivedDirectory;
+        type Resolver = DirectoryResolver;
+
+        #[inline]
+        unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+            let (fp, fo) = out_field!(out.entries);
+            ArchivedVec::resolve_from_len(self.entries.len(), pos + fp, resolver.entries, fo);
+        }
+    }
+
+    impl<S: ScratchSpace + Serializer + ?Sized> Serialize<S> for Directory {
+        #[inline]
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            let entries = self
+                .entries
+                .iter()
+                .map(|(name, pos)| Entry { name, pos: *pos });
+            Ok(DirectoryResolver {
+                entries: ArchivedVec::serialize_from_iter::<Entry, _, _, _>(entries, serializer)?,
+            })
+        }
+    }
+
+    impl<D: Fallible + ?Sized> Deserialize<Directory, D> for ArchivedDirectory {
+        #[inline]
+        fn deserialize(&self, _: &mut D) -> Result<Directory, D::Error> {
+            Ok(Directory {
+                entries: self
+                    .entries
+                    .iter()
+                    .map(|e| (e.name().to_string(), e.pos()))
+                    .collect(),
+            })
+        }
+    }
+
+    // A borrowed directory entry used to resolve archived entries in place
+    struct Entry<'a> {
+        name: &'a str,
+        pos: usize,
+    }
+
+    impl Archive for Entry<'_> {
+        type Archived = ArchivedDirectoryEntry;
+        type Resolver = StringResolver;
+
+        #[inline]
+        unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+            let (fp, fo) = out_field!(out.name);
+            ArchivedString::resolve_from_str(self.name, pos + fp, resolver, fo);
+            let (fp, fo) = out_field!(out.pos);
+            self.pos.resolve(pos + fp, (), fo);
+        }
+    }
+
+    impl<S: Serializer + ?Sized> Serialize<S> for Entry<'_> {
+        #[inline]
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedString::serialize_from_str(self.name, serializer)
+        }
+    }
+};
+
+/// A directory of named roots in an archive.
+///
+/// See the [module-level documentation](self) for more information.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Directory {
+    entries: Vec<(String, usize)>,
+}
+
+/// The resolver for a [`Directory`].
+#[cfg(feature = "alloc")]
+pub struct DirectoryResolver {
+    entries: VecResolver,
+}
@@ -0,0 +1,247 @@
+//! Encrypted archives using [XChaCha20-Poly1305](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-xchacha).
+//!
+//! Archives are accessed directly from their bytes, so they can't be accessed while they are
+//! encrypted. This module provides functions that encrypt an archive into an envelope and decrypt
+//! the envelope into an [`AlignedVec`] so it can be accessed as usual. The archive is decrypted to
+//! exactly the bytes that were serialized, so its root stays at the end of the buffer and no other
+//! framing is needed to find it.
+//!
+//! An envelope is the 24-byte nonce, followed by the encrypted archive, followed by the 16-byte
+//! authentication tag. Decrypting checks the tag, so an envelope that was modified or encrypted with
+//! a different key fails to decrypt instead of producing a corrupted archive.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     archived_root,
+//!     encryption::{decrypt_bytes, to_encrypted_bytes, KEY_SIZE},
+//! };
+//!
+//! let key = [7u8; KEY_SIZE];
+//! let value = vec!["secret".to_string(), "plans".to_string()];
+//! let envelope = to_encrypted_bytes::<_, 256>(&value, &key).unwrap();
+//!
+//! let bytes = decrypt_bytes(&envelope, &key).unwrap();
+//! let archived = unsafe { archived_root::<Vec<String>>(&bytes) };
+//! assert_eq!(archived[1], "plans");
+//!
+//! assert!(decrypt_bytes(&envelope, &[8u8; KEY_SIZE]).is_err());
+//! ```
+
+use crate::{ser::serializers::AllocSerializer, AlignedVec, Fallible, Serialize};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use chacha20poly1305::{
+    aead::{AeadCore, AeadInPlace, KeyInit, OsRng},
+    Key, Tag, XChaCha20Poly1305, XNonce,
+};
+use core::fmt;
+
+/// The size of an encryption key in bytes.
+pub const KEY_SIZE: usize = 32;
+/// The size of the nonce at the start of an envelope in bytes.
+pub const NONCE_SIZE: usize = 24;
+/// The size of the authentication tag at the end of an envelope in bytes.
+pub const TAG_SIZE: usize = 16;
+/// The number of bytes an envelope adds to the archive it contains.
+pub const OVERHEAD: usize = NONCE_SIZE + TAG_SIZE;
+
+/// An error that occurred while encrypting or decrypting an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionError {
+    /// The archive was too long to encrypt
+    TooLong,
+    /// The envelope was too short to contain a nonce and an authentication tag
+    Truncated {
+        /// The length of the envelope
+        len: usize,
+    },
+    /// The envelope failed authentication because it was modified or the key was wrong
+    Authentication,
+}
+
+impl fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionError::TooLong => write!(f, "archive is too long to encrypt"),
+            EncryptionError::Truncated { len } => write!(
+                f,
+                "envelope of {} bytes is shorter than the {} bytes of its nonce and tag",
+                len, OVERHEAD,
+            ),
+            EncryptionError::Authentication => write!(f, "envelope failed authentication"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncryptionError {}
+
+/// An error that occurred while serializing a value and encrypting its archive.
+#[derive(Debug)]
+pub enum EncryptedSerializeError<E> {
+    /// An error occurred while serializing the value
+    Serialize(E),
+    /// An error occurred while encrypting the archive
+    Encrypt(EncryptionError),
+}
+
+impl<E: fmt::Display> fmt::Display for EncryptedSerializeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptedSerializeError::Serialize(e) => write!(f, "serialization error: {}", e),
+            EncryptedSerializeError::Encrypt(e) => write!(f, "encryption error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for EncryptedSerializeError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EncryptedSerializeError::Serialize(e) => Some(e as &dyn std::error::Error),
+            EncryptedSerializeError::Encrypt(e) => Some(e as &dyn std::error::Error),
+        }
+    }
+}
+
+/// Encrypts an archive into an envelope with a random nonce.
+#[inline]
+pub fn encrypt_bytes(archive: &[u8], key: &[u8; KEY_SIZE]) -> Result<Vec<u8>, EncryptionError> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    encrypt_bytes_with_nonce(archive, key, nonce.as_ref())
+}
+
+/// Encrypts an archive into an envelope with the given nonce.
+///
+/// A nonce must never be used to encrypt more than one archive with the same key, or the contents
+/// of both archives can be recovered without the key. Prefer [`encrypt_bytes`], which uses a random
+/// nonce, unless the nonce is known to be unique.
+pub fn encrypt_bytes_with_nonce(
+    archive: &[u8],
+    key: &[u8; KEY_SIZE],
+    nonce: &[u8; NONCE_SIZE],
+) -> Result<Vec<u8>, EncryptionError> {
+    let mut envelope = Vec::with_capacity(archive.len() + OVERHEAD);
+    envelope.extend_from_slice(nonce);
+    envelope.extend_from_slice(archive);
+
+    let tag = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt_in_place_detached(XNonce::from_slice(nonce), &[], &mut envelope[NONCE_SIZE..])
+        .map_err(|_| EncryptionError::TooLong)?;
+    envelope.extend_from_slice(&tag);
+    Ok(envelope)
+}
+
+/// Serializes the given value and returns the encrypted archive.
+///
+/// This is the encrypting equivalent of [`to_bytes`](crate::to_bytes). Use [`decrypt_bytes`] to get
+/// the archive back.
+#[inline]
+pub fn to_encrypted_bytes<T, const N: usize>(
+    value: &T,
+    key: &[u8; KEY_SIZE],
+) -> Result<Vec<u8>, EncryptedSerializeError<<AllocSerializer<N> as Fallible>::Error>>
+where
+    T: Serialize<AllocSerializer<N>>,
+{
+    let bytes = crate::to_bytes::<T, N>(value).map_err(EncryptedSerializeError::Serialize)?;
+    encrypt_bytes(&bytes, key).map_err(EncryptedSerializeError::Encrypt)
+}
+
+/// Decrypts an archive from the given envelope.
+pub fn decrypt_bytes(envelope: &[u8], key: &[u8; KEY_SIZE]) -> Result<AlignedVec, EncryptionError> {
+    if envelope.len() < OVERHEAD {
+        return Err(EncryptionError::Truncated {
+            len: envelope.len(),
+        });
+    }
+    let (nonce, rest) = envelope.split_at(NONCE_SIZE);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_SIZE);
+
+    let mut bytes = AlignedVec::with_capacity(ciphertext.len());
+    bytes.extend_from_slice(ciphertext);
+    XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt_in_place_detached(
+            XNonce::from_slice(nonce),
+            &[],
+            bytes.as_mut_slice(),
+            Tag::from_slice(tag),
+        )
+        .map_err(|_| EncryptionError::Authentication)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decrypt_bytes, encrypt_bytes, encrypt_bytes_with_nonce, to_encrypted_bytes,
+        EncryptionError, KEY_SIZE, NONCE_SIZE, OVERHEAD,
+    };
+    use crate::{archived_root, Archive, Deserialize, Infallible, Serialize};
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(crate = "crate")]
+    struct Test {
+        name: String,
+        values: Vec<u64>,
+    }
+
+    const KEY: [u8; KEY_SIZE] = [0x42; KEY_SIZE];
+
+    #[test]
+    fn encrypted_round_trip() {
+        let value = Test {
+            name: "encrypted".to_string(),
+            values: (0..64).collect(),
+        };
+
+        let envelope = to_encrypted_bytes::<_, 256>(&value, &KEY).unwrap();
+        let plain = crate::to_bytes::<_, 256>(&value).unwrap();
+        assert_eq!(envelope.len(), plain.len() + OVERHEAD);
+        assert!(!envelope
+            .windows(b"encrypted".len())
+            .any(|w| w == b"encrypted"));
+
+        let bytes = decrypt_bytes(&envelope, &KEY).unwrap();
+        assert_eq!(bytes.as_slice(), plain.as_slice());
+        let archived = unsafe { archived_root::<Test>(&bytes) };
+        assert_eq!(archived.name, "encrypted");
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Each envelope gets a fresh nonce
+        assert_ne!(encrypt_bytes(&plain, &KEY).unwrap(), envelope);
+    }
+
+    #[test]
+    fn decrypt_errors() {
+        let nonce = [1; NONCE_SIZE];
+        let envelope = encrypt_bytes_with_nonce(b"archive", &KEY, &nonce).unwrap();
+        assert_eq!(&envelope[..NONCE_SIZE], &nonce);
+        assert_eq!(
+            encrypt_bytes_with_nonce(b"archive", &KEY, &nonce).unwrap(),
+            envelope
+        );
+
+        assert_eq!(
+            decrypt_bytes(&envelope, &[0; KEY_SIZE]).unwrap_err(),
+            EncryptionError::Authentication,
+        );
+        let mut modified = envelope.clone();
+        modified[NONCE_SIZE] ^= 1;
+        assert_eq!(
+            decrypt_bytes(&modified, &KEY).unwrap_err(),
+            EncryptionError::Authentication,
+        );
+        assert_eq!(
+            decrypt_bytes(&envelope[..OVERHEAD - 1], &KEY).unwrap_err(),
+            EncryptionError::Truncated { len: OVERHEAD - 1 },
+        );
+
+        let empty = encrypt_bytes(&[], &KEY).unwrap();
+        assert_eq!(empty.len(), OVERHEAD);
+        assert!(decrypt_bytes(&empty, &KEY).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+//! Forward-compatible structs that can gain trailing fields without breaking old archives.
+//!
+//! Deriving `Archive` with `#[archive(extensible)]` stores the fields of a struct out of line and
+//! records how many bytes of fields were written. New fields can then be added to the end of the
+//! struct: archives written before the fields were added are still readable, and the missing
+//! fields are replaced with their default values when deserializing. Archives written after the
+//! fields were added can be also read by older versions of the struct, which ignore the extra
+//! fields.
+//!
+//! The archived type of an extensible struct has an accessor for each field, which returns `None`
+//! if the field is missing from the archive. Fields may only be added to the end of the struct,
+//! and existing fields may not be removed, reordered, or changed.
+//!
+//! With the `validation` feature, extensible structs can be checked by deriving `CheckBytes` for
+//! the archived type as usual. Only the fields that are present in the archive are checked.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, Archive, Deserialize, Infallible, Serialize};
+//!
+//! mod old {
+//!     use rkyv::{Archive, Serialize};
+//!
+//!     #[derive(Archive, Serialize)]
+//!     #[archive(extensible)]
+//!     pub struct Settings {
+//!         pub volume: u8,
+//!     }
+//! }
+//!
+//! #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+//! #[archive(extensible)]
+//! struct Settings {
+//!     volume: u8,
+//!     theme: String,
+//! }
+//!
+//! let bytes = rkyv::to_bytes::<_, 256>(&old::Settings { volume: 7 }).unwrap();
+//! let archived = unsafe { archived_root::<Settings>(&bytes) };
+//! assert_eq!(archived.volume(), Some(&7));
+//! assert!(archived.theme().is_none());
+//!
+//! let settings: Settings = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(
+//!     settings,
+//!     Settings {
+//!         volume: 7,
+//!         theme: String::new(),
+//!     }
+//! );
+//! ```
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{ser::Serializer, Archive, Archived, RawRelPtr};
+use core::{fmt, marker::PhantomData, mem, slice};
+
+/// A type that archives its fields out of line.
+///
+/// This is implemented by deriving `Archive` with `#[archive(extensible)]`.
+pub trait ArchiveFields: Archive<Resolver = ExtensibleResolver> {
+    /// The archived fields of the type.
+    ///
+    /// This must be `repr(C)`, so that adding trailing fields doesn't change the offsets of the
+    /// existing fields.
+    type Fields: ExtensibleFields;
+    /// The resolver for the archived fields of the type.
+    type FieldsResolver;
+
+    /// Creates the archived fields of the type from their resolver.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing the fields of `self`
+    unsafe fn resolve_fields(
+        &self,
+        pos: usize,
+        resolver: Self::FieldsResolver,
+        out: *mut Self::Fields,
+    );
+}
+
+/// The archived fields of an extensible struct.
+///
+/// This is implemented by deriving `Archive` with `#[archive(extensible)]`.
+///
+/// # Safety
+///
+/// `fields_len` must return the offset of the end of the last field of the type.
+pub unsafe trait ExtensibleFields {
+    /// Returns the number of bytes from the start of the fields to the end of the last field.
+    ///
+    /// This doesn't include any trailing padding, which fields that are added later may occupy.
+    fn fields_len() -> usize;
+}
+
+/// The archived fields of an extensible struct.
+///
+/// This stores a pointer to the fields and the number of bytes of fields that were archived.
+#[repr(C)]
+pub struct ArchivedFields<F> {
+    len: Archived<u32>,
+    ptr: RawRelPtr,
+    _phantom: PhantomData<F>,
+}
+
+impl<F> ArchivedFields<F> {
+    /// Returns the number of bytes of fields that were archived.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns whether no fields were archived.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the field at the given offset if it was archived.
+    ///
+    /// # Safety
+    ///
+    /// `offset` must be the offset of a field of type `T` in `F`.
+    #[inline]
+    pub unsafe fn field<T>(&self, offset: usize) -> Option<&T> {
+        if offset + mem::size_of::<T>() <= self.len() {
+            Some(&*self.ptr.as_ptr().cast::<u8>().add(offset).cast())
+        } else {
+            None
+        }
+    }
+
+    /// Resolves archived fields from the given parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing fields of type `F`
+    #[inline]
+    pub unsafe fn resolve_from_pos(pos: usize, resolver: ExtensibleResolver, out: *mut Self) {
+        let (_, fo) = out_field!(out.len);
+        fo.write(to_archived!(resolver.len as u32));
+        let (fp, fo) = out_field!(out.ptr);
+        RawRelPtr::emplace(pos + fp, resolver.pos, fo);
+    }
+}
+
+impl<F: ExtensibleFields> ArchivedFields<F> {
+    /// Returns whether all of the fields of `F` were archived.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.len() >= F::fields_len()
+    }
+
+    /// Returns the archived fields if all of them were archived.
+    #[inline]
+    pub fn get(&self) -> Option<&F> {
+        if self.is_complete() {
+            // SAFETY: All of the fields were archived, so the pointer points to a complete `F`.
+            // Writers always write the trailing padding of their fields, and writers with more
+            // fields have at least as much padding.
+            Some(unsafe { &*self.ptr.as_ptr().cast() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<F> fmt::Debug for ArchivedFields<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedFields")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// The resolver for an extensible struct.
+pub struct ExtensibleResolver {
+    pos: usize,
+    len: usize,
+}
+
+/// Writes the archived fields of the given value and returns a resolver for its archived type.
+#[inline]
+pub fn serialize_fields<T, S>(
+    value: &T,
+    resolver: T::FieldsResolver,
+    serializer: &mut S,
+) -> Result<ExtensibleResolver, S::Error>
+where
+    T: ArchiveFields + ?Sized,
+    S: Serializer + ?Sized,
+{
+    serializer.align_for::<T::Fields>()?;
+    let pos = serializer.pos();
+
+    let mut resolved = mem::MaybeUninit::<T::Fields>::uninit();
+    unsafe {
+        resolved.as_mut_ptr().write_bytes(0, 1);
+        value.resolve_fields(pos, resolver, resolved.as_mut_ptr());
+
+        let data = resolved.as_ptr().cast::<u8>();
+        serializer.write(slice::from_raw_parts(data, mem::size_of::<T::Fields>()))?;
+    }
+
+    Ok(ExtensibleResolver {
+        pos,
+        len: T::Fields::fields_len(),
+    })
+}
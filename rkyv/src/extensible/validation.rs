@@ -0,0 +1,99 @@
+//! Validation implementations for extensible structs.
+
+use super::{ArchivedFields, ExtensibleFields};
+use crate::{validation::ArchiveContext, Archived, Fallible, RawRelPtr};
+use bytecheck::{CheckBytes, Error, StructCheckError};
+use core::{alloc::Layout, fmt, mem, ptr};
+
+/// Archived fields that can be checked when only some of them were archived.
+///
+/// This is implemented by deriving `Archive` with `#[archive(extensible)]` when the
+/// `validation` feature is enabled.
+pub trait CheckFields<C: ?Sized> {
+    /// Checks the fields that end within the first `len` bytes of the given fields.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be aligned for `Self` and point to at least `len` bytes.
+    unsafe fn check_fields(
+        value: *const Self,
+        len: usize,
+        context: &mut C,
+    ) -> Result<(), StructCheckError>;
+}
+
+/// Errors that can occur while checking archived fields.
+#[derive(Debug)]
+pub enum ArchivedFieldsError<C> {
+    /// A field was invalid
+    CheckFieldsError(StructCheckError),
+    /// An error occurred from the validation context
+    ContextError(C),
+}
+
+impl<C: fmt::Display> fmt::Display for ArchivedFieldsError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchivedFieldsError::CheckFieldsError(e) => e.fmt(f),
+            ArchivedFieldsError::ContextError(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: std::error::Error + 'static> std::error::Error for ArchivedFieldsError<C> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArchivedFieldsError::CheckFieldsError(e) => Some(e as &dyn std::error::Error),
+            ArchivedFieldsError::ContextError(e) => Some(e as &dyn std::error::Error),
+        }
+    }
+}
+
+impl<F, C> CheckBytes<C> for ArchivedFields<F>
+where
+    F: ExtensibleFields + CheckFields<C>,
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error = ArchivedFieldsError<<C as Fallible>::Error>;
+
+    #[inline]
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        Archived::<u32>::check_bytes(ptr::addr_of!((*value).len), context).unwrap();
+        let rel_ptr = RawRelPtr::manual_check_bytes(ptr::addr_of!((*value).ptr), context).unwrap();
+
+        let len = from_archived!(*ptr::addr_of!((*value).len)) as usize;
+        // Complete fields are accessed as a whole `F`, including any trailing padding
+        let size = if len >= F::fields_len() {
+            len.max(mem::size_of::<F>())
+        } else {
+            len
+        };
+        let layout = Layout::from_size_align_unchecked(size, mem::align_of::<F>());
+
+        let data = context
+            .bounds_check_ptr(rel_ptr.base(), rel_ptr.offset())
+            .map_err(ArchivedFieldsError::ContextError)?;
+        context
+            .bounds_check_layout(data, &layout)
+            .map_err(ArchivedFieldsError::ContextError)?;
+        context
+            .bounds_check_subtree_ptr_layout(data, &layout)
+            .map_err(ArchivedFieldsError::ContextError)?;
+
+        let range = context
+            .push_prefix_subtree_range(data, data.add(size))
+            .map_err(ArchivedFieldsError::ContextError)?;
+        F::check_fields(data.cast(), len, context)
+            .map_err(ArchivedFieldsError::CheckFieldsError)?;
+        context
+            .pop_prefix_range(range)
+            .map_err(ArchivedFieldsError::ContextError)?;
+
+        Ok(&*value)
+    }
+}
@@ -1,13 +1,21 @@
 //! Archived versions of FFI types.
 
-use crate::{ser::Serializer, ArchiveUnsized, MetadataResolver, RelPtr, SerializeUnsized};
+use crate::{
+    ser::Serializer, ArchivePointee, ArchiveUnsized, Archived, MetadataResolver, RelPtr,
+    SerializeUnsized,
+};
 use core::{
     borrow::Borrow,
     cmp, fmt, hash,
     ops::{Deref, Index, RangeFull},
     pin::Pin,
+    str,
+};
+use ptr_meta::Pointee;
+use std::{
+    borrow::Cow,
+    ffi::{CStr, OsStr, OsString},
 };
-use std::ffi::CStr;
 
 /// An archived [`CString`](std::ffi::CString).
 ///
@@ -159,6 +167,161 @@ impl PartialOrd for ArchivedCString {
     }
 }
 
+/// An archived [`OsStr`].
+///
+/// OS strings are archived as bytes so that archives can be read on any platform. On Unix
+/// platforms these are the raw bytes of the OS string, and on other platforms OS strings are
+/// archived as UTF-8 with any invalid sequences replaced by U+FFFD. Archived OS strings that aren't
+/// valid UTF-8 are only preserved exactly when they're read on Unix platforms.
+#[repr(transparent)]
+pub struct ArchivedOsStr([u8]);
+
+impl ArchivedOsStr {
+    /// Returns the archived bytes of the OS string.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the length of the OS string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the OS string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the OS string as a `str` if it's valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Option<&str> {
+        str::from_utf8(&self.0).ok()
+    }
+
+    /// Returns the archived OS string as an `OsStr`.
+    ///
+    /// This only allocates if the bytes can't be represented as an `OsStr` on the current
+    /// platform, and then they're converted lossily.
+    #[inline]
+    pub fn to_os_str(&self) -> Cow<'_, OsStr> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Cow::Borrowed(OsStr::from_bytes(&self.0))
+        }
+        #[cfg(not(unix))]
+        match self.to_str() {
+            Some(s) => Cow::Borrowed(OsStr::new(s)),
+            None => Cow::Owned(OsString::from(
+                String::from_utf8_lossy(&self.0).into_owned(),
+            )),
+        }
+    }
+
+    /// Returns the bytes that an `OsStr` archives as.
+    #[inline]
+    pub(crate) fn archived_bytes(os_str: &OsStr) -> Cow<'_, [u8]> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Cow::Borrowed(os_str.as_bytes())
+        }
+        #[cfg(not(unix))]
+        match os_str.to_string_lossy() {
+            Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+            Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+        }
+    }
+}
+
+impl Pointee for ArchivedOsStr {
+    type Metadata = usize;
+}
+
+impl ArchivePointee for ArchivedOsStr {
+    type ArchivedMetadata = Archived<usize>;
+
+    #[inline]
+    fn pointer_metadata(archived: &Self::ArchivedMetadata) -> <Self as Pointee>::Metadata {
+        <[u8]>::pointer_metadata(archived)
+    }
+}
+
+impl fmt::Debug for ArchivedOsStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_os_str().fmt(f)
+    }
+}
+
+impl Eq for ArchivedOsStr {}
+
+impl hash::Hash for ArchivedOsStr {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl Ord for ArchivedOsStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl PartialEq for ArchivedOsStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialEq<OsStr> for ArchivedOsStr {
+    #[inline]
+    fn eq(&self, other: &OsStr) -> bool {
+        self.0 == *Self::archived_bytes(other)
+    }
+}
+
+impl PartialEq<ArchivedOsStr> for OsStr {
+    #[inline]
+    fn eq(&self, other: &ArchivedOsStr) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<OsString> for ArchivedOsStr {
+    #[inline]
+    fn eq(&self, other: &OsString) -> bool {
+        self.eq(other.as_os_str())
+    }
+}
+
+impl PartialEq<str> for ArchivedOsStr {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.0 == *other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for ArchivedOsStr {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.eq(*other)
+    }
+}
+
+impl PartialOrd for ArchivedOsStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// The resolver for `CString`.
 pub struct CStringResolver {
     pos: usize,
@@ -169,9 +332,35 @@ pub struct CStringResolver {
 const _: () = {
     use crate::validation::{
         owned::{CheckOwnedPointerError, OwnedPointerError},
-        ArchiveContext,
+        ArchiveContext, LayoutRaw,
     };
     use bytecheck::{CheckBytes, Error};
+    use core::{
+        alloc::{Layout, LayoutError},
+        convert::Infallible,
+    };
+
+    impl LayoutRaw for ArchivedOsStr {
+        #[inline]
+        fn layout_raw(value: *const Self) -> Result<Layout, LayoutError> {
+            Layout::array::<u8>(ptr_meta::metadata(value))
+        }
+
+        #[inline]
+        fn string_len(value: *const Self) -> Option<usize> {
+            Some(ptr_meta::metadata(value))
+        }
+    }
+
+    // Every sequence of bytes is a valid archived OS string
+    impl<C: ?Sized> CheckBytes<C> for ArchivedOsStr {
+        type Error = Infallible;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+            Ok(&*value)
+        }
+    }
 
     impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedCString
     where
@@ -186,6 +375,9 @@ const _: () = {
         ) -> Result<&'a Self, Self::Error> {
             let rel_ptr = RelPtr::<CStr>::manual_check_bytes(value.cast(), context)
                 .map_err(OwnedPointerError::PointerCheckBytesError)?;
+            context
+                .check_string_len(from_archived!(*rel_ptr.metadata()) as usize)
+                .map_err(OwnedPointerError::ContextError)?;
             let ptr = context
                 .check_subtree_rel_ptr(rel_ptr)
                 .map_err(OwnedPointerError::ContextError)?;
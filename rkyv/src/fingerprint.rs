@@ -0,0 +1,440 @@
+//! Schema fingerprints for detecting archives written with a different layout.
+//!
+//! A [`Fingerprint`] is a stable hash of the archived layout of a type: the names, order, and
+//! fingerprints of its fields, and the size and alignment of its archived type. It can be derived
+//! with `#[archive(fingerprint)]`. If any of those change, the fingerprint changes too.
+//!
+//! [`write_fingerprint`] writes the fingerprint of a type as a header at the start of an archive,
+//! and [`archived_root_with_fingerprint`] checks it before accessing the root. The root of the
+//! archive is still stored at the end of the buffer, so archives with a fingerprint header can also
+//! be accessed and validated as usual.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     fingerprint::{archived_root_with_fingerprint, write_fingerprint, FingerprintError},
+//!     ser::{serializers::AllocSerializer, Serializer},
+//!     Archive, Serialize,
+//! };
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(fingerprint)]
+//! struct V1 {
+//!     id: u32,
+//!     name: String,
+//! }
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(fingerprint)]
+//! struct V2 {
+//!     id: u64,
+//!     name: String,
+//! }
+//!
+//! let mut serializer = AllocSerializer::<256>::default();
+//! write_fingerprint::<V1, _>(&mut serializer).unwrap();
+//! serializer
+//!     .serialize_value(&V1 {
+//!         id: 1,
+//!         name: "first".to_string(),
+//!     })
+//!     .unwrap();
+//! let bytes = serializer.into_serializer().into_inner();
+//!
+//! let archived = unsafe { archived_root_with_fingerprint::<V1>(&bytes) }.unwrap();
+//! assert_eq!(archived.name, "first");
+//! assert!(matches!(
+//!     unsafe { archived_root_with_fingerprint::<V2>(&bytes) },
+//!     Err(FingerprintError::Mismatch { .. })
+//! ));
+//! ```
+
+use crate::{archived_root, ser::Serializer, with::With, Archive, Archived};
+use core::{
+    cmp::{Ordering, Reverse},
+    fmt,
+    marker::{PhantomData, PhantomPinned},
+    mem,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    ops::{Range, RangeInclusive},
+};
+
+/// The length of the fingerprint header written at the start of an archive.
+pub const FINGERPRINT_LEN: usize = 8;
+
+/// A type with a stable hash of its archived layout.
+///
+/// This can be derived with `#[archive(fingerprint)]`, which requires every field to implement
+/// `Fingerprint` as well.
+pub trait Fingerprint {
+    /// The fingerprint of the archived layout of the type.
+    const FINGERPRINT: u64;
+}
+
+/// A hasher for computing fingerprints in const contexts.
+///
+/// This uses 64-bit FNV-1a, which is stable across platforms and compiler versions.
+#[derive(Clone, Copy, Debug)]
+pub struct FingerprintHasher {
+    state: u64,
+}
+
+impl FingerprintHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Creates a new hasher.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    /// Adds the given bytes to the hash.
+    #[inline]
+    pub const fn write(mut self, bytes: &[u8]) -> Self {
+        let mut i = 0;
+        while i < bytes.len() {
+            self.state ^= bytes[i] as u64;
+            self.state = self.state.wrapping_mul(Self::PRIME);
+            i += 1;
+        }
+        self
+    }
+
+    /// Adds the given string to the hash.
+    ///
+    /// The length of the string is hashed as well, so that adjacent strings can't run together.
+    #[inline]
+    pub const fn write_str(self, value: &str) -> Self {
+        self.write_usize(value.len()).write(value.as_bytes())
+    }
+
+    /// Adds the given `u64` to the hash.
+    #[inline]
+    pub const fn write_u64(self, value: u64) -> Self {
+        self.write(&value.to_le_bytes())
+    }
+
+    /// Adds the given `usize` to the hash.
+    ///
+    /// The value is always hashed as a `u64`, so the hash does not depend on the target.
+    #[inline]
+    pub const fn write_usize(self, value: usize) -> Self {
+        self.write_u64(value as u64)
+    }
+
+    /// Returns the hash of the values added so far.
+    #[inline]
+    pub const fn finish(self) -> u64 {
+        self.state
+    }
+}
+
+impl Default for FingerprintHasher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The byte order of archived multibyte primitives
+#[cfg(any(
+    feature = "archive_le",
+    all(target_endian = "little", not(feature = "archive_be"))
+))]
+const ENDIAN: &str = "le";
+#[cfg(any(
+    feature = "archive_be",
+    all(target_endian = "big", not(feature = "archive_le"))
+))]
+const ENDIAN: &str = "be";
+
+macro_rules! impl_primitive {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl Fingerprint for $type {
+                const FINGERPRINT: u64 = FingerprintHasher::new()
+                    .write_str(stringify!($type))
+                    .write_str(ENDIAN)
+                    .write_usize(mem::size_of::<Archived<$type>>())
+                    .write_usize(mem::align_of::<Archived<$type>>())
+                    .finish();
+            }
+        )*
+    };
+}
+
+impl_primitive!(
+    (),
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    char,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    PhantomPinned,
+    Ordering,
+);
+
+impl Fingerprint for str {
+    const FINGERPRINT: u64 = FingerprintHasher::new()
+        .write_str("str")
+        .write_usize(mem::size_of::<Archived<usize>>())
+        .finish();
+}
+
+impl<T: Fingerprint> Fingerprint for [T] {
+    const FINGERPRINT: u64 = FingerprintHasher::new()
+        .write_str("[T]")
+        .write_u64(T::FINGERPRINT)
+        .write_usize(mem::size_of::<Archived<usize>>())
+        .finish();
+}
+
+impl<T: Fingerprint, const N: usize> Fingerprint for [T; N] {
+    const FINGERPRINT: u64 = FingerprintHasher::new()
+        .write_str("[T; N]")
+        .write_u64(T::FINGERPRINT)
+        .write_usize(N)
+        .finish();
+}
+
+impl<T: ?Sized> Fingerprint for PhantomData<T> {
+    const FINGERPRINT: u64 = FingerprintHasher::new().write_str("PhantomData").finish();
+}
+
+#[cfg(not(feature = "strict"))]
+macro_rules! impl_tuple {
+    () => {};
+    ($first:ident, $($rest:ident,)*) => {
+        impl<$first: Fingerprint, $($rest: Fingerprint,)*> Fingerprint for ($first, $($rest,)*) {
+            const FINGERPRINT: u64 = FingerprintHasher::new()
+                .write_str("(..)")
+                .write_u64($first::FINGERPRINT)
+                $(.write_u64($rest::FINGERPRINT))*
+                .finish();
+        }
+
+        impl_tuple!($($rest,)*);
+    };
+}
+
+#[cfg(not(feature = "strict"))]
+impl_tuple!(T11, T10, T9, T8, T7, T6, T5, T4, T3, T2, T1, T0,);
+
+macro_rules! impl_wrapper {
+    ($($name:ident<$($param:ident),*>),* $(,)?) => {
+        $(
+            impl<$($param: Fingerprint),*> Fingerprint for $name<$($param),*> {
+                const FINGERPRINT: u64 = FingerprintHasher::new()
+                    .write_str(stringify!($name))
+                    $(.write_u64($param::FINGERPRINT))*
+                    .finish();
+            }
+        )*
+    };
+}
+
+impl_wrapper!(
+    Option<T>,
+    Result<T, E>,
+    Reverse<T>,
+    Range<T>,
+    RangeInclusive<T>,
+);
+
+impl<F: ?Sized, W> Fingerprint for With<F, W>
+where
+    With<F, W>: Archive,
+{
+    // The archived type is chosen by the wrapper, so only its layout can be fingerprinted
+    const FINGERPRINT: u64 = FingerprintHasher::new()
+        .write_str("With")
+        .write_usize(mem::size_of::<Archived<With<F, W>>>())
+        .write_usize(mem::align_of::<Archived<With<F, W>>>())
+        .finish();
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet},
+        rc::Rc,
+        string::String,
+        vec::Vec,
+    };
+    #[cfg(feature = "std")]
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        rc::Rc,
+    };
+
+    impl Fingerprint for String {
+        const FINGERPRINT: u64 = str::FINGERPRINT;
+    }
+
+    impl<T: Fingerprint> Fingerprint for Vec<T> {
+        const FINGERPRINT: u64 = <[T]>::FINGERPRINT;
+    }
+
+    macro_rules! impl_pointer {
+        ($($name:ident),*) => {
+            $(
+                impl<T: Fingerprint + ?Sized> Fingerprint for $name<T> {
+                    const FINGERPRINT: u64 = FingerprintHasher::new()
+                        .write_str(stringify!($name))
+                        .write_u64(T::FINGERPRINT)
+                        .finish();
+                }
+            )*
+        };
+    }
+
+    impl_pointer!(Box, Rc);
+
+    impl_wrapper!(BTreeMap<K, V>, BTreeSet<T>);
+};
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    };
+
+    impl<T: Fingerprint + ?Sized> Fingerprint for Arc<T> {
+        const FINGERPRINT: u64 = FingerprintHasher::new()
+            .write_str("Arc")
+            .write_u64(T::FINGERPRINT)
+            .finish();
+    }
+
+    impl<K: Fingerprint, V: Fingerprint, S> Fingerprint for HashMap<K, V, S> {
+        const FINGERPRINT: u64 = FingerprintHasher::new()
+            .write_str("HashMap")
+            .write_u64(K::FINGERPRINT)
+            .write_u64(V::FINGERPRINT)
+            .finish();
+    }
+
+    impl<K: Fingerprint, S> Fingerprint for HashSet<K, S> {
+        const FINGERPRINT: u64 = FingerprintHasher::new()
+            .write_str("HashSet")
+            .write_u64(K::FINGERPRINT)
+            .finish();
+    }
+};
+
+/// An error resulting from an archive with a missing or mismatched fingerprint.
+#[derive(Debug)]
+pub enum FingerprintError {
+    /// The buffer was too short to contain a fingerprint.
+    Truncated {
+        /// The length of the buffer
+        len: usize,
+    },
+    /// The fingerprint stored in the archive did not match the fingerprint of the type.
+    Mismatch {
+        /// The fingerprint of the type being accessed
+        expected: u64,
+        /// The fingerprint stored in the archive
+        actual: u64,
+    },
+}
+
+impl fmt::Display for FingerprintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FingerprintError::Truncated { len } => write!(
+                f,
+                "buffer of length {} is too short to contain a fingerprint",
+                len
+            ),
+            FingerprintError::Mismatch { expected, actual } => write!(
+                f,
+                "fingerprint mismatch: expected {:#018x}, found {:#018x}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FingerprintError {}
+
+/// Writes the fingerprint of `T` as a header.
+///
+/// This should be called before anything else is serialized, so that the fingerprint is stored at
+/// the start of the archive.
+#[inline]
+pub fn write_fingerprint<T: Fingerprint + ?Sized, S: Serializer + ?Sized>(
+    serializer: &mut S,
+) -> Result<(), S::Error> {
+    serializer.write(&T::FINGERPRINT.to_le_bytes())
+}
+
+/// Verifies that the archive in the given bytes starts with the fingerprint of `T`.
+#[inline]
+pub fn verify_fingerprint<T: Fingerprint + ?Sized>(bytes: &[u8]) -> Result<(), FingerprintError> {
+    if bytes.len() < FINGERPRINT_LEN {
+        return Err(FingerprintError::Truncated { len: bytes.len() });
+    }
+
+    let mut header = [0; FINGERPRINT_LEN];
+    header.copy_from_slice(&bytes[..FINGERPRINT_LEN]);
+    let actual = u64::from_le_bytes(header);
+    if actual == T::FINGERPRINT {
+        Ok(())
+    } else {
+        Err(FingerprintError::Mismatch {
+            expected: T::FINGERPRINT,
+            actual,
+        })
+    }
+}
+
+/// Verifies the fingerprint at the start of the given bytes and casts the root of the archive.
+///
+/// This is a wrapper for [`archived_root`] that verifies the fingerprint first.
+///
+/// # Safety
+///
+/// If the fingerprint matches, the bytes must satisfy the safety requirements of
+/// [`archived_root`].
+#[inline]
+pub unsafe fn archived_root_with_fingerprint<T: Archive + Fingerprint + ?Sized>(
+    bytes: &[u8],
+) -> Result<&T::Archived, FingerprintError> {
+    verify_fingerprint::<T>(bytes)?;
+    Ok(archived_root::<T>(bytes))
+}
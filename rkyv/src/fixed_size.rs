@@ -0,0 +1,137 @@
+//! Compile-time sizes for archives of fixed-layout types.
+//!
+//! A [`FixedSize`] type has no out-of-line data, so its entire archive is just its archived root.
+//! That makes the size of the archive known at compile time, which is useful for sizing buffers
+//! in environments without an allocator.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     archived_root,
+//!     fixed_size::FixedSize,
+//!     ser::{serializers::BufferSerializer, Serializer},
+//!     AlignedBytes, Archive, Serialize,
+//! };
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(fixed_size)]
+//! struct Reading {
+//!     sensor: u8,
+//!     values: [f32; 4],
+//!     timestamp: Option<u64>,
+//! }
+//!
+//! let mut serializer =
+//!     BufferSerializer::new(AlignedBytes::<{ <Reading as FixedSize>::SIZE }>::default());
+//! serializer
+//!     .serialize_value(&Reading {
+//!         sensor: 3,
+//!         values: [1.0, 2.0, 3.0, 4.0],
+//!         timestamp: Some(42),
+//!     })
+//!     .unwrap();
+//! assert_eq!(serializer.pos(), Reading::SIZE);
+//!
+//! let buf = serializer.into_inner();
+//! let archived = unsafe { archived_root::<Reading>(buf.as_ref()) };
+//! assert_eq!(archived.sensor, 3);
+//! assert_eq!(archived.timestamp.as_ref().copied(), Some(42));
+//! ```
+
+use crate::{Archive, Archived};
+use core::{
+    cmp::{Ordering, Reverse},
+    marker::{PhantomData, PhantomPinned},
+    mem,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+    },
+    ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive},
+    time::Duration,
+};
+
+/// A type whose archived form contains no out-of-line data.
+///
+/// Serializing a `FixedSize` value as the root of an archive only writes its archived root, so the
+/// archive is exactly [`SIZE`](FixedSize::SIZE) bytes long. This trait is implemented for
+/// primitives and for compositions of other `FixedSize` types. It can be implemented for derived
+/// types with `#[archive(fixed_size)]`.
+pub trait FixedSize: Archive {
+    /// The size of the archived type in bytes.
+    const SIZE: usize = mem::size_of::<Archived<Self>>();
+
+    /// The alignment of the archived type in bytes.
+    const ALIGN: usize = mem::align_of::<Archived<Self>>();
+}
+
+macro_rules! impl_fixed_size {
+    ($($type:ty),* $(,)?) => {
+        $(impl FixedSize for $type {})*
+    };
+}
+
+impl_fixed_size!(
+    (),
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    char,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    PhantomPinned,
+    Ordering,
+    RangeFull,
+    Duration,
+);
+
+impl<T: ?Sized> FixedSize for PhantomData<T> {}
+
+impl<T: FixedSize, const N: usize> FixedSize for [T; N] {}
+
+#[cfg(not(feature = "strict"))]
+macro_rules! impl_tuple {
+    () => {};
+    ($first:ident, $($rest:ident,)*) => {
+        impl<$first: FixedSize, $($rest: FixedSize,)*> FixedSize for ($first, $($rest,)*) {}
+
+        impl_tuple!($($rest,)*);
+    };
+}
+
+#[cfg(not(feature = "strict"))]
+impl_tuple!(T11, T10, T9, T8, T7, T6, T5, T4, T3, T2, T1, T0,);
+
+impl<T: FixedSize> FixedSize for Option<T> {}
+impl<T: FixedSize, E: FixedSize> FixedSize for Result<T, E> {}
+impl<T: FixedSize> FixedSize for Reverse<T> {}
+impl<T: FixedSize> FixedSize for Wrapping<T> {}
+impl<T: FixedSize> FixedSize for Saturating<T> {}
+impl<T: FixedSize> FixedSize for Range<T> {}
+impl<T: FixedSize> FixedSize for RangeInclusive<T> {}
+impl<T: FixedSize> FixedSize for RangeFrom<T> {}
+impl<T: FixedSize> FixedSize for RangeTo<T> {}
+impl<T: FixedSize> FixedSize for RangeToInclusive<T> {}
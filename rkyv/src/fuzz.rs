@@ -0,0 +1,174 @@
+//! Entry points for fuzzing archived types with [`arbitrary`].
+//!
+//! Each function in this module takes the raw input of a fuzzer and panics only if it finds a bug
+//! in rkyv or in the `Archive`, `Serialize`, `Deserialize`, or `CheckBytes` implementations of the
+//! fuzzed type. This makes them suitable to call directly from a fuzz target:
+//!
+//! ```ignore
+//! #![no_main]
+//!
+//! use libfuzzer_sys::fuzz_target;
+//! use my_crate::MySchema;
+//!
+//! fuzz_target!(|data: &[u8]| {
+//!     rkyv::fuzz::check_root::<MySchema>(data);
+//! });
+//! ```
+//!
+//! [`check_root`] validates arbitrary bytes as an archive, [`round_trip`] serializes arbitrary
+//! values and checks that they validate and deserialize to the same value, and [`corrupt`]
+//! serializes an arbitrary value and then validates it with some of its bytes overwritten.
+//!
+//! The `fuzz` directory of the rkyv repository contains ready-made `cargo fuzz` targets for these
+//! entry points.
+
+use crate::{
+    check_archived_root, de::deserializers::SharedDeserializeMap,
+    ser::serializers::AllocSerializer, to_bytes, validation::validators::DefaultValidator,
+    AlignedVec, Archive, Deserialize, Serialize,
+};
+use arbitrary::{Arbitrary, Unstructured};
+use bytecheck::CheckBytes;
+use core::fmt::Debug;
+
+/// The number of bytes of scratch space that the entry points pre-allocate to serialize values.
+pub const SCRATCH_SPACE: usize = 256;
+
+/// Validates the given bytes as an archived `T` and deserializes it if it's valid.
+///
+/// The bytes are copied into an aligned buffer first so that inputs aren't rejected just because
+/// the fuzzer passed them at an unaligned address. Returns the deserialized value if the bytes
+/// were a valid archive.
+///
+/// # Panics
+///
+/// Panics if a valid archive fails to deserialize.
+pub fn check_root<T>(data: &[u8]) -> Option<T>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<T, SharedDeserializeMap>,
+{
+    let mut bytes = AlignedVec::with_capacity(data.len());
+    bytes.extend_from_slice(data);
+
+    let archived = check_archived_root::<T>(&bytes).ok()?;
+    match archived.deserialize(&mut SharedDeserializeMap::default()) {
+        Ok(value) => Some(value),
+        Err(e) => panic!("failed to deserialize a valid archive: {}", e),
+    }
+}
+
+/// Serializes an arbitrary `T` and checks that it validates and deserializes to the same value.
+///
+/// Returns an error if there wasn't enough data to build a `T`.
+///
+/// # Panics
+///
+/// Panics if the value fails to serialize, its archive fails to validate, or the deserialized
+/// value isn't equal to the original value.
+pub fn round_trip<'a, T>(data: &'a [u8]) -> arbitrary::Result<()>
+where
+    T: Arbitrary<'a> + Debug + PartialEq + Serialize<AllocSerializer<SCRATCH_SPACE>>,
+    T::Archived: for<'b> CheckBytes<DefaultValidator<'b>> + Deserialize<T, SharedDeserializeMap>,
+{
+    let value = T::arbitrary_take_rest(Unstructured::new(data))?;
+    let bytes = serialize(&value);
+
+    let archived = match check_archived_root::<T>(&bytes) {
+        Ok(archived) => archived,
+        Err(e) => panic!("failed to validate the archive of {:?}: {}", value, e),
+    };
+    match archived.deserialize(&mut SharedDeserializeMap::default()) {
+        Ok(deserialized) => assert_eq!(deserialized, value),
+        Err(e) => panic!("failed to deserialize the archive of {:?}: {}", value, e),
+    }
+
+    Ok(())
+}
+
+/// Serializes an arbitrary `T`, overwrites some of its bytes, and then validates it.
+///
+/// The rest of the data after building the `T` is used to choose which bytes to overwrite and
+/// what to overwrite them with. If the corrupted archive still validates, it's deserialized as in
+/// [`check_root`]. Returns an error if there wasn't enough data to build a `T`.
+///
+/// # Panics
+///
+/// Panics if the value fails to serialize, or if the corrupted archive validates but fails to
+/// deserialize.
+pub fn corrupt<'a, T>(data: &'a [u8]) -> arbitrary::Result<()>
+where
+    T: Arbitrary<'a> + Debug + Serialize<AllocSerializer<SCRATCH_SPACE>>,
+    T::Archived: for<'b> CheckBytes<DefaultValidator<'b>> + Deserialize<T, SharedDeserializeMap>,
+{
+    let mut u = Unstructured::new(data);
+    let value = T::arbitrary(&mut u)?;
+    let mut bytes = serialize(&value);
+
+    if !bytes.is_empty() {
+        while !u.is_empty() {
+            let index = u.choose_index(bytes.len())?;
+            bytes[index] = u.arbitrary()?;
+        }
+    }
+    check_root::<T>(&bytes);
+
+    Ok(())
+}
+
+fn serialize<T>(value: &T) -> AlignedVec
+where
+    T: Debug + Serialize<AllocSerializer<SCRATCH_SPACE>>,
+{
+    match to_bytes::<T, SCRATCH_SPACE>(value) {
+        Ok(bytes) => bytes,
+        Err(e) => panic!("failed to serialize {:?}: {:?}", value, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_root, corrupt, round_trip};
+    use crate::to_bytes;
+
+    type Schema = Vec<(u32, String, Option<Vec<u8>>)>;
+
+    const INPUTS: &[&[u8]] = &[
+        b"",
+        b"\x00",
+        b"\x01\x02\x03\x04\x05\x06\x07\x08",
+        b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff",
+        b"the quick brown fox jumps over the lazy dog",
+        &[0x80; 64],
+    ];
+
+    #[test]
+    fn fuzz_check_root() {
+        for input in INPUTS {
+            check_root::<Schema>(input);
+        }
+
+        let value: Schema = vec![
+            (1, "one".to_string(), None),
+            (2, "two".to_string(), Some(vec![2])),
+        ];
+        let bytes = to_bytes::<_, 256>(&value).unwrap();
+        assert_eq!(check_root::<Schema>(&bytes), Some(value));
+    }
+
+    #[test]
+    fn fuzz_round_trip() {
+        for input in INPUTS {
+            let _ = round_trip::<Schema>(input);
+            let _ = round_trip::<Option<(bool, char, u64)>>(input);
+        }
+    }
+
+    #[test]
+    fn fuzz_corrupt() {
+        for input in INPUTS {
+            let _ = corrupt::<Schema>(input);
+            let _ = corrupt::<Option<(bool, char, u64)>>(input);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    de::CapacityError,
+    ser::{ScratchSpace, Serializer},
+    string::{ArchivedString, StringResolver},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, Deserialize, Fallible, Serialize, SerializeUnsized,
+};
+use arrayvec::{ArrayString, ArrayVec};
+
+// ArrayVec
+
+impl<T: Archive, const CAP: usize> Archive for ArrayVec<T, CAP> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedVec::resolve_from_slice(self.as_slice(), pos, resolver, out);
+    }
+}
+
+impl<T, S, const CAP: usize> Serialize<S> for ArrayVec<T, CAP>
+where
+    T: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::serialize_from_slice(self.as_slice(), serializer)
+    }
+}
+
+impl<T, D, const CAP: usize> Deserialize<ArrayVec<T, CAP>, D> for ArchivedVec<Archived<T>>
+where
+    T: Archive,
+    Archived<T>: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+    D::Error: From<CapacityError>,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<ArrayVec<T, CAP>, D::Error> {
+        if self.len() > CAP {
+            return Err(CapacityError {
+                len: self.len(),
+                capacity: CAP,
+            }
+            .into());
+        }
+        let mut result = ArrayVec::new();
+        for item in self.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: PartialEq<U>, U, const CAP: usize> PartialEq<ArrayVec<U, CAP>> for ArchivedVec<T> {
+    #[inline]
+    fn eq(&self, other: &ArrayVec<U, CAP>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+// ArrayString
+
+impl<const CAP: usize> Archive for ArrayString<CAP> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedString::resolve_from_str(self.as_str(), pos, resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized, const CAP: usize> Serialize<S> for ArrayString<CAP>
+where
+    str: SerializeUnsized<S>,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_str(), serializer)
+    }
+}
+
+impl<D, const CAP: usize> Deserialize<ArrayString<CAP>, D> for ArchivedString
+where
+    D: Fallible + ?Sized,
+    D::Error: From<CapacityError>,
+{
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<ArrayString<CAP>, D::Error> {
+        ArrayString::from(self.as_str()).map_err(|_| {
+            CapacityError {
+                len: self.len(),
+                capacity: CAP,
+            }
+            .into()
+        })
+    }
+}
+
+impl<const CAP: usize> PartialEq<ArrayString<CAP>> for ArchivedString {
+    #[inline]
+    fn eq(&self, other: &ArrayString<CAP>) -> bool {
+        PartialEq::eq(self.as_str(), other.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        de::CapacityError,
+        ser::{serializers::CoreSerializer, Serializer},
+        Deserialize, Fallible,
+    };
+    use arrayvec::{ArrayString, ArrayVec};
+
+    struct CapacityDeserializer;
+
+    impl Fallible for CapacityDeserializer {
+        type Error = CapacityError;
+    }
+
+    #[test]
+    fn array_vec() {
+        let value: ArrayVec<i32, 10> = [10, 20, 40, 80].iter().copied().collect();
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<ArrayVec<i32, 10>>(&result[0..end]) };
+        assert_eq!(archived.as_slice(), &[10, 20, 40, 80]);
+        assert_eq!(archived, &value);
+
+        let deserialized: ArrayVec<i32, 10> =
+            archived.deserialize(&mut CapacityDeserializer).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn array_string() {
+        let value = ArrayString::<32>::from("hello world").unwrap();
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<ArrayString<32>>(&result[0..end]) };
+        assert_eq!(archived.as_str(), "hello world");
+        assert_eq!(archived, &value);
+
+        let deserialized: ArrayString<32> =
+            archived.deserialize(&mut CapacityDeserializer).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn over_capacity() {
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        let value: ArrayVec<i32, 4> = [1, 2, 3].iter().copied().collect();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<ArrayVec<i32, 4>>(&result[0..end]) };
+        let deserialized: Result<ArrayVec<i32, 2>, _> =
+            Deserialize::<ArrayVec<i32, 2>, _>::deserialize(archived, &mut CapacityDeserializer);
+        let error: CapacityError = deserialized.unwrap_err();
+        assert_eq!((error.len, error.capacity), (3, 2));
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        let value = ArrayString::<32>::from("hello world").unwrap();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<ArrayString<32>>(&result[0..end]) };
+        let deserialized: Result<ArrayString<8>, _> =
+            Deserialize::<ArrayString<8>, _>::deserialize(archived, &mut CapacityDeserializer);
+        let error: CapacityError = deserialized.unwrap_err();
+        assert_eq!((error.len, error.capacity), (11, 8));
+    }
+}
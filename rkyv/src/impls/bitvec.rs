@@ -67,8 +67,55 @@ where
     }
 }
 
+#[cfg(feature = "bitvec_alloc")]
+impl<T: BitStore + Archive, O: BitOrder> Archive for BitBox<T, O>
+where
+    Archived<T>: BitStore,
+{
+    type Archived = ArchivedBitVec<Archived<T>, O>;
+    type Resolver = VecResolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (fp, fo) = out_field!(out.inner);
+        ArchivedVec::resolve_from_slice(self.as_raw_slice(), pos + fp, resolver, fo);
+        let (fp, fo) = out_field!(out.bit_len);
+        usize::resolve(&self.len(), pos + fp, (), fo);
+    }
+}
+
+#[cfg(feature = "bitvec_alloc")]
+impl<T, O, S> Serialize<S> for BitBox<T, O>
+where
+    T: BitStore + Archive + Serialize<S>,
+    O: BitOrder,
+    S: Fallible + ?Sized + ScratchSpace + Serializer,
+    Archived<T>: BitStore,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, <S as Fallible>::Error> {
+        let resolver = ArchivedVec::serialize_from_slice(self.as_raw_slice(), serializer)?;
+        usize::serialize(&self.len(), serializer)?;
+
+        Ok(resolver)
+    }
+}
+
+#[cfg(feature = "bitvec_alloc")]
+impl<T, O, D> Deserialize<BitBox<T, O>, D> for ArchivedBitVec<Archived<T>, O>
+where
+    T: BitStore + Archive,
+    O: BitOrder,
+    D: Fallible + ?Sized,
+    Archived<T>: Deserialize<T, D> + BitStore,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<BitBox<T, O>, <D as Fallible>::Error> {
+        let bitvec: BitVec<T, O> = self.deserialize(deserializer)?;
+        Ok(bitvec.into_boxed_bitslice())
+    }
+}
+
 #[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "strict", repr(C))]
 pub struct ArchivedBitArray<A = [Archived<usize>; 1], O = Lsb0>
 where
     A: BitViewSized,
@@ -164,6 +211,26 @@ mod tests {
         assert_eq!(deserialized, original);
     }
 
+    #[test]
+    #[cfg(feature = "bitvec_alloc")]
+    fn bitbox() {
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        let original = bitbox![1, 0, 1, 1, 0, 0, 1, 1, 0, 1, 1];
+
+        serializer.serialize_value(&original).unwrap();
+        let end = serializer.pos();
+        let buffer = serializer.into_serializer().into_inner();
+
+        let output = unsafe { archived_root::<BitBox>(&buffer[0..end]) };
+        assert_eq!(&original, output.as_bitslice());
+        assert_eq!(output.get(3).as_deref(), Some(&true));
+        assert_eq!(output.get(4).as_deref(), Some(&false));
+        assert!(output.get(11).is_none());
+
+        let deserialized: BitBox = output.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, original);
+    }
+
     #[test]
     fn bitarr() {
         let mut serializer = CoreSerializer::<256, 256>::default();
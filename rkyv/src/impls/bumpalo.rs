@@ -0,0 +1,219 @@
+use crate::{
+    boxed::{ArchivedBox, BoxResolver},
+    de::BumpArena,
+    ser::{ScratchSpace, Serializer},
+    string::{ArchivedString, StringResolver},
+    vec::{ArchivedVec, VecResolver},
+    Archive, ArchivePointee, ArchiveUnsized, Deserialize, Fallible, Serialize, SerializeUnsized,
+};
+use bumpalo::{boxed::Box, collections::String, collections::Vec};
+
+// Box
+
+impl<T: ArchiveUnsized + ?Sized> Archive for Box<'_, T> {
+    type Archived = ArchivedBox<T::Archived>;
+    type Resolver = BoxResolver<T::MetadataResolver>;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedBox::resolve_from_ref(self.as_ref(), pos, resolver, out);
+    }
+}
+
+impl<T: SerializeUnsized<S> + ?Sized, S: Fallible + ?Sized> Serialize<S> for Box<'_, T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedBox::serialize_from_ref(self.as_ref(), serializer)
+    }
+}
+
+impl<'a, T, D> Deserialize<Box<'a, T>, D> for ArchivedBox<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: BumpArena<'a> + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Box<'a, T>, D::Error> {
+        let value = self.get().deserialize(deserializer)?;
+        Ok(Box::new_in(value, deserializer.bump()))
+    }
+}
+
+impl<T: ArchivePointee + PartialEq<U> + ?Sized, U: ?Sized> PartialEq<Box<'_, U>>
+    for ArchivedBox<T>
+{
+    #[inline]
+    fn eq(&self, other: &Box<'_, U>) -> bool {
+        self.get().eq(other.as_ref())
+    }
+}
+
+// Vec
+
+impl<T: Archive> Archive for Vec<'_, T> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedVec::resolve_from_slice(self.as_slice(), pos, resolver, out);
+    }
+}
+
+impl<T: Serialize<S>, S: ScratchSpace + Serializer + ?Sized> Serialize<S> for Vec<'_, T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_slice(self.as_slice(), serializer)
+    }
+}
+
+impl<'a, T, D> Deserialize<Vec<'a, T>, D> for ArchivedVec<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: BumpArena<'a> + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Vec<'a, T>, D::Error> {
+        let mut result = Vec::with_capacity_in(self.len(), deserializer.bump());
+        for item in self.iter() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Vec<'_, U>> for ArchivedVec<T> {
+    #[inline]
+    fn eq(&self, other: &Vec<'_, U>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+// String
+
+impl Archive for String<'_> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedString::resolve_from_str(self.as_str(), pos, resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for String<'_>
+where
+    str: SerializeUnsized<S>,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_str(), serializer)
+    }
+}
+
+impl<'a, D: BumpArena<'a> + ?Sized> Deserialize<String<'a>, D> for ArchivedString {
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<String<'a>, D::Error> {
+        Ok(String::from_str_in(self.as_str(), deserializer.bump()))
+    }
+}
+
+impl PartialEq<String<'_>> for ArchivedString {
+    #[inline]
+    fn eq(&self, other: &String<'_>) -> bool {
+        PartialEq::eq(self.as_str(), other.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        de::deserializers::BumpDeserializer,
+        ser::{serializers::CoreSerializer, Serializer},
+        Archive, Deserialize, Serialize,
+    };
+    use bumpalo::{
+        boxed::Box,
+        collections::{String, Vec},
+        Bump,
+    };
+
+    #[derive(Archive, Serialize, Deserialize)]
+    #[archive(
+        crate = "crate",
+        bound(
+            serialize = "__S: crate::ser::ScratchSpace + Serializer",
+            deserialize = "__D: crate::de::BumpArena<'a>"
+        )
+    )]
+    struct Node<'a> {
+        name: String<'a>,
+        values: Vec<'a, u32>,
+        #[omit_bounds]
+        children: Vec<'a, Node<'a>>,
+        boxed: Box<'a, u64>,
+    }
+
+    #[test]
+    fn deserialize_into_arena() {
+        let source = Bump::new();
+        let mut children = Vec::new_in(&source);
+        children.push(Node {
+            name: String::from_str_in("child", &source),
+            values: Vec::new_in(&source),
+            children: Vec::new_in(&source),
+            boxed: Box::new_in(2, &source),
+        });
+        let mut values = Vec::new_in(&source);
+        values.extend_from_slice(&[1, 2, 3]);
+        let value = Node {
+            name: String::from_str_in("root", &source),
+            values,
+            children,
+            boxed: Box::new_in(1, &source),
+        };
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Node<'_>>(&result[0..end]) };
+        assert_eq!(archived.name, value.name);
+        assert_eq!(archived.values, value.values);
+        assert_eq!(archived.boxed, value.boxed);
+
+        let bump = Bump::new();
+        assert_eq!(bump.allocated_bytes(), 0);
+        let mut deserializer = BumpDeserializer::new(&bump);
+        let deserialized: Node<'_> = archived.deserialize(&mut deserializer).unwrap();
+        assert_eq!(deserialized.name, "root");
+        assert_eq!(deserialized.values.as_slice(), &[1, 2, 3]);
+        assert_eq!(*deserialized.boxed, 1);
+        assert_eq!(deserialized.children.len(), 1);
+        assert_eq!(deserialized.children[0].name, "child");
+        assert_eq!(*deserialized.children[0].boxed, 2);
+
+        // Everything was allocated in the arena, which was empty before deserializing
+        assert!(bump.allocated_bytes() > 0);
+        assert!(core::ptr::eq(deserialized.values.bump(), &bump));
+        assert!(core::ptr::eq(deserialized.name.bump(), &bump));
+        let in_arena = |ptr: *const u8| unsafe {
+            bump.iter_allocated_chunks_raw()
+                .any(|(start, len)| (start as *const u8..start.add(len)).contains(&ptr))
+        };
+        let child = &deserialized.children[0];
+        for ptr in [
+            deserialized.name.as_ptr(),
+            deserialized.values.as_ptr().cast(),
+            deserialized.children.as_ptr().cast(),
+            (&*deserialized.boxed as *const u64).cast(),
+            child.name.as_ptr(),
+            (&*child.boxed as *const u64).cast(),
+        ] {
+            assert!(in_arena(ptr));
+        }
+    }
+}
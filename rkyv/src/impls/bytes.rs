@@ -0,0 +1,141 @@
+use crate::{
+    de::BackingBytes,
+    ser::{ScratchSpace, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    with::{ArchiveWith, DeserializeWith, SerializeWith, SharedBytes},
+    Archive, Deserialize, Fallible, Serialize,
+};
+use bytes::Bytes;
+
+// Bytes
+
+impl Archive for Bytes {
+    type Archived = ArchivedVec<u8>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedVec::resolve_from_slice(self.as_ref(), pos, resolver, out);
+    }
+}
+
+impl<S: ScratchSpace + Serializer + ?Sized> Serialize<S> for Bytes {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::serialize_from_slice(self.as_ref(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Bytes, D> for ArchivedVec<u8> {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Bytes, D::Error> {
+        Ok(Bytes::copy_from_slice(self.as_slice()))
+    }
+}
+
+impl PartialEq<Bytes> for ArchivedVec<u8> {
+    #[inline]
+    fn eq(&self, other: &Bytes) -> bool {
+        self.as_slice() == other.as_ref()
+    }
+}
+
+// SharedBytes
+
+impl ArchiveWith<Bytes> for SharedBytes {
+    type Archived = ArchivedVec<u8>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Bytes,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        field.resolve(pos, resolver, out);
+    }
+}
+
+impl<S: ScratchSpace + Serializer + ?Sized> SerializeWith<Bytes, S> for SharedBytes {
+    #[inline]
+    fn serialize_with(field: &Bytes, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        field.serialize(serializer)
+    }
+}
+
+impl<D: BackingBytes + ?Sized> DeserializeWith<ArchivedVec<u8>, Bytes, D> for SharedBytes {
+    #[inline]
+    fn deserialize_with(field: &ArchivedVec<u8>, deserializer: &mut D) -> Result<Bytes, D::Error> {
+        let slice = field.as_slice();
+        // `slice_ref` panics on empty slices that are not in the buffer, so handle them separately
+        if slice.is_empty() {
+            Ok(Bytes::new())
+        } else {
+            Ok(deserializer.backing_bytes().slice_ref(slice))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        de::deserializers::BytesDeserializer,
+        ser::{serializers::CoreSerializer, Serializer},
+        with::SharedBytes,
+        Archive, Deserialize, Infallible, Serialize,
+    };
+    use bytes::Bytes;
+
+    #[test]
+    fn bytes() {
+        let value = Bytes::from_static(b"hello world");
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Bytes>(&result[0..end]) };
+        assert_eq!(archived.as_slice(), b"hello world");
+        assert_eq!(archived, &value);
+
+        let deserialized: Bytes = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn shared_bytes() {
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive(crate = "crate")]
+        struct Test {
+            #[with(SharedBytes)]
+            payload: Bytes,
+            #[with(SharedBytes)]
+            empty: Bytes,
+        }
+
+        let value = Test {
+            payload: Bytes::from_static(b"hello world"),
+            empty: Bytes::new(),
+        };
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let buffer = Bytes::copy_from_slice(&result[0..end]);
+
+        let archived = unsafe { archived_root::<Test>(&buffer) };
+        assert_eq!(archived.payload, value.payload);
+
+        let mut deserializer = BytesDeserializer::new(buffer.clone());
+        let deserialized: Test = archived.deserialize(&mut deserializer).unwrap();
+        assert_eq!(deserialized.payload, value.payload);
+        assert!(deserialized.empty.is_empty());
+
+        // The deserialized bytes point into the backing buffer
+        let range = buffer.as_ptr_range();
+        assert!(range.contains(&deserialized.payload.as_ptr()));
+    }
+}
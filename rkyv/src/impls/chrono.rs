@@ -0,0 +1,292 @@
+use crate::{
+    time::{ArchivedDate, ArchivedDateTime, ArchivedOffsetDateTime},
+    Archive, Deserialize, Fallible, Serialize,
+};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+/// The number of days from 0001-01-01 to the Unix epoch in the proleptic Gregorian calendar.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+// Validation checks that archived dates and times convert to chrono types, so these only return
+// `None` for archives that were not validated
+
+#[inline]
+pub(crate) fn naive_date(date: &ArchivedDate) -> Option<NaiveDate> {
+    date.days_since_epoch()
+        .checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+}
+
+#[inline]
+pub(crate) fn date_time(date_time: &ArchivedDateTime) -> Option<DateTime<Utc>> {
+    // Leap seconds are only valid in the last second of a minute
+    DateTime::from_timestamp(date_time.timestamp(), date_time.timestamp_subsec_nanos())
+}
+
+#[inline]
+pub(crate) fn fixed_offset(offset_seconds: i32) -> Option<FixedOffset> {
+    FixedOffset::east_opt(offset_seconds)
+}
+
+// NaiveDate
+
+impl Archive for NaiveDate {
+    type Archived = ArchivedDate;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedDate::emplace(self.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for NaiveDate {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<NaiveDate, D> for ArchivedDate {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<NaiveDate, D::Error> {
+        Ok(naive_date(self).expect("archived date is out of range for chrono"))
+    }
+}
+
+impl PartialEq<NaiveDate> for ArchivedDate {
+    #[inline]
+    fn eq(&self, other: &NaiveDate) -> bool {
+        self.days_since_epoch() == other.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE
+    }
+}
+
+// NaiveDateTime
+
+impl Archive for NaiveDateTime {
+    type Archived = ArchivedDateTime;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        let utc = self.and_utc();
+        ArchivedDateTime::emplace(utc.timestamp(), utc.timestamp_subsec_nanos(), out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for NaiveDateTime {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<NaiveDateTime, D> for ArchivedDateTime {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<NaiveDateTime, D::Error> {
+        Ok(date_time(self)
+            .expect("archived date time is out of range for chrono")
+            .naive_utc())
+    }
+}
+
+impl PartialEq<NaiveDateTime> for ArchivedDateTime {
+    #[inline]
+    fn eq(&self, other: &NaiveDateTime) -> bool {
+        let utc = other.and_utc();
+        self.timestamp() == utc.timestamp()
+            && self.timestamp_subsec_nanos() == utc.timestamp_subsec_nanos()
+    }
+}
+
+// DateTime
+
+macro_rules! impl_date_time {
+    ($tz:ty, $offset:ident, $from_utc:expr) => {
+        impl Archive for DateTime<$tz> {
+            type Archived = ArchivedOffsetDateTime;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+                let $offset = self.offset();
+                ArchivedOffsetDateTime::emplace(
+                    self.timestamp(),
+                    self.timestamp_subsec_nanos(),
+                    $from_utc,
+                    out,
+                );
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for DateTime<$tz> {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl PartialEq<DateTime<$tz>> for ArchivedOffsetDateTime {
+            #[inline]
+            fn eq(&self, other: &DateTime<$tz>) -> bool {
+                let $offset = other.offset();
+                self.utc().timestamp() == other.timestamp()
+                    && self.utc().timestamp_subsec_nanos() == other.timestamp_subsec_nanos()
+                    && self.offset_seconds() == $from_utc
+            }
+        }
+    };
+}
+
+impl_date_time!(Utc, _offset, 0);
+impl_date_time!(FixedOffset, offset, offset.local_minus_utc());
+
+impl<D: Fallible + ?Sized> Deserialize<DateTime<Utc>, D> for ArchivedOffsetDateTime {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<DateTime<Utc>, D::Error> {
+        Ok(date_time(self.utc()).expect("archived date time is out of range for chrono"))
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<DateTime<FixedOffset>, D> for ArchivedOffsetDateTime {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<DateTime<FixedOffset>, D::Error> {
+        let offset = fixed_offset(self.offset_seconds())
+            .expect("archived offset is out of range for chrono");
+        Ok(date_time(self.utc())
+            .expect("archived date time is out of range for chrono")
+            .with_timezone(&offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        ser::{serializers::CoreSerializer, Serializer},
+        time::{ArchivedDate, ArchivedDateTime, ArchivedOffsetDateTime},
+        Deserialize, Infallible, Serialize,
+    };
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+    use core::fmt::Debug;
+
+    fn test_archive<T>(value: &T)
+    where
+        T: Debug + PartialEq + Serialize<CoreSerializer<256, 256>>,
+        T::Archived: PartialEq<T> + Deserialize<T, Infallible>,
+    {
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<T>(&result[0..end]) };
+        assert!(archived == value);
+
+        let deserialized: T = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, &deserialized);
+    }
+
+    fn archive<T: Serialize<CoreSerializer<256, 256>>>(value: &T) -> T::Archived
+    where
+        T::Archived: Copy,
+    {
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        unsafe { *archived_root::<T>(&result[0..end]) }
+    }
+
+    #[test]
+    fn naive_date() {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        let before = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+        let after = NaiveDate::from_ymd_opt(2021, 6, 15).unwrap();
+
+        test_archive(&epoch);
+        test_archive(&before);
+        test_archive(&after);
+
+        let archived: ArchivedDate = archive(&epoch);
+        assert_eq!(archived.days_since_epoch(), 0);
+        assert!(archive(&before) < archived);
+        assert!(archive(&after) > archived);
+    }
+
+    #[test]
+    fn naive_date_time() {
+        let value = NaiveDate::from_ymd_opt(2021, 6, 15)
+            .unwrap()
+            .and_hms_nano_opt(12, 34, 56, 789)
+            .unwrap();
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_500_000_000)
+            .unwrap();
+
+        test_archive(&value);
+        test_archive(&leap);
+        test_archive(&NaiveDateTime::default());
+
+        let archived: ArchivedDateTime = archive(&value);
+        assert_eq!(archived.timestamp(), 1_623_760_496);
+        assert_eq!(archived.timestamp_subsec_nanos(), 789);
+        assert!(archive(&leap) < archived);
+    }
+
+    #[test]
+    fn date_time() {
+        let utc = DateTime::<Utc>::from_timestamp(1_623_760_496, 789).unwrap();
+        let fixed = utc.with_timezone(&FixedOffset::east_opt(-5 * 3600).unwrap());
+
+        test_archive(&utc);
+        test_archive(&fixed);
+
+        let archived: ArchivedOffsetDateTime = archive(&fixed);
+        assert_eq!(archived.utc(), &archive(&utc.naive_utc()));
+        assert_eq!(archived.offset_seconds(), -5 * 3600);
+
+        let same_instant: DateTime<Utc> = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(same_instant, utc);
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn check_date_time() {
+        use crate::{check_archived_root, Infallible};
+        use bytecheck::CheckBytes;
+        use core::mem::MaybeUninit;
+
+        fn check(secs: i64, nanos: u32, offset: i32) -> bool {
+            let mut value = MaybeUninit::<ArchivedOffsetDateTime>::zeroed();
+            unsafe {
+                ArchivedOffsetDateTime::emplace(secs, nanos, offset, value.as_mut_ptr());
+                ArchivedOffsetDateTime::check_bytes(value.as_ptr(), &mut Infallible).is_ok()
+            }
+        }
+
+        let utc = DateTime::<Utc>::from_timestamp(1_623_760_496, 789).unwrap();
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&utc).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        check_archived_root::<DateTime<Utc>>(&result[0..end]).unwrap();
+
+        assert!(check(1_623_760_496, 789, -5 * 3600));
+        // Leap seconds are only valid in the last second of a minute
+        assert!(check(1_483_228_799, 1_500_000_000, 0));
+        assert!(!check(1_483_228_798, 1_500_000_000, 0));
+        // Offsets must be less than a day in either direction
+        assert!(check(0, 0, 86_399));
+        assert!(!check(0, 0, 86_400));
+        assert!(!check(0, 0, -86_400));
+        // Dates must be in range for chrono
+        assert!(!check(i64::MAX, 0, 0));
+
+        let mut date = MaybeUninit::<ArchivedDate>::zeroed();
+        unsafe {
+            ArchivedDate::emplace(i32::MAX, date.as_mut_ptr());
+            ArchivedDate::check_bytes(date.as_ptr(), &mut Infallible).unwrap_err();
+        }
+    }
+}
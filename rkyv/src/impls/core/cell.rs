@@ -0,0 +1,75 @@
+use crate::{Archive, Deserialize, Fallible, Serialize};
+use core::cell::{Cell, RefCell};
+
+// Cell
+
+// The value of a `Cell` can change through a shared reference at any time, including between the
+// serialize and resolve steps. Copying the value into the resolver guarantees that the value that
+// gets resolved is the same one that was serialized.
+impl<T: Archive + Copy> Archive for Cell<T> {
+    type Archived = T::Archived;
+    type Resolver = (T, T::Resolver);
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (value, resolver) = resolver;
+        value.resolve(pos, resolver, out);
+    }
+}
+
+impl<T: Serialize<S> + Copy, S: Fallible + ?Sized> Serialize<S> for Cell<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let value = self.get();
+        Ok((value, value.serialize(serializer)?))
+    }
+}
+
+impl<T, D> Deserialize<Cell<T>, D> for T::Archived
+where
+    T: Archive + Copy,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Cell<T>, D::Error> {
+        Ok(Cell::new(self.deserialize(deserializer)?))
+    }
+}
+
+// RefCell
+
+// Like `Cell`, the value of a `RefCell` can change between the serialize and resolve steps, so the
+// value is cloned into the resolver. This also means the cell is only borrowed once, while it is
+// serialized.
+impl<T: Archive + Clone> Archive for RefCell<T> {
+    type Archived = T::Archived;
+    type Resolver = (T, T::Resolver);
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (value, resolver) = resolver;
+        value.resolve(pos, resolver, out);
+    }
+}
+
+impl<T: Serialize<S> + Clone, S: Fallible + ?Sized> Serialize<S> for RefCell<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let value = self.borrow().clone();
+        let resolver = value.serialize(serializer)?;
+        Ok((value, resolver))
+    }
+}
+
+impl<T, D> Deserialize<RefCell<T>, D> for T::Archived
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<RefCell<T>, D::Error> {
+        Ok(RefCell::new(self.deserialize(deserializer)?))
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{cmp::ArchivedOrdering, Archive, Deserialize, Fallible, Serialize};
+use core::cmp::{Ordering, Reverse};
+
+// Ordering
+
+impl Archive for Ordering {
+    type Archived = ArchivedOrdering;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        out.write((*self).into());
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Ordering {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Ordering, D> for ArchivedOrdering {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Ordering, D::Error> {
+        Ok(self.as_ordering())
+    }
+}
+
+// Reverse
+
+impl<T: Archive> Archive for Reverse<T> {
+    type Archived = T::Archived;
+    type Resolver = T::Resolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        self.0.resolve(pos, resolver, out);
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Reverse<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Reverse<T>, D> for T::Archived
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Reverse<T>, D::Error> {
+        Ok(Reverse(self.deserialize(deserializer)?))
+    }
+}
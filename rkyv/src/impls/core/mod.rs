@@ -8,6 +8,9 @@ use crate::{
 use core::{alloc::Layout, ptr, str};
 use ptr_meta::Pointee;
 
+pub mod cell;
+pub mod cmp;
+pub mod num;
 pub mod ops;
 pub mod option;
 pub mod primitive;
@@ -358,7 +361,7 @@ impl<D: Fallible + ?Sized> DeserializeUnsized<str, D> for <str as ArchiveUnsized
         mut alloc: impl FnMut(Layout) -> *mut u8,
     ) -> Result<*mut (), D::Error> {
         if self.is_empty() {
-            Ok(ptr::null_mut())
+            Ok(ptr::NonNull::<u8>::dangling().as_ptr().cast())
         } else {
             let bytes = alloc(Layout::array::<u8>(self.len()).unwrap());
             assert!(!bytes.is_null());
@@ -0,0 +1,43 @@
+use crate::{Archive, Deserialize, Fallible, Serialize};
+use core::num::{Saturating, Wrapping};
+
+macro_rules! impl_num_wrapper {
+    ($wrapper:ident) => {
+        impl<T: Archive> Archive for $wrapper<T> {
+            type Archived = T::Archived;
+            type Resolver = T::Resolver;
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                pos: usize,
+                resolver: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                self.0.resolve(pos, resolver, out);
+            }
+        }
+
+        impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for $wrapper<T> {
+            #[inline]
+            fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<T, D> Deserialize<$wrapper<T>, D> for T::Archived
+        where
+            T: Archive,
+            T::Archived: Deserialize<T, D>,
+            D: Fallible + ?Sized,
+        {
+            #[inline]
+            fn deserialize(&self, deserializer: &mut D) -> Result<$wrapper<T>, D::Error> {
+                Ok($wrapper(self.deserialize(deserializer)?))
+            }
+        }
+    };
+}
+
+impl_num_wrapper!(Wrapping);
+impl_num_wrapper!(Saturating);
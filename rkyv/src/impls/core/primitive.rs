@@ -1,4 +1,10 @@
-use crate::{Archive, Archived, Deserialize, Fallible, FixedIsize, FixedUsize, Serialize};
+use crate::{
+    collections::util::Equivalent,
+    primitive::{
+        ArchivedChar, ArchivedI128, ArchivedNonZeroI128, ArchivedNonZeroU128, ArchivedU128,
+    },
+    Archive, Archived, Deserialize, Fallible, FixedIsize, FixedUsize, Serialize,
+};
 #[cfg(has_atomics)]
 use core::sync::atomic::{
     AtomicBool, AtomicI16, AtomicI32, AtomicI8, AtomicIsize, AtomicU16, AtomicU32, AtomicU8,
@@ -148,25 +154,19 @@ impl_atomic!(AtomicU8, u8);
 impl_primitive!(@multibyte i16);
 impl_primitive!(@multibyte i32);
 impl_primitive!(@multibyte i64);
-impl_primitive!(@multibyte i128);
 impl_primitive!(@multibyte u16);
 impl_primitive!(@multibyte u32);
 impl_primitive!(@multibyte u64);
-impl_primitive!(@multibyte u128);
 
 impl_primitive!(@multibyte f32);
 impl_primitive!(@multibyte f64);
 
-impl_primitive!(@multibyte char);
-
 impl_primitive!(@multibyte NonZeroI16);
 impl_primitive!(@multibyte NonZeroI32);
 impl_primitive!(@multibyte NonZeroI64);
-impl_primitive!(@multibyte NonZeroI128);
 impl_primitive!(@multibyte NonZeroU16);
 impl_primitive!(@multibyte NonZeroU32);
 impl_primitive!(@multibyte NonZeroU64);
-impl_primitive!(@multibyte NonZeroU128);
 
 #[cfg(has_atomics)]
 impl_atomic!(@multibyte AtomicI16, i16);
@@ -181,6 +181,76 @@ impl_atomic!(@multibyte AtomicU32, u32);
 #[cfg(has_atomics_64)]
 impl_atomic!(@multibyte AtomicU64, u64);
 
+// i128, u128, NonZeroI128, and NonZeroU128
+
+macro_rules! impl_int128 {
+    ($type:ty, $archived:ty) => {
+        impl Archive for $type {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+                <$archived>::emplace(*self, out);
+            }
+        }
+
+        impl_primitive!(@serialize $type);
+
+        impl<D: Fallible + ?Sized> Deserialize<$type, D> for $archived {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$type, D::Error> {
+                Ok(self.value())
+            }
+        }
+
+        impl Equivalent<$archived> for $type {
+            #[inline]
+            fn equivalent(&self, key: &$archived) -> bool {
+                *self == key.value()
+            }
+        }
+    };
+}
+
+impl_int128!(i128, ArchivedI128);
+impl_int128!(u128, ArchivedU128);
+impl_int128!(NonZeroI128, ArchivedNonZeroI128);
+impl_int128!(NonZeroU128, ArchivedNonZeroU128);
+
+// char
+
+impl Archive for char {
+    type Archived = ArchivedChar;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedChar::emplace(*self, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for char {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<char, D> for ArchivedChar {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<char, D::Error> {
+        Ok(self.as_char())
+    }
+}
+
+impl Equivalent<ArchivedChar> for char {
+    #[inline]
+    fn equivalent(&self, key: &ArchivedChar) -> bool {
+        *self == *key
+    }
+}
+
 // PhantomData
 
 impl<T: ?Sized> Archive for PhantomData<T> {
@@ -0,0 +1,244 @@
+use crate::{
+    math::{
+        ArchivedMat2, ArchivedMat3, ArchivedMat4, ArchivedQuat, ArchivedVec2, ArchivedVec3,
+        ArchivedVec4,
+    },
+    Archive, Deserialize, Fallible, Serialize,
+};
+use glam::{Mat2, Mat3, Mat4, Quat, Vec2, Vec3, Vec4};
+
+macro_rules! impl_glam {
+    ($glam:ty, $archived:ident, |$from:ident| $to_archived:expr, |$value:ident| $from_archived:expr) => {
+        impl From<$glam> for $archived {
+            #[inline]
+            fn from($from: $glam) -> Self {
+                $to_archived
+            }
+        }
+
+        impl From<$archived> for $glam {
+            #[inline]
+            fn from($value: $archived) -> Self {
+                $from_archived
+            }
+        }
+
+        impl Archive for $glam {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+                out.write((*self).into());
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $glam {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$glam, D> for $archived {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$glam, D::Error> {
+                Ok((*self).into())
+            }
+        }
+
+        impl PartialEq<$glam> for $archived {
+            #[inline]
+            fn eq(&self, other: &$glam) -> bool {
+                <$glam>::from(*self) == *other
+            }
+        }
+
+        impl PartialEq<$archived> for $glam {
+            #[inline]
+            fn eq(&self, other: &$archived) -> bool {
+                other.eq(self)
+            }
+        }
+
+        // Safety: the archived type has the same layout as the glam type when the archive
+        // endianness matches the target, and neither contains padding.
+        #[cfg(all(
+            feature = "copy",
+            not(any(
+                all(target_endian = "little", feature = "archive_be"),
+                all(target_endian = "big", feature = "archive_le"),
+            )),
+        ))]
+        const _: () = {
+            use core::mem::{align_of, size_of};
+
+            assert!(
+                size_of::<$glam>() == size_of::<$archived>()
+                    && align_of::<$glam>() == align_of::<$archived>(),
+                concat!(
+                    "the layout of `",
+                    stringify!($glam),
+                    "` does not match its archived counterpart"
+                ),
+            );
+
+            unsafe impl crate::copy::ArchiveCopySafe for $glam {}
+        };
+    };
+}
+
+impl_glam!(
+    Vec2,
+    ArchivedVec2,
+    |v| ArchivedVec2 {
+        x: to_archived!(v.x),
+        y: to_archived!(v.y),
+    },
+    |v| Vec2::new(from_archived!(v.x), from_archived!(v.y))
+);
+impl_glam!(
+    Vec3,
+    ArchivedVec3,
+    |v| ArchivedVec3 {
+        x: to_archived!(v.x),
+        y: to_archived!(v.y),
+        z: to_archived!(v.z),
+    },
+    |v| Vec3::new(
+        from_archived!(v.x),
+        from_archived!(v.y),
+        from_archived!(v.z)
+    )
+);
+impl_glam!(
+    Vec4,
+    ArchivedVec4,
+    |v| ArchivedVec4 {
+        x: to_archived!(v.x),
+        y: to_archived!(v.y),
+        z: to_archived!(v.z),
+        w: to_archived!(v.w),
+    },
+    |v| Vec4::new(
+        from_archived!(v.x),
+        from_archived!(v.y),
+        from_archived!(v.z),
+        from_archived!(v.w)
+    )
+);
+impl_glam!(
+    Quat,
+    ArchivedQuat,
+    |q| ArchivedQuat {
+        x: to_archived!(q.x),
+        y: to_archived!(q.y),
+        z: to_archived!(q.z),
+        w: to_archived!(q.w),
+    },
+    |q| Quat::from_xyzw(
+        from_archived!(q.x),
+        from_archived!(q.y),
+        from_archived!(q.z),
+        from_archived!(q.w)
+    )
+);
+impl_glam!(
+    Mat2,
+    ArchivedMat2,
+    |m| ArchivedMat2 {
+        x_axis: m.x_axis.into(),
+        y_axis: m.y_axis.into(),
+    },
+    |m| Mat2::from_cols(m.x_axis.into(), m.y_axis.into())
+);
+impl_glam!(
+    Mat3,
+    ArchivedMat3,
+    |m| ArchivedMat3 {
+        x_axis: m.x_axis.into(),
+        y_axis: m.y_axis.into(),
+        z_axis: m.z_axis.into(),
+    },
+    |m| Mat3::from_cols(m.x_axis.into(), m.y_axis.into(), m.z_axis.into())
+);
+impl_glam!(
+    Mat4,
+    ArchivedMat4,
+    |m| ArchivedMat4 {
+        x_axis: m.x_axis.into(),
+        y_axis: m.y_axis.into(),
+        z_axis: m.z_axis.into(),
+        w_axis: m.w_axis.into(),
+    },
+    |m| Mat4::from_cols(
+        m.x_axis.into(),
+        m.y_axis.into(),
+        m.z_axis.into(),
+        m.w_axis.into()
+    )
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        ser::{serializers::AllocSerializer, Serializer},
+        Archive, Deserialize, Infallible, Serialize,
+    };
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec::Vec;
+    use glam::{Mat2, Mat3, Mat4, Quat, Vec2, Vec3, Vec4};
+
+    #[test]
+    fn glam() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate = "crate", compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            a: Vec2,
+            b: Vec3,
+            c: Vec4,
+            d: Quat,
+            e: Mat2,
+            f: Mat3,
+            g: Mat4,
+            points: Vec<Vec3>,
+        }
+
+        let value = Test {
+            a: Vec2::new(1.0, 2.0),
+            b: Vec3::new(3.0, 4.0, 5.0),
+            c: Vec4::new(6.0, 7.0, 8.0, 9.0),
+            d: Quat::from_rotation_z(1.0),
+            e: Mat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]),
+            f: Mat3::from_diagonal(Vec3::new(1.0, 2.0, 3.0)),
+            g: Mat4::from_translation(Vec3::new(10.0, 20.0, 30.0)),
+            points: vec![Vec3::X, Vec3::Y, Vec3::Z],
+        };
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_ref()) };
+        assert_eq!(archived, &value);
+        assert_eq!(archived.g.w_axis.y, 20.0);
+        assert_eq!(archived.points[1], Vec3::Y);
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn check_glam() {
+        use crate::check_archived_root;
+
+        let value = (Vec3::ONE, Quat::IDENTITY, Mat4::IDENTITY);
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        check_archived_root::<(Vec3, Quat, Mat4)>(result.as_ref()).unwrap();
+    }
+}
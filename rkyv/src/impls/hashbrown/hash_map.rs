@@ -65,8 +65,8 @@ impl<K: Hash + Eq + Borrow<AK>, V, AK: Hash + Eq, AV: PartialEq<V>, S: BuildHash
     }
 }
 
-impl<K: Hash + Eq + Borrow<AK>, V, AK: Hash + Eq, AV: PartialEq<V>>
-    PartialEq<ArchivedHashMap<AK, AV>> for HashMap<K, V>
+impl<K: Hash + Eq + Borrow<AK>, V, AK: Hash + Eq, AV: PartialEq<V>, S: BuildHasher>
+    PartialEq<ArchivedHashMap<AK, AV>> for HashMap<K, V, S>
 {
     #[inline]
     fn eq(&self, other: &ArchivedHashMap<AK, AV>) -> bool {
@@ -86,7 +86,7 @@ mod tests {
     use alloc::string::String;
 
     #[test]
-    fn index_map() {
+    fn hash_map() {
         let mut value = HashMap::new();
         value.insert(String::from("foo"), 10);
         value.insert(String::from("bar"), 20);
@@ -109,9 +109,31 @@ mod tests {
         assert_eq!(value, deserialized);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_std_interop() {
+        use core::hash::BuildHasherDefault;
+        use seahash::SeaHasher;
+
+        let mut value = HashMap::<_, _, BuildHasherDefault<SeaHasher>>::default();
+        value.insert(1u8, 10);
+        value.insert(2u8, 20);
+
+        let mut serializer = AllocSerializer::<4096>::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<HashMap<u8, i32>>(result.as_ref()) };
+        assert_eq!(archived, &value);
+
+        let deserialized: std::collections::HashMap<u8, i32> =
+            archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(archived, &deserialized);
+        assert_eq!(value, *archived);
+    }
+
     #[cfg(feature = "validation")]
     #[test]
-    fn validate_index_map() {
+    fn validate_hash_map() {
         use crate::check_archived_root;
 
         let mut value = HashMap::new();
@@ -124,6 +146,6 @@ mod tests {
         serializer.serialize_value(&value).unwrap();
         let result = serializer.into_serializer().into_inner();
         check_archived_root::<HashMap<String, i32>>(result.as_ref())
-            .expect("failed to validate archived index map");
+            .expect("failed to validate archived hash map");
     }
 }
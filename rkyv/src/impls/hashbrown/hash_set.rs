@@ -43,7 +43,7 @@ where
 {
     #[inline]
     fn deserialize(&self, deserializer: &mut D) -> Result<HashSet<K, S>, D::Error> {
-        let mut result = HashSet::with_hasher(S::default());
+        let mut result = HashSet::with_capacity_and_hasher(self.len(), S::default());
         for k in self.iter() {
             result.insert(k.deserialize(deserializer)?);
         }
@@ -85,7 +85,7 @@ mod tests {
     use alloc::string::String;
 
     #[test]
-    fn index_set() {
+    fn hash_set() {
         let mut value = HashSet::new();
         value.insert(String::from("foo"));
         value.insert(String::from("bar"));
@@ -109,7 +109,7 @@ mod tests {
 
     #[cfg(feature = "validation")]
     #[test]
-    fn validate_index_set() {
+    fn validate_hash_set() {
         use crate::check_archived_root;
 
         let mut value = HashSet::new();
@@ -122,6 +122,6 @@ mod tests {
         serializer.serialize_value(&value).unwrap();
         let result = serializer.into_serializer().into_inner();
         check_archived_root::<HashSet<String>>(result.as_ref())
-            .expect("failed to validate archived index set");
+            .expect("failed to validate archived hash set");
     }
 }
@@ -3,10 +3,10 @@ use crate::{
     ser::{ScratchSpace, Serializer},
     Archive, Deserialize, Fallible, Serialize,
 };
-use core::hash::Hash;
+use core::hash::{BuildHasher, Hash};
 use indexmap::IndexMap;
 
-impl<K: Archive, V: Archive> Archive for IndexMap<K, V> {
+impl<K: Archive, V: Archive, S> Archive for IndexMap<K, V, S> {
     type Archived = ArchivedIndexMap<K::Archived, V::Archived>;
     type Resolver = IndexMapResolver;
 
@@ -15,13 +15,14 @@ impl<K: Archive, V: Archive> Archive for IndexMap<K, V> {
     }
 }
 
-impl<K, V, S> Serialize<S> for IndexMap<K, V>
+impl<K, V, S, Ser> Serialize<Ser> for IndexMap<K, V, S>
 where
-    K: Hash + Eq + Serialize<S>,
-    V: Serialize<S>,
-    S: ScratchSpace + Serializer + ?Sized,
+    K: Hash + Eq + Serialize<Ser>,
+    V: Serialize<Ser>,
+    S: BuildHasher,
+    Ser: ScratchSpace + Serializer + ?Sized,
 {
-    fn serialize(&self, serializer: &mut S) -> Result<IndexMapResolver, S::Error> {
+    fn serialize(&self, serializer: &mut Ser) -> Result<IndexMapResolver, Ser::Error> {
         unsafe {
             ArchivedIndexMap::serialize_from_iter_index(
                 self.iter(),
@@ -32,16 +33,17 @@ where
     }
 }
 
-impl<K, V, D> Deserialize<IndexMap<K, V>, D> for ArchivedIndexMap<K::Archived, V::Archived>
+impl<K, V, D, S> Deserialize<IndexMap<K, V, S>, D> for ArchivedIndexMap<K::Archived, V::Archived>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D>,
     V: Archive,
     V::Archived: Deserialize<V, D>,
     D: Fallible + ?Sized,
+    S: Default + BuildHasher,
 {
-    fn deserialize(&self, deserializer: &mut D) -> Result<IndexMap<K, V>, D::Error> {
-        let mut result = IndexMap::with_capacity(self.len());
+    fn deserialize(&self, deserializer: &mut D) -> Result<IndexMap<K, V, S>, D::Error> {
+        let mut result = IndexMap::with_capacity_and_hasher(self.len(), S::default());
         for (k, v) in self.iter() {
             result.insert(k.deserialize(deserializer)?, v.deserialize(deserializer)?);
         }
@@ -49,15 +51,17 @@ where
     }
 }
 
-impl<UK, K, UV, V> PartialEq<IndexMap<UK, UV>> for ArchivedIndexMap<K, V>
+impl<UK, K, UV, V, S> PartialEq<IndexMap<UK, UV, S>> for ArchivedIndexMap<K, V>
 where
     K: PartialEq<UK>,
     V: PartialEq<UV>,
 {
-    fn eq(&self, other: &IndexMap<UK, UV>) -> bool {
-        self.iter()
-            .zip(other.iter())
-            .all(|((ak, av), (bk, bv))| ak == bk && av == bv)
+    fn eq(&self, other: &IndexMap<UK, UV, S>) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|((ak, av), (bk, bv))| ak == bk && av == bv)
     }
 }
 
@@ -91,10 +95,76 @@ mod tests {
             assert_eq!(v, av);
         }
 
+        for (i, (k, v)) in value.iter().enumerate() {
+            assert_eq!(archived.get_index_of(k.as_str()), Some(i));
+            assert_eq!(&archived[i], v);
+            assert_eq!(&archived[k.as_str()], v);
+        }
+
         let deserialized: IndexMap<String, i32> = archived.deserialize(&mut Infallible).unwrap();
         assert_eq!(value, deserialized);
     }
 
+    #[test]
+    fn index_map_with_hasher() {
+        use core::hash::BuildHasherDefault;
+        use seahash::SeaHasher;
+
+        type Map = IndexMap<String, i32, BuildHasherDefault<SeaHasher>>;
+
+        let mut value = Map::default();
+        value.insert(String::from("foo"), 10);
+        value.insert(String::from("bar"), 20);
+
+        let mut serializer = AllocSerializer::<4096>::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Map>(result.as_ref()) };
+
+        assert!(archived == &value);
+        value.pop();
+        assert!(archived != &value);
+
+        let deserialized: Map = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(archived.len(), deserialized.len());
+        assert!(archived == &deserialized);
+    }
+
+    #[test]
+    fn index_map_pinned_mutation() {
+        use crate::archived_root_mut;
+        use core::pin::Pin;
+
+        let value = indexmap! {
+            String::from("foo") => 10,
+            String::from("bar") => 20,
+            String::from("baz") => 40,
+        };
+
+        let mut serializer = AllocSerializer::<4096>::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut result = serializer.into_serializer().into_inner();
+        let mut archived =
+            unsafe { archived_root_mut::<IndexMap<String, i32>>(Pin::new(result.as_mut())) };
+
+        *archived.as_mut().get_pin("foo").unwrap() = to_archived!(11i32);
+        let (key, mut bar) = archived.as_mut().get_index_pin(1).unwrap();
+        assert_eq!(key, "bar");
+        *bar = to_archived!(21i32);
+        assert!(archived.as_mut().get_index_pin(3).is_none());
+
+        for (_, mut v) in archived.as_mut().iter_pin() {
+            *v = to_archived!(from_archived!(*v) * 2);
+        }
+        for mut v in archived.as_mut().values_pin() {
+            *v = to_archived!(from_archived!(*v) + 1);
+        }
+
+        assert_eq!(archived["foo"], 23);
+        assert_eq!(archived["bar"], 43);
+        assert_eq!(archived["baz"], 81);
+    }
+
     #[cfg(feature = "validation")]
     #[test]
     fn validate_index_map() {
@@ -3,10 +3,10 @@ use crate::{
     ser::{ScratchSpace, Serializer},
     Archive, Deserialize, Fallible, Serialize,
 };
-use core::hash::Hash;
+use core::hash::{BuildHasher, Hash};
 use indexmap::IndexSet;
 
-impl<K: Archive> Archive for IndexSet<K> {
+impl<K: Archive, S> Archive for IndexSet<K, S> {
     type Archived = ArchivedIndexSet<K::Archived>;
     type Resolver = IndexSetResolver;
 
@@ -15,12 +15,13 @@ impl<K: Archive> Archive for IndexSet<K> {
     }
 }
 
-impl<K, S> Serialize<S> for IndexSet<K>
+impl<K, S, Ser> Serialize<Ser> for IndexSet<K, S>
 where
-    K: Hash + Eq + Serialize<S>,
-    S: ScratchSpace + Serializer + ?Sized,
+    K: Hash + Eq + Serialize<Ser>,
+    S: BuildHasher,
+    Ser: ScratchSpace + Serializer + ?Sized,
 {
-    fn serialize(&self, serializer: &mut S) -> Result<IndexSetResolver, S::Error> {
+    fn serialize(&self, serializer: &mut Ser) -> Result<IndexSetResolver, Ser::Error> {
         unsafe {
             ArchivedIndexSet::serialize_from_iter_index(
                 self.iter(),
@@ -31,14 +32,15 @@ where
     }
 }
 
-impl<K, D> Deserialize<IndexSet<K>, D> for ArchivedIndexSet<K::Archived>
+impl<K, D, S> Deserialize<IndexSet<K, S>, D> for ArchivedIndexSet<K::Archived>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D>,
     D: Fallible + ?Sized,
+    S: Default + BuildHasher,
 {
-    fn deserialize(&self, deserializer: &mut D) -> Result<IndexSet<K>, D::Error> {
-        let mut result = IndexSet::with_capacity(self.len());
+    fn deserialize(&self, deserializer: &mut D) -> Result<IndexSet<K, S>, D::Error> {
+        let mut result = IndexSet::with_capacity_and_hasher(self.len(), S::default());
         for k in self.iter() {
             result.insert(k.deserialize(deserializer)?);
         }
@@ -46,8 +48,8 @@ where
     }
 }
 
-impl<UK, K: PartialEq<UK>> PartialEq<IndexSet<UK>> for ArchivedIndexSet<K> {
-    fn eq(&self, other: &IndexSet<UK>) -> bool {
+impl<UK, K: PartialEq<UK>, S> PartialEq<IndexSet<UK, S>> for ArchivedIndexSet<K> {
+    fn eq(&self, other: &IndexSet<UK, S>) -> bool {
         self.iter().eq(other.iter())
     }
 }
@@ -81,6 +83,11 @@ mod tests {
             assert_eq!(k, ak);
         }
 
+        for (i, k) in value.iter().enumerate() {
+            assert_eq!(archived.get_index_of(k.as_str()), Some(i));
+            assert_eq!(&archived[i], k);
+        }
+
         let deserialized: IndexSet<String> = archived.deserialize(&mut Infallible).unwrap();
         assert_eq!(value, deserialized);
     }
@@ -0,0 +1,208 @@
+use crate::{
+    math::{ArchivedMatrix, ArchivedPoint, ArchivedQuaternion},
+    Archive, Deserialize, Fallible, Serialize,
+};
+use core::ptr::addr_of_mut;
+use nalgebra::{ArrayStorage, Point, Quaternion, SMatrix, Scalar, Vector4};
+
+// SMatrix
+
+impl<T: Archive, const R: usize, const C: usize> Archive for SMatrix<T, R, C> {
+    type Archived = ArchivedMatrix<T::Archived, R, C>;
+    type Resolver = <[[T; R]; C] as Archive>::Resolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        self.data.0.resolve(pos, resolver, addr_of_mut!((*out).0));
+    }
+}
+
+impl<T, S, const R: usize, const C: usize> Serialize<S> for SMatrix<T, R, C>
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.data.0.serialize(serializer)
+    }
+}
+
+impl<T, D, const R: usize, const C: usize> Deserialize<SMatrix<T, R, C>, D>
+    for ArchivedMatrix<T::Archived, R, C>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<SMatrix<T, R, C>, D::Error> {
+        let data = Deserialize::<[[T; R]; C], D>::deserialize(&self.0, deserializer)?;
+        Ok(SMatrix::from_array_storage(ArrayStorage(data)))
+    }
+}
+
+impl<T: PartialEq<U>, U, const R: usize, const C: usize> PartialEq<SMatrix<U, R, C>>
+    for ArchivedMatrix<T, R, C>
+{
+    #[inline]
+    fn eq(&self, other: &SMatrix<U, R, C>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+// Safety: `SMatrix` is `repr(C)` and stores its components in a `repr(transparent)` array
+#[cfg(feature = "copy")]
+unsafe impl<T: crate::copy::ArchiveCopySafe, const R: usize, const C: usize>
+    crate::copy::ArchiveCopySafe for SMatrix<T, R, C>
+{
+}
+
+// Point
+
+impl<T: Scalar + Archive, const D: usize> Archive for Point<T, D> {
+    type Archived = ArchivedPoint<T::Archived, D>;
+    type Resolver = <SMatrix<T, D, 1> as Archive>::Resolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (fp, fo) = out_field!(out.coords);
+        self.coords.resolve(pos + fp, resolver, fo);
+    }
+}
+
+impl<T, S, const D: usize> Serialize<S> for Point<T, D>
+where
+    T: Scalar + Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.coords.serialize(serializer)
+    }
+}
+
+impl<T, D, const N: usize> Deserialize<Point<T, N>, D> for ArchivedPoint<T::Archived, N>
+where
+    T: Scalar + Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Point<T, N>, D::Error> {
+        let coords: SMatrix<T, N, 1> = self.coords.deserialize(deserializer)?;
+        Ok(Point::from(coords))
+    }
+}
+
+impl<T: PartialEq<U>, U: Scalar, const D: usize> PartialEq<Point<U, D>> for ArchivedPoint<T, D> {
+    #[inline]
+    fn eq(&self, other: &Point<U, D>) -> bool {
+        self.coords.eq(&other.coords)
+    }
+}
+
+// Safety: `Point` is `repr(C)` and only contains its coordinates
+#[cfg(feature = "copy")]
+unsafe impl<T: Scalar + crate::copy::ArchiveCopySafe, const D: usize> crate::copy::ArchiveCopySafe
+    for Point<T, D>
+{
+}
+
+// Quaternion
+
+impl<T: Archive> Archive for Quaternion<T> {
+    type Archived = ArchivedQuaternion<T::Archived>;
+    type Resolver = <Vector4<T> as Archive>::Resolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (fp, fo) = out_field!(out.coords);
+        self.coords.resolve(pos + fp, resolver, fo);
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Quaternion<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.coords.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Quaternion<T>, D> for ArchivedQuaternion<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Quaternion<T>, D::Error> {
+        Ok(Quaternion {
+            coords: self.coords.deserialize(deserializer)?,
+        })
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Quaternion<U>> for ArchivedQuaternion<T> {
+    #[inline]
+    fn eq(&self, other: &Quaternion<U>) -> bool {
+        self.coords.eq(&other.coords)
+    }
+}
+
+// Safety: `Quaternion` is `repr(C)` and only contains its coordinates
+#[cfg(feature = "copy")]
+unsafe impl<T: crate::copy::ArchiveCopySafe> crate::copy::ArchiveCopySafe for Quaternion<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        ser::{serializers::AllocSerializer, Serializer},
+        Deserialize, Infallible,
+    };
+    use nalgebra::{Matrix2x3, Point3, Quaternion, Vector3};
+
+    #[test]
+    fn nalgebra() {
+        let value = (
+            Matrix2x3::new(1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0),
+            Vector3::new(1.0f64, 2.0, 3.0),
+            Point3::new(4i32, 5, 6),
+            Quaternion::new(1.0f32, 0.0, 0.0, 0.0),
+        );
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe {
+            archived_root::<(Matrix2x3<f32>, Vector3<f64>, Point3<i32>, Quaternion<f32>)>(
+                result.as_ref(),
+            )
+        };
+        assert_eq!(archived.0, value.0);
+        assert_eq!(archived.0[(1, 0)], 4.0);
+        assert_eq!(archived.0.column(2), &[3.0, 6.0]);
+        assert_eq!(archived.1, value.1);
+        assert_eq!(archived.2, value.2);
+        assert_eq!(archived.3, value.3);
+        assert_eq!(archived.3.coords[3], 1.0);
+
+        let deserialized: (Matrix2x3<f32>, Vector3<f64>, Point3<i32>, Quaternion<f32>) =
+            archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn check_nalgebra() {
+        use crate::check_archived_root;
+
+        let value = (Vector3::new(1.0f32, 2.0, 3.0), Point3::new(1u8, 2, 3));
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        check_archived_root::<(Vector3<f32>, Point3<u8>)>(result.as_ref()).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    num::{ArchivedNotNan, ArchivedOrderedFloat},
+    Archive, Archived, Deserialize, Fallible, Serialize,
+};
+use ordered_float::{NotNan, OrderedFloat};
+
+macro_rules! impl_float_wrapper {
+    ($archived:ident, $wrapper:ident, $float:ty) => {
+        impl Archive for $wrapper<$float> {
+            type Archived = $archived<Archived<$float>>;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+                <$archived<Archived<$float>>>::emplace(*self, out);
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $wrapper<$float> {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$wrapper<$float>, D>
+            for $archived<Archived<$float>>
+        {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$wrapper<$float>, D::Error> {
+                Ok(self.value())
+            }
+        }
+    };
+}
+
+impl_float_wrapper!(ArchivedOrderedFloat, OrderedFloat, f32);
+impl_float_wrapper!(ArchivedOrderedFloat, OrderedFloat, f64);
+impl_float_wrapper!(ArchivedNotNan, NotNan, f32);
+impl_float_wrapper!(ArchivedNotNan, NotNan, f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        ser::{serializers::CoreSerializer, Serializer},
+        Deserialize, Infallible,
+    };
+    use ordered_float::{NotNan, OrderedFloat};
+
+    #[test]
+    fn ordered_float() {
+        let value = [
+            OrderedFloat(f64::NAN),
+            OrderedFloat(1.5),
+            OrderedFloat(f64::NEG_INFINITY),
+        ];
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<[OrderedFloat<f64>; 3]>(&result[0..end]) };
+        assert_eq!(archived, &value);
+
+        let mut sorted = *archived;
+        sorted.sort();
+        assert_eq!(sorted[0], OrderedFloat(f64::NEG_INFINITY));
+        assert_eq!(sorted[2], OrderedFloat(f64::NAN));
+
+        let deserialized: [OrderedFloat<f64>; 3] = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn not_nan() {
+        let value = NotNan::new(-2.25f32).unwrap();
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<NotNan<f32>>(&result[0..end]) };
+        assert_eq!(archived, &value);
+        assert!(*archived < NotNan::new(0.0).unwrap());
+
+        let deserialized: NotNan<f32> = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn check_not_nan() {
+        use crate::check_archived_root;
+
+        let value = NotNan::new(1.0f64).unwrap();
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        check_archived_root::<NotNan<f64>>(&result[0..end]).unwrap();
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&OrderedFloat(f64::NAN)).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        check_archived_root::<OrderedFloat<f64>>(&result[0..end]).unwrap();
+        // The same bytes must not validate as a not-NaN float
+        check_archived_root::<NotNan<f64>>(&result[0..end]).unwrap_err();
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{num::ArchivedDecimal, Archive, Deserialize, Fallible, Serialize};
+use rust_decimal::Decimal;
+
+impl Archive for Decimal {
+    type Archived = ArchivedDecimal;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedDecimal::emplace(*self, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Decimal {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Decimal, D> for ArchivedDecimal {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Decimal, D::Error> {
+        Ok(self.as_decimal())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        ser::{serializers::CoreSerializer, Serializer},
+        Deserialize, Infallible,
+    };
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn decimal() {
+        let value = [Decimal::new(-123_456, 3), Decimal::MAX, Decimal::ZERO];
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<[Decimal; 3]>(&result[0..end]) };
+        assert_eq!(archived, &value);
+        assert!(archived[0] < archived[2]);
+        assert_eq!(archived[0].to_string(), "-123.456");
+
+        let deserialized: [Decimal; 3] = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, deserialized);
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn check_decimal() {
+        use crate::check_archived_root;
+
+        let value = Decimal::new(42, 28);
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let mut result = serializer.into_serializer().into_inner();
+        check_archived_root::<Decimal>(&result[0..end]).unwrap();
+
+        // Set the scale to 29
+        result[2] = 29;
+        check_archived_root::<Decimal>(&result[0..end]).unwrap_err();
+    }
+}
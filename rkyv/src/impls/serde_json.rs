@@ -0,0 +1,254 @@
+use crate::{
+    collections::ArchivedBTreeMap,
+    ser::{ScratchSpace, Serializer},
+    string::ArchivedString,
+    value::{ArchivedValue, ValueResolver},
+    vec::ArchivedVec,
+    Archive, Archived, Deserialize, Fallible, Serialize,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ptr;
+use serde_json::{Map, Number, Value};
+
+#[allow(dead_code)]
+#[repr(u8)]
+enum ArchivedValueTag {
+    Null,
+    Bool,
+    Int,
+    UInt,
+    Float,
+    String,
+    Array,
+    Map,
+}
+
+#[repr(C)]
+struct ArchivedValueVariant<T>(ArchivedValueTag, T);
+
+type ArchivedMap = ArchivedBTreeMap<ArchivedString, ArchivedValue>;
+
+// Numbers are archived as the first of `Int`, `UInt`, and `Float` that can hold them
+enum ArchivedNumber {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+}
+
+#[inline]
+fn archived_number(number: &Number) -> ArchivedNumber {
+    if let Some(value) = number.as_i64() {
+        ArchivedNumber::Int(value)
+    } else if let Some(value) = number.as_u64() {
+        ArchivedNumber::UInt(value)
+    } else {
+        ArchivedNumber::Float(number.as_f64().unwrap_or(f64::NAN))
+    }
+}
+
+#[inline]
+unsafe fn write_variant<T>(out: *mut ArchivedValue, tag: ArchivedValueTag, value: T) {
+    let out = out.cast::<ArchivedValueVariant<T>>();
+    ptr::addr_of_mut!((*out).0).write(tag);
+    ptr::addr_of_mut!((*out).1).write(value);
+}
+
+// Archived maps are sorted by key, but maps are kept in insertion order when `serde_json` has the
+// `preserve_order` feature
+fn sorted_entries(map: &Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+impl Archive for Value {
+    type Archived = ArchivedValue;
+    type Resolver = ValueResolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        match (self, resolver) {
+            (Value::Null, _) => out.cast::<ArchivedValueTag>().write(ArchivedValueTag::Null),
+            (Value::Bool(value), _) => write_variant(out, ArchivedValueTag::Bool, *value),
+            (Value::Number(number), _) => match archived_number(number) {
+                ArchivedNumber::Int(value) => {
+                    write_variant::<Archived<i64>>(out, ArchivedValueTag::Int, to_archived!(value))
+                }
+                ArchivedNumber::UInt(value) => {
+                    write_variant::<Archived<u64>>(out, ArchivedValueTag::UInt, to_archived!(value))
+                }
+                ArchivedNumber::Float(value) => write_variant::<Archived<f64>>(
+                    out,
+                    ArchivedValueTag::Float,
+                    to_archived!(value),
+                ),
+            },
+            (Value::String(value), ValueResolver::String(resolver)) => {
+                let out = out.cast::<ArchivedValueVariant<ArchivedString>>();
+                ptr::addr_of_mut!((*out).0).write(ArchivedValueTag::String);
+                let (fp, fo) = out_field!(out.1);
+                ArchivedString::resolve_from_str(value, pos + fp, resolver, fo);
+            }
+            (Value::Array(values), ValueResolver::Array(resolver)) => {
+                let out = out.cast::<ArchivedValueVariant<ArchivedVec<ArchivedValue>>>();
+                ptr::addr_of_mut!((*out).0).write(ArchivedValueTag::Array);
+                let (fp, fo) = out_field!(out.1);
+                ArchivedVec::resolve_from_slice(values, pos + fp, resolver, fo);
+            }
+            (Value::Object(map), ValueResolver::Map(resolver)) => {
+                let out = out.cast::<ArchivedValueVariant<ArchivedMap>>();
+                ptr::addr_of_mut!((*out).0).write(ArchivedValueTag::Map);
+                let (fp, fo) = out_field!(out.1);
+                ArchivedBTreeMap::resolve_from_len(map.len(), pos + fp, resolver, fo);
+            }
+            _ => unreachable!("resolver does not match the serialized value"),
+        }
+    }
+}
+
+impl<S: ScratchSpace + Serializer + ?Sized> Serialize<S> for Value {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(match self {
+            Value::Null => ValueResolver::Null,
+            Value::Bool(_) => ValueResolver::Bool(()),
+            // Every number variant has a unit resolver
+            Value::Number(_) => ValueResolver::Int(()),
+            Value::String(value) => {
+                ValueResolver::String(ArchivedString::serialize_from_str(value, serializer)?)
+            }
+            Value::Array(values) => {
+                ValueResolver::Array(ArchivedVec::serialize_from_slice(values, serializer)?)
+            }
+            Value::Object(map) => {
+                let entries = sorted_entries(map);
+                // Entries are sorted by key and keys are unique
+                ValueResolver::Map(unsafe {
+                    ArchivedBTreeMap::serialize_from_reverse_iter(
+                        entries.iter().rev().copied(),
+                        serializer,
+                    )?
+                })
+            }
+        })
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Value, D> for ArchivedValue {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Value, D::Error> {
+        Ok(deserialize_value(self))
+    }
+}
+
+// Deserializing never fails, so nested values don't need the deserializer
+fn deserialize_value(value: &ArchivedValue) -> Value {
+    match value {
+        ArchivedValue::Null => Value::Null,
+        ArchivedValue::Bool(value) => Value::Bool(*value),
+        ArchivedValue::Int(value) => Value::Number(from_archived!(*value).into()),
+        ArchivedValue::UInt(value) => Value::Number(from_archived!(*value).into()),
+        // Like `serde_json`, non-finite floats become null
+        ArchivedValue::Float(value) => {
+            Number::from_f64(from_archived!(*value)).map_or(Value::Null, Value::Number)
+        }
+        ArchivedValue::String(value) => Value::String(value.as_str().to_string()),
+        ArchivedValue::Array(values) => {
+            Value::Array(values.iter().map(deserialize_value).collect())
+        }
+        ArchivedValue::Map(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.as_str().to_string(), deserialize_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+impl PartialEq<Value> for ArchivedValue {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (ArchivedValue::Null, Value::Null) => true,
+            (ArchivedValue::Bool(a), Value::Bool(b)) => a == b,
+            (_, Value::Number(b)) => match archived_number(b) {
+                ArchivedNumber::Int(b) => {
+                    matches!(self, ArchivedValue::Int(a) if from_archived!(*a) == b)
+                }
+                ArchivedNumber::UInt(b) => {
+                    matches!(self, ArchivedValue::UInt(a) if from_archived!(*a) == b)
+                }
+                ArchivedNumber::Float(b) => {
+                    matches!(self, ArchivedValue::Float(a) if from_archived!(*a) == b)
+                }
+            },
+            (ArchivedValue::String(a), Value::String(b)) => a == b,
+            (ArchivedValue::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a == b)
+            }
+            (ArchivedValue::Map(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && b.iter()
+                        .all(|(key, b)| matches!(a.get(key.as_str()), Some(a) if a == b))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<ArchivedValue> for Value {
+    #[inline]
+    fn eq(&self, other: &ArchivedValue) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{archived_root, value::ArchivedValue, Deserialize, Infallible};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn serde_json_value() {
+        let value = json!({
+            "name": "rkyv",
+            "version": [0, 7, 39],
+            "zero_copy": true,
+            "license": null,
+            "ratio": 0.5,
+            "big": u64::MAX,
+            "negative": -3,
+            "nested": { "b": [], "a": {} },
+        });
+
+        let bytes = crate::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Value>(&bytes) };
+        assert_eq!(*archived, value);
+        assert_eq!(archived["name"].as_str(), Some("rkyv"));
+        assert_eq!(archived["version"][2].as_i64(), Some(39));
+        assert_eq!(archived["big"].as_u64(), Some(u64::MAX));
+        assert_eq!(archived["negative"].as_i64(), Some(-3));
+        assert!(archived["license"].is_null());
+        assert!(matches!(archived["nested"]["a"], ArchivedValue::Map(_)));
+
+        let deserialized: Value = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        assert_ne!(*archived, json!({ "name": "rkyv" }));
+        assert_ne!(archived["ratio"], json!(1));
+        assert_ne!(archived["negative"], json!(-3.0));
+    }
+
+    #[test]
+    fn serde_json_and_dynamic_values_match() {
+        use crate::value::Value as DynamicValue;
+
+        let value = json!([1, "two", { "three": 3.0 }]);
+        let json_bytes = crate::to_bytes::<_, 256>(&value).unwrap();
+        let dynamic: DynamicValue = unsafe { archived_root::<Value>(&json_bytes) }
+            .deserialize(&mut Infallible)
+            .unwrap();
+        let dynamic_bytes = crate::to_bytes::<_, 256>(&dynamic).unwrap();
+        assert_eq!(json_bytes.as_slice(), dynamic_bytes.as_slice());
+    }
+}
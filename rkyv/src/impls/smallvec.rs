@@ -43,6 +43,13 @@ where
     }
 }
 
+impl<T: PartialEq<A::Item>, A: Array> PartialEq<SmallVec<A>> for ArchivedVec<T> {
+    #[inline]
+    fn eq(&self, other: &SmallVec<A>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{archived_root, ser::Serializer, Deserialize, Infallible};
@@ -60,6 +67,7 @@ mod tests {
         let result = serializer.into_serializer().into_inner();
         let archived = unsafe { archived_root::<SmallVec<[i32; 10]>>(&result[0..end]) };
         assert_eq!(archived.as_slice(), &[10, 20, 40, 80]);
+        assert_eq!(archived, &value);
 
         let deserialized: SmallVec<[i32; 10]> = archived.deserialize(&mut Infallible).unwrap();
         assert_eq!(value, deserialized);
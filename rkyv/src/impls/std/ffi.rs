@@ -1,5 +1,5 @@
 use crate::{
-    ffi::{ArchivedCString, CStringResolver},
+    ffi::{ArchivedCString, ArchivedOsStr, CStringResolver},
     ser::Serializer,
     Archive, ArchivePointee, ArchiveUnsized, Archived, ArchivedMetadata, Deserialize,
     DeserializeUnsized, Fallible, FixedUsize, Serialize, SerializeUnsized,
@@ -7,7 +7,7 @@ use crate::{
 use core::{alloc::Layout, ptr};
 use ptr_meta::Pointee;
 use std::alloc;
-use std::ffi::{CStr, CString};
+use std::ffi::{CStr, CString, OsStr};
 
 // CStr
 
@@ -70,6 +70,64 @@ impl<D: Fallible + ?Sized> DeserializeUnsized<CStr, D> for <CStr as ArchiveUnsiz
     }
 }
 
+// OsStr
+
+impl ArchiveUnsized for OsStr {
+    type Archived = ArchivedOsStr;
+
+    type MetadataResolver = ();
+
+    #[inline]
+    unsafe fn resolve_metadata(
+        &self,
+        _: usize,
+        _: Self::MetadataResolver,
+        out: *mut ArchivedMetadata<Self>,
+    ) {
+        let len = ArchivedOsStr::archived_bytes(self).len();
+        out.write(to_archived!(len as FixedUsize))
+    }
+}
+
+impl<S: Serializer + ?Sized> SerializeUnsized<S> for OsStr {
+    #[inline]
+    fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
+        let result = serializer.pos();
+        serializer.write(&ArchivedOsStr::archived_bytes(self))?;
+        Ok(result)
+    }
+
+    #[inline]
+    fn serialize_metadata(&self, _: &mut S) -> Result<Self::MetadataResolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> DeserializeUnsized<OsStr, D> for ArchivedOsStr {
+    #[inline]
+    unsafe fn deserialize_unsized(
+        &self,
+        _: &mut D,
+        mut alloc: impl FnMut(Layout) -> *mut u8,
+    ) -> Result<*mut (), D::Error> {
+        // An `OsStr` has the same layout as its encoded bytes
+        let os_str = self.to_os_str();
+        let slice = os_str.as_encoded_bytes();
+        if slice.is_empty() {
+            return Ok(ptr::NonNull::<u8>::dangling().as_ptr().cast());
+        }
+        let bytes = alloc(Layout::array::<u8>(slice.len()).unwrap());
+        assert!(!bytes.is_null());
+        ptr::copy_nonoverlapping(slice.as_ptr(), bytes, slice.len());
+        Ok(bytes.cast())
+    }
+
+    #[inline]
+    fn deserialize_metadata(&self, _: &mut D) -> Result<<OsStr as Pointee>::Metadata, D::Error> {
+        Ok(ptr_meta::metadata(&*self.to_os_str()))
+    }
+}
+
 // CString
 
 impl PartialEq<CString> for ArchivedCString {
@@ -1,4 +1,5 @@
 mod collections;
 mod ffi;
 mod net;
+mod path;
 mod time;
@@ -5,7 +5,7 @@ use crate::{
     },
     Archive, Deserialize, Fallible, Serialize,
 };
-use core::{cmp, ptr};
+use core::{cmp, fmt, ptr};
 use std::{
     io,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs},
@@ -97,6 +97,13 @@ impl ArchivedIpv4Addr {
     }
 }
 
+impl fmt::Display for ArchivedIpv4Addr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_ipv4(), f)
+    }
+}
+
 impl PartialEq<Ipv4Addr> for ArchivedIpv4Addr {
     #[inline]
     fn eq(&self, other: &Ipv4Addr) -> bool {
@@ -208,6 +215,13 @@ impl ArchivedIpv6Addr {
     }
 }
 
+impl fmt::Display for ArchivedIpv6Addr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_ipv6(), f)
+    }
+}
+
 impl PartialEq<Ipv6Addr> for ArchivedIpv6Addr {
     #[inline]
     fn eq(&self, other: &Ipv6Addr) -> bool {
@@ -306,6 +320,13 @@ impl ArchivedIpAddr {
     }
 }
 
+impl fmt::Display for ArchivedIpAddr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_ipaddr(), f)
+    }
+}
+
 impl PartialEq<IpAddr> for ArchivedIpAddr {
     #[inline]
     fn eq(&self, other: &IpAddr) -> bool {
@@ -429,6 +450,13 @@ impl ToSocketAddrs for ArchivedSocketAddrV4 {
     }
 }
 
+impl fmt::Display for ArchivedSocketAddrV4 {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_socket_addr_v4(), f)
+    }
+}
+
 impl PartialEq<SocketAddrV4> for ArchivedSocketAddrV4 {
     #[inline]
     fn eq(&self, other: &SocketAddrV4) -> bool {
@@ -508,6 +536,13 @@ impl ToSocketAddrs for ArchivedSocketAddrV6 {
     }
 }
 
+impl fmt::Display for ArchivedSocketAddrV6 {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_socket_addr_v6(), f)
+    }
+}
+
 impl PartialEq<SocketAddrV6> for ArchivedSocketAddrV6 {
     #[inline]
     fn eq(&self, other: &SocketAddrV6) -> bool {
@@ -603,6 +638,13 @@ impl ToSocketAddrs for ArchivedSocketAddr {
     }
 }
 
+impl fmt::Display for ArchivedSocketAddr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_socket_addr(), f)
+    }
+}
+
 impl PartialEq<SocketAddr> for ArchivedSocketAddr {
     #[inline]
     fn eq(&self, other: &SocketAddr) -> bool {
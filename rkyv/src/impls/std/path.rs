@@ -0,0 +1,39 @@
+use crate::{
+    boxed::{ArchivedBox, BoxResolver},
+    ffi::ArchivedOsStr,
+    path::ArchivedPath,
+    ser::Serializer,
+    Archive, Deserialize, Fallible, Serialize,
+};
+use std::path::Path;
+
+// Path
+
+// `Path` can't implement `ArchiveUnsized` because `ptr_meta` doesn't implement `Pointee` for it, so
+// boxed paths are archived through their OS strings instead. An `ArchivedBox<ArchivedPath>` has the
+// same layout as an `ArchivedBox<ArchivedOsStr>` since `ArchivedPath` is a transparent wrapper
+// around an `ArchivedOsStr`.
+
+impl Archive for Box<Path> {
+    type Archived = ArchivedBox<ArchivedPath>;
+    type Resolver = BoxResolver<()>;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedBox::resolve_from_ref(self.as_os_str(), pos, resolver, out.cast());
+    }
+}
+
+impl<S: Serializer + ?Sized> Serialize<S> for Box<Path> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedBox::<ArchivedOsStr>::serialize_from_ref(self.as_os_str(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Box<Path>, D> for ArchivedBox<ArchivedPath> {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Box<Path>, D::Error> {
+        Ok(self.get().to_path().into_owned().into_boxed_path())
+    }
+}
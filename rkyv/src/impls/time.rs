@@ -0,0 +1,244 @@
+use crate::{
+    time::{ArchivedDate, ArchivedDateTime, ArchivedOffsetDateTime},
+    Archive, Deserialize, Fallible, Serialize,
+};
+use ::time::{Date, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+/// The Julian day number of the Unix epoch.
+const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+// Validation checks that archived dates and times convert to time types, so these only return
+// `None` for archives that were not validated
+
+#[inline]
+pub(crate) fn date(date: &ArchivedDate) -> Option<Date> {
+    date.days_since_epoch()
+        .checked_add(UNIX_EPOCH_JULIAN_DAY)
+        .and_then(|day| Date::from_julian_day(day).ok())
+}
+
+#[inline]
+pub(crate) fn utc_date_time(date_time: &ArchivedDateTime) -> Option<OffsetDateTime> {
+    // Leap seconds roll over into the next second
+    let nanos =
+        date_time.timestamp() as i128 * NANOS_PER_SEC + date_time.timestamp_subsec_nanos() as i128;
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+}
+
+#[inline]
+pub(crate) fn offset_date_time(date_time: &ArchivedOffsetDateTime) -> Option<OffsetDateTime> {
+    let offset = UtcOffset::from_whole_seconds(date_time.offset_seconds()).ok()?;
+    let utc = utc_date_time(date_time.utc())?;
+    // The date time has to be in range in its offset as well as in UTC
+    OffsetDateTime::from_unix_timestamp(utc.unix_timestamp() + offset.whole_seconds() as i64)
+        .ok()?;
+    Some(utc.to_offset(offset))
+}
+
+// Date
+
+impl Archive for Date {
+    type Archived = ArchivedDate;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedDate::emplace(self.to_julian_day() - UNIX_EPOCH_JULIAN_DAY, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Date {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Date, D> for ArchivedDate {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Date, D::Error> {
+        Ok(date(self).expect("archived date is out of range for time"))
+    }
+}
+
+impl PartialEq<Date> for ArchivedDate {
+    #[inline]
+    fn eq(&self, other: &Date) -> bool {
+        self.days_since_epoch() == other.to_julian_day() - UNIX_EPOCH_JULIAN_DAY
+    }
+}
+
+// PrimitiveDateTime
+
+impl Archive for PrimitiveDateTime {
+    type Archived = ArchivedDateTime;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedDateTime::emplace(self.assume_utc().unix_timestamp(), self.nanosecond(), out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for PrimitiveDateTime {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<PrimitiveDateTime, D> for ArchivedDateTime {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<PrimitiveDateTime, D::Error> {
+        let utc = utc_date_time(self).expect("archived date time is out of range for time");
+        Ok(PrimitiveDateTime::new(utc.date(), utc.time()))
+    }
+}
+
+impl PartialEq<PrimitiveDateTime> for ArchivedDateTime {
+    #[inline]
+    fn eq(&self, other: &PrimitiveDateTime) -> bool {
+        self.timestamp() == other.assume_utc().unix_timestamp()
+            && self.timestamp_subsec_nanos() == other.nanosecond()
+    }
+}
+
+// OffsetDateTime
+
+impl Archive for OffsetDateTime {
+    type Archived = ArchivedOffsetDateTime;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedOffsetDateTime::emplace(
+            self.unix_timestamp(),
+            self.nanosecond(),
+            self.offset().whole_seconds(),
+            out,
+        );
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for OffsetDateTime {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<OffsetDateTime, D> for ArchivedOffsetDateTime {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<OffsetDateTime, D::Error> {
+        Ok(offset_date_time(self).expect("archived date time is out of range for time"))
+    }
+}
+
+impl PartialEq<OffsetDateTime> for ArchivedOffsetDateTime {
+    #[inline]
+    fn eq(&self, other: &OffsetDateTime) -> bool {
+        self.utc().timestamp() == other.unix_timestamp()
+            && self.utc().timestamp_subsec_nanos() == other.nanosecond()
+            && self.offset_seconds() == other.offset().whole_seconds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        ser::{serializers::CoreSerializer, Serializer},
+        Deserialize, Infallible, Serialize,
+    };
+    use ::time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+    use core::fmt::Debug;
+
+    fn test_archive<T>(value: &T)
+    where
+        T: Debug + PartialEq + Serialize<CoreSerializer<256, 256>>,
+        T::Archived: PartialEq<T> + Deserialize<T, Infallible>,
+    {
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer.serialize_value(value).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<T>(&result[0..end]) };
+        assert!(archived == value);
+
+        let deserialized: T = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(value, &deserialized);
+    }
+
+    #[test]
+    fn date_and_time() {
+        let date = Date::from_calendar_date(2021, Month::June, 15).unwrap();
+        let time = Time::from_hms_nano(12, 34, 56, 789).unwrap();
+        let date_time = PrimitiveDateTime::new(date, time);
+        let offset_date_time = date_time.assume_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+
+        test_archive(&date);
+        test_archive(&Date::from_calendar_date(1969, Month::December, 31).unwrap());
+        test_archive(&date_time);
+        test_archive(&offset_date_time);
+        test_archive(&OffsetDateTime::UNIX_EPOCH);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_interop() {
+        use chrono::{DateTime, FixedOffset, NaiveDate};
+
+        let date = Date::from_calendar_date(2021, Month::June, 15).unwrap();
+        let offset_date_time = PrimitiveDateTime::new(date, Time::from_hms(12, 34, 56).unwrap())
+            .assume_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+
+        let mut serializer = CoreSerializer::<256, 256>::default();
+        serializer
+            .serialize_value(&(date, offset_date_time))
+            .unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<(Date, OffsetDateTime)>(&result[0..end]) };
+
+        let chrono_date: NaiveDate = archived.0.deserialize(&mut Infallible).unwrap();
+        assert_eq!(chrono_date, NaiveDate::from_ymd_opt(2021, 6, 15).unwrap());
+        let chrono_date_time: DateTime<FixedOffset> =
+            archived.1.deserialize(&mut Infallible).unwrap();
+        assert_eq!(
+            chrono_date_time,
+            DateTime::parse_from_rfc3339("2021-06-15T12:34:56-05:00").unwrap()
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn check_offset_date_time() {
+        use crate::time::{ArchivedDate, ArchivedOffsetDateTime};
+        use bytecheck::CheckBytes;
+        use core::mem::MaybeUninit;
+
+        fn check(secs: i64, nanos: u32, offset: i32) -> bool {
+            let mut value = MaybeUninit::<ArchivedOffsetDateTime>::zeroed();
+            unsafe {
+                ArchivedOffsetDateTime::emplace(secs, nanos, offset, value.as_mut_ptr());
+                ArchivedOffsetDateTime::check_bytes(value.as_ptr(), &mut Infallible).is_ok()
+            }
+        }
+
+        assert!(check(1_623_760_496, 789, -5 * 3600));
+        // time supports offsets of up to 25:59:59, but chrono only supports offsets under a day
+        assert_eq!(check(0, 0, 90_000), cfg!(not(feature = "chrono")));
+        assert!(!check(0, 0, 93_600));
+        assert!(!check(0, 0, -93_600));
+        // Dates must be in range for time
+        assert!(!check(i64::MAX / 2, 0, 0));
+
+        let mut date = MaybeUninit::<ArchivedDate>::zeroed();
+        unsafe {
+            ArchivedDate::emplace(i32::MAX, date.as_mut_ptr());
+            ArchivedDate::check_bytes(date.as_ptr(), &mut Infallible).unwrap_err();
+        }
+    }
+}
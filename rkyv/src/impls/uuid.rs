@@ -51,10 +51,27 @@ mod rkyv_tests {
 
         assert_eq!(&u, archived);
 
-        let deserialized = archived
+        let deserialized: Uuid = archived
             .deserialize(&mut Infallible)
             .expect("failed to deserialize uuid");
 
         assert_eq!(u, deserialized);
     }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_check_archived() {
+        use crate::check_archived_root;
+
+        let u = Uuid::parse_str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4").unwrap();
+
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer
+            .serialize_value(&u)
+            .expect("failed to archive uuid");
+        let buf = serializer.into_inner();
+        let archived = check_archived_root::<Uuid>(buf.as_ref()).expect("failed to check uuid");
+
+        assert_eq!(&u, archived);
+    }
 }
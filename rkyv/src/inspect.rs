@@ -0,0 +1,847 @@
+//! Inspecting the contents of archives.
+//!
+//! An [`Inspector`] walks an archive using the [`Schema`] of its root type and prints a tree of
+//! every value it reaches, with the position of each value, the targets of relative pointers, and
+//! the padding inside of structs and enums. After the tree, it summarizes how many bytes of the
+//! archive were reachable from the root and lists the regions that weren't, which makes it easy to
+//! see where an archive is wasting space:
+//!
+//! ```
+//! use rkyv::{inspect::Inspector, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(schema)]
+//! #[archive_attr(repr(C))]
+//! struct Sensor {
+//!     id: u16,
+//!     name: String,
+//!     readings: Vec<i32>,
+//! }
+//!
+//! let value = Sensor {
+//!     id: 7,
+//!     name: "thermometer".to_string(),
+//!     readings: vec![21, -4],
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//!
+//! assert_eq!(
+//!     Inspector::new::<Sensor>(&bytes).to_string(),
+//!     "\
+//!         0x0014  root: ArchivedSensor (20 bytes)\n\
+//!         0x0014    id: u16 = 7\n\
+//!         0x0016    padding (2 bytes)\n\
+//!         0x0018    name: String = \"thermometer\" -> 0x0000..0x000b\n\
+//!         0x0020    readings: Vec<i32> (2 elements) -> 0x000c..0x0014\n\
+//!         0x000c      [0]: i32 = 21\n\
+//!         0x0010      [1]: i32 = -4\n\
+//!         40 bytes: 39 reachable, 2 padding, 1 unreferenced, 0 errors\n\
+//!         unreferenced 0x000b..0x000c (1 byte)\
+//!     ",
+//! );
+//! ```
+//!
+//! Every read is checked against the bounds of the archive, so inspecting an archive is safe even
+//! if the archive is corrupted. Relative pointers that point outside of the archive and invalid
+//! values like out-of-range enum tags are printed as errors instead of being followed. Values
+//! reachable from more than one pointer are printed once for each pointer, and nesting deeper than
+//! [`max_depth`](Inspector::max_depth) is cut off, so that cycles in corrupted archives terminate.
+//!
+//! [`zero_padding`] uses the same walk to zero the padding of an archive, so that no padding bytes
+//! leak the contents of memory. Like the printed tree, it doesn't look inside of opaque values.
+//!
+//! [`hex_dump`] prints the raw bytes of a region of an archive, for example to look at one of the
+//! unreferenced regions found by an inspector.
+
+use crate::{
+    rel_ptr::checked_target,
+    schema::{read_tag, round_up, ArchiveSchema, FieldSchema, Schema},
+    string::repr::{ArchivedStringRepr, INLINE_CAPACITY},
+    vec::ArchivedVec,
+    Archived, RelPtr,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{fmt, mem::size_of, ops::Range, str};
+
+const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Walks an archive and prints its contents.
+///
+/// The contents are printed with the `Display` implementation, and [`report`](Inspector::report)
+/// returns the summary without printing anything.
+#[derive(Clone, Copy)]
+pub struct Inspector<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    schema: &'static Schema,
+    max_depth: usize,
+}
+
+impl<'a> Inspector<'a> {
+    /// Returns an inspector for an archive of `T` with the root at the end, as for
+    /// [`archived_root`](crate::archived_root).
+    #[inline]
+    pub fn new<T: ArchiveSchema>(bytes: &'a [u8]) -> Self {
+        let schema = &T::SCHEMA;
+        Self::with_root(bytes, bytes.len().saturating_sub(schema.size()), schema)
+    }
+
+    /// Returns an inspector for an archive with a root value of the given schema at the given
+    /// position.
+    #[inline]
+    pub fn with_root(bytes: &'a [u8], pos: usize, schema: &'static Schema) -> Self {
+        Self {
+            bytes,
+            pos,
+            schema,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets the maximum depth of values below the root to walk. The default is 64.
+    #[inline]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Walks the archive and returns a summary of its contents.
+    pub fn report(&self) -> Report {
+        let mut sink = Sink;
+        let mut walker = Walker::new(self, &mut sink);
+        // Writing to the sink never fails
+        let _ = walker.walk(self.pos, 0, Label::Name("root"), self.schema);
+        walker.finish()
+    }
+}
+
+impl fmt::Display for Inspector<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut walker = Walker::new(self, f);
+        walker.walk(self.pos, 0, Label::Name("root"), self.schema)?;
+        let report = walker.finish();
+
+        let reachable = report.reachable_len();
+        write!(
+            f,
+            "{} bytes: {} reachable, {} padding, {} unreferenced, {} errors",
+            report.len,
+            reachable,
+            report.padding(),
+            report.len - reachable,
+            report.errors,
+        )?;
+        for range in report.unreferenced() {
+            write!(
+                f,
+                "\nunreferenced {:#06x}..{:#06x} ({})",
+                range.start,
+                range.end,
+                Bytes(range.len())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A summary of the contents of an archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    len: usize,
+    reachable: Vec<Range<usize>>,
+    padding: Vec<Range<usize>>,
+    errors: usize,
+}
+
+impl Report {
+    /// Returns the regions of the archive that are reachable from the root, sorted and merged.
+    ///
+    /// Padding inside of structs and enums is included in the reachable regions.
+    #[inline]
+    pub fn reachable(&self) -> &[Range<usize>] {
+        &self.reachable
+    }
+
+    /// Returns the number of bytes of the archive that are reachable from the root.
+    #[inline]
+    pub fn reachable_len(&self) -> usize {
+        self.reachable.iter().map(|r| r.len()).sum()
+    }
+
+    /// Returns the regions of the archive that are not reachable from the root.
+    ///
+    /// These regions are padding between values, or values that nothing points to.
+    pub fn unreferenced(&self) -> Vec<Range<usize>> {
+        let mut result = Vec::new();
+        let mut start = 0;
+        for range in self.reachable.iter() {
+            if range.start > start {
+                result.push(start..range.start);
+            }
+            start = range.end;
+        }
+        if self.len > start {
+            result.push(start..self.len);
+        }
+        result
+    }
+
+    /// Returns the number of padding bytes inside of the structs and enums that were walked.
+    ///
+    /// Values that were reached more than once are counted once for each time.
+    #[inline]
+    pub fn padding(&self) -> usize {
+        self.padding.iter().map(|r| r.len()).sum()
+    }
+
+    /// Returns the regions of padding inside of the structs and enums that were walked, in the
+    /// order they were walked.
+    ///
+    /// Values that were reached more than once have their padding listed once for each time.
+    #[inline]
+    pub fn padding_regions(&self) -> &[Range<usize>] {
+        &self.padding
+    }
+
+    /// Returns the number of errors found while walking the archive.
+    #[inline]
+    pub fn errors(&self) -> usize {
+        self.errors
+    }
+}
+
+struct Sink;
+
+impl fmt::Write for Sink {
+    #[inline]
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Label {
+    Name(&'static str),
+    Index(usize),
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Label::Name(name) => write!(f, "{}", name),
+            Label::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 1 {
+            write!(f, "1 byte")
+        } else {
+            write!(f, "{} bytes", self.0)
+        }
+    }
+}
+
+struct TypeName(&'static Schema);
+
+impl fmt::Display for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self.0 {
+            Schema::Unit => write!(f, "()"),
+            Schema::Bool => write!(f, "bool"),
+            Schema::I8 => write!(f, "i8"),
+            Schema::I16 => write!(f, "i16"),
+            Schema::I32 => write!(f, "i32"),
+            Schema::I64 => write!(f, "i64"),
+            Schema::I128 => write!(f, "i128"),
+            Schema::U8 => write!(f, "u8"),
+            Schema::U16 => write!(f, "u16"),
+            Schema::U32 => write!(f, "u32"),
+            Schema::U64 => write!(f, "u64"),
+            Schema::U128 => write!(f, "u128"),
+            Schema::F32 => write!(f, "f32"),
+            Schema::F64 => write!(f, "f64"),
+            Schema::Char => write!(f, "char"),
+            Schema::String => write!(f, "String"),
+            Schema::Array { element, len } => write!(f, "[{}; {}]", TypeName(element), len),
+            Schema::Vec { element } => write!(f, "Vec<{}>", TypeName(element)),
+            Schema::Box { inner } => write!(f, "Box<{}>", TypeName(inner)),
+            Schema::Option { inner } => write!(f, "Option<{}>", TypeName(inner)),
+            Schema::Struct { name, .. }
+            | Schema::Enum { name, .. }
+            | Schema::Opaque { name, .. } => write!(f, "{}", name),
+        }
+    }
+}
+
+struct Walker<'a, 'w> {
+    bytes: &'a [u8],
+    out: &'w mut dyn fmt::Write,
+    max_depth: usize,
+    reachable: Vec<Range<usize>>,
+    padding: Vec<Range<usize>>,
+    errors: usize,
+}
+
+impl<'a, 'w> Walker<'a, 'w> {
+    fn new(inspector: &Inspector<'a>, out: &'w mut dyn fmt::Write) -> Self {
+        Self {
+            bytes: inspector.bytes,
+            out,
+            max_depth: inspector.max_depth,
+            reachable: Vec::new(),
+            padding: Vec::new(),
+            errors: 0,
+        }
+    }
+
+    fn finish(self) -> Report {
+        let mut ranges = self.reachable;
+        ranges.sort_by_key(|r| r.start);
+        let mut reachable: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges.into_iter().filter(|r| !r.is_empty()) {
+            match reachable.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => reachable.push(range),
+            }
+        }
+        Report {
+            len: self.bytes.len(),
+            reachable,
+            padding: self.padding,
+            errors: self.errors,
+        }
+    }
+
+    #[inline]
+    fn bytes_at(&self, pos: usize, len: usize) -> Option<&'a [u8]> {
+        self.bytes.get(pos..pos.checked_add(len)?)
+    }
+
+    /// # Safety
+    ///
+    /// Every bit pattern must be a valid `T`.
+    #[inline]
+    unsafe fn read<T>(&self, pos: usize) -> Option<T> {
+        let bytes = self.bytes_at(pos, size_of::<T>())?;
+        Some(bytes.as_ptr().cast::<T>().read_unaligned())
+    }
+
+    fn line(
+        &mut self,
+        pos: usize,
+        depth: usize,
+        label: Label,
+        args: fmt::Arguments<'_>,
+    ) -> fmt::Result {
+        writeln!(
+            self.out,
+            "{:#06x}  {:indent$}{}: {}",
+            pos,
+            "",
+            label,
+            args,
+            indent = depth * 2,
+        )
+    }
+
+    fn error(
+        &mut self,
+        pos: usize,
+        depth: usize,
+        label: Label,
+        schema: &'static Schema,
+        args: fmt::Arguments<'_>,
+    ) -> fmt::Result {
+        self.errors += 1;
+        self.line(
+            pos,
+            depth,
+            label,
+            format_args!("{} = <error: {}>", TypeName(schema), args),
+        )
+    }
+
+    fn padding(&mut self, pos: usize, depth: usize, len: usize) -> fmt::Result {
+        self.padding.push(pos..pos + len);
+        writeln!(
+            self.out,
+            "{:#06x}  {:indent$}padding ({})",
+            pos,
+            "",
+            Bytes(len),
+            indent = depth * 2,
+        )
+    }
+
+    fn rel_target(&self, base: usize, offset: isize) -> Result<usize, crate::rel_ptr::TargetError> {
+        checked_target(base, offset, self.bytes.len())
+    }
+
+    fn walk(
+        &mut self,
+        pos: usize,
+        depth: usize,
+        label: Label,
+        schema: &'static Schema,
+    ) -> fmt::Result {
+        let size = schema.size();
+        if self.bytes_at(pos, size).is_none() {
+            return self.error(
+                pos,
+                depth,
+                label,
+                schema,
+                format_args!(
+                    "{} bytes at {:#x} are out of bounds of the {}-byte archive",
+                    size,
+                    pos,
+                    self.bytes.len()
+                ),
+            );
+        }
+        self.reachable.push(pos..pos + size);
+        if depth > self.max_depth {
+            return self.line(
+                pos,
+                depth,
+                label,
+                format_args!("{} = <max depth reached>", TypeName(schema)),
+            );
+        }
+
+        // Every read below is in bounds, and only reads types that are valid for any bit pattern
+        macro_rules! primitive {
+            ($ty:ty) => {{
+                let value = from_archived!(unsafe { self.read::<Archived<$ty>>(pos).unwrap() });
+                self.line(
+                    pos,
+                    depth,
+                    label,
+                    format_args!("{} = {:?}", TypeName(schema), value),
+                )
+            }};
+            ($ty:ty, $value:ident => $expr:expr) => {{
+                let $value = unsafe { self.read::<$ty>(pos).unwrap() };
+                self.line(
+                    pos,
+                    depth,
+                    label,
+                    format_args!("{} = {:?}", TypeName(schema), $expr),
+                )
+            }};
+        }
+
+        match *schema {
+            Schema::Unit => self.line(pos, depth, label, format_args!("() = ()")),
+            Schema::Bool => match unsafe { self.read::<u8>(pos).unwrap() } {
+                value @ (0 | 1) => {
+                    self.line(pos, depth, label, format_args!("bool = {}", value == 1))
+                }
+                value => self.error(
+                    pos,
+                    depth,
+                    label,
+                    schema,
+                    format_args!("invalid bool {:#04x}", value),
+                ),
+            },
+            Schema::I8 => primitive!(i8, value => value),
+            Schema::I16 => primitive!(i16),
+            Schema::I32 => primitive!(i32),
+            Schema::I64 => primitive!(i64),
+            Schema::I128 => primitive!(Archived<i128>, value => value.value()),
+            Schema::U8 => primitive!(u8, value => value),
+            Schema::U16 => primitive!(u16),
+            Schema::U32 => primitive!(u32),
+            Schema::U64 => primitive!(u64),
+            Schema::U128 => primitive!(Archived<u128>, value => value.value()),
+            Schema::F32 => primitive!(f32),
+            Schema::F64 => primitive!(f64),
+            Schema::Char => {
+                let value = unsafe { self.read::<Archived<char>>(pos).unwrap() }.as_u32();
+                match char::from_u32(value) {
+                    Some(c) => self.line(pos, depth, label, format_args!("char = {:?}", c)),
+                    None => self.error(
+                        pos,
+                        depth,
+                        label,
+                        schema,
+                        format_args!("invalid char {:#x}", value),
+                    ),
+                }
+            }
+            Schema::String => self.walk_string(pos, depth, label, schema),
+            Schema::Array { element, len } => {
+                self.line(pos, depth, label, format_args!("{}", TypeName(schema)))?;
+                for i in 0..len {
+                    self.walk(
+                        pos + i * element.size(),
+                        depth + 1,
+                        Label::Index(i),
+                        element,
+                    )?;
+                }
+                Ok(())
+            }
+            Schema::Vec { element } => {
+                let header = unsafe { self.read::<ArchivedVec<u8>>(pos).unwrap() };
+                let len = header.len();
+                let rel_ptr = header.rel_ptr();
+                let base = pos + (rel_ptr.base() as usize - &header as *const _ as usize);
+                let target = match self.rel_target(base, rel_ptr.offset()) {
+                    Ok(target) => target,
+                    Err(e) => return self.error(pos, depth, label, schema, format_args!("{}", e)),
+                };
+                let end = match len
+                    .checked_mul(element.size())
+                    .and_then(|size| target.checked_add(size))
+                {
+                    Some(end) if end <= self.bytes.len() => end,
+                    _ => {
+                        return self.error(
+                            pos,
+                            depth,
+                            label,
+                            schema,
+                            format_args!(
+                                "{} elements at {:#x} are out of bounds of the {}-byte archive",
+                                len,
+                                target,
+                                self.bytes.len(),
+                            ),
+                        )
+                    }
+                };
+                self.line(
+                    pos,
+                    depth,
+                    label,
+                    format_args!(
+                        "{} ({} elements) -> {:#06x}..{:#06x}",
+                        TypeName(schema),
+                        len,
+                        target,
+                        end
+                    ),
+                )?;
+                for i in 0..len {
+                    self.walk(
+                        target + i * element.size(),
+                        depth + 1,
+                        Label::Index(i),
+                        element,
+                    )?;
+                }
+                Ok(())
+            }
+            Schema::Box { inner } => {
+                // Archived boxes are transparent wrappers around a relative pointer
+                let rel_ptr = unsafe { self.read::<RelPtr<u8>>(pos).unwrap() };
+                let base = pos + (rel_ptr.base() as usize - &rel_ptr as *const _ as usize);
+                match self.rel_target(base, rel_ptr.offset()) {
+                    Ok(target) => {
+                        self.line(
+                            pos,
+                            depth,
+                            label,
+                            format_args!("{} -> {:#06x}", TypeName(schema), target),
+                        )?;
+                        self.walk(target, depth + 1, Label::Name("*"), inner)
+                    }
+                    Err(e) => self.error(pos, depth, label, schema, format_args!("{}", e)),
+                }
+            }
+            Schema::Option { inner } => match unsafe { self.read::<u8>(pos).unwrap() } {
+                0 => self.line(
+                    pos,
+                    depth,
+                    label,
+                    format_args!("{} = None", TypeName(schema)),
+                ),
+                1 => {
+                    self.line(
+                        pos,
+                        depth,
+                        label,
+                        format_args!("{} = Some", TypeName(schema)),
+                    )?;
+                    let offset = round_up(1, inner.align());
+                    self.walk(pos + offset, depth + 1, Label::Name("0"), inner)
+                }
+                tag => self.error(
+                    pos,
+                    depth,
+                    label,
+                    schema,
+                    format_args!("invalid option tag {}", tag),
+                ),
+            },
+            Schema::Struct { name, fields, .. } => {
+                self.line(
+                    pos,
+                    depth,
+                    label,
+                    format_args!("{} ({})", name, Bytes(size)),
+                )?;
+                self.walk_fields(pos, depth + 1, 0, size, fields)
+            }
+            Schema::Enum {
+                name,
+                tag,
+                variants,
+                ..
+            } => {
+                let value = unsafe { read_tag(self.bytes[pos..].as_ptr(), tag) };
+                let variant = value.and_then(|value| variants.iter().find(|v| v.tag == value));
+                match variant {
+                    Some(variant) => {
+                        self.line(
+                            pos,
+                            depth,
+                            label,
+                            format_args!("{}::{} ({})", name, variant.name, Bytes(size)),
+                        )?;
+                        self.line(
+                            pos,
+                            depth + 1,
+                            Label::Name("tag"),
+                            format_args!("{} = {}", TypeName(tag), variant.tag),
+                        )?;
+                        self.walk_fields(pos, depth + 1, tag.size(), size, variant.fields)
+                    }
+                    None => self.error(
+                        pos,
+                        depth,
+                        label,
+                        schema,
+                        format_args!("invalid tag {}", value.unwrap_or_default()),
+                    ),
+                }
+            }
+            Schema::Opaque { name, .. } => self.line(
+                pos,
+                depth,
+                label,
+                format_args!("{} (opaque, {})", name, Bytes(size)),
+            ),
+        }
+    }
+
+    fn walk_string(
+        &mut self,
+        pos: usize,
+        depth: usize,
+        label: Label,
+        schema: &'static Schema,
+    ) -> fmt::Result {
+        let repr = unsafe { self.read::<ArchivedStringRepr>(pos).unwrap() };
+        let len = repr.len();
+        let (bytes, target) = if repr.is_inline() {
+            if len > INLINE_CAPACITY {
+                return self.error(
+                    pos,
+                    depth,
+                    label,
+                    schema,
+                    format_args!("inline length {} is too long", len),
+                );
+            }
+            (repr.bytes(), None)
+        } else {
+            let offset = unsafe { repr.out_of_line_offset() };
+            let target = match self.rel_target(pos, offset) {
+                Ok(target) => target,
+                Err(e) => return self.error(pos, depth, label, schema, format_args!("{}", e)),
+            };
+            match self.bytes_at(target, len) {
+                Some(bytes) => {
+                    self.reachable.push(target..target + len);
+                    (bytes, Some(target))
+                }
+                None => {
+                    return self.error(
+                        pos,
+                        depth,
+                        label,
+                        schema,
+                        format_args!(
+                            "{} bytes at {:#x} are out of bounds of the {}-byte archive",
+                            len,
+                            target,
+                            self.bytes.len(),
+                        ),
+                    )
+                }
+            }
+        };
+        let value = match str::from_utf8(bytes) {
+            Ok(value) => value,
+            Err(e) => return self.error(pos, depth, label, schema, format_args!("{}", e)),
+        };
+        match target {
+            Some(target) => self.line(
+                pos,
+                depth,
+                label,
+                format_args!(
+                    "String = {:?} -> {:#06x}..{:#06x}",
+                    value,
+                    target,
+                    target + len
+                ),
+            ),
+            None => self.line(pos, depth, label, format_args!("String = {:?}", value)),
+        }
+    }
+
+    fn walk_fields(
+        &mut self,
+        pos: usize,
+        depth: usize,
+        start: usize,
+        size: usize,
+        fields: &'static [FieldSchema],
+    ) -> fmt::Result {
+        let mut fields = fields.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|f| f.offset);
+
+        let mut end = start;
+        for field in fields {
+            if field.offset > end {
+                self.padding(pos + end, depth, field.offset - end)?;
+            }
+            self.walk(
+                pos + field.offset,
+                depth,
+                Label::Name(field.name),
+                field.schema,
+            )?;
+            end = end.max(field.offset + field.schema.size());
+        }
+        if size > end {
+            self.padding(pos + end, depth, size - end)?;
+        }
+        Ok(())
+    }
+}
+
+/// Zeroes the padding inside of every struct and enum reachable from the root of an archive of
+/// `T`, with the root at the end as for [`archived_root`](crate::archived_root).
+///
+/// Serializers zero the bytes of each value before resolving it, but a custom [`Archive`]
+/// implementation that writes a whole struct into its output copies the padding bytes of that
+/// struct as well, and so do the copy optimizations for slices that assert they are safe to copy.
+/// Those padding bytes can contain whatever was in memory next to the value, like secrets that
+/// shouldn't end up in an archive. Zeroing the padding of an archive after serializing it
+/// guarantees that none of the padding bytes that can be walked leak memory, and that archives of
+/// equal values are byte-for-byte equal.
+///
+/// The archive is walked the same way as by an [`Inspector`], so zeroing the padding of a corrupted
+/// archive is safe and only zeroes padding in the parts of it that could be walked. Values with a
+/// [`Schema::Opaque`] schema can't be walked, so their padding and everything they point to is
+/// left as it is. That includes fields archived with a wrapper and fields with omitted bounds, like
+/// the recursive fields of recursive types. If a value with secrets can be reached through an
+/// opaque value, its padding isn't guaranteed to be zeroed. Returns the report of the walk, whose
+/// [`padding_regions`](Report::padding_regions) are the regions that were zeroed.
+///
+/// [`Archive`]: crate::Archive
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{inspect::zero_padding, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive(schema)]
+/// #[archive_attr(repr(C))]
+/// struct Credentials {
+///     kind: u8,
+///     key: u64,
+/// }
+///
+/// let value = Credentials { kind: 1, key: 0x5ec2e7 };
+/// let mut bytes = rkyv::to_bytes::<_, 64>(&value).unwrap();
+///
+/// // Pretend that the padding after the kind leaked some memory
+/// bytes[1..8].fill(0xaa);
+///
+/// let report = zero_padding::<Credentials>(&mut bytes);
+/// assert_eq!(report.padding_regions(), [1..8]);
+/// assert_eq!(&bytes[0..8], [1, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+pub fn zero_padding<T: ArchiveSchema>(bytes: &mut [u8]) -> Report {
+    let report = Inspector::new::<T>(bytes).report();
+    for range in report.padding_regions() {
+        bytes[range.clone()].fill(0);
+    }
+    report
+}
+
+/// Returns a hex dump of the given range of bytes.
+///
+/// The range is clamped to the bounds of the bytes. The dump is printed with its `Display`
+/// implementation, with sixteen bytes on each line:
+///
+/// ```
+/// use rkyv::inspect::hex_dump;
+///
+/// let bytes = b"hello, archive!\x00\x01\x02";
+/// assert_eq!(
+///     hex_dump(bytes, 4..18).to_string(),
+///     "\
+///         0x0004  6f 2c 20 61 72 63 68 69  76 65 21 00 01 02        |o, archive!...|\
+///     ",
+/// );
+/// ```
+#[inline]
+pub fn hex_dump(bytes: &[u8], range: Range<usize>) -> HexDump<'_> {
+    let end = range.end.min(bytes.len());
+    let start = range.start.min(end);
+    HexDump {
+        bytes: &bytes[start..end],
+        start,
+    }
+}
+
+/// A hex dump of a range of bytes, returned by [`hex_dump`].
+pub struct HexDump<'a> {
+    bytes: &'a [u8],
+    start: usize,
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.bytes.chunks(16).enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:#06x} ", self.start + i * 16)?;
+            for j in 0..16 {
+                if j == 8 {
+                    write!(f, " ")?;
+                }
+                match row.get(j) {
+                    Some(b) => write!(f, " {:02x}", b)?,
+                    None => write!(f, "   ")?,
+                }
+            }
+            write!(f, "  |")?;
+            for &b in row {
+                let c = if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            write!(f, "|")?;
+        }
+        Ok(())
+    }
+}
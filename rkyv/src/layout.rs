@@ -0,0 +1,185 @@
+//! Introspecting the layouts of archived types.
+//!
+//! Deriving `Archive` with `#[archive(layout)]` implements [`ArchiveLayout`] for a struct, which
+//! describes the offset, size, and alignment of each field of its archived type as a constant. This
+//! can be used to document the archived format for other readers, or to check in tests that the
+//! format hasn't changed:
+//!
+//! ```
+//! use rkyv::{layout::ArchiveLayout, Archive};
+//!
+//! #[derive(Archive)]
+//! #[archive(layout)]
+//! #[archive_attr(repr(C))]
+//! struct Header {
+//!     magic: u32,
+//!     version: u16,
+//!     flags: u8,
+//!     checksum: u64,
+//! }
+//!
+//! const LAYOUT: rkyv::layout::StructLayout = Header::LAYOUT;
+//! assert_eq!(LAYOUT.size, 16);
+//! assert_eq!(LAYOUT.field("checksum").unwrap().offset, 8);
+//! assert_eq!(LAYOUT.padding(), 1);
+//!
+//! assert_eq!(
+//!     LAYOUT.to_string(),
+//!     "\
+//!         ArchivedHeader (size 16, align 8)\n\
+//!         \x20 offset  size  align  field\n\
+//!         \x20      0     4      4  magic: u32\n\
+//!         \x20      4     2      2  version: u16\n\
+//!         \x20      6     1      1  flags: u8\n\
+//!         \x20      8     8      8  checksum: u64\
+//!     "
+//! );
+//! ```
+//!
+//! Layouts can also find the bytes that a field occupies, either directly from an archived value
+//! with [`ArchiveLayout::field_bytes`] or as a range of the buffer with
+//! [`StructLayout::field_range`]. This lets single fields be hashed or sent somewhere else without
+//! copying them out of the archive:
+//!
+//! ```
+//! use rkyv::{archived_root, layout::ArchiveLayout, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(layout)]
+//! struct Reading {
+//!     sensor: u32,
+//!     value: f64,
+//! }
+//!
+//! let bytes = rkyv::to_bytes::<_, 256>(&Reading { sensor: 3, value: 0.5 }).unwrap();
+//! let archived = unsafe { archived_root::<Reading>(&bytes) };
+//!
+//! let value = Reading::field_bytes(archived, "value").unwrap();
+//! assert_eq!(value, &0.5f64.to_ne_bytes());
+//!
+//! let root_pos = bytes.len() - core::mem::size_of::<ArchivedReading>();
+//! let range = Reading::LAYOUT.field_range(root_pos, "value").unwrap();
+//! assert_eq!(&bytes[range], value);
+//! ```
+//!
+//! Without the `strict` feature, archived structs that aren't explicitly `repr(C)` use the default
+//! Rust layout, which may change between compilations. With the `strict` feature, every archived
+//! struct and every archived type provided by rkyv is `repr(C)`, so the layouts described here are
+//! fully determined by the declarations of the types and are stable across platforms and
+//! compilations.
+
+use crate::Archive;
+use core::{fmt, ops::Range, slice};
+
+/// The layout of a field of an archived struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldLayout {
+    /// The name of the field in the archived struct. Fields of tuple structs are named by their
+    /// index.
+    pub name: &'static str,
+    /// The type of the original field, before it was archived.
+    pub ty: &'static str,
+    /// The offset of the field from the start of the archived struct in bytes.
+    pub offset: usize,
+    /// The size of the archived field in bytes.
+    pub size: usize,
+    /// The alignment of the archived field in bytes.
+    pub align: usize,
+}
+
+impl FieldLayout {
+    /// Returns the range of bytes that the field occupies, relative to the start of the archived
+    /// struct.
+    #[inline]
+    pub const fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+}
+
+/// The layout of an archived struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StructLayout {
+    /// The name of the archived struct.
+    pub name: &'static str,
+    /// The size of the archived struct in bytes.
+    pub size: usize,
+    /// The alignment of the archived struct in bytes.
+    pub align: usize,
+    /// The layouts of the fields of the archived struct, in the order they're declared.
+    pub fields: &'static [FieldLayout],
+}
+
+impl StructLayout {
+    /// Returns the layout of the field with the given name, or `None` if the struct doesn't have
+    /// that field.
+    #[inline]
+    pub fn field(&self, name: &str) -> Option<&'static FieldLayout> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Returns the range of bytes that the field with the given name occupies in an archived struct
+    /// at `pos`, or `None` if the struct doesn't have that field.
+    ///
+    /// Only the bytes of the field itself are included. Data that the field points to, like the
+    /// contents of a string or vector, is elsewhere in the archive.
+    #[inline]
+    pub fn field_range(&self, pos: usize, name: &str) -> Option<Range<usize>> {
+        self.field(name)
+            .map(|field| pos + field.offset..pos + field.offset + field.size)
+    }
+
+    /// Returns the number of padding bytes in the archived struct.
+    #[inline]
+    pub fn padding(&self) -> usize {
+        self.size - self.fields.iter().map(|f| f.size).sum::<usize>()
+    }
+}
+
+impl fmt::Display for StructLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} (size {}, align {})",
+            self.name, self.size, self.align
+        )?;
+        write!(f, "  offset  size  align  field")?;
+        for field in self.fields.iter() {
+            write!(
+                f,
+                "\n  {:>6}  {:>4}  {:>5}  {}: {}",
+                field.offset, field.size, field.align, field.name, field.ty,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A type whose archived layout can be introspected.
+///
+/// This can be implemented for derived structs with `#[archive(layout)]`.
+pub trait ArchiveLayout {
+    /// The layout of the archived type.
+    const LAYOUT: StructLayout;
+
+    /// Returns the bytes that the field with the given name occupies in the archived value, or
+    /// `None` if the struct doesn't have that field.
+    ///
+    /// Only the bytes of the field itself are returned. Data that the field points to, like the
+    /// contents of a string or vector, is elsewhere in the archive.
+    #[inline]
+    fn field_bytes<'a>(archived: &'a Self::Archived, name: &str) -> Option<&'a [u8]>
+    where
+        Self: Archive,
+    {
+        Self::LAYOUT.field(name).map(|field| {
+            // The field is inside of the archived value, and archived values are always read from
+            // initialized bytes
+            unsafe {
+                let start = (archived as *const Self::Archived)
+                    .cast::<u8>()
+                    .add(field.offset);
+                slice::from_raw_parts(start, field.size)
+            }
+        })
+    }
+}
@@ -0,0 +1,177 @@
+//! Values that are deserialized the first time they are accessed.
+//!
+//! A [`Lazy`] stores its value as a [`SubArchive`] inside of the outer archive. Deserializing a
+//! `Lazy` only copies the bytes of its sub-archive, and the value itself is deserialized and cached
+//! the first time it is accessed. This lets applications deserialize only the parts of an archive
+//! that they actually use, while still working with owned values.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, lazy::Lazy, Archive, Deserialize, Infallible, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Document {
+//!     title: String,
+//!     body: Lazy<Vec<String>>,
+//! }
+//!
+//! let document = Document {
+//!     title: "Notes".to_string(),
+//!     body: Lazy::new::<256>(vec!["first".to_string(), "second".to_string()]).unwrap(),
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&document).unwrap();
+//!
+//! let archived = unsafe { archived_root::<Document>(&bytes) };
+//! let deserialized: Document = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized.title, "Notes");
+//! assert!(!deserialized.body.is_deserialized());
+//!
+//! assert_eq!(deserialized.body.get()[1], "second");
+//! assert!(deserialized.body.is_deserialized());
+//! ```
+
+use crate::{
+    ser::{serializers::AllocSerializer, Serializer},
+    sub_archive::{ArchivedSubArchive, SubArchive},
+    vec::VecResolver,
+    Archive, Deserialize, Fallible, Infallible, Serialize,
+};
+use core::{cell::OnceCell, fmt};
+
+/// A value that is deserialized from its archive the first time it is accessed.
+///
+/// The deserialized value is cached, so later accesses return the same value. `Lazy` archives as an
+/// [`ArchivedSubArchive`], which gives direct access to the archived value without deserializing
+/// it.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct Lazy<T: Archive> {
+    archive: SubArchive<T>,
+    value: OnceCell<T>,
+}
+
+impl<T: Archive> Lazy<T> {
+    /// Serializes the given value and creates a `Lazy` that already holds it.
+    #[inline]
+    pub fn new<const N: usize>(value: T) -> Result<Self, <AllocSerializer<N> as Fallible>::Error>
+    where
+        T: Serialize<AllocSerializer<N>>,
+    {
+        let archive = SubArchive::from_value::<N>(&value)?;
+        Ok(Self {
+            archive,
+            value: OnceCell::from(value),
+        })
+    }
+
+    /// Creates a `Lazy` that will deserialize its value from the given sub-archive.
+    #[inline]
+    pub fn from_sub_archive(archive: SubArchive<T>) -> Self {
+        Self {
+            archive,
+            value: OnceCell::new(),
+        }
+    }
+
+    /// Returns the sub-archive that the value is deserialized from.
+    #[inline]
+    pub fn sub_archive(&self) -> &SubArchive<T> {
+        &self.archive
+    }
+
+    /// Returns the archived value without deserializing it.
+    #[inline]
+    pub fn archived(&self) -> &T::Archived {
+        self.archive.root()
+    }
+
+    /// Returns whether the value has been deserialized yet.
+    #[inline]
+    pub fn is_deserialized(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Returns the value, deserializing it with the given deserializer if it has not been
+    /// deserialized yet.
+    pub fn get_with<D>(&self, deserializer: &mut D) -> Result<&T, D::Error>
+    where
+        D: Fallible + ?Sized,
+        T::Archived: Deserialize<T, D>,
+    {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+        let value = self.archived().deserialize(deserializer)?;
+        // Deserializing can't access this `Lazy`, so the cell is still empty
+        Ok(self.value.get_or_init(|| value))
+    }
+
+    /// Returns the value, deserializing it with the [`Infallible`] deserializer if it has not been
+    /// deserialized yet.
+    #[inline]
+    pub fn get(&self) -> &T
+    where
+        T::Archived: Deserialize<T, Infallible>,
+    {
+        self.value
+            .get_or_init(|| crate::deserialize::<T>(self.archived()))
+    }
+
+    /// Consumes the `Lazy` and returns the value, deserializing it with the [`Infallible`]
+    /// deserializer if it has not been deserialized yet.
+    #[inline]
+    pub fn into_inner(self) -> T
+    where
+        T::Archived: Deserialize<T, Infallible>,
+    {
+        match self.value.into_inner() {
+            Some(value) => value,
+            None => crate::deserialize::<T>(self.archive.root()),
+        }
+    }
+}
+
+impl<T: Archive + Clone> Clone for Lazy<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            archive: self.archive.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<T: Archive + fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("len", &self.archive.as_bytes().len())
+            .field("value", &self.value.get())
+            .finish()
+    }
+}
+
+impl<T: Archive> Archive for Lazy<T> {
+    type Archived = ArchivedSubArchive<T>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        self.archive.resolve(pos, resolver, out);
+    }
+}
+
+impl<T: Archive, S: Serializer + ?Sized> Serialize<S> for Lazy<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.archive.serialize(serializer)
+    }
+}
+
+impl<T: Archive, D: Fallible + ?Sized> Deserialize<Lazy<T>, D> for ArchivedSubArchive<T> {
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Lazy<T>, D::Error> {
+        let archive = Deserialize::<SubArchive<T>, D>::deserialize(self, deserializer)?;
+        Ok(Lazy::from_sub_archive(archive))
+    }
+}
@@ -53,16 +53,30 @@
 //! ## Features
 //!
 //! - `alloc`: Enables types that require the `alloc` crate. Enabled by default.
+//! - `arbitrary`: Enables the `fuzz` module of entry points for fuzzing archived types with
+//!   `arbitrary`. Implies `validation`.
 //! - `arbitrary_enum_discriminant`: Enables the `arbitrary_enum_discriminant` feature for stable
 //!   multibyte enum discriminants using `archive_le` and `archive_be`. Requires nightly.
 //! - `archive_be`: Forces archives into a big-endian format. This guarantees cross-endian
 //!   compatibility optimized for big-endian architectures.
 //! - `archive_le`: Forces archives into a little-endian format. This guarantees cross-endian
 //!   compatibility optimized for little-endian architectures.
+//! - `c_header`: Enables the `c_header` module and `#[archive(c_header)]` for generating C headers
+//!   for archived types. Implies `alloc`.
 //! - `copy`: Enables copy optimizations for packed copyable data types. Requires nightly.
 //! - `copy_unsafe`: Automatically opts all potentially copyable types into copy optimization. This
 //!   broadly improves performance but may cause uninitialized bytes to be copied to the output.
 //!   Requires nightly.
+//! - `encryption`: Enables encrypted archives in the `encryption` module through
+//!   `chacha20poly1305`. Implies `alloc`.
+//! - `lz4`: Enables compressed archives in the `compression` module through `lz4_flex`. Implies
+//!   `std`.
+//! - `memmap2`: Enables accessing memory-mapped archives in the `mmap` module through `memmap2`.
+//!   Implies `std`.
+//! - `proptest`: Enables `proptest_archive` in the `test_util` module for running round-trip tests
+//!   on values generated by `proptest`. Implies `std` and `test_util`.
+//! - `serde`: Enables the `value::transcode` module for converting types that implement
+//!   `serde::Serialize` into dynamically-typed values. Implies `alloc`.
 //! - `size_16`: Archives integral `*size` types as 16-bit integers. This is intended to be used
 //!   only for small archives and may not handle large, more general data.
 //! - `size_32`: Archives integral `*size` types as 32-bit integers. Enabled by default.
@@ -71,11 +85,18 @@
 //! - `std`: Enables standard library support. Enabled by default.
 //! - `strict`: Guarantees that types will have the same representations across platforms and
 //!   compilations. This is already the case in practice, but this feature provides a guarantee
-//!   along with C type compatibility.
+//!   along with C type compatibility. Every archived type provided by rkyv and every derived
+//!   archived struct is `repr(C)`, and the `layout` module can describe the resulting layouts.
 //!
 //!   *Note*: Enabling `strict` will disable [`Archive`] implementations for tuples, as tuples
 //!   do not have a C type layout. Making a generic `Tuple<T1, T2>` and deriving [`Archive`] for it
 //!   should provide similar functionality.
+//! - `test_util`: Enables the `test_util` module of helpers for testing that types round-trip
+//!   through archives. Implies `alloc`.
+//! - `tokio`: Enables serializing to a `tokio` `AsyncWrite` through `AsyncWriteSerializer` and
+//!   `to_async_writer`. Implies `std`.
+//! - `tokio-util`: Enables the `codec` module for sending archives as framed messages with
+//!   `tokio-util`. Implies `std` and `validation`.
 //! - `validation`: Enables validation support through `bytecheck`.
 //!
 //! ## Crate support
@@ -87,9 +108,24 @@
 //!
 //! Crates supported by rkyv:
 //!
+//! - [`arrayvec`](https://docs.rs/arrayvec)
+//! - [`bumpalo`](https://docs.rs/bumpalo) *Supports deserializing into an arena with
+//!   `BumpDeserializer`.*
+//! - [`bytes`](https://docs.rs/bytes)
+//! - [`chrono`](https://docs.rs/chrono)
+//! - [`glam`](https://docs.rs/glam)
+//! - [`hashbrown`](https://docs.rs/hashbrown) *Enabled automatically when using the `alloc`
+//!   feature.*
 //! - [`indexmap`](https://docs.rs/indexmap)
+//! - [`nalgebra`](https://docs.rs/nalgebra)
+//! - [`ordered-float`](https://docs.rs/ordered-float)
 //! - [`rend`](https://docs.rs/rend) *Enabled automatically when using endian-specific archive
 //!   features.*
+//! - [`rust_decimal`](https://docs.rs/rust_decimal)
+//! - [`serde_json`](https://docs.rs/serde_json) *Archives `Value` as a dynamically-typed
+//!   [`ArchivedValue`](value::ArchivedValue).*
+//! - [`smallvec`](https://docs.rs/smallvec)
+//! - [`time`](https://docs.rs/time)
 //! - [`tinyvec`](https://docs.rs/tinyvec)
 //! - [`uuid`](https://docs.rs/uuid)
 //!
@@ -141,34 +177,80 @@ extern crate std;
 #[macro_use]
 pub mod macros;
 
+pub mod arena;
 #[cfg(feature = "bitvec")]
 pub mod bitvec;
 pub mod boxed;
+#[cfg(feature = "c_header")]
+pub mod c_header;
+pub mod checksum;
+pub mod cmp;
+#[cfg(feature = "tokio-util")]
+pub mod codec;
 pub mod collections;
+pub mod columnar;
+#[cfg(feature = "alloc")]
+pub mod compact;
+#[cfg(feature = "lz4")]
+pub mod compression;
 #[cfg(feature = "copy")]
 pub mod copy;
 pub mod de;
+#[cfg(feature = "alloc")]
+pub mod diff;
+pub mod directory;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+pub mod extensible;
 // This is pretty unfortunate. CStr doesn't rely on the rest of std, but it's not in core.
 // If CStr ever gets moved into `core` then this module will no longer need cfg(feature = "std")
 #[cfg(feature = "std")]
 pub mod ffi;
+pub mod fingerprint;
+pub mod fixed_size;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
 mod impls;
+#[cfg(feature = "alloc")]
+pub mod inspect;
+pub mod layout;
+#[cfg(feature = "alloc")]
+pub mod lazy;
+#[cfg(any(feature = "glam", feature = "nalgebra"))]
+pub mod math;
+#[cfg(feature = "memmap2")]
+pub mod mmap;
 pub mod net;
 pub mod niche;
+#[cfg(any(feature = "ordered-float", feature = "rust_decimal"))]
+pub mod num;
 pub mod ops;
 pub mod option;
+#[cfg(feature = "std")]
+pub mod path;
+pub mod primitive;
 pub mod rc;
 pub mod rel_ptr;
 pub mod result;
+pub mod schema;
 pub mod ser;
 pub mod string;
+pub mod sub_archive;
+pub mod tail;
+#[cfg(feature = "test_util")]
+pub mod test_util;
 pub mod time;
 pub mod util;
 #[cfg(feature = "validation")]
 pub mod validation;
+#[cfg(feature = "alloc")]
+pub mod value;
 pub mod vec;
+pub mod versioned;
 pub mod with;
 
+#[cfg(feature = "validation")]
+pub use bytecheck;
 #[cfg(feature = "rend")]
 pub use rend;
 
@@ -180,7 +262,7 @@ pub use util::*;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "validation")))]
 pub use validation::{
     check_archived_root_with_context, check_archived_value_with_context,
-    validators::{check_archived_root, check_archived_value, from_bytes},
+    validators::{access, access_pos, check_archived_root, check_archived_value, from_bytes},
 };
 
 /// A type that can produce an error.
@@ -270,8 +352,8 @@ impl Default for Infallible {
 /// serializer.serialize_value(&value).unwrap();
 /// let bytes = serializer.into_serializer().into_inner();
 ///
-/// // You can use the safe API with the validation feature turned on,
-/// // or you can use the unsafe API (shown here) for maximum performance
+/// // You can use the safe API (`rkyv::access`) with the validation feature
+/// // turned on, or you can use the unsafe API (shown here) for maximum performance
 /// let archived = unsafe { rkyv::archived_root::<Test>(&bytes[..]) };
 /// assert_eq!(archived, &value);
 ///
@@ -392,6 +474,12 @@ impl Default for Infallible {
 /// // Let's make sure our data got written correctly
 /// assert_eq!(archived.as_str(), STR_VAL);
 /// ```
+#[cfg_attr(
+    has_diagnostic_namespace,
+    diagnostic::on_unimplemented(
+        note = "consider deriving `Archive` for `{Self}`, or archiving it with a wrapper using `#[with(...)]`"
+    )
+)]
 pub trait Archive {
     /// The archived representation of this type.
     ///
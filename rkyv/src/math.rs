@@ -0,0 +1,234 @@
+//! Archived versions of math types.
+//!
+//! Archived vectors, matrices, and quaternions have the same layout as their unarchived
+//! counterparts when the archive endianness matches the target. With the `copy` feature enabled,
+//! this makes them eligible for the bulk-copy fast path used by containers like `Vec`.
+
+#[cfg(feature = "glam")]
+use crate::Archived;
+#[cfg(feature = "nalgebra")]
+use core::ops::Index;
+
+/// An archived [`Vec2`](glam::Vec2).
+#[cfg(feature = "glam")]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct ArchivedVec2 {
+    /// The x component of the vector.
+    pub x: Archived<f32>,
+    /// The y component of the vector.
+    pub y: Archived<f32>,
+}
+
+/// An archived [`Vec3`](glam::Vec3).
+#[cfg(feature = "glam")]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct ArchivedVec3 {
+    /// The x component of the vector.
+    pub x: Archived<f32>,
+    /// The y component of the vector.
+    pub y: Archived<f32>,
+    /// The z component of the vector.
+    pub z: Archived<f32>,
+}
+
+/// An archived [`Vec4`](glam::Vec4).
+#[cfg(feature = "glam")]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct ArchivedVec4 {
+    /// The x component of the vector.
+    pub x: Archived<f32>,
+    /// The y component of the vector.
+    pub y: Archived<f32>,
+    /// The z component of the vector.
+    pub z: Archived<f32>,
+    /// The w component of the vector.
+    pub w: Archived<f32>,
+}
+
+/// An archived [`Quat`](glam::Quat).
+#[cfg(feature = "glam")]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct ArchivedQuat {
+    /// The x component of the quaternion.
+    pub x: Archived<f32>,
+    /// The y component of the quaternion.
+    pub y: Archived<f32>,
+    /// The z component of the quaternion.
+    pub z: Archived<f32>,
+    /// The w component of the quaternion.
+    pub w: Archived<f32>,
+}
+
+/// An archived [`Mat2`](glam::Mat2).
+#[cfg(feature = "glam")]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct ArchivedMat2 {
+    /// The first column of the matrix.
+    pub x_axis: ArchivedVec2,
+    /// The second column of the matrix.
+    pub y_axis: ArchivedVec2,
+}
+
+/// An archived [`Mat3`](glam::Mat3).
+#[cfg(feature = "glam")]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct ArchivedMat3 {
+    /// The first column of the matrix.
+    pub x_axis: ArchivedVec3,
+    /// The second column of the matrix.
+    pub y_axis: ArchivedVec3,
+    /// The third column of the matrix.
+    pub z_axis: ArchivedVec3,
+}
+
+/// An archived [`Mat4`](glam::Mat4).
+#[cfg(feature = "glam")]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct ArchivedMat4 {
+    /// The first column of the matrix.
+    pub x_axis: ArchivedVec4,
+    /// The second column of the matrix.
+    pub y_axis: ArchivedVec4,
+    /// The third column of the matrix.
+    pub z_axis: ArchivedVec4,
+    /// The fourth column of the matrix.
+    pub w_axis: ArchivedVec4,
+}
+
+/// An archived statically-sized nalgebra [`SMatrix`](nalgebra::SMatrix).
+///
+/// Like nalgebra matrices, the components of an archived matrix are stored in column-major order.
+/// Archived vectors are archived matrices with a single column.
+#[cfg(feature = "nalgebra")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(transparent)]
+pub struct ArchivedMatrix<T, const R: usize, const C: usize>(pub(crate) [[T; R]; C]);
+
+#[cfg(feature = "nalgebra")]
+impl<T, const R: usize, const C: usize> ArchivedMatrix<T, R, C> {
+    /// Returns the number of rows in the matrix.
+    #[inline]
+    pub const fn nrows(&self) -> usize {
+        R
+    }
+
+    /// Returns the number of columns in the matrix.
+    #[inline]
+    pub const fn ncols(&self) -> usize {
+        C
+    }
+
+    /// Returns the components of the matrix in column-major order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `[[T; R]; C]` has the same layout as `[T; R * C]`
+        unsafe { core::slice::from_raw_parts(self.0.as_ptr().cast(), R * C) }
+    }
+
+    /// Returns the column of the matrix at the given index.
+    #[inline]
+    pub fn column(&self, index: usize) -> &[T; R] {
+        &self.0[index]
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T, const R: usize, const C: usize> Index<usize> for ArchivedMatrix<T, R, C> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.as_slice()[index]
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for ArchivedMatrix<T, R, C> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        assert!(row < R, "matrix row index out of bounds");
+        &self.0[column][row]
+    }
+}
+
+/// An archived nalgebra [`Point`](nalgebra::Point).
+#[cfg(feature = "nalgebra")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(transparent)]
+pub struct ArchivedPoint<T, const D: usize> {
+    /// The coordinates of the point.
+    pub coords: ArchivedMatrix<T, D, 1>,
+}
+
+/// An archived nalgebra [`Quaternion`](nalgebra::Quaternion).
+///
+/// The coordinates of the quaternion are stored in `(i, j, k, w)` order.
+#[cfg(feature = "nalgebra")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(transparent)]
+pub struct ArchivedQuaternion<T> {
+    /// The coordinates of the quaternion.
+    pub coords: ArchivedMatrix<T, 4, 1>,
+}
+
+#[cfg(all(feature = "validation", feature = "nalgebra"))]
+const _: () = {
+    use bytecheck::CheckBytes;
+
+    impl<T: CheckBytes<C>, C: ?Sized, const R: usize, const N: usize> CheckBytes<C>
+        for ArchivedMatrix<T, R, N>
+    {
+        type Error = <[[T; R]; N] as CheckBytes<C>>::Error;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            <[[T; R]; N]>::check_bytes(value.cast(), context)?;
+            Ok(&*value)
+        }
+    }
+
+    impl<T: CheckBytes<C>, C: ?Sized, const D: usize> CheckBytes<C> for ArchivedPoint<T, D> {
+        type Error = <ArchivedMatrix<T, D, 1> as CheckBytes<C>>::Error;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            ArchivedMatrix::<T, D, 1>::check_bytes(value.cast(), context)?;
+            Ok(&*value)
+        }
+    }
+
+    impl<T: CheckBytes<C>, C: ?Sized> CheckBytes<C> for ArchivedQuaternion<T> {
+        type Error = <ArchivedMatrix<T, 4, 1> as CheckBytes<C>>::Error;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            ArchivedMatrix::<T, 4, 1>::check_bytes(value.cast(), context)?;
+            Ok(&*value)
+        }
+    }
+};
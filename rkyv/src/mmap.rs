@@ -0,0 +1,285 @@
+//! Access to archives in memory-mapped files.
+//!
+//! An [`MmapArchive`] maps a file into memory with [`memmap2`] and checks that it can hold an
+//! archive before giving access to its root. The mapping is owned by the `MmapArchive`, so the
+//! archived root can never outlive it. With the `validation` feature, `MmapArchive::open` also
+//! validates the archive.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::mmap::MmapArchive;
+//! use std::fs;
+//!
+//! let path = std::env::temp_dir().join("rkyv_mmap_doc_example");
+//! let bytes = rkyv::to_bytes::<_, 256>(&vec![1u32, 2, 3]).unwrap();
+//! fs::write(&path, &bytes).unwrap();
+//!
+//! // SAFETY: The file is not modified while it is mapped, and it contains an archived `Vec<u32>`.
+//! let archive = unsafe { MmapArchive::<Vec<u32>>::open_unchecked(&path) }.unwrap();
+//! assert_eq!(archive.as_slice(), &[1, 2, 3]);
+//! # drop(archive);
+//! # fs::remove_file(&path).unwrap();
+//! ```
+
+use crate::{archived_root, Archive};
+use core::{fmt, marker::PhantomData, mem, ops::Deref};
+use memmap2::Mmap;
+use std::{error::Error, fs::File, io, path::Path};
+
+/// An error that can occur while mapping an archive.
+#[derive(Debug)]
+pub enum MmapError {
+    /// An I/O error occurred while opening or mapping the file.
+    Io(io::Error),
+    /// The file was too short to contain the archived root.
+    TooShort {
+        /// The length of the file
+        len: usize,
+        /// The size of the archived root
+        root_size: usize,
+    },
+    /// The mapped memory was not aligned for the archived root.
+    Unaligned {
+        /// The address of the mapped memory
+        address: usize,
+        /// The alignment of the archived root
+        align: usize,
+    },
+    /// The archive failed validation.
+    ///
+    /// The validation error refers to the mapped memory, so it is formatted as a message.
+    Invalid(String),
+}
+
+impl fmt::Display for MmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MmapError::Io(e) => write!(f, "I/O error: {}", e),
+            MmapError::TooShort { len, root_size } => write!(
+                f,
+                "file of length {} is too short to contain a root of size {}",
+                len, root_size
+            ),
+            MmapError::Unaligned { address, align } => write!(
+                f,
+                "mapped memory at {:#x} is not aligned to {}",
+                address, align
+            ),
+            MmapError::Invalid(e) => write!(f, "invalid archive: {}", e),
+        }
+    }
+}
+
+impl Error for MmapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MmapError::Io(e) => Some(e as &dyn Error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MmapError {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        MmapError::Io(e)
+    }
+}
+
+/// A memory-mapped archive that dereferences to its archived root.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct MmapArchive<T> {
+    mmap: Mmap,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Archive> MmapArchive<T> {
+    /// Maps the file at the given path without validating it.
+    ///
+    /// # Safety
+    ///
+    /// - The file must not be modified while it is mapped
+    /// - The file must contain a valid archive with an archived `T` at its root
+    #[inline]
+    pub unsafe fn open_unchecked<P: AsRef<Path>>(path: P) -> Result<Self, MmapError> {
+        Self::map_unchecked(&File::open(path)?)
+    }
+
+    /// Maps the given file without validating it.
+    ///
+    /// # Safety
+    ///
+    /// - The file must not be modified while it is mapped
+    /// - The file must contain a valid archive with an archived `T` at its root
+    pub unsafe fn map_unchecked(file: &File) -> Result<Self, MmapError> {
+        let len = file.metadata()?.len() as usize;
+        let root_size = mem::size_of::<T::Archived>();
+        if len < root_size {
+            return Err(MmapError::TooShort { len, root_size });
+        }
+
+        let mmap = Mmap::map(file)?;
+        let address = mmap.as_ptr() as usize;
+        let align = mem::align_of::<T::Archived>();
+        if address & (align - 1) != 0 {
+            return Err(MmapError::Unaligned { address, align });
+        }
+        if mmap.len() < root_size {
+            return Err(MmapError::TooShort {
+                len: mmap.len(),
+                root_size,
+            });
+        }
+
+        Ok(Self {
+            mmap,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns the mapped bytes of the archive.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Consumes the archive and returns the underlying memory map.
+    #[inline]
+    pub fn into_mmap(self) -> Mmap {
+        self.mmap
+    }
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::validators::{check_archived_root, DefaultValidator};
+    use bytecheck::CheckBytes;
+
+    impl<T: Archive> MmapArchive<T>
+    where
+        T::Archived: for<'a> CheckBytes<DefaultValidator<'a>>,
+    {
+        /// Maps and validates the file at the given path.
+        ///
+        /// # Safety
+        ///
+        /// The file must not be modified while it is mapped.
+        #[inline]
+        pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<Self, MmapError> {
+            Self::map(&File::open(path)?)
+        }
+
+        /// Maps and validates the given file.
+        ///
+        /// # Safety
+        ///
+        /// The file must not be modified while it is mapped.
+        pub unsafe fn map(file: &File) -> Result<Self, MmapError> {
+            let result = Self::map_unchecked(file)?;
+            check_archived_root::<T>(result.as_bytes())
+                .map_err(|e| MmapError::Invalid(e.to_string()))?;
+            Ok(result)
+        }
+    }
+};
+
+impl<T: Archive> Deref for MmapArchive<T> {
+    type Target = T::Archived;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The mapping was checked to be long enough and aligned for the root, and the
+        // archive was either validated or guaranteed to be valid by the caller.
+        unsafe { archived_root::<T>(&self.mmap) }
+    }
+}
+
+impl<T> fmt::Debug for MmapArchive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MmapArchive")
+            .field("len", &self.mmap.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MmapArchive, MmapError};
+    use crate::{Archive, Serialize};
+    use std::{fs, path::PathBuf};
+
+    #[derive(Archive, Serialize)]
+    #[archive(crate = "crate")]
+    #[cfg_attr(feature = "validation", archive_attr(derive(bytecheck::CheckBytes)))]
+    struct Record {
+        id: u64,
+        name: String,
+    }
+
+    // Removes the file when the test ends, even if it panics
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, contents: &[u8]) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("rkyv_mmap_{}_{}", name, std::process::id()));
+            fs::write(&path, contents).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn map_archive() {
+        let bytes = crate::to_bytes::<_, 256>(&Record {
+            id: 42,
+            name: "mapped record".to_string(),
+        })
+        .unwrap();
+        let file = TempFile::new("map_archive", &bytes);
+
+        let archive = unsafe { MmapArchive::<Record>::open_unchecked(&file.0) }.unwrap();
+        assert_eq!(archive.as_bytes(), bytes.as_slice());
+        assert_eq!(archive.id, 42);
+        assert_eq!(archive.name, "mapped record");
+
+        let short = TempFile::new("map_archive_short", &[0; 2]);
+        assert!(matches!(
+            unsafe { MmapArchive::<Record>::open_unchecked(&short.0) },
+            Err(MmapError::TooShort { len: 2, .. })
+        ));
+        assert!(matches!(
+            unsafe { MmapArchive::<Record>::open_unchecked(file.0.with_extension("missing")) },
+            Err(MmapError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn validate_archive() {
+        let bytes = crate::to_bytes::<_, 256>(&Record {
+            id: 7,
+            name: "a record name that is stored out of line".to_string(),
+        })
+        .unwrap();
+        let file = TempFile::new("validate_archive", &bytes);
+        let archive = unsafe { MmapArchive::<Record>::open(&file.0) }.unwrap();
+        assert_eq!(archive.name, "a record name that is stored out of line");
+
+        let mut corrupt = bytes.to_vec();
+        let len = corrupt.len();
+        corrupt[len - 8..].iter_mut().for_each(|b| *b = 0xff);
+        let file = TempFile::new("validate_archive_corrupt", &corrupt);
+        assert!(matches!(
+            unsafe { MmapArchive::<Record>::open(&file.0) },
+            Err(MmapError::Invalid(_))
+        ));
+    }
+}
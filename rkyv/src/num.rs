@@ -0,0 +1,359 @@
+//! Archived versions of third-party numeric types.
+
+#[cfg(feature = "ordered-float")]
+use crate::Archived;
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+#[cfg(feature = "ordered-float")]
+use ordered_float::{NotNan, OrderedFloat};
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
+
+/// An archived [`OrderedFloat`].
+///
+/// Archived ordered floats compare and hash the same way as their unarchived counterparts, so they
+/// can be sorted and searched without deserializing them.
+#[cfg(feature = "ordered-float")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct ArchivedOrderedFloat<F>(F);
+
+/// An archived [`NotNan`].
+///
+/// Archived not-NaN floats are checked during validation to make sure that they do not contain a
+/// NaN value.
+#[cfg(feature = "ordered-float")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct ArchivedNotNan<F>(F);
+
+#[cfg(feature = "ordered-float")]
+macro_rules! impl_archived_float {
+    ($archived:ident, $wrapper:ident, $float:ty, |$value:ident| $to_wrapper:expr) => {
+        impl $archived<Archived<$float>> {
+            #[doc = concat!("Returns the `", stringify!($wrapper), "<", stringify!($float), ">` value of this archived float.")]
+            #[inline]
+            pub fn value(&self) -> $wrapper<$float> {
+                let $value = from_archived!(self.0);
+                $to_wrapper
+            }
+
+            /// Constructs an archived float at the given position.
+            ///
+            /// # Safety
+            ///
+            #[doc = concat!("`out` must point to memory suitable for holding an `", stringify!($archived), "`.")]
+            #[inline]
+            pub unsafe fn emplace(value: $wrapper<$float>, out: *mut Self) {
+                out.cast::<Archived<$float>>()
+                    .write(to_archived!(value.into_inner()));
+            }
+        }
+
+        impl fmt::Debug for $archived<Archived<$float>> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.value(), f)
+            }
+        }
+
+        impl fmt::Display for $archived<Archived<$float>> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.value(), f)
+            }
+        }
+
+        impl Hash for $archived<Archived<$float>> {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.value().hash(state);
+            }
+        }
+
+        impl PartialEq for $archived<Archived<$float>> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.value() == other.value()
+            }
+        }
+
+        impl Eq for $archived<Archived<$float>> {}
+
+        impl PartialOrd for $archived<Archived<$float>> {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $archived<Archived<$float>> {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.value().cmp(&other.value())
+            }
+        }
+
+        impl PartialEq<$wrapper<$float>> for $archived<Archived<$float>> {
+            #[inline]
+            fn eq(&self, other: &$wrapper<$float>) -> bool {
+                self.value() == *other
+            }
+        }
+
+        impl PartialEq<$archived<Archived<$float>>> for $wrapper<$float> {
+            #[inline]
+            fn eq(&self, other: &$archived<Archived<$float>>) -> bool {
+                other.eq(self)
+            }
+        }
+
+        impl PartialOrd<$wrapper<$float>> for $archived<Archived<$float>> {
+            #[inline]
+            fn partial_cmp(&self, other: &$wrapper<$float>) -> Option<Ordering> {
+                self.value().partial_cmp(other)
+            }
+        }
+
+        impl PartialOrd<$archived<Archived<$float>>> for $wrapper<$float> {
+            #[inline]
+            fn partial_cmp(&self, other: &$archived<Archived<$float>>) -> Option<Ordering> {
+                self.partial_cmp(&other.value())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "ordered-float")]
+impl_archived_float!(ArchivedOrderedFloat, OrderedFloat, f32, |value| {
+    OrderedFloat(value)
+});
+#[cfg(feature = "ordered-float")]
+impl_archived_float!(ArchivedOrderedFloat, OrderedFloat, f64, |value| {
+    OrderedFloat(value)
+});
+// SAFETY: `ArchivedNotNan` is only ever constructed from a valid `NotNan` or checked for a non-NaN
+// value during validation.
+#[cfg(feature = "ordered-float")]
+impl_archived_float!(ArchivedNotNan, NotNan, f32, |value| unsafe {
+    NotNan::new_unchecked(value)
+});
+#[cfg(feature = "ordered-float")]
+impl_archived_float!(ArchivedNotNan, NotNan, f64, |value| unsafe {
+    NotNan::new_unchecked(value)
+});
+
+/// An archived [`Decimal`].
+///
+/// Decimals are archived using their 16-byte serialized representation, which is the same on every
+/// platform.
+#[cfg(feature = "rust_decimal")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct ArchivedDecimal([u8; 16]);
+
+#[cfg(feature = "rust_decimal")]
+impl ArchivedDecimal {
+    /// Returns the `Decimal` value of this archived decimal.
+    #[inline]
+    pub fn as_decimal(&self) -> Decimal {
+        Decimal::deserialize(self.0)
+    }
+
+    /// Constructs an archived decimal at the given position.
+    ///
+    /// # Safety
+    ///
+    /// `out` must point to memory suitable for holding an `ArchivedDecimal`.
+    #[inline]
+    pub unsafe fn emplace(value: Decimal, out: *mut ArchivedDecimal) {
+        out.cast::<[u8; 16]>().write(value.serialize());
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl fmt::Debug for ArchivedDecimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_decimal(), f)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl fmt::Display for ArchivedDecimal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_decimal(), f)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Hash for ArchivedDecimal {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_decimal().hash(state);
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl PartialEq for ArchivedDecimal {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_decimal() == other.as_decimal()
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Eq for ArchivedDecimal {}
+
+#[cfg(feature = "rust_decimal")]
+impl PartialOrd for ArchivedDecimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Ord for ArchivedDecimal {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_decimal().cmp(&other.as_decimal())
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl PartialEq<Decimal> for ArchivedDecimal {
+    #[inline]
+    fn eq(&self, other: &Decimal) -> bool {
+        self.as_decimal() == *other
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl PartialEq<ArchivedDecimal> for Decimal {
+    #[inline]
+    fn eq(&self, other: &ArchivedDecimal) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl PartialOrd<Decimal> for ArchivedDecimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        self.as_decimal().partial_cmp(other)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl PartialOrd<ArchivedDecimal> for Decimal {
+    #[inline]
+    fn partial_cmp(&self, other: &ArchivedDecimal) -> Option<Ordering> {
+        self.partial_cmp(&other.as_decimal())
+    }
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use bytecheck::CheckBytes;
+
+    #[cfg(feature = "ordered-float")]
+    impl<F: CheckBytes<C>, C: ?Sized> CheckBytes<C> for ArchivedOrderedFloat<F> {
+        type Error = F::Error;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            F::check_bytes(value.cast(), context)?;
+            Ok(&*value)
+        }
+    }
+
+    /// An error resulting from an archived not-NaN float containing a NaN value.
+    #[cfg(feature = "ordered-float")]
+    #[derive(Debug)]
+    pub struct NotNanError;
+
+    #[cfg(feature = "ordered-float")]
+    impl fmt::Display for NotNanError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "NotNan error: float value is NaN")
+        }
+    }
+
+    #[cfg(all(feature = "ordered-float", feature = "std"))]
+    impl std::error::Error for NotNanError {}
+
+    #[cfg(feature = "ordered-float")]
+    macro_rules! impl_check_not_nan {
+        ($float:ty) => {
+            impl<C: ?Sized> CheckBytes<C> for ArchivedNotNan<Archived<$float>> {
+                type Error = NotNanError;
+
+                #[inline]
+                unsafe fn check_bytes<'a>(
+                    value: *const Self,
+                    _: &mut C,
+                ) -> Result<&'a Self, Self::Error> {
+                    // The inner float of `ArchivedNotNan` is always valid
+                    let value = &*value;
+                    if from_archived!(value.0).is_nan() {
+                        Err(NotNanError)
+                    } else {
+                        Ok(value)
+                    }
+                }
+            }
+        };
+    }
+
+    #[cfg(feature = "ordered-float")]
+    impl_check_not_nan!(f32);
+    #[cfg(feature = "ordered-float")]
+    impl_check_not_nan!(f64);
+
+    /// An error resulting from an archived decimal with invalid flags.
+    #[cfg(feature = "rust_decimal")]
+    #[derive(Debug)]
+    pub struct DecimalError {
+        /// The invalid flags
+        pub flags: u32,
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    impl fmt::Display for DecimalError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Decimal error: invalid flags {:#010x}", self.flags)
+        }
+    }
+
+    #[cfg(all(feature = "rust_decimal", feature = "std"))]
+    impl std::error::Error for DecimalError {}
+
+    #[cfg(feature = "rust_decimal")]
+    impl<C: ?Sized> CheckBytes<C> for ArchivedDecimal {
+        type Error = DecimalError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+            // Only the sign bit and a scale of at most 28 may be set in the flags
+            const UNUSED_BITS: u32 = 0x7F00_FFFF;
+            const SCALE_SHIFT: u32 = 16;
+
+            let value = &*value;
+            let flags = u32::from_le_bytes([value.0[0], value.0[1], value.0[2], value.0[3]]);
+            let scale = (flags >> SCALE_SHIFT) & 0xFF;
+            if flags & UNUSED_BITS != 0 || scale > Decimal::MAX_SCALE {
+                Err(DecimalError { flags })
+            } else {
+                Ok(value)
+            }
+        }
+    }
+};
@@ -109,6 +109,78 @@ impl<T> ArchivedOption<T> {
             self.as_mut().unwrap()
         }
     }
+
+    /// Returns a reference to the contained value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the option is a `None` with a custom panic message provided by `msg`.
+    #[inline]
+    pub fn expect(&self, msg: &str) -> &T {
+        match self {
+            ArchivedOption::None => panic!("{}", msg),
+            ArchivedOption::Some(value) => value,
+        }
+    }
+
+    /// Returns a reference to the contained value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the option is a `None`.
+    #[inline]
+    pub fn unwrap(&self) -> &T {
+        self.expect("called `ArchivedOption::unwrap()` on a `None` value")
+    }
+
+    /// Returns a reference to the contained value or the provided default.
+    #[inline]
+    pub fn unwrap_or<'a>(&'a self, default: &'a T) -> &'a T {
+        self.as_ref().unwrap_or(default)
+    }
+
+    /// Returns a reference to the contained value or computes one from a closure.
+    #[inline]
+    pub fn unwrap_or_else<'a, F: FnOnce() -> &'a T>(&'a self, f: F) -> &'a T {
+        self.as_ref().unwrap_or_else(f)
+    }
+
+    /// Maps the contained value by reference with the given function.
+    #[inline]
+    pub fn map<U, F: FnOnce(&T) -> U>(&self, f: F) -> Option<U> {
+        self.as_ref().map(f)
+    }
+
+    /// Returns `None` if the option is `None`, otherwise calls `f` with a reference to the
+    /// contained value and returns the result.
+    #[inline]
+    pub fn and_then<U, F: FnOnce(&T) -> Option<U>>(&self, f: F) -> Option<U> {
+        self.as_ref().and_then(f)
+    }
+
+    /// Returns `true` if the option is a `Some` and the contained value matches a predicate.
+    #[inline]
+    pub fn is_some_and<F: FnOnce(&T) -> bool>(&self, f: F) -> bool {
+        self.as_ref().is_some_and(f)
+    }
+
+    /// Converts to an `Option<T>` by copying the contained value.
+    #[inline]
+    pub fn copied(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.as_ref().copied()
+    }
+
+    /// Converts to an `Option<T>` by cloning the contained value.
+    #[inline]
+    pub fn cloned(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.as_ref().cloned()
+    }
 }
 
 impl<T: Deref> ArchivedOption<T> {
@@ -185,6 +257,26 @@ impl<T: PartialEq<U>, U> PartialEq<ArchivedOption<T>> for Option<U> {
     }
 }
 
+impl<'a, T> IntoIterator for &'a ArchivedOption<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArchivedOption<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 /// An iterator over a reference to the `Some` variant of an `ArchivedOption`.
 ///
 /// This iterator yields one value if the `ArchivedOption` is a `Some`, otherwise none.
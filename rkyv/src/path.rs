@@ -0,0 +1,145 @@
+//! Archived versions of path types.
+
+use crate::{ffi::ArchivedOsStr, ArchivePointee, Archived};
+use core::{cmp, fmt, hash};
+use ptr_meta::Pointee;
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+/// An archived [`Path`].
+///
+/// Paths are archived like [`OsStr`](std::ffi::OsStr)s, see [`ArchivedOsStr`] for how they're
+/// encoded.
+#[repr(transparent)]
+pub struct ArchivedPath(ArchivedOsStr);
+
+impl ArchivedPath {
+    /// Returns the archived path as an archived OS string.
+    #[inline]
+    pub fn as_os_str(&self) -> &ArchivedOsStr {
+        &self.0
+    }
+
+    /// Returns the path as a `str` if it's valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Option<&str> {
+        self.0.to_str()
+    }
+
+    /// Returns the archived path as a `Path`.
+    ///
+    /// This only allocates if the path can't be represented as a `Path` on the current platform,
+    /// and then it's converted lossily.
+    #[inline]
+    pub fn to_path(&self) -> Cow<'_, Path> {
+        match self.0.to_os_str() {
+            Cow::Borrowed(os_str) => Cow::Borrowed(Path::new(os_str)),
+            Cow::Owned(os_string) => Cow::Owned(PathBuf::from(os_string)),
+        }
+    }
+}
+
+impl Pointee for ArchivedPath {
+    type Metadata = usize;
+}
+
+impl ArchivePointee for ArchivedPath {
+    type ArchivedMetadata = Archived<usize>;
+
+    #[inline]
+    fn pointer_metadata(archived: &Self::ArchivedMetadata) -> <Self as Pointee>::Metadata {
+        <[u8]>::pointer_metadata(archived)
+    }
+}
+
+impl fmt::Debug for ArchivedPath {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_path().fmt(f)
+    }
+}
+
+impl Eq for ArchivedPath {}
+
+impl hash::Hash for ArchivedPath {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl Ord for ArchivedPath {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl PartialEq for ArchivedPath {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialEq<Path> for ArchivedPath {
+    #[inline]
+    fn eq(&self, other: &Path) -> bool {
+        self.0 == *other.as_os_str()
+    }
+}
+
+impl PartialEq<ArchivedPath> for Path {
+    #[inline]
+    fn eq(&self, other: &ArchivedPath) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<PathBuf> for ArchivedPath {
+    #[inline]
+    fn eq(&self, other: &PathBuf) -> bool {
+        self.eq(other.as_path())
+    }
+}
+
+impl PartialOrd for ArchivedPath {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::LayoutRaw;
+    use bytecheck::CheckBytes;
+    use core::{
+        alloc::{Layout, LayoutError},
+        convert::Infallible,
+    };
+
+    impl LayoutRaw for ArchivedPath {
+        #[inline]
+        fn layout_raw(value: *const Self) -> Result<Layout, LayoutError> {
+            Layout::array::<u8>(ptr_meta::metadata(value))
+        }
+
+        #[inline]
+        fn string_len(value: *const Self) -> Option<usize> {
+            Some(ptr_meta::metadata(value))
+        }
+    }
+
+    // Every sequence of bytes is a valid archived path
+    impl<C: ?Sized> CheckBytes<C> for ArchivedPath {
+        type Error = Infallible;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+            Ok(&*value)
+        }
+    }
+};
@@ -0,0 +1,348 @@
+//! Archived versions of primitive types.
+
+use crate::Archived;
+use core::{
+    cmp::Ordering,
+    fmt,
+    num::{NonZeroI128, NonZeroU128},
+};
+
+/// An archived [`char`].
+///
+/// Chars are archived as their `u32` value so that archived bytes can be checked for a valid
+/// Unicode scalar value before they are used.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct ArchivedChar(Archived<u32>);
+
+impl ArchivedChar {
+    /// Returns the `char` value of this archived char.
+    #[inline]
+    pub fn as_char(&self) -> char {
+        // SAFETY: `ArchivedChar` is only ever constructed from a valid `char` or checked for a
+        // valid value during validation.
+        unsafe { char::from_u32_unchecked(from_archived!(self.0)) }
+    }
+
+    /// Returns the `u32` value of this archived char.
+    #[inline]
+    pub const fn as_u32(&self) -> u32 {
+        from_archived!(self.0)
+    }
+
+    /// Constructs an archived char at the given position.
+    ///
+    /// # Safety
+    ///
+    /// `out` must point to memory suitable for holding an `ArchivedChar`.
+    #[inline]
+    pub unsafe fn emplace(value: char, out: *mut ArchivedChar) {
+        out.cast::<Archived<u32>>()
+            .write(to_archived!(value as u32));
+    }
+}
+
+impl fmt::Debug for ArchivedChar {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_char(), f)
+    }
+}
+
+impl fmt::Display for ArchivedChar {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_char(), f)
+    }
+}
+
+impl From<ArchivedChar> for char {
+    #[inline]
+    fn from(value: ArchivedChar) -> Self {
+        value.as_char()
+    }
+}
+
+impl PartialEq<char> for ArchivedChar {
+    #[inline]
+    fn eq(&self, other: &char) -> bool {
+        self.as_char() == *other
+    }
+}
+
+impl PartialEq<ArchivedChar> for char {
+    #[inline]
+    fn eq(&self, other: &ArchivedChar) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialOrd<char> for ArchivedChar {
+    #[inline]
+    fn partial_cmp(&self, other: &char) -> Option<Ordering> {
+        self.as_char().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<ArchivedChar> for char {
+    #[inline]
+    fn partial_cmp(&self, other: &ArchivedChar) -> Option<Ordering> {
+        self.partial_cmp(&other.as_char())
+    }
+}
+
+macro_rules! impl_archived_int128 {
+    ($archived:ident, $prim:ty, $raw:ident) => {
+        #[cfg(not(any(feature = "archive_le", feature = "archive_be")))]
+        type $raw = $prim;
+        #[cfg(feature = "archive_le")]
+        type $raw = crate::rend::LittleEndian<$prim>;
+        #[cfg(feature = "archive_be")]
+        type $raw = crate::rend::BigEndian<$prim>;
+
+        #[doc = concat!("An archived [`", stringify!($prim), "`].")]
+        ///
+        /// The alignment of 128-bit integers varies between targets and compiler versions, so
+        /// they are archived with a fixed alignment of 16 bytes. This keeps the layout of
+        #[doc = concat!("archived `", stringify!($prim), "`s the same on every platform.")]
+        #[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[repr(C, align(16))]
+        pub struct $archived($raw);
+
+        impl $archived {
+            /// Returns the value of this archived integer.
+            #[inline]
+            pub const fn value(&self) -> $prim {
+                from_archived!(self.0)
+            }
+
+            /// Constructs an archived integer at the given position.
+            ///
+            /// # Safety
+            ///
+            /// `out` must point to memory suitable for holding the archived integer.
+            #[inline]
+            pub unsafe fn emplace(value: $prim, out: *mut $archived) {
+                out.cast::<$raw>().write(to_archived!(value));
+            }
+        }
+
+        impl fmt::Debug for $archived {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.value(), f)
+            }
+        }
+
+        impl fmt::Display for $archived {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.value(), f)
+            }
+        }
+
+        impl From<$prim> for $archived {
+            #[inline]
+            fn from(value: $prim) -> Self {
+                Self(to_archived!(value))
+            }
+        }
+
+        impl From<$archived> for $prim {
+            #[inline]
+            fn from(value: $archived) -> Self {
+                value.value()
+            }
+        }
+
+        impl PartialEq<$prim> for $archived {
+            #[inline]
+            fn eq(&self, other: &$prim) -> bool {
+                self.value() == *other
+            }
+        }
+
+        impl PartialEq<$archived> for $prim {
+            #[inline]
+            fn eq(&self, other: &$archived) -> bool {
+                other.eq(self)
+            }
+        }
+
+        impl PartialOrd<$prim> for $archived {
+            #[inline]
+            fn partial_cmp(&self, other: &$prim) -> Option<Ordering> {
+                self.value().partial_cmp(other)
+            }
+        }
+
+        impl PartialOrd<$archived> for $prim {
+            #[inline]
+            fn partial_cmp(&self, other: &$archived) -> Option<Ordering> {
+                self.partial_cmp(&other.value())
+            }
+        }
+
+        #[cfg(feature = "validation")]
+        impl<C: ?Sized> bytecheck::CheckBytes<C> for $archived {
+            type Error = core::convert::Infallible;
+
+            #[inline]
+            unsafe fn check_bytes<'a>(
+                value: *const Self,
+                _: &mut C,
+            ) -> Result<&'a Self, Self::Error> {
+                // All bit patterns are valid 128-bit integers
+                Ok(&*value)
+            }
+        }
+    };
+}
+
+impl_archived_int128!(ArchivedI128, i128, RawI128);
+impl_archived_int128!(ArchivedU128, u128, RawU128);
+
+macro_rules! impl_archived_nonzero128 {
+    ($archived:ident, $nonzero:ident, $prim:ty, $raw:ident) => {
+        #[cfg(not(any(feature = "archive_le", feature = "archive_be")))]
+        type $raw = $nonzero;
+        #[cfg(feature = "archive_le")]
+        type $raw = crate::rend::LittleEndian<$nonzero>;
+        #[cfg(feature = "archive_be")]
+        type $raw = crate::rend::BigEndian<$nonzero>;
+
+        #[doc = concat!("An archived [`", stringify!($nonzero), "`].")]
+        ///
+        /// Like the other 128-bit integers, these are archived with a fixed alignment of 16 bytes
+        /// so that their layout is the same on every platform.
+        #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[repr(C, align(16))]
+        pub struct $archived($raw);
+
+        impl $archived {
+            /// Returns the value of this archived integer.
+            #[inline]
+            pub const fn value(&self) -> $nonzero {
+                from_archived!(self.0)
+            }
+
+            /// Returns the value of this archived integer as a primitive integer.
+            #[inline]
+            pub const fn get(&self) -> $prim {
+                self.value().get()
+            }
+
+            /// Constructs an archived integer at the given position.
+            ///
+            /// # Safety
+            ///
+            /// `out` must point to memory suitable for holding the archived integer.
+            #[inline]
+            pub unsafe fn emplace(value: $nonzero, out: *mut $archived) {
+                out.cast::<$raw>().write(to_archived!(value));
+            }
+        }
+
+        impl fmt::Debug for $archived {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.value(), f)
+            }
+        }
+
+        impl fmt::Display for $archived {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.value(), f)
+            }
+        }
+
+        impl From<$nonzero> for $archived {
+            #[inline]
+            fn from(value: $nonzero) -> Self {
+                Self(to_archived!(value))
+            }
+        }
+
+        impl From<$archived> for $nonzero {
+            #[inline]
+            fn from(value: $archived) -> Self {
+                value.value()
+            }
+        }
+
+        impl PartialEq<$nonzero> for $archived {
+            #[inline]
+            fn eq(&self, other: &$nonzero) -> bool {
+                self.value() == *other
+            }
+        }
+
+        impl PartialEq<$archived> for $nonzero {
+            #[inline]
+            fn eq(&self, other: &$archived) -> bool {
+                other.eq(self)
+            }
+        }
+
+        impl PartialOrd<$nonzero> for $archived {
+            #[inline]
+            fn partial_cmp(&self, other: &$nonzero) -> Option<Ordering> {
+                self.value().partial_cmp(other)
+            }
+        }
+
+        impl PartialOrd<$archived> for $nonzero {
+            #[inline]
+            fn partial_cmp(&self, other: &$archived) -> Option<Ordering> {
+                self.partial_cmp(&other.value())
+            }
+        }
+
+        #[cfg(feature = "validation")]
+        impl<C: ?Sized> bytecheck::CheckBytes<C> for $archived {
+            type Error = bytecheck::NonZeroCheckError;
+
+            #[inline]
+            unsafe fn check_bytes<'a>(
+                value: *const Self,
+                _: &mut C,
+            ) -> Result<&'a Self, Self::Error> {
+                // Zero has the same bytes in every byte order
+                if *value.cast::<$prim>() == 0 {
+                    Err(bytecheck::NonZeroCheckError::IsZero)
+                } else {
+                    Ok(&*value)
+                }
+            }
+        }
+    };
+}
+
+impl_archived_nonzero128!(ArchivedNonZeroI128, NonZeroI128, i128, RawNonZeroI128);
+impl_archived_nonzero128!(ArchivedNonZeroU128, NonZeroU128, u128, RawNonZeroU128);
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::Fallible;
+    use bytecheck::{CharCheckError, CheckBytes};
+
+    impl<C: Fallible + ?Sized> CheckBytes<C> for ArchivedChar {
+        type Error = CharCheckError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+            // The inner `u32` of `ArchivedChar` is always valid
+            let value = &*value;
+            let code = value.as_u32();
+
+            if char::from_u32(code).is_some() {
+                Ok(value)
+            } else {
+                Err(CharCheckError {
+                    invalid_value: code,
+                })
+            }
+        }
+    }
+};
@@ -153,7 +153,12 @@ pub struct RcResolver<T> {
 
 /// An archived `rc::Weak`.
 ///
-/// This is essentially just an optional [`ArchivedRc`].
+/// This is essentially just an optional [`ArchivedRc`]. A weak pointer that can be upgraded when
+/// it's serialized is archived as `Some` and points to the same archived value as the shared
+/// pointers to its target, regardless of whether it's serialized before or after them. Weak
+/// pointers that are dangling are archived as `None`, as are weak pointers back to a value that is
+/// still being serialized (like a child pointing to its parent) because archived pointers can only
+/// point to values that were already written.
 #[repr(u8)]
 pub enum ArchivedRcWeak<T: ArchivePointee + ?Sized, F> {
     /// A null weak pointer
@@ -214,6 +219,10 @@ impl<T: ArchivePointee + ?Sized, F> ArchivedRcWeak<T, F> {
     }
 
     /// Serializes an archived `Weak` from a given optional reference.
+    ///
+    /// If the value hasn't been serialized yet, it's serialized now and later shared pointers to
+    /// it will point to the same archived value. Values that are still being serialized can't be
+    /// pointed to, so the weak pointer is archived as `None` instead.
     #[inline]
     pub fn serialize_from_ref<U, S>(
         value: Option<&U>,
@@ -224,8 +233,10 @@ impl<T: ArchivePointee + ?Sized, F> ArchivedRcWeak<T, F> {
         S: Serializer + SharedSerializeRegistry + ?Sized,
     {
         Ok(match value {
-            None => RcWeakResolver::None,
-            Some(r) => RcWeakResolver::Some(ArchivedRc::<T, F>::serialize_from_ref(r, serializer)?),
+            Some(r) if !serializer.is_shared_pending(r) => {
+                RcWeakResolver::Some(ArchivedRc::<T, F>::serialize_from_ref(r, serializer)?)
+            }
+            _ => RcWeakResolver::None,
         })
     }
 }
@@ -2,7 +2,7 @@
 
 use super::{ArchivedRc, ArchivedRcWeak, ArchivedRcWeakTag, ArchivedRcWeakVariantSome};
 use crate::{
-    validation::{ArchiveContext, LayoutRaw, SharedContext},
+    validation::{ArchiveContext, LayoutRaw, PathSegment, SharedContext},
     ArchivePointee, RelPtr,
 };
 use bytecheck::{CheckBytes, Error};
@@ -134,7 +134,10 @@ where
             let range = context
                 .push_prefix_subtree(ptr)
                 .map_err(SharedPointerError::ContextError)?;
-            T::check_bytes(ptr, context).map_err(SharedPointerError::ValueCheckBytesError)?;
+            T::check_bytes(ptr, context).map_err(|e| {
+                context.record_check_error(&e, PathSegment::Deref, ptr.cast());
+                SharedPointerError::ValueCheckBytesError(e)
+            })?;
             context
                 .pop_prefix_range(range)
                 .map_err(SharedPointerError::ContextError)?;
@@ -1,4 +1,130 @@
 //! Relative pointer implementations and options.
+//!
+//! Relative pointers are the primitive that all of the built-in archived types use to point to
+//! other data in an archive, and they can be used the same way to build custom archived data
+//! structures like linked lists, trees, tries, and graphs.
+//!
+//! A relative pointer stores the signed distance from its own position to the position of its
+//! target. It's written in two steps:
+//!
+//! 1. During serialization, the target is serialized first and its position is returned as part
+//!    of the resolver.
+//! 2. During resolution, the relative pointer is emplaced with
+//!    [`emplace`](RelPtr::emplace) or [`resolve_emplace`](RelPtr::resolve_emplace) using the
+//!    position of the relative pointer itself and the position of the target.
+//!
+//! Relative pointers with an offset of `0` point to themselves, and are conventionally used as
+//! null pointers. They can be created with [`emplace_null`](RelPtr::emplace_null) and checked
+//! with [`is_null`](RelPtr::is_null).
+//!
+//! # Safety
+//!
+//! [`as_ptr`](RelPtr::as_ptr) and [`as_mut_ptr`](RelPtr::as_mut_ptr) are safe to call, but the
+//! pointers they return are only valid to dereference when:
+//!
+//! - The relative pointer was emplaced at the position it's located at
+//! - The relative pointer and its target are located in the same contiguous buffer, which has not
+//!   been partially moved or truncated since it was written
+//! - The relative pointer is not null
+//!
+//! The data in a buffer accessed with [`archived_root`](crate::archived_root) or validated with
+//! `check_archived_root` meets these requirements. Mutable references to archived data must
+//! remain pinned, since moving a relative pointer invalidates it.
+//!
+//! # Examples
+//!
+//! A singly-linked list which stores each node as a relative pointer to the next:
+//!
+//! ```
+//! use rkyv::{
+//!     archived_root,
+//!     out_field,
+//!     ser::{serializers::AllocSerializer, Serializer},
+//!     Archive,
+//!     Archived,
+//!     RelPtr,
+//!     Serialize,
+//! };
+//!
+//! struct Node {
+//!     value: u32,
+//!     next: Option<Box<Node>>,
+//! }
+//!
+//! struct ArchivedNode {
+//!     value: Archived<u32>,
+//!     // A null relative pointer marks the end of the list
+//!     next: RelPtr<ArchivedNode>,
+//! }
+//!
+//! impl ArchivedNode {
+//!     fn next(&self) -> Option<&ArchivedNode> {
+//!         if self.next.is_null() {
+//!             None
+//!         } else {
+//!             // SAFETY: non-null pointers were emplaced pointing at the next node.
+//!             unsafe { Some(&*self.next.as_ptr()) }
+//!         }
+//!     }
+//! }
+//!
+//! struct NodeResolver {
+//!     // The position of the next node in the archive
+//!     next: Option<usize>,
+//! }
+//!
+//! impl Archive for Node {
+//!     type Archived = ArchivedNode;
+//!     type Resolver = NodeResolver;
+//!
+//!     unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+//!         let (fp, fo) = out_field!(out.value);
+//!         self.value.resolve(pos + fp, (), fo);
+//!
+//!         // The relative pointer is located at the position of its field, not the node.
+//!         let (fp, fo) = out_field!(out.next);
+//!         match resolver.next {
+//!             Some(next_pos) => RelPtr::emplace(pos + fp, next_pos, fo),
+//!             None => RelPtr::emplace_null(pos + fp, fo),
+//!         }
+//!     }
+//! }
+//!
+//! impl<S: Serializer + ?Sized> Serialize<S> for Node {
+//!     fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+//!         // The rest of the list is written before this node so we know where it is.
+//!         let next = match self.next {
+//!             Some(ref next) => Some(serializer.serialize_value(next.as_ref())?),
+//!             None => None,
+//!         };
+//!         Ok(NodeResolver { next })
+//!     }
+//! }
+//!
+//! let list = Node {
+//!     value: 1,
+//!     next: Some(Box::new(Node {
+//!         value: 2,
+//!         next: Some(Box::new(Node {
+//!             value: 3,
+//!             next: None,
+//!         })),
+//!     })),
+//! };
+//!
+//! let mut serializer = AllocSerializer::<256>::default();
+//! serializer.serialize_value(&list).unwrap();
+//! let bytes = serializer.into_serializer().into_inner();
+//! let archived = unsafe { archived_root::<Node>(&bytes) };
+//!
+//! let mut values = Vec::new();
+//! let mut node = Some(archived);
+//! while let Some(current) = node {
+//!     values.push(current.value);
+//!     node = current.next();
+//! }
+//! assert_eq!(values, [1, 2, 3]);
+//! ```
 
 #[cfg(feature = "validation")]
 mod validation;
@@ -66,6 +192,94 @@ pub fn signed_offset(from: usize, to: usize) -> Result<isize, OffsetError> {
     }
 }
 
+/// An error where the target of a relative pointer could not be safely computed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetError {
+    /// Adding the offset to the position of the relative pointer overflowed
+    Overflow {
+        /// The position of the relative pointer
+        pos: usize,
+        /// The offset of the relative pointer
+        offset: isize,
+    },
+    /// The relative pointer is not located inside the buffer
+    BaseOutOfBounds {
+        /// The position of the relative pointer relative to the start of the buffer
+        pos: usize,
+        /// The length of the buffer
+        len: usize,
+    },
+    /// The target of the relative pointer is outside of the buffer
+    OutOfBounds {
+        /// The position of the relative pointer
+        pos: usize,
+        /// The offset of the relative pointer
+        offset: isize,
+        /// The length of the buffer
+        len: usize,
+    },
+}
+
+impl fmt::Display for TargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetError::Overflow { pos, offset } => write!(
+                f,
+                "relative pointer target overflowed: pos {} offset {}",
+                pos, offset
+            ),
+            TargetError::BaseOutOfBounds { pos, len } => write!(
+                f,
+                "relative pointer out of bounds: pos {} not in buffer of length {}",
+                pos, len
+            ),
+            TargetError::OutOfBounds { pos, offset, len } => write!(
+                f,
+                "relative pointer target out of bounds: pos {} offset {} not in buffer of length {}",
+                pos, offset, len
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TargetError {}
+
+/// Calculates the target position of a relative pointer at `pos` with the given `offset`, checking
+/// that it lies within a buffer of length `len`.
+///
+/// The target may be equal to `len`, which is the position just past the end of the buffer. This
+/// is only valid to point to with zero-sized types.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::rel_ptr::{checked_target, TargetError};
+///
+/// assert_eq!(checked_target(4, -4, 16), Ok(0));
+/// assert_eq!(checked_target(4, 12, 16), Ok(16));
+/// assert_eq!(
+///     checked_target(4, -5, 16),
+///     Err(TargetError::OutOfBounds { pos: 4, offset: -5, len: 16 }),
+/// );
+/// assert_eq!(
+///     checked_target(isize::MAX as usize, 1, usize::MAX),
+///     Err(TargetError::Overflow { pos: isize::MAX as usize, offset: 1 }),
+/// );
+/// ```
+#[inline]
+pub fn checked_target(pos: usize, offset: isize, len: usize) -> Result<usize, TargetError> {
+    let target = isize::try_from(pos)
+        .ok()
+        .and_then(|base| base.checked_add(offset))
+        .ok_or(TargetError::Overflow { pos, offset })?;
+    if target < 0 || target as usize > len {
+        Err(TargetError::OutOfBounds { pos, offset, len })
+    } else {
+        Ok(target as usize)
+    }
+}
+
 /// A offset that can be used with [`RawRelPtr`].
 pub trait Offset: Copy {
     /// Creates a new offset between a `from` position and a `to` position.
@@ -208,13 +422,50 @@ impl<O: Offset> RawRelPtr<O> {
     }
 
     /// Calculates the memory address being pointed to by this relative pointer.
+    ///
+    /// The returned pointer is only valid to dereference under the conditions described in the
+    /// [module-level safety documentation](crate::rel_ptr#safety).
     #[inline]
     pub fn as_ptr(&self) -> *const () {
         unsafe { self.base().offset(self.offset()).cast() }
     }
 
+    /// Calculates the memory address being pointed to by this relative pointer, returning an
+    /// error if computing it would overflow the address space.
+    #[inline]
+    pub fn checked_as_ptr(&self) -> Result<*const (), TargetError> {
+        let pos = self.base() as usize;
+        let offset = self.offset();
+        let target = if offset >= 0 {
+            pos.checked_add(offset as usize)
+        } else {
+            pos.checked_sub(offset.unsigned_abs())
+        };
+        target.ok_or(TargetError::Overflow { pos, offset })?;
+        Ok(self.base().wrapping_offset(offset).cast())
+    }
+
+    /// Calculates the memory address being pointed to by this relative pointer, returning an
+    /// error if the relative pointer or its target are not located in `bytes`.
+    ///
+    /// This only checks the position of the target. It doesn't check that there's enough space
+    /// for the pointee or that the target is properly aligned, which is done during validation.
+    #[inline]
+    pub fn checked_as_ptr_in(&self, bytes: &[u8]) -> Result<*const (), TargetError> {
+        let pos = (self.base() as usize).wrapping_sub(bytes.as_ptr() as usize);
+        let len = bytes.len();
+        if pos > len || len - pos < core::mem::size_of::<Self>() {
+            return Err(TargetError::BaseOutOfBounds { pos, len });
+        }
+        let target = checked_target(pos, self.offset(), len)?;
+        Ok(bytes.as_ptr().wrapping_add(target).cast())
+    }
+
     /// Returns an unsafe mutable pointer to the memory address being pointed to
     /// by this relative pointer.
+    ///
+    /// The returned pointer is only valid to dereference under the conditions described in the
+    /// [module-level safety documentation](crate::rel_ptr#safety).
     #[inline]
     pub fn as_mut_ptr(&mut self) -> *mut () {
         unsafe { self.base_mut().offset(self.offset()).cast() }
@@ -261,9 +512,13 @@ pub type RawRelPtrU64 = RawRelPtr<Archived<u64>>;
 
 /// A pointer which resolves to relative to its position in memory.
 ///
-/// This is a strongly-typed version of [`RawRelPtr`].
+/// This is a strongly-typed version of [`RawRelPtr`]. Along with the offset, it stores the
+/// archived metadata of its target, which is `()` for sized types and the length for slices and
+/// strings.
 ///
-/// See [`Archive`](crate::Archive) for an example of creating one.
+/// See the [module-level documentation](crate::rel_ptr) for an example of building a custom
+/// archived data structure with relative pointers, and [`Archive`](crate::Archive) for an example
+/// of pointing to unsized data.
 pub struct RelPtr<T: ArchivePointee + ?Sized, O> {
     raw_ptr: RawRelPtr<O>,
     metadata: T::ArchivedMetadata,
@@ -414,13 +669,42 @@ impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
     }
 
     /// Calculates the memory address being pointed to by this relative pointer.
+    ///
+    /// The returned pointer is only valid to dereference under the conditions described in the
+    /// [module-level safety documentation](crate::rel_ptr#safety).
     #[inline]
     pub fn as_ptr(&self) -> *const T {
         ptr_meta::from_raw_parts(self.raw_ptr.as_ptr(), T::pointer_metadata(&self.metadata))
     }
 
+    /// Calculates the memory address being pointed to by this relative pointer, returning an
+    /// error if computing it would overflow the address space.
+    #[inline]
+    pub fn checked_as_ptr(&self) -> Result<*const T, TargetError> {
+        Ok(ptr_meta::from_raw_parts(
+            self.raw_ptr.checked_as_ptr()?,
+            T::pointer_metadata(&self.metadata),
+        ))
+    }
+
+    /// Calculates the memory address being pointed to by this relative pointer, returning an
+    /// error if the relative pointer or its target are not located in `bytes`.
+    ///
+    /// This only checks the position of the target. It doesn't check that there's enough space
+    /// for the pointee or that the target is properly aligned, which is done during validation.
+    #[inline]
+    pub fn checked_as_ptr_in(&self, bytes: &[u8]) -> Result<*const T, TargetError> {
+        Ok(ptr_meta::from_raw_parts(
+            self.raw_ptr.checked_as_ptr_in(bytes)?,
+            T::pointer_metadata(&self.metadata),
+        ))
+    }
+
     /// Returns an unsafe mutable pointer to the memory address being pointed to by this relative
     /// pointer.
+    ///
+    /// The returned pointer is only valid to dereference under the conditions described in the
+    /// [module-level safety documentation](crate::rel_ptr#safety).
     #[inline]
     pub fn as_mut_ptr(&mut self) -> *mut T {
         ptr_meta::from_raw_parts_mut(
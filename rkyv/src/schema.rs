@@ -0,0 +1,804 @@
+//! Schema descriptors for walking archives without knowing their types.
+//!
+//! A [`Schema`] describes the layout of an archived type: the kind of value it is, and for
+//! compound types the names, offsets, and schemas of its fields. Types that implement
+//! [`ArchiveSchema`] have a static schema, and deriving `Archive` with `#[archive(schema)]`
+//! implements it for structs and enums. An [`ArchivedValue`] pairs a schema with a pointer into an
+//! archive, so generic tools like debuggers and inspectors can read any archive whose root type has
+//! a schema:
+//!
+//! ```
+//! use rkyv::{schema::ArchivedValue, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(schema)]
+//! enum Shape {
+//!     Circle { radius: f32 },
+//!     Polygon(Vec<i32>),
+//! }
+//!
+//! #[derive(Archive, Serialize)]
+//! #[archive(schema)]
+//! struct Scene {
+//!     name: String,
+//!     shapes: Vec<Shape>,
+//!     selected: Option<u32>,
+//! }
+//!
+//! let value = Scene {
+//!     name: "demo".to_string(),
+//!     shapes: vec![Shape::Circle { radius: 1.5 }, Shape::Polygon(vec![1, 2, 3])],
+//!     selected: Some(1),
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//!
+//! let root = unsafe { ArchivedValue::root::<Scene>(&bytes) };
+//! assert_eq!(root.field("name").unwrap().as_str(), Some("demo"));
+//! let shapes = root.field("shapes").unwrap();
+//! assert_eq!(shapes.len(), Some(2));
+//! let polygon = shapes.get(1).unwrap();
+//! assert_eq!(polygon.variant().unwrap().name, "Polygon");
+//! assert_eq!(polygon.field("0").unwrap().get(2).unwrap().as_i64(), Some(3));
+//!
+//! assert_eq!(
+//!     format!("{:?}", root),
+//!     "ArchivedScene { \
+//!         name: \"demo\", \
+//!         shapes: [Circle { radius: 1.5 }, Polygon { 0: [1, 2, 3] }], \
+//!         selected: Some(1) \
+//!     }",
+//! );
+//! ```
+//!
+//! [`hash_archived`] uses the schema of a value to hash its contents independently of the bytes of
+//! its archive.
+//! The [`diff`](crate::diff) module uses schemas to compare two archived values field by field.
+//!
+//! Fields archived with a wrapper (`#[with(...)]`) and fields with omitted bounds are described as
+//! [`Schema::Opaque`], since their archived types can't be described in general. Recursive types
+//! must omit the bounds of their recursive fields, which makes those fields opaque as well.
+
+use crate::{fingerprint::FingerprintHasher, Archive, Archived, FixedUsize};
+use core::{
+    convert::TryFrom,
+    fmt,
+    marker::PhantomData,
+    mem::{align_of, size_of},
+};
+
+/// A description of the layout of an archived type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Schema {
+    /// An archived `()`
+    Unit,
+    /// An archived `bool`
+    Bool,
+    /// An archived `i8`
+    I8,
+    /// An archived `i16`
+    I16,
+    /// An archived `i32`
+    I32,
+    /// An archived `i64`
+    I64,
+    /// An archived `i128`
+    I128,
+    /// An archived `u8`
+    U8,
+    /// An archived `u16`
+    U16,
+    /// An archived `u32`
+    U32,
+    /// An archived `u64`
+    U64,
+    /// An archived `u128`
+    U128,
+    /// An archived `f32`
+    F32,
+    /// An archived `f64`
+    F64,
+    /// An archived `char`
+    Char,
+    /// An [`ArchivedString`](crate::string::ArchivedString)
+    String,
+    /// An archived array
+    Array {
+        /// The schema of the elements of the array
+        element: &'static Schema,
+        /// The number of elements in the array
+        len: usize,
+    },
+    /// An [`ArchivedVec`](crate::vec::ArchivedVec)
+    Vec {
+        /// The schema of the elements of the vector
+        element: &'static Schema,
+    },
+    /// An [`ArchivedBox`](crate::boxed::ArchivedBox) of a sized type
+    Box {
+        /// The schema of the boxed value
+        inner: &'static Schema,
+    },
+    /// An [`ArchivedOption`](crate::option::ArchivedOption)
+    Option {
+        /// The schema of the value in `Some`
+        inner: &'static Schema,
+    },
+    /// An archived struct
+    Struct {
+        /// The name of the archived struct
+        name: &'static str,
+        /// The size of the archived struct in bytes
+        size: usize,
+        /// The alignment of the archived struct in bytes
+        align: usize,
+        /// The fields of the archived struct, in the order they're declared
+        fields: &'static [FieldSchema],
+    },
+    /// An archived enum
+    ///
+    /// The tag of an archived enum is always stored in native byte order.
+    Enum {
+        /// The name of the archived enum
+        name: &'static str,
+        /// The size of the archived enum in bytes
+        size: usize,
+        /// The alignment of the archived enum in bytes
+        align: usize,
+        /// The schema of the integer tag at the start of the archived enum
+        tag: &'static Schema,
+        /// The variants of the archived enum
+        variants: &'static [VariantSchema],
+    },
+    /// An archived type that can't be described
+    Opaque {
+        /// The name of the type before it was archived
+        name: &'static str,
+        /// The size of the archived type in bytes
+        size: usize,
+        /// The alignment of the archived type in bytes
+        align: usize,
+    },
+}
+
+/// A description of a field of an archived struct or enum variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldSchema {
+    /// The name of the field. Fields of tuple structs and variants are named by their index.
+    pub name: &'static str,
+    /// The offset of the field from the start of the archived struct or enum in bytes.
+    pub offset: usize,
+    /// The schema of the archived field.
+    pub schema: &'static Schema,
+}
+
+/// A description of a variant of an archived enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VariantSchema {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The value of the tag for the variant.
+    pub tag: i128,
+    /// The fields of the variant, with offsets from the start of the archived enum.
+    pub fields: &'static [FieldSchema],
+}
+
+pub(crate) const fn round_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Reads the tag of an archived enum with the given tag schema, or returns `None` if the schema is
+/// not an integer.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of the size of the tag.
+pub(crate) unsafe fn read_tag(ptr: *const u8, tag: &Schema) -> Option<i128> {
+    // Enum tags are always in native byte order
+    Some(match tag {
+        Schema::I8 => ptr.cast::<i8>().read_unaligned() as i128,
+        Schema::I16 => ptr.cast::<i16>().read_unaligned() as i128,
+        Schema::I32 => ptr.cast::<i32>().read_unaligned() as i128,
+        Schema::I64 => ptr.cast::<i64>().read_unaligned() as i128,
+        Schema::I128 => ptr.cast::<i128>().read_unaligned(),
+        Schema::U8 => ptr.cast::<u8>().read_unaligned() as i128,
+        Schema::U16 => ptr.cast::<u16>().read_unaligned() as i128,
+        Schema::U32 => ptr.cast::<u32>().read_unaligned() as i128,
+        Schema::U64 => ptr.cast::<u64>().read_unaligned() as i128,
+        Schema::U128 => ptr.cast::<u128>().read_unaligned() as i128,
+        _ => return None,
+    })
+}
+
+impl Schema {
+    /// Returns the size of the archived type in bytes.
+    pub const fn size(&self) -> usize {
+        match *self {
+            Schema::Unit => 0,
+            Schema::Bool => size_of::<Archived<bool>>(),
+            Schema::I8 => size_of::<Archived<i8>>(),
+            Schema::I16 => size_of::<Archived<i16>>(),
+            Schema::I32 => size_of::<Archived<i32>>(),
+            Schema::I64 => size_of::<Archived<i64>>(),
+            Schema::I128 => size_of::<Archived<i128>>(),
+            Schema::U8 => size_of::<Archived<u8>>(),
+            Schema::U16 => size_of::<Archived<u16>>(),
+            Schema::U32 => size_of::<Archived<u32>>(),
+            Schema::U64 => size_of::<Archived<u64>>(),
+            Schema::U128 => size_of::<Archived<u128>>(),
+            Schema::F32 => size_of::<Archived<f32>>(),
+            Schema::F64 => size_of::<Archived<f64>>(),
+            Schema::Char => size_of::<Archived<char>>(),
+            Schema::String => size_of::<crate::string::ArchivedString>(),
+            Schema::Array { element, len } => element.size() * len,
+            Schema::Vec { .. } => size_of::<crate::vec::ArchivedVec<()>>(),
+            Schema::Box { .. } => size_of::<crate::boxed::ArchivedBox<()>>(),
+            Schema::Option { inner } => round_up(
+                round_up(1, inner.align()) + inner.size(),
+                max(1, inner.align()),
+            ),
+            Schema::Struct { size, .. }
+            | Schema::Enum { size, .. }
+            | Schema::Opaque { size, .. } => size,
+        }
+    }
+
+    /// Returns the alignment of the archived type in bytes.
+    pub const fn align(&self) -> usize {
+        match *self {
+            Schema::Unit => 1,
+            Schema::Bool => align_of::<Archived<bool>>(),
+            Schema::I8 => align_of::<Archived<i8>>(),
+            Schema::I16 => align_of::<Archived<i16>>(),
+            Schema::I32 => align_of::<Archived<i32>>(),
+            Schema::I64 => align_of::<Archived<i64>>(),
+            Schema::I128 => align_of::<Archived<i128>>(),
+            Schema::U8 => align_of::<Archived<u8>>(),
+            Schema::U16 => align_of::<Archived<u16>>(),
+            Schema::U32 => align_of::<Archived<u32>>(),
+            Schema::U64 => align_of::<Archived<u64>>(),
+            Schema::U128 => align_of::<Archived<u128>>(),
+            Schema::F32 => align_of::<Archived<f32>>(),
+            Schema::F64 => align_of::<Archived<f64>>(),
+            Schema::Char => align_of::<Archived<char>>(),
+            Schema::String => align_of::<crate::string::ArchivedString>(),
+            Schema::Array { element, .. } => element.align(),
+            Schema::Vec { .. } => align_of::<crate::vec::ArchivedVec<()>>(),
+            Schema::Box { .. } => align_of::<crate::boxed::ArchivedBox<()>>(),
+            Schema::Option { inner } => max(1, inner.align()),
+            Schema::Struct { align, .. }
+            | Schema::Enum { align, .. }
+            | Schema::Opaque { align, .. } => align,
+        }
+    }
+}
+
+/// A type whose archived type has a static [`Schema`].
+///
+/// This can be implemented for derived types with `#[archive(schema)]`.
+///
+/// # Safety
+///
+/// The schema must exactly describe the layout of the archived type, since archived values are
+/// read according to their schema.
+pub unsafe trait ArchiveSchema {
+    /// The schema of the archived type.
+    const SCHEMA: Schema;
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $schema:ident,)*) => {
+        $(
+            unsafe impl ArchiveSchema for $ty {
+                const SCHEMA: Schema = Schema::$schema;
+            }
+        )*
+    };
+}
+
+impl_primitive! {
+    () => Unit,
+    bool => Bool,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    f32 => F32,
+    f64 => F64,
+    char => Char,
+}
+
+unsafe impl ArchiveSchema for usize {
+    const SCHEMA: Schema = <FixedUsize as ArchiveSchema>::SCHEMA;
+}
+
+unsafe impl ArchiveSchema for isize {
+    const SCHEMA: Schema = <crate::FixedIsize as ArchiveSchema>::SCHEMA;
+}
+
+unsafe impl<T: ArchiveSchema, const N: usize> ArchiveSchema for [T; N] {
+    const SCHEMA: Schema = Schema::Array {
+        element: &T::SCHEMA,
+        len: N,
+    };
+}
+
+unsafe impl<T: ArchiveSchema> ArchiveSchema for Option<T> {
+    const SCHEMA: Schema = Schema::Option { inner: &T::SCHEMA };
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    #[cfg(not(feature = "std"))]
+    use alloc::{boxed::Box, string::String, vec::Vec};
+
+    unsafe impl ArchiveSchema for String {
+        const SCHEMA: Schema = Schema::String;
+    }
+
+    unsafe impl<T: ArchiveSchema> ArchiveSchema for Vec<T> {
+        const SCHEMA: Schema = Schema::Vec {
+            element: &T::SCHEMA,
+        };
+    }
+
+    unsafe impl<T: ArchiveSchema> ArchiveSchema for Box<T> {
+        const SCHEMA: Schema = Schema::Box { inner: &T::SCHEMA };
+    }
+};
+
+/// A value in an archive along with its schema.
+///
+/// Values can be inspected with the accessor for their kind of schema, which returns `None` if the
+/// value has a different kind of schema. The `Debug` implementation prints the whole value.
+#[derive(Clone, Copy)]
+pub struct ArchivedValue<'a> {
+    ptr: *const u8,
+    schema: &'static Schema,
+    _phantom: PhantomData<&'a [u8]>,
+}
+
+impl<'a> ArchivedValue<'a> {
+    /// Returns the value with the given schema at the given pointer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid archived value with the given schema, inside of an archive that
+    /// lives for `'a`.
+    #[inline]
+    pub unsafe fn new(ptr: *const u8, schema: &'static Schema) -> Self {
+        Self {
+            ptr,
+            schema,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the root value of the given archive bytes.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must contain a valid archive of `T` with the root at the end, as for
+    /// [`archived_root`](crate::archived_root).
+    #[inline]
+    pub unsafe fn root<T: ArchiveSchema>(bytes: &'a [u8]) -> Self {
+        let schema = &T::SCHEMA;
+        Self::new(bytes.as_ptr().add(bytes.len() - schema.size()), schema)
+    }
+
+    /// Returns the schema of the value.
+    #[inline]
+    pub fn schema(&self) -> &'static Schema {
+        self.schema
+    }
+
+    /// Returns a pointer to the archived value.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr
+    }
+
+    #[inline]
+    unsafe fn read<T: Copy>(&self) -> T {
+        *self.ptr.cast::<T>()
+    }
+
+    #[inline]
+    fn child(&self, offset: usize, schema: &'static Schema) -> Self {
+        unsafe { Self::new(self.ptr.add(offset), schema) }
+    }
+
+    /// Returns the value of a `bool`.
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.schema {
+            Schema::Bool => Some(unsafe { self.read::<bool>() }),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a signed integer, or of an unsigned integer that fits in an `i128`.
+    pub fn as_i128(&self) -> Option<i128> {
+        unsafe {
+            match self.schema {
+                Schema::I8 => Some(self.read::<i8>() as i128),
+                Schema::I16 => Some(from_archived!(self.read::<Archived<i16>>()) as i128),
+                Schema::I32 => Some(from_archived!(self.read::<Archived<i32>>()) as i128),
+                Schema::I64 => Some(from_archived!(self.read::<Archived<i64>>()) as i128),
+                Schema::I128 => Some(self.read::<Archived<i128>>().value()),
+                _ => self.as_u128().and_then(|v| i128::try_from(v).ok()),
+            }
+        }
+    }
+
+    /// Returns the value of an unsigned integer.
+    pub fn as_u128(&self) -> Option<u128> {
+        unsafe {
+            match self.schema {
+                Schema::U8 => Some(self.read::<u8>() as u128),
+                Schema::U16 => Some(from_archived!(self.read::<Archived<u16>>()) as u128),
+                Schema::U32 => Some(from_archived!(self.read::<Archived<u32>>()) as u128),
+                Schema::U64 => Some(from_archived!(self.read::<Archived<u64>>()) as u128),
+                Schema::U128 => Some(self.read::<Archived<u128>>().value()),
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns the value of an integer that fits in an `i64`.
+    #[inline]
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i128().and_then(|v| i64::try_from(v).ok())
+    }
+
+    /// Returns the value of an unsigned integer that fits in a `u64`.
+    #[inline]
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_u128().and_then(|v| u64::try_from(v).ok())
+    }
+
+    /// Returns the value of a floating-point number.
+    pub fn as_f64(&self) -> Option<f64> {
+        unsafe {
+            match self.schema {
+                Schema::F32 => Some(from_archived!(self.read::<Archived<f32>>()) as f64),
+                Schema::F64 => Some(from_archived!(self.read::<Archived<f64>>())),
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns the value of a `char`.
+    #[inline]
+    pub fn as_char(&self) -> Option<char> {
+        match self.schema {
+            Schema::Char => Some(unsafe { self.read::<Archived<char>>() }.as_char()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a string.
+    #[inline]
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.schema {
+            Schema::String => {
+                Some(unsafe { (*self.ptr.cast::<crate::string::ArchivedString>()).as_str() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of elements in an array or vector.
+    #[inline]
+    pub fn len(&self) -> Option<usize> {
+        match *self.schema {
+            Schema::Array { len, .. } => Some(len),
+            Schema::Vec { .. } => {
+                Some(unsafe { (*self.ptr.cast::<crate::vec::ArchivedVec<u8>>()).len() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether an array or vector has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Returns the element at the given index of an array or vector, or `None` if the index is out
+    /// of bounds.
+    pub fn get(&self, index: usize) -> Option<Self> {
+        if index >= self.len()? {
+            return None;
+        }
+        match *self.schema {
+            Schema::Array { element, .. } => Some(self.child(index * element.size(), element)),
+            Schema::Vec { element } => unsafe {
+                let elements = (*self.ptr.cast::<crate::vec::ArchivedVec<u8>>()).as_ptr();
+                Some(Self::new(elements.add(index * element.size()), element))
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the value inside a box, or the value inside an option if it is `Some`.
+    pub fn inner(&self) -> Option<Self> {
+        match *self.schema {
+            Schema::Box { inner } => unsafe {
+                let ptr = (*self.ptr.cast::<crate::boxed::ArchivedBox<u8>>()).get() as *const u8;
+                Some(Self::new(ptr, inner))
+            },
+            Schema::Option { inner } => {
+                if unsafe { self.read::<u8>() } == 0 {
+                    None
+                } else {
+                    Some(self.child(round_up(1, inner.align()), inner))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the variant of an enum.
+    pub fn variant(&self) -> Option<&'static VariantSchema> {
+        match *self.schema {
+            Schema::Enum { tag, variants, .. } => {
+                let tag = unsafe { read_tag(self.ptr, tag)? };
+                variants.iter().find(|v| v.tag == tag)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the fields of a struct or of the variant of an enum.
+    #[inline]
+    pub fn fields(&self) -> Option<Fields<'a>> {
+        let fields = match *self.schema {
+            Schema::Struct { fields, .. } => fields,
+            Schema::Enum { .. } => self.variant()?.fields,
+            _ => return None,
+        };
+        Some(Fields {
+            value: *self,
+            fields: fields.iter(),
+        })
+    }
+
+    /// Returns the field of a struct or of the variant of an enum with the given name.
+    #[inline]
+    pub fn field(&self, name: &str) -> Option<Self> {
+        self.fields()?
+            .find(|(field_name, _)| *field_name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns a stable hash of the contents of the value.
+    ///
+    /// See [`hash_archived`] for details.
+    #[inline]
+    pub fn content_hash(&self) -> u64 {
+        self.hash_into(FingerprintHasher::new()).finish()
+    }
+
+    fn hash_into(&self, hasher: FingerprintHasher) -> FingerprintHasher {
+        // Every value starts with its kind, so that values of different kinds can't collide
+        let hasher = hasher.write(&[HashKind::of(self.schema) as u8]);
+        match *self.schema {
+            Schema::Unit => hasher,
+            Schema::Bool => hasher.write(&[self.as_bool().unwrap() as u8]),
+            Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 | Schema::I128 => {
+                hasher.write(&self.as_i128().unwrap().to_le_bytes())
+            }
+            Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 | Schema::U128 => {
+                hasher.write(&self.as_u128().unwrap().to_le_bytes())
+            }
+            Schema::F32 => {
+                let value = from_archived!(unsafe { self.read::<Archived<f32>>() });
+                hasher.write(&value.to_bits().to_le_bytes())
+            }
+            Schema::F64 => hasher.write(&self.as_f64().unwrap().to_bits().to_le_bytes()),
+            Schema::Char => hasher.write(&(self.as_char().unwrap() as u32).to_le_bytes()),
+            Schema::String => hasher.write_str(self.as_str().unwrap()),
+            Schema::Array { .. } | Schema::Vec { .. } => {
+                let len = self.len().unwrap();
+                (0..len).fold(hasher.write_usize(len), |hasher, i| {
+                    self.get(i).unwrap().hash_into(hasher)
+                })
+            }
+            Schema::Box { .. } => self.inner().unwrap().hash_into(hasher),
+            Schema::Option { .. } => match self.inner() {
+                None => hasher.write(&[0]),
+                Some(inner) => inner.hash_into(hasher.write(&[1])),
+            },
+            Schema::Struct { .. } | Schema::Enum { .. } => {
+                let hasher = match self.variant() {
+                    Some(variant) => hasher.write_str(variant.name),
+                    None => hasher,
+                };
+                let fields = self.fields().unwrap();
+                let len = fields.len();
+                fields.fold(hasher.write_usize(len), |hasher, (name, value)| {
+                    value.hash_into(hasher.write_str(name))
+                })
+            }
+            Schema::Opaque { size, .. } => {
+                hasher.write(unsafe { core::slice::from_raw_parts(self.ptr, size) })
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum HashKind {
+    Unit,
+    Bool,
+    Signed,
+    Unsigned,
+    F32,
+    F64,
+    Char,
+    String,
+    Array,
+    Vec,
+    Box,
+    Option,
+    Struct,
+    Enum,
+    Opaque,
+}
+
+impl HashKind {
+    fn of(schema: &Schema) -> Self {
+        match schema {
+            Schema::Unit => HashKind::Unit,
+            Schema::Bool => HashKind::Bool,
+            Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 | Schema::I128 => HashKind::Signed,
+            Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 | Schema::U128 => {
+                HashKind::Unsigned
+            }
+            Schema::F32 => HashKind::F32,
+            Schema::F64 => HashKind::F64,
+            Schema::Char => HashKind::Char,
+            Schema::String => HashKind::String,
+            Schema::Array { .. } => HashKind::Array,
+            Schema::Vec { .. } => HashKind::Vec,
+            Schema::Box { .. } => HashKind::Box,
+            Schema::Option { .. } => HashKind::Option,
+            Schema::Struct { .. } => HashKind::Struct,
+            Schema::Enum { .. } => HashKind::Enum,
+            Schema::Opaque { .. } => HashKind::Opaque,
+        }
+    }
+}
+
+/// Returns a stable hash of the contents of an archived value.
+///
+/// The hash is computed by walking the value with its schema, so it only depends on the values of
+/// the fields and elements and not on the bytes of the archive. Two archives of equal values have
+/// the same hash even if their padding bytes differ or their values are laid out at different
+/// positions, which makes the hash suitable for cache keys and deduplicating archives. The hash is
+/// also the same on every platform and with every endianness and size feature: integers are hashed
+/// by their value regardless of their width, and floats by their bits.
+///
+/// The names of fields and enum variants are part of the hash, but the names of structs and enums
+/// are not. Opaque fields are hashed as their raw bytes, which may include padding and relative
+/// pointers whose values depend on the position of the field.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{schema::hash_archived, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive(schema)]
+/// #[archive_attr(repr(C))]
+/// struct Entry {
+///     key: u8,
+///     value: u64,
+/// }
+///
+/// let entry = Entry { key: 1, value: 2 };
+/// let mut a = rkyv::to_bytes::<_, 64>(&entry).unwrap();
+/// let mut b = a.clone();
+///
+/// // Fill the padding after the key with different bytes
+/// let pos = a.len() - core::mem::size_of::<ArchivedEntry>();
+/// a[pos + 1..pos + 8].fill(0x00);
+/// b[pos + 1..pos + 8].fill(0xff);
+/// assert_ne!(a.as_slice(), b.as_slice());
+///
+/// let hash_a = hash_archived::<Entry>(unsafe { rkyv::archived_root::<Entry>(&a) });
+/// let hash_b = hash_archived::<Entry>(unsafe { rkyv::archived_root::<Entry>(&b) });
+/// assert_eq!(hash_a, hash_b);
+/// ```
+#[inline]
+pub fn hash_archived<T: Archive + ArchiveSchema + ?Sized>(value: &T::Archived) -> u64 {
+    unsafe { ArchivedValue::new((value as *const T::Archived).cast(), &T::SCHEMA) }.content_hash()
+}
+
+impl fmt::Debug for ArchivedValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self.schema {
+            Schema::Unit => write!(f, "()"),
+            Schema::Bool => fmt::Debug::fmt(&self.as_bool().unwrap(), f),
+            Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 | Schema::I128 => {
+                fmt::Debug::fmt(&self.as_i128().unwrap(), f)
+            }
+            Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 | Schema::U128 => {
+                fmt::Debug::fmt(&self.as_u128().unwrap(), f)
+            }
+            Schema::F32 | Schema::F64 => fmt::Debug::fmt(&self.as_f64().unwrap(), f),
+            Schema::Char => fmt::Debug::fmt(&self.as_char().unwrap(), f),
+            Schema::String => fmt::Debug::fmt(self.as_str().unwrap(), f),
+            Schema::Array { .. } | Schema::Vec { .. } => {
+                let len = self.len().unwrap();
+                f.debug_list()
+                    .entries((0..len).map(|i| self.get(i).unwrap()))
+                    .finish()
+            }
+            Schema::Box { .. } => fmt::Debug::fmt(&self.inner().unwrap(), f),
+            Schema::Option { .. } => fmt::Debug::fmt(&self.inner(), f),
+            Schema::Struct { name, .. } => {
+                let mut s = f.debug_struct(name);
+                for (field_name, value) in self.fields().unwrap() {
+                    s.field(field_name, &value);
+                }
+                s.finish()
+            }
+            Schema::Enum { name, .. } => match self.variant() {
+                Some(variant) => {
+                    if variant.fields.is_empty() {
+                        write!(f, "{}", variant.name)
+                    } else {
+                        let mut s = f.debug_struct(variant.name);
+                        for (field_name, value) in self.fields().unwrap() {
+                            s.field(field_name, &value);
+                        }
+                        s.finish()
+                    }
+                }
+                None => write!(f, "<invalid {} variant>", name),
+            },
+            Schema::Opaque { name, .. } => write!(f, "<{}>", name),
+        }
+    }
+}
+
+/// An iterator over the fields of an [`ArchivedValue`].
+pub struct Fields<'a> {
+    value: ArchivedValue<'a>,
+    fields: core::slice::Iter<'static, FieldSchema>,
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = (&'static str, ArchivedValue<'a>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let field = self.fields.next()?;
+        Some((field.name, self.value.child(field.offset, field.schema)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.fields.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Fields<'_> {}
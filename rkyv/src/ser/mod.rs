@@ -8,7 +8,9 @@ use core::{alloc::Layout, mem, ptr::NonNull, slice};
 /// A byte sink that knows where it is.
 ///
 /// A type that is [`io::Write`](std::io::Write) can be wrapped in a
-/// [`WriteSerializer`](serializers::WriteSerializer) to equip it with `Serializer`.
+/// [`WriteSerializer`](serializers::WriteSerializer) to equip it with `Serializer`. Writers that
+/// are expensive to write to, like files and sockets, should be wrapped in a
+/// [`BufferedWriteSerializer`](serializers::BufferedWriteSerializer) instead.
 ///
 /// It's important that the memory for archived objects is properly aligned before attempting to
 /// read objects out of it; use an [`AlignedVec`](crate::AlignedVec) or the
@@ -20,14 +22,32 @@ pub trait Serializer: Fallible {
     /// Attempts to write the given bytes to the serializer.
     fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
 
+    /// Attempts to write each of the given slices of bytes to the serializer in order.
+    ///
+    /// This is equivalent to writing each slice with [`write`](Serializer::write), but serializers
+    /// backed by a writer which supports vectored writes can write them with a single call.
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        for bytes in bufs {
+            self.write(bytes)?;
+        }
+        Ok(())
+    }
+
     /// Advances the given number of bytes as padding.
+    ///
+    /// The padding bytes are written as zeroes.
     #[inline]
-    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+    fn pad(&mut self, mut padding: usize) -> Result<(), Self::Error> {
         const MAX_ZEROES: usize = 32;
         const ZEROES: [u8; MAX_ZEROES] = [0; MAX_ZEROES];
-        debug_assert!(padding < MAX_ZEROES);
 
-        self.write(&ZEROES[0..padding])
+        while padding > 0 {
+            let len = usize::min(padding, MAX_ZEROES);
+            self.write(&ZEROES[0..len])?;
+            padding -= len;
+        }
+        Ok(())
     }
 
     /// Aligns the position of the serializer to the given alignment.
@@ -167,6 +187,28 @@ pub trait SharedSerializeRegistry: Fallible {
         self.add_shared_ptr(value as *const T as *const u8, pos)
     }
 
+    /// Marks a shared pointer as being serialized until its position is added to the registry.
+    ///
+    /// Registries that don't track pending shared pointers can ignore this.
+    #[inline]
+    fn start_shared_ptr(&mut self, value: *const u8) -> Result<(), Self::Error> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Returns whether a shared pointer is currently being serialized.
+    #[inline]
+    fn is_shared_ptr_pending(&self, value: *const u8) -> bool {
+        let _ = value;
+        false
+    }
+
+    /// Returns whether a shared value is currently being serialized.
+    #[inline]
+    fn is_shared_pending<T: ?Sized>(&self, value: &T) -> bool {
+        self.is_shared_ptr_pending(value as *const T as *const u8)
+    }
+
     /// Archives the given shared value and returns its position. If the value has already been
     /// added then it returns the position of the previously added value.
     #[inline]
@@ -180,6 +222,7 @@ pub trait SharedSerializeRegistry: Fallible {
         if let Some(pos) = self.get_shared(value) {
             Ok(pos)
         } else {
+            self.start_shared_ptr(value as *const T as *const u8)?;
             let pos = value.serialize_unsized(self)?;
             self.add_shared(value, pos)?;
             Ok(pos)
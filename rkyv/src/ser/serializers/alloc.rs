@@ -14,9 +14,9 @@ use core::{
     ptr::NonNull,
 };
 #[cfg(not(feature = "std"))]
-use hashbrown::hash_map;
+use hashbrown::{hash_map, hash_set};
 #[cfg(feature = "std")]
-use std::collections::hash_map;
+use std::collections::{hash_map, hash_set};
 
 /// A serializer made specifically to work with [`AlignedVec`](crate::util::AlignedVec).
 ///
@@ -66,6 +66,13 @@ impl<A: Borrow<AlignedVec> + BorrowMut<AlignedVec>> Serializer for AlignedSerial
         Ok(())
     }
 
+    #[inline]
+    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+        let vec = self.inner.borrow_mut();
+        vec.resize(vec.len() + padding, 0);
+        Ok(())
+    }
+
     #[inline]
     unsafe fn resolve_aligned<T: Archive + ?Sized>(
         &mut self,
@@ -229,12 +236,37 @@ const _: () = {
 
 /// Scratch space that always uses the global allocator.
 ///
+/// Scratch space is allocated from blocks of memory that are kept after they're popped and reused
+/// for later allocations. Each new block is at least twice as large as the last one, so only a
+/// logarithmic number of heap allocations are made no matter how many times scratch space is
+/// pushed and popped. This makes serializing large nested collections with spilled scratch space
+/// almost allocation-free.
+///
 /// This allocator will panic if scratch is popped that it did not allocate. For this reason, it
 /// should only ever be used as a fallback allocator.
 #[derive(Debug)]
 pub struct AllocScratch {
     remaining: Option<usize>,
-    allocations: Vec<(*mut u8, Layout)>,
+    blocks: Vec<AllocBlock>,
+    current: usize,
+    allocations: Vec<AllocRecord>,
+}
+
+/// A block of memory that scratch space is allocated from.
+#[derive(Debug)]
+struct AllocBlock {
+    ptr: *mut u8,
+    layout: Layout,
+    used: usize,
+}
+
+/// An outstanding allocation and the state of its block before it was made.
+#[derive(Debug)]
+struct AllocRecord {
+    ptr: *mut u8,
+    layout: Layout,
+    block: usize,
+    prev_used: usize,
 }
 
 // SAFETY: AllocScratch is safe to send to another thread
@@ -246,10 +278,17 @@ unsafe impl Send for AllocScratch {}
 unsafe impl Sync for AllocScratch {}
 
 impl AllocScratch {
+    /// The size of the first block of memory allocated.
+    const MIN_BLOCK_SIZE: usize = 4096;
+    /// The minimum alignment of allocated blocks of memory.
+    const MIN_BLOCK_ALIGN: usize = 16;
+
     /// Creates a new scratch allocator with no allocation limit.
     pub fn new() -> Self {
         Self {
             remaining: None,
+            blocks: Vec::new(),
+            current: 0,
             allocations: Vec::new(),
         }
     }
@@ -258,16 +297,78 @@ impl AllocScratch {
     pub fn with_limit(limit: usize) -> Self {
         Self {
             remaining: Some(limit),
+            blocks: Vec::new(),
+            current: 0,
             allocations: Vec::new(),
         }
     }
+
+    /// Returns the total size of the blocks of memory that this allocator has allocated.
+    ///
+    /// Blocks are kept until the allocator is dropped, so this is also the amount of memory that
+    /// the allocator is holding onto.
+    pub fn capacity(&self) -> usize {
+        self.blocks.iter().map(|b| b.layout.size()).sum()
+    }
+
+    /// Returns the offset into the given block that an allocation with the given layout would be
+    /// placed at, if it fits.
+    fn fit(block: &AllocBlock, layout: Layout) -> Option<usize> {
+        let address = block.ptr as usize + block.used;
+        let offset = block.used + (layout.align() - address % layout.align()) % layout.align();
+        if offset
+            .checked_add(layout.size())
+            .is_some_and(|end| end <= block.layout.size())
+        {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    /// Makes sure that the block after the current one can hold the given layout, and moves to it.
+    unsafe fn grow(&mut self, layout: Layout) {
+        let next = if self.blocks.is_empty() {
+            0
+        } else {
+            self.current + 1
+        };
+
+        if let Some(block) = self.blocks.get(next) {
+            if Self::fit(block, layout).is_some() {
+                self.current = next;
+                return;
+            }
+        }
+
+        // None of the unused blocks are large enough, so replace them with one that is
+        for block in self.blocks.drain(next..).rev() {
+            alloc::dealloc(block.ptr, block.layout);
+        }
+        let size = self
+            .blocks
+            .last()
+            .map_or(Self::MIN_BLOCK_SIZE, |b| b.layout.size().saturating_mul(2))
+            .max(layout.size().saturating_add(layout.align()));
+        let align = layout.align().max(Self::MIN_BLOCK_ALIGN);
+        let block_layout =
+            Layout::from_size_align(size, align).expect("scratch block size overflowed");
+        let ptr = alloc::alloc(block_layout);
+        assert!(!ptr.is_null());
+        self.blocks.push(AllocBlock {
+            ptr,
+            layout: block_layout,
+            used: 0,
+        });
+        self.current = next;
+    }
 }
 
 impl Drop for AllocScratch {
     fn drop(&mut self) {
-        for (ptr, layout) in self.allocations.drain(..).rev() {
+        for block in self.blocks.drain(..).rev() {
             unsafe {
-                alloc::dealloc(ptr, layout);
+                alloc::dealloc(block.ptr, block.layout);
             }
         }
     }
@@ -294,9 +395,29 @@ impl ScratchSpace for AllocScratch {
                 });
             }
         }
-        let result_ptr = alloc::alloc(layout);
-        assert!(!result_ptr.is_null());
-        self.allocations.push((result_ptr, layout));
+
+        let fits = self
+            .blocks
+            .get(self.current)
+            .and_then(|block| Self::fit(block, layout));
+        let offset = match fits {
+            Some(offset) => offset,
+            None => {
+                self.grow(layout);
+                Self::fit(&self.blocks[self.current], layout).unwrap()
+            }
+        };
+
+        let block = &mut self.blocks[self.current];
+        let result_ptr = block.ptr.add(offset);
+        self.allocations.push(AllocRecord {
+            ptr: result_ptr,
+            layout,
+            block: self.current,
+            prev_used: block.used,
+        });
+        block.used = offset + layout.size();
+
         let result_slice = ptr_meta::from_raw_parts_mut(result_ptr.cast(), layout.size());
         let result = NonNull::new_unchecked(result_slice);
         Ok(result)
@@ -304,15 +425,16 @@ impl ScratchSpace for AllocScratch {
 
     #[inline]
     unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
-        if let Some(&(last_ptr, last_layout)) = self.allocations.last() {
-            if ptr.as_ptr() == last_ptr && layout == last_layout {
-                alloc::dealloc(ptr.as_ptr(), layout);
+        if let Some(last) = self.allocations.last() {
+            if ptr.as_ptr() == last.ptr && layout == last.layout {
+                self.blocks[last.block].used = last.prev_used;
+                self.current = last.block;
                 self.allocations.pop();
                 Ok(())
             } else {
                 Err(AllocScratchError::NotPoppedInReverseOrder {
-                    expected: last_ptr,
-                    expected_layout: last_layout,
+                    expected: last.ptr,
+                    expected_layout: last.layout,
                     actual: ptr.as_ptr(),
                     actual_layout: layout,
                 })
@@ -328,6 +450,8 @@ impl ScratchSpace for AllocScratch {
 pub enum SharedSerializeMapError {
     /// A shared pointer was added multiple times
     DuplicateSharedPointer(*const u8),
+    /// A shared pointer was serialized again while it was still being serialized
+    CyclicSharedPointer(*const u8),
 }
 
 // SAFETY: SharedSerializeMapError is safe to send to another thread
@@ -342,6 +466,7 @@ impl fmt::Display for SharedSerializeMapError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::DuplicateSharedPointer(p) => write!(f, "duplicate shared pointer: {:p}", p),
+            Self::CyclicSharedPointer(p) => write!(f, "cyclic shared pointer: {:p}", p),
         }
     }
 }
@@ -357,6 +482,7 @@ const _: () = {
 #[derive(Debug)]
 pub struct SharedSerializeMap {
     shared_resolvers: hash_map::HashMap<*const u8, usize>,
+    pending: hash_set::HashSet<*const u8>,
 }
 
 // SAFETY: SharedSerializeMap is safe to send to another thread
@@ -373,6 +499,7 @@ impl SharedSerializeMap {
     pub fn new() -> Self {
         Self {
             shared_resolvers: hash_map::HashMap::new(),
+            pending: hash_set::HashSet::new(),
         }
     }
 }
@@ -400,8 +527,21 @@ impl SharedSerializeRegistry for SharedSerializeMap {
             }
             hash_map::Entry::Vacant(e) => {
                 e.insert(pos);
+                self.pending.remove(&value);
                 Ok(())
             }
         }
     }
+
+    fn start_shared_ptr(&mut self, value: *const u8) -> Result<(), Self::Error> {
+        if self.pending.insert(value) {
+            Ok(())
+        } else {
+            Err(SharedSerializeMapError::CyclicSharedPointer(value))
+        }
+    }
+
+    fn is_shared_ptr_pending(&self, value: *const u8) -> bool {
+        self.pending.contains(&value)
+    }
 }
@@ -1,11 +1,12 @@
 use crate::{
-    ser::{ScratchSpace, Serializer},
-    Fallible,
+    checksum::Crc32,
+    ser::{ScratchSpace, Serializer, SharedSerializeRegistry},
+    Fallible, Infallible,
 };
 use core::{
     alloc::Layout,
     fmt,
-    ops::DerefMut,
+    ops::{ControlFlow, DerefMut},
     ptr::{copy_nonoverlapping, NonNull},
 };
 
@@ -23,6 +24,26 @@ pub enum BufferSerializerError {
     },
 }
 
+impl BufferSerializerError {
+    /// Returns the buffer length that would have been needed for the write to succeed.
+    #[inline]
+    pub fn required_len(&self) -> usize {
+        match self {
+            Self::Overflow {
+                pos, bytes_needed, ..
+            } => pos + bytes_needed,
+        }
+    }
+
+    /// Returns the length of the buffer that overflowed.
+    #[inline]
+    pub fn available_len(&self) -> usize {
+        match self {
+            Self::Overflow { archive_len, .. } => *archive_len,
+        }
+    }
+}
+
 impl fmt::Display for BufferSerializerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -32,8 +53,12 @@ impl fmt::Display for BufferSerializerError {
                 archive_len,
             } => write!(
                 f,
-                "writing has overflowed the serializer buffer: pos {}, needed {}, total length {}",
-                pos, bytes_needed, archive_len
+                "writing has overflowed the serializer buffer: needed a buffer of at least {} bytes \
+                but only {} are available (writing {} bytes at pos {})",
+                pos + bytes_needed,
+                archive_len,
+                bytes_needed,
+                pos
             ),
         }
     }
@@ -109,6 +134,60 @@ impl<T> BufferSerializer<T> {
     }
 }
 
+impl<T: AsMut<[u8]>> BufferSerializer<T> {
+    /// Copies the bytes written so far into a new buffer and returns a serializer that continues
+    /// writing into it from the same position.
+    ///
+    /// This can be used to recover from an overflow by moving to a larger buffer and retrying
+    /// without serializing everything from the beginning again. A failed serialization may leave
+    /// some unused bytes at the end of the written prefix, but any values that were serialized
+    /// before it are still valid.
+    ///
+    /// Returns an error if the new buffer is too short to hold the bytes written so far. The bytes
+    /// stay in this serializer, so it can be moved to a larger buffer instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     archived_value,
+    ///     ser::{Serializer, serializers::BufferSerializer},
+    ///     AlignedBytes,
+    /// };
+    ///
+    /// let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 16]));
+    /// let first = serializer.serialize_value(&[1u32, 2]).unwrap();
+    ///
+    /// let error = serializer.serialize_value(&[3u32; 4]).unwrap_err();
+    /// assert_eq!(error.required_len(), 24);
+    /// assert_eq!(error.available_len(), 16);
+    ///
+    /// let mut serializer = serializer.move_to_buffer(AlignedBytes([0u8; 64])).unwrap();
+    /// let second = serializer.serialize_value(&[3u32; 4]).unwrap();
+    ///
+    /// let buf = serializer.into_inner();
+    /// unsafe {
+    ///     assert_eq!(archived_value::<[u32; 2]>(buf.as_ref(), first), &[1, 2]);
+    ///     assert_eq!(archived_value::<[u32; 4]>(buf.as_ref(), second), &[3; 4]);
+    /// }
+    /// ```
+    pub fn move_to_buffer<U: AsMut<[u8]>>(
+        &mut self,
+        mut buffer: U,
+    ) -> Result<BufferSerializer<U>, BufferSerializerError> {
+        let archive_len = buffer.as_mut().len();
+        if self.pos > archive_len {
+            return Err(BufferSerializerError::Overflow {
+                pos: 0,
+                bytes_needed: self.pos,
+                archive_len,
+            });
+        }
+
+        buffer.as_mut()[..self.pos].copy_from_slice(&self.inner.as_mut()[..self.pos]);
+        Ok(BufferSerializer::with_pos(buffer, self.pos))
+    }
+}
+
 impl<T: Default> Default for BufferSerializer<T> {
     #[inline]
     fn default() -> Self {
@@ -149,6 +228,61 @@ impl<T: AsMut<[u8]>> Serializer for BufferSerializer<T> {
     }
 }
 
+/// A serializer that only counts the bytes written to it.
+///
+/// This can be used to find the size of a value's archive without writing it anywhere, for example
+/// to allocate a buffer of exactly the right size or to enforce a size limit before serializing.
+///
+/// # Examples
+/// ```
+/// use rkyv::ser::{serializers::CountingSerializer, Serializer};
+///
+/// let mut serializer = CountingSerializer::new();
+/// serializer.serialize_value(&[1u32, 2, 3, 4]).unwrap();
+/// assert_eq!(serializer.pos(), 16);
+/// ```
+#[derive(Debug, Default)]
+pub struct CountingSerializer {
+    pos: usize,
+}
+
+impl CountingSerializer {
+    /// Creates a new counting serializer.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_pos(0)
+    }
+
+    /// Creates a new counting serializer that starts counting from the given position.
+    #[inline]
+    pub fn with_pos(pos: usize) -> Self {
+        Self { pos }
+    }
+}
+
+impl Fallible for CountingSerializer {
+    type Error = Infallible;
+}
+
+impl Serializer for CountingSerializer {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.pos += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+        self.pos += padding;
+        Ok(())
+    }
+}
+
 /// Errors that can occur when using a fixed-size allocator.
 ///
 /// Pairing a fixed-size allocator with a fallback allocator can help prevent running out of scratch
@@ -413,3 +547,481 @@ impl<T> From<T> for ScratchTracker<T> {
         Self::new(inner)
     }
 }
+
+/// A passthrough serializer that appends a checksum of the archive when it is finished.
+///
+/// The checksum covers every byte written through the adapter, so the inner serializer should start
+/// at the beginning of the archive. Call [`finish`](ChecksumSerializer::finish) after serializing
+/// the root to append the checksum, and use [`verify_checksum`](crate::checksum::verify_checksum) to
+/// check and strip it before accessing the archive.
+///
+/// See the [`checksum`](crate::checksum) module for an example.
+#[derive(Debug)]
+pub struct ChecksumSerializer<S> {
+    inner: S,
+    hasher: Crc32,
+}
+
+impl<S> ChecksumSerializer<S> {
+    /// Creates a new checksum serializer from the given inner serializer.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            hasher: Crc32::new(),
+        }
+    }
+
+    /// Returns the checksum of the bytes written so far.
+    #[inline]
+    pub fn checksum(&self) -> u32 {
+        self.hasher.finish()
+    }
+
+    /// Consumes the serializer and returns the inner serializer without appending the checksum.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Serializer> ChecksumSerializer<S> {
+    /// Appends the checksum to the archive and returns the inner serializer.
+    #[inline]
+    pub fn finish(mut self) -> Result<S, S::Error> {
+        self.inner.write(&self.hasher.finish().to_le_bytes())?;
+        Ok(self.inner)
+    }
+}
+
+impl<S: Default> Default for ChecksumSerializer<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: Fallible> Fallible for ChecksumSerializer<S> {
+    type Error = S::Error;
+}
+
+impl<S: Serializer> Serializer for ChecksumSerializer<S> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(bytes)?;
+        self.hasher.update(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.inner.write_vectored(bufs)?;
+        for bytes in bufs {
+            self.hasher.update(bytes);
+        }
+        Ok(())
+    }
+}
+
+impl<S: ScratchSpace> ScratchSpace for ChecksumSerializer<S> {
+    #[inline]
+    unsafe fn push_scratch(&mut self, layout: Layout) -> Result<NonNull<[u8]>, Self::Error> {
+        self.inner.push_scratch(layout)
+    }
+
+    #[inline]
+    unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
+        self.inner.pop_scratch(ptr, layout)
+    }
+}
+
+impl<S: SharedSerializeRegistry> SharedSerializeRegistry for ChecksumSerializer<S> {
+    #[inline]
+    fn get_shared_ptr(&self, value: *const u8) -> Option<usize> {
+        self.inner.get_shared_ptr(value)
+    }
+
+    #[inline]
+    fn add_shared_ptr(&mut self, value: *const u8, pos: usize) -> Result<(), Self::Error> {
+        self.inner.add_shared_ptr(value, pos)
+    }
+
+    #[inline]
+    fn start_shared_ptr(&mut self, value: *const u8) -> Result<(), Self::Error> {
+        self.inner.start_shared_ptr(value)
+    }
+
+    #[inline]
+    fn is_shared_ptr_pending(&self, value: *const u8) -> bool {
+        self.inner.is_shared_ptr_pending(value)
+    }
+}
+
+/// Statistics collected by a [`StatsSerializer`].
+///
+/// The number of out-of-line objects isn't included. Types write their out-of-line data with
+/// ordinary calls to [`Serializer`] methods, so a serializer can't tell where one object ends and
+/// the next begins: a slice is written one element at a time, and a short string isn't written
+/// out of line at all. Use the number of bytes written to size buffers instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SerializeStats {
+    /// The total number of bytes written to the archive, including padding
+    pub bytes_written: usize,
+    /// The number of bytes written as padding
+    pub padding_bytes: usize,
+    /// The total number of scratch space allocations
+    pub scratch_allocations: usize,
+    /// The maximum number of bytes of scratch space that were allocated at the same time
+    pub peak_scratch_bytes: usize,
+    /// The maximum number of scratch space allocations that were live at the same time
+    pub peak_scratch_allocations: usize,
+    /// The number of shared values that were serialized
+    pub shared_values: usize,
+}
+
+/// A passthrough serializer that collects statistics about serialization.
+///
+/// The statistics can be retrieved with [`stats`](StatsSerializer::stats) after serialization
+/// completes. They can be used to size the buffers and scratch space of other serializers, like
+/// [`BufferSerializer`] and [`BufferScratch`].
+///
+/// # Examples
+/// ```
+/// use rkyv::ser::{
+///     serializers::{AllocSerializer, StatsSerializer},
+///     Serializer,
+/// };
+///
+/// let mut serializer = StatsSerializer::new(AllocSerializer::<256>::default());
+/// serializer
+///     .serialize_value(&vec![vec![1u32, 2], vec![3, 4]])
+///     .unwrap();
+/// let stats = serializer.stats();
+/// assert_eq!(stats.bytes_written, serializer.pos());
+/// assert_eq!(stats.scratch_allocations, 1);
+/// assert_eq!(stats.peak_scratch_allocations, 1);
+/// ```
+#[derive(Debug)]
+pub struct StatsSerializer<S> {
+    inner: S,
+    stats: SerializeStats,
+    scratch_bytes: usize,
+    scratch_allocations: usize,
+}
+
+impl<S> StatsSerializer<S> {
+    /// Creates a new stats serializer from the given inner serializer.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            stats: SerializeStats::default(),
+            scratch_bytes: 0,
+            scratch_allocations: 0,
+        }
+    }
+
+    /// Returns the statistics collected so far.
+    #[inline]
+    pub fn stats(&self) -> SerializeStats {
+        self.stats
+    }
+
+    /// Consumes the serializer and returns the inner serializer.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Consumes the serializer and returns the inner serializer and the collected statistics.
+    #[inline]
+    pub fn into_parts(self) -> (S, SerializeStats) {
+        (self.inner, self.stats)
+    }
+}
+
+impl<S: Default> Default for StatsSerializer<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: Fallible> Fallible for StatsSerializer<S> {
+    type Error = S::Error;
+}
+
+impl<S: Serializer> Serializer for StatsSerializer<S> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(bytes)?;
+        self.stats.bytes_written += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.inner.write_vectored(bufs)?;
+        self.stats.bytes_written += bufs.iter().map(|bytes| bytes.len()).sum::<usize>();
+        Ok(())
+    }
+
+    #[inline]
+    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+        self.inner.pad(padding)?;
+        self.stats.bytes_written += padding;
+        self.stats.padding_bytes += padding;
+        Ok(())
+    }
+}
+
+impl<S: ScratchSpace> ScratchSpace for StatsSerializer<S> {
+    #[inline]
+    unsafe fn push_scratch(&mut self, layout: Layout) -> Result<NonNull<[u8]>, Self::Error> {
+        let result = self.inner.push_scratch(layout)?;
+
+        self.scratch_bytes += layout.size();
+        self.scratch_allocations += 1;
+        self.stats.scratch_allocations += 1;
+        self.stats.peak_scratch_bytes =
+            usize::max(self.stats.peak_scratch_bytes, self.scratch_bytes);
+        self.stats.peak_scratch_allocations = usize::max(
+            self.stats.peak_scratch_allocations,
+            self.scratch_allocations,
+        );
+
+        Ok(result)
+    }
+
+    #[inline]
+    unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
+        self.inner.pop_scratch(ptr, layout)?;
+
+        self.scratch_bytes -= layout.size();
+        self.scratch_allocations -= 1;
+
+        Ok(())
+    }
+}
+
+impl<S: SharedSerializeRegistry> SharedSerializeRegistry for StatsSerializer<S> {
+    #[inline]
+    fn get_shared_ptr(&self, value: *const u8) -> Option<usize> {
+        self.inner.get_shared_ptr(value)
+    }
+
+    #[inline]
+    fn add_shared_ptr(&mut self, value: *const u8, pos: usize) -> Result<(), Self::Error> {
+        self.inner.add_shared_ptr(value, pos)?;
+        self.stats.shared_values += 1;
+        Ok(())
+    }
+
+    #[inline]
+    fn start_shared_ptr(&mut self, value: *const u8) -> Result<(), Self::Error> {
+        self.inner.start_shared_ptr(value)
+    }
+
+    #[inline]
+    fn is_shared_ptr_pending(&self, value: *const u8) -> bool {
+        self.inner.is_shared_ptr_pending(value)
+    }
+}
+
+/// The error type returned by a [`ProgressSerializer`].
+#[derive(Debug)]
+pub enum ProgressError<E> {
+    /// Serialization was cancelled by the progress callback.
+    Cancelled {
+        /// The position of the serializer when it was cancelled
+        pos: usize,
+    },
+    /// An error occurred in the inner serializer.
+    Inner(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ProgressError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cancelled { pos } => write!(f, "serialization was cancelled at position {}", pos),
+            Self::Inner(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ProgressError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Cancelled { .. } => None,
+            Self::Inner(e) => Some(e as &dyn std::error::Error),
+        }
+    }
+}
+
+/// A passthrough serializer that reports its progress and can be cancelled.
+///
+/// The callback is called with the position of the serializer whenever at least `interval` bytes
+/// have been written since it was last called. Returning [`ControlFlow::Break`] from the callback
+/// cancels serialization, and the write that called it returns [`ProgressError::Cancelled`]. An
+/// interval of zero calls the callback after every write.
+///
+/// Since the serializer only wraps writes, scratch space, and shared pointers, it works with every
+/// type that can be serialized, including derived types. This makes it possible to show progress
+/// while serializing very large archives and to abort serializing them early.
+///
+/// # Examples
+/// ```
+/// use core::ops::ControlFlow;
+/// use rkyv::ser::{
+///     serializers::{AllocSerializer, ProgressError, ProgressSerializer},
+///     Serializer,
+/// };
+///
+/// let value = vec![0u64; 1024];
+///
+/// let mut reports = Vec::new();
+/// let mut serializer = ProgressSerializer::new(AllocSerializer::<256>::default(), 1024, |pos| {
+///     reports.push(pos);
+///     ControlFlow::Continue(())
+/// });
+/// serializer.serialize_value(&value).unwrap();
+/// drop(serializer);
+/// assert_eq!(reports, [1024, 2048, 3072, 4096, 5120, 6144, 7168, 8192]);
+///
+/// let mut serializer = ProgressSerializer::new(AllocSerializer::<256>::default(), 1024, |pos| {
+///     if pos > 4096 {
+///         ControlFlow::Break(())
+///     } else {
+///         ControlFlow::Continue(())
+///     }
+/// });
+/// let values = vec![vec![0u64; 256]; 4];
+/// assert!(matches!(
+///     serializer.serialize_value(&values),
+///     Err(ProgressError::Cancelled { .. }),
+/// ));
+/// ```
+pub struct ProgressSerializer<S, F> {
+    inner: S,
+    callback: F,
+    interval: usize,
+    next: usize,
+}
+
+impl<S: Serializer, F: FnMut(usize) -> ControlFlow<()>> ProgressSerializer<S, F> {
+    /// Creates a new progress serializer from the given inner serializer, reporting interval, and
+    /// callback.
+    #[inline]
+    pub fn new(inner: S, interval: usize, callback: F) -> Self {
+        Self {
+            next: inner.pos() + interval,
+            inner,
+            callback,
+            interval,
+        }
+    }
+
+    #[inline]
+    fn report(&mut self) -> Result<(), ProgressError<S::Error>> {
+        let pos = self.inner.pos();
+        if pos >= self.next {
+            self.next = pos + self.interval;
+            if let ControlFlow::Break(()) = (self.callback)(pos) {
+                return Err(ProgressError::Cancelled { pos });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S, F> ProgressSerializer<S, F> {
+    /// Consumes the serializer and returns the inner serializer.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Fallible, F> Fallible for ProgressSerializer<S, F> {
+    type Error = ProgressError<S::Error>;
+}
+
+impl<S: Serializer, F: FnMut(usize) -> ControlFlow<()>> Serializer for ProgressSerializer<S, F> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(bytes).map_err(ProgressError::Inner)?;
+        self.report()
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.inner
+            .write_vectored(bufs)
+            .map_err(ProgressError::Inner)?;
+        self.report()
+    }
+
+    #[inline]
+    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+        self.inner.pad(padding).map_err(ProgressError::Inner)?;
+        self.report()
+    }
+}
+
+impl<S: ScratchSpace, F> ScratchSpace for ProgressSerializer<S, F> {
+    #[inline]
+    unsafe fn push_scratch(&mut self, layout: Layout) -> Result<NonNull<[u8]>, Self::Error> {
+        self.inner
+            .push_scratch(layout)
+            .map_err(ProgressError::Inner)
+    }
+
+    #[inline]
+    unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
+        self.inner
+            .pop_scratch(ptr, layout)
+            .map_err(ProgressError::Inner)
+    }
+}
+
+impl<S: SharedSerializeRegistry, F> SharedSerializeRegistry for ProgressSerializer<S, F> {
+    #[inline]
+    fn get_shared_ptr(&self, value: *const u8) -> Option<usize> {
+        self.inner.get_shared_ptr(value)
+    }
+
+    #[inline]
+    fn add_shared_ptr(&mut self, value: *const u8, pos: usize) -> Result<(), Self::Error> {
+        self.inner
+            .add_shared_ptr(value, pos)
+            .map_err(ProgressError::Inner)
+    }
+
+    #[inline]
+    fn start_shared_ptr(&mut self, value: *const u8) -> Result<(), Self::Error> {
+        self.inner
+            .start_shared_ptr(value)
+            .map_err(ProgressError::Inner)
+    }
+
+    #[inline]
+    fn is_shared_ptr_pending(&self, value: *const u8) -> bool {
+        self.inner.is_shared_ptr_pending(value)
+    }
+}
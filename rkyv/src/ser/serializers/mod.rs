@@ -5,10 +5,13 @@ mod alloc;
 mod core;
 #[cfg(feature = "std")]
 mod std;
+#[cfg(feature = "tokio")]
+mod tokio;
 
 #[cfg(feature = "alloc")]
 use crate::AlignedVec;
 use crate::{
+    rel_ptr::OffsetError,
     ser::{ScratchSpace, Serializer, SharedSerializeRegistry},
     AlignedBytes, Archive, ArchiveUnsized, Fallible, Infallible,
 };
@@ -22,6 +25,9 @@ pub use self::core::*;
 #[doc(inline)]
 #[cfg(feature = "std")]
 pub use self::std::*;
+#[doc(inline)]
+#[cfg(feature = "tokio")]
+pub use self::tokio::*;
 
 /// The default serializer error.
 #[derive(Debug)]
@@ -32,6 +38,15 @@ pub enum CompositeSerializerError<S, C, H> {
     ScratchSpaceError(C),
     /// An error occurred while serializing shared memory
     SharedError(H),
+    /// A relative pointer could not reach its target
+    OffsetError(OffsetError),
+}
+
+impl<S, C, H> From<OffsetError> for CompositeSerializerError<S, C, H> {
+    #[inline]
+    fn from(e: OffsetError) -> Self {
+        Self::OffsetError(e)
+    }
 }
 
 impl<S, C, H> fmt::Display for CompositeSerializerError<S, C, H>
@@ -45,6 +60,7 @@ where
             Self::SerializerError(e) => write!(f, "serialization error: {}", e),
             Self::ScratchSpaceError(e) => write!(f, "scratch space error: {}", e),
             Self::SharedError(e) => write!(f, "shared memory error: {}", e),
+            Self::OffsetError(e) => write!(f, "offset error: {}", e),
         }
     }
 }
@@ -64,6 +80,7 @@ const _: () = {
                 Self::SerializerError(e) => Some(e as &dyn Error),
                 Self::ScratchSpaceError(e) => Some(e as &dyn Error),
                 Self::SharedError(e) => Some(e as &dyn Error),
+                Self::OffsetError(e) => Some(e as &dyn Error),
             }
         }
     }
@@ -131,6 +148,13 @@ impl<S: Serializer, C: Fallible, H: Fallible> Serializer for CompositeSerializer
             .map_err(CompositeSerializerError::SerializerError)
     }
 
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.serializer
+            .write_vectored(bufs)
+            .map_err(CompositeSerializerError::SerializerError)
+    }
+
     #[inline]
     fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
         self.serializer
@@ -206,6 +230,18 @@ impl<S: Fallible, C: Fallible, H: SharedSerializeRegistry> SharedSerializeRegist
             .add_shared_ptr(value, pos)
             .map_err(CompositeSerializerError::SharedError)
     }
+
+    #[inline]
+    fn start_shared_ptr(&mut self, value: *const u8) -> Result<(), Self::Error> {
+        self.shared
+            .start_shared_ptr(value)
+            .map_err(CompositeSerializerError::SharedError)
+    }
+
+    #[inline]
+    fn is_shared_ptr_pending(&self, value: *const u8) -> bool {
+        self.shared.is_shared_ptr_pending(value)
+    }
 }
 
 /// A serializer suitable for environments where allocations cannot be made.
@@ -232,3 +268,36 @@ pub type AllocSerializer<const N: usize> = CompositeSerializer<
     FallbackScratch<HeapScratch<N>, AllocScratch>,
     SharedSerializeMap,
 >;
+
+/// A general-purpose serializer that only counts the bytes it would write.
+///
+/// This is the serializer used by [`serialized_size`](crate::serialized_size). It is the same as
+/// [`AllocSerializer`] except that it writes to a [`CountingSerializer`].
+#[cfg(feature = "alloc")]
+pub type SizeSerializer<const N: usize> = CompositeSerializer<
+    CountingSerializer,
+    FallbackScratch<HeapScratch<N>, AllocScratch>,
+    SharedSerializeMap,
+>;
+
+/// A general-purpose serializer that appends to an existing [`AlignedVec`].
+///
+/// This is the serializer used by [`append_to_bytes`](crate::append_to_bytes). It is the same as
+/// [`AllocSerializer`] except that it borrows the buffer it writes to.
+#[cfg(feature = "alloc")]
+pub type AppendSerializer<'a, const N: usize> = CompositeSerializer<
+    AlignedSerializer<&'a mut AlignedVec>,
+    FallbackScratch<HeapScratch<N>, AllocScratch>,
+    SharedSerializeMap,
+>;
+
+/// A general-purpose serializer that buffers the archive and writes it to an async writer.
+///
+/// This is the serializer used by [`to_async_writer`](crate::to_async_writer). It is the same as
+/// [`AllocSerializer`] except that it writes to an [`AsyncWriteSerializer`].
+#[cfg(feature = "tokio")]
+pub type AsyncSerializer<W, const N: usize> = CompositeSerializer<
+    AsyncWriteSerializer<W>,
+    FallbackScratch<HeapScratch<N>, AllocScratch>,
+    SharedSerializeMap,
+>;
@@ -1,5 +1,48 @@
 use crate::{ser::Serializer, Fallible};
-use std::io;
+use core::{mem::ManuallyDrop, ptr};
+use std::io::{self, IoSlice};
+
+const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+
+/// Writes all of the given slices to the writer, calling `write_vectored` until they are written.
+fn write_all_vectored<W: io::Write>(writer: &mut W, mut bufs: &[&[u8]]) -> io::Result<()> {
+    // Skip empty slices so that a write of zero bytes always means the writer failed
+    let mut offset = 0;
+    while let Some((first, rest)) = bufs.split_first() {
+        if offset == first.len() {
+            bufs = rest;
+            offset = 0;
+            continue;
+        }
+
+        let mut slices = [IoSlice::new(&[]); 16];
+        let count = usize::min(bufs.len(), slices.len());
+        slices[0] = IoSlice::new(&first[offset..]);
+        for (slice, bytes) in slices[1..count].iter_mut().zip(rest) {
+            *slice = IoSlice::new(bytes);
+        }
+
+        let mut written = match writer.write_vectored(&slices[..count]) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(written) => written,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        // Advance past the fully written slices and into the partially written one
+        while let Some((first, rest)) = bufs.split_first() {
+            let remaining = first.len() - offset;
+            if written < remaining {
+                offset += written;
+                break;
+            }
+            written -= remaining;
+            bufs = rest;
+            offset = 0;
+        }
+    }
+    Ok(())
+}
 
 /// Wraps a type that implements [`io::Write`](std::io::Write) and equips it with [`Serializer`].
 ///
@@ -30,6 +73,9 @@ impl<W: io::Write> WriteSerializer<W> {
 
     /// Creates a new serializer from a writer, and assumes that the underlying writer is currently
     /// at the given position.
+    ///
+    /// This can be used to append values to an existing archive, such as a file opened in append
+    /// mode, by passing the current length of the archive as the position.
     #[inline]
     pub fn with_pos(inner: W, pos: usize) -> Self {
         Self { inner, pos }
@@ -58,4 +104,183 @@ impl<W: io::Write> Serializer for WriteSerializer<W> {
         self.pos += bytes.len();
         Ok(())
     }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        write_all_vectored(&mut self.inner, bufs)?;
+        self.pos += bufs.iter().map(|bytes| bytes.len()).sum::<usize>();
+        Ok(())
+    }
+}
+
+/// Wraps a type that implements [`io::Write`](std::io::Write) and equips it with a buffered
+/// [`Serializer`].
+///
+/// Archives are built from many small writes for individual fields and padding. A
+/// `WriteSerializer` passes each of these straight to its writer, which is slow when writing to a
+/// file or socket. `BufferedWriteSerializer` collects them into a buffer and only writes to the
+/// writer when the buffer is full, combining the buffer and any write larger than it into a single
+/// vectored write.
+///
+/// Buffered bytes are written when the serializer is [flushed](BufferedWriteSerializer::flush) or
+/// [consumed](BufferedWriteSerializer::into_inner). Any bytes that are still buffered when the
+/// serializer is dropped are written then, but errors from that write are ignored. Call
+/// [`into_inner`](BufferedWriteSerializer::into_inner) to handle them instead.
+///
+/// # Examples
+/// ```
+/// use rkyv::ser::{serializers::BufferedWriteSerializer, Serializer};
+///
+/// let mut serializer = BufferedWriteSerializer::new(Vec::new());
+/// serializer.serialize_value(&"hello world".to_string()).unwrap();
+/// assert_ne!(serializer.buffered(), 0);
+///
+/// let buf = serializer.into_inner().unwrap();
+/// assert_eq!(&buf[..11], b"hello world");
+/// ```
+#[derive(Debug)]
+pub struct BufferedWriteSerializer<W: io::Write> {
+    inner: W,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl<W: io::Write> BufferedWriteSerializer<W> {
+    /// Creates a new buffered serializer from a writer.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(DEFAULT_BUFFER_CAPACITY, inner)
+    }
+
+    /// Creates a new buffered serializer from a writer with a buffer of the given capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    #[inline]
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        assert!(capacity > 0, "buffer capacity must be nonzero");
+        Self {
+            inner,
+            buffer: Vec::with_capacity(capacity),
+            pos: 0,
+        }
+    }
+
+    /// Creates a new buffered serializer from a writer, and assumes that the underlying writer is
+    /// currently at the given position.
+    ///
+    /// See [`WriteSerializer::with_pos`] for more details.
+    #[inline]
+    pub fn with_pos(inner: W, pos: usize) -> Self {
+        let mut result = Self::new(inner);
+        result.pos = pos;
+        result
+    }
+
+    /// Returns the number of bytes that have been serialized but not yet written to the writer.
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Writes all of the buffered bytes to the writer and flushes it.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.flush_buffer()?;
+        self.inner.flush()
+    }
+
+    /// Writes all of the buffered bytes to the writer and returns it.
+    ///
+    /// If writing fails, the buffered bytes are discarded and the error is returned.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        let result = self.flush_buffer();
+        self.buffer.clear();
+        result?;
+
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so the buffer is dropped and the writer is moved out
+        // exactly once.
+        unsafe {
+            ptr::drop_in_place(&mut this.buffer);
+            Ok(ptr::read(&this.inner))
+        }
+    }
+
+    fn flush_buffer(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.inner.write_all(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.buffer.capacity() - self.buffer.len()
+    }
+}
+
+impl<W: io::Write> Drop for BufferedWriteSerializer<W> {
+    fn drop(&mut self) {
+        // There's no way to report an error from here
+        let _ = self.flush_buffer();
+    }
+}
+
+impl<W: io::Write> Fallible for BufferedWriteSerializer<W> {
+    type Error = io::Error;
+}
+
+impl<W: io::Write> Serializer for BufferedWriteSerializer<W> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        if bytes.len() <= self.remaining() {
+            self.buffer.extend_from_slice(bytes);
+        } else if bytes.len() < self.buffer.capacity() {
+            self.flush_buffer()?;
+            self.buffer.extend_from_slice(bytes);
+        } else {
+            write_all_vectored(&mut self.inner, &[&self.buffer, bytes])?;
+            self.buffer.clear();
+        }
+        self.pos += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        let len = bufs.iter().map(|bytes| bytes.len()).sum::<usize>();
+        if len <= self.remaining() {
+            for bytes in bufs {
+                self.buffer.extend_from_slice(bytes);
+            }
+        } else {
+            self.flush_buffer()?;
+            write_all_vectored(&mut self.inner, bufs)?;
+        }
+        self.pos += len;
+        Ok(())
+    }
+
+    #[inline]
+    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+        let mut remaining = padding;
+        loop {
+            let len = usize::min(remaining, self.remaining());
+            self.buffer.resize(self.buffer.len() + len, 0);
+            remaining -= len;
+            if remaining == 0 {
+                break;
+            }
+            self.flush_buffer()?;
+        }
+        self.pos += padding;
+        Ok(())
+    }
 }
@@ -0,0 +1,163 @@
+use crate::{ser::Serializer, AlignedVec, Fallible};
+use std::{collections::VecDeque, io};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Wraps a type that implements [`AsyncWrite`] and equips it with [`Serializer`].
+///
+/// Serialization can't wait on the writer, so bytes are written into a queue of aligned chunks
+/// until [`flush`](AsyncWriteSerializer::flush) is awaited to write them out. Flushing between
+/// values keeps the amount of buffered data small when streaming many values.
+///
+/// # Examples
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use rkyv::ser::{serializers::AsyncWriteSerializer, Serializer};
+///
+/// let mut serializer = AsyncWriteSerializer::new(Vec::new());
+/// serializer.write(&[0u8, 1u8, 2u8, 3u8]).unwrap();
+/// assert_eq!(serializer.pending(), 4);
+/// serializer.flush().await.unwrap();
+/// assert_eq!(serializer.pending(), 0);
+/// assert_eq!(serializer.into_inner(), vec![0u8, 1u8, 2u8, 3u8]);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncWriteSerializer<W> {
+    inner: W,
+    chunks: VecDeque<AlignedVec>,
+    chunk_size: usize,
+    pos: usize,
+}
+
+impl<W> AsyncWriteSerializer<W> {
+    /// Creates a new serializer from an async writer.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self::with_chunk_size(inner, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a new serializer from an async writer that buffers bytes in chunks of the given
+    /// size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[inline]
+    pub fn with_chunk_size(inner: W, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be nonzero");
+        Self {
+            inner,
+            chunks: VecDeque::new(),
+            chunk_size,
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes that have been serialized but not yet flushed.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    /// Consumes the serializer and returns the internal writer used to create it.
+    ///
+    /// Any bytes that have not been flushed are discarded.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWriteSerializer<W> {
+    /// Writes all of the buffered bytes to the writer and flushes it.
+    ///
+    /// If writing fails, the chunk that was being written and any chunks after it are kept, but
+    /// part of that chunk may have already been written.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        while let Some(chunk) = self.chunks.front() {
+            self.inner.write_all(chunk).await?;
+            self.chunks.pop_front();
+        }
+        self.inner.flush().await
+    }
+}
+
+impl<W> Fallible for AsyncWriteSerializer<W> {
+    type Error = io::Error;
+}
+
+impl<W> Serializer for AsyncWriteSerializer<W> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    fn write(&mut self, mut bytes: &[u8]) -> Result<(), Self::Error> {
+        self.pos += bytes.len();
+        while !bytes.is_empty() {
+            let chunk = match self.chunks.back_mut() {
+                Some(chunk) if chunk.len() < self.chunk_size => chunk,
+                _ => {
+                    self.chunks
+                        .push_back(AlignedVec::with_capacity(self.chunk_size));
+                    self.chunks.back_mut().unwrap()
+                }
+            };
+            let len = usize::min(self.chunk_size - chunk.len(), bytes.len());
+            chunk.extend_from_slice(&bytes[..len]);
+            bytes = &bytes[len..];
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsyncWriteSerializer;
+    use crate::{archived_root, archived_value, ser::Serializer, to_async_writer};
+
+    #[tokio::test]
+    async fn stream_values() {
+        let mut serializer = AsyncWriteSerializer::with_chunk_size(Vec::new(), 8);
+        let first = serializer
+            .serialize_value(&"first value".to_string())
+            .unwrap();
+        assert!(serializer.chunks.iter().all(|chunk| chunk.len() <= 8));
+        serializer.flush().await.unwrap();
+        assert_eq!(serializer.pending(), 0);
+        let second = serializer.serialize_value(&[1u32, 2, 3]).unwrap();
+        let end = serializer.pos();
+        serializer.flush().await.unwrap();
+
+        let bytes = serializer.into_inner();
+        assert_eq!(bytes.len(), end);
+        let mut aligned = crate::AlignedVec::new();
+        aligned.extend_from_slice(&bytes);
+        unsafe {
+            assert_eq!(archived_value::<String>(&aligned, first), "first value");
+            assert_eq!(archived_value::<[u32; 3]>(&aligned, second), &[1, 2, 3]);
+        }
+    }
+
+    #[tokio::test]
+    async fn to_async_writer_matches_to_bytes() {
+        fn assert_send<F: Send>(future: F) -> F {
+            future
+        }
+
+        let value = vec!["a".to_string(), "b".to_string()];
+        let mut bytes = Vec::new();
+        assert_send(to_async_writer::<_, _, 256>(&value, &mut bytes))
+            .await
+            .unwrap();
+        let expected = crate::to_bytes::<_, 256>(&value).unwrap();
+        assert_eq!(bytes, expected.as_slice());
+
+        let mut aligned = crate::AlignedVec::new();
+        aligned.extend_from_slice(&bytes);
+        let archived = unsafe { archived_root::<Vec<String>>(&aligned) };
+        assert_eq!(archived[1], "b");
+    }
+}
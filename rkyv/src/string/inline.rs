@@ -0,0 +1,317 @@
+//! An archived string with a configurable inline capacity.
+//!
+//! [`ArchivedString`](crate::string::ArchivedString) inlines strings that fit in the space of its
+//! relative pointer, which is only a few bytes. [`ArchivedInlineString`] inlines strings of up to
+//! `N` bytes instead, at the cost of taking up `N` bytes plus a length. Data with lots of short
+//! strings like identifiers and codes can pick an `N` that fits most of them, so that reading them
+//! doesn't need to follow a pointer out of the archived struct.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, with::AsInlineString, Archive, Deserialize, Infallible, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Order {
+//!     #[with(AsInlineString<16>)]
+//!     id: String,
+//!     #[with(AsInlineString<16>)]
+//!     note: String,
+//! }
+//!
+//! let value = Order {
+//!     id: "ORD-2024-000123".to_string(),
+//!     note: "this note is too long to be inlined".to_string(),
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//! let archived = unsafe { archived_root::<Order>(&bytes) };
+//!
+//! assert!(archived.id.is_inline());
+//! assert_eq!(archived.id, "ORD-2024-000123");
+//! assert!(!archived.note.is_inline());
+//! assert_eq!(archived.note, "this note is too long to be inlined");
+//!
+//! let deserialized: Order = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized.note, value.note);
+//! ```
+
+use crate::{
+    rel_ptr::{signed_offset, OffsetError},
+    ser::Serializer,
+    Archived, FixedIsize, FixedUsize, SerializeUnsized,
+};
+use core::{
+    borrow::Borrow, cmp, convert::TryFrom, fmt, hash, marker::PhantomPinned, mem::size_of,
+    ops::Deref, ptr, slice, str,
+};
+
+const OFFSET_BYTES: usize = size_of::<FixedIsize>();
+
+#[inline]
+fn offset_between(from: usize, to: usize) -> Result<FixedIsize, OffsetError> {
+    FixedIsize::try_from(signed_offset(from, to)?).map_err(|_| OffsetError::ExceedsStorageRange)
+}
+
+/// An archived string that stores strings of up to `N` bytes inline.
+///
+/// Longer strings store a relative pointer to their bytes in the same space instead, so `N` must
+/// be at least the size of a relative offset (4 bytes by default).
+#[repr(C)]
+pub struct ArchivedInlineString<const N: usize> {
+    len: Archived<usize>,
+    // The bytes of inline strings, or the offset to the bytes of out-of-line strings. Offsets are
+    // always stored in little-endian format, the same as for `ArchivedString`.
+    bytes: [u8; N],
+    _phantom: PhantomPinned,
+}
+
+impl<const N: usize> ArchivedInlineString<N> {
+    const CAPACITY_CHECK: () = assert!(
+        N >= OFFSET_BYTES,
+        "the inline capacity must be large enough to hold a relative offset",
+    );
+
+    /// Returns whether the string is stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.len() <= N
+    }
+
+    /// Returns the length of the string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    fn out_of_line_offset(&self) -> isize {
+        let mut offset = [0; OFFSET_BYTES];
+        offset.copy_from_slice(&self.bytes[..OFFSET_BYTES]);
+        FixedIsize::from_le_bytes(offset) as isize
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const u8 {
+        if self.is_inline() {
+            self.bytes.as_ptr()
+        } else {
+            unsafe {
+                (self as *const Self)
+                    .cast::<u8>()
+                    .offset(self.out_of_line_offset())
+            }
+        }
+    }
+
+    /// Extracts a string slice containing the entire string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(slice::from_raw_parts(self.as_ptr(), self.len())) }
+    }
+
+    /// Resolves an archived inline string from a given `str`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes of an out-of-line string are too far from `pos` to be reached with a
+    /// relative offset.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `value`
+    #[inline]
+    pub unsafe fn resolve_from_str(
+        value: &str,
+        pos: usize,
+        resolver: InlineStringResolver,
+        out: *mut Self,
+    ) {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::CAPACITY_CHECK;
+
+        let (_, fo) = out_field!(out.len);
+        fo.write(to_archived!(value.len() as FixedUsize));
+
+        let (_, fo) = out_field!(out.bytes);
+        let out_bytes = fo.cast::<u8>();
+        ptr::write_bytes(out_bytes, 0, N);
+        if value.len() <= N {
+            ptr::copy_nonoverlapping(value.as_ptr(), out_bytes, value.len());
+        } else {
+            let offset = offset_between(pos, resolver.pos).unwrap().to_le_bytes();
+            ptr::copy_nonoverlapping(offset.as_ptr(), out_bytes, OFFSET_BYTES);
+        }
+    }
+
+    /// Serializes an archived inline string from a given `str`.
+    ///
+    /// The archived string is always resolved after the bytes of an out-of-line string, so this
+    /// returns an [`OffsetError`] if the bytes are already too far away to be reached with a
+    /// relative offset.
+    #[inline]
+    pub fn serialize_from_str<S: Serializer + ?Sized>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<InlineStringResolver, S::Error>
+    where
+        str: SerializeUnsized<S>,
+        S::Error: From<OffsetError>,
+    {
+        if value.len() <= N {
+            Ok(InlineStringResolver { pos: 0 })
+        } else {
+            let pos = value.serialize_unsized(serializer)?;
+            offset_between(serializer.pos(), pos)?;
+            Ok(InlineStringResolver { pos })
+        }
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArchivedInlineString<N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Borrow<str> for ArchivedInlineString<N> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArchivedInlineString<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Deref for ArchivedInlineString<N> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Display for ArchivedInlineString<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Eq for ArchivedInlineString<N> {}
+
+impl<const N: usize> hash::Hash for ArchivedInlineString<N> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<const N: usize> Ord for ArchivedInlineString<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const N: usize> PartialEq for ArchivedInlineString<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> PartialOrd for ArchivedInlineString<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for ArchivedInlineString<N> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        PartialEq::eq(self.as_str(), *other)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for ArchivedInlineString<N> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        PartialEq::eq(self.as_str(), other)
+    }
+}
+
+impl<const N: usize> PartialEq<ArchivedInlineString<N>> for &str {
+    #[inline]
+    fn eq(&self, other: &ArchivedInlineString<N>) -> bool {
+        PartialEq::eq(other.as_str(), *self)
+    }
+}
+
+/// The resolver for an [`ArchivedInlineString`].
+pub struct InlineStringResolver {
+    pos: usize,
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::{owned::OwnedPointerError, ArchiveContext};
+    use bytecheck::{CheckBytes, Error};
+    use core::convert::Infallible;
+
+    impl<C: ArchiveContext + ?Sized, const N: usize> CheckBytes<C> for ArchivedInlineString<N>
+    where
+        C::Error: Error + 'static,
+    {
+        type Error = OwnedPointerError<Infallible, <str as CheckBytes<C>>::Error, C::Error>;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            // The length and bytes are always valid
+            let string = &*value;
+            context
+                .check_string_len(string.len())
+                .map_err(OwnedPointerError::ContextError)?;
+
+            if string.is_inline() {
+                let ptr = ptr_meta::from_raw_parts(string.bytes.as_ptr().cast(), string.len());
+                str::check_bytes(ptr, context).map_err(OwnedPointerError::ValueCheckBytesError)?;
+            } else {
+                let ptr = context
+                    .check_subtree_ptr::<str>(
+                        value.cast(),
+                        string.out_of_line_offset(),
+                        string.len(),
+                    )
+                    .map_err(OwnedPointerError::ContextError)?;
+
+                let range = context
+                    .push_prefix_subtree(ptr)
+                    .map_err(OwnedPointerError::ContextError)?;
+                str::check_bytes(ptr, context).map_err(OwnedPointerError::ValueCheckBytesError)?;
+                context
+                    .pop_prefix_range(range)
+                    .map_err(OwnedPointerError::ContextError)?;
+            }
+
+            Ok(string)
+        }
+    }
+};
@@ -1,5 +1,7 @@
 //! Archived versions of string types.
 
+pub mod inline;
+pub mod nul_terminated;
 pub mod repr;
 
 use crate::{Fallible, SerializeUnsized};
@@ -12,11 +14,17 @@ use core::{
 };
 use repr::{ArchivedStringRepr, INLINE_CAPACITY};
 
+pub use self::{inline::ArchivedInlineString, nul_terminated::ArchivedNulTerminatedString};
+
 /// An archived [`String`].
 ///
 /// This has inline and out-of-line representations. Short strings will use the available space
 /// inside the structure to store the string, and long strings will store a
 /// [`RelPtr`](crate::RelPtr) to a `str` instead.
+///
+/// Only strings of up to [`INLINE_CAPACITY`] bytes fit inside the structure. To inline longer
+/// strings, use [`ArchivedInlineString`] with the [`AsInlineString`](crate::with::AsInlineString)
+/// wrapper.
 #[repr(transparent)]
 pub struct ArchivedString(repr::ArchivedStringRepr);
 
@@ -189,7 +197,7 @@ pub struct StringResolver {
 const _: () = {
     use crate::validation::{
         owned::OwnedPointerError,
-        ArchiveContext,
+        ArchiveContext, PathSegment,
     };
     use bytecheck::{CheckBytes, Error};
 
@@ -211,10 +219,16 @@ const _: () = {
             // The repr is always valid
             let repr = ArchivedStringRepr::check_bytes(value.cast(), context)
                 .map_err(OwnedPointerError::PointerCheckBytesError)?;
+            context
+                .check_string_len(repr.len())
+                .map_err(OwnedPointerError::ContextError)?;
 
             if repr.is_inline() {
-                str::check_bytes(repr.as_str_ptr(), context)
-                    .map_err(OwnedPointerError::ValueCheckBytesError)?;
+                let ptr = repr.as_str_ptr();
+                str::check_bytes(ptr, context).map_err(|e| {
+                    context.record_check_error(&e, PathSegment::Deref, ptr.cast());
+                    OwnedPointerError::ValueCheckBytesError(e)
+                })?;
             } else {
                 let base = value.cast();
                 let offset = repr.out_of_line_offset();
@@ -227,7 +241,10 @@ const _: () = {
                 let range = context
                     .push_prefix_subtree(ptr)
                     .map_err(OwnedPointerError::ContextError)?;
-                str::check_bytes(ptr, context).map_err(OwnedPointerError::ValueCheckBytesError)?;
+                str::check_bytes(ptr, context).map_err(|e| {
+                    context.record_check_error(&e, PathSegment::Deref, ptr.cast());
+                    OwnedPointerError::ValueCheckBytesError(e)
+                })?;
                 context
                     .pop_prefix_range(range)
                     .map_err(OwnedPointerError::ContextError)?;
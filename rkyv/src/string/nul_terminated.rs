@@ -0,0 +1,298 @@
+//! An archived string with a trailing NUL for C interop.
+//!
+//! [`ArchivedNulTerminatedString`] stores a relative pointer and a length like
+//! [`ArchivedVec`](crate::vec::ArchivedVec), and its bytes are always followed by a NUL. C code
+//! reading a mapped archive can pass the bytes directly to functions expecting a `const char *`
+//! without copying them. The layout is always `repr(C)`:
+//!
+//! ```c
+//! struct {
+//!     int32_t offset; // relative to the start of the struct
+//!     uint32_t len;   // not including the trailing NUL
+//! };
+//! ```
+//!
+//! The offset and length are `int16_t`/`uint16_t` or `int64_t`/`uint64_t` with the `size_16` and
+//! `size_64` features instead.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, with::NulTerminated, Archive, Deserialize, Infallible, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Config {
+//!     #[with(NulTerminated)]
+//!     path: String,
+//! }
+//!
+//! let value = Config {
+//!     path: "/etc/app.conf".to_string(),
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//! let archived = unsafe { archived_root::<Config>(&bytes) };
+//!
+//! assert_eq!(archived.path, "/etc/app.conf");
+//! assert_eq!(archived.path.as_bytes_with_nul(), b"/etc/app.conf\0");
+//!
+//! let deserialized: Config = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized.path, value.path);
+//! ```
+
+use crate::{ser::Serializer, Archived, FixedUsize, RawRelPtr};
+use core::{borrow::Borrow, cmp, fmt, hash, ops::Deref, slice, str};
+
+/// An archived string whose bytes are followed by a NUL.
+///
+/// Interior NULs are preserved, so C code that treats the string as NUL-terminated will only see
+/// the bytes before the first one. Use the length to read the whole string.
+#[repr(C)]
+pub struct ArchivedNulTerminatedString {
+    ptr: RawRelPtr,
+    len: Archived<usize>,
+}
+
+impl ArchivedNulTerminatedString {
+    /// Returns the length of the string in bytes, not including the trailing NUL.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the NUL-terminated bytes of the string.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr().cast()
+    }
+
+    /// Returns the bytes of the string, not including the trailing NUL.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Returns the bytes of the string, including the trailing NUL.
+    #[inline]
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len() + 1) }
+    }
+
+    /// Extracts a string slice containing the entire string, not including the trailing NUL.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    /// Resolves an archived NUL-terminated string from a given `str`.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `value`
+    #[inline]
+    pub unsafe fn resolve_from_str(
+        value: &str,
+        pos: usize,
+        resolver: NulTerminatedStringResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.ptr);
+        RawRelPtr::emplace(pos + fp, resolver.pos, fo);
+        let (_, fo) = out_field!(out.len);
+        fo.write(to_archived!(value.len() as FixedUsize));
+    }
+
+    /// Serializes the bytes of a `str` followed by a NUL.
+    #[inline]
+    pub fn serialize_from_str<S: Serializer + ?Sized>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<NulTerminatedStringResolver, S::Error> {
+        let pos = serializer.pos();
+        serializer.write(value.as_bytes())?;
+        serializer.write(&[0])?;
+        Ok(NulTerminatedStringResolver { pos })
+    }
+}
+
+impl AsRef<str> for ArchivedNulTerminatedString {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for ArchivedNulTerminatedString {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for ArchivedNulTerminatedString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Deref for ArchivedNulTerminatedString {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for ArchivedNulTerminatedString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Eq for ArchivedNulTerminatedString {}
+
+impl hash::Hash for ArchivedNulTerminatedString {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl Ord for ArchivedNulTerminatedString {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialEq for ArchivedNulTerminatedString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialOrd for ArchivedNulTerminatedString {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<&str> for ArchivedNulTerminatedString {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        PartialEq::eq(self.as_str(), *other)
+    }
+}
+
+impl PartialEq<str> for ArchivedNulTerminatedString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        PartialEq::eq(self.as_str(), other)
+    }
+}
+
+impl PartialEq<ArchivedNulTerminatedString> for &str {
+    #[inline]
+    fn eq(&self, other: &ArchivedNulTerminatedString) -> bool {
+        PartialEq::eq(other.as_str(), *self)
+    }
+}
+
+/// The resolver for an [`ArchivedNulTerminatedString`].
+pub struct NulTerminatedStringResolver {
+    pos: usize,
+}
+
+/// Errors that can occur while checking an archived NUL-terminated string.
+#[cfg(feature = "validation")]
+#[derive(Debug)]
+pub enum NulTerminatedStringError<C> {
+    /// The bytes of the string were not followed by a NUL
+    MissingNul,
+    /// The bytes of the string were not valid UTF-8
+    InvalidUtf8(str::Utf8Error),
+    /// A context error occurred
+    ContextError(C),
+}
+
+#[cfg(feature = "validation")]
+impl<C: fmt::Display> fmt::Display for NulTerminatedStringError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NulTerminatedStringError::MissingNul => {
+                write!(f, "string is not followed by a NUL")
+            }
+            NulTerminatedStringError::InvalidUtf8(e) => write!(f, "invalid UTF-8: {}", e),
+            NulTerminatedStringError::ContextError(e) => write!(f, "context error: {}", e),
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "validation"))]
+impl<C: std::error::Error + 'static> std::error::Error for NulTerminatedStringError<C> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NulTerminatedStringError::MissingNul => None,
+            NulTerminatedStringError::InvalidUtf8(e) => Some(e as &dyn std::error::Error),
+            NulTerminatedStringError::ContextError(e) => Some(e as &dyn std::error::Error),
+        }
+    }
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::validation::ArchiveContext;
+    use bytecheck::{CheckBytes, Error};
+
+    impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedNulTerminatedString
+    where
+        C::Error: Error,
+    {
+        type Error = NulTerminatedStringError<C::Error>;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            // The relative pointer and length are always valid
+            let string = &*value;
+            let len = string.len();
+            context
+                .check_string_len(len)
+                .map_err(NulTerminatedStringError::ContextError)?;
+            let len_with_nul = len
+                .checked_add(1)
+                .ok_or(NulTerminatedStringError::MissingNul)?;
+
+            let ptr = context
+                .check_subtree_ptr::<[u8]>(string.ptr.base(), string.ptr.offset(), len_with_nul)
+                .map_err(NulTerminatedStringError::ContextError)?;
+
+            let range = context
+                .push_prefix_subtree(ptr)
+                .map_err(NulTerminatedStringError::ContextError)?;
+            let bytes = &*ptr;
+            if bytes[len] != 0 {
+                return Err(NulTerminatedStringError::MissingNul);
+            }
+            str::from_utf8(&bytes[..len]).map_err(NulTerminatedStringError::InvalidUtf8)?;
+            context
+                .pop_prefix_range(range)
+                .map_err(NulTerminatedStringError::ContextError)?;
+
+            Ok(string)
+        }
+    }
+};
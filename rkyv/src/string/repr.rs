@@ -153,6 +153,18 @@ impl ArchivedStringRepr {
         let out_len = ptr::addr_of_mut!((*out).out_of_line.len);
         out_len.write(to_archived!(value.len() as FixedUsize));
 
+        Self::emplace_out_of_line_offset(pos, target, out);
+    }
+
+    /// Emplaces the offset of an out-of-line representation, pointing it to the given target.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the location of the representation within the archive.
+    /// - `target` must be located before `pos` in the archive.
+    /// - `out` must point to a valid location to write the offset.
+    #[inline]
+    pub unsafe fn emplace_out_of_line_offset(pos: usize, target: usize, out: *mut Self) {
         let out_offset = ptr::addr_of_mut!((*out).out_of_line.offset);
         let offset = crate::rel_ptr::signed_offset(pos, target).unwrap();
         *out_offset = (offset as FixedIsize).to_le_bytes();
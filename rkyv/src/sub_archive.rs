@@ -0,0 +1,218 @@
+//! Archives embedded inside of other archives.
+//!
+//! A [`SubArchive`] holds the bytes of an archive that has already been serialized. When it is
+//! serialized as part of another archive, those bytes are copied into the outer archive as-is and
+//! aligned so that the root of the inner archive can still be accessed without copying. This is
+//! useful for sharded data, where each shard is serialized separately and later collected into a
+//! single archive.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, sub_archive::SubArchive, to_bytes};
+//!
+//! let shards = vec![
+//!     SubArchive::<Vec<u32>>::from_value::<256>(&vec![1, 2, 3]).unwrap(),
+//!     SubArchive::<Vec<u32>>::from_value::<256>(&vec![4, 5]).unwrap(),
+//! ];
+//! let bytes = to_bytes::<_, 256>(&shards).unwrap();
+//!
+//! let archived = unsafe { archived_root::<Vec<SubArchive<Vec<u32>>>>(&bytes) };
+//! assert_eq!(archived[0].root().as_slice(), &[1, 2, 3]);
+//! assert_eq!(archived[1].root().as_slice(), &[4, 5]);
+//! ```
+
+use crate::{archived_root, vec::ArchivedVec, Archive};
+#[cfg(feature = "alloc")]
+use crate::{util::AlignedVec, vec::VecResolver};
+use core::{fmt, marker::PhantomData};
+
+/// An archived [`SubArchive`].
+///
+/// The bytes of the inner archive are stored with the same alignment as an
+/// [`AlignedVec`](crate::util::AlignedVec), so its root can be accessed in place.
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedSubArchive<T> {
+    bytes: ArchivedVec<u8>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Archive> ArchivedSubArchive<T> {
+    /// Returns the bytes of the inner archive.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Returns the root of the inner archive.
+    #[inline]
+    pub fn root(&self) -> &T::Archived {
+        // SAFETY: Archived sub-archives can only be accessed through an unsafe access to the outer
+        // archive, or after validation, which also checks the inner archive.
+        unsafe { archived_root::<T>(self.as_bytes()) }
+    }
+}
+
+impl<T> fmt::Debug for ArchivedSubArchive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedSubArchive")
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "validation")]
+impl<T, C> bytecheck::CheckBytes<C> for ArchivedSubArchive<T>
+where
+    T: Archive,
+    T::Archived: for<'b> bytecheck::CheckBytes<crate::validation::validators::DefaultValidator<'b>>,
+    C: crate::validation::ArchiveContext + ?Sized,
+    C::Error: bytecheck::Error,
+{
+    type Error = bytecheck::StructCheckError;
+
+    #[inline]
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        use crate::validation::{check_archived_root_with_context, validators::DefaultValidator};
+        use bytecheck::{ErrorBox, StructCheckError};
+
+        let bytes = ArchivedVec::<u8>::check_bytes(core::ptr::addr_of!((*value).bytes), context)
+            .map_err(|e| StructCheckError {
+                field_name: "bytes",
+                inner: ErrorBox::new(e),
+            })?;
+        // The inner archive is checked on its own, since its objects lie outside of the subtree
+        // ranges of the outer archive. It may only use what's left of the limits of the outer
+        // archive.
+        let mut validator =
+            DefaultValidator::with_limits(bytes.as_slice(), context.nested_limits());
+        check_archived_root_with_context::<T, _>(bytes.as_slice(), &mut validator).map_err(
+            |e| StructCheckError {
+                field_name: "root",
+                inner: ErrorBox::new(e),
+            },
+        )?;
+        Ok(&*value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::{
+        ser::{serializers::AllocSerializer, Serializer},
+        to_bytes, Deserialize, Fallible, Serialize,
+    };
+
+    impl<T> SubArchive<T> {
+        /// Creates a sub-archive from the bytes of an archive.
+        ///
+        /// # Safety
+        ///
+        /// `bytes` must contain a valid archive with an archived `T` at its root.
+        #[inline]
+        pub unsafe fn from_bytes_unchecked(bytes: AlignedVec) -> Self {
+            Self {
+                bytes,
+                _phantom: PhantomData,
+            }
+        }
+
+        /// Serializes a value into a new sub-archive.
+        #[inline]
+        pub fn from_value<const N: usize>(
+            value: &T,
+        ) -> Result<Self, <AllocSerializer<N> as Fallible>::Error>
+        where
+            T: Serialize<AllocSerializer<N>>,
+        {
+            Ok(Self {
+                bytes: to_bytes::<T, N>(value)?,
+                _phantom: PhantomData,
+            })
+        }
+
+        /// Returns the bytes of the archive.
+        #[inline]
+        pub fn as_bytes(&self) -> &[u8] {
+            self.bytes.as_slice()
+        }
+
+        /// Consumes the sub-archive and returns the bytes of the archive.
+        #[inline]
+        pub fn into_bytes(self) -> AlignedVec {
+            self.bytes
+        }
+
+        /// Returns the root of the archive.
+        #[inline]
+        pub fn root(&self) -> &T::Archived
+        where
+            T: Archive,
+        {
+            // SAFETY: Sub-archives always contain a valid archive with an archived `T` at its root.
+            unsafe { archived_root::<T>(self.as_bytes()) }
+        }
+    }
+
+    impl<T> Clone for SubArchive<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self {
+                bytes: self.bytes.clone(),
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<T> fmt::Debug for SubArchive<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("SubArchive")
+                .field("len", &self.bytes.len())
+                .finish()
+        }
+    }
+
+    impl<T: Archive> Archive for SubArchive<T> {
+        type Archived = ArchivedSubArchive<T>;
+        type Resolver = VecResolver;
+
+        #[inline]
+        unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+            let (fp, fo) = out_field!(out.bytes);
+            ArchivedVec::resolve_from_len(self.bytes.len(), pos + fp, resolver, fo);
+        }
+    }
+
+    impl<T: Archive, S: Serializer + ?Sized> Serialize<S> for SubArchive<T> {
+        #[inline]
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            serializer.align(AlignedVec::ALIGNMENT)?;
+            // SAFETY: `u8` has no padding bytes
+            unsafe { ArchivedVec::serialize_copy_from_slice(self.as_bytes(), serializer) }
+        }
+    }
+
+    impl<T: Archive, D: Fallible + ?Sized> Deserialize<SubArchive<T>, D> for ArchivedSubArchive<T> {
+        #[inline]
+        fn deserialize(&self, _: &mut D) -> Result<SubArchive<T>, D::Error> {
+            let mut bytes = AlignedVec::with_capacity(self.bytes.len());
+            bytes.extend_from_slice(self.as_bytes());
+            Ok(SubArchive {
+                bytes,
+                _phantom: PhantomData,
+            })
+        }
+    }
+};
+
+/// The bytes of an archive that can be embedded inside of another archive.
+///
+/// See the [module-level documentation](self) for more information.
+#[cfg(feature = "alloc")]
+pub struct SubArchive<T> {
+    bytes: AlignedVec,
+    _phantom: PhantomData<T>,
+}
@@ -0,0 +1,356 @@
+//! Archiving the last field of a struct inline as an unsized tail.
+//!
+//! Structs derived with `#[archive(unsized)]` have an unsized archived type: their last field is
+//! archived inline after the rest of the fields instead of behind a relative pointer. This is the
+//! header + payload layout that's common in file formats:
+//!
+//! ```
+//! use rkyv::{archived_unsized_root, ser::{serializers::AllocSerializer, Serializer}};
+//! use rkyv::{Archive, Deserialize, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+//! #[archive(unsized)]
+//! struct Packet {
+//!     id: u32,
+//!     flags: u8,
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let value = Packet { id: 42, flags: 1, payload: vec![1, 2, 3, 4] };
+//!
+//! let mut serializer = AllocSerializer::<256>::default();
+//! serializer.serialize_unsized_value(&value).unwrap();
+//! let bytes = serializer.into_serializer().into_inner();
+//!
+//! let archived = unsafe { archived_unsized_root::<Packet>(&bytes) };
+//! assert_eq!(archived.id, 42);
+//! assert_eq!(archived.payload, [1, 2, 3, 4]);
+//! ```
+//!
+//! The last field must implement [`ArchiveTail`], which is implemented for `Vec<T>`, `Box<[T]>`,
+//! and `String`. The tail is archived as an [`InlineTail`], which dereferences to the
+//! [`ArchivedTail`](ArchiveTail::ArchivedTail) of the field: a slice of its archived elements for
+//! `Vec<T>` and `Box<[T]>`, and a `str` for `String`.
+//!
+//! Because the archived struct is unsized, the struct implements [`ArchiveUnsized`] and
+//! [`SerializeUnsized`] instead of [`Archive`] and [`Serialize`](crate::Serialize). It can be
+//! archived as the root of an archive with
+//! [`serialize_unsized_value`](crate::ser::Serializer::serialize_unsized_value), or inside of other
+//! types with `Box<T>`. To validate it, validate a `Box` of it or derive `CheckBytes` for the
+//! archived struct and check it like any other unsized type.
+//!
+//! [`ArchiveUnsized`]: crate::ArchiveUnsized
+//! [`SerializeUnsized`]: crate::SerializeUnsized
+
+use crate::{
+    ser::{ScratchSpace, Serializer},
+    Archive, Archived, Fallible, ScratchVec, Serialize,
+};
+use core::{
+    alloc::{Layout, LayoutError},
+    fmt,
+    marker::PhantomData,
+    mem::{align_of, size_of, MaybeUninit},
+    ops::Deref,
+    ptr,
+};
+#[doc(hidden)]
+pub use ptr_meta::{metadata, Pointee};
+
+/// A type that can be archived inline as the unsized last field of an `#[archive(unsized)]`
+/// struct.
+///
+/// # Safety
+///
+/// `ArchivedTail` must have the same layout as a slice of the archived elements of the tail, with
+/// the length of the slice as its pointer metadata. The archived elements of every value returned
+/// by `tail_elements` must be a valid `ArchivedTail`.
+pub unsafe trait ArchiveTail {
+    /// The type of the elements of the tail.
+    type Element: Archive;
+
+    /// The archived counterpart of the tail.
+    type ArchivedTail: Pointee<Metadata = usize> + ?Sized;
+
+    /// Returns the elements of the tail.
+    fn tail_elements(&self) -> &[Self::Element];
+}
+
+/// A type that can be deserialized from an archived tail.
+pub trait DeserializeTail<D: Fallible + ?Sized>: ArchiveTail + Sized {
+    /// Deserializes an archived tail.
+    fn deserialize_tail(
+        archived: &Self::ArchivedTail,
+        deserializer: &mut D,
+    ) -> Result<Self, D::Error>;
+}
+
+/// A tail archived inline at the end of an `#[archive(unsized)]` struct.
+///
+/// This holds the archived elements of the tail and dereferences to its
+/// [`ArchivedTail`](ArchiveTail::ArchivedTail).
+#[repr(transparent)]
+pub struct InlineTail<T: ArchiveTail> {
+    _phantom: PhantomData<T>,
+    elements: [Archived<T::Element>],
+}
+
+impl<T: ArchiveTail> Pointee for InlineTail<T> {
+    type Metadata = usize;
+}
+
+impl<T: ArchiveTail> InlineTail<T> {
+    /// Gets the archived tail as a slice of its archived elements.
+    #[inline]
+    pub fn as_elements(&self) -> &[Archived<T::Element>] {
+        &self.elements
+    }
+}
+
+impl<T: ArchiveTail> Deref for InlineTail<T> {
+    type Target = T::ArchivedTail;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        let elements = self.as_elements();
+        unsafe { &*ptr_meta::from_raw_parts(elements.as_ptr().cast(), elements.len()) }
+    }
+}
+
+impl<T: ArchiveTail> fmt::Debug for InlineTail<T>
+where
+    T::ArchivedTail: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T: ArchiveTail, U: ?Sized> PartialEq<U> for InlineTail<T>
+where
+    T::ArchivedTail: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &U) -> bool {
+        self.deref().eq(other)
+    }
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use bytecheck::CheckBytes;
+
+    impl<T, C> CheckBytes<C> for InlineTail<T>
+    where
+        T: ArchiveTail,
+        T::ArchivedTail: CheckBytes<C>,
+        C: ?Sized,
+    {
+        type Error = <T::ArchivedTail as CheckBytes<C>>::Error;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            let tail = ptr_meta::from_raw_parts::<T::ArchivedTail>(value.cast(), metadata(value));
+            T::ArchivedTail::check_bytes(tail, context)?;
+            Ok(&*value)
+        }
+    }
+};
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::Deserialize;
+    #[cfg(not(feature = "std"))]
+    use ::alloc::{boxed::Box, string::String, vec::Vec};
+
+    unsafe impl<T: Archive> ArchiveTail for Vec<T> {
+        type Element = T;
+        type ArchivedTail = [T::Archived];
+
+        #[inline]
+        fn tail_elements(&self) -> &[T] {
+            self.as_slice()
+        }
+    }
+
+    impl<T: Archive, D: Fallible + ?Sized> DeserializeTail<D> for Vec<T>
+    where
+        T::Archived: Deserialize<T, D>,
+    {
+        #[inline]
+        fn deserialize_tail(
+            archived: &[T::Archived],
+            deserializer: &mut D,
+        ) -> Result<Self, D::Error> {
+            archived
+                .iter()
+                .map(|x| x.deserialize(deserializer))
+                .collect()
+        }
+    }
+
+    unsafe impl<T: Archive> ArchiveTail for Box<[T]> {
+        type Element = T;
+        type ArchivedTail = [T::Archived];
+
+        #[inline]
+        fn tail_elements(&self) -> &[T] {
+            self
+        }
+    }
+
+    impl<T: Archive, D: Fallible + ?Sized> DeserializeTail<D> for Box<[T]>
+    where
+        T::Archived: Deserialize<T, D>,
+    {
+        #[inline]
+        fn deserialize_tail(
+            archived: &[T::Archived],
+            deserializer: &mut D,
+        ) -> Result<Self, D::Error> {
+            Vec::deserialize_tail(archived, deserializer).map(Vec::into_boxed_slice)
+        }
+    }
+
+    // The bytes of a `String` are always valid UTF-8, so they can be archived as a `str`
+    unsafe impl ArchiveTail for String {
+        type Element = u8;
+        type ArchivedTail = str;
+
+        #[inline]
+        fn tail_elements(&self) -> &[u8] {
+            self.as_bytes()
+        }
+    }
+
+    impl<D: Fallible + ?Sized> DeserializeTail<D> for String {
+        #[inline]
+        fn deserialize_tail(archived: &str, _: &mut D) -> Result<Self, D::Error> {
+            Ok(String::from(archived))
+        }
+    }
+};
+
+/// A struct that archives with an unsized tail.
+///
+/// This is implemented by `#[derive(Archive)]` for `#[archive(unsized)]` structs and is not meant
+/// to be implemented directly.
+///
+/// # Safety
+///
+/// `Header` must be a `#[repr(C)]` struct with the same fields as the archived struct, except that
+/// its last field is a zero-length array of the archived elements of the tail. `tail_offset` must
+/// return the offset of that field, and `resolve_header` must initialize every other field.
+#[doc(hidden)]
+pub unsafe trait ArchiveWithTail {
+    /// The archived struct without its tail.
+    type Header;
+
+    /// The resolver for the header of the archived struct.
+    type HeaderResolver;
+
+    /// The type of the last field of the struct.
+    type Tail: ArchiveTail;
+
+    /// Returns the offset of the tail in the archived struct.
+    fn tail_offset() -> usize;
+
+    /// Returns the last field of the struct.
+    fn tail(&self) -> &Self::Tail;
+
+    /// Creates the archived header of the struct in the output pointer.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing the fields of the header
+    unsafe fn resolve_header(
+        &self,
+        pos: usize,
+        resolver: Self::HeaderResolver,
+        out: *mut Self::Header,
+    );
+}
+
+/// Serializes a struct with an unsized tail and returns the position of the archived struct.
+///
+/// The elements of the tail are serialized first, then the header is resolved and written followed
+/// by the archived elements.
+#[doc(hidden)]
+pub fn serialize_with_tail<T, S>(
+    value: &T,
+    resolver: T::HeaderResolver,
+    serializer: &mut S,
+) -> Result<usize, S::Error>
+where
+    T: ArchiveWithTail + ?Sized,
+    <T::Tail as ArchiveTail>::Element: Serialize<S>,
+    S: Serializer + ScratchSpace + ?Sized,
+{
+    let elements = value.tail().tail_elements();
+    let tail_offset = T::tail_offset();
+
+    unsafe {
+        let mut resolvers = ScratchVec::new(serializer, elements.len())?;
+        for element in elements {
+            resolvers.push(element.serialize(serializer)?);
+        }
+
+        let pos = serializer.align(align_of::<T::Header>())?;
+        let mut header = MaybeUninit::<T::Header>::zeroed();
+        value.resolve_header(pos, resolver, header.as_mut_ptr());
+        serializer.write(core::slice::from_raw_parts(
+            header.as_ptr().cast::<u8>(),
+            tail_offset,
+        ))?;
+        for (element, resolver) in elements.iter().zip(resolvers.drain(..)) {
+            serializer.resolve_aligned(element, resolver)?;
+        }
+
+        resolvers.free(serializer)?;
+
+        // Like sized types, archived structs are padded out to a multiple of their alignment
+        let len = tail_offset + elements.len() * size_of_element::<T>();
+        let size = (len + align_of::<T::Header>() - 1) & !(align_of::<T::Header>() - 1);
+        serializer.pad(size - len)?;
+
+        Ok(pos)
+    }
+}
+
+#[inline]
+fn size_of_element<T: ArchiveWithTail + ?Sized>() -> usize {
+    size_of::<Archived<<T::Tail as ArchiveTail>::Element>>()
+}
+
+/// Returns the layout of the archived struct of `T` with a tail of the given length.
+#[doc(hidden)]
+#[inline]
+pub fn tail_layout<T: ArchiveWithTail + ?Sized>(len: usize) -> Result<Layout, LayoutError> {
+    let tail = Layout::array::<Archived<<T::Tail as ArchiveTail>::Element>>(len)?;
+    let layout = Layout::from_size_align(T::tail_offset() + tail.size(), align_of::<T::Header>())?;
+    Ok(layout.pad_to_align())
+}
+
+/// Moves a deserialized value into memory allocated by `alloc` and returns a pointer to it.
+///
+/// # Safety
+///
+/// `alloc` must return memory that fits the given layout.
+#[doc(hidden)]
+#[inline]
+pub unsafe fn alloc_deserialized<T>(value: T, mut alloc: impl FnMut(Layout) -> *mut u8) -> *mut () {
+    let layout = Layout::new::<T>();
+    if layout.size() == 0 {
+        ptr::NonNull::<T>::dangling().as_ptr().cast()
+    } else {
+        let result = alloc(layout).cast::<T>();
+        assert!(!result.is_null());
+        result.write(value);
+        result.cast()
+    }
+}
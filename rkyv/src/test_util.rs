@@ -0,0 +1,196 @@
+//! Helpers for testing that types round-trip through archives.
+//!
+//! These are the same helpers that rkyv uses to test its own types. Each one serializes a value,
+//! checks that the archived value is equal to the original, and panics with a descriptive message
+//! if it isn't:
+//!
+//! ```
+//! use rkyv::{test_util::test_archive, Archive, Deserialize, Serialize};
+//!
+//! #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+//! #[archive(compare(PartialEq))]
+//! #[archive_attr(derive(Debug))]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! test_archive(&Point { x: 1, y: 2 });
+//! ```
+//!
+//! With the `proptest` feature, [`proptest_archive`] runs one of these checks on every value
+//! generated by a [`proptest`] strategy.
+
+use crate::{
+    archived_root, archived_unsized_root,
+    de::deserializers::SharedDeserializeMap,
+    ser::{serializers::AllocSerializer, Serializer},
+    AlignedVec, Deserialize, Serialize, SerializeUnsized,
+};
+use core::{fmt::Debug, ops::Deref};
+
+/// The serializer used by the test helpers.
+pub type TestSerializer = AllocSerializer<256>;
+
+/// The deserializer used by the test helpers.
+pub type TestDeserializer = SharedDeserializeMap;
+
+fn serialize_with<F: FnOnce(&mut TestSerializer)>(f: F) -> AlignedVec {
+    let mut serializer = TestSerializer::default();
+    f(&mut serializer);
+    serializer.into_serializer().into_inner()
+}
+
+/// Checks that the given value archives and deserializes to an equal value.
+///
+/// # Panics
+///
+/// Panics if the value fails to serialize or deserialize, or if the archived or deserialized
+/// value isn't equal to the original value.
+pub fn test_archive<T>(value: &T)
+where
+    T: Debug + PartialEq + Serialize<TestSerializer>,
+    T::Archived: Debug + PartialEq<T> + Deserialize<T, TestDeserializer>,
+{
+    let buffer = serialize_with(|s| {
+        s.serialize_value(value).expect("failed to archive value");
+    });
+
+    // SAFETY: The buffer contains an archived `T` at the root.
+    let archived_value = unsafe { archived_root::<T>(buffer.as_slice()) };
+    assert_eq!(archived_value, value);
+    let mut deserializer = TestDeserializer::default();
+    assert_eq!(
+        &archived_value.deserialize(&mut deserializer).unwrap(),
+        value
+    );
+}
+
+/// Checks that the given unsized value archives to an equal value.
+///
+/// # Panics
+///
+/// Panics if the value fails to serialize, or if the archived value isn't equal to the original
+/// value.
+pub fn test_archive_ref<T: Debug + SerializeUnsized<TestSerializer> + ?Sized>(value: &T)
+where
+    T::Archived: Debug + PartialEq<T>,
+{
+    let buffer = serialize_with(|s| {
+        s.serialize_unsized_value(value)
+            .expect("failed to archive ref");
+    });
+
+    // SAFETY: The buffer contains an archived `T` at the root.
+    let archived_ref = unsafe { archived_unsized_root::<T>(buffer.as_slice()) };
+    assert_eq!(archived_ref, value);
+}
+
+/// Checks that the contents of the given container archive to equal contents.
+///
+/// This is for smart pointers and other containers that can't be compared with their archived
+/// versions directly, but whose targets can.
+///
+/// # Panics
+///
+/// Panics if the value fails to serialize, or if the archived target isn't equal to the original
+/// target.
+pub fn test_archive_container<
+    T: Serialize<TestSerializer, Archived = U> + Deref<Target = TV>,
+    TV: Debug + ?Sized,
+    U: Deref<Target = TU>,
+    TU: Debug + PartialEq<TV> + ?Sized,
+>(
+    value: &T,
+) {
+    let buffer = serialize_with(|s| {
+        s.serialize_value(value).expect("failed to archive ref");
+    });
+
+    // SAFETY: The buffer contains an archived `T` at the root.
+    let archived_ref = unsafe { archived_root::<T>(buffer.as_slice()) };
+    assert_eq!(archived_ref.deref(), value.deref());
+}
+
+/// Checks that the given value archives to a valid archive and deserializes to an equal value.
+///
+/// This is like [`test_archive`], but it also validates the archive before accessing it.
+///
+/// # Panics
+///
+/// Panics if the value fails to serialize, validate, or deserialize, or if the archived or
+/// deserialized value isn't equal to the original value.
+#[cfg(feature = "validation")]
+pub fn test_archive_checked<T>(value: &T)
+where
+    T: Debug + PartialEq + Serialize<TestSerializer>,
+    T::Archived: Debug
+        + PartialEq<T>
+        + for<'a> bytecheck::CheckBytes<crate::validation::validators::DefaultValidator<'a>>
+        + Deserialize<T, TestDeserializer>,
+{
+    let buffer = serialize_with(|s| {
+        s.serialize_value(value).expect("failed to archive value");
+    });
+
+    let archived_value = match crate::check_archived_root::<T>(buffer.as_slice()) {
+        Ok(archived) => archived,
+        Err(e) => panic!("failed to validate the archive of {:?}: {}", value, e),
+    };
+    assert_eq!(archived_value, value);
+    let mut deserializer = TestDeserializer::default();
+    assert_eq!(
+        &archived_value.deserialize(&mut deserializer).unwrap(),
+        value
+    );
+}
+
+/// Runs a test helper on every value generated by the given strategy.
+///
+/// Failing values are shrunk to a minimal failing value as usual for proptest.
+///
+/// # Examples
+/// ```
+/// use proptest::prelude::*;
+/// use rkyv::test_util::{proptest_archive, test_archive};
+///
+/// proptest_archive(any::<Vec<String>>(), test_archive);
+/// ```
+///
+/// # Panics
+///
+/// Panics with the minimal failing value if the helper fails for any generated value.
+#[cfg(feature = "proptest")]
+pub fn proptest_archive<S>(strategy: S, test: fn(&S::Value))
+where
+    S: proptest::strategy::Strategy,
+{
+    let mut runner = proptest::test_runner::TestRunner::default();
+    if let Err(e) = runner.run(&strategy, |value| {
+        test(&value);
+        Ok(())
+    }) {
+        panic!("{}", e);
+    }
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod tests {
+    use super::{proptest_archive, test_archive, test_archive_ref};
+    use proptest::prelude::*;
+
+    #[test]
+    fn proptest_round_trip() {
+        proptest_archive(any::<(u8, i64, bool)>(), test_archive);
+        proptest_archive(any::<Vec<String>>(), test_archive);
+        proptest_archive(any::<String>(), |s| test_archive_ref(s.as_str()));
+        #[cfg(feature = "validation")]
+        proptest_archive(any::<Vec<Option<u32>>>(), super::test_archive_checked);
+    }
+
+    #[test]
+    #[should_panic]
+    fn proptest_failure() {
+        proptest_archive(any::<u32>(), |value| assert!(*value < 1000));
+    }
+}
@@ -149,3 +149,271 @@ const _: () = {
         }
     }
 };
+
+/// An archived calendar date.
+///
+/// Dates are archived as the number of days since the Unix epoch (1970-01-01), so archived dates
+/// are portable and compare in chronological order. This is the archived form of `NaiveDate` from
+/// `chrono` and `Date` from `time`.
+///
+/// Validating an archived date checks that it is in range for each of `chrono` and `time` that is
+/// enabled, so that it can be deserialized as any of them.
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedDate {
+    days: Archived<i32>,
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl ArchivedDate {
+    /// Returns the number of days between the Unix epoch and this date.
+    #[inline]
+    pub const fn days_since_epoch(&self) -> i32 {
+        from_archived!(self.days)
+    }
+
+    /// Constructs an archived date at the given position.
+    ///
+    /// # Safety
+    ///
+    /// `out` must point to memory suitable for holding an `ArchivedDate`.
+    #[inline]
+    pub unsafe fn emplace(days_since_epoch: i32, out: *mut ArchivedDate) {
+        use core::ptr::addr_of_mut;
+
+        addr_of_mut!((*out).days).write(to_archived!(days_since_epoch));
+    }
+}
+
+/// An archived date and time without a time zone.
+///
+/// Date times are archived as the number of whole seconds since the Unix epoch and a number of
+/// nanoseconds, so archived date times are portable and compare in chronological order. This is
+/// the archived form of `NaiveDateTime` from `chrono` and `PrimitiveDateTime` from `time`.
+///
+/// The nanoseconds may exceed one second to represent a leap second. Like dates, validating an
+/// archived date time checks that it is valid for each of `chrono` and `time` that is enabled.
+/// `chrono` only allows leap seconds in the last second of a minute.
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedDateTime {
+    secs: Archived<i64>,
+    nanos: Archived<u32>,
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl ArchivedDateTime {
+    /// Returns the number of non-leap seconds between the Unix epoch and this date time.
+    #[inline]
+    pub const fn timestamp(&self) -> i64 {
+        from_archived!(self.secs)
+    }
+
+    /// Returns the number of nanoseconds since the last whole second.
+    ///
+    /// The returned value is less than two billion, and is only greater than one billion for leap
+    /// seconds.
+    #[inline]
+    pub const fn timestamp_subsec_nanos(&self) -> u32 {
+        from_archived!(self.nanos)
+    }
+
+    /// Constructs an archived date time at the given position.
+    ///
+    /// # Safety
+    ///
+    /// - `out` must point to memory suitable for holding an `ArchivedDateTime`
+    /// - `nanos` must be less than two billion
+    #[inline]
+    pub unsafe fn emplace(secs: i64, nanos: u32, out: *mut ArchivedDateTime) {
+        use core::ptr::addr_of_mut;
+
+        addr_of_mut!((*out).secs).write(to_archived!(secs));
+        addr_of_mut!((*out).nanos).write(to_archived!(nanos));
+    }
+}
+
+/// An archived date and time with a fixed offset from UTC.
+///
+/// Offset date times are archived as an [`ArchivedDateTime`] in UTC and the offset in seconds, so
+/// archived offset date times compare by the instant they represent. This is the archived form of
+/// `DateTime` from `chrono` and `OffsetDateTime` from `time`.
+///
+/// `chrono` supports offsets of less than a day and `time` supports offsets of less than 26 hours,
+/// so the offsets that pass validation depend on which of them are enabled.
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedOffsetDateTime {
+    utc: ArchivedDateTime,
+    offset: Archived<i32>,
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl ArchivedOffsetDateTime {
+    /// Returns the archived date time in UTC.
+    #[inline]
+    pub const fn utc(&self) -> &ArchivedDateTime {
+        &self.utc
+    }
+
+    /// Returns the offset from UTC in seconds.
+    #[inline]
+    pub const fn offset_seconds(&self) -> i32 {
+        from_archived!(self.offset)
+    }
+
+    /// Constructs an archived offset date time at the given position.
+    ///
+    /// # Safety
+    ///
+    /// - `out` must point to memory suitable for holding an `ArchivedOffsetDateTime`
+    /// - `nanos` must be less than two billion
+    /// - `offset` must be less than 26 hours in either direction
+    #[inline]
+    pub unsafe fn emplace(secs: i64, nanos: u32, offset: i32, out: *mut ArchivedOffsetDateTime) {
+        use core::ptr::addr_of_mut;
+
+        ArchivedDateTime::emplace(secs, nanos, addr_of_mut!((*out).utc));
+        addr_of_mut!((*out).offset).write(to_archived!(offset));
+    }
+}
+
+/// An error resulting from an invalid date time.
+#[cfg(all(feature = "validation", any(feature = "chrono", feature = "time")))]
+#[derive(Debug)]
+pub enum DateTimeError {
+    /// The date was out of range
+    Date(i32),
+    /// The nanoseconds were greater than or equal to two billion
+    Nanos(u32),
+    /// The date time was out of range, or was a leap second that isn't supported
+    DateTime {
+        /// The seconds since the Unix epoch
+        secs: i64,
+        /// The nanoseconds since the last whole second
+        nanos: u32,
+    },
+    /// The offset from UTC was out of range
+    Offset(i32),
+}
+
+#[cfg(all(feature = "validation", any(feature = "chrono", feature = "time")))]
+impl core::fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DateTimeError::Date(days) => write!(
+                f,
+                "Date time error: date {} days from the Unix epoch is out of range",
+                days
+            ),
+            DateTimeError::Nanos(nanos) => write!(
+                f,
+                "Date time error: nanos field {} is greater than or equal to 2 billion",
+                nanos
+            ),
+            DateTimeError::DateTime { secs, nanos } => write!(
+                f,
+                "Date time error: timestamp {}s {}ns is out of range or an invalid leap second",
+                secs, nanos
+            ),
+            DateTimeError::Offset(offset) => {
+                write!(f, "Date time error: offset {} is out of range", offset)
+            }
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    feature = "validation",
+    any(feature = "chrono", feature = "time")
+))]
+impl std::error::Error for DateTimeError {}
+
+#[cfg(all(feature = "validation", any(feature = "chrono", feature = "time")))]
+const _: () = {
+    use crate::Fallible;
+    use bytecheck::CheckBytes;
+
+    const MAX_NANOS: u32 = 2 * NANOS_PER_SEC;
+
+    impl<C: Fallible + ?Sized> CheckBytes<C> for ArchivedDate {
+        type Error = DateTimeError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+            let date = &*value;
+
+            let in_range = true;
+            #[cfg(feature = "chrono")]
+            let in_range = in_range && crate::impls::chrono::naive_date(date).is_some();
+            #[cfg(feature = "time")]
+            let in_range = in_range && crate::impls::time::date(date).is_some();
+
+            if in_range {
+                Ok(date)
+            } else {
+                Err(DateTimeError::Date(date.days_since_epoch()))
+            }
+        }
+    }
+
+    impl<C: Fallible + ?Sized> CheckBytes<C> for ArchivedDateTime {
+        type Error = DateTimeError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+            let date_time = &*value;
+
+            let nanos = date_time.timestamp_subsec_nanos();
+            if nanos >= MAX_NANOS {
+                return Err(DateTimeError::Nanos(nanos));
+            }
+
+            let valid = true;
+            #[cfg(feature = "chrono")]
+            let valid = valid && crate::impls::chrono::date_time(date_time).is_some();
+            #[cfg(feature = "time")]
+            let valid = valid && crate::impls::time::utc_date_time(date_time).is_some();
+
+            if valid {
+                Ok(date_time)
+            } else {
+                Err(DateTimeError::DateTime {
+                    secs: date_time.timestamp(),
+                    nanos,
+                })
+            }
+        }
+    }
+
+    impl<C: Fallible + ?Sized> CheckBytes<C> for ArchivedOffsetDateTime {
+        type Error = DateTimeError;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            let date_time = &*value;
+
+            ArchivedDateTime::check_bytes(&date_time.utc, context)?;
+
+            let offset = date_time.offset_seconds();
+            let valid = true;
+            #[cfg(feature = "chrono")]
+            let valid = valid && crate::impls::chrono::fixed_offset(offset).is_some();
+            #[cfg(feature = "time")]
+            let valid = valid && crate::impls::time::offset_date_time(date_time).is_some();
+
+            if valid {
+                Ok(date_time)
+            } else {
+                Err(DateTimeError::Offset(offset))
+            }
+        }
+    }
+};
@@ -11,15 +11,27 @@
 
 #[cfg(feature = "alloc")]
 mod aligned_vec;
+#[cfg(feature = "alloc")]
+mod owned_archive;
 mod scratch_vec;
 
+#[cfg(feature = "tokio")]
+use crate::ser::serializers::{AsyncSerializer, AsyncWriteSerializer, CompositeSerializerError};
 #[cfg(feature = "alloc")]
 use crate::{
+    boxed::ArchivedBox,
     de::deserializers::SharedDeserializeMap,
-    ser::{serializers::AllocSerializer, Serializer},
-    Fallible,
+    ser::{
+        serializers::{
+            AlignedSerializer, AllocSerializer, AppendSerializer, CompositeSerializer,
+            SizeSerializer,
+        },
+        Serializer,
+    },
+    vec::ArchivedVec,
+    Fallible, SerializeUnsized,
 };
-use crate::{Archive, ArchiveUnsized, RelPtr, Deserialize, Serialize};
+use crate::{Archive, ArchiveUnsized, Deserialize, Infallible, RelPtr, Serialize};
 use core::{
     mem,
     ops::{Deref, DerefMut},
@@ -30,6 +42,9 @@ use core::{
 #[cfg(feature = "alloc")]
 pub use self::aligned_vec::*;
 #[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use self::owned_archive::*;
+#[doc(inline)]
 pub use self::scratch_vec::*;
 
 #[cfg(debug_assertions)]
@@ -297,6 +312,376 @@ where
     Ok(serializer.into_serializer().into_inner())
 }
 
+/// Serializes the given value and returns the resulting bytes with all of the padding inside of
+/// structs and enums zeroed.
+///
+/// This is the same as [`to_bytes`] followed by [`zero_padding`](crate::inspect::zero_padding), and
+/// guarantees that the archive doesn't contain any padding bytes copied from memory, even if some
+/// of the serialized types have custom implementations that write whole structs at once. Use it to
+/// serialize values that contain secrets, or when archives of equal values must be byte-for-byte
+/// equal.
+///
+/// The padding is found by walking the schema of `T` after serializing, so the guarantee only
+/// covers values that can be walked. Opaque fields, which are fields archived with a wrapper and
+/// fields with omitted bounds, are skipped along with everything that they point to.
+///
+/// # Examples
+/// ```
+/// use rkyv::{Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive(schema)]
+/// struct Secret {
+///     id: u8,
+///     key: [u64; 2],
+/// }
+///
+/// let value = Secret { id: 1, key: [2, 3] };
+/// let bytes = rkyv::to_bytes_zero_padded::<_, 256>(&value).unwrap();
+/// let archived = unsafe { rkyv::archived_root::<Secret>(&bytes) };
+/// assert_eq!(archived.key, [2, 3]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_bytes_zero_padded<T, const N: usize>(
+    value: &T,
+) -> Result<AlignedVec, <AllocSerializer<N> as Fallible>::Error>
+where
+    T: Serialize<AllocSerializer<N>> + crate::schema::ArchiveSchema,
+{
+    let mut bytes = to_bytes::<T, N>(value)?;
+    crate::inspect::zero_padding::<T>(&mut bytes);
+    Ok(bytes)
+}
+
+/// Returns the number of bytes that the given value would be serialized to.
+///
+/// This serializes the value without writing the bytes anywhere. The returned size is the length of
+/// the buffer that [`to_bytes`] would return for the same value.
+///
+/// The const generic parameter `N` specifies the number of bytes to pre-allocate as scratch space.
+///
+/// # Examples
+/// ```
+/// let value = vec!["hello".to_string(), "world".to_string()];
+/// let size = rkyv::serialized_size::<_, 256>(&value).unwrap();
+///
+/// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+/// assert_eq!(bytes.len(), size);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn serialized_size<T, const N: usize>(
+    value: &T,
+) -> Result<usize, <SizeSerializer<N> as Fallible>::Error>
+where
+    T: Serialize<SizeSerializer<N>>,
+{
+    let mut serializer = SizeSerializer::<N>::default();
+    serializer.serialize_value(value)?;
+    Ok(serializer.pos())
+}
+
+/// Serializes the given value onto the end of an existing archive and returns the position of the
+/// new root.
+///
+/// The bytes that were already in the archive are not modified, so any positions previously
+/// returned for the archive remain valid. The new value can be accessed with
+/// [`archived_value`] at the returned position, or with [`archived_root`] until another value is
+/// appended. Shared pointers in the new value are not deduplicated with those in earlier values.
+///
+/// If serialization fails, the archive is truncated back to its original length.
+///
+/// The const generic parameter `N` specifies the number of bytes to pre-allocate as scratch space.
+///
+/// # Examples
+/// ```
+/// use rkyv::{archived_root, archived_value, append_to_bytes};
+///
+/// let mut bytes = rkyv::to_bytes::<_, 256>(&"first".to_string()).unwrap();
+/// let first_pos = bytes.len() - core::mem::size_of::<rkyv::Archived<String>>();
+/// let second_pos = append_to_bytes::<_, 256>(&mut bytes, &"second".to_string()).unwrap();
+///
+/// unsafe {
+///     assert_eq!(archived_value::<String>(&bytes, first_pos), "first");
+///     assert_eq!(archived_value::<String>(&bytes, second_pos), "second");
+///     assert_eq!(archived_root::<String>(&bytes), "second");
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn append_to_bytes<T, const N: usize>(
+    bytes: &mut AlignedVec,
+    value: &T,
+) -> Result<usize, <AllocSerializer<N> as Fallible>::Error>
+where
+    T: for<'a> Serialize<AppendSerializer<'a, N>>,
+{
+    let len = bytes.len();
+    let mut serializer = CompositeSerializer::new(
+        AlignedSerializer::new(&mut *bytes),
+        Default::default(),
+        Default::default(),
+    );
+    let result = serializer.serialize_value(value);
+    if result.is_err() {
+        bytes.resize(len, 0);
+    }
+    result
+}
+
+// Appends data to an archive and resolves the value selected from the archive in place, or
+// truncates the archive back to its original length if serializing the data fails
+#[cfg(feature = "alloc")]
+fn patch_in_place<T, R, E, const N: usize>(
+    bytes: &mut AlignedVec,
+    select: impl FnOnce(Pin<&mut [u8]>) -> Pin<&mut T>,
+    serialize: impl FnOnce(&mut AppendSerializer<'_, N>) -> Result<R, E>,
+    resolve: impl FnOnce(usize, R, *mut T),
+) -> Result<(), E> {
+    let len = bytes.len();
+    let start = bytes.as_ptr() as usize;
+    let target = select(Pin::new(bytes.as_mut_slice()));
+    let pos = (&*target as *const T as usize).wrapping_sub(start);
+    assert!(
+        pos <= len && mem::size_of::<T>() <= len - pos,
+        "the value to patch is not located in the archive"
+    );
+
+    let mut serializer = CompositeSerializer::new(
+        AlignedSerializer::new(&mut *bytes),
+        Default::default(),
+        Default::default(),
+    );
+    match serialize(&mut serializer) {
+        Ok(resolver) => {
+            // Appending may have moved the archive, so the pointer is recomputed from the position
+            resolve(pos, resolver, unsafe { bytes.as_mut_ptr().add(pos).cast() });
+            Ok(())
+        }
+        Err(e) => {
+            bytes.resize(len, 0);
+            Err(e)
+        }
+    }
+}
+
+/// Serializes a new value for an archived box onto the end of an existing archive and points the
+/// box to it.
+///
+/// `select` is given the pinned bytes of the archive and returns the box to patch, usually by
+/// mutably accessing the root with [`archived_root_mut`] or [`archived_value_mut`]. Only the new
+/// value and the relative pointer of the box are written, so this is much cheaper than serializing
+/// the whole archive again when one part of a large value changes. The value that the box pointed
+/// to before is left in the archive but is no longer reachable.
+///
+/// After patching, the root is no longer at the end of the archive, so it must be accessed with
+/// [`archived_value`] at its original position. The box points forward to the new value, so
+/// patched archives don't pass validation until they're compacted with
+/// [`compact`](crate::compact::compact). Shared pointers in the new value are not deduplicated
+/// with those already in the archive.
+///
+/// If serialization fails, the archive is truncated back to its original length and the box is left
+/// unchanged.
+///
+/// The const generic parameter `N` specifies the number of bytes to pre-allocate as scratch space.
+///
+/// # Panics
+///
+/// Panics if the box returned by `select` is not located in the archive.
+///
+/// # Examples
+/// ```
+/// use rkyv::{archived_root_mut, archived_value, patch_box, Archive, Archived, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Header {
+///     title: String,
+///     version: u32,
+/// }
+///
+/// #[derive(Archive, Serialize)]
+/// struct Document {
+///     header: Box<Header>,
+///     body: Vec<String>,
+/// }
+///
+/// let value = Document {
+///     header: Box::new(Header {
+///         title: "draft".to_string(),
+///         version: 1,
+///     }),
+///     body: vec!["a long body that shouldn't be serialized again".to_string(); 100],
+/// };
+/// let mut bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+/// let root_pos = bytes.len() - core::mem::size_of::<Archived<Document>>();
+///
+/// let header = Header {
+///     title: "final version".to_string(),
+///     version: 2,
+/// };
+/// patch_box::<_, _, 256>(
+///     &mut bytes,
+///     |bytes| unsafe {
+///         archived_root_mut::<Document>(bytes).map_unchecked_mut(|document| &mut document.header)
+///     },
+///     &header,
+/// )
+/// .unwrap();
+///
+/// let archived = unsafe { archived_value::<Document>(&bytes, root_pos) };
+/// assert_eq!(archived.header.title, "final version");
+/// assert_eq!(archived.header.version, 2);
+/// assert_eq!(archived.body.len(), 100);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn patch_box<T, F, const N: usize>(
+    bytes: &mut AlignedVec,
+    select: F,
+    value: &T,
+) -> Result<(), <AllocSerializer<N> as Fallible>::Error>
+where
+    T: for<'a> SerializeUnsized<AppendSerializer<'a, N>> + ?Sized,
+    F: FnOnce(Pin<&mut [u8]>) -> Pin<&mut ArchivedBox<T::Archived>>,
+{
+    patch_in_place(
+        bytes,
+        select,
+        |serializer| ArchivedBox::serialize_from_ref(value, serializer),
+        |pos, resolver, out| unsafe { ArchivedBox::resolve_from_ref(value, pos, resolver, out) },
+    )
+}
+
+/// Serializes new elements for an archived vector onto the end of an existing archive and points
+/// the vector to them.
+///
+/// This is like [`patch_box`], but replaces all of the elements of a vector. The new elements may
+/// have a different length than the old ones.
+///
+/// # Examples
+/// ```
+/// use rkyv::{archived_root_mut, archived_value, patch_vec, Archived};
+///
+/// let mut bytes = rkyv::to_bytes::<_, 256>(&(1u32, vec![1u32, 2, 3])).unwrap();
+/// let root_pos = bytes.len() - core::mem::size_of::<Archived<(u32, Vec<u32>)>>();
+///
+/// patch_vec::<_, _, 256>(
+///     &mut bytes,
+///     |bytes| unsafe {
+///         archived_root_mut::<(u32, Vec<u32>)>(bytes).map_unchecked_mut(|value| &mut value.1)
+///     },
+///     &[4u32, 5, 6, 7],
+/// )
+/// .unwrap();
+///
+/// let archived = unsafe { archived_value::<(u32, Vec<u32>)>(&bytes, root_pos) };
+/// assert_eq!(archived.1.as_slice(), &[4, 5, 6, 7]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn patch_vec<T, F, const N: usize>(
+    bytes: &mut AlignedVec,
+    select: F,
+    values: &[T],
+) -> Result<(), <AllocSerializer<N> as Fallible>::Error>
+where
+    T: for<'a> Serialize<AppendSerializer<'a, N>>,
+    F: FnOnce(Pin<&mut [u8]>) -> Pin<&mut ArchivedVec<T::Archived>>,
+{
+    patch_in_place(
+        bytes,
+        select,
+        |serializer| ArchivedVec::serialize_from_slice(values, serializer),
+        |pos, resolver, out| unsafe { ArchivedVec::resolve_from_slice(values, pos, resolver, out) },
+    )
+}
+
+/// Serializes the given value and writes it to an async writer.
+///
+/// The value is serialized into an [`AsyncWriteSerializer`] and then flushed to the writer. The
+/// writer is returned once all of the bytes have been written.
+///
+/// The const generic parameter `N` specifies the number of bytes to pre-allocate as scratch space.
+///
+/// # Examples
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use rkyv::{archived_root, to_async_writer, AlignedVec};
+///
+/// let written = to_async_writer::<_, _, 256>(&vec![1, 2, 3, 4], Vec::new())
+///     .await
+///     .expect("failed to write archive");
+///
+/// // A `Vec<u8>` isn't guaranteed to be aligned, so copy the archive before accessing it
+/// let mut bytes = AlignedVec::new();
+/// bytes.extend_from_slice(&written);
+/// let archived = unsafe { archived_root::<Vec<i32>>(&bytes) };
+/// assert_eq!(archived.as_slice(), &[1, 2, 3, 4]);
+/// # });
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn to_async_writer<T, W, const N: usize>(
+    value: &T,
+    writer: W,
+) -> Result<W, <AsyncSerializer<W, N> as Fallible>::Error>
+where
+    T: Serialize<AsyncSerializer<W, N>>,
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    // Only the writer is held across the await so that the future can be sent between threads
+    let mut serializer = {
+        let mut serializer = AsyncSerializer::<W, N>::new(
+            AsyncWriteSerializer::new(writer),
+            Default::default(),
+            Default::default(),
+        );
+        serializer.serialize_value(value)?;
+        serializer.into_serializer()
+    };
+    serializer
+        .flush()
+        .await
+        .map_err(CompositeSerializerError::SerializerError)?;
+    Ok(serializer.into_inner())
+}
+
+/// Deserializes an archived value with the [`Infallible`] deserializer.
+///
+/// This is a convenience for the common case where deserialization can't fail, so the result
+/// doesn't need to be unwrapped. Types that need additional deserializer capabilities, like `Rc`
+/// and `Arc`, can't be deserialized this way.
+///
+/// # Examples
+/// ```
+/// use rkyv::{archived_root, Archive, Deserialize, Serialize};
+///
+/// #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+/// struct Example {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     value: 31415926,
+/// };
+///
+/// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+/// let archived = unsafe { archived_root::<Example>(&bytes) };
+/// let deserialized: Example = rkyv::deserialize(archived);
+/// assert_eq!(deserialized, value);
+/// ```
+#[inline]
+pub fn deserialize<T>(archived: &T::Archived) -> T
+where
+    T: Archive,
+    T::Archived: Deserialize<T, Infallible>,
+{
+    match archived.deserialize(&mut Infallible) {
+        Ok(value) => value,
+        Err(e) => match e {},
+    }
+}
+
 /// Deserializes a value from the given bytes.
 ///
 /// This function is only available with the `alloc` feature because it uses a general-purpose
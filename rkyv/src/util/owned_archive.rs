@@ -0,0 +1,108 @@
+use crate::{
+    archived_root, archived_root_mut,
+    ser::{serializers::AllocSerializer, Serializer},
+    AlignedVec, Archive, Fallible, Serialize,
+};
+use core::{fmt, marker::PhantomData, ops::Deref, pin::Pin};
+
+/// An owned archive that dereferences to its archived root.
+///
+/// An `OwnedArchive` can only be created by serializing a value with [`to_archive`], so its bytes
+/// are always a valid archive of a `T`. This makes it safe to access and mutate the archived root
+/// without validating it first.
+///
+/// # Examples
+/// ```
+/// let value = vec![1i32, 2, 3];
+///
+/// let mut archive = rkyv::to_archive::<_, 256>(&value).unwrap();
+/// assert_eq!(archive.as_slice(), &[1, 2, 3]);
+///
+/// archive.pin_mut().get_pin(1).unwrap().set(4);
+/// assert_eq!(archive.as_slice(), &[1, 4, 3]);
+///
+/// let bytes = archive.into_bytes();
+/// ```
+pub struct OwnedArchive<T> {
+    bytes: AlignedVec,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Archive> OwnedArchive<T> {
+    /// Returns the bytes of the archive.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Consumes the archive and returns its bytes.
+    #[inline]
+    pub fn into_bytes(self) -> AlignedVec {
+        self.bytes
+    }
+
+    /// Returns a pinned mutable reference to the archived root.
+    #[inline]
+    pub fn pin_mut(&mut self) -> Pin<&mut T::Archived> {
+        // SAFETY: The bytes were serialized from a `T` with the root at the end.
+        unsafe { archived_root_mut::<T>(Pin::new(self.bytes.as_mut_slice())) }
+    }
+}
+
+impl<T: Archive> Deref for OwnedArchive<T> {
+    type Target = T::Archived;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The bytes were serialized from a `T` with the root at the end.
+        unsafe { archived_root::<T>(self.bytes.as_slice()) }
+    }
+}
+
+impl<T> Clone for OwnedArchive<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for OwnedArchive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedArchive")
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+/// Serializes the given value and returns an [`OwnedArchive`] of it.
+///
+/// This is like [`to_bytes`](crate::to_bytes), but the returned archive remembers the type that was
+/// serialized so that it can be accessed without any `unsafe` code.
+///
+/// The const generic parameter `N` specifies the number of bytes to pre-allocate as scratch space.
+///
+/// # Examples
+/// ```
+/// let value = vec!["hello".to_string(), "world".to_string()];
+///
+/// let archive = rkyv::to_archive::<_, 256>(&value).unwrap();
+/// assert_eq!(archive[0], "hello");
+/// assert_eq!(archive.len(), 2);
+/// ```
+#[inline]
+pub fn to_archive<T, const N: usize>(
+    value: &T,
+) -> Result<OwnedArchive<T>, <AllocSerializer<N> as Fallible>::Error>
+where
+    T: Serialize<AllocSerializer<N>>,
+{
+    let mut serializer = AllocSerializer::<N>::default();
+    serializer.serialize_value(value)?;
+    Ok(OwnedArchive {
+        bytes: serializer.into_serializer().into_inner(),
+        _phantom: PhantomData,
+    })
+}
@@ -4,49 +4,85 @@ pub mod owned;
 pub mod validators;
 
 use crate::{Archive, ArchivePointee, Fallible, RelPtr};
-use bytecheck::CheckBytes;
-use core::{alloc::Layout, any::TypeId, fmt};
+use bytecheck::{CheckBytes, EnumCheckError, StructCheckError, TupleStructCheckError};
+use core::{
+    alloc::{Layout, LayoutError},
+    any::{Any, TypeId},
+    fmt,
+};
 use ptr_meta::Pointee;
 #[cfg(feature = "std")]
 use std::error::Error;
+use validators::ValidationLimits;
 
 // Replace this trait with core::mem::{align_of_val_raw, size_of_val_raw} when they get stabilized.
 
 /// Gets the layout of a type from its pointer.
 pub trait LayoutRaw {
     /// Gets the layout of the type.
-    fn layout_raw(value: *const Self) -> Layout;
+    ///
+    /// Returns an error if the layout can't be computed, like when the length of a collection is
+    /// too large for the collection to fit in memory.
+    fn layout_raw(value: *const Self) -> Result<Layout, LayoutError>;
+
+    /// Gets the number of elements of the type if it's a collection.
+    ///
+    /// Subtree claims check this against the collection length limit of the validator.
+    #[inline]
+    fn collection_len(_: *const Self) -> Option<usize> {
+        None
+    }
+
+    /// Gets the length of the type in bytes if it's a string.
+    ///
+    /// Subtree claims check this against the string length limit of the validator.
+    #[inline]
+    fn string_len(_: *const Self) -> Option<usize> {
+        None
+    }
 }
 
 impl<T> LayoutRaw for T {
     #[inline]
-    fn layout_raw(_: *const Self) -> Layout {
-        Layout::new::<T>()
+    fn layout_raw(_: *const Self) -> Result<Layout, LayoutError> {
+        Ok(Layout::new::<T>())
     }
 }
 
 impl<T> LayoutRaw for [T] {
     #[inline]
-    fn layout_raw(value: *const Self) -> Layout {
-        let metadata = ptr_meta::metadata(value);
-        Layout::array::<T>(metadata).unwrap()
+    fn layout_raw(value: *const Self) -> Result<Layout, LayoutError> {
+        Layout::array::<T>(ptr_meta::metadata(value))
+    }
+
+    #[inline]
+    fn collection_len(value: *const Self) -> Option<usize> {
+        Some(ptr_meta::metadata(value))
     }
 }
 
 impl LayoutRaw for str {
     #[inline]
-    fn layout_raw(value: *const Self) -> Layout {
-        let metadata = ptr_meta::metadata(value);
-        Layout::array::<u8>(metadata).unwrap()
+    fn layout_raw(value: *const Self) -> Result<Layout, LayoutError> {
+        Layout::array::<u8>(ptr_meta::metadata(value))
+    }
+
+    #[inline]
+    fn string_len(value: *const Self) -> Option<usize> {
+        Some(ptr_meta::metadata(value))
     }
 }
 
 #[cfg(feature = "std")]
 impl LayoutRaw for ::std::ffi::CStr {
     #[inline]
-    fn layout_raw(value: *const Self) -> Layout {
-        let metadata = ptr_meta::metadata(value);
-        Layout::array::<::std::os::raw::c_char>(metadata).unwrap()
+    fn layout_raw(value: *const Self) -> Result<Layout, LayoutError> {
+        Layout::array::<::std::os::raw::c_char>(ptr_meta::metadata(value))
+    }
+
+    #[inline]
+    fn string_len(value: *const Self) -> Option<usize> {
+        Some(ptr_meta::metadata(value))
     }
 }
 
@@ -114,7 +150,7 @@ pub trait ArchiveContext: Fallible {
     ) -> Result<*const T, Self::Error> {
         let data_address = self.bounds_check_ptr(base, offset)?;
         let ptr = ptr_meta::from_raw_parts(data_address.cast(), metadata);
-        let layout = T::layout_raw(ptr);
+        let layout = T::layout_raw(ptr).map_err(|e| self.wrap_layout_error(e))?;
         self.bounds_check_layout(data_address, &layout)?;
         Ok(ptr)
     }
@@ -160,7 +196,7 @@ pub trait ArchiveContext: Fallible {
         &mut self,
         ptr: *const T,
     ) -> Result<(), Self::Error> {
-        let layout = T::layout_raw(ptr);
+        let layout = T::layout_raw(ptr).map_err(|e| self.wrap_layout_error(e))?;
         self.bounds_check_subtree_ptr_layout(ptr.cast(), &layout)
     }
 
@@ -215,6 +251,7 @@ pub trait ArchiveContext: Fallible {
     /// Pushes a new subtree range onto the validator and starts validating it.
     ///
     /// The claimed range spans from the end of `start` to the end of the current subobject range.
+    /// Collections and strings are also checked against the length limits of the validator.
     ///
     /// # Safety
     ///
@@ -224,7 +261,13 @@ pub trait ArchiveContext: Fallible {
         &mut self,
         root: *const T,
     ) -> Result<Self::PrefixRange, Self::Error> {
-        let layout = T::layout_raw(root);
+        if let Some(len) = T::collection_len(root) {
+            self.check_collection_len(len)?;
+        }
+        if let Some(len) = T::string_len(root) {
+            self.check_string_len(len)?;
+        }
+        let layout = T::layout_raw(root).map_err(|e| self.wrap_layout_error(e))?;
         self.push_prefix_subtree_range(root as *const u8, (root as *const u8).add(layout.size()))
     }
 
@@ -253,10 +296,189 @@ pub trait ArchiveContext: Fallible {
     /// If the range was not popped in reverse order, an error is returned.
     fn pop_suffix_range(&mut self, range: Self::SuffixRange) -> Result<(), Self::Error>;
 
+    /// Converts an error from computing the layout of a pointer into an error of this context.
+    ///
+    /// Pointers to collections and strings get their layouts from lengths stored in the archive,
+    /// which may be too large for a layout.
+    fn wrap_layout_error(&mut self, error: LayoutError) -> Self::Error;
+
+    /// Checks that a collection with the given number of elements may be validated.
+    ///
+    /// Collections call this with their length before checking their elements, so validators can
+    /// reject archives that claim unreasonably large collections. The default implementation
+    /// accepts any length.
+    #[inline]
+    fn check_collection_len(&mut self, _len: usize) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Checks that a string with the given length in bytes may be validated.
+    ///
+    /// Strings call this with their length before checking their contents. The default
+    /// implementation accepts any length.
+    #[inline]
+    fn check_string_len(&mut self, _len: usize) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Returns the limits that archives nested inside of the one being checked must be checked
+    /// with.
+    ///
+    /// Nested archives are checked with validators of their own, which may only go as deep as
+    /// what's left of the subtree depth of this context. The bytes of a nested archive are already
+    /// counted toward the size limit of this context, and it can't check more objects than fit in
+    /// them. The default implementation doesn't limit nested archives.
+    #[inline]
+    fn nested_limits(&self) -> ValidationLimits {
+        ValidationLimits::unlimited()
+    }
+
+    /// Records a segment of the path to a value that failed to check.
+    ///
+    /// Because errors propagate outward, segments are recorded from the invalid value to the root
+    /// of the archive. `pos` is the location of the value that the segment leads to, if it's
+    /// known. The default implementation does nothing.
+    #[inline]
+    fn record_error_segment(&mut self, _segment: PathSegment, _pos: Option<*const u8>) {}
+
+    /// Records a description of what was wrong with a value that failed to check.
+    ///
+    /// This is called with the innermost error that could be found each time a path is recorded,
+    /// so only the first message describes the invalid value itself. The default implementation
+    /// does nothing.
+    #[inline]
+    fn record_error_message(&mut self, _message: &dyn fmt::Display) {}
+
+    /// Records the path to a value that failed to check with the given error.
+    ///
+    /// Types call this when checking one of their parts fails, with the segment that leads to the
+    /// part and its location. The fields and variants that the error failed in are recorded before
+    /// `segment`. Only the outermost derived error can be inspected without the `std` feature, so
+    /// the fields of nested derived errors are only recorded with it.
+    #[inline]
+    fn record_check_error<E: bytecheck::Error>(
+        &mut self,
+        error: &E,
+        segment: PathSegment,
+        pos: *const u8,
+    ) {
+        record_error_path(self, error);
+        self.record_error_segment(segment, Some(pos));
+    }
+
     /// Verifies that all outstanding claims have been returned.
     fn finish(&mut self) -> Result<(), Self::Error>;
 }
 
+/// A segment of the path from the root of an archive to a value inside of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A field of a struct or struct variant
+    Field(&'static str),
+    /// A field of a tuple struct or tuple variant
+    TupleField(usize),
+    /// A variant of an enum
+    Variant(&'static str),
+    /// An element of a collection
+    Index(usize),
+    /// The key of a map entry
+    Key,
+    /// The value of a map entry
+    Value,
+    /// The value that a pointer points to
+    Deref,
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, ".{}", name),
+            PathSegment::TupleField(index) => write!(f, ".{}", index),
+            PathSegment::Variant(name) => write!(f, "::{}", name),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+            PathSegment::Key => write!(f, ".key"),
+            PathSegment::Value => write!(f, ".value"),
+            PathSegment::Deref => Ok(()),
+        }
+    }
+}
+
+// The errors of derived `CheckBytes` implementations are type-erased after the outermost one. With
+// the `std` feature, they can be downcast again through `std::error::Error`.
+trait DowncastError: fmt::Display {
+    fn downcast<T: bytecheck::Error>(&self) -> Option<&T>;
+}
+
+impl<E: fmt::Display + 'static> DowncastError for E {
+    #[inline]
+    fn downcast<T: bytecheck::Error>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+impl DowncastError for dyn Error {
+    #[inline]
+    fn downcast<T: bytecheck::Error>(&self) -> Option<&T> {
+        self.downcast_ref()
+    }
+}
+
+fn record_error_path<C, E>(context: &mut C, error: &E)
+where
+    C: ArchiveContext + ?Sized,
+    E: DowncastError + ?Sized,
+{
+    macro_rules! record_enum_error_path {
+        ($($tag:ty),*) => {
+            $(if let Some(error) = error.downcast::<EnumCheckError<$tag>>() {
+                match error {
+                    EnumCheckError::InvalidStruct {
+                        variant_name,
+                        inner,
+                    } => {
+                        record_error_path(context, inner);
+                        context.record_error_segment(PathSegment::Variant(*variant_name), None);
+                    }
+                    EnumCheckError::InvalidTuple {
+                        variant_name,
+                        inner,
+                    } => {
+                        record_error_path(context, inner);
+                        context.record_error_segment(PathSegment::Variant(*variant_name), None);
+                    }
+                    EnumCheckError::InvalidTag(_) => {
+                        context.record_error_message(&format_args!("{}", error));
+                    }
+                }
+                return;
+            })*
+        };
+    }
+
+    if let Some(error) = error.downcast::<StructCheckError>() {
+        record_inner_error_path(context, &*error.inner);
+        context.record_error_segment(PathSegment::Field(error.field_name), None);
+    } else if let Some(error) = error.downcast::<TupleStructCheckError>() {
+        record_inner_error_path(context, &*error.inner);
+        context.record_error_segment(PathSegment::TupleField(error.field_index), None);
+    } else {
+        record_enum_error_path!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+        context.record_error_message(&format_args!("{}", error));
+    }
+}
+
+#[inline]
+fn record_inner_error_path<C>(context: &mut C, error: &dyn bytecheck::Error)
+where
+    C: ArchiveContext + ?Sized,
+{
+    #[cfg(feature = "std")]
+    record_error_path(context, error.as_error());
+    #[cfg(not(feature = "std"))]
+    context.record_error_message(&format_args!("{}", error));
+}
+
 /// A context that can validate shared archive memory.
 ///
 /// Shared pointers require this kind of context to validate.
@@ -314,14 +536,16 @@ where
 {
     unsafe {
         let ptr = context
-            .check_subtree_ptr(buf.as_ptr(), pos, ())
+            .check_subtree_ptr::<T::Archived>(buf.as_ptr(), pos, ())
             .map_err(CheckArchiveError::ContextError)?;
 
         let range = context
             .push_prefix_subtree(ptr)
             .map_err(CheckArchiveError::ContextError)?;
-        let result =
-            CheckBytes::check_bytes(ptr, context).map_err(CheckArchiveError::CheckBytesError)?;
+        let result = CheckBytes::check_bytes(ptr, context).map_err(|e| {
+            context.record_check_error(&e, PathSegment::Deref, ptr.cast());
+            CheckArchiveError::CheckBytesError(e)
+        })?;
         context
             .pop_prefix_range(range)
             .map_err(CheckArchiveError::ContextError)?;
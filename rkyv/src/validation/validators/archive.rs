@@ -1,7 +1,15 @@
 //! The provided implementation for `ArchiveContext`.
 
-use crate::{validation::ArchiveContext, Fallible};
-use core::{alloc::Layout, fmt, ops::Range};
+use crate::{
+    rel_ptr::{checked_target, TargetError},
+    validation::{validators::ErrorPath, ArchiveContext, PathSegment},
+    Fallible,
+};
+use core::{
+    alloc::{Layout, LayoutError},
+    fmt,
+    ops::Range,
+};
 
 /// Errors that can occur when checking archive memory.
 #[derive(Debug)]
@@ -80,6 +88,30 @@ pub enum ArchiveError {
         /// The maximum depth that subtrees may be validated down to
         max_subtree_depth: usize,
     },
+    /// The total size of the checked objects exceeded the maximum.
+    ExceededMaximumBytes {
+        /// The maximum number of bytes that may be checked
+        max_bytes: usize,
+    },
+    /// A collection had more elements than the maximum.
+    ExceededMaximumCollectionLength {
+        /// The number of elements in the collection
+        len: usize,
+        /// The maximum number of elements that collections may have
+        max_collection_len: usize,
+    },
+    /// A string was longer than the maximum.
+    ExceededMaximumStringLength {
+        /// The length of the string in bytes
+        len: usize,
+        /// The maximum length in bytes that strings may have
+        max_string_len: usize,
+    },
+    /// The layout of a pointed-to value couldn't be computed.
+    LayoutError {
+        /// The error returned when computing the layout
+        layout_error: LayoutError,
+    },
 }
 
 // SAFETY: ArchiveError is safe to send to another thread
@@ -155,6 +187,30 @@ impl fmt::Display for ArchiveError {
                 "pushed a subtree range that exceeded the maximum subtree depth of {}",
                 max_subtree_depth
             ),
+            ArchiveError::ExceededMaximumBytes { max_bytes } => write!(
+                f,
+                "checked objects exceeded the maximum total size of {} bytes",
+                max_bytes
+            ),
+            ArchiveError::ExceededMaximumCollectionLength {
+                len,
+                max_collection_len,
+            } => write!(
+                f,
+                "collection length {} exceeded the maximum collection length of {}",
+                len, max_collection_len
+            ),
+            ArchiveError::ExceededMaximumStringLength {
+                len,
+                max_string_len,
+            } => write!(
+                f,
+                "string length {} exceeded the maximum string length of {}",
+                len, max_string_len
+            ),
+            ArchiveError::LayoutError { layout_error } => {
+                write!(f, "failed to compute layout: {}", layout_error)
+            }
         }
     }
 }
@@ -192,13 +248,69 @@ unsafe impl<'a> Send for SuffixRange {}
 // This trait is not automatically implemented because the struct contains a pointer
 unsafe impl Sync for SuffixRange {}
 
+/// Limits on the resources that an [`ArchiveValidator`] may use to check an archive.
+///
+/// Every limit is unbounded by default. Limits can be set by overriding the defaults:
+///
+/// ```
+/// use rkyv::validation::validators::ValidationLimits;
+///
+/// let limits = ValidationLimits {
+///     max_collection_len: 1024,
+///     max_string_len: 256,
+///     ..ValidationLimits::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationLimits {
+    /// The maximum number of subtree ranges that may be pushed at once.
+    pub max_subtree_depth: usize,
+    /// The maximum total size in bytes of the objects that may be checked.
+    pub max_bytes: usize,
+    /// The maximum number of elements that each collection may have.
+    pub max_collection_len: usize,
+    /// The maximum length in bytes that each string may have.
+    pub max_string_len: usize,
+}
+
+impl ValidationLimits {
+    /// Returns validation limits that don't limit anything.
+    #[inline]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_subtree_depth: usize::MAX,
+            max_bytes: usize::MAX,
+            max_collection_len: usize::MAX,
+            max_string_len: usize::MAX,
+        }
+    }
+}
+
+impl Default for ValidationLimits {
+    #[inline]
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
 /// A validator that can verify archives with nonlocal memory.
+///
+/// Every pointer that is checked must point into the current subtree range, and the subtree range
+/// shrinks each time a pointer is followed. This prevents owned pointers from forming cycles or
+/// overlapping, so checking always terminates. Each subtree range that is pushed also increases the
+/// subtree depth, which can be limited with [`with_max_depth`](ArchiveValidator::with_max_depth) to
+/// bound the amount of stack used to check deeply nested archives. The other
+/// [`ValidationLimits`] bound the amount of work done to check an archive.
 #[derive(Debug)]
 pub struct ArchiveValidator<'a> {
     bytes: &'a [u8],
     subtree_range: Range<*const u8>,
     subtree_depth: usize,
-    max_subtree_depth: usize,
+    checked_bytes: usize,
+    // The lowest pointer that was checked or claimed in a subtree range
+    min_subtree_ptr: *const u8,
+    limits: ValidationLimits,
+    error_path: ErrorPath,
 }
 
 // SAFETY: ArchiveValidator is safe to send to another thread
@@ -211,19 +323,43 @@ unsafe impl<'a> Sync for ArchiveValidator<'a> {}
 
 impl<'a> ArchiveValidator<'a> {
     /// Creates a new bounds validator for the given bytes.
+    ///
+    /// The subtree depth is not limited, so checking an untrusted archive with deeply nested data
+    /// may overflow the stack. Use [`with_max_depth`](ArchiveValidator::with_max_depth) to limit
+    /// it.
     #[inline]
     pub fn new(bytes: &'a [u8]) -> Self {
-        Self::with_max_depth(bytes, usize::MAX)
+        Self::with_limits(bytes, ValidationLimits::unlimited())
     }
 
     /// Crates a new bounds validator for the given bytes with a maximum validation depth.
+    ///
+    /// Checking fails with [`ExceededMaximumSubtreeDepth`](ArchiveError::ExceededMaximumSubtreeDepth)
+    /// if more than `max_subtree_depth` subtree ranges are pushed at once.
     #[inline]
     pub fn with_max_depth(bytes: &'a [u8], max_subtree_depth: usize) -> Self {
+        Self::with_limits(
+            bytes,
+            ValidationLimits {
+                max_subtree_depth,
+                ..ValidationLimits::unlimited()
+            },
+        )
+    }
+
+    /// Creates a new bounds validator for the given bytes with the given limits.
+    ///
+    /// Checking fails with an error if any of the limits are exceeded.
+    #[inline]
+    pub fn with_limits(bytes: &'a [u8], limits: ValidationLimits) -> Self {
         Self {
             bytes,
             subtree_range: bytes.as_ptr_range(),
             subtree_depth: 0,
-            max_subtree_depth,
+            checked_bytes: 0,
+            min_subtree_ptr: bytes.as_ptr_range().end,
+            limits,
+            error_path: ErrorPath::default(),
         }
     }
 
@@ -241,6 +377,49 @@ impl<'a> ArchiveValidator<'a> {
     pub fn alignment(&self) -> usize {
         1 << self.log_alignment()
     }
+
+    /// Returns the maximum subtree depth of the validator.
+    #[inline]
+    pub fn max_subtree_depth(&self) -> usize {
+        self.limits.max_subtree_depth
+    }
+
+    /// Returns the limits of the validator.
+    #[inline]
+    pub fn limits(&self) -> &ValidationLimits {
+        &self.limits
+    }
+
+    /// Returns the path to the value that failed to check, if checking failed.
+    #[inline]
+    pub fn error_path(&self) -> &ErrorPath {
+        &self.error_path
+    }
+
+    /// Returns the lowest pointer that was checked or claimed in a subtree range.
+    #[inline]
+    pub(crate) fn min_subtree_ptr(&self) -> *const u8 {
+        self.min_subtree_ptr
+    }
+
+    /// Returns the current subtree range.
+    #[inline]
+    pub(crate) fn subtree_range(&self) -> Range<*const u8> {
+        self.subtree_range.clone()
+    }
+
+    /// Returns the total size of the objects that have been checked.
+    #[inline]
+    pub(crate) fn checked_bytes(&self) -> usize {
+        self.checked_bytes
+    }
+
+    #[inline]
+    fn note_subtree_ptr(&mut self, ptr: *const u8) {
+        if ptr < self.min_subtree_ptr {
+            self.min_subtree_ptr = ptr;
+        }
+    }
 }
 
 impl<'a> Fallible for ArchiveValidator<'a> {
@@ -257,18 +436,15 @@ impl<'a> ArchiveContext for ArchiveValidator<'a> {
         base: *const u8,
         offset: isize,
     ) -> Result<*const u8, Self::Error> {
-        let base_pos = base.offset_from(self.bytes.as_ptr());
-        let target_pos = base_pos
-            .checked_add(offset)
-            .ok_or(ArchiveError::Overflow { base, offset })?;
-        if target_pos < 0 || target_pos as usize > self.bytes.len() {
-            Err(ArchiveError::OutOfBounds {
+        let base_pos = (base as usize).wrapping_sub(self.bytes.as_ptr() as usize);
+        match checked_target(base_pos, offset, self.bytes.len()) {
+            Ok(target_pos) => Ok(self.bytes.as_ptr().add(target_pos)),
+            Err(TargetError::Overflow { .. }) => Err(ArchiveError::Overflow { base, offset }),
+            Err(_) => Err(ArchiveError::OutOfBounds {
                 base,
                 offset,
                 range: self.bytes.as_ptr_range(),
-            })
-        } else {
-            Ok(base.offset(offset))
+            }),
         }
     }
 
@@ -315,6 +491,7 @@ impl<'a> ArchiveContext for ArchiveValidator<'a> {
                     subtree_range: self.subtree_range.clone(),
                 })
             } else {
+                self.note_subtree_ptr(data_address);
                 Ok(())
             }
         } else if !self.subtree_range.contains(&data_address) {
@@ -330,7 +507,15 @@ impl<'a> ArchiveContext for ArchiveValidator<'a> {
                     size: layout.size(),
                     subtree_range: self.subtree_range.clone(),
                 })
+            } else if layout.size() > self.limits.max_bytes - self.checked_bytes {
+                Err(ArchiveError::ExceededMaximumBytes {
+                    max_bytes: self.limits.max_bytes,
+                })
             } else {
+                // Subtree ranges shrink past the objects that are checked in them, so every object
+                // is counted once
+                self.checked_bytes += layout.size();
+                self.note_subtree_ptr(data_address);
                 Ok(())
             }
         }
@@ -342,9 +527,9 @@ impl<'a> ArchiveContext for ArchiveValidator<'a> {
         root: *const u8,
         end: *const u8,
     ) -> Result<PrefixRange, Self::Error> {
-        if self.subtree_depth >= self.max_subtree_depth {
+        if self.subtree_depth >= self.limits.max_subtree_depth {
             Err(ArchiveError::ExceededMaximumSubtreeDepth {
-                max_subtree_depth: self.max_subtree_depth,
+                max_subtree_depth: self.limits.max_subtree_depth,
             })
         } else {
             let result = PrefixRange {
@@ -356,6 +541,7 @@ impl<'a> ArchiveContext for ArchiveValidator<'a> {
             };
             self.subtree_depth += 1;
             self.subtree_range.end = root;
+            self.note_subtree_ptr(root);
             Ok(result)
         }
     }
@@ -380,14 +566,21 @@ impl<'a> ArchiveContext for ArchiveValidator<'a> {
         start: *const u8,
         root: *const u8,
     ) -> Result<SuffixRange, Self::Error> {
-        let result = SuffixRange {
-            start: self.subtree_range.start,
-            depth: self.subtree_depth,
-        };
-        self.subtree_depth += 1;
-        self.subtree_range.start = start;
-        self.subtree_range.end = root;
-        Ok(result)
+        if self.subtree_depth >= self.limits.max_subtree_depth {
+            Err(ArchiveError::ExceededMaximumSubtreeDepth {
+                max_subtree_depth: self.limits.max_subtree_depth,
+            })
+        } else {
+            let result = SuffixRange {
+                start: self.subtree_range.start,
+                depth: self.subtree_depth,
+            };
+            self.subtree_depth += 1;
+            self.subtree_range.start = start;
+            self.subtree_range.end = root;
+            self.note_subtree_ptr(start);
+            Ok(result)
+        }
     }
 
     #[inline]
@@ -405,6 +598,56 @@ impl<'a> ArchiveContext for ArchiveValidator<'a> {
         }
     }
 
+    #[inline]
+    fn wrap_layout_error(&mut self, error: LayoutError) -> Self::Error {
+        ArchiveError::LayoutError {
+            layout_error: error,
+        }
+    }
+
+    #[inline]
+    fn check_collection_len(&mut self, len: usize) -> Result<(), Self::Error> {
+        if len > self.limits.max_collection_len {
+            Err(ArchiveError::ExceededMaximumCollectionLength {
+                len,
+                max_collection_len: self.limits.max_collection_len,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn check_string_len(&mut self, len: usize) -> Result<(), Self::Error> {
+        if len > self.limits.max_string_len {
+            Err(ArchiveError::ExceededMaximumStringLength {
+                len,
+                max_string_len: self.limits.max_string_len,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn nested_limits(&self) -> ValidationLimits {
+        ValidationLimits {
+            max_subtree_depth: self.limits.max_subtree_depth - self.subtree_depth,
+            ..self.limits
+        }
+    }
+
+    #[inline]
+    fn record_error_segment(&mut self, segment: PathSegment, pos: Option<*const u8>) {
+        let pos = pos.map(|pos| pos as usize - self.bytes.as_ptr() as usize);
+        self.error_path.record_segment(segment, pos);
+    }
+
+    #[inline]
+    fn record_error_message(&mut self, message: &dyn fmt::Display) {
+        self.error_path.record_message(message);
+    }
+
     #[inline]
     fn finish(&mut self) -> Result<(), Self::Error> {
         if self.subtree_depth != 0 {
@@ -1,19 +1,29 @@
 //! Validators that can check archived types.
 
 mod archive;
+#[cfg(feature = "std")]
+mod parallel;
+mod report;
 mod shared;
 mod util;
 
 use crate::{
     validation::{
         check_archived_root_with_context, check_archived_value_with_context, ArchiveContext,
-        CheckTypeError, SharedContext,
+        CheckTypeError, PathSegment, SharedContext,
     },
     Archive, Fallible,
 };
 pub use archive::*;
 use bytecheck::CheckBytes;
-use core::{alloc::Layout, any::TypeId, fmt};
+use core::{
+    alloc::{Layout, LayoutError},
+    any::{type_name, TypeId},
+    fmt,
+};
+#[cfg(feature = "std")]
+pub use parallel::*;
+pub use report::*;
 pub use shared::*;
 pub use util::*;
 
@@ -50,6 +60,9 @@ const _: () = {
 };
 
 /// The default validator.
+///
+/// This combines an [`ArchiveValidator`], which prevents owned pointers from forming cycles, with a
+/// [`SharedValidator`], which checks each shared pointer only the first time it's encountered.
 #[derive(Debug)]
 pub struct DefaultValidator<'a> {
     archive: ArchiveValidator<'a>,
@@ -58,6 +71,10 @@ pub struct DefaultValidator<'a> {
 
 impl<'a> DefaultValidator<'a> {
     /// Creates a new validator from a byte range.
+    ///
+    /// The subtree depth is not limited, so checking an untrusted archive with deeply nested data
+    /// may overflow the stack. Use [`with_max_depth`](DefaultValidator::with_max_depth) to limit
+    /// it.
     #[inline]
     pub fn new(bytes: &'a [u8]) -> Self {
         Self {
@@ -65,6 +82,36 @@ impl<'a> DefaultValidator<'a> {
             shared: SharedValidator::new(),
         }
     }
+
+    /// Creates a new validator from a byte range with a maximum subtree depth.
+    ///
+    /// See [`ArchiveValidator::with_max_depth`] for more details.
+    #[inline]
+    pub fn with_max_depth(bytes: &'a [u8], max_subtree_depth: usize) -> Self {
+        Self {
+            archive: ArchiveValidator::with_max_depth(bytes, max_subtree_depth),
+            shared: SharedValidator::new(),
+        }
+    }
+
+    /// Creates a new validator from a byte range with the given limits.
+    ///
+    /// See [`ArchiveValidator::with_limits`] for more details.
+    #[inline]
+    pub fn with_limits(bytes: &'a [u8], limits: ValidationLimits) -> Self {
+        Self {
+            archive: ArchiveValidator::with_limits(bytes, limits),
+            shared: SharedValidator::new(),
+        }
+    }
+
+    /// Returns the path to the value that failed to check, if checking failed.
+    ///
+    /// See [`ArchiveValidator::error_path`] for more details.
+    #[inline]
+    pub fn error_path(&self) -> &ErrorPath {
+        self.archive.error_path()
+    }
 }
 
 impl<'a> Fallible for DefaultValidator<'a> {
@@ -144,6 +191,40 @@ impl<'a> ArchiveContext for DefaultValidator<'a> {
             .map_err(DefaultValidatorError::ArchiveError)
     }
 
+    #[inline]
+    fn wrap_layout_error(&mut self, error: LayoutError) -> Self::Error {
+        DefaultValidatorError::ArchiveError(self.archive.wrap_layout_error(error))
+    }
+
+    #[inline]
+    fn check_collection_len(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.archive
+            .check_collection_len(len)
+            .map_err(DefaultValidatorError::ArchiveError)
+    }
+
+    #[inline]
+    fn check_string_len(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.archive
+            .check_string_len(len)
+            .map_err(DefaultValidatorError::ArchiveError)
+    }
+
+    #[inline]
+    fn nested_limits(&self) -> ValidationLimits {
+        self.archive.nested_limits()
+    }
+
+    #[inline]
+    fn record_error_segment(&mut self, segment: PathSegment, pos: Option<*const u8>) {
+        self.archive.record_error_segment(segment, pos);
+    }
+
+    #[inline]
+    fn record_error_message(&mut self, message: &dyn fmt::Display) {
+        self.archive.record_error_message(message);
+    }
+
     #[inline]
     fn finish(&mut self) -> Result<(), Self::Error> {
         self.archive
@@ -170,6 +251,11 @@ impl<'a> SharedContext for DefaultValidator<'a> {
 /// This is a safe alternative to [`archived_value`](crate::archived_value) for types that implement
 /// `CheckBytes`.
 ///
+/// Only the value at `pos` and the objects it points to are checked, so this can be used to check
+/// the values in archives that have multiple roots, for example at positions kept in an external
+/// index. Each value may only point to objects located before it in the archive, and positions that
+/// are out of bounds or unaligned fail to check instead of panicking.
+///
 /// # Examples
 /// ```
 /// use rkyv::{
@@ -227,3 +313,129 @@ where
     let mut validator = DefaultValidator::new(bytes);
     check_archived_root_with_context::<T, DefaultValidator>(bytes, &mut validator)
 }
+
+/// Checks the given archive for an archived version of the given type, limiting how deeply nested
+/// the archived data may be.
+///
+/// This is like [`check_archived_root`], but fails if the archived data is nested more than
+/// `max_subtree_depth` levels deep. The root of the archive is the first level, and each pointer
+/// that is followed from it adds another. This bounds the amount of stack used when checking
+/// untrusted data.
+///
+/// # Examples
+/// ```
+/// use rkyv::{to_bytes, validation::validators::check_archived_root_with_max_depth};
+///
+/// let value = Some(Box::new(Some(Box::new(1u32))));
+/// let bytes = to_bytes::<_, 256>(&value).unwrap();
+///
+/// assert!(check_archived_root_with_max_depth::<Option<Box<Option<Box<u32>>>>>(&bytes, 3).is_ok());
+/// assert!(check_archived_root_with_max_depth::<Option<Box<Option<Box<u32>>>>>(&bytes, 2).is_err());
+/// ```
+#[inline]
+pub fn check_archived_root_with_max_depth<'a, T: Archive>(
+    bytes: &'a [u8],
+    max_subtree_depth: usize,
+) -> Result<&'a T::Archived, CheckTypeError<T::Archived, DefaultValidator<'a>>>
+where
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    let mut validator = DefaultValidator::with_max_depth(bytes, max_subtree_depth);
+    check_archived_root_with_context::<T, DefaultValidator>(bytes, &mut validator)
+}
+
+/// Checks the given archive for an archived version of the given type, limiting the resources used
+/// to check it.
+///
+/// This is like [`check_archived_root`], but fails if checking the archive would exceed any of the
+/// given [`ValidationLimits`]. This prevents small malicious archives from claiming huge
+/// collections and stalling validation.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     to_bytes,
+///     validation::validators::{check_archived_root_with_limits, ValidationLimits},
+/// };
+///
+/// let value = vec![(); 1000];
+/// let bytes = to_bytes::<_, 256>(&value).unwrap();
+///
+/// let limits = ValidationLimits {
+///     max_collection_len: 100,
+///     ..ValidationLimits::default()
+/// };
+/// assert!(check_archived_root_with_limits::<Vec<()>>(&bytes, limits).is_err());
+/// ```
+#[inline]
+pub fn check_archived_root_with_limits<'a, T: Archive>(
+    bytes: &'a [u8],
+    limits: ValidationLimits,
+) -> Result<&'a T::Archived, CheckTypeError<T::Archived, DefaultValidator<'a>>>
+where
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    let mut validator = DefaultValidator::with_limits(bytes, limits);
+    check_archived_root_with_context::<T, DefaultValidator>(bytes, &mut validator)
+}
+
+/// Checks the given archive at the given position for an archived version of the given type,
+/// returning a detailed report if it's invalid.
+///
+/// This is like [`check_archived_value`], but the returned [`CheckErrorReport`] also describes
+/// where the invalid value is located in the archive. This is useful for debugging corrupted
+/// archives.
+#[inline]
+pub fn check_archived_value_with_report<'a, T: Archive>(
+    bytes: &'a [u8],
+    pos: usize,
+) -> Result<&'a T::Archived, CheckErrorReport<CheckTypeError<T::Archived, DefaultValidator<'a>>>>
+where
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    let mut validator = DefaultValidator::new(bytes);
+    check_archived_value_with_context::<T, DefaultValidator>(bytes, pos, &mut validator)
+        .map_err(|e| CheckErrorReport::new(e, type_name::<T>(), validator.error_path()))
+}
+
+/// Checks the given archive for an archived version of the given type, returning a detailed report
+/// if it's invalid.
+///
+/// This is like [`check_archived_root`], but the returned [`CheckErrorReport`] also describes where
+/// the invalid value is located in the archive. This is useful for debugging corrupted archives.
+///
+/// # Examples
+/// ```
+/// use bytecheck::CheckBytes;
+/// use rkyv::{to_bytes, validation::validators::check_archived_root_with_report, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive_attr(derive(CheckBytes, Debug))]
+/// struct Example {
+///     flags: Vec<bool>,
+/// }
+///
+/// let value = Example {
+///     flags: vec![true, false, true],
+/// };
+/// let mut bytes = to_bytes::<_, 256>(&value).unwrap();
+///
+/// // Corrupt the second flag
+/// bytes[1] = 2;
+///
+/// let report = check_archived_root_with_report::<Example>(&bytes).unwrap_err();
+/// assert_eq!(report.path(), "root.flags[1]");
+/// assert_eq!(report.offset(), Some(1));
+/// assert!(report.type_name().ends_with("Example"));
+/// ```
+#[inline]
+pub fn check_archived_root_with_report<'a, T: Archive>(
+    bytes: &'a [u8],
+) -> Result<&'a T::Archived, CheckErrorReport<CheckTypeError<T::Archived, DefaultValidator<'a>>>>
+where
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    let mut validator = DefaultValidator::new(bytes);
+    check_archived_root_with_context::<T, DefaultValidator>(bytes, &mut validator)
+        .map_err(|e| CheckErrorReport::new(e, type_name::<T>(), validator.error_path()))
+}
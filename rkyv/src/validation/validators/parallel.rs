@@ -0,0 +1,227 @@
+//! Checking large archives on multiple threads.
+
+use crate::{
+    validation::{
+        check_archived_root_with_context,
+        owned::OwnedPointerError,
+        validators::{ArchiveError, ArchiveValidator, ValidationLimits},
+        ArchiveContext, CheckArchiveError, CheckTypeError,
+    },
+    vec::ArchivedVec,
+    Archive,
+};
+use bytecheck::{CheckBytes, SliceCheckError};
+use core::{mem::size_of, ops::Range};
+use std::thread;
+
+/// Options for checking archives on multiple threads.
+///
+/// ```
+/// use rkyv::validation::validators::ParallelOptions;
+///
+/// let options = ParallelOptions {
+///     threads: 4,
+///     ..ParallelOptions::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParallelOptions {
+    /// The maximum number of threads to check with, including the calling thread.
+    ///
+    /// If zero, the number of threads is the available parallelism of the system.
+    pub threads: usize,
+    /// The minimum number of elements to check on each thread.
+    ///
+    /// Collections with fewer than twice this many elements are checked on the calling thread.
+    pub min_chunk_len: usize,
+    /// The limits on the resources used to check the whole archive.
+    pub limits: ValidationLimits,
+}
+
+impl Default for ParallelOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            threads: 0,
+            min_chunk_len: 1024,
+            limits: ValidationLimits::unlimited(),
+        }
+    }
+}
+
+impl ParallelOptions {
+    fn chunk_count(&self, len: usize) -> usize {
+        let threads = if self.threads == 0 {
+            thread::available_parallelism().map_or(1, |n| n.get())
+        } else {
+            self.threads
+        };
+        threads.min(len / self.min_chunk_len.max(1)).max(1)
+    }
+}
+
+// What a chunk of elements claimed from the subtree range before the elements
+struct ChunkClaims {
+    // The lowest pointer the chunk checked or claimed
+    start: *const u8,
+    // The start of the subtree range after the chunk was checked
+    end: *const u8,
+    checked_bytes: usize,
+}
+
+// SAFETY: ChunkClaims is safe to send to another thread
+// This trait is not automatically implemented because the struct contains pointers
+unsafe impl Send for ChunkClaims {}
+
+type VecCheckError<'a, T> = CheckTypeError<ArchivedVec<T>, ArchiveValidator<'a>>;
+
+/// Checks the given archive for an archived vector, checking its elements on multiple threads.
+///
+/// This is like [`check_archived_root`](super::check_archived_root), but the elements of the root
+/// vector are split into chunks that are checked on scoped threads. This can cut the time it takes
+/// to check very large archives, like memory-mapped files that are gigabytes long.
+///
+/// Each thread checks its chunk with its own [`ArchiveValidator`], and afterward the subtree ranges
+/// claimed by each chunk are checked to come one after the other. This accepts exactly the
+/// archives that checking on a single thread does. Because the threads can't share a registry of
+/// shared pointers, archives that contain shared pointers like `Rc` and `Arc` can't be checked in
+/// parallel.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     to_bytes,
+///     validation::validators::{check_archived_root_parallel, ParallelOptions},
+/// };
+///
+/// let value = (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>();
+/// let bytes = to_bytes::<_, 256>(&value).unwrap();
+///
+/// let options = ParallelOptions {
+///     threads: 4,
+///     ..ParallelOptions::default()
+/// };
+/// let archived = check_archived_root_parallel::<Vec<String>, _>(&bytes, &options).unwrap();
+/// assert_eq!(archived[1234], "1234");
+/// ```
+pub fn check_archived_root_parallel<'a, T, U>(
+    bytes: &'a [u8],
+    options: &ParallelOptions,
+) -> Result<&'a ArchivedVec<U>, VecCheckError<'a, U>>
+where
+    T: Archive<Archived = ArchivedVec<U>>,
+    U: CheckBytes<ArchiveValidator<'a>>,
+    U::Error: Send,
+{
+    // Find out how long the vector is, the root is checked again by each chunk
+    let pos = bytes.len() as isize - size_of::<ArchivedVec<U>>() as isize;
+    let len = unsafe {
+        ArchiveValidator::new(bytes)
+            .check_subtree_ptr::<ArchivedVec<U>>(bytes.as_ptr(), pos, ())
+            .map_or(0, |root| (*root).len())
+    };
+    let chunks = options.chunk_count(len);
+    if chunks <= 1 {
+        let mut validator = ArchiveValidator::with_limits(bytes, options.limits);
+        return check_archived_root_with_context::<T, _>(bytes, &mut validator);
+    }
+
+    let chunk_len = len.div_ceil(chunks);
+    let results = thread::scope(|scope| {
+        let handles = (1..chunks)
+            .map(|i| {
+                let range = i * chunk_len..((i + 1) * chunk_len).min(len);
+                scope.spawn(move || check_chunk::<U>(bytes, range, options.limits))
+            })
+            .collect::<Vec<_>>();
+        let mut results = Vec::with_capacity(chunks);
+        results.push(check_chunk::<U>(bytes, 0..chunk_len, options.limits));
+        for handle in handles {
+            results.push(
+                handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e)),
+            );
+        }
+        results
+    });
+
+    // Each chunk must only claim memory after the memory claimed by the chunks before it, which is
+    // what checking them one after another on a single thread requires
+    let mut cursor = core::ptr::null();
+    let mut checked_bytes = 0;
+    for result in results {
+        let (claims, base_bytes) = result?;
+        if let Some(claims) = claims {
+            if claims.start < cursor {
+                return Err(CheckArchiveError::CheckBytesError(
+                    OwnedPointerError::ContextError(ArchiveError::SubtreePointerOutOfBounds {
+                        ptr: claims.start,
+                        subtree_range: cursor..claims.end.max(cursor),
+                    }),
+                ));
+            }
+            cursor = claims.end;
+            checked_bytes = checked_bytes.max(base_bytes) + claims.checked_bytes;
+            if checked_bytes > options.limits.max_bytes {
+                return Err(CheckArchiveError::ContextError(
+                    ArchiveError::ExceededMaximumBytes {
+                        max_bytes: options.limits.max_bytes,
+                    },
+                ));
+            }
+        }
+    }
+
+    // Every chunk checked the root the same way, so it's valid
+    Ok(unsafe { crate::archived_root::<T>(bytes) })
+}
+
+// Checks the root vector and the elements in the given range, returning what the elements claimed
+// (if anything) and how many bytes were checked before the elements were
+fn check_chunk<'a, U>(
+    bytes: &'a [u8],
+    elements: Range<usize>,
+    limits: ValidationLimits,
+) -> Result<(Option<ChunkClaims>, usize), VecCheckError<'a, U>>
+where
+    U: CheckBytes<ArchiveValidator<'a>>,
+{
+    let mut validator = ArchiveValidator::with_limits(bytes, limits);
+    let mut claims = None;
+    let mut base_bytes = 0;
+    unsafe {
+        let pos = bytes.len() as isize - size_of::<ArchivedVec<U>>() as isize;
+        let ptr = validator
+            .check_subtree_ptr::<ArchivedVec<U>>(bytes.as_ptr(), pos, ())
+            .map_err(CheckArchiveError::ContextError)?;
+        let range = validator
+            .push_prefix_subtree(ptr)
+            .map_err(CheckArchiveError::ContextError)?;
+        ArchivedVec::check_bytes_with(ptr, &mut validator, |slice, context| {
+            base_bytes = context.checked_bytes();
+            // Everything the elements point to is below the elements themselves
+            let min_before = context.min_subtree_ptr();
+            for index in elements {
+                U::check_bytes(slice.cast::<U>().add(index), context)
+                    .map_err(|error| SliceCheckError::CheckBytes { index, error })?;
+            }
+            if context.min_subtree_ptr() < min_before {
+                claims = Some(ChunkClaims {
+                    start: context.min_subtree_ptr(),
+                    end: context.subtree_range().start,
+                    checked_bytes: context.checked_bytes() - base_bytes,
+                });
+            }
+            Ok(())
+        })
+        .map_err(CheckArchiveError::CheckBytesError)?;
+        validator
+            .pop_prefix_range(range)
+            .map_err(CheckArchiveError::ContextError)?;
+        validator
+            .finish()
+            .map_err(CheckArchiveError::ContextError)?;
+    }
+    Ok((claims, base_bytes))
+}
@@ -0,0 +1,155 @@
+//! Detailed reports for archives that fail to validate.
+
+use crate::validation::PathSegment;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// The path to a value that failed to check, as recorded by a validator.
+///
+/// Validators record the path while the error propagates out of the types being checked. Segments
+/// are recorded for the fields and variants of derived types, the elements of archived vectors,
+/// the keys and values of hash maps, and the targets of pointers.
+#[derive(Debug, Default)]
+pub struct ErrorPath {
+    // Ordered from the invalid value to the root, along with the positions they lead to
+    segments: Vec<(PathSegment, Option<usize>)>,
+    message: Option<String>,
+}
+
+impl ErrorPath {
+    /// Returns the segments of the path from the root of the archive to the invalid value.
+    #[inline]
+    pub fn segments(&self) -> impl Iterator<Item = PathSegment> + '_ {
+        self.segments.iter().rev().map(|(segment, _)| *segment)
+    }
+
+    /// Returns the position of the innermost value on the path whose position is known.
+    #[inline]
+    pub fn pos(&self) -> Option<usize> {
+        self.segments.iter().find_map(|(_, pos)| *pos)
+    }
+
+    /// Returns a description of what was wrong with the invalid value, if one was recorded.
+    #[inline]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    #[inline]
+    pub(crate) fn record_segment(&mut self, segment: PathSegment, pos: Option<usize>) {
+        self.segments.push((segment, pos));
+    }
+
+    #[inline]
+    pub(crate) fn record_message(&mut self, message: &dyn fmt::Display) {
+        if self.message.is_none() {
+            self.message = Some(message.to_string());
+        }
+    }
+}
+
+impl fmt::Display for ErrorPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "root")?;
+        for segment in self.segments() {
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// A detailed report of why an archive failed to validate.
+///
+/// In addition to the original error, this contains the type that the archive was checked as, a
+/// path to the invalid value from the root of the archive (e.g. `root.users[3].name`), and the
+/// byte offset of the invalid value or the innermost archived object that contains it.
+#[derive(Debug)]
+pub struct CheckErrorReport<E> {
+    error: E,
+    type_name: &'static str,
+    offset: Option<usize>,
+    path: String,
+    message: String,
+}
+
+impl<E: fmt::Display> CheckErrorReport<E> {
+    /// Creates a new report for the given error from the path recorded by the validator.
+    ///
+    /// If no message was recorded with the path, the displayed error is used instead.
+    pub fn new(error: E, type_name: &'static str, path: &ErrorPath) -> Self {
+        let message = match path.message() {
+            Some(message) => message.to_string(),
+            None => error.to_string(),
+        };
+        Self {
+            error,
+            type_name,
+            offset: path.pos(),
+            path: path.to_string(),
+            message,
+        }
+    }
+}
+
+impl<E> CheckErrorReport<E> {
+    /// Returns the error that caused validation to fail.
+    #[inline]
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Consumes the report and returns the error that caused validation to fail.
+    #[inline]
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    /// Returns the name of the type that the archive was checked as.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the byte offset of the invalid value or the innermost archived object that contains
+    /// it.
+    ///
+    /// This is `None` if the error occurred before the root object could be checked, for example
+    /// if the buffer is too small to contain it.
+    #[inline]
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Returns the path from the root of the archive to the invalid value.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns a description of what was wrong with the invalid value.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl<E> fmt::Display for CheckErrorReport<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid archived `{}`", self.type_name)?;
+        if let Some(offset) = self.offset {
+            write!(f, " at byte offset {}", offset)?;
+        }
+        write!(f, " in `{}`: {}", self.path, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for CheckErrorReport<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
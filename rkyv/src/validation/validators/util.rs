@@ -1,5 +1,5 @@
 use crate::{
-    check_archived_root,
+    check_archived_root, check_archived_value,
     de::deserializers::SharedDeserializeMap,
     validation::validators::{CheckTypeError, DefaultValidator},
     Archive, Deserialize, Fallible,
@@ -39,6 +39,61 @@ const _: () = {
     }
 };
 
+/// The error type for [`access`] and [`access_pos`].
+pub type AccessError<'a, T> = CheckTypeError<<T as Archive>::Archived, DefaultValidator<'a>>;
+
+/// Validates and accesses the archived root of the given bytes.
+///
+/// This is the safe way to access an archive: the bytes are validated before the archived root is
+/// returned, so the bytes may come from an untrusted source. It's equivalent to
+/// [`check_archived_root`], and [`archived_root`](crate::archived_root) can be used instead to skip
+/// validation for trusted data.
+///
+/// # Examples
+/// ```
+/// let value = vec!["hello".to_string(), "world".to_string()];
+///
+/// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+/// let archived = rkyv::access::<Vec<String>>(&bytes).unwrap();
+///
+/// assert_eq!(archived[0], "hello");
+/// assert_eq!(archived[1], "world");
+/// ```
+#[inline]
+pub fn access<'a, T>(bytes: &'a [u8]) -> Result<&'a T::Archived, AccessError<'a, T>>
+where
+    T: Archive,
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    check_archived_root::<T>(bytes)
+}
+
+/// Validates and accesses the archived value at the given position in the given bytes.
+///
+/// This is like [`access`] for archives that store values at known positions instead of at the
+/// root. It's equivalent to [`check_archived_value`].
+///
+/// # Examples
+/// ```
+/// use rkyv::ser::{serializers::AllocSerializer, Serializer};
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let first = serializer.serialize_value(&"first".to_string()).unwrap();
+/// let second = serializer.serialize_value(&"second".to_string()).unwrap();
+/// let bytes = serializer.into_serializer().into_inner();
+///
+/// assert_eq!(rkyv::access_pos::<String>(&bytes, first).unwrap(), "first");
+/// assert_eq!(rkyv::access_pos::<String>(&bytes, second).unwrap(), "second");
+/// ```
+#[inline]
+pub fn access_pos<'a, T>(bytes: &'a [u8], pos: usize) -> Result<&'a T::Archived, AccessError<'a, T>>
+where
+    T: Archive,
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    check_archived_value::<T>(bytes, pos)
+}
+
 /// The error type for [`from_bytes`].
 pub type FromBytesError<'a, T> = CheckDeserializeError<
     CheckTypeError<<T as Archive>::Archived, DefaultValidator<'a>>,
@@ -0,0 +1,207 @@
+//! Dynamically-typed values.
+//!
+//! [`Value`] holds JSON-like data whose shape isn't known ahead of time: nulls, booleans, numbers,
+//! strings, arrays, and maps with string keys. It can be archived like any other type, so
+//! schema-less data can be stored in an archive alongside typed data. Maps are archived as
+//! [`ArchivedBTreeMap`](crate::collections::ArchivedBTreeMap)s, so archived maps are sorted by key
+//! and archiving the same value always produces the same bytes.
+//!
+//! With the `serde` feature, [`transcode`] converts any type that implements `serde::Serialize`
+//! into a `Value`, which lets data parsed from other formats be archived without a strongly-typed
+//! intermediate.
+//!
+//! ```
+//! use rkyv::{archived_root, value::Value, Deserialize, Infallible};
+//! use std::collections::BTreeMap;
+//!
+//! let mut map = BTreeMap::new();
+//! map.insert("name".to_string(), Value::String("rkyv".to_string()));
+//! map.insert("stars".to_string(), Value::Int(2_000));
+//! let value = Value::Map(map);
+//!
+//! let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+//! let archived = unsafe { archived_root::<Value>(&bytes) };
+//! assert_eq!(archived.get("name").and_then(|name| name.as_str()), Some("rkyv"));
+//! assert_eq!(archived["stars"].as_i64(), Some(2_000));
+//! assert!(archived.get("forks").is_none());
+//! assert_eq!(*archived, value);
+//!
+//! let deserialized: Value = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(deserialized, value);
+//! ```
+//!
+//! Archived values can be checked with the `validation` feature, so values from untrusted sources
+//! can be accessed without deserializing them first.
+
+#[cfg(feature = "serde")]
+pub mod transcode;
+
+use crate::{Archive, Deserialize, Serialize};
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::ops::Index;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// A dynamically-typed value.
+///
+/// This is archived as an [`ArchivedValue`], which has accessors for the contents of each variant
+/// (like `as_bool` and `as_map`), accessors that convert numbers and strings (like `as_i64` and
+/// `as_str`), and can be indexed by map key or array index.
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[archive(crate = "crate")]
+// The derive macros can't find the bounds for recursive types on their own
+#[archive(bound(serialize = "__S: crate::ser::ScratchSpace + crate::ser::Serializer"))]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(
+    feature = "validation",
+    archive_attr(
+        derive(bytecheck::CheckBytes),
+        check_bytes(
+            bound = "__C: crate::validation::ArchiveContext, <__C as crate::Fallible>::Error: bytecheck::Error"
+        )
+    )
+)]
+pub enum Value {
+    /// A null value
+    Null,
+    /// A boolean
+    Bool(bool),
+    /// A signed integer
+    Int(i64),
+    /// An unsigned integer that's too large to be an [`Int`](Value::Int)
+    UInt(u64),
+    /// A floating-point number
+    Float(f64),
+    /// A string
+    String(String),
+    /// An array of values
+    Array(
+        #[omit_bounds]
+        #[cfg_attr(feature = "validation", archive_attr(omit_bounds))]
+        Vec<Value>,
+    ),
+    /// A map from strings to values
+    Map(
+        #[omit_bounds]
+        #[cfg_attr(feature = "validation", archive_attr(omit_bounds))]
+        BTreeMap<String, Value>,
+    ),
+}
+
+impl Default for Value {
+    #[inline]
+    fn default() -> Self {
+        Value::Null
+    }
+}
+
+impl ArchivedValue {
+    /// Returns the value as an `i64` if it is an integer that fits in one.
+    #[inline]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            ArchivedValue::Int(value) => Some(from_archived!(*value)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a `u64` if it is an integer that fits in one.
+    #[inline]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ArchivedValue::Int(value) if from_archived!(*value) >= 0 => {
+                Some(from_archived!(*value) as u64)
+            }
+            ArchivedValue::UInt(value) => Some(from_archived!(*value)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `f64` if it is a number.
+    ///
+    /// Integers are converted to the nearest `f64`.
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ArchivedValue::Int(value) => Some(from_archived!(*value) as f64),
+            ArchivedValue::UInt(value) => Some(from_archived!(*value) as f64),
+            ArchivedValue::Float(value) => Some(from_archived!(*value)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a string slice if it is a string.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ArchivedValue::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Gets the value for the given key if the value is a map that contains it.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&ArchivedValue> {
+        self.as_map().and_then(|map| map.get(key))
+    }
+}
+
+impl Index<&'_ str> for ArchivedValue {
+    type Output = ArchivedValue;
+
+    /// Gets the value for the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value isn't a map or doesn't contain the key.
+    #[inline]
+    fn index(&self, key: &str) -> &ArchivedValue {
+        self.get(key).unwrap()
+    }
+}
+
+impl Index<usize> for ArchivedValue {
+    type Output = ArchivedValue;
+
+    /// Gets the element at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value isn't an array or the index is out of bounds.
+    #[inline]
+    fn index(&self, index: usize) -> &ArchivedValue {
+        &self.as_array().unwrap()[index]
+    }
+}
+
+impl PartialEq<Value> for ArchivedValue {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (ArchivedValue::Null, Value::Null) => true,
+            (ArchivedValue::Bool(a), Value::Bool(b)) => a == b,
+            (ArchivedValue::Int(a), Value::Int(b)) => from_archived!(*a) == *b,
+            (ArchivedValue::UInt(a), Value::UInt(b)) => from_archived!(*a) == *b,
+            (ArchivedValue::Float(a), Value::Float(b)) => from_archived!(*a) == *b,
+            (ArchivedValue::String(a), Value::String(b)) => a == b,
+            (ArchivedValue::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a == b)
+            }
+            // Both maps are sorted by key
+            (ArchivedValue::Map(a), Value::Map(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|((ak, av), (bk, bv))| ak == bk && av == bv)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<ArchivedValue> for Value {
+    #[inline]
+    fn eq(&self, other: &ArchivedValue) -> bool {
+        other.eq(self)
+    }
+}
@@ -0,0 +1,588 @@
+//! Transcoding `serde` data into dynamically-typed values.
+//!
+//! [`to_value`] runs a type's `serde::Serialize` implementation and builds a [`Value`] from it,
+//! and [`to_bytes`] archives the result. Together with a `serde` data format like `serde_json`,
+//! this transcodes external formats into archives that can be accessed directly:
+//!
+//! ```
+//! use rkyv::{archived_root, value::{transcode, Value}, Deserialize, Infallible};
+//! use std::collections::BTreeMap;
+//!
+//! let mut config = BTreeMap::new();
+//! config.insert("threads", vec![1, 2, 4]);
+//! let bytes = transcode::to_bytes::<_, 256>(&config).unwrap();
+//!
+//! let archived = unsafe { archived_root::<Value>(&bytes) };
+//! let value: Value = archived.deserialize(&mut Infallible).unwrap();
+//! assert_eq!(value, transcode::to_value(&config).unwrap());
+//! ```
+//!
+//! Values are built the same way that `serde_json` builds its `Value`:
+//!
+//! - Unit, unit structs, and `None` become [`Value::Null`].
+//! - Sequences, tuples, tuple structs, and byte arrays become [`Value::Array`]s.
+//! - Maps and structs become [`Value::Map`]s. Map keys must serialize to strings, integers, or
+//!   booleans, and are converted to strings.
+//! - Unit variants become their name as a [`Value::String`], and all other enum variants become a
+//!   map from their name to their contents.
+
+use crate::{ser::serializers::AllocSerializer, value::Value, AlignedVec, Fallible};
+use ::serde::ser::{self, Serialize};
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// An error that occurred while transcoding a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscodeError {
+    /// A map key was not a string, integer, or boolean
+    InvalidKey,
+    /// A `Serialize` implementation returned an error
+    Custom(String),
+}
+
+impl fmt::Display for TranscodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscodeError::InvalidKey => {
+                write!(f, "map key must be a string, integer, or boolean")
+            }
+            TranscodeError::Custom(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TranscodeError {}
+
+#[cfg(not(feature = "std"))]
+impl ::serde::ser::StdError for TranscodeError {}
+
+impl ser::Error for TranscodeError {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TranscodeError::Custom(msg.to_string())
+    }
+}
+
+/// An error that occurred while transcoding a value and archiving it.
+#[derive(Debug)]
+pub enum TranscodeSerializeError<E> {
+    /// An error occurred while transcoding the value
+    Transcode(TranscodeError),
+    /// An error occurred while serializing the transcoded value
+    Serialize(E),
+}
+
+impl<E: fmt::Display> fmt::Display for TranscodeSerializeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscodeSerializeError::Transcode(e) => write!(f, "transcoding error: {}", e),
+            TranscodeSerializeError::Serialize(e) => write!(f, "serialization error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for TranscodeSerializeError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TranscodeSerializeError::Transcode(e) => Some(e as &dyn std::error::Error),
+            TranscodeSerializeError::Serialize(e) => Some(e as &dyn std::error::Error),
+        }
+    }
+}
+
+/// Converts a type that implements `serde::Serialize` into a [`Value`].
+#[inline]
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, TranscodeError> {
+    value.serialize(ValueSerializer)
+}
+
+/// Converts a type that implements `serde::Serialize` into a [`Value`] and archives it.
+///
+/// The root of the returned archive is an [`ArchivedValue`](crate::value::ArchivedValue).
+#[inline]
+pub fn to_bytes<T, const N: usize>(
+    value: &T,
+) -> Result<AlignedVec, TranscodeSerializeError<<AllocSerializer<N> as Fallible>::Error>>
+where
+    T: Serialize + ?Sized,
+{
+    let value = to_value(value).map_err(TranscodeSerializeError::Transcode)?;
+    crate::to_bytes::<Value, N>(&value).map_err(TranscodeSerializeError::Serialize)
+}
+
+/// A `serde` serializer that builds a [`Value`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeMap;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<Value, TranscodeError> {
+        Ok(Value::Bool(v))
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<Value, TranscodeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<Value, TranscodeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<Value, TranscodeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<Value, TranscodeError> {
+        Ok(Value::Int(v))
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<Value, TranscodeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<Value, TranscodeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<Value, TranscodeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<Value, TranscodeError> {
+        if v <= i64::MAX as u64 {
+            Ok(Value::Int(v as i64))
+        } else {
+            Ok(Value::UInt(v))
+        }
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<Value, TranscodeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<Value, TranscodeError> {
+        Ok(Value::Float(v))
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Value, TranscodeError> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Value, TranscodeError> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, TranscodeError> {
+        Ok(Value::Array(
+            v.iter().map(|&b| Value::Int(b as i64)).collect(),
+        ))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Value, TranscodeError> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, TranscodeError> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Value, TranscodeError> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, TranscodeError> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value, TranscodeError> {
+        self.serialize_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Value, TranscodeError> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, TranscodeError> {
+        Ok(wrap_variant(Some(variant), value.serialize(self)?))
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, TranscodeError> {
+        Ok(SerializeArray {
+            values: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, TranscodeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, TranscodeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, TranscodeError> {
+        Ok(SerializeArray {
+            values: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+
+    #[inline]
+    fn serialize_map(self, _: Option<usize>) -> Result<SerializeMap, TranscodeError> {
+        Ok(SerializeMap {
+            map: BTreeMap::new(),
+            next_key: None,
+            variant: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<SerializeMap, TranscodeError> {
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<SerializeMap, TranscodeError> {
+        Ok(SerializeMap {
+            map: BTreeMap::new(),
+            next_key: None,
+            variant: Some(variant),
+        })
+    }
+}
+
+// Enum variants with contents are wrapped in a map from the name of the variant to the contents
+fn wrap_variant(variant: Option<&'static str>, value: Value) -> Value {
+    match variant {
+        Some(variant) => {
+            let mut map = BTreeMap::new();
+            map.insert(variant.to_string(), value);
+            Value::Map(map)
+        }
+        None => value,
+    }
+}
+
+/// Builds a [`Value::Array`] for [`ValueSerializer`].
+pub struct SerializeArray {
+    values: Vec<Value>,
+    variant: Option<&'static str>,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), TranscodeError> {
+        self.values.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Value, TranscodeError> {
+        Ok(wrap_variant(self.variant, Value::Array(self.values)))
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), TranscodeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Value, TranscodeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TranscodeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Value, TranscodeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TranscodeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Value, TranscodeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Builds a [`Value::Map`] for [`ValueSerializer`].
+pub struct SerializeMap {
+    map: BTreeMap<String, Value>,
+    next_key: Option<String>,
+    variant: Option<&'static str>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), TranscodeError> {
+        self.next_key = Some(match key.serialize(ValueSerializer)? {
+            Value::String(key) => key,
+            Value::Bool(key) => key.to_string(),
+            Value::Int(key) => key.to_string(),
+            Value::UInt(key) => key.to_string(),
+            _ => return Err(TranscodeError::InvalidKey),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TranscodeError> {
+        let key = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.map.insert(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Value, TranscodeError> {
+        Ok(wrap_variant(self.variant, Value::Map(self.map)))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), TranscodeError> {
+        self.map
+            .insert(key.to_string(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Value, TranscodeError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeMap {
+    type Ok = Value;
+    type Error = TranscodeError;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), TranscodeError> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Value, TranscodeError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_bytes, to_value, TranscodeError};
+    use crate::{archived_root, value::Value, Deserialize, Infallible};
+    use ::serde::Serialize;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Serialize)]
+    struct Unit;
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Point(i32, i32),
+        Rect { w: u32, h: u32 },
+    }
+
+    #[derive(Serialize)]
+    struct Document {
+        title: &'static str,
+        tags: Vec<&'static str>,
+        rating: Option<u8>,
+        unit: Unit,
+        shapes: Vec<Shape>,
+        counts: HashMap<u64, i8>,
+        big: u64,
+    }
+
+    fn map<const N: usize>(entries: [(&str, Value); N]) -> Value {
+        Value::Map(
+            entries
+                .iter()
+                .cloned()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn transcode_document() {
+        let mut counts = HashMap::new();
+        counts.insert(7, -1);
+        let document = Document {
+            title: "shapes",
+            tags: vec!["a", "b"],
+            rating: None,
+            unit: Unit,
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(0.5),
+                Shape::Point(1, -2),
+                Shape::Rect { w: 3, h: 4 },
+            ],
+            counts,
+            big: u64::MAX,
+        };
+
+        let expected = map([
+            ("title", Value::String("shapes".to_string())),
+            (
+                "tags",
+                Value::Array(vec![
+                    Value::String("a".to_string()),
+                    Value::String("b".to_string()),
+                ]),
+            ),
+            ("rating", Value::Null),
+            ("unit", Value::Null),
+            (
+                "shapes",
+                Value::Array(vec![
+                    Value::String("Empty".to_string()),
+                    map([("Circle", Value::Float(0.5))]),
+                    map([("Point", Value::Array(vec![Value::Int(1), Value::Int(-2)]))]),
+                    map([("Rect", map([("w", Value::Int(3)), ("h", Value::Int(4))]))]),
+                ]),
+            ),
+            ("counts", map([("7", Value::Int(-1))])),
+            ("big", Value::UInt(u64::MAX)),
+        ]);
+        assert_eq!(to_value(&document).unwrap(), expected);
+
+        let bytes = to_bytes::<_, 256>(&document).unwrap();
+        let archived = unsafe { archived_root::<Value>(&bytes) };
+        let deserialized: Value = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, expected);
+    }
+
+    #[test]
+    fn transcode_errors() {
+        let mut invalid = BTreeMap::new();
+        invalid.insert(vec![1], 2);
+        assert_eq!(to_value(&invalid).unwrap_err(), TranscodeError::InvalidKey);
+        assert!(to_bytes::<_, 256>(&invalid).is_err());
+    }
+}
@@ -9,9 +9,10 @@ use crate::{
 use core::{
     borrow::Borrow,
     cmp, fmt, hash,
+    iter::FusedIterator,
     ops::{Deref, Index, IndexMut},
     pin::Pin,
-    slice::SliceIndex,
+    slice::{Iter, IterMut, SliceIndex},
 };
 
 pub use self::raw::*;
@@ -20,6 +21,28 @@ pub use self::raw::*;
 ///
 /// This uses a [`RelPtr`] to a `[T]` under the hood. Unlike
 /// [`ArchivedString`](crate::string::ArchivedString), it does not have an inline representation.
+///
+/// An archived vec dereferences to a slice of its archived elements, so all of the slice methods
+/// work on it without deserializing. For example, [`chunks`](slice::chunks),
+/// [`chunks_exact`](slice::chunks_exact), and [`windows`](slice::windows) iterate over archived
+/// sub-slices for batch processing:
+///
+/// ```
+/// use rkyv::archived_root;
+///
+/// let value = (0..10u32).collect::<Vec<_>>();
+/// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+/// let archived = unsafe { archived_root::<Vec<u32>>(&bytes) };
+///
+/// let sums = archived
+///     .chunks_exact(4)
+///     .map(|chunk| chunk.iter().sum::<u32>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(sums, [6, 22]);
+/// assert_eq!(archived.chunks_exact(4).remainder(), [8, 9]);
+/// assert_eq!(archived.chunks(4).last().unwrap(), [8, 9]);
+/// assert!(archived.windows(2).all(|w| w[0] < w[1]));
+/// ```
 #[cfg_attr(feature = "strict", repr(C))]
 pub struct ArchivedVec<T> {
     ptr: RelPtr<T>,
@@ -33,6 +56,12 @@ impl<T> ArchivedVec<T> {
         self.ptr.as_ptr()
     }
 
+    /// Returns the relative pointer to the elements of the archived vec.
+    #[inline]
+    pub(crate) fn rel_ptr(&self) -> &RelPtr<T> {
+        &self.ptr
+    }
+
     /// Returns the number of elements in the archived vec.
     #[inline]
     pub fn len(&self) -> usize {
@@ -71,6 +100,123 @@ impl<T> ArchivedVec<T> {
         unsafe { self.pin_mut_slice().map_unchecked_mut(|s| &mut s[index]) }
     }
 
+    /// Gets the element at the given index of this archived vec as a pinned mutable reference, or
+    /// `None` if the index is out of bounds.
+    #[inline]
+    pub fn get_pin(self: Pin<&mut Self>, index: usize) -> Option<Pin<&mut T>> {
+        unsafe {
+            let slice = self.pin_mut_slice().get_unchecked_mut();
+            slice.get_mut(index).map(|x| Pin::new_unchecked(x))
+        }
+    }
+
+    /// Gets an iterator over the pinned mutable elements of this archived vec.
+    #[inline]
+    pub fn iter_pin(self: Pin<&mut Self>) -> IterPin<'_, T> {
+        IterPin {
+            inner: unsafe { self.pin_mut_slice().get_unchecked_mut().iter_mut() },
+        }
+    }
+
+    /// Binary searches this sorted archived vec for the given value.
+    ///
+    /// This behaves like [`slice::binary_search`], but the value may be any type that the elements
+    /// can be borrowed as. For example, an archived vec of archived strings can be searched for a
+    /// `str` without creating an archived string first:
+    ///
+    /// ```
+    /// use rkyv::archived_root;
+    ///
+    /// let value = vec!["apple".to_string(), "banana".to_string(), "cherry".to_string()];
+    /// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+    /// let archived = unsafe { archived_root::<Vec<String>>(&bytes) };
+    ///
+    /// assert_eq!(archived.binary_search("banana"), Ok(1));
+    /// assert_eq!(archived.binary_search("blueberry"), Err(2));
+    /// ```
+    #[inline]
+    pub fn binary_search<Q: Ord + ?Sized>(&self, value: &Q) -> Result<usize, usize>
+    where
+        T: Borrow<Q>,
+    {
+        self.as_slice()
+            .binary_search_by(|probe| probe.borrow().cmp(value))
+    }
+
+    /// Binary searches this archived vec, which is sorted by the given key extraction function,
+    /// for the given key.
+    ///
+    /// This behaves like [`slice::binary_search_by_key`], but the key may be any type that the
+    /// extracted keys can be borrowed as.
+    ///
+    /// ```
+    /// use rkyv::{archived_root, Archive, Serialize};
+    ///
+    /// #[derive(Archive, Serialize)]
+    /// struct Entry {
+    ///     name: String,
+    ///     id: u32,
+    /// }
+    ///
+    /// let value = vec![
+    ///     Entry { name: "first".to_string(), id: 10 },
+    ///     Entry { name: "second".to_string(), id: 20 },
+    /// ];
+    /// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+    /// let archived = unsafe { archived_root::<Vec<Entry>>(&bytes) };
+    ///
+    /// assert_eq!(archived.binary_search_by_key("second", |e| e.name.as_str()), Ok(1));
+    /// assert_eq!(archived.binary_search_by_key(&15, |e| rkyv::from_archived!(e.id)), Err(1));
+    /// ```
+    #[inline]
+    pub fn binary_search_by_key<'a, B, Q, F>(&'a self, key: &Q, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> B,
+        B: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.as_slice()
+            .binary_search_by(|probe| f(probe).borrow().cmp(key))
+    }
+
+    /// Returns the index of the partition point of this archived vec according to the given
+    /// predicate.
+    ///
+    /// This behaves like [`slice::partition_point`]: the elements must be partitioned so that all
+    /// of the elements for which the predicate returns `true` come before all of the elements for
+    /// which it returns `false`, and the index of the first element for which it returns `false` is
+    /// returned.
+    #[inline]
+    pub fn partition_point<P: FnMut(&T) -> bool>(&self, pred: P) -> usize {
+        self.as_slice().partition_point(pred)
+    }
+
+    /// Returns whether the elements of this archived vec are sorted.
+    ///
+    /// Archived vecs that are sorted can be searched with [`binary_search`](Self::binary_search).
+    #[inline]
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.as_slice().windows(2).all(|w| w[0] <= w[1])
+    }
+
+    /// Returns whether the elements of this archived vec are sorted by the given key extraction
+    /// function.
+    #[inline]
+    pub fn is_sorted_by_key<'a, K: PartialOrd, F: FnMut(&'a T) -> K>(&'a self, mut f: F) -> bool {
+        let mut keys = self.as_slice().iter().map(&mut f);
+        match keys.next() {
+            Some(mut prev) => keys.all(|key| {
+                let sorted = prev <= key;
+                prev = key;
+                sorted
+            }),
+            None => true,
+        }
+    }
+
     /// Resolves an archived `Vec` from a given slice.
     ///
     /// # Safety
@@ -144,6 +290,97 @@ impl<T> ArchivedVec<T> {
         Ok(VecResolver { pos })
     }
 
+    /// Serializes an archived `Vec` of `len` elements by calling `f` with the index of each
+    /// element.
+    ///
+    /// Elements are resolved and written to the serializer one at a time as they are produced, so
+    /// very large sequences can be serialized without building a `Vec` first. Because no resolvers
+    /// are kept, this only supports element types that don't need to serialize any dependencies.
+    #[inline]
+    pub fn serialize_from_fn<U, F, S>(
+        len: usize,
+        mut f: F,
+        serializer: &mut S,
+    ) -> Result<VecResolver, S::Error>
+    where
+        U: Archive<Archived = T, Resolver = ()>,
+        F: FnMut(usize) -> U,
+        S: Serializer + ?Sized,
+    {
+        let pos = serializer.align_for::<T>()?;
+        for i in 0..len {
+            unsafe {
+                serializer.resolve_aligned(&f(i), ())?;
+            }
+        }
+        Ok(VecResolver { pos })
+    }
+
+    /// Serializes an archived `Vec` from the first `len` elements of an iterator by directly
+    /// copying bytes.
+    ///
+    /// Elements are collected into a 4 KiB buffer on the stack and written to the serializer in
+    /// chunks, so very large sequences can be serialized without building a `Vec` first. Elements
+    /// larger than the buffer are written one at a time.
+    ///
+    /// # Safety
+    ///
+    /// The type being serialized must be copy-safe. See
+    /// [`serialize_copy_from_slice`](ArchivedVec::serialize_copy_from_slice) for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields fewer than `len` elements.
+    #[inline]
+    pub unsafe fn serialize_copy_from_iter<U, I, S>(
+        len: usize,
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<VecResolver, S::Error>
+    where
+        U: Archive<Archived = T>,
+        I: IntoIterator<Item = U>,
+        S: Serializer + ?Sized,
+    {
+        use ::core::{
+            mem::{size_of, MaybeUninit},
+            slice::from_raw_parts,
+        };
+
+        const CHUNK_BYTES: usize = 4096;
+
+        let pos = serializer.align_for::<T>()?;
+
+        let mut iter = iter.into_iter();
+        let mut next = || {
+            iter.next()
+                .expect("iterator yielded fewer elements than the requested length")
+        };
+        let size = size_of::<U>();
+        if size == 0 || size > CHUNK_BYTES {
+            for _ in 0..len {
+                let value = MaybeUninit::new(next());
+                serializer.write(from_raw_parts(value.as_ptr().cast::<u8>(), size))?;
+            }
+        } else {
+            let chunk_cap = CHUNK_BYTES / size;
+            let mut chunk = [MaybeUninit::<u8>::uninit(); CHUNK_BYTES];
+            let mut remaining = len;
+            while remaining > 0 {
+                let chunk_len = remaining.min(chunk_cap);
+                for i in 0..chunk_len {
+                    let slot = chunk.as_mut_ptr().add(i * size).cast::<U>();
+                    slot.write_unaligned(next());
+                }
+                let bytes = from_raw_parts(chunk.as_ptr().cast::<u8>(), size * chunk_len);
+                serializer.write(bytes)?;
+                remaining -= chunk_len;
+            }
+        }
+
+        Ok(VecResolver { pos })
+    }
+
     /// Serializes an archived `Vec` from a given iterator.
     ///
     /// This method is unable to perform copy optimizations; prefer
@@ -211,6 +448,16 @@ impl<T> Deref for ArchivedVec<T> {
 
 impl<T: Eq> Eq for ArchivedVec<T> {}
 
+impl<'a, T> IntoIterator for &'a ArchivedVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
 impl<T: hash::Hash> hash::Hash for ArchivedVec<T> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
@@ -290,6 +537,39 @@ impl<T: PartialOrd> PartialOrd<ArchivedVec<T>> for [T] {
     }
 }
 
+/// An iterator over the pinned mutable elements of an archived vec.
+///
+/// This `struct` is created by the [`ArchivedVec::iter_pin`] function.
+pub struct IterPin<'a, T> {
+    inner: IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterPin<'a, T> {
+    type Item = Pin<&'a mut T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|x| unsafe { Pin::new_unchecked(x) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterPin<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|x| unsafe { Pin::new_unchecked(x) })
+    }
+}
+
+impl<T> ExactSizeIterator for IterPin<'_, T> {}
+impl<T> FusedIterator for IterPin<'_, T> {}
+
 /// The resolver for [`ArchivedVec`].
 pub struct VecResolver {
     pos: usize,
@@ -299,9 +579,9 @@ pub struct VecResolver {
 const _: () = {
     use crate::validation::{
         owned::{CheckOwnedPointerError, OwnedPointerError},
-        ArchiveContext,
+        ArchiveContext, PathSegment,
     };
-    use bytecheck::{CheckBytes, Error};
+    use bytecheck::{CheckBytes, Error, SliceCheckError};
 
     impl<T> ArchivedVec<T> {
         /// Checks the bytes of the `ArchivedVec` with the given element checking function.
@@ -351,7 +631,15 @@ const _: () = {
             context: &mut C,
         ) -> Result<&'a Self, Self::Error> {
             Self::check_bytes_with::<C, _>(value, context, |v, c| {
-                <[T]>::check_bytes(v, c).map(|_| ())
+                let data = v.cast::<T>();
+                for index in 0..ptr_meta::metadata(v) {
+                    let element = data.add(index);
+                    T::check_bytes(element, c).map_err(|error| {
+                        c.record_check_error(&error, PathSegment::Index(index), element.cast());
+                        SliceCheckError::CheckBytes { index, error }
+                    })?;
+                }
+                Ok(())
             })
         }
     }
@@ -0,0 +1,238 @@
+//! Versioned archives that upgrade older versions of a type when they are loaded.
+//!
+//! A [`Versioned`] envelope stores the [`VERSION`](Version::VERSION) of its type next to a pointer
+//! to the archived value. Archives written with a prior version of the type can still be read as
+//! the current version: [`ArchivedVersioned::upgrade`] checks the stored version and migrates
+//! archives from prior versions to the current type instead of misreading them.
+//!
+//! The version of a type and the prior versions it migrates from can be declared with
+//! `#[archive(version = ..., migrate_from(...))]`. Each type listed in `migrate_from` must also
+//! implement [`Version`], and the current type must implement `From<&Archived<Prior>>` for each of
+//! them.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{archived_root, versioned::Versioned, Archive, Archived, Deserialize, Serialize};
+//!
+//! mod v1 {
+//!     use rkyv::{Archive, Serialize};
+//!
+//!     #[derive(Archive, Serialize)]
+//!     #[archive(version = 1)]
+//!     pub struct Config {
+//!         pub name: String,
+//!     }
+//! }
+//!
+//! #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+//! #[archive(version = 2, migrate_from(v1::Config))]
+//! struct Config {
+//!     name: String,
+//!     retries: u32,
+//! }
+//!
+//! impl From<&Archived<v1::Config>> for Config {
+//!     fn from(old: &Archived<v1::Config>) -> Self {
+//!         Self {
+//!             name: old.name.to_string(),
+//!             retries: 3,
+//!         }
+//!     }
+//! }
+//!
+//! let old = v1::Config {
+//!     name: "server".to_string(),
+//! };
+//! let bytes = rkyv::to_bytes::<_, 256>(&Versioned(old)).unwrap();
+//!
+//! let archived = unsafe { archived_root::<Versioned<Config>>(&bytes) };
+//! assert_eq!(archived.version(), 1);
+//! assert!(archived.get().is_none());
+//! assert_eq!(
+//!     archived.upgrade().unwrap(),
+//!     Config {
+//!         name: "server".to_string(),
+//!         retries: 3,
+//!     }
+//! );
+//! ```
+
+use crate::{
+    ser::Serializer, Archive, Archived, Deserialize, Fallible, Infallible, RawRelPtr, Serialize,
+};
+use core::{fmt, marker::PhantomData};
+
+/// A type with a version number that can migrate from prior versions of itself.
+///
+/// This can be derived with `#[archive(version = ...)]`, and prior versions can be declared with
+/// `#[archive(migrate_from(...))]`.
+pub trait Version: Archive {
+    /// The version of the type.
+    ///
+    /// Every version of a type must have a different version number.
+    const VERSION: u32;
+
+    /// Migrates the archived value of a prior version of the type to the current version.
+    ///
+    /// Returns `None` if the type can't migrate from the given version. By default, a type can't
+    /// migrate from any prior versions.
+    ///
+    /// # Safety
+    ///
+    /// `value` must point to an archived value of the version of the type with the given version
+    /// number.
+    #[inline]
+    unsafe fn migrate(version: u32, value: *const u8) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        let _ = (version, value);
+        None
+    }
+}
+
+/// An error resulting from an archive with a version that can't be migrated.
+#[derive(Debug)]
+pub enum VersionError {
+    /// The archive was written with a version that the current type can't migrate from.
+    Unsupported {
+        /// The version of the archive
+        version: u32,
+        /// The version of the current type
+        current: u32,
+    },
+}
+
+impl fmt::Display for VersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionError::Unsupported { version, current } => write!(
+                f,
+                "unsupported archive version {}, can't migrate to version {}",
+                version, current
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VersionError {}
+
+/// An envelope that archives a value together with the version of its type.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Versioned<T>(pub T);
+
+/// An archived [`Versioned`].
+///
+/// The layout of an archived envelope doesn't depend on its type, so envelopes written with any
+/// version of a type can be read as an envelope of the current version.
+#[repr(C)]
+pub struct ArchivedVersioned<T> {
+    version: Archived<u32>,
+    value: RawRelPtr,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Version> ArchivedVersioned<T> {
+    /// Returns the version of the archived value.
+    #[inline]
+    pub fn version(&self) -> u32 {
+        from_archived!(self.version)
+    }
+
+    /// Returns whether the archived value was written with the current version of the type.
+    #[inline]
+    pub fn is_current(&self) -> bool {
+        self.version() == T::VERSION
+    }
+
+    /// Returns the archived value if it was written with the current version of the type.
+    #[inline]
+    pub fn get(&self) -> Option<&T::Archived> {
+        if self.is_current() {
+            // SAFETY: The archived value was written with the current version of the type.
+            Some(unsafe { &*self.value.as_ptr().cast() })
+        } else {
+            None
+        }
+    }
+
+    /// Deserializes the archived value with the [`Infallible`] deserializer, migrating it to the
+    /// current version of the type if it was written with a prior version.
+    #[inline]
+    pub fn upgrade(&self) -> Result<T, VersionError>
+    where
+        T::Archived: Deserialize<T, Infallible>,
+    {
+        match self.get() {
+            Some(value) => Ok(crate::deserialize::<T>(value)),
+            None => self.migrate(),
+        }
+    }
+
+    fn migrate(&self) -> Result<T, VersionError> {
+        let version = self.version();
+        // SAFETY: The archived value was written with the version of the type that has this
+        // version number.
+        unsafe { T::migrate(version, self.value.as_ptr().cast()) }.ok_or(
+            VersionError::Unsupported {
+                version,
+                current: T::VERSION,
+            },
+        )
+    }
+}
+
+impl<T> fmt::Debug for ArchivedVersioned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedVersioned")
+            .field("version", &from_archived!(self.version))
+            .finish()
+    }
+}
+
+/// The resolver for a [`Versioned`].
+pub struct VersionedResolver {
+    pos: usize,
+}
+
+impl<T: Version> Archive for Versioned<T> {
+    type Archived = ArchivedVersioned<T>;
+    type Resolver = VersionedResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (_, fo) = out_field!(out.version);
+        fo.write(to_archived!(T::VERSION));
+        let (fp, fo) = out_field!(out.value);
+        RawRelPtr::emplace(pos + fp, resolver.pos, fo);
+    }
+}
+
+impl<T: Version + Serialize<S>, S: Serializer + ?Sized> Serialize<S> for Versioned<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(VersionedResolver {
+            pos: serializer.serialize_value(&self.0)?,
+        })
+    }
+}
+
+impl<T, D> Deserialize<Versioned<T>, D> for ArchivedVersioned<T>
+where
+    T: Version,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+    D::Error: From<VersionError>,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Versioned<T>, D::Error> {
+        match self.get() {
+            Some(value) => Ok(Versioned(value.deserialize(deserializer)?)),
+            None => Ok(Versioned(self.migrate()?)),
+        }
+    }
+}
@@ -1,13 +1,26 @@
 use crate::{
     boxed::{ArchivedBox, BoxResolver},
-    collections::util::Entry,
+    collections::{
+        bit_set::{ArchivedBitSet, BitSetResolver},
+        btree_map::{ArchivedBTreeMap, BTreeMapResolver},
+        delta_vec::{ArchivedDeltaVec, DeltaInt, DeltaVecResolver},
+        dictionary::{ArchivedDictionaryVec, DictionaryVecResolver},
+        util::Entry,
+    },
+    columnar::{Columnar, DeserializeColumns, SerializeColumns},
     niche::option_box::{ArchivedOptionBox, OptionBoxResolver},
+    rel_ptr::OffsetError,
     ser::{ScratchSpace, Serializer},
-    string::{ArchivedString, StringResolver},
+    string::{
+        inline::{ArchivedInlineString, InlineStringResolver},
+        nul_terminated::{ArchivedNulTerminatedString, NulTerminatedStringResolver},
+        ArchivedString, StringResolver,
+    },
     vec::{ArchivedVec, RawArchivedVec, VecResolver},
     with::{
-        ArchiveWith, AsOwned, AsVec, CopyOptimize, DeserializeWith, Map, Niche, Raw, RefAsBox,
-        SerializeWith, With,
+        ArchiveWith, AsBitSet, AsColumns, AsDelta, AsDictionary, AsInlineString, AsOwned,
+        AsSortedVec, AsVec, CopyOptimize, DeserializeWith, Map, Niche, NulTerminated, Raw,
+        RefAsBox, SerializeWith, With,
     },
     Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized, Fallible,
     MetadataResolver, Serialize, SerializeUnsized,
@@ -18,6 +31,7 @@ use alloc::{
     borrow::Cow,
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    string::String,
     vec::Vec,
 };
 #[cfg(feature = "std")]
@@ -104,6 +118,71 @@ where
     }
 }
 
+// Map for BTreeMaps
+
+impl<A, K, V> ArchiveWith<BTreeMap<K, V>> for Map<A>
+where
+    A: ArchiveWith<V>,
+    K: Archive + Ord,
+    K::Archived: Ord,
+{
+    type Archived = ArchivedBTreeMap<K::Archived, <A as ArchiveWith<V>>::Archived>;
+    type Resolver = BTreeMapResolver;
+
+    unsafe fn resolve_with(
+        field: &BTreeMap<K, V>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedBTreeMap::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<A, K, V, S> SerializeWith<BTreeMap<K, V>, S> for Map<A>
+where
+    A: SerializeWith<V, S>,
+    K: Serialize<S> + Ord,
+    K::Archived: Ord,
+    S: Serializer + ?Sized,
+{
+    fn serialize_with(field: &BTreeMap<K, V>, s: &mut S) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            ArchivedBTreeMap::serialize_from_reverse_iter(
+                field
+                    .iter()
+                    .rev()
+                    .map(|(key, value)| (key, With::<V, A>::cast(value))),
+                s,
+            )
+        }
+    }
+}
+
+impl<A, K, V, D>
+    DeserializeWith<
+        ArchivedBTreeMap<K::Archived, <A as ArchiveWith<V>>::Archived>,
+        BTreeMap<K, V>,
+        D,
+    > for Map<A>
+where
+    A: ArchiveWith<V> + DeserializeWith<<A as ArchiveWith<V>>::Archived, V, D>,
+    K: Archive + Ord,
+    K::Archived: Deserialize<K, D> + Ord,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBTreeMap<K::Archived, <A as ArchiveWith<V>>::Archived>,
+        d: &mut D,
+    ) -> Result<BTreeMap<K, V>, D::Error> {
+        let mut result = BTreeMap::new();
+        for (key, value) in field.iter() {
+            result.insert(key.deserialize(d)?, A::deserialize_with(value, d)?);
+        }
+        Ok(result)
+    }
+}
+
 // AsOwned
 
 impl<'a, F: Archive + Clone> ArchiveWith<Cow<'a, F>> for AsOwned {
@@ -217,8 +296,14 @@ impl<'a, D: Fallible + ?Sized> DeserializeWith<ArchivedString, Cow<'a, str>, D>
 
 #[cfg(feature = "std")]
 const _: () = {
-    use crate::ffi::{ArchivedCString, CStringResolver};
-    use std::ffi::CStr;
+    use crate::{
+        ffi::{ArchivedCString, ArchivedOsStr, CStringResolver},
+        path::ArchivedPath,
+    };
+    use std::{
+        ffi::{CStr, OsStr},
+        path::Path,
+    };
 
     impl<'a> ArchiveWith<Cow<'a, CStr>> for AsOwned {
         type Archived = ArchivedCString;
@@ -254,8 +339,251 @@ const _: () = {
             Ok(Cow::Owned(field.deserialize(deserializer)?))
         }
     }
+
+    impl<'a> ArchiveWith<Cow<'a, OsStr>> for AsOwned {
+        type Archived = ArchivedBox<ArchivedOsStr>;
+        type Resolver = BoxResolver<()>;
+
+        #[inline]
+        unsafe fn resolve_with(
+            field: &Cow<'a, OsStr>,
+            pos: usize,
+            resolver: Self::Resolver,
+            out: *mut Self::Archived,
+        ) {
+            ArchivedBox::resolve_from_ref(&**field, pos, resolver, out);
+        }
+    }
+
+    impl<'a, S: Serializer + ?Sized> SerializeWith<Cow<'a, OsStr>, S> for AsOwned {
+        #[inline]
+        fn serialize_with(
+            field: &Cow<'a, OsStr>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedBox::serialize_from_ref(&**field, serializer)
+        }
+    }
+
+    impl<'a, D: Fallible + ?Sized> DeserializeWith<ArchivedBox<ArchivedOsStr>, Cow<'a, OsStr>, D>
+        for AsOwned
+    {
+        #[inline]
+        fn deserialize_with(
+            field: &ArchivedBox<ArchivedOsStr>,
+            _: &mut D,
+        ) -> Result<Cow<'a, OsStr>, D::Error> {
+            Ok(Cow::Owned(field.to_os_str().into_owned()))
+        }
+    }
+
+    // Paths are archived through their OS strings, the same as `Box<Path>`
+    impl<'a> ArchiveWith<Cow<'a, Path>> for AsOwned {
+        type Archived = ArchivedBox<ArchivedPath>;
+        type Resolver = BoxResolver<()>;
+
+        #[inline]
+        unsafe fn resolve_with(
+            field: &Cow<'a, Path>,
+            pos: usize,
+            resolver: Self::Resolver,
+            out: *mut Self::Archived,
+        ) {
+            ArchivedBox::resolve_from_ref(field.as_os_str(), pos, resolver, out.cast());
+        }
+    }
+
+    impl<'a, S: Serializer + ?Sized> SerializeWith<Cow<'a, Path>, S> for AsOwned {
+        #[inline]
+        fn serialize_with(
+            field: &Cow<'a, Path>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedBox::<ArchivedOsStr>::serialize_from_ref(field.as_os_str(), serializer)
+        }
+    }
+
+    impl<'a, D: Fallible + ?Sized> DeserializeWith<ArchivedBox<ArchivedPath>, Cow<'a, Path>, D>
+        for AsOwned
+    {
+        #[inline]
+        fn deserialize_with(
+            field: &ArchivedBox<ArchivedPath>,
+            _: &mut D,
+        ) -> Result<Cow<'a, Path>, D::Error> {
+            Ok(Cow::Owned(field.to_path().into_owned()))
+        }
+    }
 };
 
+// AsBitSet
+
+impl ArchiveWith<Vec<bool>> for AsBitSet {
+    type Archived = ArchivedBitSet;
+    type Resolver = BitSetResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<bool>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedBitSet::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<S: ScratchSpace + Serializer + ?Sized> SerializeWith<Vec<bool>, S> for AsBitSet {
+    #[inline]
+    fn serialize_with(field: &Vec<bool>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedBitSet::serialize_from_bools(field.as_slice(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> DeserializeWith<ArchivedBitSet, Vec<bool>, D> for AsBitSet {
+    #[inline]
+    fn deserialize_with(field: &ArchivedBitSet, _: &mut D) -> Result<Vec<bool>, D::Error> {
+        Ok(field.iter().collect())
+    }
+}
+
+// AsDelta
+
+impl<T: DeltaInt> ArchiveWith<Vec<T>> for AsDelta {
+    type Archived = ArchivedDeltaVec<T>;
+    type Resolver = DeltaVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedDeltaVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T: DeltaInt, S: ScratchSpace + Serializer + ?Sized> SerializeWith<Vec<T>, S> for AsDelta {
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedDeltaVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T: DeltaInt, D: Fallible + ?Sized> DeserializeWith<ArchivedDeltaVec<T>, Vec<T>, D>
+    for AsDelta
+{
+    #[inline]
+    fn deserialize_with(field: &ArchivedDeltaVec<T>, _: &mut D) -> Result<Vec<T>, D::Error> {
+        Ok(field.iter().collect())
+    }
+}
+
+// AsDictionary
+
+impl ArchiveWith<Vec<String>> for AsDictionary {
+    type Archived = ArchivedDictionaryVec;
+    type Resolver = DictionaryVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<String>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedDictionaryVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<S: ScratchSpace + Serializer + ?Sized> SerializeWith<Vec<String>, S> for AsDictionary {
+    #[inline]
+    fn serialize_with(field: &Vec<String>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedDictionaryVec::serialize_from_strs(field.as_slice(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> DeserializeWith<ArchivedDictionaryVec, Vec<String>, D> for AsDictionary {
+    #[inline]
+    fn deserialize_with(field: &ArchivedDictionaryVec, _: &mut D) -> Result<Vec<String>, D::Error> {
+        Ok(field.iter().map(String::from).collect())
+    }
+}
+
+// AsInlineString
+
+impl<const N: usize> ArchiveWith<String> for AsInlineString<N> {
+    type Archived = ArchivedInlineString<N>;
+    type Resolver = InlineStringResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &String,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedInlineString::resolve_from_str(field.as_str(), pos, resolver, out);
+    }
+}
+
+impl<S: Serializer + ?Sized, const N: usize> SerializeWith<String, S> for AsInlineString<N>
+where
+    str: SerializeUnsized<S>,
+    S::Error: From<OffsetError>,
+{
+    #[inline]
+    fn serialize_with(field: &String, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedInlineString::<N>::serialize_from_str(field.as_str(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized, const N: usize> DeserializeWith<ArchivedInlineString<N>, String, D>
+    for AsInlineString<N>
+{
+    #[inline]
+    fn deserialize_with(field: &ArchivedInlineString<N>, _: &mut D) -> Result<String, D::Error> {
+        Ok(String::from(field.as_str()))
+    }
+}
+
+// NulTerminated
+
+impl ArchiveWith<String> for NulTerminated {
+    type Archived = ArchivedNulTerminatedString;
+    type Resolver = NulTerminatedStringResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &String,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedNulTerminatedString::resolve_from_str(field.as_str(), pos, resolver, out);
+    }
+}
+
+impl<S: Serializer + ?Sized> SerializeWith<String, S> for NulTerminated {
+    #[inline]
+    fn serialize_with(field: &String, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedNulTerminatedString::serialize_from_str(field.as_str(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> DeserializeWith<ArchivedNulTerminatedString, String, D>
+    for NulTerminated
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedNulTerminatedString,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        Ok(String::from(field.as_str()))
+    }
+}
+
 // AsVec
 
 impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsVec {
@@ -355,6 +683,137 @@ where
     }
 }
 
+// AsSortedVec
+
+impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsSortedVec {
+    type Archived = ArchivedVec<Entry<K::Archived, V::Archived>>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &BTreeMap<K, V>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        AsVec::resolve_with(field, pos, resolver, out);
+    }
+}
+
+impl<K, V, S> SerializeWith<BTreeMap<K, V>, S> for AsSortedVec
+where
+    K: Serialize<S>,
+    V: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(
+        field: &BTreeMap<K, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        // The entries of a `BTreeMap` are already sorted by key
+        AsVec::serialize_with(field, serializer)
+    }
+}
+
+impl<K, V, D> DeserializeWith<ArchivedVec<Entry<K::Archived, V::Archived>>, BTreeMap<K, V>, D>
+    for AsSortedVec
+where
+    K: Archive + Ord,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedVec<Entry<K::Archived, V::Archived>>,
+        deserializer: &mut D,
+    ) -> Result<BTreeMap<K, V>, D::Error> {
+        AsVec::deserialize_with(field, deserializer)
+    }
+}
+
+impl<T: Archive> ArchiveWith<BTreeSet<T>> for AsSortedVec {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &BTreeSet<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        AsVec::resolve_with(field, pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<BTreeSet<T>, S> for AsSortedVec
+where
+    T: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &BTreeSet<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        // The keys of a `BTreeSet` are already sorted
+        AsVec::serialize_with(field, serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, BTreeSet<T>, D> for AsSortedVec
+where
+    T: Archive + Ord,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeSet<T>, D::Error> {
+        AsVec::deserialize_with(field, deserializer)
+    }
+}
+
+// AsColumns
+
+impl<T: Columnar> ArchiveWith<Vec<T>> for AsColumns {
+    type Archived = T::ArchivedColumns;
+    type Resolver = T::ColumnsResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        T::resolve_columns(field, pos, resolver, out);
+    }
+}
+
+impl<T: SerializeColumns<S>, S: Fallible + ?Sized> SerializeWith<Vec<T>, S> for AsColumns {
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        T::serialize_columns(field, serializer)
+    }
+}
+
+impl<T: DeserializeColumns<D>, D: Fallible + ?Sized> DeserializeWith<T::ArchivedColumns, Vec<T>, D>
+    for AsColumns
+{
+    #[inline]
+    fn deserialize_with(
+        field: &T::ArchivedColumns,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        (0..T::columns_len(field))
+            .map(|i| T::deserialize_row(field, i, deserializer))
+            .collect()
+    }
+}
+
 // Niche
 
 impl<T: ArchiveUnsized + ?Sized> ArchiveWith<Option<Box<T>>> for Niche
@@ -8,10 +8,11 @@ use crate::{
     },
     option::ArchivedOption,
     with::{
-        ArchiveWith, AsBox, DeserializeWith, Inline, Map, Niche, RefAsBox, SerializeWith, Skip,
-        Unsafe,
+        ArchiveWith, AsBox, DeserializeWith, Immutable, Inline, Map, Niche, ReadOnly, RefAsBox,
+        SerializeWith, Skip, Transform, Transformed, Unsafe,
     },
-    Archive, ArchiveUnsized, Deserialize, Fallible, Serialize, SerializeUnsized,
+    Archive, ArchiveUnsized, Archived, Deserialize, Fallible, Resolver, Serialize,
+    SerializeUnsized,
 };
 use ::core::{
     cell::{Cell, UnsafeCell},
@@ -104,6 +105,43 @@ struct ArchivedOptionVariantNone(ArchivedOptionTag);
 #[repr(C)]
 struct ArchivedOptionVariantSome<T>(ArchivedOptionTag, T);
 
+// ReadOnly
+
+impl<F: Archive> ArchiveWith<F> for ReadOnly {
+    type Archived = Immutable<F::Archived>;
+    type Resolver = F::Resolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &F,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        field.resolve(pos, resolver, out.cast());
+    }
+}
+
+impl<F: Serialize<S>, S: Fallible + ?Sized> SerializeWith<F, S> for ReadOnly {
+    #[inline]
+    fn serialize_with(field: &F, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        field.serialize(serializer)
+    }
+}
+
+impl<F: Archive, D: Fallible + ?Sized> DeserializeWith<Immutable<F::Archived>, F, D> for ReadOnly
+where
+    F::Archived: Deserialize<F, D>,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &Immutable<F::Archived>,
+        deserializer: &mut D,
+    ) -> Result<F, D::Error> {
+        field.value().deserialize(deserializer)
+    }
+}
+
 // Inline
 
 impl<F: Archive> ArchiveWith<&F> for Inline {
@@ -432,3 +470,46 @@ impl<F: Default, D: Fallible + ?Sized> DeserializeWith<(), F, D> for Skip {
         Ok(Default::default())
     }
 }
+
+// Transformed
+
+impl<F, T: Transform<F>> ArchiveWith<F> for Transformed<T> {
+    type Archived = Archived<T::Output>;
+    type Resolver = (T::Output, Resolver<T::Output>);
+
+    #[inline]
+    unsafe fn resolve_with(
+        _: &F,
+        pos: usize,
+        (output, resolver): Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        output.resolve(pos, resolver, out);
+    }
+}
+
+impl<F, T, S> SerializeWith<F, S> for Transformed<T>
+where
+    T: Transform<F>,
+    T::Output: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &F, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let output = T::serialize(field);
+        let resolver = output.serialize(serializer)?;
+        Ok((output, resolver))
+    }
+}
+
+impl<F, T, D> DeserializeWith<Archived<T::Output>, F, D> for Transformed<T>
+where
+    T: Transform<F>,
+    Archived<T::Output>: Deserialize<T::Output, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(field: &Archived<T::Output>, deserializer: &mut D) -> Result<F, D::Error> {
+        Ok(T::deserialize(field.deserialize(deserializer)?))
+    }
+}
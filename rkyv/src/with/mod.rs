@@ -2,6 +2,27 @@
 //!
 //! Wrappers can be applied with the `#[with(...)]` attribute in the
 //! [`Archive`](macro@crate::Archive) macro. See [`With`] for examples.
+//!
+//! Many common field types don't implement `Archive` directly, either because they can fail to
+//! serialize or because there's more than one reasonable way to archive them. Those are covered by
+//! the wrappers in this module:
+//!
+//! | Field type                           | Wrapper                          |
+//! |--------------------------------------|----------------------------------|
+//! | `Cow<'a, T>`                         | [`AsOwned`]                      |
+//! | `PathBuf`, `OsString`                | [`AsString`]                     |
+//! | `SystemTime`                         | [`UnixTimestamp`]                |
+//! | `AtomicU32` and other atomics        | [`Atomic`]                       |
+//! | `Mutex<T>`, `RwLock<T>`              | [`Lock`]                         |
+//! | `&'a T`, `&'a [T]`, `&'a str`        | [`Inline`], [`RefAsBox`]         |
+//! | `HashMap<K, V>`, `BTreeMap<K, V>`    | [`AsVec`], [`AsSortedVec`]       |
+//! | `Vec<T>` of `#[archive(columnar)]`    | [`AsColumns`]                    |
+//! | `Vec<String>` with repeated values    | [`AsDictionary`]                 |
+//! | `Vec<u64>` of sorted integers         | [`AsDelta`]                      |
+//! | `String` that is usually short        | [`AsInlineString`]               |
+//! | `String` read from C                  | [`NulTerminated`]                |
+//! | `Option<Box<T>>`                     | [`Niche`]                        |
+//! | `Box<dyn Error + Send + Sync>`       | [`AsErrorString`]                |
 
 #[cfg(feature = "alloc")]
 mod alloc;
@@ -17,7 +38,12 @@ pub use self::alloc::*;
 pub use self::std::*;
 
 use crate::{Archive, Deserialize, Fallible, Serialize};
-use ::core::{fmt, marker::PhantomData, mem::transmute, ops::Deref};
+use ::core::{
+    fmt,
+    marker::{PhantomData, PhantomPinned},
+    mem::transmute,
+    ops::Deref,
+};
 
 /// A transparent wrapper for archived fields.
 ///
@@ -236,15 +262,25 @@ where
 }
 
 /// A wrapper to make a type immutable.
+///
+/// `Immutable` only provides shared access to the value it wraps. It's also `!Unpin`, so a pinned
+/// mutable reference to an `Immutable` can't be used to overwrite or swap it either. This is the
+/// archived type of fields serialized with the [`ReadOnly`] wrapper.
 #[repr(transparent)]
-#[derive(Debug)]
-pub struct Immutable<T: ?Sized>(T);
+pub struct Immutable<T: ?Sized>(PhantomPinned, T);
 
 impl<T: ?Sized> Immutable<T> {
     /// Gets the underlying immutable value.
     #[inline]
     pub fn value(&self) -> &T {
-        &self.0
+        &self.1
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Immutable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Immutable").field(&&self.1).finish()
     }
 }
 
@@ -253,7 +289,7 @@ impl<T: ?Sized> Deref for Immutable<T> {
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.1
     }
 }
 
@@ -268,17 +304,21 @@ const _: () = {
             value: *const Self,
             context: &mut C,
         ) -> Result<&'a Self, Self::Error> {
-            CheckBytes::check_bytes(::core::ptr::addr_of!((*value).0), context)?;
+            CheckBytes::check_bytes(::core::ptr::addr_of!((*value).1), context)?;
             Ok(&*value)
         }
     }
 };
 
-/// A generic wrapper that allows wrapping an Option<T>.
+/// A generic wrapper that applies another wrapper to the elements of a container.
+///
+/// `Map<W>` applies `W` to the value of an `Option`, the elements of a `Vec`, and the values of a
+/// `BTreeMap` or `HashMap`. Map keys are archived normally.
 ///
 /// # Example
 ///
 /// ```
+/// use std::collections::{BTreeMap, HashMap};
 /// use rkyv::{Archive, with::{Map, RefAsBox}};
 ///
 /// #[derive(Archive)]
@@ -287,6 +327,10 @@ const _: () = {
 ///     option: Option<&'a i32>,
 ///     #[with(Map<RefAsBox>)]
 ///     vec: Vec<&'a i32>,
+///     #[with(Map<RefAsBox>)]
+///     btree_map: BTreeMap<u32, &'a str>,
+///     #[with(Map<RefAsBox>)]
+///     hash_map: HashMap<u32, &'a str>,
 /// }
 /// ```
 #[derive(Debug)]
@@ -428,6 +472,51 @@ pub struct RefAsBox;
 #[derive(Debug)]
 pub struct AsString;
 
+/// A wrapper that serializes a `String` as an
+/// [`ArchivedInlineString`](crate::string::ArchivedInlineString) with an inline capacity of `N`
+/// bytes.
+///
+/// Strings of up to `N` bytes are stored inside the archived string instead of behind a relative
+/// pointer. `N` must be at least the size of a relative offset.
+///
+/// Serializing fails if the bytes of a longer string can't be reached with a relative offset. The
+/// default serializers already report this, but custom serializers must provide an error type
+/// satisfying `<S as Fallible>::Error: From<`[`OffsetError`](crate::rel_ptr::OffsetError)`>`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsInlineString};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsInlineString<24>)]
+///     id: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsInlineString<const N: usize>;
+
+/// A wrapper that serializes a `String` as an
+/// [`ArchivedNulTerminatedString`](crate::string::ArchivedNulTerminatedString), which always has a
+/// trailing NUL and a C-compatible layout.
+///
+/// This lets C code reading an archive use the string as a `const char *` directly.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::NulTerminated};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(NulTerminated)]
+///     name: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct NulTerminated;
+
 /// Errors that can occur when serializing a [`AsString`] wrapper.
 #[derive(Debug)]
 pub enum AsStringError {
@@ -444,6 +533,57 @@ impl fmt::Display for AsStringError {
 #[cfg(feature = "std")]
 impl ::std::error::Error for AsStringError {}
 
+/// A wrapper that archives a field as [`Immutable`].
+///
+/// The archived field can be read like the original archived type, but it can't be mutated through
+/// a pinned mutable reference to the archive. This statically protects fields that must not be
+/// edited in place, even when the rest of the archive is accessed mutably.
+///
+/// # Example
+///
+/// ```
+/// use core::pin::Pin;
+/// use rkyv::{archived_root_mut, ser::{serializers::AllocSerializer, Serializer}};
+/// use rkyv::{Archive, Serialize, with::{Immutable, ReadOnly}};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Account {
+///     balance: u64,
+///     #[with(ReadOnly)]
+///     owner: u32,
+/// }
+///
+/// impl ArchivedAccount {
+///     fn balance(self: Pin<&mut Self>) -> Pin<&mut u64> {
+///         unsafe { self.map_unchecked_mut(|s| &mut s.balance) }
+///     }
+///
+///     fn owner(self: Pin<&mut Self>) -> Pin<&mut Immutable<u32>> {
+///         unsafe { self.map_unchecked_mut(|s| &mut s.owner) }
+///     }
+/// }
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Account { balance: 10, owner: 42 }).unwrap();
+/// let mut bytes = serializer.into_serializer().into_inner();
+///
+/// let mut archived = unsafe { archived_root_mut::<Account>(Pin::new(&mut bytes)) };
+/// *archived.as_mut().balance() = 20;
+/// assert_eq!(**archived.as_mut().owner(), 42);
+/// ```
+///
+/// The pinned `Immutable` can't be used to change the owner:
+///
+/// ```compile_fail
+/// # use core::pin::Pin;
+/// # use rkyv::with::Immutable;
+/// fn steal(owner: Pin<&mut Immutable<u32>>, other: Pin<&mut Immutable<u32>>) {
+///     core::mem::swap(owner.get_mut(), other.get_mut());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ReadOnly;
+
 /// A wrapper that locks a lock and serializes the value immutably.
 ///
 /// This wrapper can panic under very specific circumstances when:
@@ -532,6 +672,132 @@ pub struct AsOwned;
 #[derive(Debug)]
 pub struct AsVec;
 
+/// A wrapper that serializes associative containers as a `Vec` of key-value pairs sorted by key.
+///
+/// This archives to the same flat layout as [`AsVec`], but the pairs are sorted so that the
+/// archived entries can be binary searched by key. Sorting requires the keys to implement `Ord`
+/// and uses scratch space to hold the sorted entries during serialization. `BTreeMap` and
+/// `BTreeSet` are already sorted, so this is the same as `AsVec` for them.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}};
+/// use rkyv::{Archive, Serialize, with::AsSortedVec};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     #[with(AsSortedVec)]
+///     values: HashMap<u32, u32>,
+/// }
+///
+/// let mut values = HashMap::new();
+/// for i in 0..10 {
+///     values.insert(i * 2, i);
+/// }
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Example { values }).unwrap();
+/// let bytes = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Example>(&bytes) };
+///
+/// let index = archived.values.binary_search_by_key(&6, |e| e.key).unwrap();
+/// assert_eq!(archived.values[index].value, 3);
+/// ```
+#[derive(Debug)]
+pub struct AsSortedVec;
+
+/// A wrapper that archives a `Vec` of structs as a column for each field.
+///
+/// The struct must be derived with `#[archive(columnar)]`, and archives as its generated
+/// `Archived{Name}Columns` type. See the [`columnar`](crate::columnar) module for more
+/// information.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsColumns};
+///
+/// #[derive(Archive)]
+/// #[archive(columnar)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsColumns)]
+///     points: Vec<Point>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsColumns;
+
+/// A wrapper that serializes a `Vec<bool>` as a packed [`ArchivedBitSet`](crate::collections::ArchivedBitSet).
+///
+/// Each bit takes up a single bit in the archive instead of a whole byte, and can still be read
+/// directly from the archive with [`get`](crate::collections::ArchivedBitSet::get).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsBitSet};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsBitSet)]
+///     flags: Vec<bool>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsBitSet;
+
+/// A wrapper that serializes a `Vec<String>` as an
+/// [`ArchivedDictionaryVec`](crate::collections::ArchivedDictionaryVec).
+///
+/// Each unique string is only archived once, and each element is archived as a `u32` code for its
+/// string. This is much smaller for strings with lots of repeated values, and the elements can
+/// still be read directly from the archive with
+/// [`get`](crate::collections::ArchivedDictionaryVec::get).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsDictionary};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsDictionary)]
+///     hostnames: Vec<String>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsDictionary;
+
+/// A wrapper that serializes a `Vec` of integers as an
+/// [`ArchivedDeltaVec`](crate::collections::ArchivedDeltaVec).
+///
+/// The integers are split into blocks, and each integer is stored as its difference from the
+/// smallest integer in its block using only as many bits as the block needs. This is much smaller
+/// for sorted or slowly-changing integers like timestamps and offsets, and the elements can still be
+/// read in constant time with [`get`](crate::collections::ArchivedDeltaVec::get).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsDelta};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsDelta)]
+///     offsets: Vec<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsDelta;
+
 /// A wrapper that niches some type combinations.
 ///
 /// A common type combination is `Option<Box<T>>`. By using a null pointer, the archived version can
@@ -628,6 +894,60 @@ impl fmt::Display for UnixTimestampError {
 #[cfg(feature = "std")]
 impl ::std::error::Error for UnixTimestampError {}
 
+/// A wrapper that archives an error trait object as the string produced by its `Display` impl.
+///
+/// This is useful for including errors in archived data without archiving the trait object
+/// itself. Deserializing the archived string produces an error with the same `Display` output,
+/// but the original type of the error and its source chain are lost.
+///
+/// # Example
+///
+/// ```
+/// use std::error::Error;
+/// use rkyv::{Archive, with::AsErrorString};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsErrorString)]
+///     error: Box<dyn Error + Send + Sync>,
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct AsErrorString;
+
+/// The resolver for an [`AsErrorString`] wrapper.
+///
+/// The resolver holds the formatted error message so that the error is only formatted once.
+#[cfg(feature = "std")]
+pub struct ErrorStringResolver {
+    message: ::std::string::String,
+    resolver: crate::string::StringResolver,
+}
+
+/// A wrapper that deserializes [`Bytes`](::bytes::Bytes) by pointing into the buffer the archive
+/// is stored in instead of copying.
+///
+/// The deserializer must implement [`BackingBytes`](crate::de::BackingBytes), and the archive it is
+/// deserializing from must be located inside of its backing buffer. Deserializing an archive that
+/// is not located in the backing buffer will panic.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use rkyv::{Archive, with::SharedBytes};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(SharedBytes)]
+///     payload: Bytes,
+/// }
+/// ```
+#[cfg(feature = "bytes")]
+#[derive(Debug)]
+pub struct SharedBytes;
+
 /// A wrapper that provides an optimized bulk data array. This is primarily intended for large
 /// amounts of raw data, like bytes, floats, or integers.
 ///
@@ -670,7 +990,9 @@ pub struct Raw;
 /// that rkyv is unable to guarantee.
 ///
 /// This wrapper enables serializing these types, and places the burden of verifying that their
-/// access semantics are used safely on the user.
+/// access semantics are used safely on the user. Unlike the `Archive` impls for `Cell<T: Copy>` and
+/// `RefCell<T: Clone>`, which archive just the inner value, it also archives the value inside of a
+/// `Cell` or `UnsafeCell`.
 ///
 /// # Safety
 ///
@@ -697,7 +1019,9 @@ pub struct Unsafe;
 
 /// A wrapper that skips serializing a field.
 ///
-/// Skipped fields must implement `Default` to be deserialized.
+/// Skipped fields are archived as `()`, so they don't take up any space in the archive and don't
+/// need to implement `Archive`. This is useful for caches and runtime-only handles. Skipped fields
+/// must implement `Default` to be deserialized, and are set to their default value.
 ///
 /// # Example
 ///
@@ -712,3 +1036,68 @@ pub struct Unsafe;
 /// ```
 #[derive(Debug)]
 pub struct Skip;
+
+/// A pair of functions that transform a field when it's serialized and deserialized.
+///
+/// This is a lighter alternative to implementing [`ArchiveWith`], [`SerializeWith`], and
+/// [`DeserializeWith`] for fields that only need to be converted to and from another archivable
+/// type. Types that implement `Transform` can be used as wrappers with [`Transformed`].
+pub trait Transform<F> {
+    /// The type that the field is converted to before it's archived.
+    type Output: Archive;
+
+    /// Converts the field to the type that gets archived.
+    fn serialize(field: &F) -> Self::Output;
+
+    /// Converts a deserialized value back to the field type.
+    fn deserialize(output: Self::Output) -> F;
+}
+
+/// A wrapper that archives a field by converting it with a [`Transform`].
+///
+/// The field is converted once when it's serialized, and the archived type is the archived type
+/// of the transform's output.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     archived_root,
+///     with::{Transform, Transformed},
+///     Archive, Deserialize, Infallible, Serialize,
+/// };
+///
+/// struct Lowercase;
+///
+/// impl Transform<String> for Lowercase {
+///     type Output = String;
+///
+///     fn serialize(field: &String) -> String {
+///         field.to_lowercase()
+///     }
+///
+///     fn deserialize(output: String) -> String {
+///         output
+///     }
+/// }
+///
+/// #[derive(Archive, Deserialize, Serialize)]
+/// struct Example {
+///     #[with(Transformed<Lowercase>)]
+///     name: String,
+/// }
+///
+/// let value = Example {
+///     name: "Hello World".to_string(),
+/// };
+/// let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+/// let archived = unsafe { archived_root::<Example>(&bytes) };
+/// assert_eq!(archived.name, "hello world");
+///
+/// let deserialized: Example = archived.deserialize(&mut Infallible).unwrap();
+/// assert_eq!(deserialized.name, "hello world");
+/// ```
+#[derive(Debug)]
+pub struct Transformed<T> {
+    _transform: PhantomData<T>,
+}
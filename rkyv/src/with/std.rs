@@ -1,24 +1,101 @@
 use crate::{
-    collections::util::Entry,
+    boxed::{ArchivedBox, BoxResolver},
+    collections::{
+        hash_map::{ArchivedHashMap, HashMapResolver},
+        util::Entry,
+    },
+    ffi::ArchivedOsStr,
+    path::ArchivedPath,
     ser::{ScratchSpace, Serializer},
     string::{ArchivedString, StringResolver},
     time::ArchivedDuration,
     vec::{ArchivedVec, VecResolver},
     with::{
-        ArchiveWith, AsString, AsStringError, AsVec, DeserializeWith, Immutable, Lock, LockError,
-        SerializeWith, UnixTimestamp, UnixTimestampError,
+        ArchiveWith, AsErrorString, AsSortedVec, AsString, AsStringError, AsVec, DeserializeWith,
+        ErrorStringResolver, Immutable, Lock, LockError, Map, RefAsBox, SerializeWith,
+        UnixTimestamp, UnixTimestampError, With,
     },
-    Archive, Deserialize, Fallible, Serialize, SerializeUnsized,
+    Archive, Deserialize, Fallible, ScratchVec, Serialize, SerializeUnsized,
+};
+use core::{
+    hash::{BuildHasher, Hash},
+    str::FromStr,
 };
-use core::{hash::Hash, str::FromStr};
 use std::{
     collections::{HashMap, HashSet},
+    error::Error,
     ffi::OsString,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    string::{String, ToString},
     sync::{Mutex, RwLock},
     time::{SystemTime, UNIX_EPOCH},
 };
 
+// Map for HashMaps
+
+impl<A, K, V, H> ArchiveWith<HashMap<K, V, H>> for Map<A>
+where
+    A: ArchiveWith<V>,
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+{
+    type Archived = ArchivedHashMap<K::Archived, <A as ArchiveWith<V>>::Archived>;
+    type Resolver = HashMapResolver;
+
+    unsafe fn resolve_with(
+        field: &HashMap<K, V, H>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<A, K, V, H, S> SerializeWith<HashMap<K, V, H>, S> for Map<A>
+where
+    A: SerializeWith<V, S>,
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    fn serialize_with(field: &HashMap<K, V, H>, s: &mut S) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            ArchivedHashMap::serialize_from_iter(
+                field
+                    .iter()
+                    .map(|(key, value)| (key, With::<V, A>::cast(value))),
+                s,
+            )
+        }
+    }
+}
+
+impl<A, K, V, H, D>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, <A as ArchiveWith<V>>::Archived>,
+        HashMap<K, V, H>,
+        D,
+    > for Map<A>
+where
+    A: ArchiveWith<V> + DeserializeWith<<A as ArchiveWith<V>>::Archived, V, D>,
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    H: Default + BuildHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, <A as ArchiveWith<V>>::Archived>,
+        d: &mut D,
+    ) -> Result<HashMap<K, V, H>, D::Error> {
+        let mut result = HashMap::with_capacity_and_hasher(field.len(), H::default());
+        for (key, value) in field.iter() {
+            result.insert(key.deserialize(d)?, A::deserialize_with(value, d)?);
+        }
+        Ok(result)
+    }
+}
+
 // AsString
 
 impl ArchiveWith<OsString> for AsString {
@@ -97,6 +174,84 @@ impl<D: Fallible + ?Sized> DeserializeWith<ArchivedString, PathBuf, D> for AsStr
     }
 }
 
+// RefAsBox
+
+// Paths are archived through their OS strings, like `Box<Path>`
+impl ArchiveWith<&Path> for RefAsBox {
+    type Archived = ArchivedBox<ArchivedPath>;
+    type Resolver = BoxResolver<()>;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &&Path,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedBox::resolve_from_ref(field.as_os_str(), pos, resolver, out.cast());
+    }
+}
+
+impl<S: Serializer + ?Sized> SerializeWith<&Path, S> for RefAsBox {
+    #[inline]
+    fn serialize_with(field: &&Path, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedBox::<ArchivedOsStr>::serialize_from_ref(field.as_os_str(), serializer)
+    }
+}
+
+// AsErrorString
+
+macro_rules! impl_as_error_string {
+    ($error:ty) => {
+        impl ArchiveWith<Box<$error>> for AsErrorString {
+            type Archived = ArchivedString;
+            type Resolver = ErrorStringResolver;
+
+            #[inline]
+            unsafe fn resolve_with(
+                _: &Box<$error>,
+                pos: usize,
+                resolver: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                ArchivedString::resolve_from_str(&resolver.message, pos, resolver.resolver, out);
+            }
+        }
+
+        impl<S: Fallible + ?Sized> SerializeWith<Box<$error>, S> for AsErrorString
+        where
+            str: SerializeUnsized<S>,
+        {
+            #[inline]
+            fn serialize_with(
+                field: &Box<$error>,
+                serializer: &mut S,
+            ) -> Result<Self::Resolver, S::Error> {
+                let message = field.to_string();
+                let resolver = ArchivedString::serialize_from_str(&message, serializer)?;
+                Ok(ErrorStringResolver { message, resolver })
+            }
+        }
+
+        impl<D: Fallible + ?Sized> DeserializeWith<ArchivedString, Box<$error>, D>
+            for AsErrorString
+        {
+            #[inline]
+            fn deserialize_with(
+                field: &ArchivedString,
+                _: &mut D,
+            ) -> Result<Box<$error>, D::Error> {
+                let error: Box<dyn Error + Send + Sync> = String::from(field.as_str()).into();
+                Ok(error)
+            }
+        }
+    };
+}
+
+impl_as_error_string!(dyn Error);
+impl_as_error_string!(dyn Error + Send);
+impl_as_error_string!(dyn Error + Send + Sync);
+
 // Lock
 
 impl<F: Archive> ArchiveWith<Mutex<F>> for Lock {
@@ -290,6 +445,123 @@ where
     }
 }
 
+// AsSortedVec
+
+impl<K: Archive, V: Archive> ArchiveWith<HashMap<K, V>> for AsSortedVec {
+    type Archived = ArchivedVec<Entry<K::Archived, V::Archived>>;
+    type Resolver = VecResolver;
+
+    unsafe fn resolve_with(
+        field: &HashMap<K, V>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<K, V, S> SerializeWith<HashMap<K, V>, S> for AsSortedVec
+where
+    K: Serialize<S> + Ord,
+    V: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    fn serialize_with(
+        field: &HashMap<K, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            let mut entries = ScratchVec::new(serializer, field.len())?;
+            for entry in field.iter() {
+                entries.push(entry);
+            }
+            entries.sort_unstable_by_key(|&(key, _)| key);
+
+            let resolver = ArchivedVec::serialize_from_iter(
+                entries.iter().map(|&(key, value)| Entry { key, value }),
+                serializer,
+            )?;
+
+            entries.free(serializer)?;
+
+            Ok(resolver)
+        }
+    }
+}
+
+impl<K, V, D> DeserializeWith<ArchivedVec<Entry<K::Archived, V::Archived>>, HashMap<K, V>, D>
+    for AsSortedVec
+where
+    K: Archive + Hash + Eq,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedVec<Entry<K::Archived, V::Archived>>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V>, D::Error> {
+        AsVec::deserialize_with(field, deserializer)
+    }
+}
+
+impl<T: Archive> ArchiveWith<HashSet<T>> for AsSortedVec {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    unsafe fn resolve_with(
+        field: &HashSet<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<HashSet<T>, S> for AsSortedVec
+where
+    T: Serialize<S> + Ord,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    fn serialize_with(field: &HashSet<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            let mut keys = ScratchVec::new(serializer, field.len())?;
+            for key in field.iter() {
+                keys.push(key);
+            }
+            keys.sort_unstable();
+
+            let resolver = ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _, _>(
+                keys.iter().copied(),
+                serializer,
+            )?;
+
+            keys.free(serializer)?;
+
+            Ok(resolver)
+        }
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, HashSet<T>, D> for AsSortedVec
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<T>, D::Error> {
+        AsVec::deserialize_with(field, deserializer)
+    }
+}
+
 // UnixTimestamp
 
 impl ArchiveWith<SystemTime> for UnixTimestamp {
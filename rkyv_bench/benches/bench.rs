@@ -5,12 +5,46 @@ use rand_pcg::Lcg64Xsh32;
 use rkyv::{
     archived_root, check_archived_root,
     ser::{
-        serializers::{AlignedSerializer, BufferScratch, CompositeSerializer},
+        serializers::{AlignedSerializer, AllocSerializer, BufferScratch, CompositeSerializer},
         Serializer,
     },
     AlignedVec, Archive, Deserialize, Infallible, Serialize,
 };
-use std::collections::HashMap;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Counts heap allocations so that benchmarks can report how many they make.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<F: FnOnce()>(f: F) -> usize {
+    let start = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - start
+}
 
 trait Generate {
     fn generate<R: Rng>(rng: &mut R) -> Self;
@@ -450,6 +484,41 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
     }
     group.finish();
+
+    // Serializing with no pre-allocated scratch space spills every scratch allocation to the heap
+    let mut group = c.benchmark_group("rkyv_alloc_scratch");
+    {
+        let nested = (0..10_000)
+            .map(|i| (0..i % 10).map(|j| j.to_string()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        fn serialize<T: Serialize<AllocSerializer<0>>>(value: &T) -> AlignedVec {
+            let mut serializer = AllocSerializer::<0>::default();
+            serializer.serialize_value(value).unwrap();
+            serializer.into_serializer().into_inner()
+        }
+
+        println!(
+            "rkyv_alloc_scratch/serialize players: {} heap allocations",
+            count_allocations(|| {
+                black_box(serialize(&players));
+            })
+        );
+        println!(
+            "rkyv_alloc_scratch/serialize nested: {} heap allocations",
+            count_allocations(|| {
+                black_box(serialize(&nested));
+            })
+        );
+
+        group.bench_function("serialize players", |b| {
+            b.iter(|| black_box(serialize(black_box(&players))));
+        });
+        group.bench_function("serialize nested", |b| {
+            b.iter(|| black_box(serialize(black_box(&nested))));
+        });
+    }
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
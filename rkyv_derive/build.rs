@@ -0,0 +1,16 @@
+use std::{env, process::Command};
+
+fn main() {
+    // The diagnostic namespace was stabilized in Rust 1.78
+    println!("cargo:rustc-check-cfg=cfg(has_diagnostic_namespace)");
+    if matches!(rustc_minor_version(), Some(minor) if minor >= 78) {
+        println!("cargo:rustc-cfg=has_diagnostic_namespace");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split('.').nth(1)?.parse().ok()
+}
@@ -1,22 +1,46 @@
 use crate::{
-    attributes::{parse_attributes, Attributes},
+    attributes::{
+        archived_field_name, collect_attributes, parse_field_attributes, Attributes,
+        FieldAttributes,
+    },
+    c_header, columnar, layout,
     repr::{BaseRepr, IntRepr, Repr},
-    util::{add_bounds, strip_raw},
-    with::{make_with_cast, make_with_ty},
+    schema, tail,
+    util::{
+        add_bounds, field_bound, is_string, mentions_generics, omit_bounds, strip_raw,
+        to_snake_case, type_name, Errors,
+    },
+    with::{make_with_cast, make_with_ty, with},
 };
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
-    parse_quote, spanned::Spanned, Attribute, Data, DeriveInput, Error, Field, Fields, Ident,
-    Index, Meta, NestedMeta, Type,
+    parse_quote, spanned::Spanned, Attribute, Data, DeriveInput, Error, Expr, ExprLit, ExprUnary,
+    Field, Fields, FieldsNamed, Ident, Index, Lit, Meta, NestedMeta, Type, UnOp, Visibility,
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
-    let attributes = parse_attributes(&input)?;
-    derive_archive_impl(input, &attributes)
+    // Report every problem with the attributes at once instead of stopping at the first one
+    let mut errors = Errors::default();
+    let attributes = collect_attributes(&input, &mut errors);
+    check_attributes(&input, &attributes, &mut errors);
+    errors.finish()?;
+    if attributes.unsized_tail.is_some() {
+        return tail::derive_archive_impl(input, &attributes);
+    }
+    let columns_impl = if attributes.columnar.is_some() {
+        Some(columnar::derive_archive_impl(input.clone(), &attributes)?)
+    } else {
+        None
+    };
+    let result = derive_archive_impl(input, &attributes)?;
+    Ok(quote! {
+        #result
+        #columns_impl
+    })
 }
 
-fn field_archive_attrs(field: &Field) -> impl '_ + Iterator<Item = NestedMeta> {
+pub fn field_archive_attrs(field: &Field) -> impl '_ + Iterator<Item = NestedMeta> {
     field
         .attrs
         .iter()
@@ -45,6 +69,8 @@ fn derive_archive_impl(
 
     let name = &input.ident;
     let vis = &input.vis;
+    let archived_vis = attributes.archived_vis.as_ref().unwrap_or(vis);
+    let resolver_vis = attributes.resolver_vis.as_ref().unwrap_or(vis);
     let generics = &input.generics;
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
@@ -55,48 +81,17 @@ fn derive_archive_impl(
     let with_ty = make_with_ty(rkyv_path);
     let with_cast = make_with_cast(rkyv_path);
 
+    // Archived types are as non-exhaustive as the types they archive
+    let non_exhaustive = input
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("non_exhaustive"))
+        .cloned();
     let archive_attrs = attributes
         .attrs
         .iter()
-        .map::<Attribute, _>(|d| parse_quote! { #[#d] });
-
-    if let Some(ref archive_as) = attributes.archive_as {
-        if let Some(ref ident) = attributes.archived {
-            return Err(Error::new_spanned(
-                ident,
-                "archived = \"...\" may not be used with as = \"...\" because no type is generated",
-            ));
-        }
-        if let Some(first) = attributes.attrs.first() {
-            return Err(Error::new_spanned(
-                first,
-                format!(
-                    "\
-                        archive_attr(...) may not be used with as = \"...\"\n\
-                        place any attributes on the archived type ({}) instead\
-                    ",
-                    archive_as.value(),
-                ),
-            ));
-        }
-        if let Some(span) = attributes
-            .archived_repr
-            .base_repr
-            .map(|(_, s)| s)
-            .or_else(|| attributes.archived_repr.modifier.as_ref().map(|(_, s)| *s))
-        {
-            return Err(Error::new(
-                span,
-                format!(
-                    "\
-                        repr(...) may not be used with as = \"...\"\n\
-                        place the repr attribute on the archived type ({}) instead\
-                    ",
-                    archive_as.value()
-                ),
-            ));
-        }
-    }
+        .map::<Attribute, _>(|d| parse_quote! { #[#d] })
+        .chain(non_exhaustive.clone());
 
     let archived_name = attributes.archived.as_ref().map_or_else(
         || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
@@ -115,6 +110,9 @@ fn derive_archive_impl(
     );
     let resolver_doc = format!("The resolver for an archived [`{}`]", name);
 
+    // The schema of an enum describes its tag, which is only known once the enum is generated
+    let mut enum_tag_repr = None;
+
     let (archive_types, archive_impls) = match input.data {
         Data::Struct(ref data) => {
             let base_repr = if cfg!(feature = "strict") {
@@ -129,6 +127,14 @@ fn derive_archive_impl(
                     // If unspecified, the base repr is set to C
                     None => (BaseRepr::C, Span::call_site()),
                 })
+            } else if attributes.c_header.is_some() {
+                // C headers describe the archived struct as repr(C)
+                Some(
+                    attributes
+                        .archived_repr
+                        .base_repr
+                        .unwrap_or((BaseRepr::C, Span::call_site())),
+                )
             } else {
                 attributes.archived_repr.base_repr
             };
@@ -140,15 +146,11 @@ fn derive_archive_impl(
             match data.fields {
                 Fields::Named(ref fields) => {
                     let mut archive_where = where_clause.clone();
-                    for field in fields
-                        .named
-                        .iter()
-                        .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                    {
+                    for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                         let ty = with_ty(field)?;
                         archive_where
                             .predicates
-                            .push(parse_quote! { #ty: #rkyv_path::Archive });
+                            .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
                     }
 
                     let resolver_fields = fields.named.iter().map(|f| {
@@ -158,14 +160,16 @@ fn derive_archive_impl(
                     });
 
                     let archived_def = if attributes.archive_as.is_none() {
-                        let archived_fields = fields.named.iter().map(|f| {
-                            let field_name = f.ident.as_ref();
+                        let ordered_fields =
+                            archived_field_order(fields, attributes.reorder.is_some());
+                        let archived_fields = ordered_fields.into_iter().map(|f| {
+                            let field_name = archived_field_name(f).unwrap();
                             let ty = with_ty(f).unwrap();
                             let vis = &f.vis;
                             let field_doc = format!(
                                 "The archived counterpart of [`{}::{}`]",
                                 name,
-                                field_name.unwrap()
+                                f.ident.as_ref().unwrap()
                             );
                             let archive_attrs = field_archive_attrs(f);
                             quote! {
@@ -180,7 +184,7 @@ fn derive_archive_impl(
                             #[doc = #archived_doc]
                             #(#archive_attrs)*
                             #repr
-                            #vis struct #archived_name #generics #archive_where {
+                            #archived_vis struct #archived_name #generics #archive_where {
                                 #(#archived_fields,)*
                             }
                         })
@@ -190,22 +194,56 @@ fn derive_archive_impl(
 
                     let resolve_fields = fields.named.iter().map(|f| {
                         let name = &f.ident;
+                        let archived_name = archived_field_name(f).unwrap();
                         let field = with_cast(f, parse_quote! { (&self.#name) }).unwrap();
                         quote! {
-                            let (fp, fo) = out_field!(out.#name);
+                            let (fp, fo) = out_field!(out.#archived_name);
                             #rkyv_path::Archive::resolve(#field, pos + fp, resolver.#name, fo);
                         }
                     });
 
+                    let accessors_impl = if attributes.accessors.is_some() {
+                        let accessors = fields.named.iter().map(|f| {
+                            let field_name = archived_field_name(f).unwrap();
+                            let ty = with_ty(f).unwrap();
+                            let doc =
+                                format!("Returns the archived `{}` field", strip_raw(&field_name));
+                            let has_with = f.attrs.iter().any(|a| a.path.is_ident("with"));
+                            if is_string(&f.ty) && !has_with {
+                                quote! {
+                                    #[doc = #doc]
+                                    #[inline]
+                                    pub fn #field_name(&self) -> &str {
+                                        self.#field_name.as_str()
+                                    }
+                                }
+                            } else {
+                                quote! {
+                                    #[doc = #doc]
+                                    #[inline]
+                                    pub fn #field_name(&self) -> &#rkyv_path::Archived<#ty> {
+                                        &self.#field_name
+                                    }
+                                }
+                            }
+                        });
+
+                        Some(quote! {
+                            impl #impl_generics #archived_type #archive_where {
+                                #(#accessors)*
+                            }
+                        })
+                    } else {
+                        None
+                    };
+
                     let mut partial_eq_impl = None;
                     let mut partial_ord_impl = None;
                     if let Some((_, ref compares)) = attributes.compares {
                         for compare in compares {
                             if compare.is_ident("PartialEq") {
                                 let mut partial_eq_where = archive_where.clone();
-                                for field in fields.named.iter().filter(|f| {
-                                    !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                }) {
+                                for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                                     let ty = &field.ty;
                                     let wrapped_ty = with_ty(field).unwrap();
                                     partial_eq_where.predicates.push(
@@ -214,12 +252,14 @@ fn derive_archive_impl(
                                 }
 
                                 let field_names = fields.named.iter().map(|f| &f.ident);
+                                let archived_field_names =
+                                    fields.named.iter().map(|f| archived_field_name(f).unwrap());
 
                                 partial_eq_impl = Some(quote! {
                                     impl #impl_generics PartialEq<#archived_type> for #name #ty_generics #partial_eq_where {
                                         #[inline]
                                         fn eq(&self, other: &#archived_type) -> bool {
-                                            true #(&& other.#field_names.eq(&self.#field_names))*
+                                            true #(&& other.#archived_field_names.eq(&self.#field_names))*
                                         }
                                     }
 
@@ -232,9 +272,7 @@ fn derive_archive_impl(
                                 });
                             } else if compare.is_ident("PartialOrd") {
                                 let mut partial_ord_where = archive_where.clone();
-                                for field in fields.named.iter().filter(|f| {
-                                    !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                }) {
+                                for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                                     let ty = &field.ty;
                                     let archived_ty = with_ty(field).unwrap();
                                     partial_ord_where.predicates.push(
@@ -243,13 +281,15 @@ fn derive_archive_impl(
                                 }
 
                                 let field_names = fields.named.iter().map(|f| &f.ident);
+                                let archived_field_names =
+                                    fields.named.iter().map(|f| archived_field_name(f).unwrap());
 
                                 partial_ord_impl = Some(quote! {
                                     impl #impl_generics PartialOrd<#archived_type> for #name #ty_generics #partial_ord_where {
                                         #[inline]
                                         fn partial_cmp(&self, other: &#archived_type) -> Option<::core::cmp::Ordering> {
                                             #(
-                                                match other.#field_names.partial_cmp(&self.#field_names) {
+                                                match other.#archived_field_names.partial_cmp(&self.#field_names) {
                                                     Some(::core::cmp::Ordering::Equal) => (),
                                                     x => return x,
                                                 }
@@ -277,11 +317,7 @@ fn derive_archive_impl(
                     let copy_safe_impl = if cfg!(feature = "copy") && attributes.copy_safe.is_some()
                     {
                         let mut copy_safe_where = where_clause.clone();
-                        for field in fields
-                            .named
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field).unwrap();
                             copy_safe_where
                                 .predicates
@@ -295,46 +331,229 @@ fn derive_archive_impl(
                         None
                     };
 
-                    (
-                        quote! {
-                            #archived_def
+                    if attributes.extensible.is_some() {
+                        let fields_name = Ident::new(
+                            &format!("{}Fields", strip_raw(&archived_name)),
+                            name.span(),
+                        );
+                        let fields_doc = format!("The archived fields of [`{}`]", name);
+                        let archive_attrs = attributes
+                            .attrs
+                            .iter()
+                            .map::<Attribute, _>(|d| parse_quote! { #[#d] })
+                            .chain(non_exhaustive.clone());
 
-                            #[automatically_derived]
-                            #[doc = #resolver_doc]
-                            #vis struct #resolver #generics #archive_where {
-                                #(#resolver_fields,)*
+                        let archived_fields = fields.named.iter().map(|f| {
+                            let field_name = archived_field_name(f).unwrap();
+                            let ty = with_ty(f).unwrap();
+                            let vis = &f.vis;
+                            let field_doc = format!(
+                                "The archived counterpart of [`{}::{}`]",
+                                name,
+                                f.ident.as_ref().unwrap()
+                            );
+                            let archive_attrs = field_archive_attrs(f);
+                            quote! {
+                                #[doc = #field_doc]
+                                #(#[#archive_attrs])*
+                                #vis #field_name: #rkyv_path::Archived<#ty>
                             }
-                        },
-                        quote! {
-                            impl #impl_generics Archive for #name #ty_generics #archive_where {
-                                type Archived = #archived_type;
-                                type Resolver = #resolver #ty_generics;
+                        });
 
-                                // Some resolvers will be (), this allow is to prevent clippy from complaining
-                                #[allow(clippy::unit_arg)]
+                        let accessors = fields.named.iter().map(|f| {
+                            let field_name = archived_field_name(f).unwrap();
+                            let ty = with_ty(f).unwrap();
+                            let doc = format!(
+                                "Returns the archived `{}` field, or `None` if it is missing from the archive",
+                                strip_raw(&field_name)
+                            );
+                            quote! {
+                                #[doc = #doc]
                                 #[inline]
-                                unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
-                                    #(#resolve_fields)*
+                                pub fn #field_name(&self) -> Option<&#rkyv_path::Archived<#ty>> {
+                                    let mut fields = ::core::mem::MaybeUninit::<#fields_name #ty_generics>::uninit();
+                                    let out = fields.as_mut_ptr();
+                                    let (offset, _) = out_field!(out.#field_name);
+                                    // SAFETY: `offset` is the offset of the field in the archived fields.
+                                    unsafe { self.fields.field(offset) }
                                 }
                             }
+                        });
 
-                            #partial_eq_impl
-                            #partial_ord_impl
-                            #copy_safe_impl
-                        },
-                    )
+                        let fields_len = match fields.named.last() {
+                            Some(f) => {
+                                let field_name = archived_field_name(f)?;
+                                let ty = with_ty(f)?;
+                                quote! {
+                                    let mut fields = ::core::mem::MaybeUninit::<Self>::uninit();
+                                    let out = fields.as_mut_ptr();
+                                    let (offset, _) = out_field!(out.#field_name);
+                                    offset + ::core::mem::size_of::<#rkyv_path::Archived<#ty>>()
+                                }
+                            }
+                            None => quote! { 0 },
+                        };
+
+                        let check_fields_impl = if cfg!(feature = "validation") {
+                            let mut check_where = archive_where.clone();
+                            for field in fields.named.iter().filter(|f| {
+                                !field_archive_attrs(f).any(|m| {
+                                    matches!(m, NestedMeta::Meta(Meta::Path(ref p)) if p.is_ident("omit_bounds"))
+                                })
+                            }) {
+                                let ty = with_ty(field)?;
+                                check_where.predicates.push(parse_quote! {
+                                    #rkyv_path::Archived<#ty>: #rkyv_path::bytecheck::CheckBytes<__C>
+                                });
+                            }
+
+                            let check_fields = fields
+                                .named
+                                .iter()
+                                .map(|f| {
+                                    let field_name = archived_field_name(f)?;
+                                    let ty = with_ty(f)?;
+                                    let name_str = strip_raw(&field_name);
+                                    Ok(quote! {
+                                        let (offset, _) = out_field!(out.#field_name);
+                                        if offset + ::core::mem::size_of::<#rkyv_path::Archived<#ty>>() <= len {
+                                            <#rkyv_path::Archived<#ty> as #rkyv_path::bytecheck::CheckBytes<__C>>::check_bytes(
+                                                value.cast::<u8>().add(offset).cast(),
+                                                context,
+                                            )
+                                            .map_err(|e| #rkyv_path::bytecheck::StructCheckError {
+                                                field_name: #name_str,
+                                                inner: #rkyv_path::bytecheck::ErrorBox::new(e),
+                                            })?;
+                                        }
+                                    })
+                                })
+                                .collect::<Result<Vec<_>, Error>>()?;
+
+                            let mut check_generics = generics.clone();
+                            check_generics.params.push(parse_quote! { __C: ?Sized });
+                            let (check_impl_generics, _, _) = check_generics.split_for_impl();
+
+                            Some(quote! {
+                                impl #check_impl_generics #rkyv_path::extensible::validation::CheckFields<__C> for #fields_name #ty_generics #check_where {
+                                    #[inline]
+                                    #[allow(unused_variables)]
+                                    unsafe fn check_fields(
+                                        value: *const Self,
+                                        len: usize,
+                                        context: &mut __C,
+                                    ) -> ::core::result::Result<(), #rkyv_path::bytecheck::StructCheckError> {
+                                        let mut fields = ::core::mem::MaybeUninit::<Self>::uninit();
+                                        let out = fields.as_mut_ptr();
+                                        #(#check_fields)*
+                                        Ok(())
+                                    }
+                                }
+                            })
+                        } else {
+                            None
+                        };
+
+                        (
+                            quote! {
+                                #[automatically_derived]
+                                #[doc = #fields_doc]
+                                #[repr(C)]
+                                #archived_vis struct #fields_name #generics #archive_where {
+                                    #(#archived_fields,)*
+                                }
+
+                                #[automatically_derived]
+                                #[doc = #archived_doc]
+                                #(#archive_attrs)*
+                                #repr
+                                #archived_vis struct #archived_name #generics #archive_where {
+                                    fields: #rkyv_path::extensible::ArchivedFields<#fields_name #ty_generics>,
+                                }
+
+                                #[automatically_derived]
+                                #[doc = #resolver_doc]
+                                #resolver_vis struct #resolver #generics #archive_where {
+                                    #(#resolver_fields,)*
+                                }
+                            },
+                            quote! {
+                                unsafe impl #impl_generics #rkyv_path::extensible::ExtensibleFields for #fields_name #ty_generics #archive_where {
+                                    #[inline]
+                                    fn fields_len() -> usize {
+                                        #fields_len
+                                    }
+                                }
+
+                                #check_fields_impl
+
+                                impl #impl_generics #rkyv_path::extensible::ArchiveFields for #name #ty_generics #archive_where {
+                                    type Fields = #fields_name #ty_generics;
+                                    type FieldsResolver = #resolver #ty_generics;
+
+                                    // Some resolvers will be (), this allow is to prevent clippy from complaining
+                                    #[allow(clippy::unit_arg)]
+                                    #[inline]
+                                    unsafe fn resolve_fields(&self, pos: usize, resolver: Self::FieldsResolver, out: *mut Self::Fields) {
+                                        #(#resolve_fields)*
+                                    }
+                                }
+
+                                impl #impl_generics Archive for #name #ty_generics #archive_where {
+                                    type Archived = #archived_type;
+                                    type Resolver = #rkyv_path::extensible::ExtensibleResolver;
+
+                                    #[inline]
+                                    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                                        let (fp, fo) = out_field!(out.fields);
+                                        #rkyv_path::extensible::ArchivedFields::resolve_from_pos(pos + fp, resolver, fo);
+                                    }
+                                }
+
+                                impl #impl_generics #archived_type #archive_where {
+                                    #(#accessors)*
+                                }
+                            },
+                        )
+                    } else {
+                        (
+                            quote! {
+                                #archived_def
+
+                                #[automatically_derived]
+                                #[doc = #resolver_doc]
+                                #resolver_vis struct #resolver #generics #archive_where {
+                                    #(#resolver_fields,)*
+                                }
+                            },
+                            quote! {
+                                impl #impl_generics Archive for #name #ty_generics #archive_where {
+                                    type Archived = #archived_type;
+                                    type Resolver = #resolver #ty_generics;
+
+                                    // Some resolvers will be (), this allow is to prevent clippy from complaining
+                                    #[allow(clippy::unit_arg)]
+                                    #[inline]
+                                    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                                        #(#resolve_fields)*
+                                    }
+                                }
+
+                                #partial_eq_impl
+                                #partial_ord_impl
+                                #copy_safe_impl
+                                #accessors_impl
+                            },
+                        )
+                    }
                 }
                 Fields::Unnamed(ref fields) => {
                     let mut archive_where = where_clause.clone();
-                    for field in fields
-                        .unnamed
-                        .iter()
-                        .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                    {
+                    for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                         let ty = with_ty(field)?;
                         archive_where
                             .predicates
-                            .push(parse_quote! { #ty: #rkyv_path::Archive });
+                            .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
                     }
 
                     let resolver_fields = fields.unnamed.iter().map(|f| {
@@ -361,7 +580,7 @@ fn derive_archive_impl(
                             #[doc = #archived_doc]
                             #(#archive_attrs)*
                             #repr
-                            #vis struct #archived_name #generics (#(#archived_fields,)*) #archive_where;
+                            #archived_vis struct #archived_name #generics (#(#archived_fields,)*) #archive_where;
                         })
                     } else {
                         None
@@ -382,9 +601,7 @@ fn derive_archive_impl(
                         for compare in compares {
                             if compare.is_ident("PartialEq") {
                                 let mut partial_eq_where = archive_where.clone();
-                                for field in fields.unnamed.iter().filter(|f| {
-                                    !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                }) {
+                                for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                                     let ty = &field.ty;
                                     let wrapped_ty = with_ty(field).unwrap();
                                     partial_eq_where.predicates.push(
@@ -415,9 +632,7 @@ fn derive_archive_impl(
                                 });
                             } else if compare.is_ident("PartialOrd") {
                                 let mut partial_ord_where = archive_where.clone();
-                                for field in fields.unnamed.iter().filter(|f| {
-                                    !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                }) {
+                                for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                                     let ty = &field.ty;
                                     let wrapped_ty = with_ty(field).unwrap();
                                     partial_ord_where.predicates.push(
@@ -461,11 +676,7 @@ fn derive_archive_impl(
                     let copy_safe_impl = if cfg!(feature = "copy") && attributes.copy_safe.is_some()
                     {
                         let mut copy_safe_where = where_clause.clone();
-                        for field in fields
-                            .unnamed
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field).unwrap();
                             copy_safe_where
                                 .predicates
@@ -485,7 +696,7 @@ fn derive_archive_impl(
 
                             #[automatically_derived]
                             #[doc = #resolver_doc]
-                            #vis struct #resolver #generics (#(#resolver_fields,)*) #archive_where;
+                            #resolver_vis struct #resolver #generics (#(#resolver_fields,)*) #archive_where;
                         },
                         quote! {
                             impl #impl_generics Archive for #name #ty_generics #archive_where {
@@ -513,7 +724,7 @@ fn derive_archive_impl(
                             #[doc = #archived_doc]
                             #(#archive_attrs)*
                             #repr
-                            #vis struct #archived_name #generics
+                            #archived_vis struct #archived_name #generics
                             #where_clause;
                         })
                     } else {
@@ -580,7 +791,7 @@ fn derive_archive_impl(
 
                             #[automatically_derived]
                             #[doc = #resolver_doc]
-                            #vis struct #resolver #generics
+                            #resolver_vis struct #resolver #generics
                             #where_clause;
                         },
                         quote! {
@@ -605,27 +816,19 @@ fn derive_archive_impl(
             for variant in data.variants.iter() {
                 match variant.fields {
                     Fields::Named(ref fields) => {
-                        for field in fields
-                            .named
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field)?;
                             archive_where
                                 .predicates
-                                .push(parse_quote! { #ty: #rkyv_path::Archive });
+                                .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
                         }
                     }
                     Fields::Unnamed(ref fields) => {
-                        for field in fields
-                            .unnamed
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field)?;
                             archive_where
                                 .predicates
-                                .push(parse_quote! { #ty: #rkyv_path::Archive });
+                                .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
                         }
                     }
                     Fields::Unit => (),
@@ -768,6 +971,20 @@ fn derive_archive_impl(
                 }
             });
 
+            // Variants without an explicit discriminant take the discriminant after the previous
+            // variant, just like they do in the unarchived enum
+            let has_explicit_discriminants = data.variants.iter().any(|v| v.discriminant.is_some());
+            let mut discriminants = Vec::with_capacity(data.variants.len());
+            let mut next_discriminant = 0;
+            for variant in data.variants.iter() {
+                let discriminant = match variant.discriminant {
+                    Some((_, ref expr)) => parse_discriminant(expr)?,
+                    None => next_discriminant,
+                };
+                discriminants.push(discriminant);
+                next_discriminant = discriminant.wrapping_add(1);
+            }
+
             let (int_repr, int_repr_span) = match attributes.archived_repr.base_repr {
                 // The base repr for enums may not be Rust, transparent, or C
                 Some((BaseRepr::Transparent | BaseRepr::C, span)) => {
@@ -775,6 +992,36 @@ fn derive_archive_impl(
                 }
                 // The base repr for enums may be i*/u*
                 Some((BaseRepr::Int(int_repr), span)) => (int_repr, span),
+                // If unspecified and the enum has explicit discriminants, the base repr is the
+                // integer repr of the unarchived enum or the smallest integer that can represent
+                // all of the discriminants
+                None if has_explicit_discriminants => {
+                    let int_repr = source_int_repr(&input.attrs).unwrap_or_else(|| {
+                        let candidates = if discriminants.iter().all(|&d| d >= 0) {
+                            [
+                                IntRepr::U8,
+                                IntRepr::U16,
+                                IntRepr::U32,
+                                IntRepr::U64,
+                                IntRepr::U128,
+                            ]
+                        } else {
+                            [
+                                IntRepr::I8,
+                                IntRepr::I16,
+                                IntRepr::I32,
+                                IntRepr::I64,
+                                IntRepr::I128,
+                            ]
+                        };
+                        candidates
+                            .iter()
+                            .copied()
+                            .find(|r| discriminants.iter().all(|&d| r.contains(d)))
+                            .unwrap_or(IntRepr::I128)
+                    });
+                    (int_repr, Span::call_site())
+                }
                 // If unspecified, the base repr is set to u* with the smallest unsigned integer
                 // that can represent the number of variants
                 None => {
@@ -788,11 +1035,24 @@ fn derive_archive_impl(
                     (int_repr, Span::call_site())
                 }
             };
+            enum_tag_repr = Some(int_repr);
             let repr = Repr {
                 base_repr: Some((BaseRepr::Int(int_repr), int_repr_span)),
                 modifier: attributes.archived_repr.modifier.clone(),
             };
 
+            for (variant, &discriminant) in data.variants.iter().zip(discriminants.iter()) {
+                if !int_repr.contains(discriminant) {
+                    return Err(Error::new_spanned(
+                        variant,
+                        format!(
+                            "the discriminant of this variant does not fit in the archived repr ({})",
+                            quote! { #int_repr },
+                        ),
+                    ));
+                }
+            }
+
             let is_fieldless = data
                 .variants
                 .iter()
@@ -801,7 +1061,10 @@ fn derive_archive_impl(
                 not(feature = "arbitrary_enum_discriminant"),
                 any(feature = "archive_le", feature = "archive_be")
             ))]
-            if !is_fieldless && !matches!(int_repr, IntRepr::U8 | IntRepr::I8) {
+            if !is_fieldless
+                && !has_explicit_discriminants
+                && !matches!(int_repr, IntRepr::U8 | IntRepr::I8)
+            {
                 return Err(Error::new_spanned(
                     name,
                     "\
@@ -811,15 +1074,26 @@ fn derive_archive_impl(
                 ));
             }
 
+            let tag_name = Ident::new(
+                &format!("{}Tag", strip_raw(&archived_name)),
+                archived_name.span(),
+            );
+            let tag_doc = format!("The variant tags of [`{}`]", archived_name);
+
             let archived_def = if attributes.archive_as.is_none() {
                 let archived_variants = data.variants.iter().enumerate().map(|(i, v)| {
                     let variant = &v.ident;
-                    let discriminant =
-                        if is_fieldless || cfg!(feature = "arbitrary_enum_discriminant") {
-                            Some(int_repr.enum_discriminant(i))
-                        } else {
-                            None
-                        };
+                    let discriminant = if has_explicit_discriminants {
+                        let discriminant = int_repr.explicit_discriminant(discriminants[i]);
+                        quote! { #discriminant }
+                    } else if is_fieldless || cfg!(feature = "arbitrary_enum_discriminant") {
+                        let discriminant = int_repr.enum_discriminant(i);
+                        quote! { #discriminant }
+                    } else {
+                        quote! {}
+                    };
+                    let non_exhaustive =
+                        v.attrs.iter().filter(|a| a.path.is_ident("non_exhaustive"));
                     match v.fields {
                         Fields::Named(ref fields) => {
                             let fields = fields.named.iter().map(|f| {
@@ -844,6 +1118,7 @@ fn derive_archive_impl(
                             quote! {
                                 #[doc = #variant_doc]
                                 #[allow(dead_code)]
+                                #(#non_exhaustive)*
                                 #variant {
                                     #(#fields,)*
                                 } #discriminant
@@ -869,6 +1144,7 @@ fn derive_archive_impl(
                             quote! {
                                 #[doc = #variant_doc]
                                 #[allow(dead_code)]
+                                #(#non_exhaustive)*
                                 #variant(#(#fields,)*) #discriminant
                             }
                         }
@@ -878,20 +1154,127 @@ fn derive_archive_impl(
                             quote! {
                                 #[doc = #variant_doc]
                                 #[allow(dead_code)]
+                                #(#non_exhaustive)*
                                 #variant #discriminant
                             }
                         }
                     }
                 });
 
+                let tag_variants = data.variants.iter().map(|v| {
+                    let variant = &v.ident;
+                    let variant_doc = format!("The tag of [`{}::{}`]", archived_name, variant);
+                    quote! {
+                        #[doc = #variant_doc]
+                        #variant
+                    }
+                });
+
                 Some(quote! {
                     #[automatically_derived]
                     #[doc = #archived_doc]
                     #(#archive_attrs)*
                     #repr
-                    #vis enum #archived_name #generics #archive_where {
+                    #archived_vis enum #archived_name #generics #archive_where {
                         #(#archived_variants,)*
                     }
+
+                    #[automatically_derived]
+                    #[doc = #tag_doc]
+                    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+                    #non_exhaustive
+                    #archived_vis enum #tag_name {
+                        #(#tag_variants,)*
+                    }
+                })
+            } else {
+                None
+            };
+
+            let tag_impl = if attributes.archive_as.is_none() {
+                let tag_arms = data.variants.iter().map(|v| {
+                    let variant = &v.ident;
+                    quote! { #archived_name::#variant { .. } => #tag_name::#variant }
+                });
+                let variant_accessors = data.variants.iter().map(|v| {
+                    let variant = &v.ident;
+                    let snake_name = to_snake_case(&strip_raw(variant));
+                    let is_name = Ident::new(&format!("is_{}", snake_name), variant.span());
+                    let is_doc = format!("Returns whether this is a [`{}::{}`]", archived_name, variant);
+                    let is_fn = quote! {
+                        #[doc = #is_doc]
+                        #[inline]
+                        pub fn #is_name(&self) -> bool {
+                            matches!(self, #archived_name::#variant { .. })
+                        }
+                    };
+
+                    let as_name = Ident::new(&format!("as_{}", snake_name), variant.span());
+                    let as_doc = format!(
+                        "Returns the fields of this [`{}::{}`], or `None` if it's a different variant",
+                        archived_name,
+                        variant,
+                    );
+                    let (pattern, tys, bindings) = match v.fields {
+                        Fields::Named(ref fields) => {
+                            let names = fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+                            let bindings = fields.named.iter().map(|f| {
+                                let name = f.ident.as_ref().unwrap();
+                                Ident::new(&format!("self_{}", strip_raw(name)), name.span())
+                            }).collect::<Vec<_>>();
+                            let pattern = quote! { #archived_name::#variant { #(#names: #bindings,)* } };
+                            let tys = fields.named.iter().map(|f| with_ty(f).unwrap()).collect::<Vec<_>>();
+                            (pattern, tys, bindings)
+                        }
+                        Fields::Unnamed(ref fields) => {
+                            let bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                Ident::new(&format!("self_{}", i), f.span())
+                            }).collect::<Vec<_>>();
+                            let pattern = quote! { #archived_name::#variant(#(#bindings,)*) };
+                            let tys = fields.unnamed.iter().map(|f| with_ty(f).unwrap()).collect::<Vec<_>>();
+                            (pattern, tys, bindings)
+                        }
+                        // Unit variants have no fields to return
+                        Fields::Unit => return is_fn,
+                    };
+                    // Variants with a single field return it directly instead of in a tuple
+                    let (ret, value) = if tys.len() == 1 {
+                        let ty = &tys[0];
+                        let binding = &bindings[0];
+                        (quote! { &#rkyv_path::Archived<#ty> }, quote! { #binding })
+                    } else {
+                        (
+                            quote! { (#(&#rkyv_path::Archived<#tys>,)*) },
+                            quote! { (#(#bindings,)*) },
+                        )
+                    };
+                    quote! {
+                        #is_fn
+
+                        #[doc = #as_doc]
+                        #[inline]
+                        pub fn #as_name(&self) -> Option<#ret> {
+                            match self {
+                                #pattern => Some(#value),
+                                #[allow(unreachable_patterns)]
+                                _ => None,
+                            }
+                        }
+                    }
+                });
+
+                Some(quote! {
+                    impl #impl_generics #archived_name #ty_generics #archive_where {
+                        /// Returns the tag of the variant of this archived enum
+                        #[inline]
+                        pub fn tag(&self) -> #tag_name {
+                            match self {
+                                #(#tag_arms,)*
+                            }
+                        }
+
+                        #(#variant_accessors)*
+                    }
                 })
             } else {
                 None
@@ -899,8 +1282,13 @@ fn derive_archive_impl(
 
             let archived_variant_tags = data.variants.iter().enumerate().map(|(i, v)| {
                 let variant = &v.ident;
-                let discriminant = int_repr.enum_discriminant(i);
-                quote! { #variant #discriminant }
+                if has_explicit_discriminants {
+                    let discriminant = int_repr.explicit_discriminant(discriminants[i]);
+                    quote! { #variant #discriminant }
+                } else {
+                    let discriminant = int_repr.enum_discriminant(i);
+                    quote! { #variant #discriminant }
+                }
             });
 
             let archived_variant_structs = data.variants.iter().map(|v| {
@@ -945,9 +1333,7 @@ fn derive_archive_impl(
                         for variant in data.variants.iter() {
                             match variant.fields {
                                 Fields::Named(ref fields) => {
-                                    for field in fields.named.iter().filter(|f| {
-                                        !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                    }) {
+                                    for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                                         let ty = &field.ty;
                                         let wrapped_ty = with_ty(field).unwrap();
                                         partial_eq_where.predicates.push(
@@ -956,9 +1342,7 @@ fn derive_archive_impl(
                                     }
                                 }
                                 Fields::Unnamed(ref fields) => {
-                                    for field in fields.unnamed.iter().filter(|f| {
-                                        !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                    }) {
+                                    for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                                         let ty = &field.ty;
                                         let wrapped_ty = with_ty(field).unwrap();
                                         partial_eq_where.predicates.push(
@@ -1042,9 +1426,7 @@ fn derive_archive_impl(
                         for variant in data.variants.iter() {
                             match variant.fields {
                                 Fields::Named(ref fields) => {
-                                    for field in fields.named.iter().filter(|f| {
-                                        !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                    }) {
+                                    for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                                         let ty = &field.ty;
                                         let wrapped_ty = with_ty(field).unwrap();
                                         partial_ord_where.predicates.push(
@@ -1053,9 +1435,7 @@ fn derive_archive_impl(
                                     }
                                 }
                                 Fields::Unnamed(ref fields) => {
-                                    for field in fields.unnamed.iter().filter(|f| {
-                                        !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
-                                    }) {
+                                    for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                                         let ty = &field.ty;
                                         let wrapped_ty = with_ty(field).unwrap();
                                         partial_ord_where.predicates.push(
@@ -1200,11 +1580,7 @@ fn derive_archive_impl(
                 for variant in data.variants.iter() {
                     match variant.fields {
                         Fields::Named(ref fields) => {
-                            for field in fields
-                                .named
-                                .iter()
-                                .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                            {
+                            for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                                 let ty = with_ty(field).unwrap();
                                 copy_safe_where
                                     .predicates
@@ -1212,11 +1588,7 @@ fn derive_archive_impl(
                             }
                         }
                         Fields::Unnamed(ref fields) => {
-                            for field in fields
-                                .unnamed
-                                .iter()
-                                .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                            {
+                            for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                                 let ty = with_ty(field).unwrap();
                                 copy_safe_where
                                     .predicates
@@ -1240,7 +1612,7 @@ fn derive_archive_impl(
 
                     #[automatically_derived]
                     #[doc = #resolver_doc]
-                    #vis enum #resolver #generics #archive_where {
+                    #resolver_vis enum #resolver #generics #archive_where {
                         #(#resolver_variants,)*
                     }
                 },
@@ -1266,6 +1638,7 @@ fn derive_archive_impl(
                         }
                     }
 
+                    #tag_impl
                     #partial_eq_impl
                     #partial_ord_impl
                     #copy_safe_impl
@@ -1280,6 +1653,253 @@ fn derive_archive_impl(
         }
     };
 
+    let fixed_size_impl = if attributes.fixed_size.is_some() {
+        let mut fixed_size_where = where_clause.clone();
+        let fields: Vec<&Field> = match input.data {
+            Data::Struct(ref data) => data.fields.iter().collect(),
+            Data::Enum(ref data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+            Data::Union(_) => Vec::new(),
+        };
+        for field in fields.into_iter().filter(|f| !omit_bounds(f)) {
+            let ty = with_ty(field)?;
+            fixed_size_where
+                .predicates
+                .push(parse_quote! { #ty: #rkyv_path::fixed_size::FixedSize });
+        }
+
+        Some(quote! {
+            impl #impl_generics #rkyv_path::fixed_size::FixedSize for #name #ty_generics #fixed_size_where {}
+        })
+    } else {
+        None
+    };
+
+    let fingerprint_impl = if attributes.fingerprint.is_some() {
+        let mut fingerprint_where = where_clause.clone();
+        let mut writes = Vec::new();
+        let mut write_fields = |writes: &mut Vec<TokenStream>,
+                                fields: &Fields|
+         -> Result<(), Error> {
+            let len = fields.len();
+            writes.push(quote! { .write_usize(#len) });
+            // Fields are hashed in the order they're laid out in the archived type
+            let named_fields: Vec<(String, &Field)> = match fields {
+                Fields::Named(fields) => archived_field_order(fields, attributes.reorder.is_some())
+                    .into_iter()
+                    .map(|f| Ok((strip_raw(&archived_field_name(f)?), f)))
+                    .collect::<Result<_, Error>>()?,
+                _ => fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| (i.to_string(), f))
+                    .collect(),
+            };
+            for (field_name, field) in named_fields {
+                writes.push(quote! { .write_str(#field_name) });
+                if omit_bounds(field) {
+                    // Omitted bounds are usually recursive, so the type can only be named
+                    let ty_name = type_name(&field.ty);
+                    writes.push(quote! { .write_str(#ty_name) });
+                } else {
+                    let ty = with_ty(field)?;
+                    fingerprint_where
+                        .predicates
+                        .push(parse_quote! { #ty: #rkyv_path::fingerprint::Fingerprint });
+                    writes.push(quote! {
+                        .write_u64(<#ty as #rkyv_path::fingerprint::Fingerprint>::FINGERPRINT)
+                    });
+                }
+            }
+            Ok(())
+        };
+        match input.data {
+            Data::Struct(ref data) => {
+                writes.push(quote! { .write_str("struct") });
+                write_fields(&mut writes, &data.fields)?;
+            }
+            Data::Enum(ref data) => {
+                writes.push(quote! { .write_str("enum") });
+                for variant in data.variants.iter() {
+                    let variant_name = strip_raw(&variant.ident);
+                    writes.push(quote! { .write_str(#variant_name) });
+                    write_fields(&mut writes, &variant.fields)?;
+                }
+            }
+            Data::Union(_) => (),
+        }
+
+        Some(quote! {
+            impl #impl_generics #rkyv_path::fingerprint::Fingerprint for #name #ty_generics #fingerprint_where {
+                const FINGERPRINT: u64 = #rkyv_path::fingerprint::FingerprintHasher::new()
+                    #(#writes)*
+                    .write_usize(::core::mem::size_of::<Archived<Self>>())
+                    .write_usize(::core::mem::align_of::<Archived<Self>>())
+                    .finish();
+            }
+        })
+    } else {
+        None
+    };
+
+    let version_impl = if let Some(ref version) = attributes.version {
+        let version = version.base10_parse::<u32>()?;
+        let migrations = attributes
+            .migrate_from
+            .iter()
+            .flat_map(|(_, versions)| versions.iter())
+            .map(|prior| {
+                quote! {
+                    if version == <#prior as #rkyv_path::versioned::Version>::VERSION {
+                        return Some(<Self as From<&Archived<#prior>>>::from(
+                            &*value.cast::<Archived<#prior>>(),
+                        ));
+                    }
+                }
+            });
+
+        Some(quote! {
+            impl #impl_generics #rkyv_path::versioned::Version for #name #ty_generics #where_clause {
+                const VERSION: u32 = #version;
+
+                #[inline]
+                unsafe fn migrate(version: u32, value: *const u8) -> Option<Self> {
+                    #(#migrations)*
+                    let _ = (version, value);
+                    None
+                }
+            }
+        })
+    } else if let Some((ref path, _)) = attributes.migrate_from {
+        return Err(Error::new_spanned(
+            path,
+            "migrate_from requires a version to be specified",
+        ));
+    } else {
+        None
+    };
+
+    let layout_impl = if attributes.layout.is_some() {
+        Some(layout::derive_archive_layout_impl(
+            &input, attributes, rkyv_path,
+        )?)
+    } else {
+        None
+    };
+
+    let schema_impl = if attributes.schema.is_some() {
+        Some(schema::derive_archive_schema_impl(
+            &input,
+            attributes,
+            rkyv_path,
+            enum_tag_repr,
+        )?)
+    } else {
+        None
+    };
+
+    let c_header_impl = if attributes.c_header.is_some() {
+        Some(c_header::derive_c_struct_impl(
+            &input, attributes, rkyv_path,
+        )?)
+    } else {
+        None
+    };
+
+    let mut layout_asserts = Vec::new();
+    for (assertion, attribute, function, what) in [
+        (
+            &attributes.assert_size,
+            "assert_size",
+            quote! { size_of },
+            "size",
+        ),
+        (
+            &attributes.assert_align,
+            "assert_align",
+            quote! { align_of },
+            "alignment",
+        ),
+    ] {
+        if let Some(expected) = assertion {
+            if !input.generics.params.is_empty() {
+                return Err(Error::new_spanned(
+                    expected,
+                    format!("{} may not be used with generic types", attribute),
+                ));
+            }
+            let value = expected.base10_parse::<usize>()?;
+            let message = format!(
+                "the archived {} of `{}` is not {} bytes",
+                what,
+                strip_raw(name),
+                value,
+            );
+            layout_asserts.push(quote! {
+                const _: () = assert!(
+                    ::core::mem::#function::<Archived<#name>>() == #value,
+                    #message,
+                );
+            });
+        }
+    }
+
+    // Fields with concrete types are also checked on their own, so that a missing `Archive` impl
+    // is reported with the name of the field that requires it
+    let mut field_checks = Vec::new();
+    if cfg!(has_diagnostic_namespace) {
+        let owned_fields = match input.data {
+            Data::Struct(ref data) => vec![(strip_raw(name), &data.fields)],
+            Data::Enum(ref data) => data
+                .variants
+                .iter()
+                .map(|v| {
+                    (
+                        format!("{}::{}", strip_raw(name), strip_raw(&v.ident)),
+                        &v.fields,
+                    )
+                })
+                .collect(),
+            Data::Union(_) => Vec::new(),
+        };
+        for (owner, fields) in owned_fields {
+            for (i, field) in fields.iter().enumerate() {
+                let has_with = field.attrs.iter().any(|a| a.path.is_ident("with"));
+                if omit_bounds(field) || has_with || mentions_generics(&field.ty, &input.generics) {
+                    continue;
+                }
+                let field_name = field
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| i.to_string(), strip_raw);
+                let message = format!(
+                    "the trait `Archive` is not implemented for `{{Self}}`, required by field `{}` \
+                     of `{}`",
+                    field_name, owner,
+                );
+                let label = format!("required by field `{}`", field_name);
+                let note = format!(
+                    "consider deriving `Archive` for `{{Self}}`, or archiving field `{}` with a \
+                     wrapper using `#[with(...)]`",
+                    field_name,
+                );
+                let ty = &field.ty;
+                field_checks.push(quote! {
+                    const _: () = {
+                        #[diagnostic::on_unimplemented(
+                            message = #message,
+                            label = #label,
+                            note = #note,
+                        )]
+                        trait ArchiveField {}
+                        impl<T: Archive + ?Sized> ArchiveField for T {}
+                        fn assert_archive_field<T: ArchiveField + ?Sized>() {}
+                        let _ = assert_archive_field::<#ty>;
+                    };
+                });
+            }
+        }
+    }
+
     Ok(quote! {
         #archive_types
 
@@ -1289,6 +1909,359 @@ fn derive_archive_impl(
             use #rkyv_path::{out_field, Archive, Archived};
 
             #archive_impls
+            #fixed_size_impl
+            #fingerprint_impl
+            #layout_impl
+            #schema_impl
+            #version_impl
+            #c_header_impl
+            #(#layout_asserts)*
+            #(#field_checks)*
         };
     })
 }
+
+fn parse_discriminant(expr: &Expr) -> Result<i128, Error> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) if matches!(**inner, Expr::Lit(_)) => Ok(-parse_discriminant(inner)?),
+        _ => Err(Error::new_spanned(
+            expr,
+            "archived enum discriminants must be integer literals",
+        )),
+    }
+}
+
+fn source_int_repr(attrs: &[Attribute]) -> Option<IntRepr> {
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("repr"))
+        .filter_map(|a| a.parse_meta().ok())
+        .find_map(|meta| match meta {
+            Meta::List(list) => list.nested.iter().find_map(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    path.get_ident().and_then(IntRepr::from_ident)
+                }
+                _ => None,
+            }),
+            _ => None,
+        })
+}
+
+/// Checks the attributes of the type and its fields, adding an error to `errors` for each problem.
+fn check_attributes(input: &DeriveInput, attributes: &Attributes, errors: &mut Errors) {
+    let vis = &input.vis;
+
+    // The archived and resolver types are named by the `Archive` impl, so they can't be less
+    // visible than the type they archive
+    for (generated_vis, generated) in [
+        (&attributes.archived_vis, "archived type"),
+        (&attributes.resolver_vis, "resolver"),
+    ] {
+        if let Some(generated_vis) = generated_vis {
+            if matches!(vis, Visibility::Public(_))
+                && !matches!(generated_vis, Visibility::Public(_))
+            {
+                errors.push(Error::new_spanned(
+                    generated_vis,
+                    format!("the {} of a public type must also be public", generated),
+                ));
+            }
+        }
+    }
+
+    if let Some(ref archive_as) = attributes.archive_as {
+        if let Some(ref ident) = attributes.archived {
+            errors.push(Error::new_spanned(
+                ident,
+                "archived = \"...\" may not be used with as = \"...\" because no type is generated",
+            ));
+        }
+        if let Some(ref archived_vis) = attributes.archived_vis {
+            errors.push(Error::new_spanned(
+                archived_vis,
+                "vis = \"...\" may not be used with as = \"...\" because no type is generated",
+            ));
+        }
+        if let Some(first) = attributes.attrs.first() {
+            errors.push(Error::new_spanned(
+                first,
+                format!(
+                    "\
+                        archive_attr(...) may not be used with as = \"...\"\n\
+                        place any attributes on the archived type ({}) instead\
+                    ",
+                    archive_as.value(),
+                ),
+            ));
+        }
+        if let Some(span) = attributes
+            .archived_repr
+            .base_repr
+            .map(|(_, s)| s)
+            .or_else(|| attributes.archived_repr.modifier.as_ref().map(|(_, s)| *s))
+        {
+            errors.push(Error::new(
+                span,
+                format!(
+                    "\
+                        repr(...) may not be used with as = \"...\"\n\
+                        place the repr attribute on the archived type ({}) instead\
+                    ",
+                    archive_as.value()
+                ),
+            ));
+        }
+    }
+
+    if let Some(ref accessors) = attributes.accessors {
+        if attributes.archive_as.is_some() {
+            errors.push(Error::new_spanned(
+                accessors,
+                "accessors may not be used with as = \"...\" because no type is generated",
+            ));
+        }
+        if !matches!(input.data, Data::Struct(ref data) if matches!(data.fields, Fields::Named(_)))
+        {
+            errors.push(Error::new_spanned(
+                accessors,
+                "accessors may only be generated for structs with named fields",
+            ));
+        }
+    }
+
+    if let Some(ref extensible) = attributes.extensible {
+        if !matches!(input.data, Data::Struct(ref data) if matches!(data.fields, Fields::Named(_)))
+        {
+            errors.push(Error::new_spanned(
+                extensible,
+                "extensible may only be used on structs with named fields",
+            ));
+        }
+        let conflict = if attributes.archive_as.is_some() {
+            Some("as = \"...\"")
+        } else if attributes.compares.is_some() {
+            Some("compare(...)")
+        } else if attributes.copy_safe.is_some() {
+            Some("copy_safe")
+        } else if attributes.fixed_size.is_some() {
+            Some("fixed_size")
+        } else if attributes.accessors.is_some() {
+            Some("accessors")
+        } else {
+            None
+        };
+        if let Some(conflict) = conflict {
+            errors.push(Error::new_spanned(
+                extensible,
+                format!("extensible may not be used with {}", conflict),
+            ));
+        }
+    }
+
+    if let Some(ref reorder) = attributes.reorder {
+        if !matches!(input.data, Data::Struct(ref data) if matches!(data.fields, Fields::Named(_)))
+        {
+            errors.push(Error::new_spanned(
+                reorder,
+                "reorder may only be used on structs with named fields",
+            ));
+        }
+        let conflict = if attributes.archive_as.is_some() {
+            Some("as = \"...\"")
+        } else if attributes.extensible.is_some() {
+            Some("extensible")
+        } else {
+            None
+        };
+        if let Some(conflict) = conflict {
+            errors.push(Error::new_spanned(
+                reorder,
+                format!("reorder may not be used with {}", conflict),
+            ));
+        }
+    }
+
+    if let Some(ref unsized_tail) = attributes.unsized_tail {
+        errors.check(tail::split_fields(input, unsized_tail));
+        let conflict = if attributes.archive_as.is_some() {
+            Some("as = \"...\"")
+        } else if attributes.compares.is_some() {
+            Some("compare(...)")
+        } else if attributes.copy_safe.is_some() {
+            Some("copy_safe")
+        } else if attributes.fixed_size.is_some() {
+            Some("fixed_size")
+        } else if attributes.fingerprint.is_some() {
+            Some("fingerprint")
+        } else if attributes.version.is_some() {
+            Some("version = ...")
+        } else if attributes.accessors.is_some() {
+            Some("accessors")
+        } else if attributes.extensible.is_some() {
+            Some("extensible")
+        } else if attributes.reorder.is_some() {
+            Some("reorder")
+        } else if attributes.assert_size.is_some() {
+            Some("assert_size = ...")
+        } else {
+            None
+        };
+        if let Some(conflict) = conflict {
+            errors.push(Error::new_spanned(
+                unsized_tail,
+                format!("unsized may not be used with {}", conflict),
+            ));
+        }
+        if let Some((BaseRepr::Int(_) | BaseRepr::Transparent, span)) =
+            attributes.archived_repr.base_repr
+        {
+            errors.push(Error::new(
+                span,
+                "archived structs with an unsized tail are always repr(C)",
+            ));
+        }
+    }
+
+    if let Some(ref columnar) = attributes.columnar {
+        errors.check(columnar::columnar_fields(input, columnar));
+        if attributes.unsized_tail.is_some() {
+            errors.push(Error::new_spanned(
+                columnar,
+                "columnar may not be used with unsized",
+            ));
+        }
+    }
+
+    if let Some(ref layout) = attributes.layout {
+        layout::check_attributes(input, attributes, layout, errors);
+    }
+
+    if let Some(ref schema) = attributes.schema {
+        schema::check_attributes(input, attributes, schema, errors);
+    }
+
+    if let Some(ref c_header) = attributes.c_header {
+        c_header::check_attributes(input, attributes, c_header, errors);
+    }
+
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    for field in fields {
+        errors.check(with(field, (), |(), _| ()));
+        errors.check(parse_field_attributes(field));
+    }
+
+    match input.data {
+        Data::Struct(ref data) => {
+            if let Fields::Unnamed(ref fields) = data.fields {
+                check_not_renamed(fields.unnamed.iter(), errors);
+            }
+        }
+        Data::Enum(ref data) => {
+            check_not_renamed(data.variants.iter().flat_map(|v| v.fields.iter()), errors)
+        }
+        Data::Union(_) => (),
+    }
+}
+
+/// Returns the named fields of a struct in the order they're declared in the archived type.
+///
+/// Reordered fields are sorted by the alignment of their archived types, largest first, to
+/// minimize padding. Macros can't see the alignment of most types, so only fields of primitive
+/// types (and arrays of them) are sorted. All other fields are placed first in declaration order,
+/// since they usually contain relative pointers or lengths that are at least as aligned as most
+/// primitives. The order is stable, so fields with the same alignment keep their relative order.
+pub fn archived_field_order(fields: &FieldsNamed, reorder: bool) -> Vec<&Field> {
+    let mut ordered = fields.named.iter().collect::<Vec<_>>();
+    if reorder {
+        ordered.sort_by_key(|f| {
+            let has_with = f.attrs.iter().any(|a| a.path.is_ident("with"));
+            let align = if has_with {
+                None
+            } else {
+                primitive_align(&f.ty)
+            };
+            ::std::cmp::Reverse(align.unwrap_or(usize::MAX))
+        });
+    }
+    ordered
+}
+
+fn primitive_align(ty: &Type) -> Option<usize> {
+    match ty {
+        Type::Array(array) => primitive_align(&array.elem),
+        Type::Paren(paren) => primitive_align(&paren.elem),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => Some(0),
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+            if !segment.arguments.is_empty() {
+                return None;
+            }
+            let align = match segment.ident.to_string().as_str() {
+                "bool" | "i8" | "u8" | "NonZeroI8" | "NonZeroU8" => 1,
+                "i16" | "u16" | "NonZeroI16" | "NonZeroU16" => 2,
+                "i32" | "u32" | "f32" | "char" | "NonZeroI32" | "NonZeroU32" => 4,
+                "i64" | "u64" | "f64" | "NonZeroI64" | "NonZeroU64" => 8,
+                "i128" | "u128" | "NonZeroI128" | "NonZeroU128" => 16,
+                _ => return None,
+            };
+            Some(align)
+        }
+        _ => None,
+    }
+}
+
+fn check_not_renamed<'a>(fields: impl Iterator<Item = &'a Field>, errors: &mut Errors) {
+    for field in fields {
+        if let Ok(FieldAttributes {
+            rename: Some(rename),
+        }) = parse_field_attributes(field)
+        {
+            errors.push(Error::new_spanned(
+                rename,
+                "rename may only be used on fields of structs with named fields",
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::derive;
+    use syn::{parse_quote, DeriveInput};
+
+    #[test]
+    fn reports_every_error() {
+        let input: DeriveInput = parse_quote! {
+            #[archive(unknown)]
+            #[archive("literal")]
+            #[archive(as = "ArchivedExample", archived = "Archived")]
+            pub struct Example {
+                value: u32,
+            }
+        };
+        let messages = derive(input)
+            .unwrap_err()
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "unrecognized archive argument",
+                "archive arguments must be metas",
+                "archived = \"...\" may not be used with as = \"...\" because no type is generated",
+            ]
+        );
+    }
+}
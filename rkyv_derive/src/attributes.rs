@@ -1,12 +1,17 @@
-use crate::repr::Repr;
+use crate::{repr::Repr, util::Errors};
 use quote::ToTokens;
-use syn::{AttrStyle, DeriveInput, Error, Ident, Lit, LitStr, Meta, NestedMeta, Path};
+use syn::{
+    AttrStyle, DeriveInput, Error, Field, Ident, Lit, LitInt, LitStr, Meta, NestedMeta, Path,
+    Visibility,
+};
 
 #[derive(Default)]
 pub struct Attributes {
     pub archive_as: Option<LitStr>,
     pub archived: Option<Ident>,
+    pub archived_vis: Option<Visibility>,
     pub resolver: Option<Ident>,
+    pub resolver_vis: Option<Visibility>,
     pub attrs: Vec<Meta>,
     pub archived_repr: Repr,
     pub compares: Option<(Path, Vec<Path>)>,
@@ -14,6 +19,20 @@ pub struct Attributes {
     pub serialize_bound: Option<LitStr>,
     pub deserialize_bound: Option<LitStr>,
     pub copy_safe: Option<Path>,
+    pub fixed_size: Option<Path>,
+    pub fingerprint: Option<Path>,
+    pub layout: Option<Path>,
+    pub schema: Option<Path>,
+    pub version: Option<LitInt>,
+    pub assert_size: Option<LitInt>,
+    pub assert_align: Option<LitInt>,
+    pub migrate_from: Option<(Path, Vec<Path>)>,
+    pub accessors: Option<Path>,
+    pub extensible: Option<Path>,
+    pub reorder: Option<Path>,
+    pub unsized_tail: Option<Path>,
+    pub columnar: Option<Path>,
+    pub c_header: Option<Path>,
     pub rkyv_path: Option<Path>,
 }
 
@@ -38,6 +57,26 @@ fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<
         Meta::Path(path) => {
             if path.is_ident("copy_safe") {
                 try_set_attribute(&mut attributes.copy_safe, path.clone(), "copy_safe")
+            } else if path.is_ident("accessors") {
+                try_set_attribute(&mut attributes.accessors, path.clone(), "accessors")
+            } else if path.is_ident("extensible") {
+                try_set_attribute(&mut attributes.extensible, path.clone(), "extensible")
+            } else if path.is_ident("fixed_size") {
+                try_set_attribute(&mut attributes.fixed_size, path.clone(), "fixed_size")
+            } else if path.is_ident("fingerprint") {
+                try_set_attribute(&mut attributes.fingerprint, path.clone(), "fingerprint")
+            } else if path.is_ident("layout") {
+                try_set_attribute(&mut attributes.layout, path.clone(), "layout")
+            } else if path.is_ident("schema") {
+                try_set_attribute(&mut attributes.schema, path.clone(), "schema")
+            } else if path.is_ident("reorder") {
+                try_set_attribute(&mut attributes.reorder, path.clone(), "reorder")
+            } else if path.is_ident("unsized") {
+                try_set_attribute(&mut attributes.unsized_tail, path.clone(), "unsized")
+            } else if path.is_ident("columnar") {
+                try_set_attribute(&mut attributes.columnar, path.clone(), "columnar")
+            } else if path.is_ident("c_header") {
+                try_set_attribute(&mut attributes.c_header, path.clone(), "c_header")
             } else {
                 Err(Error::new_spanned(meta, "unrecognized archive argument"))
             }
@@ -86,7 +125,7 @@ fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<
                             } else {
                                 return Err(Error::new_spanned(
                                     bound,
-                                    "bound must be either serialize or deserialize",
+                                    "bound must be either archive, serialize, or deserialize",
                                 ));
                             }
                         } else {
@@ -98,11 +137,29 @@ fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<
                     } else {
                         return Err(Error::new_spanned(
                             bound,
-                            "bound arguments must be serialize or deserialize bounds to apply",
+                            "bound arguments must be archive, serialize, or deserialize bounds to apply",
                         ));
                     }
                 }
                 Ok(())
+            } else if list.path.is_ident("migrate_from") {
+                if attributes.migrate_from.is_none() {
+                    let mut versions = Vec::new();
+                    for version in list.nested.iter() {
+                        if let NestedMeta::Meta(Meta::Path(path)) = version {
+                            versions.push(path.clone());
+                        } else {
+                            return Err(Error::new_spanned(
+                                version,
+                                "migrate_from arguments must be prior versions of the type",
+                            ));
+                        }
+                    }
+                    attributes.migrate_from = Some((list.path.clone(), versions));
+                    Ok(())
+                } else {
+                    Err(Error::new_spanned(list, "migrate_from already specified"))
+                }
             } else if list.path.is_ident("repr") {
                 // TODO: remove `archive(repr(...))` syntax
                 attributes.archived_repr.parse_args(list.nested.iter())
@@ -124,6 +181,22 @@ fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<
                 } else {
                     Err(Error::new_spanned(meta, "archived must be a string"))
                 }
+            } else if meta.path.is_ident("vis") {
+                if let Lit::Str(ref lit_str) = meta.lit {
+                    try_set_attribute(&mut attributes.archived_vis, lit_str.parse()?, "vis")
+                } else {
+                    Err(Error::new_spanned(meta, "vis must be a string"))
+                }
+            } else if meta.path.is_ident("resolver_vis") {
+                if let Lit::Str(ref lit_str) = meta.lit {
+                    try_set_attribute(
+                        &mut attributes.resolver_vis,
+                        lit_str.parse()?,
+                        "resolver_vis",
+                    )
+                } else {
+                    Err(Error::new_spanned(meta, "resolver_vis must be a string"))
+                }
             } else if meta.path.is_ident("resolver") {
                 if let Lit::Str(ref lit_str) = meta.lit {
                     try_set_attribute(
@@ -140,6 +213,28 @@ fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<
                 } else {
                     Err(Error::new_spanned(meta, "archive as must be a string"))
                 }
+            } else if meta.path.is_ident("version") {
+                if let Lit::Int(ref lit_int) = meta.lit {
+                    try_set_attribute(&mut attributes.version, lit_int.clone(), "version")
+                } else {
+                    Err(Error::new_spanned(meta, "version must be an integer"))
+                }
+            } else if meta.path.is_ident("assert_size") {
+                if let Lit::Int(ref lit_int) = meta.lit {
+                    try_set_attribute(&mut attributes.assert_size, lit_int.clone(), "assert_size")
+                } else {
+                    Err(Error::new_spanned(meta, "assert_size must be an integer"))
+                }
+            } else if meta.path.is_ident("assert_align") {
+                if let Lit::Int(ref lit_int) = meta.lit {
+                    try_set_attribute(
+                        &mut attributes.assert_align,
+                        lit_int.clone(),
+                        "assert_align",
+                    )
+                } else {
+                    Err(Error::new_spanned(meta, "assert_align must be an integer"))
+                }
             } else if meta.path.is_ident("crate") {
                 if let Lit::Str(ref lit_str) = meta.lit {
                     let stream = syn::parse_str(&lit_str.value())?;
@@ -156,51 +251,123 @@ fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<
     }
 }
 
+/// Parses the `archive` and `archive_attr` attributes of a type.
+///
+/// Every invalid argument is reported, not just the first one.
 pub fn parse_attributes(input: &DeriveInput) -> Result<Attributes, Error> {
+    let mut errors = Errors::default();
+    let result = collect_attributes(input, &mut errors);
+    errors.finish()?;
+    Ok(result)
+}
+
+/// Parses the valid `archive` and `archive_attr` attributes of a type, adding an error to `errors`
+/// for each invalid argument.
+pub fn collect_attributes(input: &DeriveInput, errors: &mut Errors) -> Attributes {
     let mut result = Attributes::default();
     for attr in input.attrs.iter() {
         if let AttrStyle::Outer = attr.style {
             if attr.path.is_ident("archive") || attr.path.is_ident("archive_attr") {
-                if let Meta::List(list) = attr.parse_meta()? {
-                    if list.path.is_ident("archive") {
-                        for nested in list.nested.iter() {
-                            if let NestedMeta::Meta(meta) = nested {
-                                parse_archive_attributes(&mut result, meta)?;
-                            } else {
-                                return Err(Error::new_spanned(
-                                    nested,
-                                    "archive arguments must be metas",
-                                ));
+                match errors.check(attr.parse_meta()) {
+                    Some(Meta::List(list)) => {
+                        if list.path.is_ident("archive") {
+                            for nested in list.nested.iter() {
+                                if let NestedMeta::Meta(meta) = nested {
+                                    errors.check(parse_archive_attributes(&mut result, meta));
+                                } else {
+                                    errors.push(Error::new_spanned(
+                                        nested,
+                                        "archive arguments must be metas",
+                                    ));
+                                }
                             }
-                        }
-                    } else if list.path.is_ident("archive_attr") {
-                        for nested in list.nested.iter() {
-                            if let NestedMeta::Meta(meta) = nested {
-                                if let Meta::List(list) = meta {
-                                    if list.path.is_ident("repr") {
-                                        result.archived_repr.parse_args(list.nested.iter())?;
+                        } else if list.path.is_ident("archive_attr") {
+                            for nested in list.nested.iter() {
+                                if let NestedMeta::Meta(meta) = nested {
+                                    if let Meta::List(list) = meta {
+                                        if list.path.is_ident("repr") {
+                                            errors.check(
+                                                result.archived_repr.parse_args(list.nested.iter()),
+                                            );
+                                        } else {
+                                            result.attrs.push(meta.clone());
+                                        }
                                     } else {
                                         result.attrs.push(meta.clone());
                                     }
                                 } else {
-                                    result.attrs.push(meta.clone());
+                                    errors.push(Error::new_spanned(
+                                        nested,
+                                        "archive_attr arguments must be metas",
+                                    ));
                                 }
-                            } else {
-                                return Err(Error::new_spanned(
-                                    nested,
-                                    "archive_attr arguments must be metas",
-                                ));
                             }
                         }
                     }
-                } else {
-                    return Err(Error::new_spanned(
+                    Some(_) => errors.push(Error::new_spanned(
                         attr,
                         "archive and archive_attr may only be structured list attributes",
-                    ));
+                    )),
+                    None => (),
                 }
             }
         }
     }
+    result
+}
+
+#[derive(Default)]
+pub struct FieldAttributes {
+    pub rename: Option<Ident>,
+}
+
+pub fn parse_field_attributes(field: &Field) -> Result<FieldAttributes, Error> {
+    let mut result = FieldAttributes::default();
+    let mut errors = Errors::default();
+    for attr in field.attrs.iter() {
+        if attr.path.is_ident("archive") {
+            match errors.check(attr.parse_meta()) {
+                Some(Meta::List(list)) => {
+                    for nested in list.nested.iter() {
+                        match nested {
+                            NestedMeta::Meta(Meta::NameValue(meta))
+                                if meta.path.is_ident("rename") =>
+                            {
+                                if let Lit::Str(ref lit_str) = meta.lit {
+                                    if let Some(rename) = errors.check(lit_str.parse::<Ident>()) {
+                                        errors.check(try_set_attribute(
+                                            &mut result.rename,
+                                            rename,
+                                            "rename",
+                                        ));
+                                    }
+                                } else {
+                                    errors
+                                        .push(Error::new_spanned(meta, "rename must be a string"));
+                                }
+                            }
+                            _ => errors.push(Error::new_spanned(
+                                nested,
+                                "unrecognized archive field argument",
+                            )),
+                        }
+                    }
+                }
+                Some(_) => errors.push(Error::new_spanned(
+                    attr,
+                    "archive may only be a structured list attribute",
+                )),
+                None => (),
+            }
+        }
+    }
+    errors.finish()?;
     Ok(result)
 }
+
+/// Returns the name of a named field in the archived type.
+pub fn archived_field_name(field: &Field) -> Result<Ident, Error> {
+    Ok(parse_field_attributes(field)?
+        .rename
+        .unwrap_or_else(|| field.ident.clone().unwrap()))
+}
@@ -0,0 +1,122 @@
+use crate::{
+    archive::archived_field_order,
+    attributes::{archived_field_name, Attributes},
+    repr::BaseRepr,
+    util::{strip_raw, Errors},
+    with::make_with_ty,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, Path};
+
+/// Checks that a `#[archive(c_header)]` type can be described in a C header.
+pub fn check_attributes(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    c_header: &Path,
+    errors: &mut Errors,
+) {
+    if !cfg!(feature = "c_header") {
+        errors.push(Error::new_spanned(
+            c_header,
+            "c_header requires the `c_header` feature of rkyv",
+        ));
+    }
+    if !matches!(input.data, Data::Struct(ref data) if !data.fields.is_empty()) {
+        errors.push(Error::new_spanned(
+            c_header,
+            "c_header may only be used on structs with at least one field",
+        ));
+    }
+    if !input.generics.params.is_empty() {
+        errors.push(Error::new_spanned(
+            c_header,
+            "c_header may not be used with generic types",
+        ));
+    }
+    let conflict = if attributes.archive_as.is_some() {
+        Some("as = \"...\"")
+    } else if attributes.extensible.is_some() {
+        Some("extensible")
+    } else if attributes.unsized_tail.is_some() {
+        Some("unsized")
+    } else {
+        None
+    };
+    if let Some(conflict) = conflict {
+        errors.push(Error::new_spanned(
+            c_header,
+            format!("c_header may not be used with {}", conflict),
+        ));
+    }
+    if let Some((BaseRepr::Int(_) | BaseRepr::Transparent, span)) =
+        attributes.archived_repr.base_repr
+    {
+        errors.push(Error::new(
+            span,
+            "archived structs with c_header are always repr(C)",
+        ));
+    }
+}
+
+/// Returns the fields of the archived struct in layout order, along with their names in C.
+fn c_fields<'a>(input: &'a DeriveInput, attributes: &Attributes) -> Vec<(String, &'a Field)> {
+    match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => archived_field_order(fields, attributes.reorder.is_some())
+                .into_iter()
+                .map(|f| (strip_raw(&archived_field_name(f).unwrap()), f))
+                .collect(),
+            Fields::Unnamed(ref fields) => fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(i, f)| (format!("_{}", i), f))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+pub fn derive_c_struct_impl(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let archived_name = attributes.archived.as_ref().map_or_else(
+        || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    );
+    let c_name = strip_raw(&archived_name);
+
+    let with_ty = make_with_ty(rkyv_path);
+    let fields = c_fields(input, attributes);
+    let tys = fields
+        .iter()
+        .map(|(_, f)| with_ty(f))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let field_names = fields.iter().map(|(name, _)| name);
+
+    Ok(quote! {
+        impl #rkyv_path::c_header::CStruct for #archived_name
+        where
+            #(#rkyv_path::Archived<#tys>: #rkyv_path::c_header::CType,)*
+        {
+            const C_NAME: &'static str = #c_name;
+
+            fn define_struct(header: &mut #rkyv_path::c_header::CHeader) {
+                use #rkyv_path::c_header::CType;
+
+                #(<#rkyv_path::Archived<#tys> as CType>::define(header);)*
+                header.push_struct(
+                    Self::C_NAME,
+                    &[#(<#rkyv_path::Archived<#tys> as CType>::c_declaration(#field_names),)*],
+                    ::core::mem::size_of::<Self>(),
+                    ::core::mem::align_of::<Self>(),
+                );
+            }
+        }
+    })
+}
@@ -0,0 +1,345 @@
+use crate::{
+    archive::field_archive_attrs,
+    attributes::{archived_field_name, Attributes},
+    util::{add_bounds, field_bound, impl_generics_with, omit_bounds, rkyv_path, strip_raw},
+    with::{make_with_cast, make_with_ty, with_inner},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Attribute, Data, DeriveInput, Error, Field, Fields, Ident, Path};
+
+/// Returns the fields of an `#[archive(columnar)]` struct.
+pub fn columnar_fields<'a>(
+    input: &'a DeriveInput,
+    attribute: &Path,
+) -> Result<Vec<&'a Field>, Error> {
+    if let Data::Struct(ref data) = input.data {
+        if let Fields::Named(ref fields) = data.fields {
+            if fields.named.is_empty() {
+                return Err(Error::new_spanned(
+                    attribute,
+                    "columnar structs must have at least one field to archive as a column",
+                ));
+            }
+            return Ok(fields.named.iter().collect());
+        }
+    }
+    Err(Error::new_spanned(
+        attribute,
+        "columnar may only be used on structs with named fields",
+    ))
+}
+
+fn columns_name(name: &Ident, attributes: &Attributes) -> Ident {
+    let archived_name = attributes
+        .archived
+        .as_ref()
+        .map_or_else(|| format!("Archived{}", strip_raw(name)), strip_raw);
+    Ident::new(&format!("{}Columns", archived_name), name.span())
+}
+
+fn columns_resolver_name(name: &Ident) -> Ident {
+    Ident::new(&format!("{}ColumnsResolver", strip_raw(name)), name.span())
+}
+
+pub fn derive_archive_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+
+    let fields = columnar_fields(&input, attributes.columnar.as_ref().unwrap())?;
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let archived_vis = attributes.archived_vis.as_ref().unwrap_or(vis);
+    let resolver_vis = attributes.resolver_vis.as_ref().unwrap_or(vis);
+    let generics = &input.generics;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let where_clause = where_clause.unwrap();
+
+    let rkyv_path = rkyv_path(attributes);
+    let with_ty = make_with_ty(&rkyv_path);
+
+    let archive_attrs = attributes
+        .attrs
+        .iter()
+        .map::<Attribute, _>(|d| parse_quote! { #[#d] });
+
+    let columns = columns_name(name, attributes);
+    let columns_doc = format!("The archived columns of a slice of [`{}`]", name);
+    let resolver = columns_resolver_name(name);
+    let resolver_doc = format!("The resolver for the columns of a slice of [`{}`]", name);
+
+    let mut archive_where = where_clause.clone();
+    for field in fields.iter().filter(|f| !omit_bounds(f)) {
+        let ty = with_ty(field)?;
+        archive_where
+            .predicates
+            .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
+    }
+
+    let column_fields = fields
+        .iter()
+        .map(|f| {
+            let field_name = archived_field_name(f)?;
+            let ty = with_ty(f)?;
+            let vis = &f.vis;
+            let field_doc = format!(
+                "The archived column of [`{}::{}`]",
+                name,
+                f.ident.as_ref().unwrap()
+            );
+            let archive_attrs = field_archive_attrs(f);
+            Ok(quote! {
+                #[doc = #field_doc]
+                #(#[#archive_attrs])*
+                #vis #field_name: #rkyv_path::vec::ArchivedVec<#rkyv_path::Archived<#ty>>
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let resolver_fields = fields.iter().map(|f| {
+        let name = &f.ident;
+        quote! { #name: #rkyv_path::vec::VecResolver }
+    });
+
+    let column_names = fields
+        .iter()
+        .map(|f| archived_field_name(f))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let first_column = &column_names[0];
+    let other_columns = &column_names[1..];
+
+    let resolve_columns = fields.iter().zip(column_names.iter()).map(|(f, column)| {
+        let name = &f.ident;
+        quote! {
+            let (fp, fo) = out_field!(out.#column);
+            ArchivedVec::resolve_from_len(rows.len(), pos + fp, resolver.#name, fo);
+        }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[doc = #columns_doc]
+        #(#archive_attrs)*
+        #[repr(C)]
+        #archived_vis struct #columns #generics #archive_where {
+            #(#column_fields,)*
+        }
+
+        #[automatically_derived]
+        #[doc = #resolver_doc]
+        #resolver_vis struct #resolver {
+            #(#resolver_fields,)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #columns #ty_generics #archive_where {
+            /// Returns the number of rows in the columns.
+            ///
+            /// If the columns have different lengths, this is the number of rows that are present
+            /// in every column.
+            #[inline]
+            pub fn len(&self) -> usize {
+                let len = self.#first_column.len();
+                #(let len = ::core::cmp::min(len, self.#other_columns.len());)*
+                len
+            }
+
+            /// Returns whether there are no rows in the columns.
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+        }
+
+        #[automatically_derived]
+        const _: () = {
+            use #rkyv_path::{columnar::Columnar, out_field, vec::ArchivedVec};
+
+            impl #impl_generics Columnar for #name #ty_generics #archive_where {
+                type ArchivedColumns = #columns #ty_generics;
+                type ColumnsResolver = #resolver;
+
+                #[inline]
+                fn columns_len(columns: &Self::ArchivedColumns) -> usize {
+                    columns.len()
+                }
+
+                #[inline]
+                unsafe fn resolve_columns(
+                    rows: &[Self],
+                    pos: usize,
+                    resolver: Self::ColumnsResolver,
+                    out: *mut Self::ArchivedColumns,
+                ) {
+                    #(#resolve_columns)*
+                }
+            }
+        };
+    })
+}
+
+pub fn derive_serialize_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+    if let Some(ref bounds) = attributes.serialize_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+
+    let fields = columnar_fields(&input, attributes.columnar.as_ref().unwrap())?;
+
+    let rkyv_path = rkyv_path(attributes);
+    let with_ty = make_with_ty(&rkyv_path);
+    let with_cast = make_with_cast(&rkyv_path);
+
+    let impl_input_generics = impl_generics_with(
+        &input,
+        parse_quote! { __S: Fallible + Serializer + ScratchSpace + ?Sized },
+    );
+    let name = &input.ident;
+    let (impl_generics, _, _) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let where_clause = where_clause.unwrap();
+
+    let resolver = columns_resolver_name(name);
+
+    let mut serialize_where = where_clause.clone();
+    for field in fields.iter().filter(|f| !omit_bounds(f)) {
+        let ty = with_ty(field)?;
+        serialize_where
+            .predicates
+            .push(field_bound(field, quote! { #ty: Serialize<__S> }));
+    }
+
+    let resolver_values = fields
+        .iter()
+        .map(|f| {
+            let name = &f.ident;
+            let ty = with_ty(f)?;
+            let field = with_cast(f, parse_quote! { &row.#name })?;
+            Ok(quote! {
+                #name: ArchivedVec::serialize_from_iter::<#ty, _, _, _>(
+                    rows.iter().map(|row| #field),
+                    serializer,
+                )?
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        const _: () = {
+            use #rkyv_path::{
+                columnar::SerializeColumns,
+                ser::{ScratchSpace, Serializer},
+                vec::ArchivedVec,
+                Fallible, Serialize,
+            };
+
+            impl #impl_generics SerializeColumns<__S> for #name #ty_generics #serialize_where {
+                #[inline]
+                fn serialize_columns(
+                    rows: &[Self],
+                    serializer: &mut __S,
+                ) -> ::core::result::Result<Self::ColumnsResolver, __S::Error> {
+                    Ok(#resolver {
+                        #(#resolver_values,)*
+                    })
+                }
+            }
+        };
+    })
+}
+
+pub fn derive_deserialize_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+    if let Some(ref bounds) = attributes.deserialize_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+
+    let fields = columnar_fields(&input, attributes.columnar.as_ref().unwrap())?;
+
+    let rkyv_path = rkyv_path(attributes);
+    let with_ty = make_with_ty(&rkyv_path);
+
+    let impl_input_generics = impl_generics_with(&input, parse_quote! { __D: Fallible + ?Sized });
+    let name = &input.ident;
+    let (impl_generics, _, _) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let where_clause = where_clause.unwrap();
+
+    let mut deserialize_where = where_clause.clone();
+    for field in fields.iter().filter(|f| !omit_bounds(f)) {
+        let ty = with_ty(field)?;
+        deserialize_where
+            .predicates
+            .push(field_bound(field, quote! { #ty: Archive }));
+        deserialize_where.predicates.push(field_bound(
+            field,
+            quote! { Archived<#ty>: Deserialize<#ty, __D> },
+        ));
+    }
+
+    let deserialize_fields = fields
+        .iter()
+        .map(|f| {
+            let name = &f.ident;
+            let column = archived_field_name(f)?;
+            let ty = with_ty(f)?;
+            let value = with_inner(
+                f,
+                parse_quote! {
+                    Deserialize::<#ty, __D>::deserialize(
+                        &columns.#column[index],
+                        deserializer,
+                    )?
+                },
+            )?;
+            Ok(quote! { #name: #value })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        const _: () = {
+            use #rkyv_path::{
+                columnar::{Columnar, DeserializeColumns},
+                Archive, Archived, Deserialize, Fallible,
+            };
+
+            impl #impl_generics DeserializeColumns<__D> for #name #ty_generics #deserialize_where {
+                #[inline]
+                fn deserialize_row(
+                    columns: &Self::ArchivedColumns,
+                    index: usize,
+                    deserializer: &mut __D,
+                ) -> ::core::result::Result<Self, __D::Error> {
+                    assert!(
+                        index < <Self as Columnar>::columns_len(columns),
+                        "row index out of bounds",
+                    );
+                    Ok(#name {
+                        #(#deserialize_fields,)*
+                    })
+                }
+            }
+        };
+    })
+}
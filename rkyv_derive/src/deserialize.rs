@@ -1,6 +1,7 @@
 use crate::{
-    attributes::{parse_attributes, Attributes},
-    util::add_bounds,
+    attributes::{archived_field_name, parse_attributes, Attributes},
+    columnar, tail,
+    util::{add_bounds, field_bound, omit_bounds},
     with::{make_with_ty, with_inner},
 };
 use proc_macro2::TokenStream;
@@ -12,7 +13,22 @@ use syn::{
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
     let attributes = parse_attributes(&input)?;
-    derive_deserialize_impl(input, &attributes)
+    if attributes.unsized_tail.is_some() {
+        return tail::derive_deserialize_impl(input, &attributes);
+    }
+    let columns_impl = if attributes.columnar.is_some() {
+        Some(columnar::derive_deserialize_impl(
+            input.clone(),
+            &attributes,
+        )?)
+    } else {
+        None
+    };
+    let result = derive_deserialize_impl(input, &attributes)?;
+    Ok(quote! {
+        #result
+        #columns_impl
+    })
 }
 
 fn derive_deserialize_impl(
@@ -52,35 +68,63 @@ fn derive_deserialize_impl(
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
                 let mut deserialize_where = where_clause.clone();
-                for field in fields
-                    .named
-                    .iter()
-                    .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                {
+                for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                     let ty = with_ty(field)?;
                     deserialize_where
                         .predicates
-                        .push(parse_quote! { #ty: Archive });
-                    deserialize_where
-                        .predicates
-                        .push(parse_quote! { Archived<#ty>: Deserialize<#ty, __D> });
+                        .push(field_bound(field, quote! { #ty: Archive }));
+                    deserialize_where.predicates.push(field_bound(
+                        field,
+                        quote! { Archived<#ty>: Deserialize<#ty, __D> },
+                    ));
                 }
 
-                let deserialize_fields = fields.named.iter().map(|f| {
-                    let name = &f.ident;
-                    let ty = with_ty(f).unwrap();
-                    let value = with_inner(
-                        f,
-                        parse_quote! {
-                            Deserialize::<#ty, __D>::deserialize(
-                                &self.#name,
-                                deserializer,
-                            )?
-                        },
-                    )
-                    .unwrap();
-                    quote! { #name: #value }
-                });
+                if attributes.extensible.is_some() {
+                    // Missing fields are replaced with their default values
+                    for field in fields.named.iter() {
+                        let ty = &field.ty;
+                        deserialize_where
+                            .predicates
+                            .push(parse_quote! { #ty: Default });
+                    }
+                }
+
+                let deserialize_fields = fields
+                    .named
+                    .iter()
+                    .map(|f| {
+                        let name = &f.ident;
+                        let archived_name = archived_field_name(f)?;
+                        let ty = with_ty(f).unwrap();
+                        if attributes.extensible.is_some() {
+                            let value = with_inner(
+                                f,
+                                parse_quote! {
+                                    Deserialize::<#ty, __D>::deserialize(field, deserializer)?
+                                },
+                            )
+                            .unwrap();
+                            Ok(quote! {
+                                #name: match self.#archived_name() {
+                                    Some(field) => #value,
+                                    None => Default::default(),
+                                }
+                            })
+                        } else {
+                            let value = with_inner(
+                                f,
+                                parse_quote! {
+                                    Deserialize::<#ty, __D>::deserialize(
+                                        &self.#archived_name,
+                                        deserializer,
+                                    )?
+                                },
+                            )
+                            .unwrap();
+                            Ok(quote! { #name: #value })
+                        }
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
 
                 quote! {
                     impl #impl_generics Deserialize<#name #ty_generics, __D> for Archived<#name #ty_generics> #deserialize_where {
@@ -95,18 +139,15 @@ fn derive_deserialize_impl(
             }
             Fields::Unnamed(ref fields) => {
                 let mut deserialize_where = where_clause.clone();
-                for field in fields
-                    .unnamed
-                    .iter()
-                    .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                {
+                for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                     let ty = with_ty(field)?;
                     deserialize_where
                         .predicates
-                        .push(parse_quote! { #ty: Archive });
-                    deserialize_where
-                        .predicates
-                        .push(parse_quote! { Archived<#ty>: Deserialize<#ty, __D> });
+                        .push(field_bound(field, quote! { #ty: Archive }));
+                    deserialize_where.predicates.push(field_bound(
+                        field,
+                        quote! { Archived<#ty>: Deserialize<#ty, __D> },
+                    ));
                 }
 
                 let deserialize_fields = fields.unnamed.iter().enumerate().map(|(i, f)| {
@@ -150,33 +191,27 @@ fn derive_deserialize_impl(
             for variant in data.variants.iter() {
                 match variant.fields {
                     Fields::Named(ref fields) => {
-                        for field in fields
-                            .named
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field)?;
                             deserialize_where
                                 .predicates
-                                .push(parse_quote! { #ty: Archive });
-                            deserialize_where
-                                .predicates
-                                .push(parse_quote! { Archived<#ty>: Deserialize<#ty, __D> });
+                                .push(field_bound(field, quote! { #ty: Archive }));
+                            deserialize_where.predicates.push(field_bound(
+                                field,
+                                quote! { Archived<#ty>: Deserialize<#ty, __D> },
+                            ));
                         }
                     }
                     Fields::Unnamed(ref fields) => {
-                        for field in fields
-                            .unnamed
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field)?;
                             deserialize_where
                                 .predicates
-                                .push(parse_quote! { #ty: Archive });
-                            deserialize_where
-                                .predicates
-                                .push(parse_quote! { Archived<#ty>: Deserialize<#ty, __D> });
+                                .push(field_bound(field, quote! { #ty: Archive }));
+                            deserialize_where.predicates.push(field_bound(
+                                field,
+                                quote! { Archived<#ty>: Deserialize<#ty, __D> },
+                            ));
                         }
                     }
                     Fields::Unit => (),
@@ -0,0 +1,120 @@
+use crate::{
+    archive::archived_field_order,
+    attributes::{archived_field_name, Attributes},
+    util::{add_bounds, field_bound, omit_bounds, strip_raw, Errors},
+    with::make_with_ty,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Index, Path};
+
+/// Checks that the archived layout of a `#[archive(layout)]` type can be described.
+pub fn check_attributes(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    layout: &Path,
+    errors: &mut Errors,
+) {
+    if !matches!(input.data, Data::Struct(_)) {
+        errors.push(Error::new_spanned(
+            layout,
+            "layout may only be used on structs",
+        ));
+    }
+    let conflict = if attributes.archive_as.is_some() {
+        Some("as = \"...\"")
+    } else if attributes.extensible.is_some() {
+        Some("extensible")
+    } else if attributes.unsized_tail.is_some() {
+        Some("unsized")
+    } else {
+        None
+    };
+    if let Some(conflict) = conflict {
+        errors.push(Error::new_spanned(
+            layout,
+            format!("layout may not be used with {}", conflict),
+        ));
+    }
+}
+
+pub fn derive_archive_layout_impl(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let archived_name = attributes.archived.as_ref().map_or_else(
+        || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    );
+    let archived_name_str = strip_raw(&archived_name);
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+    let with_ty = make_with_ty(rkyv_path);
+    if let Data::Struct(ref data) = input.data {
+        for field in data.fields.iter().filter(|f| !omit_bounds(f)) {
+            let ty = with_ty(field)?;
+            where_clause
+                .predicates
+                .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => archived_field_order(fields, attributes.reorder.is_some())
+                .into_iter()
+                .map(|f| {
+                    let field_name = archived_field_name(f)?;
+                    Ok((strip_raw(&field_name), quote! { #field_name }, f))
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+            Fields::Unnamed(ref fields) => fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(i, f)| {
+                    let index = Index::from(i);
+                    (i.to_string(), quote! { #index }, f)
+                })
+                .collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+
+    let field_layouts = fields
+        .iter()
+        .map(|(field_name, member, f)| {
+            let ty = with_ty(f)?;
+            let original_ty = &f.ty;
+            let ty_name = quote! { #original_ty }.to_string();
+            Ok(quote! {
+                #rkyv_path::layout::FieldLayout {
+                    name: #field_name,
+                    ty: #ty_name,
+                    offset: ::core::mem::offset_of!(#archived_name #ty_generics, #member),
+                    size: ::core::mem::size_of::<Archived<#ty>>(),
+                    align: ::core::mem::align_of::<Archived<#ty>>(),
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::layout::ArchiveLayout for #name #ty_generics #where_clause {
+            const LAYOUT: #rkyv_path::layout::StructLayout = #rkyv_path::layout::StructLayout {
+                name: #archived_name_str,
+                size: ::core::mem::size_of::<Archived<Self>>(),
+                align: ::core::mem::align_of::<Archived<Self>>(),
+                fields: &[#(#field_layouts,)*],
+            };
+        }
+    })
+}
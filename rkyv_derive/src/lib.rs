@@ -8,10 +8,15 @@
 
 mod archive;
 mod attributes;
+mod c_header;
+mod columnar;
 mod deserialize;
+mod layout;
 mod repr;
+mod schema;
 mod serde;
 mod serialize;
+mod tail;
 mod util;
 mod with;
 
@@ -30,8 +35,14 @@ use syn::{parse_macro_input, DeriveInput};
 ///
 /// - `archived = "..."`: Changes the name of the generated archived type to the given value. By
 ///   default, archived types are named "Archived" + `the name of the type`.
+/// - `vis = "..."`: Changes the visibility of the generated archived type to the given value. By
+///   default, archived types have the same visibility as the type. The archived type of a public
+///   type must also be public, since it's named by the `Archive` implementation.
 /// - `resolver = "..."`: Changes the name of the generated resolver type to the given value. By
 ///   default, resolver types are named `the name of the type` + "Resolver".
+/// - `resolver_vis = "..."`: Changes the visibility of the generated resolver type to the given
+///   value. By default, resolver types have the same visibility as the type. The resolver of a
+///   public type must also be public.
 /// - `repr(...)`: *Deprecated, use `#[archive_attr(repr(...))]` instead.* Sets the representation
 ///   for the archived type to the given representation. Available representation options may vary
 ///   depending on features and type layout.
@@ -45,13 +56,75 @@ use syn::{parse_macro_input, DeriveInput};
 ///   `Deserialize` bounds.
 /// - `copy_safe`: States that the archived type is tightly packed with no padding bytes. This
 ///   qualifies it for copy optimizations. (requires nightly)
+/// - `accessors`: Generates a public accessor method on the archived type for each field, so that
+///   archives can be read even when the fields are private. Accessors for `String` fields return
+///   `&str`. Only supported for structs with named fields.
+/// - `fixed_size`: Implements `FixedSize` for the type, which exposes the size of its archive as an
+///   associated constant. Every field must also be `FixedSize`.
+/// - `fingerprint`: Implements `Fingerprint` for the type, which hashes the names, order, and
+///   fingerprints of its fields along with the size and alignment of its archived type. Every field
+///   must also be `Fingerprint`.
+/// - `version = ...`: Implements `Version` for the type with the given version number, so that it
+///   can be archived in a `Versioned` envelope.
+/// - `migrate_from(...)`: Declares prior versions of the type that archives can be migrated from.
+///   Each prior version must implement `Version`, and the type must implement
+///   `From<&Archived<Prior>>` for each of them. Requires `version` to be specified.
+/// - `extensible`: Stores the fields of the archived struct out of line with their length, so that
+///   fields can be added to the end of the struct without breaking existing archives. The archived
+///   type has an accessor for each field that returns `None` if the field is missing, and missing
+///   fields are deserialized as their default values. Only supported for structs with named fields.
+/// - `layout`: Implements `ArchiveLayout` for the type, which describes the offset, size, and
+///   alignment of each field of its archived type as a constant. Only supported for structs, and
+///   not with `extensible` or `unsized`.
+/// - `schema`: Implements `ArchiveSchema` for the type, which describes the names, offsets, and
+///   schemas of the fields of its archived type so that archives can be walked without knowing
+///   their types. Every field must also be `ArchiveSchema`, except for fields with `#[with]` or
+///   omitted bounds which are opaque. Not supported with `as`, `extensible`, or `unsized`.
+/// - `assert_size = ...`, `assert_align = ...`: Fails the build if the size or alignment of the
+///   archived type is not the given number of bytes. This guards types that must stay
+///   wire-compatible against accidental layout changes. Not supported for generic types.
+/// - `reorder`: Sorts the fields of the archived struct by alignment, largest first, to minimize
+///   padding without reordering the fields of the original struct. Only fields of primitive types
+///   and arrays of primitives can be sorted; all other fields are placed first in declaration
+///   order. This does nothing without the `strict` feature unless the archived struct is made
+///   `repr(C)` some other way (e.g. `#[archive_attr(repr(C))]`), since the compiler is otherwise
+///   free to reorder its fields anyway. Only supported for structs with named fields, and not with
+///   `extensible`.
+/// - `unsized`: Archives the last field of the struct inline after the other fields, so that the
+///   archived type is unsized instead of holding a relative pointer to the last field. The last
+///   field must implement `ArchiveTail` (e.g. `Vec<T>`, `Box<[T]>`, or `String`) and is archived as
+///   an `InlineTail`, which dereferences to its archived tail. The struct then implements
+///   `ArchiveUnsized` and `SerializeUnsized` instead of `Archive` and `Serialize`, and can be
+///   archived behind a `Box`. Only supported for structs with named fields.
+/// - `columnar`: Also generates an `Archived{Name}Columns` type with an `ArchivedVec` column for
+///   each field, so that a `Vec` of the struct can be archived as columns with
+///   `#[with(AsColumns)]`. Only supported for structs with named fields, and not with `unsized`.
+/// - `c_header`: Lays out the archived struct as `repr(C)` and implements `CStruct` for it, so that
+///   its definition can be written to a C header with `CHeader`. Every field must have an archived
+///   type that implements `CType`. Requires the `c_header` feature. Only supported for non-generic
+///   structs, and not with `extensible` or `unsized`.
 /// - `as = "..."`: Instead of generating a separate archived type, this type will archive as the
 ///   named type. This is useful for types which are generic over their parameters.
 /// - `crate = "..."`: Chooses an alternative crate path to import rkyv from.
 ///
 /// `#[archive_attr(...)]` adds the attributes passed as arguments as attributes to the generated
 /// type. This is commonly used with attributes like `derive(...)` to derive trait implementations
-/// for the archived type.
+/// for the archived type. `#[archive_attr(...)]` can also be added to individual fields and enum
+/// variant fields to add attributes to the corresponding fields of the archived type.
+///
+/// `#[archive(rename = "...")]` can be added to the fields of structs with named fields to change
+/// the name of the corresponding field of the archived type.
+///
+/// Fields can be conditionally compiled with `#[cfg(...)]`. Fields that are compiled out are also
+/// left out of the archived type, the resolver, and all of the generated implementations.
+///
+/// # Enums
+///
+/// Archived enums also get a fieldless tag enum named after the archived type with a "Tag" suffix
+/// (e.g. `ArchivedMyEnumTag`), and accessors for branching on variants without matching on their
+/// fields: `tag()` returns the tag of the variant, `is_my_variant()` returns whether the archived
+/// enum is that variant, and `as_my_variant()` returns the archived fields of the variant if it's
+/// that variant. `as_*` accessors aren't generated for unit variants.
 ///
 /// # Recursive types
 ///
@@ -62,6 +135,10 @@ use syn::{parse_macro_input, DeriveInput};
 /// allow recursive structures. This may be too coarse for some types, in which case additional type
 /// bounds may be required with `bound(...)`.
 ///
+/// Fields of type `PhantomData<T>` never add a bound, since `PhantomData` can be archived for any
+/// `T`. Marker parameters that only appear in `PhantomData` fields therefore don't need to implement
+/// `Archive`.
+///
 /// # Wrappers
 ///
 /// Wrappers transparently customize archived types by providing different implementations of core
@@ -1,6 +1,7 @@
 use proc_macro2::{Literal, Punct, Spacing, Span, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
-use syn::{spanned::Spanned, Error, Lit, LitInt, Meta, NestedMeta};
+use std::convert::TryFrom;
+use syn::{spanned::Spanned, Error, Ident, Lit, LitInt, Meta, NestedMeta};
 
 #[derive(Clone, Copy)]
 pub enum IntRepr {
@@ -43,35 +44,70 @@ impl IntRepr {
     #[inline]
     #[cfg(feature = "arbitrary_enum_discriminant")]
     pub fn enum_discriminant(&self, index: usize) -> EnumDiscriminant {
+        self.explicit_discriminant(index as i128)
+    }
+
+    #[inline]
+    pub fn explicit_discriminant(&self, value: i128) -> EnumDiscriminant {
         #[cfg(not(any(
             all(target_endian = "little", feature = "archive_be"),
             all(target_endian = "big", feature = "archive_le"),
         )))]
-        let value = index as u128;
+        let value = value as u128;
 
         #[cfg(any(
             all(target_endian = "little", feature = "archive_be"),
             all(target_endian = "big", feature = "archive_le"),
         ))]
         let value = match self {
-            Self::I8 => (index as i8).swap_bytes() as u128,
-            Self::I16 => (index as i16).swap_bytes() as u128,
-            Self::I32 => (index as i32).swap_bytes() as u128,
-            Self::I64 => (index as i64).swap_bytes() as u128,
-            Self::I128 => (index as i128).swap_bytes() as u128,
-            Self::U8 => (index as u8).swap_bytes() as u128,
-            Self::U16 => (index as u16).swap_bytes() as u128,
-            Self::U32 => (index as u32).swap_bytes() as u128,
-            Self::U64 => (index as u64).swap_bytes() as u128,
-            Self::U128 => (index as u128).swap_bytes(),
+            Self::I8 => (value as i8).swap_bytes() as u128,
+            Self::I16 => (value as i16).swap_bytes() as u128,
+            Self::I32 => (value as i32).swap_bytes() as u128,
+            Self::I64 => (value as i64).swap_bytes() as u128,
+            Self::I128 => value.swap_bytes() as u128,
+            Self::U8 => (value as u8).swap_bytes() as u128,
+            Self::U16 => (value as u16).swap_bytes() as u128,
+            Self::U32 => (value as u32).swap_bytes() as u128,
+            Self::U64 => (value as u64).swap_bytes() as u128,
+            Self::U128 => (value as u128).swap_bytes(),
         };
 
         EnumDiscriminant { repr: *self, value }
     }
+
+    #[inline]
+    pub fn contains(&self, value: i128) -> bool {
+        match self {
+            Self::I8 => i8::try_from(value).is_ok(),
+            Self::I16 => i16::try_from(value).is_ok(),
+            Self::I32 => i32::try_from(value).is_ok(),
+            Self::I64 => i64::try_from(value).is_ok(),
+            Self::I128 => true,
+            Self::U8 => u8::try_from(value).is_ok(),
+            Self::U16 => u16::try_from(value).is_ok(),
+            Self::U32 => u32::try_from(value).is_ok(),
+            Self::U64 => u64::try_from(value).is_ok(),
+            Self::U128 => value >= 0,
+        }
+    }
+
+    pub fn from_ident(ident: &Ident) -> Option<Self> {
+        Some(match ident.to_string().as_str() {
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "i128" => Self::I128,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "u128" => Self::U128,
+            _ => return None,
+        })
+    }
 }
 
-// None of these variants are constructed unless the arbitrary_enum_discriminant feature is enabled
-#[allow(dead_code)]
 pub struct EnumDiscriminant {
     repr: IntRepr,
     value: u128,
@@ -180,26 +216,10 @@ impl Repr {
                                 BaseRepr::Transparent
                             } else if path.is_ident("C") {
                                 BaseRepr::C
-                            } else if path.is_ident("i8") {
-                                BaseRepr::Int(IntRepr::I8)
-                            } else if path.is_ident("i16") {
-                                BaseRepr::Int(IntRepr::I16)
-                            } else if path.is_ident("i32") {
-                                BaseRepr::Int(IntRepr::I32)
-                            } else if path.is_ident("i64") {
-                                BaseRepr::Int(IntRepr::I64)
-                            } else if path.is_ident("i128") {
-                                BaseRepr::Int(IntRepr::I128)
-                            } else if path.is_ident("u8") {
-                                BaseRepr::Int(IntRepr::U8)
-                            } else if path.is_ident("u16") {
-                                BaseRepr::Int(IntRepr::U16)
-                            } else if path.is_ident("u32") {
-                                BaseRepr::Int(IntRepr::U32)
-                            } else if path.is_ident("u64") {
-                                BaseRepr::Int(IntRepr::U64)
-                            } else if path.is_ident("u128") {
-                                BaseRepr::Int(IntRepr::U128)
+                            } else if let Some(int_repr) =
+                                path.get_ident().and_then(IntRepr::from_ident)
+                            {
+                                BaseRepr::Int(int_repr)
                             } else {
                                 return Err(Error::new_spanned(
                                     path,
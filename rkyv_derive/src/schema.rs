@@ -0,0 +1,228 @@
+use crate::{
+    archive::archived_field_order,
+    attributes::{archived_field_name, Attributes},
+    repr::IntRepr,
+    util::{add_bounds, field_bound, omit_bounds, strip_raw, Errors},
+    with::make_with_ty,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Field, Fields, Ident, Index, Path};
+
+/// Checks that the archived type of a `#[archive(schema)]` type can be described.
+pub fn check_attributes(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    schema: &Path,
+    errors: &mut Errors,
+) {
+    if matches!(input.data, Data::Union(_)) {
+        errors.push(Error::new_spanned(
+            schema,
+            "schema may only be used on structs and enums",
+        ));
+    }
+    let conflict = if attributes.archive_as.is_some() {
+        Some("as = \"...\"")
+    } else if attributes.extensible.is_some() {
+        Some("extensible")
+    } else if attributes.unsized_tail.is_some() {
+        Some("unsized")
+    } else {
+        None
+    };
+    if let Some(conflict) = conflict {
+        errors.push(Error::new_spanned(
+            schema,
+            format!("schema may not be used with {}", conflict),
+        ));
+    }
+}
+
+// Fields archived with a wrapper or with omitted bounds can't be described, so they're opaque
+fn is_opaque(field: &Field) -> bool {
+    omit_bounds(field) || field.attrs.iter().any(|a| a.path.is_ident("with"))
+}
+
+/// Generates the `ArchiveSchema` impl for a type.
+///
+/// Enums must pass the integer repr of their tag, and the impl must be placed next to the
+/// `ArchivedTag` enum and variant structs generated for them.
+pub fn derive_archive_schema_impl(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+    tag_repr: Option<IntRepr>,
+) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let archived_name = attributes.archived.as_ref().map_or_else(
+        || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    );
+    let archived_name_str = strip_raw(&archived_name);
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+    let with_ty = make_with_ty(rkyv_path);
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    for field in fields.iter().filter(|f| !omit_bounds(f)) {
+        let ty = with_ty(field)?;
+        where_clause
+            .predicates
+            .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
+        if !is_opaque(field) {
+            let ty = &field.ty;
+            where_clause.predicates.push(field_bound(
+                field,
+                quote! { #ty: #rkyv_path::schema::ArchiveSchema },
+            ));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_schema = |field_name: &str, offset: TokenStream, f: &Field| {
+        let ty = with_ty(f)?;
+        let schema = if is_opaque(f) {
+            let original_ty = &f.ty;
+            let ty_name = quote! { #original_ty }.to_string();
+            quote! {
+                &#rkyv_path::schema::Schema::Opaque {
+                    name: #ty_name,
+                    size: ::core::mem::size_of::<Archived<#ty>>(),
+                    align: ::core::mem::align_of::<Archived<#ty>>(),
+                }
+            }
+        } else {
+            let original_ty = &f.ty;
+            quote! { &<#original_ty as #rkyv_path::schema::ArchiveSchema>::SCHEMA }
+        };
+        Ok(quote! {
+            #rkyv_path::schema::FieldSchema {
+                name: #field_name,
+                offset: #offset,
+                schema: #schema,
+            }
+        })
+    };
+
+    let schema = match input.data {
+        Data::Struct(ref data) => {
+            let fields = match data.fields {
+                Fields::Named(ref fields) => archived_field_order(
+                    fields,
+                    attributes.reorder.is_some(),
+                )
+                .into_iter()
+                .map(|f| {
+                    let field_name = archived_field_name(f)?;
+                    field_schema(
+                        &strip_raw(&field_name),
+                        quote! {
+                            ::core::mem::offset_of!(#archived_name #ty_generics, #field_name)
+                        },
+                        f,
+                    )
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+                Fields::Unnamed(ref fields) => fields
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| {
+                        let index = Index::from(i);
+                        field_schema(
+                            &i.to_string(),
+                            quote! { ::core::mem::offset_of!(#archived_name #ty_generics, #index) },
+                            f,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?,
+                Fields::Unit => Vec::new(),
+            };
+            quote! {
+                #rkyv_path::schema::Schema::Struct {
+                    name: #archived_name_str,
+                    size: ::core::mem::size_of::<Archived<Self>>(),
+                    align: ::core::mem::align_of::<Archived<Self>>(),
+                    fields: &[#(#fields,)*],
+                }
+            }
+        }
+        Data::Enum(ref data) => {
+            let tag_repr = tag_repr.unwrap();
+            let variants = data
+                .variants
+                .iter()
+                .map(|v| {
+                    let variant = &v.ident;
+                    let variant_name = strip_raw(variant);
+                    let archived_variant_name =
+                        Ident::new(&format!("ArchivedVariant{}", variant_name), v.span());
+                    let fields = match v.fields {
+                        Fields::Named(ref fields) => fields
+                            .named
+                            .iter()
+                            .map(|f| {
+                                let field_name = f.ident.as_ref().unwrap();
+                                field_schema(
+                                    &strip_raw(field_name),
+                                    quote! {
+                                        ::core::mem::offset_of!(#archived_variant_name #ty_generics, #field_name)
+                                    },
+                                    f,
+                                )
+                            })
+                            .collect::<Result<Vec<_>, Error>>()?,
+                        // The first field of a tuple variant struct is the tag
+                        Fields::Unnamed(ref fields) => fields
+                            .unnamed
+                            .iter()
+                            .enumerate()
+                            .map(|(i, f)| {
+                                let index = Index::from(i + 1);
+                                field_schema(
+                                    &i.to_string(),
+                                    quote! {
+                                        ::core::mem::offset_of!(#archived_variant_name #ty_generics, #index)
+                                    },
+                                    f,
+                                )
+                            })
+                            .collect::<Result<Vec<_>, Error>>()?,
+                        Fields::Unit => Vec::new(),
+                    };
+                    Ok(quote! {
+                        #rkyv_path::schema::VariantSchema {
+                            name: #variant_name,
+                            tag: ArchivedTag::#variant as #tag_repr as i128,
+                            fields: &[#(#fields,)*],
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            quote! {
+                #rkyv_path::schema::Schema::Enum {
+                    name: #archived_name_str,
+                    size: ::core::mem::size_of::<Archived<Self>>(),
+                    align: ::core::mem::align_of::<Archived<Self>>(),
+                    tag: &<#tag_repr as #rkyv_path::schema::ArchiveSchema>::SCHEMA,
+                    variants: &[#(#variants,)*],
+                }
+            }
+        }
+        Data::Union(_) => unreachable!(),
+    };
+
+    Ok(quote! {
+        unsafe impl #impl_generics #rkyv_path::schema::ArchiveSchema for #name #ty_generics #where_clause {
+            const SCHEMA: #rkyv_path::schema::Schema = #schema;
+        }
+    })
+}
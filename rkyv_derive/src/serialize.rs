@@ -1,6 +1,7 @@
 use crate::{
     attributes::{parse_attributes, Attributes},
-    util::{add_bounds, strip_raw},
+    columnar, tail,
+    util::{add_bounds, field_bound, omit_bounds, strip_raw},
     with::{make_with_cast, make_with_ty},
 };
 use proc_macro2::TokenStream;
@@ -12,7 +13,19 @@ use syn::{
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
     let attributes = parse_attributes(&input)?;
-    derive_serialize_impl(input, &attributes)
+    if attributes.unsized_tail.is_some() {
+        return tail::derive_serialize_impl(input, &attributes);
+    }
+    let columns_impl = if attributes.columnar.is_some() {
+        Some(columnar::derive_serialize_impl(input.clone(), &attributes)?)
+    } else {
+        None
+    };
+    let result = derive_serialize_impl(input, &attributes)?;
+    Ok(quote! {
+        #result
+        #columns_impl
+    })
 }
 
 fn derive_serialize_impl(
@@ -58,15 +71,11 @@ fn derive_serialize_impl(
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
                 let mut serialize_where = where_clause.clone();
-                for field in fields
-                    .named
-                    .iter()
-                    .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                {
+                for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                     let ty = with_ty(field)?;
                     serialize_where
                         .predicates
-                        .push(parse_quote! { #ty: Serialize<__S> });
+                        .push(field_bound(field, quote! { #ty: Serialize<__S> }));
                 }
 
                 let resolver_values = fields.named.iter().map(|f| {
@@ -75,28 +84,42 @@ fn derive_serialize_impl(
                     quote! { #name: Serialize::<__S>::serialize(#field, serializer)? }
                 });
 
-                quote! {
-                    impl #impl_generics Serialize<__S> for #name #ty_generics #serialize_where {
-                        #[inline]
-                        fn serialize(&self, serializer: &mut __S) -> ::core::result::Result<Self::Resolver, __S::Error> {
-                            Ok(#resolver {
-                                #(#resolver_values,)*
-                            })
+                if attributes.extensible.is_some() {
+                    serialize_where
+                        .predicates
+                        .push(parse_quote! { __S: #rkyv_path::ser::Serializer });
+
+                    quote! {
+                        impl #impl_generics Serialize<__S> for #name #ty_generics #serialize_where {
+                            #[inline]
+                            fn serialize(&self, serializer: &mut __S) -> ::core::result::Result<Self::Resolver, __S::Error> {
+                                let resolver = #resolver {
+                                    #(#resolver_values,)*
+                                };
+                                #rkyv_path::extensible::serialize_fields(self, resolver, serializer)
+                            }
+                        }
+                    }
+                } else {
+                    quote! {
+                        impl #impl_generics Serialize<__S> for #name #ty_generics #serialize_where {
+                            #[inline]
+                            fn serialize(&self, serializer: &mut __S) -> ::core::result::Result<Self::Resolver, __S::Error> {
+                                Ok(#resolver {
+                                    #(#resolver_values,)*
+                                })
+                            }
                         }
                     }
                 }
             }
             Fields::Unnamed(ref fields) => {
                 let mut serialize_where = where_clause.clone();
-                for field in fields
-                    .unnamed
-                    .iter()
-                    .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                {
+                for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                     let ty = with_ty(field)?;
                     serialize_where
                         .predicates
-                        .push(parse_quote! { #ty: Serialize<__S> });
+                        .push(field_bound(field, quote! { #ty: Serialize<__S> }));
                 }
 
                 let resolver_values = fields.unnamed.iter().enumerate().map(|(i, f)| {
@@ -132,27 +155,19 @@ fn derive_serialize_impl(
             for variant in data.variants.iter() {
                 match variant.fields {
                     Fields::Named(ref fields) => {
-                        for field in fields
-                            .named
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.named.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field)?;
                             serialize_where
                                 .predicates
-                                .push(parse_quote! { #ty: Serialize<__S> });
+                                .push(field_bound(field, quote! { #ty: Serialize<__S> }));
                         }
                     }
                     Fields::Unnamed(ref fields) => {
-                        for field in fields
-                            .unnamed
-                            .iter()
-                            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
-                        {
+                        for field in fields.unnamed.iter().filter(|f| !omit_bounds(f)) {
                             let ty = with_ty(field)?;
                             serialize_where
                                 .predicates
-                                .push(parse_quote! { #ty: Serialize<__S> });
+                                .push(field_bound(field, quote! { #ty: Serialize<__S> }));
                         }
                     }
                     Fields::Unit => (),
@@ -0,0 +1,488 @@
+use crate::{
+    archive::field_archive_attrs,
+    attributes::{archived_field_name, Attributes},
+    repr::{BaseRepr, Repr},
+    util::{add_bounds, field_bound, impl_generics_with, omit_bounds, rkyv_path, strip_raw},
+    with::{make_with_cast, make_with_ty, with_inner},
+};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, Attribute, Data, DeriveInput, Error, Field, Fields, Ident, Path};
+
+/// Splits the fields of an `#[archive(unsized)]` struct into its header fields and its tail.
+pub fn split_fields<'a>(
+    input: &'a DeriveInput,
+    attribute: &Path,
+) -> Result<(Vec<&'a Field>, &'a Field), Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => Some(fields.named.iter().collect::<Vec<_>>()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let mut fields = fields.ok_or_else(|| {
+        Error::new_spanned(
+            attribute,
+            "unsized may only be used on structs with named fields",
+        )
+    })?;
+    let tail = fields.pop().ok_or_else(|| {
+        Error::new_spanned(
+            attribute,
+            "unsized structs must have a last field to archive as the tail",
+        )
+    })?;
+    if tail.attrs.iter().any(|a| a.path.is_ident("with")) {
+        return Err(Error::new_spanned(
+            tail,
+            "the tail of an unsized struct may not use a wrapper",
+        ));
+    }
+    Ok((fields, tail))
+}
+
+/// Returns the archived type of the tail of an unsized struct.
+///
+/// The type has to be syntactically unsized, or else the impls of `Pointee` and `ArchivePointee`
+/// would conflict with the blanket impls for sized types. `InlineTail` always ends in a slice, so
+/// the archived struct is unsized even though the archived tail is chosen by `ArchiveTail`.
+fn archived_tail_ty(tail: &Field, rkyv_path: &Path) -> TokenStream {
+    let ty = &tail.ty;
+    quote! { #rkyv_path::tail::InlineTail<#ty> }
+}
+
+fn resolver_name(name: &Ident, attributes: &Attributes) -> Ident {
+    attributes.resolver.as_ref().map_or_else(
+        || Ident::new(&format!("{}Resolver", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    )
+}
+
+pub fn derive_archive_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+
+    let (header_fields, tail) = split_fields(&input, attributes.unsized_tail.as_ref().unwrap())?;
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let archived_vis = attributes.archived_vis.as_ref().unwrap_or(vis);
+    let resolver_vis = attributes.resolver_vis.as_ref().unwrap_or(vis);
+    let generics = &input.generics;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let where_clause = where_clause.unwrap();
+
+    let rkyv_path = rkyv_path(attributes);
+    let with_ty = make_with_ty(&rkyv_path);
+    let with_cast = make_with_cast(&rkyv_path);
+
+    let non_exhaustive = input
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("non_exhaustive"))
+        .cloned();
+    let archive_attrs = attributes
+        .attrs
+        .iter()
+        .map::<Attribute, _>(|d| parse_quote! { #[#d] })
+        .chain(non_exhaustive);
+
+    let archived_name = attributes.archived.as_ref().map_or_else(
+        || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    );
+    let archived_doc = format!("An archived [`{}`]", name);
+    let header_name = Ident::new(&format!("{}Header", strip_raw(&archived_name)), name.span());
+
+    let resolver = resolver_name(name, attributes);
+    let resolver_doc = format!("The resolver for the fields of an archived [`{}`]", name);
+
+    // Archived structs with an unsized tail are always laid out like C structs, since the header
+    // has to match the layout of the archived struct up to the tail
+    let repr = Repr {
+        base_repr: Some((BaseRepr::C, Span::call_site())),
+        modifier: attributes.archived_repr.modifier.clone(),
+    };
+
+    let tail_ty = &tail.ty;
+    let tail_name = &tail.ident;
+    let archived_tail_name = archived_field_name(tail)?;
+
+    let mut archive_where = where_clause.clone();
+    for field in header_fields.iter().filter(|f| !omit_bounds(f)) {
+        let ty = with_ty(field)?;
+        archive_where
+            .predicates
+            .push(field_bound(field, quote! { #ty: #rkyv_path::Archive }));
+    }
+    if !omit_bounds(tail) {
+        archive_where.predicates.push(field_bound(
+            tail,
+            quote! { #tail_ty: #rkyv_path::tail::ArchiveTail },
+        ));
+    }
+
+    let archived_fields = header_fields
+        .iter()
+        .map(|f| {
+            let field_name = archived_field_name(f)?;
+            let ty = with_ty(f)?;
+            let vis = &f.vis;
+            let field_doc = format!(
+                "The archived counterpart of [`{}::{}`]",
+                name,
+                f.ident.as_ref().unwrap()
+            );
+            let archive_attrs = field_archive_attrs(f);
+            Ok(quote! {
+                #[doc = #field_doc]
+                #(#[#archive_attrs])*
+                #vis #field_name: #rkyv_path::Archived<#ty>
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let archived_tail = {
+        let vis = &tail.vis;
+        let ty = archived_tail_ty(tail, &rkyv_path);
+        let field_doc = format!(
+            "The archived counterpart of [`{}::{}`]",
+            name,
+            tail_name.as_ref().unwrap()
+        );
+        let archive_attrs = field_archive_attrs(tail);
+        quote! {
+            #[doc = #field_doc]
+            #(#[#archive_attrs])*
+            #vis #archived_tail_name: #ty
+        }
+    };
+
+    let header_fields_def = header_fields
+        .iter()
+        .map(|f| {
+            let field_name = archived_field_name(f)?;
+            let ty = with_ty(f)?;
+            Ok(quote! { #field_name: #rkyv_path::Archived<#ty> })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let resolver_fields = header_fields.iter().map(|f| {
+        let name = &f.ident;
+        let ty = with_ty(f).unwrap();
+        quote! { #name: #rkyv_path::Resolver<#ty> }
+    });
+
+    let resolve_fields = header_fields
+        .iter()
+        .map(|f| {
+            let name = &f.ident;
+            let archived_name = archived_field_name(f)?;
+            let field = with_cast(f, parse_quote! { (&self.#name) })?;
+            Ok(quote! {
+                let (fp, fo) = out_field!(out.#archived_name);
+                #rkyv_path::Archive::resolve(#field, pos + fp, resolver.#name, fo);
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let layout_raw_impl = if cfg!(feature = "validation") {
+        Some(quote! {
+            impl #impl_generics #rkyv_path::validation::LayoutRaw for #archived_name #ty_generics #archive_where {
+                #[inline]
+                fn layout_raw(
+                    value: *const Self,
+                ) -> ::core::result::Result<::core::alloc::Layout, ::core::alloc::LayoutError> {
+                    tail::tail_layout::<#name #ty_generics>(tail::metadata(value))
+                }
+
+                #[inline]
+                fn collection_len(value: *const Self) -> Option<usize> {
+                    Some(tail::metadata(value))
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[doc = #archived_doc]
+        #(#archive_attrs)*
+        #repr
+        #archived_vis struct #archived_name #generics #archive_where {
+            #(#archived_fields,)*
+            #archived_tail,
+        }
+
+        #[automatically_derived]
+        #[doc = #resolver_doc]
+        #resolver_vis struct #resolver #generics #archive_where {
+            #(#resolver_fields,)*
+        }
+
+        #[automatically_derived]
+        const _: () = {
+            use #rkyv_path::{
+                from_archived, out_field, tail, to_archived, ArchivePointee, ArchiveUnsized,
+                Archived, ArchivedMetadata, FixedUsize,
+            };
+
+            #[allow(dead_code)]
+            #repr
+            pub struct #header_name #generics #archive_where {
+                #(#header_fields_def,)*
+                #archived_tail_name: [Archived<<#tail_ty as tail::ArchiveTail>::Element>; 0],
+            }
+
+            impl #impl_generics tail::Pointee for #archived_name #ty_generics #archive_where {
+                type Metadata = usize;
+            }
+
+            impl #impl_generics ArchivePointee for #archived_name #ty_generics #archive_where {
+                type ArchivedMetadata = Archived<usize>;
+
+                #[inline]
+                fn pointer_metadata(archived: &Self::ArchivedMetadata) -> usize {
+                    from_archived!(*archived) as usize
+                }
+            }
+
+            impl #impl_generics ArchiveUnsized for #name #ty_generics #archive_where {
+                type Archived = #archived_name #ty_generics;
+                type MetadataResolver = ();
+
+                #[inline]
+                unsafe fn resolve_metadata(
+                    &self,
+                    _: usize,
+                    _: Self::MetadataResolver,
+                    out: *mut ArchivedMetadata<Self>,
+                ) {
+                    let len = tail::ArchiveTail::tail_elements(&self.#tail_name).len();
+                    out.write(to_archived!(len as FixedUsize));
+                }
+            }
+
+            unsafe impl #impl_generics tail::ArchiveWithTail for #name #ty_generics #archive_where {
+                type Header = #header_name #ty_generics;
+                type HeaderResolver = #resolver #ty_generics;
+                type Tail = #tail_ty;
+
+                #[inline]
+                fn tail_offset() -> usize {
+                    let mut header = ::core::mem::MaybeUninit::<Self::Header>::uninit();
+                    let out = header.as_mut_ptr();
+                    out_field!(out.#archived_tail_name).0
+                }
+
+                #[inline]
+                fn tail(&self) -> &Self::Tail {
+                    &self.#tail_name
+                }
+
+                // Some resolvers will be (), this allow is to prevent clippy from complaining
+                #[allow(clippy::unit_arg)]
+                #[inline]
+                unsafe fn resolve_header(
+                    &self,
+                    pos: usize,
+                    resolver: Self::HeaderResolver,
+                    out: *mut Self::Header,
+                ) {
+                    #(#resolve_fields)*
+                }
+            }
+
+            #layout_raw_impl
+        };
+    })
+}
+
+pub fn derive_serialize_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+    if let Some(ref bounds) = attributes.serialize_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+
+    let (header_fields, tail) = split_fields(&input, attributes.unsized_tail.as_ref().unwrap())?;
+
+    let rkyv_path = rkyv_path(attributes);
+    let with_ty = make_with_ty(&rkyv_path);
+    let with_cast = make_with_cast(&rkyv_path);
+
+    let impl_input_generics = impl_generics_with(
+        &input,
+        parse_quote! { __S: Fallible + Serializer + ScratchSpace + ?Sized },
+    );
+    let name = &input.ident;
+    let (impl_generics, _, _) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let where_clause = where_clause.unwrap();
+
+    let resolver = resolver_name(name, attributes);
+    let tail_ty = &tail.ty;
+
+    let mut serialize_where = where_clause.clone();
+    for field in header_fields.iter().filter(|f| !omit_bounds(f)) {
+        let ty = with_ty(field)?;
+        serialize_where
+            .predicates
+            .push(field_bound(field, quote! { #ty: Serialize<__S> }));
+    }
+    if !omit_bounds(tail) {
+        serialize_where.predicates.push(field_bound(
+            tail,
+            quote! { <#tail_ty as #rkyv_path::tail::ArchiveTail>::Element: Serialize<__S> },
+        ));
+    }
+
+    let resolver_values = header_fields
+        .iter()
+        .map(|f| {
+            let name = &f.ident;
+            let field = with_cast(f, parse_quote! { &self.#name })?;
+            Ok(quote! { #name: Serialize::<__S>::serialize(#field, serializer)? })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        const _: () = {
+            use #rkyv_path::{
+                ser::{ScratchSpace, Serializer},
+                Fallible, Serialize, SerializeUnsized,
+            };
+
+            impl #impl_generics SerializeUnsized<__S> for #name #ty_generics #serialize_where {
+                #[inline]
+                fn serialize_unsized(&self, serializer: &mut __S) -> ::core::result::Result<usize, __S::Error> {
+                    let resolver = #resolver {
+                        #(#resolver_values,)*
+                    };
+                    #rkyv_path::tail::serialize_with_tail(self, resolver, serializer)
+                }
+
+                #[inline]
+                fn serialize_metadata(&self, _: &mut __S) -> ::core::result::Result<(), __S::Error> {
+                    Ok(())
+                }
+            }
+        };
+    })
+}
+
+pub fn derive_deserialize_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+    if let Some(ref bounds) = attributes.deserialize_bound {
+        add_bounds(bounds, where_clause)?;
+    }
+
+    let (header_fields, tail) = split_fields(&input, attributes.unsized_tail.as_ref().unwrap())?;
+
+    let rkyv_path = rkyv_path(attributes);
+    let with_ty = make_with_ty(&rkyv_path);
+
+    let impl_input_generics = impl_generics_with(&input, parse_quote! { __D: Fallible + ?Sized });
+    let name = &input.ident;
+    let (impl_generics, _, _) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let where_clause = where_clause.unwrap();
+
+    let tail_ty = &tail.ty;
+    let tail_name = &tail.ident;
+    let archived_tail_name = archived_field_name(tail)?;
+
+    let mut deserialize_where = where_clause.clone();
+    for field in header_fields.iter().filter(|f| !omit_bounds(f)) {
+        let ty = with_ty(field)?;
+        deserialize_where
+            .predicates
+            .push(field_bound(field, quote! { #ty: Archive }));
+        deserialize_where.predicates.push(field_bound(
+            field,
+            quote! { Archived<#ty>: Deserialize<#ty, __D> },
+        ));
+    }
+    if !omit_bounds(tail) {
+        deserialize_where.predicates.push(field_bound(
+            tail,
+            quote! { #tail_ty: #rkyv_path::tail::DeserializeTail<__D> },
+        ));
+    }
+
+    let deserialize_fields = header_fields
+        .iter()
+        .map(|f| {
+            let name = &f.ident;
+            let archived_name = archived_field_name(f)?;
+            let ty = with_ty(f)?;
+            let value = with_inner(
+                f,
+                parse_quote! {
+                    Deserialize::<#ty, __D>::deserialize(
+                        &self.#archived_name,
+                        deserializer,
+                    )?
+                },
+            )?;
+            Ok(quote! { #name: #value })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        const _: () = {
+            use #rkyv_path::{Archive, ArchiveUnsized, Archived, Deserialize, DeserializeUnsized, Fallible};
+
+            impl #impl_generics Deserialize<#name #ty_generics, __D> for <#name #ty_generics as ArchiveUnsized>::Archived #deserialize_where {
+                #[inline]
+                fn deserialize(&self, deserializer: &mut __D) -> ::core::result::Result<#name #ty_generics, __D::Error> {
+                    Ok(#name {
+                        #(#deserialize_fields,)*
+                        #tail_name: #rkyv_path::tail::DeserializeTail::deserialize_tail(
+                            &*self.#archived_tail_name,
+                            deserializer,
+                        )?,
+                    })
+                }
+            }
+
+            impl #impl_generics DeserializeUnsized<#name #ty_generics, __D> for <#name #ty_generics as ArchiveUnsized>::Archived #deserialize_where {
+                #[inline]
+                unsafe fn deserialize_unsized(
+                    &self,
+                    deserializer: &mut __D,
+                    alloc: impl FnMut(::core::alloc::Layout) -> *mut u8,
+                ) -> ::core::result::Result<*mut (), __D::Error> {
+                    let value = Deserialize::<#name #ty_generics, __D>::deserialize(self, deserializer)?;
+                    Ok(#rkyv_path::tail::alloc_deserialized(value, alloc))
+                }
+
+                #[inline]
+                fn deserialize_metadata(&self, _: &mut __D) -> ::core::result::Result<(), __D::Error> {
+                    Ok(())
+                }
+            }
+        };
+    })
+}
@@ -1,5 +1,10 @@
-use proc_macro2::Ident;
-use syn::{punctuated::Punctuated, Error, LitStr, Token, WhereClause, WherePredicate};
+use crate::{attributes::Attributes, serde::respan::respan};
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    parse_quote, punctuated::Punctuated, spanned::Spanned, DeriveInput, Error, Field, GenericParam,
+    Generics, LitStr, Path, Token, Type, WhereClause, WherePredicate,
+};
 
 pub fn add_bounds(bounds: &LitStr, where_clause: &mut WhereClause) -> Result<(), Error> {
     let clauses = bounds.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
@@ -9,6 +14,91 @@ pub fn add_bounds(bounds: &LitStr, where_clause: &mut WhereClause) -> Result<(),
     Ok(())
 }
 
+/// Returns whether the trait bounds for a field should be omitted.
+///
+/// Bounds are omitted for fields marked with `#[omit_bounds]` and for `PhantomData` fields without
+/// a `#[with]` attribute, since `PhantomData<T>` implements the rkyv traits for every `T`.
+pub fn omit_bounds(field: &Field) -> bool {
+    field.attrs.iter().any(|a| a.path.is_ident("omit_bounds"))
+        || (is_phantom_data(&field.ty) && !field.attrs.iter().any(|a| a.path.is_ident("with")))
+}
+
+/// Parses a where predicate required by a field.
+///
+/// The predicate is spanned at the type of the field, so that an unsatisfied bound is reported at
+/// the field instead of at the derive.
+pub fn field_bound(field: &Field, predicate: TokenStream) -> WherePredicate {
+    syn::parse2(respan(predicate, field.ty.span())).unwrap()
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Returns whether the given type mentions `Self` or any of the given generic parameters.
+pub fn mentions_generics(ty: &Type, generics: &Generics) -> bool {
+    fn mentions(tokens: TokenStream, names: &[String]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Group(group) => mentions(group.stream(), names),
+            TokenTree::Ident(ident) => ident == "Self" || names.iter().any(|name| ident == name),
+            _ => false,
+        })
+    }
+
+    let names = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => param.ident.to_string(),
+            GenericParam::Lifetime(param) => param.lifetime.ident.to_string(),
+            GenericParam::Const(param) => param.ident.to_string(),
+        })
+        .collect::<Vec<_>>();
+    mentions(ty.to_token_stream(), &names)
+}
+
+/// Returns whether the given type is syntactically a `String`.
+pub fn is_string(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "String" && s.arguments.is_empty()),
+        _ => false,
+    }
+}
+
+/// Converts an upper camel case identifier like a variant name to snake case.
+pub fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // Start a new word at a lowercase-to-uppercase boundary, or at the last capital of an
+            // acronym (e.g. `HTTPServer` becomes `http_server`)
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let ends_acronym = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if after_lower || ends_acronym {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
 pub fn strip_raw(ident: &Ident) -> String {
     let as_string = ident.to_string();
     as_string
@@ -16,3 +106,85 @@ pub fn strip_raw(ident: &Ident) -> String {
         .map(ToString::to_string)
         .unwrap_or(as_string)
 }
+
+/// Returns a name for a type built from the text of each of its tokens.
+///
+/// Unlike formatting the tokens of the type, this doesn't depend on how the tokens are spaced, so
+/// the name stays the same across formatting changes and `proc-macro2` versions.
+pub fn type_name(ty: &Type) -> String {
+    fn push_tokens(name: &mut String, tokens: TokenStream) {
+        // Only adjacent words need to be separated for the name to be unambiguous
+        let mut after_word = false;
+        for token in tokens {
+            let is_word = matches!(token, TokenTree::Ident(_) | TokenTree::Literal(_));
+            if is_word && after_word {
+                name.push(' ');
+            }
+            after_word = is_word;
+            match token {
+                TokenTree::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::None => ("", ""),
+                    };
+                    name.push_str(open);
+                    push_tokens(name, group.stream());
+                    name.push_str(close);
+                }
+                TokenTree::Ident(ident) => name.push_str(&strip_raw(&ident)),
+                TokenTree::Punct(punct) => name.push(punct.as_char()),
+                TokenTree::Literal(literal) => name.push_str(&literal.to_string()),
+            }
+        }
+    }
+
+    let mut name = String::new();
+    push_tokens(&mut name, ty.to_token_stream());
+    name
+}
+
+/// Collects errors so that every problem with a derive input can be reported at once.
+#[derive(Default)]
+pub struct Errors(Option<Error>);
+
+impl Errors {
+    pub fn push(&mut self, error: Error) {
+        match self.0 {
+            Some(ref mut errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    pub fn check<T>(&mut self, result: Result<T, Error>) -> Option<T> {
+        result.map_err(|e| self.push(e)).ok()
+    }
+
+    pub fn finish(self) -> Result<(), Error> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// Returns the path to the `rkyv` crate, which can be changed with `#[archive(crate = "...")]`.
+pub fn rkyv_path(attributes: &Attributes) -> Path {
+    attributes
+        .rkyv_path
+        .clone()
+        .unwrap_or_else(|| parse_quote! { ::rkyv })
+}
+
+/// Prepends a type parameter to the generics of the input for a trait impl.
+pub fn impl_generics_with(input: &DeriveInput, param: GenericParam) -> Generics {
+    let mut params = Punctuated::default();
+    params.push(param);
+    for param in input.generics.params.iter() {
+        params.push(param.clone());
+    }
+    Generics {
+        lt_token: Some(Default::default()),
+        params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    }
+}
@@ -5,7 +5,7 @@ use bytecheck::CheckBytes;
 #[cfg(feature = "vtable_cache")]
 use core::sync::atomic::Ordering;
 use core::{
-    alloc::Layout,
+    alloc::{Layout, LayoutError},
     any::{Any, TypeId},
     convert::Infallible,
     fmt,
@@ -14,7 +14,7 @@ use core::{
 };
 use rkyv::{
     from_archived,
-    validation::{ArchiveContext, SharedContext},
+    validation::{validators::ValidationLimits, ArchiveContext, PathSegment, SharedContext},
     Archived, Fallible,
 };
 use rkyv_typename::TypeName;
@@ -113,6 +113,49 @@ pub trait DynContext {
     /// [`pop_suffix_range`]: rkyv::validation::ArchiveContext::pop_suffix_range
     fn pop_suffix_range_dyn(&mut self, range: Box<dyn Any>) -> Result<(), Box<dyn Error>>;
 
+    /// Converts an error from computing the layout of a pointer into an error of this context.
+    ///
+    /// See [`wrap_layout_error`] for more information.
+    ///
+    /// [`wrap_layout_error`]: rkyv::validation::ArchiveContext::wrap_layout_error
+    fn wrap_layout_error_dyn(&mut self, error: LayoutError) -> Box<dyn Error>;
+
+    /// Checks that a collection with the given number of elements may be validated.
+    ///
+    /// See [`check_collection_len`] for more information.
+    ///
+    /// [`check_collection_len`]: rkyv::validation::ArchiveContext::check_collection_len
+    fn check_collection_len_dyn(&mut self, len: usize) -> Result<(), Box<dyn Error>>;
+
+    /// Checks that a string with the given length in bytes may be validated.
+    ///
+    /// See [`check_string_len`] for more information.
+    ///
+    /// [`check_string_len`]: rkyv::validation::ArchiveContext::check_string_len
+    fn check_string_len_dyn(&mut self, len: usize) -> Result<(), Box<dyn Error>>;
+
+    /// Returns the limits that archives nested inside of the one being checked must be checked
+    /// with.
+    ///
+    /// See [`nested_limits`] for more information.
+    ///
+    /// [`nested_limits`]: rkyv::validation::ArchiveContext::nested_limits
+    fn nested_limits_dyn(&self) -> ValidationLimits;
+
+    /// Records a segment of the path to a value that failed to check.
+    ///
+    /// See [`record_error_segment`] for more information.
+    ///
+    /// [`record_error_segment`]: rkyv::validation::ArchiveContext::record_error_segment
+    fn record_error_segment_dyn(&mut self, segment: PathSegment, pos: Option<*const u8>);
+
+    /// Records a description of what was wrong with a value that failed to check.
+    ///
+    /// See [`record_error_message`] for more information.
+    ///
+    /// [`record_error_message`]: rkyv::validation::ArchiveContext::record_error_message
+    fn record_error_message_dyn(&mut self, message: &dyn fmt::Display);
+
     /// Verifies that all outstanding claims have been returned.
     ///
     /// See [`finish`] for more information.
@@ -194,6 +237,32 @@ where
             .map_err(|e| Box::new(e) as Box<dyn Error>)
     }
 
+    fn wrap_layout_error_dyn(&mut self, error: LayoutError) -> Box<dyn Error> {
+        Box::new(self.wrap_layout_error(error))
+    }
+
+    fn check_collection_len_dyn(&mut self, len: usize) -> Result<(), Box<dyn Error>> {
+        self.check_collection_len(len)
+            .map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn check_string_len_dyn(&mut self, len: usize) -> Result<(), Box<dyn Error>> {
+        self.check_string_len(len)
+            .map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn nested_limits_dyn(&self) -> ValidationLimits {
+        self.nested_limits()
+    }
+
+    fn record_error_segment_dyn(&mut self, segment: PathSegment, pos: Option<*const u8>) {
+        self.record_error_segment(segment, pos);
+    }
+
+    fn record_error_message_dyn(&mut self, message: &dyn fmt::Display) {
+        self.record_error_message(message);
+    }
+
     fn finish_dyn(&mut self) -> Result<(), Box<dyn Error>> {
         self.finish().map_err(|e| Box::new(e) as Box<dyn Error>)
     }
@@ -264,6 +333,30 @@ impl ArchiveContext for (dyn DynContext + '_) {
         self.pop_suffix_range_dyn(range)
     }
 
+    fn wrap_layout_error(&mut self, error: LayoutError) -> Self::Error {
+        self.wrap_layout_error_dyn(error)
+    }
+
+    fn check_collection_len(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.check_collection_len_dyn(len)
+    }
+
+    fn check_string_len(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.check_string_len_dyn(len)
+    }
+
+    fn nested_limits(&self) -> ValidationLimits {
+        self.nested_limits_dyn()
+    }
+
+    fn record_error_segment(&mut self, segment: PathSegment, pos: Option<*const u8>) {
+        self.record_error_segment_dyn(segment, pos);
+    }
+
+    fn record_error_message(&mut self, message: &dyn fmt::Display) {
+        self.record_error_message_dyn(message);
+    }
+
     fn finish(&mut self) -> Result<(), Self::Error> {
         self.finish_dyn()
     }
@@ -301,9 +301,11 @@ pub fn archive_dyn(
                 use rkyv_dyn::validation::{CHECK_BYTES_REGISTRY, CheckDynError, DynContext};
 
                 impl<#generic_params> LayoutRaw for (dyn #deserialize_trait<#generic_args> + '_) {
-                    fn layout_raw(value: *const Self) -> Layout {
+                    fn layout_raw(
+                        value: *const Self,
+                    ) -> Result<Layout, ::core::alloc::LayoutError> {
                         let metadata = ptr_meta::metadata(value);
-                        metadata.layout()
+                        Ok(metadata.layout())
                     }
                 }
 
@@ -235,6 +235,72 @@ mod tests {
         assert_eq!(value.get_id(), deserialized_value.get_id());
     }
 
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn archive_shared_dyn() {
+        use rkyv::de::deserializers::SharedDeserializeMap;
+        use std::{rc::Rc, sync::Arc};
+
+        #[archive_dyn(serialize = "SSharedTrait", deserialize = "DSharedTrait")]
+        pub trait SharedTrait {
+            fn get_id(&self) -> i32;
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive_attr(derive(TypeName))]
+        pub struct SharedTest {
+            id: i32,
+        }
+
+        #[archive_dyn(serialize = "SSharedTrait", deserialize = "DSharedTrait")]
+        impl SharedTrait for SharedTest {
+            fn get_id(&self) -> i32 {
+                self.id
+            }
+        }
+
+        impl SharedTrait for Archived<SharedTest> {
+            // Archived integers are only a different type with the archive_le and archive_be features
+            #[allow(clippy::useless_conversion)]
+            fn get_id(&self) -> i32 {
+                self.id.into()
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        pub struct Shared {
+            a: Rc<dyn SSharedTrait>,
+            b: Rc<dyn SSharedTrait>,
+            c: Arc<dyn SSharedTrait>,
+        }
+
+        let rc: Rc<dyn SSharedTrait> = Rc::new(SharedTest { id: 42 });
+        let value = Shared {
+            a: rc.clone(),
+            b: rc,
+            c: Arc::new(SharedTest { id: 7 }),
+        };
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<Shared>(buf.as_ref()) };
+        assert_eq!(archived_value.a.get_id(), 42);
+        assert_eq!(archived_value.c.get_id(), 7);
+        assert!(core::ptr::eq(
+            archived_value.a.get() as *const _ as *const u8,
+            archived_value.b.get() as *const _ as *const u8,
+        ));
+
+        let deserialized_value: Shared = archived_value
+            .deserialize(&mut SharedDeserializeMap::new())
+            .unwrap();
+        assert_eq!(deserialized_value.a.get_id(), 42);
+        assert_eq!(deserialized_value.c.get_id(), 7);
+        assert!(Rc::ptr_eq(&deserialized_value.a, &deserialized_value.b));
+        assert_eq!(Rc::strong_count(&deserialized_value.a), 2);
+    }
+
     #[test]
     #[cfg(not(feature = "wasm"))]
     fn archive_dyn_generic() {
@@ -40,6 +40,8 @@ mod tests {
         test_archive(&1234567890u32);
         test_archive(&12345678901234567890u64);
         test_archive(&123456789012345678901234567890123456789u128);
+        test_archive(&'a');
+        test_archive(&'\u{1f980}');
         #[cfg(not(any(feature = "strict", feature = "archive_le", feature = "archive_be")))]
         test_archive(&(24, true, 16f32));
         test_archive(&[1, 2, 3, 4, 5, 6]);
@@ -50,6 +52,28 @@ mod tests {
         test_archive(&Some(42));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_int128_layout() {
+        use core::{
+            mem::{align_of, size_of},
+            num::{NonZeroI128, NonZeroU128},
+        };
+        use rkyv::Archived;
+
+        assert_eq!(size_of::<Archived<i128>>(), 16);
+        assert_eq!(align_of::<Archived<i128>>(), 16);
+        assert_eq!(size_of::<Archived<u128>>(), 16);
+        assert_eq!(align_of::<Archived<u128>>(), 16);
+        assert_eq!(size_of::<Archived<NonZeroI128>>(), 16);
+        assert_eq!(align_of::<Archived<NonZeroI128>>(), 16);
+        assert_eq!(size_of::<Archived<NonZeroU128>>(), 16);
+        assert_eq!(align_of::<Archived<NonZeroU128>>(), 16);
+
+        test_archive(&[i128::MIN, -1, 0, 1, i128::MAX]);
+        test_archive(&[u128::MIN, 1, u128::MAX]);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_refs() {
@@ -100,6 +124,151 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_ordering() {
+        use core::cmp::Ordering;
+
+        test_archive(&Ordering::Less);
+        test_archive(&Ordering::Equal);
+        test_archive(&Ordering::Greater);
+        test_archive(&[Ordering::Greater, Ordering::Less]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_transparent_wrappers() {
+        use core::{
+            cmp::Reverse,
+            num::{Saturating, Wrapping},
+        };
+        use rkyv::{archived_root, ser::Serializer, Archive, Deserialize, Infallible, Serialize};
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        struct Test {
+            a: Wrapping<u32>,
+            b: Saturating<i16>,
+            c: Reverse<u64>,
+        }
+
+        let value = Test {
+            a: Wrapping(u32::MAX) + Wrapping(2),
+            b: Saturating(i16::MIN) - Saturating(1),
+            c: Reverse(42),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let len = serializer.pos();
+        let buffer = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(&buffer[0..len]) };
+
+        // The wrappers archive as their inner values
+        assert_eq!(archived.a, 1);
+        assert_eq!(archived.b, i16::MIN);
+        assert_eq!(archived.c, 42);
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_cells() {
+        use core::cell::{Cell, RefCell};
+        use rkyv::{archived_root, ser::Serializer, Archive, Deserialize, Infallible, Serialize};
+
+        #[derive(Archive, Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+        struct Pair {
+            x: i16,
+            flag: bool,
+        }
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        struct Test {
+            a: Cell<u32>,
+            b: Cell<Pair>,
+            c: RefCell<[u8; 4]>,
+        }
+
+        let value = Test {
+            a: Cell::new(42),
+            b: Cell::new(Pair { x: -1, flag: true }),
+            c: RefCell::new([1, 2, 3, 4]),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let len = serializer.pos();
+        let buffer = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(&buffer[0..len]) };
+
+        // Cells archive as their inner values
+        assert_eq!(archived.a, 42);
+        assert_eq!(archived.b.x, -1);
+        assert!(archived.b.flag);
+        assert_eq!(archived.c, [1, 2, 3, 4]);
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_read_only() {
+        use core::pin::Pin;
+        use rkyv::{
+            archived_root_mut,
+            ser::Serializer,
+            with::{Immutable, ReadOnly},
+            Archive, Archived, Deserialize, Infallible, Serialize,
+        };
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        struct Test {
+            counter: u32,
+            #[with(ReadOnly)]
+            id: u64,
+            #[with(ReadOnly)]
+            key: [u8; 4],
+        }
+
+        impl ArchivedTest {
+            fn counter(self: Pin<&mut Self>) -> Pin<&mut Archived<u32>> {
+                unsafe { self.map_unchecked_mut(|s| &mut s.counter) }
+            }
+
+            fn id(self: Pin<&mut Self>) -> Pin<&mut Immutable<Archived<u64>>> {
+                unsafe { self.map_unchecked_mut(|s| &mut s.id) }
+            }
+        }
+
+        let value = Test {
+            counter: 1,
+            id: 42,
+            key: [1, 2, 3, 4],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let len = serializer.pos();
+        let mut buffer = serializer.into_serializer().into_inner();
+
+        let mut archived = unsafe { archived_root_mut::<Test>(Pin::new(&mut buffer[0..len])) };
+        *archived.as_mut().counter() += 1;
+        assert_eq!(**archived.as_mut().id(), 42);
+        assert_eq!(*archived.key, [1, 2, 3, 4]);
+
+        let deserialized: Test = (*archived).deserialize(&mut Infallible).unwrap();
+        assert_eq!(
+            deserialized,
+            Test {
+                counter: 2,
+                ..value
+            }
+        );
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_zst() {
@@ -116,6 +285,48 @@ mod tests {
         test_archive_ref::<[MyZST]>(&[MyZST, MyZST, MyZST, MyZST]);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_phantom_data() {
+        use core::marker::PhantomData;
+        use rkyv::{Archive, Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq)]
+        struct NotArchive;
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Marked<T> {
+            value: i32,
+            marker: PhantomData<T>,
+        }
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct MarkedTuple<T>(i32, core::marker::PhantomData<T>);
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        enum MarkedEnum<T> {
+            A(PhantomData<T>),
+            B { value: i32, marker: PhantomData<T> },
+        }
+
+        test_archive(&Marked::<NotArchive> {
+            value: 42,
+            marker: PhantomData,
+        });
+        test_archive(&MarkedTuple::<NotArchive>(42, PhantomData));
+        test_archive(&MarkedEnum::<NotArchive>::A(PhantomData));
+        test_archive(&MarkedEnum::<NotArchive>::B {
+            value: 42,
+            marker: PhantomData,
+        });
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     #[allow(non_camel_case_types)]
@@ -139,4 +350,469 @@ mod tests {
         test_archive::<r#virtual>(&r#virtual { r#virtual: 42 });
         test_archive::<r#try>(&r#try::r#try { r#try: 42 });
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_layout() {
+        use core::mem::{align_of, size_of};
+        use rkyv::{
+            archived_root,
+            layout::{ArchiveLayout, FieldLayout},
+            ser::{serializers::BufferSerializer, Serializer},
+            with::AsBox,
+            AlignedBytes, Archive, Archived, Serialize,
+        };
+
+        #[derive(Archive)]
+        #[archive(layout)]
+        #[archive_attr(repr(C))]
+        struct Entry<T> {
+            key: u8,
+            #[archive(rename = "payload")]
+            value: T,
+            #[with(AsBox)]
+            boxed: u32,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(layout, archived = "ArchivedPair")]
+        struct Pair(u16, [u8; 3]);
+
+        let layout = <Entry<u64> as ArchiveLayout>::LAYOUT;
+        assert_eq!(layout.name, "ArchivedEntry");
+        assert_eq!(layout.size, size_of::<Archived<Entry<u64>>>());
+        assert_eq!(layout.align, align_of::<Archived<Entry<u64>>>());
+        assert_eq!(layout.fields.len(), 3);
+        assert_eq!(
+            layout.fields[1],
+            FieldLayout {
+                name: "payload",
+                ty: "T",
+                offset: 8,
+                size: 8,
+                align: 8,
+            }
+        );
+        let boxed = layout.field("boxed").unwrap();
+        assert_eq!(boxed.offset, 16);
+        assert_eq!(
+            boxed.size,
+            size_of::<rkyv::boxed::ArchivedBox<Archived<u32>>>()
+        );
+        assert!(layout.field("value").is_none());
+        assert_eq!(layout.padding(), layout.size - 9 - boxed.size);
+
+        let layout = Pair::LAYOUT;
+        assert_eq!(layout.name, "ArchivedPair");
+        assert_eq!(layout.fields[0].name, "0");
+        assert_eq!(layout.fields[1].name, "1");
+        assert_eq!(layout.fields[1].ty, "[u8; 3]");
+        assert_eq!(layout.fields[1].size, 3);
+        assert_eq!(layout.fields[1].range(), 2..5);
+        assert_eq!(layout.field_range(16, "1"), Some(18..21));
+        assert!(layout.field_range(16, "2").is_none());
+
+        let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 32]));
+        let pos = serializer.serialize_value(&Pair(7, [1, 2, 3])).unwrap();
+        let end = serializer.pos();
+        let buf = serializer.into_inner();
+        let archived = unsafe { archived_root::<Pair>(&buf[..end]) };
+        assert_eq!(Pair::field_bytes(archived, "1"), Some(&[1u8, 2, 3][..]));
+        assert_eq!(Pair::field_bytes(archived, "0"), Some(&buf[pos..pos + 2]));
+        assert!(Pair::field_bytes(archived, "2").is_none());
+        let range = layout.field_range(pos, "1").unwrap();
+        assert_eq!(&buf[range], [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_schema_primitives() {
+        use rkyv::{
+            schema::{ArchiveSchema, ArchivedValue, Schema},
+            ser::{serializers::BufferSerializer, Serializer},
+            AlignedBytes, Archive, Serialize,
+        };
+
+        #[allow(dead_code)]
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        #[repr(u8)]
+        enum Mode {
+            Idle = 4,
+            Run(u8) = 9,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        struct Sample(char, [i32; 2], u128, Mode, Option<u8>);
+
+        let value = Sample('x', [-1, 2], u128::MAX, Mode::Run(3), None);
+
+        let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 128]));
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let buf = serializer.into_inner();
+        let root = unsafe { ArchivedValue::root::<Sample>(&buf[..end]) };
+
+        assert_eq!(
+            root.schema().size(),
+            <Sample as ArchiveSchema>::SCHEMA.size()
+        );
+        assert_eq!(root.field("0").unwrap().as_char(), Some('x'));
+        let array = root.field("1").unwrap();
+        assert_eq!(array.len(), Some(2));
+        assert_eq!(array.get(0).unwrap().as_i64(), Some(-1));
+        let big = root.field("2").unwrap();
+        assert_eq!(big.as_u64(), None);
+        assert!(matches!(big.schema(), Schema::U128));
+
+        let mode = root.field("3").unwrap();
+        let variant = mode.variant().unwrap();
+        assert_eq!(variant.name, "Run");
+        assert_eq!(variant.tag, 9);
+        assert_eq!(mode.field("0").unwrap().as_u64(), Some(3));
+        match Mode::SCHEMA {
+            Schema::Enum { tag, variants, .. } => {
+                assert_eq!(*tag, Schema::U8);
+                assert_eq!(variants[0].tag, 4);
+            }
+            _ => panic!("expected an enum schema"),
+        }
+
+        assert!(root.field("4").unwrap().inner().is_none());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_fixed_size() {
+        use core::mem::size_of;
+        use rkyv::{
+            archived_root,
+            fixed_size::FixedSize,
+            from_archived,
+            ser::{serializers::BufferSerializer, Serializer},
+            AlignedBytes, Archive, Archived, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(fixed_size)]
+        struct Point<T> {
+            x: T,
+            y: T,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(fixed_size)]
+        enum Shape {
+            Dot(Point<i16>),
+            Line { from: Point<i16>, to: Point<i16> },
+            Empty,
+        }
+
+        assert_eq!(<u32 as FixedSize>::SIZE, 4);
+        assert_eq!(<[u16; 3] as FixedSize>::SIZE, 6);
+        assert_eq!(<Point<u64> as FixedSize>::SIZE, 16);
+        assert_eq!(<Point<u64> as FixedSize>::ALIGN, 8);
+        assert_eq!(
+            <Option<Shape> as FixedSize>::SIZE,
+            size_of::<Archived<Option<Shape>>>()
+        );
+
+        let shapes = [
+            Shape::Dot(Point { x: 5, y: 6 }),
+            Shape::Line {
+                from: Point { x: 1, y: 2 },
+                to: Point { x: -3, y: 4 },
+            },
+            Shape::Empty,
+        ];
+        for shape in shapes.iter() {
+            let mut serializer =
+                BufferSerializer::new(AlignedBytes::<{ <Shape as FixedSize>::SIZE }>::default());
+            serializer.serialize_value(shape).unwrap();
+            assert_eq!(serializer.pos(), Shape::SIZE);
+
+            let buf = serializer.into_inner();
+            let archived = unsafe { archived_root::<Shape>(buf.as_ref()) };
+            match (shape, archived) {
+                (Shape::Dot(p), ArchivedShape::Dot(a)) => {
+                    assert_eq!(from_archived!(a.x), p.x);
+                    assert_eq!(from_archived!(a.y), p.y);
+                }
+                (Shape::Line { from, to }, ArchivedShape::Line { from: af, to: at }) => {
+                    assert_eq!(from_archived!(af.x), from.x);
+                    assert_eq!(from_archived!(af.y), from.y);
+                    assert_eq!(from_archived!(at.x), to.x);
+                    assert_eq!(from_archived!(at.y), to.y);
+                }
+                (Shape::Empty, ArchivedShape::Empty) => (),
+                _ => panic!("archived the wrong variant"),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn deserialize_infallible() {
+        use rkyv::{
+            archived_root,
+            ser::{serializers::BufferSerializer, Serializer},
+            AlignedBytes, Archive, Deserialize, Serialize,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Test {
+            a: u32,
+            b: Option<[i16; 2]>,
+        }
+
+        let value = Test {
+            a: 42,
+            b: Some([-1, 1]),
+        };
+        let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 64]));
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let buf = serializer.into_inner();
+        let archived = unsafe { archived_root::<Test>(&buf[..end]) };
+
+        let deserialized: Test = rkyv::deserialize(archived);
+        assert_eq!(deserialized, value);
+        assert_eq!(rkyv::deserialize::<u32>(&archived.a), 42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_layout_assertions() {
+        use core::mem::{align_of, size_of};
+        use rkyv::{Archive, Archived};
+
+        #[derive(Archive)]
+        #[archive(assert_size = 8, assert_align = 4)]
+        struct Header {
+            magic: u32,
+            version: u16,
+            flags: [u8; 2],
+        }
+
+        #[allow(dead_code)]
+        #[derive(Archive)]
+        #[archive(assert_size = 16)]
+        enum Message {
+            Ping,
+            Data(Header, u32),
+        }
+
+        assert_eq!(size_of::<Archived<Header>>(), 8);
+        assert_eq!(align_of::<Archived<Header>>(), 4);
+        assert_eq!(size_of::<Archived<Message>>(), 16);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_unit_and_tuple_structs() {
+        use rkyv::{
+            archived_root,
+            fingerprint::Fingerprint,
+            fixed_size::FixedSize,
+            ser::{serializers::BufferSerializer, Serializer},
+            AlignedBytes, Archive, Archived, Deserialize, Infallible, Serialize,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq, PartialOrd)]
+        #[archive(compare(PartialEq, PartialOrd), fixed_size, fingerprint)]
+        #[archive_attr(derive(Debug))]
+        struct Marker;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq, PartialOrd)]
+        #[archive(
+            compare(PartialEq, PartialOrd),
+            fixed_size,
+            fingerprint,
+            assert_size = 4
+        )]
+        #[archive_attr(derive(Debug))]
+        struct Meters(f32);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq), fixed_size)]
+        struct Tagged<T>(T, Marker);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Route(Meters, Option<Meters>, Tagged<u16>);
+
+        let value = Route(Meters(1.5), Some(Meters(2.5)), Tagged(7, Marker));
+
+        let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 64]));
+        serializer.serialize_value(&value).unwrap();
+        let end = serializer.pos();
+        let buf = serializer.into_inner();
+        let archived = unsafe { archived_root::<Route>(&buf[..end]) };
+
+        assert!(*archived == value);
+        assert_eq!(archived.0, Meters(1.5));
+        assert!(archived.0 < Meters(2.0));
+        assert_eq!(
+            archived.1.as_ref().map(|m| rkyv::from_archived!(m.0)),
+            Some(2.5)
+        );
+        assert_eq!(archived.2 .0, 7);
+        assert_eq!(&archived.2 .1, &Marker);
+        assert_eq!(core::mem::size_of::<Archived<Marker>>(), 0);
+        assert_eq!(<Tagged<u32> as FixedSize>::SIZE, 4);
+        assert_ne!(Marker::FINGERPRINT, Meters::FINGERPRINT);
+
+        let deserialized: Route = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+        let marker: Marker = rkyv::deserialize(&archived.2 .1);
+        assert_eq!(marker, Marker);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_explicit_discriminants() {
+        use core::mem::size_of;
+        use rkyv::{
+            archived_root, archived_value,
+            ser::{serializers::BufferSerializer, Serializer},
+            AlignedBytes, Archive, Archived, Deserialize, Infallible, Serialize,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        #[repr(u8)]
+        enum Command {
+            Reset = 10,
+            Start { speed: u16 } = 1,
+            Stop = 3,
+            Pause,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[repr(i16)]
+        enum Level {
+            Low = -5,
+            Mid,
+            High = 100,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        enum Code {
+            Ok = 200,
+            NotFound = 404,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[non_exhaustive]
+        enum Event {
+            Open,
+            #[non_exhaustive]
+            Close {
+                code: u8,
+            },
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[non_exhaustive]
+        struct Options {
+            verbose: bool,
+        }
+
+        // Reads the tag of an archived enum as an archived `U`, which accounts for the archive's
+        // endianness
+        fn tag<T, U>(value: &T) -> U
+        where
+            T: Serialize<BufferSerializer<AlignedBytes<64>>>,
+            U: Archive,
+            Archived<U>: Deserialize<U, Infallible>,
+        {
+            let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 64]));
+            let pos = serializer.serialize_value(value).unwrap();
+            let buf = serializer.into_inner();
+            let tag = unsafe { archived_value::<U>(buf.as_ref(), pos) };
+            tag.deserialize(&mut Infallible).unwrap()
+        }
+
+        // Tags follow the discriminants, not the order of the variants
+        assert_eq!(tag::<_, u8>(&Command::Reset), 10);
+        assert_eq!(tag::<_, u8>(&Command::Start { speed: 5 }), 1);
+        assert_eq!(tag::<_, u8>(&Command::Stop), 3);
+        assert_eq!(tag::<_, u8>(&Command::Pause), 4);
+        assert_eq!(size_of::<Archived<Level>>(), 2);
+        assert_eq!(tag::<_, i16>(&Level::Low), -5);
+        assert_eq!(tag::<_, i16>(&Level::Mid), -4);
+        assert_eq!(tag::<_, i16>(&Level::High), 100);
+        assert_eq!(size_of::<Archived<Code>>(), 2);
+        assert_eq!(tag::<_, u16>(&Code::Ok), 200);
+        assert_eq!(tag::<_, u16>(&Code::NotFound), 404);
+
+        for value in [
+            Command::Reset,
+            Command::Start { speed: 5 },
+            Command::Stop,
+            Command::Pause,
+        ] {
+            let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 64]));
+            serializer.serialize_value(&value).unwrap();
+            let end = serializer.pos();
+            let buf = serializer.into_inner();
+            let archived = unsafe { archived_root::<Command>(&buf[..end]) };
+            assert_eq!(archived, &value);
+            let deserialized: Command = archived.deserialize(&mut Infallible).unwrap();
+            assert_eq!(deserialized, value);
+        }
+
+        let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 64]));
+        serializer
+            .serialize_value(&Event::Close { code: 7 })
+            .unwrap();
+        serializer.serialize_value(&Event::Open).unwrap();
+        let end = serializer.pos();
+        let buf = serializer.into_inner();
+        let archived = unsafe { archived_root::<Event>(&buf[..end]) };
+        assert!(matches!(archived, ArchivedEvent::Open));
+        let options: Options = rkyv::deserialize(&ArchivedOptions { verbose: true });
+        assert!(options.verbose);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn checked_rel_ptr() {
+        use core::mem::size_of;
+        use rkyv::{rel_ptr::TargetError, AlignedBytes, RawRelPtr};
+
+        let mut bytes = AlignedBytes([0u8; 16]);
+        unsafe {
+            RawRelPtr::emplace(8, 0, bytes.as_mut_ptr().add(8).cast());
+        }
+        let rel_ptr = unsafe { &*bytes.as_ptr().add(8).cast::<RawRelPtr>() };
+
+        assert_eq!(rel_ptr.checked_as_ptr(), Ok(rel_ptr.as_ptr()));
+        assert_eq!(
+            rel_ptr.checked_as_ptr_in(&bytes[..]),
+            Ok(bytes.as_ptr().cast())
+        );
+        assert_eq!(
+            rel_ptr.checked_as_ptr_in(&bytes[4..]),
+            Err(TargetError::OutOfBounds {
+                pos: 4,
+                offset: -8,
+                len: 12,
+            })
+        );
+        assert_eq!(
+            rel_ptr.checked_as_ptr_in(&bytes[..8 + size_of::<RawRelPtr>() - 1]),
+            Err(TargetError::BaseOutOfBounds {
+                pos: 8,
+                len: 8 + size_of::<RawRelPtr>() - 1,
+            })
+        );
+        assert!(matches!(
+            rel_ptr.checked_as_ptr_in(&bytes[12..]),
+            Err(TargetError::BaseOutOfBounds { .. })
+        ));
+    }
 }
@@ -56,6 +56,48 @@ mod tests {
         test_archive(&Some(Box::new(vec![1, 2, 3, 4])));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_option_combinators() {
+        #[derive(Archive, Serialize)]
+        struct Test {
+            a: Option<String>,
+            b: Option<Box<i32>>,
+            c: Option<u8>,
+            d: Option<u8>,
+        }
+
+        let value = Test {
+            a: Some("hello world".to_string()),
+            b: None,
+            c: Some(42),
+            d: None,
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+        assert!(archived.a.is_some());
+        assert_eq!(archived.a.as_deref(), Some("hello world"));
+        assert_eq!(archived.a.map(|s| s.len()), Some(11));
+        assert!(archived.a.is_some_and(|s| s.starts_with("hello")));
+        assert_eq!(archived.a.unwrap(), "hello world");
+        assert!(archived.b.is_none());
+        assert_eq!(archived.b.as_deref(), None);
+        assert_eq!(archived.b.map(|b| **b), None);
+
+        assert_eq!(*archived.c.unwrap_or(&0), 42);
+        assert_eq!(*archived.d.unwrap_or(&0), 0);
+        assert_eq!(*archived.d.unwrap_or_else(|| &7), 7);
+        assert_eq!(archived.c.copied(), Some(42));
+        assert_eq!(archived.d.cloned(), None);
+        assert_eq!(archived.c.and_then(|c| c.checked_add(1)), Some(43));
+        assert_eq!(archived.c.iter().count(), 1);
+        assert_eq!((&archived.d).into_iter().count(), 0);
+    }
+
     #[test]
     fn option_is_copy() {
         #[derive(Clone, Copy, Debug, PartialEq, Archive, Serialize, Deserialize)]
@@ -575,6 +617,64 @@ mod tests {
         assert_eq!(value.b[1], "WORLD");
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn collection_mutable_refs() {
+        use std::collections::HashMap;
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            a: Vec<String>,
+            b: HashMap<String, Vec<u32>>,
+        }
+
+        impl ArchivedTest {
+            fn a(self: Pin<&mut Self>) -> Pin<&mut Archived<Vec<String>>> {
+                unsafe { self.map_unchecked_mut(|s| &mut s.a) }
+            }
+
+            fn b(self: Pin<&mut Self>) -> Pin<&mut Archived<HashMap<String, Vec<u32>>>> {
+                unsafe { self.map_unchecked_mut(|s| &mut s.b) }
+            }
+        }
+
+        let mut b = HashMap::new();
+        b.insert("foo".to_string(), vec![1, 2, 3]);
+        b.insert("bar".to_string(), vec![4, 5]);
+        let value = Test {
+            a: vec!["hello".to_string(), "world".to_string()],
+            b,
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let mut value = unsafe { archived_root_mut::<Test>(Pin::new(buf.as_mut())) };
+
+        value
+            .as_mut()
+            .a()
+            .get_pin(1)
+            .unwrap()
+            .pin_mut_str()
+            .make_ascii_uppercase();
+        assert!(value.as_mut().a().get_pin(2).is_none());
+        assert_eq!(value.a[0], "hello");
+        assert_eq!(value.a[1], "WORLD");
+
+        for s in value.as_mut().a().iter_pin() {
+            s.pin_mut_str().make_ascii_uppercase();
+        }
+        assert_eq!(value.a[0], "HELLO");
+
+        for mut x in value.as_mut().b().get_pin("foo").unwrap().iter_pin() {
+            *x = rkyv::to_archived!(rkyv::from_archived!(*x) * 10);
+        }
+        assert_eq!(value.b.get("foo").unwrap().as_slice(), [10, 20, 30]);
+        assert_eq!(value.b.get("bar").unwrap().as_slice(), [4, 5]);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn enum_mutable_ref() {
@@ -875,6 +975,68 @@ mod tests {
         test_archive(&value);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_unsized_shared_pointees() {
+        use rkyv::de::deserializers::SharedDeserializeMap;
+
+        #[cfg(not(feature = "std"))]
+        use alloc::sync::Arc;
+        #[cfg(feature = "std")]
+        use std::sync::Arc;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            a: Arc<str>,
+            b: Arc<str>,
+            c: Rc<[u8]>,
+            d: Rc<[u8]>,
+            e: Arc<[String]>,
+            f: Arc<[String]>,
+            empty: Arc<str>,
+        }
+
+        let arc_str = Arc::<str>::from("hello world");
+        let rc_bytes = Rc::<[u8]>::from(&[1, 2, 3, 4][..]);
+        let arc_slice = Arc::<[String]>::from(vec!["hello".to_string(), "world".to_string()]);
+        let value = Test {
+            a: arc_str.clone(),
+            b: arc_str,
+            c: rc_bytes.clone(),
+            d: rc_bytes,
+            e: arc_slice.clone(),
+            f: arc_slice,
+            empty: Arc::from(""),
+        };
+
+        test_archive(&value);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        // Shared pointees are only serialized once
+        assert!(core::ptr::eq(archived.a.get(), archived.b.get()));
+        assert!(core::ptr::eq(archived.c.get(), archived.d.get()));
+        assert!(core::ptr::eq(archived.e.get(), archived.f.get()));
+
+        let mut deserializer = SharedDeserializeMap::new();
+        let deserialized: Test = archived.deserialize(&mut deserializer).unwrap();
+        core::mem::drop(deserializer);
+
+        assert_eq!(deserialized, value);
+        assert!(Arc::ptr_eq(&deserialized.a, &deserialized.b));
+        assert!(Rc::ptr_eq(&deserialized.c, &deserialized.d));
+        assert!(Arc::ptr_eq(&deserialized.e, &deserialized.f));
+        assert_eq!(Arc::strong_count(&deserialized.a), 2);
+        assert_eq!(Rc::strong_count(&deserialized.c), 2);
+        assert_eq!(Arc::strong_count(&deserialized.e), 2);
+        assert_eq!(Arc::strong_count(&deserialized.empty), 1);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_weak_ptr() {
@@ -968,746 +1130,3309 @@ mod tests {
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn derive_attributes() {
-        #[derive(Archive, Debug, PartialEq)]
-        #[archive(archived = "ATest", resolver = "RTest", compare(PartialEq))]
-        #[archive_attr(derive(Debug))]
-        struct Test {
-            a: i32,
-            b: Option<u32>,
-            c: String,
-            d: Vec<i32>,
-        }
-
-        impl<S: Fallible + ?Sized> Serialize<S> for Test
-        where
-            i32: Serialize<S>,
-            Option<u32>: Serialize<S>,
-            String: Serialize<S>,
-            Vec<i32>: Serialize<S>,
-        {
-            fn serialize(&self, serializer: &mut S) -> Result<RTest, S::Error> {
-                Ok(RTest {
-                    a: self.a.serialize(serializer)?,
-                    b: self.b.serialize(serializer)?,
-                    c: self.c.serialize(serializer)?,
-                    d: self.d.serialize(serializer)?,
-                })
-            }
-        }
+    fn archive_weak_ptr_order() {
+        use rkyv::de::deserializers::SharedDeserializeMap;
 
-        impl<D: Fallible + ?Sized> Deserialize<Test, D> for ATest
-        where
-            Archived<i32>: Deserialize<i32, D>,
-            Archived<Option<u32>>: Deserialize<Option<u32>, D>,
-            Archived<String>: Deserialize<String, D>,
-            Archived<Vec<i32>>: Deserialize<Vec<i32>, D>,
-        {
-            fn deserialize(&self, deserializer: &mut D) -> Result<Test, D::Error> {
-                Ok(Test {
-                    a: self.a.deserialize(deserializer)?,
-                    b: self.b.deserialize(deserializer)?,
-                    c: self.c.deserialize(deserializer)?,
-                    d: self.d.deserialize(deserializer)?,
-                })
-            }
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            before: Weak<u32>,
+            strong: Rc<u32>,
+            after: Weak<u32>,
+            dangling: Weak<u32>,
+            weak_only: Weak<u32>,
         }
 
+        let shared = Rc::new(10);
+        let weak_only = Rc::new(20);
         let value = Test {
-            a: 42,
-            b: Some(12),
-            c: "hello world".to_string(),
-            d: vec![1, 2, 3, 4],
+            before: Rc::downgrade(&shared),
+            strong: shared.clone(),
+            after: Rc::downgrade(&shared),
+            dangling: Rc::downgrade(&Rc::new(30)),
+            weak_only: Rc::downgrade(&weak_only),
         };
 
-        test_archive(&value);
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        // Weak pointers point to the same value as strong pointers whichever is serialized first
+        assert!(core::ptr::eq(
+            archived.before.upgrade().unwrap().get(),
+            archived.strong.get()
+        ));
+        assert!(core::ptr::eq(
+            archived.after.upgrade().unwrap().get(),
+            archived.strong.get()
+        ));
+        assert!(archived.dangling.upgrade().is_none());
+        assert_eq!(**archived.weak_only.upgrade().unwrap(), 20);
+
+        let mut deserializer = SharedDeserializeMap::new();
+        let deserialized: Test = archived.deserialize(&mut deserializer).unwrap();
+        core::mem::drop(deserializer);
+
+        assert!(Rc::ptr_eq(
+            &deserialized.before.upgrade().unwrap(),
+            &deserialized.strong
+        ));
+        assert!(Rc::ptr_eq(
+            &deserialized.after.upgrade().unwrap(),
+            &deserialized.strong
+        ));
+        assert_eq!(Rc::strong_count(&deserialized.strong), 1);
+        assert_eq!(Rc::weak_count(&deserialized.strong), 2);
+        assert!(deserialized.dangling.upgrade().is_none());
+        // Nothing holds the target of the weak pointer once the deserializer is dropped
+        assert!(deserialized.weak_only.upgrade().is_none());
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn compare() {
-        #[derive(Archive, Serialize, Deserialize)]
-        #[archive(compare(PartialEq, PartialOrd))]
-        pub struct UnitFoo;
+    fn archive_weak_ptr_cycle() {
+        use rkyv::ser::{ScratchSpace, SharedSerializeRegistry};
 
-        #[derive(Archive, Serialize, Deserialize)]
-        #[archive(compare(PartialEq, PartialOrd))]
-        pub struct TupleFoo(i32);
-
-        #[derive(Archive, Serialize, Deserialize)]
-        #[archive(compare(PartialEq, PartialOrd))]
-        pub struct StructFoo {
-            t: i32,
+        #[derive(Archive, Serialize)]
+        struct Parent {
+            value: u32,
+            children: Vec<Rc<Child>>,
         }
 
-        #[derive(Archive, Serialize, Deserialize)]
-        #[archive(compare(PartialEq, PartialOrd))]
-        pub enum EnumFoo {
-            #[allow(dead_code)]
-            Foo(i32),
+        #[derive(Archive, Serialize)]
+        #[archive(bound(serialize = "__S: Serializer + ScratchSpace + SharedSerializeRegistry"))]
+        struct Child {
+            value: u32,
+            #[omit_bounds]
+            parent: Weak<Parent>,
         }
+
+        let parent = Rc::new_cyclic(|weak| Parent {
+            value: 1,
+            children: vec![Rc::new(Child {
+                value: 2,
+                parent: weak.clone(),
+            })],
+        });
+
+        // A weak pointer back to a value that is still being serialized is archived as `None`
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&parent).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Rc<Parent>>(buf.as_ref()) };
+        assert_eq!(archived.value, 1);
+        assert_eq!(archived.children[0].value, 2);
+        assert!(archived.children[0].parent.upgrade().is_none());
+
+        // Serializing the child first reaches the child again through its parent
+        let mut serializer = DefaultSerializer::default();
+        assert!(serializer
+            .serialize_value(&parent.children[0].clone())
+            .is_err());
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn default_type_parameters() {
-        #[derive(Archive, Serialize, Deserialize)]
-        pub struct TupleFoo<T = i32>(T);
+    fn vec_serialize_without_buffering() {
+        use rkyv::vec::{ArchivedVec, VecResolver};
 
-        #[derive(Archive, Serialize, Deserialize)]
-        pub struct StructFoo<T = i32> {
-            t: T,
+        // Archives as a `Vec<f32>` of `len` elements without ever building one
+        struct Ramp {
+            len: usize,
         }
 
-        #[derive(Archive, Serialize, Deserialize)]
-        pub enum EnumFoo<T = i32> {
-            #[allow(dead_code)]
-            T(T),
+        impl Archive for Ramp {
+            type Archived = ArchivedVec<Archived<f32>>;
+            type Resolver = VecResolver;
+
+            unsafe fn resolve(&self, pos: usize, resolver: VecResolver, out: *mut Self::Archived) {
+                ArchivedVec::resolve_from_len(self.len, pos, resolver, out);
+            }
         }
-    }
 
-    #[test]
-    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn check_util_bounds() {
-        fn check<T: Serializer>() {}
+        impl<S: Serializer + ?Sized> Serialize<S> for Ramp {
+            fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+                ArchivedVec::serialize_from_fn(self.len, |i| i as f32 * 0.5, serializer)
+            }
+        }
 
-        check::<BufferSerializer<[u8; 256]>>();
-        check::<BufferSerializer<&mut [u8; 256]>>();
-        check::<BufferSerializer<&mut [u8]>>();
-        check::<BufferSerializer<AlignedBytes<256>>>();
-        check::<BufferSerializer<&mut AlignedBytes<256>>>();
-        check::<AlignedSerializer<AlignedVec>>();
-        check::<AlignedSerializer<&mut AlignedVec>>();
-    }
+        // Archives as a `Vec<u8>` of `len` elements copied from a repeating pattern
+        struct Pattern {
+            len: usize,
+        }
 
-    #[test]
-    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn buffer_serializer_zeroes_padding() {
-        use ::core::mem::size_of;
+        impl Archive for Pattern {
+            type Archived = ArchivedVec<u8>;
+            type Resolver = VecResolver;
+
+            unsafe fn resolve(&self, pos: usize, resolver: VecResolver, out: *mut Self::Archived) {
+                ArchivedVec::resolve_from_len(self.len, pos, resolver, out);
+            }
+        }
+
+        impl<S: Serializer + ?Sized> Serialize<S> for Pattern {
+            fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+                let iter = (0..=6u8).cycle();
+                unsafe { ArchivedVec::serialize_copy_from_iter(self.len, iter, serializer) }
+            }
+        }
+
+        // Archives as a `Vec` of `len` blocks that are each too large to buffer together
+        struct Blocks {
+            len: usize,
+        }
+
+        impl Archive for Blocks {
+            type Archived = ArchivedVec<[u8; 5000]>;
+            type Resolver = VecResolver;
+
+            unsafe fn resolve(&self, pos: usize, resolver: VecResolver, out: *mut Self::Archived) {
+                ArchivedVec::resolve_from_len(self.len, pos, resolver, out);
+            }
+        }
+
+        impl<S: Serializer + ?Sized> Serialize<S> for Blocks {
+            fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+                let iter = (0..self.len).map(|i| [i as u8; 5000]);
+                unsafe { ArchivedVec::serialize_copy_from_iter(self.len, iter, serializer) }
+            }
+        }
 
         #[derive(Archive, Serialize)]
-        pub struct PaddedExample {
-            a: u8,
-            b: u64,
+        struct Test {
+            ramp: Ramp,
+            pattern: Pattern,
+            blocks: Blocks,
+            empty: Ramp,
         }
-        let mut serializer = BufferSerializer::<[u8; 256]>::new([0xccu8; 256]);
-        serializer
-            .serialize_value(&PaddedExample { a: 0u8, b: 0u64 })
-            .unwrap();
-        let bytes = serializer.into_inner();
-        assert!(bytes[0..size_of::<Archived<PaddedExample>>()]
-            .iter()
-            .all(|&b| b == 0));
-    }
 
-    #[test]
-    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn const_generics() {
-        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
-        #[archive(compare(PartialEq))]
-        #[archive_attr(derive(Debug))]
-        pub struct Const<const N: usize>;
+        let value = Test {
+            ramp: Ramp { len: 1000 },
+            pattern: Pattern { len: 10_000 },
+            blocks: Blocks { len: 3 },
+            empty: Ramp { len: 0 },
+        };
 
-        test_archive(&Const::<1>);
-        test_archive(&Const::<2>);
-        test_archive(&Const::<3>);
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
 
-        #[derive(Archive, Deserialize, Serialize)]
-        pub struct Array<T, const N: usize>([T; N]);
+        assert_eq!(archived.ramp.len(), 1000);
+        for (i, x) in archived.ramp.iter().enumerate() {
+            assert_eq!(*x, i as f32 * 0.5);
+        }
+        assert_eq!(archived.pattern.len(), 10_000);
+        for (i, x) in archived.pattern.iter().enumerate() {
+            assert_eq!(*x as usize, i % 7);
+        }
+        assert_eq!(archived.blocks.len(), 3);
+        for (i, block) in archived.blocks.iter().enumerate() {
+            assert!(block.iter().all(|x| *x as usize == i));
+        }
+        assert!(archived.empty.is_empty());
     }
 
     #[test]
-    #[cfg(any(
-        not(any(feature = "archive_le", feature = "archive_be")),
-        feature = "arbitrary_enum_discriminant"
-    ))]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn arbitrary_enum_discriminant() {
-        use rkyv::Infallible;
+    fn vec_sorted_search() {
+        use rkyv::{from_archived, to_archived};
 
-        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
-        #[archive_attr(derive(Debug, PartialEq))]
-        #[rustfmt::skip]
-        enum ReallyBigEnum {
-            V00, V01, V02, V03, V04, V05, V06, V07, V08, V09, V0A, V0B, V0C, V0D, V0E, V0F,
-            V10, V11, V12, V13, V14, V15, V16, V17, V18, V19, V1A, V1B, V1C, V1D, V1E, V1F,
-            V20, V21, V22, V23, V24, V25, V26, V27, V28, V29, V2A, V2B, V2C, V2D, V2E, V2F,
-            V30, V31, V32, V33, V34, V35, V36, V37, V38, V39, V3A, V3B, V3C, V3D, V3E, V3F,
-            V40, V41, V42, V43, V44, V45, V46, V47, V48, V49, V4A, V4B, V4C, V4D, V4E, V4F,
-            V50, V51, V52, V53, V54, V55, V56, V57, V58, V59, V5A, V5B, V5C, V5D, V5E, V5F,
-            V60, V61, V62, V63, V64, V65, V66, V67, V68, V69, V6A, V6B, V6C, V6D, V6E, V6F,
-            V70, V71, V72, V73, V74, V75, V76, V77, V78, V79, V7A, V7B, V7C, V7D, V7E, V7F,
-            V80, V81, V82, V83, V84, V85, V86, V87, V88, V89, V8A, V8B, V8C, V8D, V8E, V8F,
-            V90, V91, V92, V93, V94, V95, V96, V97, V98, V99, V9A, V9B, V9C, V9D, V9E, V9F,
-            VA0, VA1, VA2, VA3, VA4, VA5, VA6, VA7, VA8, VA9, VAA, VAB, VAC, VAD, VAE, VAF,
-            VB0, VB1, VB2, VB3, VB4, VB5, VB6, VB7, VB8, VB9, VBA, VBB, VBC, VBD, VBE, VBF,
-            VC0, VC1, VC2, VC3, VC4, VC5, VC6, VC7, VC8, VC9, VCA, VCB, VCC, VCD, VCE, VCF,
-            VD0, VD1, VD2, VD3, VD4, VD5, VD6, VD7, VD8, VD9, VDA, VDB, VDC, VDD, VDE, VDF,
-            VE0, VE1, VE2, VE3, VE4, VE5, VE6, VE7, VE8, VE9, VEA, VEB, VEC, VED, VEE, VEF,
-            VF0, VF1, VF2, VF3, VF4, VF5, VF6, VF7, VF8, VF9, VFA, VFB, VFC, VFD, VFE, VFF,
-            V100,
+        #[derive(Archive, Serialize)]
+        struct Pair {
+            id: u32,
+            name: String,
         }
 
-        assert_eq!(ReallyBigEnum::V100 as u16, 0x100u16);
+        #[derive(Archive, Serialize)]
+        struct Index {
+            words: Vec<String>,
+            ids: Vec<u32>,
+            pairs: Vec<Pair>,
+            empty: Vec<u32>,
+        }
 
-        #[cfg(not(any(
-            all(target_endian = "little", feature = "archive_be"),
-            all(target_endian = "big", feature = "archive_le")
-        )))]
-        assert_eq!(ArchivedReallyBigEnum::V100 as u16, 0x100u16);
-        #[cfg(any(
-            all(target_endian = "little", feature = "archive_be"),
-            all(target_endian = "big", feature = "archive_le")
-        ))]
-        assert_eq!(ArchivedReallyBigEnum::V100 as u16, 0x1u16);
+        let value = Index {
+            words: ["ant", "bee", "cat", "dog", "eel"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ids: (0..100).map(|i| i * 3).collect(),
+            pairs: vec![
+                Pair {
+                    id: 1,
+                    name: "z".to_string(),
+                },
+                Pair {
+                    id: 4,
+                    name: "y".to_string(),
+                },
+                Pair {
+                    id: 9,
+                    name: "x".to_string(),
+                },
+            ],
+            empty: Vec::new(),
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Index>(bytes.as_ref()) };
 
-        let mut serializer = DefaultSerializer::default();
-        serializer.serialize_value(&ReallyBigEnum::V100).unwrap();
-        let buf = serializer.into_serializer().into_inner();
+        assert!(archived.words.is_sorted());
+        for (i, word) in value.words.iter().enumerate() {
+            assert_eq!(archived.words.binary_search(word.as_str()), Ok(i));
+        }
+        assert_eq!(archived.words.binary_search("aardvark"), Err(0));
+        assert_eq!(archived.words.binary_search("cow"), Err(3));
+        assert_eq!(archived.words.binary_search("zebra"), Err(5));
 
-        let archived = unsafe { archived_root::<ReallyBigEnum>(buf.as_ref()) };
-        assert_eq!(archived, &ArchivedReallyBigEnum::V100);
+        assert!(archived.ids.is_sorted());
+        assert_eq!(archived.ids.binary_search(&to_archived!(150u32)), Ok(50));
+        assert_eq!(archived.ids.binary_search(&to_archived!(151u32)), Err(51));
+        assert_eq!(
+            archived.ids.partition_point(|&id| from_archived!(id) < 100),
+            34
+        );
 
-        let deserialized: ReallyBigEnum = archived.deserialize(&mut Infallible).unwrap();
-        assert_eq!(deserialized, ReallyBigEnum::V100);
+        assert!(archived
+            .pairs
+            .is_sorted_by_key(|pair| from_archived!(pair.id)));
+        assert!(!archived.pairs.is_sorted_by_key(|pair| pair.name.as_str()));
+        assert_eq!(
+            archived
+                .pairs
+                .binary_search_by_key(&4, |pair| from_archived!(pair.id)),
+            Ok(1)
+        );
+        assert_eq!(
+            archived
+                .pairs
+                .binary_search_by_key(&5, |pair| from_archived!(pair.id)),
+            Err(2)
+        );
+
+        assert!(archived.empty.is_sorted());
+        assert_eq!(archived.empty.binary_search(&to_archived!(1u32)), Err(0));
+        assert_eq!(archived.empty.partition_point(|_| true), 0);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    #[cfg(not(feature = "strict"))]
-    fn repr_rust() {
-        #[derive(Archive)]
-        pub struct Test {
-            a: u8,
-            b: u16,
-            c: u8,
+    fn archived_slice_chunks() {
+        use rkyv::from_archived;
+
+        #[derive(Archive, Serialize)]
+        struct Samples {
+            values: Vec<f32>,
+            labels: Box<[String]>,
         }
 
-        assert_eq!(core::mem::size_of::<ArchivedTest>(), 4);
+        let value = Samples {
+            values: (0..10).map(|i| i as f32).collect(),
+            labels: ["a", "b", "c", "d", "e"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Samples>(bytes.as_ref()) };
+
+        let chunks = archived.values.chunks(4).collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[2], [8.0, 9.0]);
+        let exact = archived.values.chunks_exact(4);
+        assert_eq!(exact.remainder(), [8.0, 9.0]);
+        assert_eq!(
+            exact
+                .map(|c| c.iter().map(|&x| from_archived!(x)).sum::<f32>())
+                .collect::<Vec<_>>(),
+            [6.0, 22.0]
+        );
+        assert_eq!(archived.values.windows(3).count(), 8);
+        assert!(archived.values.windows(2).all(|w| w[1] - w[0] == 1.0));
+
+        let pairs = archived
+            .labels
+            .chunks(2)
+            .map(|c| c.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(""))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, ["ab", "cd", "e"]);
+        assert_eq!(archived.labels.windows(5).count(), 1);
+        assert_eq!(archived.labels.windows(6).count(), 0);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn repr_transparent() {
-        #[derive(Archive)]
-        #[archive_attr(repr(transparent))]
-        pub struct Test {
-            a: u32,
+    fn deserialize_iterators() {
+        use rkyv::de::DeserializeIterator;
+
+        let value = (0..10).map(|i| i.to_string()).collect::<Vec<_>>();
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Vec<String>>(bytes.as_ref()) };
+
+        let mut count = 0;
+        for (archived, value) in archived.into_iter().zip(value.iter()) {
+            assert_eq!(archived, value);
+            count += 1;
         }
+        assert_eq!(count, value.len());
 
-        assert_eq!(core::mem::size_of::<ArchivedTest>(), 4);
+        let evens = archived
+            .iter()
+            .step_by(2)
+            .deserialize_collect::<Vec<String>, _, _>(&mut Infallible)
+            .unwrap();
+        assert_eq!(evens, ["0", "2", "4", "6", "8"]);
+        let mut deserializer = Infallible;
+        let each = archived
+            .iter()
+            .deserialize_each::<String, _>(&mut deserializer);
+        assert_eq!(each.len(), 10);
+        assert_eq!(each.last(), Some(Ok("9".to_string())));
+
+        let mut map = BTreeMap::new();
+        map.insert(1, "one".to_string());
+        map.insert(2, "two".to_string());
+        map.insert(3, "three".to_string());
+        let bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+        let archived = unsafe { archived_root::<BTreeMap<i32, String>>(bytes.as_ref()) };
+        let odd: BTreeMap<i32, String> = archived
+            .iter()
+            .filter(|(k, _)| **k % 2 == 1)
+            .deserialize_collect(&mut Infallible)
+            .unwrap();
+        assert_eq!(odd.len(), 2);
+        assert_eq!(odd[&3], "three");
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn repr_c() {
-        #[derive(Archive)]
-        #[archive_attr(repr(C))]
-        pub struct TestStruct {
-            a: u8,
-            b: u16,
-            c: u8,
+    fn archived_dynamic_value() {
+        use rkyv::value::{ArchivedValue, Value};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Upload {
+            id: u32,
+            metadata: Value,
         }
 
-        assert_eq!(core::mem::size_of::<ArchivedTestStruct>(), 6);
+        let mut metadata = BTreeMap::new();
+        metadata.insert("camera".to_string(), Value::String("pinhole".to_string()));
+        metadata.insert("exposure".to_string(), Value::Float(0.25));
+        metadata.insert("iso".to_string(), Value::Int(400));
+        metadata.insert("serial".to_string(), Value::UInt(u64::MAX));
+        metadata.insert(
+            "tags".to_string(),
+            Value::Array(vec![Value::Bool(true), Value::Null, Value::Int(-1)]),
+        );
+        let value = Upload {
+            id: 7,
+            metadata: Value::Map(metadata),
+        };
+
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Upload>(bytes.as_ref()) };
+        let metadata = &archived.metadata;
+        assert_eq!(*metadata, value.metadata);
+        assert_eq!(metadata.as_map().unwrap().len(), 5);
+
+        assert_eq!(metadata["camera"].as_str(), Some("pinhole"));
+        assert_eq!(metadata["camera"].as_i64(), None);
+        assert_eq!(metadata["exposure"].as_f64(), Some(0.25));
+        assert_eq!(metadata["iso"].as_i64(), Some(400));
+        assert_eq!(metadata["iso"].as_u64(), Some(400));
+        assert_eq!(metadata["iso"].as_f64(), Some(400.0));
+        assert_eq!(metadata["serial"].as_i64(), None);
+        assert_eq!(metadata["serial"].as_u64(), Some(u64::MAX));
+        assert_eq!(metadata["tags"].as_array().unwrap().len(), 3);
+        assert_eq!(metadata["tags"][0].as_bool(), Some(&true));
+        assert!(metadata["tags"][1].is_null());
+        assert_eq!(metadata["tags"][2].as_u64(), None);
+        assert!(metadata.get("missing").is_none());
+        assert!(metadata["iso"].get("iso").is_none());
+        assert!(matches!(metadata["tags"][1], ArchivedValue::Null));
+
+        let deserialized: Upload = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        let mut other = value.metadata.clone();
+        if let Value::Map(map) = &mut other {
+            map.insert("iso".to_string(), Value::Int(800));
+        }
+        assert_ne!(*metadata, other);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn repr_int() {
-        #[derive(Archive)]
-        #[allow(dead_code)]
-        pub enum InferredRepr {
-            V0,
-            V1,
-        }
+    fn append_archives() {
+        use rkyv::{append_to_bytes, archived_value, to_bytes};
 
-        assert_eq!(core::mem::size_of::<ArchivedInferredRepr>(), 1);
+        #[derive(Archive, Serialize)]
+        struct Record {
+            id: u32,
+            message: String,
+            tags: Vec<String>,
+        }
 
-        #[derive(Archive)]
-        #[archive_attr(repr(u16))]
-        #[allow(dead_code)]
-        pub enum ExplicitRepr {
-            V0,
-            V1,
+        let records = (0..10)
+            .map(|id| Record {
+                id,
+                message: "record number ".to_string() + &id.to_string(),
+                tags: (0..id).map(|i| i.to_string()).collect(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut bytes = to_bytes::<_, 256>(&records[0]).unwrap();
+        let mut positions = vec![bytes.len() - core::mem::size_of::<Archived<Record>>()];
+        for record in records[1..].iter() {
+            let len = bytes.len();
+            let earlier = bytes.as_slice().to_vec();
+            positions.push(append_to_bytes::<_, 256>(&mut bytes, record).unwrap());
+            // Appending never modifies earlier bytes
+            assert_eq!(&bytes[..len], earlier.as_slice());
         }
 
-        assert_eq!(core::mem::size_of::<ArchivedExplicitRepr>(), 2);
+        for (record, pos) in records.iter().zip(positions.iter()) {
+            let archived = unsafe { archived_value::<Record>(bytes.as_ref(), *pos) };
+            assert_eq!(archived.id, record.id);
+            assert_eq!(archived.message, record.message);
+            assert_eq!(archived.tags, record.tags);
+        }
+        let last = unsafe { archived_root::<Record>(bytes.as_ref()) };
+        assert_eq!(last.id, 9);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn repr_c_packed() {
-        #[derive(Archive)]
-        #[archive_attr(repr(C, packed))]
-        struct CPackedRepr {
-            a: u8,
-            b: u32,
-            c: u8,
+    fn patch_archives() {
+        #[cfg(not(feature = "std"))]
+        use alloc::format;
+        use core::pin::Pin;
+        use rkyv::{archived_value, archived_value_mut, patch_box, patch_vec, to_bytes};
+
+        #[derive(Archive, Serialize)]
+        struct Leaf {
+            name: String,
+            tags: Vec<String>,
         }
 
-        assert_eq!(core::mem::size_of::<ArchivedCPackedRepr>(), 6);
+        #[derive(Archive, Serialize)]
+        struct Node {
+            name: String,
+            children: Vec<Leaf>,
+        }
 
-        #[derive(Archive)]
-        #[archive_attr(repr(C))]
-        #[archive_attr(repr(packed))]
-        struct CPackedRepr2 {
-            a: u8,
-            b: u32,
-            c: u8,
+        #[derive(Archive, Serialize)]
+        struct Tree {
+            root: Box<Node>,
+            values: Vec<u64>,
         }
 
-        assert_eq!(core::mem::size_of::<ArchivedCPackedRepr2>(), 6);
+        // Patching appends to the archive, so the root must be accessed at its position
+        fn select_tree(bytes: Pin<&mut [u8]>, pos: usize) -> Pin<&mut Archived<Tree>> {
+            unsafe { archived_value_mut::<Tree>(bytes, pos) }
+        }
+
+        let value = Tree {
+            root: Box::new(Node {
+                name: "root".to_string(),
+                children: (0..10)
+                    .map(|i| Leaf {
+                        name: format!("child number {}", i),
+                        tags: Vec::new(),
+                    })
+                    .collect(),
+            }),
+            values: (0..100).collect(),
+        };
+        let mut bytes = to_bytes::<_, 256>(&value).unwrap();
+        let root_pos = bytes.len() - core::mem::size_of::<Archived<Tree>>();
+        let len = bytes.len();
+        let earlier = bytes.as_slice().to_vec();
+        let box_pos = unsafe {
+            let archived = archived_value::<Tree>(bytes.as_ref(), root_pos);
+            &archived.root as *const _ as usize - bytes.as_ptr() as usize
+        };
+
+        let node = Node {
+            name: "new root with a longer name".to_string(),
+            children: vec![Leaf {
+                name: "only child".to_string(),
+                tags: Vec::new(),
+            }],
+        };
+        patch_box::<_, _, 256>(
+            &mut bytes,
+            |bytes| unsafe {
+                select_tree(bytes, root_pos).map_unchecked_mut(|tree| &mut tree.root)
+            },
+            &node,
+        )
+        .unwrap();
+        // Only the relative pointer of the box changed
+        let changed = (0..len)
+            .filter(|&i| bytes[i] != earlier[i])
+            .collect::<Vec<_>>();
+        assert!(!changed.is_empty());
+        let box_end = box_pos + core::mem::size_of::<Archived<Box<Node>>>();
+        assert!(changed.iter().all(|&i| i >= box_pos && i < box_end));
+
+        patch_vec::<_, _, 256>(
+            &mut bytes,
+            |bytes| unsafe {
+                select_tree(bytes, root_pos).map_unchecked_mut(|tree| &mut tree.values)
+            },
+            &[7u64, 8, 9],
+        )
+        .unwrap();
+
+        let archived = unsafe { archived_value::<Tree>(bytes.as_ref(), root_pos) };
+        assert_eq!(archived.root.name, "new root with a longer name");
+        assert_eq!(archived.root.children.len(), 1);
+        assert_eq!(archived.root.children[0].name, "only child");
+        assert_eq!(archived.values.as_slice(), &[7, 8, 9]);
+
+        // Values inside patched values can be patched too
+        let tags = vec!["first tag".to_string(), "second tag".to_string()];
+        patch_vec::<_, _, 256>(
+            &mut bytes,
+            |bytes| unsafe {
+                select_tree(bytes, root_pos)
+                    .map_unchecked_mut(|tree| &mut tree.root)
+                    .get_pin_mut()
+                    .map_unchecked_mut(|node| &mut node.children)
+                    .index_pin(0)
+                    .map_unchecked_mut(|leaf| &mut leaf.tags)
+            },
+            &tags,
+        )
+        .unwrap();
+        let archived = unsafe { archived_value::<Tree>(bytes.as_ref(), root_pos) };
+        assert_eq!(archived.root.children[0].tags, tags);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn repr_c_align() {
-        #[derive(Archive)]
-        #[archive_attr(repr(C, align(8)))]
-        struct CAlignRepr {
-            a: u8,
-        }
+    fn archive_directory() {
+        use rkyv::directory::Directory;
 
-        assert_eq!(core::mem::align_of::<ArchivedCAlignRepr>(), 8);
-
-        #[derive(Archive)]
-        #[archive_attr(repr(C))]
-        #[archive_attr(repr(align(8)))]
-        struct CAlignRepr2 {
-            a: u8,
+        #[derive(Archive, Serialize)]
+        struct Header {
+            version: u32,
+            name: String,
         }
 
-        assert_eq!(core::mem::align_of::<ArchivedCAlignRepr>(), 8);
+        let mut serializer = DefaultSerializer::default();
+        let mut directory = Directory::new();
+        let header_pos = directory
+            .serialize_root(
+                "header",
+                &Header {
+                    version: 3,
+                    name: "test".to_string(),
+                },
+                &mut serializer,
+            )
+            .unwrap();
+        directory
+            .serialize_root("index", &vec![10u64, 20, 30], &mut serializer)
+            .unwrap();
+        let payload_pos = serializer
+            .serialize_value(&"a payload that is stored out of line".to_string())
+            .unwrap();
+        assert_eq!(directory.insert("payload", payload_pos), None);
+        assert_eq!(directory.len(), 3);
+        assert_eq!(directory.pos("header"), Some(header_pos));
+        serializer.serialize_value(&directory).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+
+        let archived = unsafe { archived_root::<Directory>(buf.as_ref()) };
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived.index_of("index"), Some(1));
+        assert_eq!(archived.pos("header"), Some(header_pos));
+        assert_eq!(archived.pos("missing"), None);
+        let names = archived.entries().iter().map(|e| e.name());
+        assert!(names.eq(["header", "index", "payload"]));
+
+        let header = unsafe { archived.root::<Header>(buf.as_ref(), "header") }.unwrap();
+        assert_eq!(header.version, 3);
+        assert_eq!(header.name, "test");
+        let index = unsafe { archived.root_at::<Vec<u64>>(buf.as_ref(), 1) }.unwrap();
+        assert_eq!(index.as_slice(), [10, 20, 30]);
+        let payload = unsafe { archived.root::<String>(buf.as_ref(), "payload") }.unwrap();
+        assert_eq!(payload, "a payload that is stored out of line");
+        assert!(unsafe { archived.root_at::<String>(buf.as_ref(), 3) }.is_none());
+
+        let deserialized: Directory = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, directory);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn archive_as() {
-        // Struct
+    fn archive_sub_archive() {
+        use rkyv::sub_archive::SubArchive;
 
-        #[derive(Archive, Serialize, Deserialize, Debug)]
-        #[archive(as = "ExampleStruct<T::Archived>")]
-        #[repr(transparent)]
-        struct ExampleStruct<T> {
-            value: T,
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Shard {
+            id: u32,
+            values: Vec<u64>,
         }
 
-        impl<T: PartialEq<U>, U> PartialEq<ExampleStruct<U>> for ExampleStruct<T> {
-            fn eq(&self, other: &ExampleStruct<U>) -> bool {
-                self.value.eq(&other.value)
-            }
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Collection {
+            name: String,
+            shards: Vec<SubArchive<Shard>>,
         }
 
-        let value = ExampleStruct {
-            value: "hello world".to_string(),
-        };
-
-        test_archive(&value);
-
-        // Tuple struct
+        let shards = (0..3)
+            .map(|id| {
+                let shard = Shard {
+                    id,
+                    values: (0..id as u64 + 1).collect(),
+                };
+                SubArchive::from_value::<256>(&shard).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(shards[2].root().values.as_slice(), [0, 1, 2]);
 
-        #[derive(Archive, Serialize, Deserialize, Debug)]
-        #[archive(as = "ExampleTupleStruct<T::Archived>")]
-        #[repr(transparent)]
-        struct ExampleTupleStruct<T>(T);
+        let mut serializer = DefaultSerializer::default();
+        serializer.write(&[0xff; 3]).unwrap();
+        serializer
+            .serialize_value(&Collection {
+                name: "shards".to_string(),
+                shards: shards.clone(),
+            })
+            .unwrap();
+        let buf = serializer.into_serializer().into_inner();
 
-        impl<T: PartialEq<U>, U> PartialEq<ExampleTupleStruct<U>> for ExampleTupleStruct<T> {
-            fn eq(&self, other: &ExampleTupleStruct<U>) -> bool {
-                self.0.eq(&other.0)
-            }
+        let archived = unsafe { archived_root::<Collection>(buf.as_ref()) };
+        assert_eq!(archived.name, "shards");
+        assert_eq!(archived.shards.len(), 3);
+        for (i, shard) in archived.shards.iter().enumerate() {
+            assert_eq!(shard.as_bytes(), shards[i].as_bytes());
+            assert_eq!(shard.as_bytes().as_ptr() as usize % 16, 0);
+            let root = shard.root();
+            assert_eq!(root.id, i as u32);
+            assert_eq!(root.values.len(), i + 1);
         }
 
-        let value = ExampleTupleStruct("hello world".to_string());
-
-        test_archive(&value);
+        let deserialized: Collection = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.name, "shards");
+        assert_eq!(deserialized.shards[1].as_bytes(), shards[1].as_bytes());
+        assert_eq!(deserialized.shards[1].root().values.as_slice(), [0, 1]);
+    }
 
-        // Unit struct
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_arena() {
+        use rkyv::arena::{Arena, ArenaIndex};
 
         #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
-        #[archive(as = "ExampleUnitStruct")]
-        struct ExampleUnitStruct;
-
-        test_archive(&ExampleUnitStruct);
-
-        // Enum
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        enum Expr {
+            Literal(i32),
+            Add(ArenaIndex<Expr>, ArenaIndex<Expr>),
+            Neg(ArenaIndex<Expr>),
+        }
 
-        #[derive(Archive, Serialize, Deserialize, Debug)]
-        #[archive(as = "ExampleEnum<T::Archived>")]
-        #[repr(u8)]
-        enum ExampleEnum<T> {
-            A(T),
-            B,
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Ast {
+            exprs: Arena<Expr>,
+            root: ArenaIndex<Expr>,
         }
 
-        impl<T: PartialEq<U>, U> PartialEq<ExampleEnum<U>> for ExampleEnum<T> {
-            fn eq(&self, other: &ExampleEnum<U>) -> bool {
-                match self {
-                    ExampleEnum::A(value) => {
-                        if let ExampleEnum::A(other) = other {
-                            value.eq(other)
-                        } else {
-                            false
-                        }
-                    }
-                    ExampleEnum::B => {
-                        if let ExampleEnum::B = other {
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                }
+        fn eval(exprs: &<Arena<Expr> as Archive>::Archived, expr: &ArchivedExpr) -> i32 {
+            match expr {
+                ArchivedExpr::Literal(value) => rkyv::from_archived!(*value),
+                ArchivedExpr::Add(a, b) => eval(exprs, &exprs[*a]) + eval(exprs, &exprs[*b]),
+                ArchivedExpr::Neg(a) => -eval(exprs, &exprs[*a]),
             }
         }
 
-        let value = ExampleEnum::A("hello world".to_string());
+        // The literal is shared by both sides of the addition
+        let mut exprs = Arena::new();
+        let two = exprs.push(Expr::Literal(2));
+        let neg = exprs.push(Expr::Neg(two));
+        let root = exprs.push(Expr::Add(two, neg));
+        let sum = exprs.push(Expr::Add(root, two));
+        assert_eq!(exprs.len(), 4);
+        assert_eq!(exprs[neg], Expr::Neg(two));
 
-        test_archive(&value);
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&Ast { exprs, root: sum })
+            .unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Ast>(buf.as_ref()) };
+
+        assert_eq!(archived.exprs.len(), 4);
+        assert_eq!(archived.root, sum);
+        assert_eq!(archived.root.index(), 3);
+        assert_eq!(eval(&archived.exprs, &archived.exprs[archived.root]), 2);
+        // Indices from before serialization can be used with the archived arena
+        assert_eq!(archived.exprs[two], Expr::Literal(2));
+        assert_eq!(archived.exprs[neg], Expr::Neg(two));
+
+        let deserialized: Ast = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.root, sum);
+        assert_eq!(deserialized.exprs.as_slice(), archived.exprs.as_slice());
     }
 
-    mod with {
-        #[cfg(not(feature = "std"))]
-        use alloc::string::{String, ToString};
-        use core::str::FromStr;
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn checksummed_archive() {
         use rkyv::{
-            archived_root,
-            ser::serializers::AlignedSerializer,
-            ser::Serializer,
-            with::{ArchiveWith, DeserializeWith, SerializeWith},
-            AlignedVec, Archive, Archived, Deserialize, Fallible, Infallible, Serialize,
+            checksum::{
+                archived_root_checksummed, crc32, verify_checksum, ChecksumError, CHECKSUM_LEN,
+            },
+            ser::serializers::ChecksumSerializer,
         };
 
-        #[cfg(feature = "wasm")]
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(&[]), 0);
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            name: String,
+            values: Vec<u32>,
+        }
+
+        let value = Test {
+            name: "checksummed".to_string(),
+            values: vec![1, 2, 3, 4],
+        };
+        let mut serializer = ChecksumSerializer::new(DefaultSerializer::default());
+        serializer.serialize_value(&value).unwrap();
+        let checksum = serializer.checksum();
+        let mut buf = serializer.finish().unwrap().into_serializer().into_inner();
+        assert_eq!(buf[buf.len() - CHECKSUM_LEN..], checksum.to_le_bytes());
+
+        let archive = verify_checksum(buf.as_ref()).unwrap();
+        assert_eq!(archive.len(), buf.len() - CHECKSUM_LEN);
+        let archived = unsafe { archived_root_checksummed::<Test>(buf.as_ref()) }.unwrap();
+        assert_eq!(archived.name, "checksummed");
+        assert_eq!(archived.values.as_slice(), [1, 2, 3, 4]);
+
+        buf[3] ^= 0x10;
+        match verify_checksum(buf.as_ref()) {
+            Err(ChecksumError::Mismatch { expected, actual }) => {
+                assert_eq!(expected, checksum);
+                assert_ne!(actual, checksum);
+            }
+            result => panic!("expected a checksum mismatch, found {:?}", result),
+        }
+        assert!(matches!(
+            verify_checksum(&buf[..2]),
+            Err(ChecksumError::Truncated { len: 2 })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn serialized_size() {
+        use rkyv::{serialized_size, to_bytes};
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            id: u8,
+            name: String,
+            values: Vec<u64>,
+            first: Rc<String>,
+            second: Rc<String>,
+        }
+
+        fn assert_size<T>(value: &T)
+        where
+            T: Serialize<rkyv::ser::serializers::SizeSerializer<256>>
+                + Serialize<rkyv::ser::serializers::AllocSerializer<256>>,
+        {
+            let size = serialized_size::<T, 256>(value).unwrap();
+            assert_eq!(size, to_bytes::<T, 256>(value).unwrap().len());
+        }
+
+        let shared = Rc::new("a shared string long enough to be out of line".to_string());
+        assert_size(&Test {
+            id: 1,
+            name: "a name that is long enough to be out of line".to_string(),
+            values: vec![1, 2, 3],
+            first: shared.clone(),
+            second: shared,
+        });
+        assert_size(&"short".to_string());
+        assert_size(&Vec::<u32>::new());
+        assert_size(&Some(Box::new([1u16; 7])));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_accessors() {
+        mod private {
+            #[cfg(not(feature = "std"))]
+            use alloc::{string::String, vec::Vec};
+            use rkyv::{Archive, Serialize};
+
+            #[derive(Archive, Serialize)]
+            #[archive(accessors)]
+            pub struct Record<T> {
+                name: String,
+                values: Vec<T>,
+                r#type: u8,
+                pub(crate) id: Option<u32>,
+            }
+
+            impl<T> Record<T> {
+                pub fn new(name: String, values: Vec<T>, r#type: u8, id: Option<u32>) -> Self {
+                    Self {
+                        name,
+                        values,
+                        r#type,
+                        id,
+                    }
+                }
+            }
+        }
+
+        let value = private::Record::new("record".to_string(), vec![1u16, 2, 3], 7, Some(42));
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<private::Record<u16>>(&bytes) };
+        let name: &str = archived.name();
+        assert_eq!(name, "record");
+        assert_eq!(archived.values().as_slice(), &[1, 2, 3]);
+        assert_eq!(*archived.r#type(), 7);
+        assert_eq!(
+            archived.id().as_ref().map(|id| rkyv::from_archived!(*id)),
+            Some(42)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_lazy() {
+        use rkyv::{de::deserializers::SharedDeserializeMap, lazy::Lazy};
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Leaf {
+            name: String,
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Tree {
+            name: String,
+            children: Vec<Lazy<Leaf>>,
+        }
+
+        let leaf = |name: &str| {
+            Lazy::new::<256>(Leaf {
+                name: name.to_string(),
+            })
+            .unwrap()
+        };
+        let value = Tree {
+            name: "root".to_string(),
+            children: vec![leaf("a"), leaf("b")],
+        };
+        assert!(value.children[0].is_deserialized());
+
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Tree>(&bytes) };
+        assert_eq!(archived.children[1].root().name, "b");
+
+        let deserialized: Tree = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.name, "root");
+        assert!(deserialized
+            .children
+            .iter()
+            .all(|child| !child.is_deserialized()));
+        assert_eq!(deserialized.children[1].archived().name, "b");
+        assert!(!deserialized.children[1].is_deserialized());
+
+        let b = deserialized.children[1].get();
+        assert_eq!(b.name, "b");
+        assert!(core::ptr::eq(b, deserialized.children[1].get()));
+        assert!(!deserialized.children[0].is_deserialized());
+
+        let a = deserialized.children[0]
+            .get_with(&mut SharedDeserializeMap::new())
+            .unwrap();
+        assert_eq!(a.name, "a");
+
+        let mut children = deserialized.children.into_iter();
+        assert_eq!(children.next().unwrap().into_inner().name, "a");
+        assert_eq!(children.next().unwrap().into_inner().name, "b");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_fingerprint() {
+        use rkyv::fingerprint::{
+            archived_root_with_fingerprint, verify_fingerprint, write_fingerprint, Fingerprint,
+            FingerprintError,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(fingerprint)]
+        struct Record {
+            id: u32,
+            name: String,
+            tags: Vec<Option<u16>>,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(fingerprint)]
+        struct Renamed {
+            id: u32,
+            title: String,
+            tags: Vec<Option<u16>>,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(fingerprint)]
+        struct Widened {
+            id: u64,
+            name: String,
+            tags: Vec<Option<u16>>,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(fingerprint)]
+        struct Reordered {
+            name: String,
+            id: u32,
+            tags: Vec<Option<u16>>,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(fingerprint)]
+        enum Shape {
+            Circle(f32),
+            Rect { width: f32, height: f32 },
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(fingerprint)]
+        enum Renumbered {
+            Circle(f32),
+            Rect { height: f32, width: f32 },
+        }
+
+        // The name of the type is not part of the fingerprint
+        #[derive(Archive, Serialize)]
+        #[archive(fingerprint)]
+        struct Duplicate {
+            id: u32,
+            name: String,
+            tags: Vec<Option<u16>>,
+        }
+
+        // Recursive fields are named by their type, however it's spaced
+        mod spaced {
+            use super::*;
+
+            #[derive(Archive)]
+            #[archive(fingerprint)]
+            pub struct Node {
+                #[omit_bounds]
+                pub next: Option<Box<Node>>,
+            }
+        }
+
+        mod unspaced {
+            use super::*;
+
+            #[rustfmt::skip]
+            #[derive(Archive)]
+            #[archive(fingerprint)]
+            pub struct Node {
+                #[omit_bounds]
+                pub next: Option< Box< Node > >,
+            }
+        }
+
+        assert_eq!(spaced::Node::FINGERPRINT, unspaced::Node::FINGERPRINT);
+        assert_eq!(Record::FINGERPRINT, Duplicate::FINGERPRINT);
+        assert_ne!(Record::FINGERPRINT, Renamed::FINGERPRINT);
+        assert_ne!(Record::FINGERPRINT, Widened::FINGERPRINT);
+        assert_ne!(Record::FINGERPRINT, Reordered::FINGERPRINT);
+        assert_ne!(Shape::FINGERPRINT, Renumbered::FINGERPRINT);
+        for shape in [
+            Shape::Circle(1.0),
+            Shape::Rect {
+                width: 1.0,
+                height: 2.0,
+            },
+        ] {
+            assert!(rkyv::to_bytes::<_, 256>(&shape).is_ok());
+        }
+        for shape in [
+            Renumbered::Circle(1.0),
+            Renumbered::Rect {
+                height: 2.0,
+                width: 1.0,
+            },
+        ] {
+            assert!(rkyv::to_bytes::<_, 256>(&shape).is_ok());
+        }
+        assert_ne!(<Vec<u32>>::FINGERPRINT, <Vec<i32>>::FINGERPRINT);
+
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        write_fingerprint::<Record, _>(&mut serializer).unwrap();
+        let mut serializer = rkyv::ser::serializers::AllocSerializer::<256>::new(
+            serializer,
+            Default::default(),
+            Default::default(),
+        );
+        serializer
+            .serialize_value(&Record {
+                id: 42,
+                name: "record".to_string(),
+                tags: vec![Some(1), None],
+            })
+            .unwrap();
+        let bytes = serializer.into_serializer().into_inner();
+
+        assert!(verify_fingerprint::<Record>(&bytes).is_ok());
+        let archived = unsafe { archived_root_with_fingerprint::<Record>(&bytes) }.unwrap();
+        assert_eq!(archived.id, 42);
+        assert_eq!(archived.name, "record");
+        assert_eq!(archived.tags[0], Some(1));
+
+        match verify_fingerprint::<Widened>(&bytes) {
+            Err(FingerprintError::Mismatch { expected, actual }) => {
+                assert_eq!(expected, Widened::FINGERPRINT);
+                assert_eq!(actual, Record::FINGERPRINT);
+            }
+            _ => panic!("expected a fingerprint mismatch"),
+        }
+        assert!(matches!(
+            verify_fingerprint::<Record>(&bytes[..4]),
+            Err(FingerprintError::Truncated { len: 4 })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_versioned() {
+        use rkyv::versioned::{Version, VersionError, Versioned};
+
+        mod v1 {
+            use super::*;
+
+            #[derive(Archive, Serialize)]
+            #[archive(version = 1)]
+            pub struct User {
+                pub name: String,
+            }
+        }
+
+        mod v2 {
+            use super::*;
+
+            #[derive(Archive, Serialize)]
+            #[archive(version = 2)]
+            pub struct User {
+                pub name: String,
+                pub age: u8,
+            }
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(version = 4)]
+        struct Unrelated {
+            value: u64,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(version = 3, migrate_from(v1::User, v2::User))]
+        struct User {
+            name: String,
+            age: u32,
+            email: Option<String>,
+        }
+
+        impl From<&Archived<v1::User>> for User {
+            fn from(old: &Archived<v1::User>) -> Self {
+                Self {
+                    name: old.name.to_string(),
+                    age: 0,
+                    email: None,
+                }
+            }
+        }
+
+        impl From<&Archived<v2::User>> for User {
+            fn from(old: &Archived<v2::User>) -> Self {
+                Self {
+                    name: old.name.to_string(),
+                    age: old.age as u32,
+                    email: None,
+                }
+            }
+        }
+
+        assert_eq!(User::VERSION, 3);
+
+        let bytes = rkyv::to_bytes::<_, 256>(&Versioned(v1::User {
+            name: "first".to_string(),
+        }))
+        .unwrap();
+        let archived = unsafe { archived_root::<Versioned<User>>(&bytes) };
+        assert_eq!(archived.version(), 1);
+        assert!(!archived.is_current());
+        assert!(archived.get().is_none());
+        assert_eq!(
+            archived.upgrade().unwrap(),
+            User {
+                name: "first".to_string(),
+                age: 0,
+                email: None,
+            }
+        );
+
+        let bytes = rkyv::to_bytes::<_, 256>(&Versioned(v2::User {
+            name: "second".to_string(),
+            age: 30,
+        }))
+        .unwrap();
+        let archived = unsafe { archived_root::<Versioned<User>>(&bytes) };
+        assert_eq!(archived.version(), 2);
+        assert_eq!(archived.upgrade().unwrap().age, 30);
+
+        let current = User {
+            name: "third".to_string(),
+            age: 40,
+            email: Some("third@example.com".to_string()),
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&Versioned(current)).unwrap();
+        let archived = unsafe { archived_root::<Versioned<User>>(&bytes) };
+        assert!(archived.is_current());
+        assert_eq!(archived.get().unwrap().name, "third");
+        assert_eq!(
+            archived.upgrade().unwrap().email.as_deref(),
+            Some("third@example.com")
+        );
+
+        let bytes = rkyv::to_bytes::<_, 256>(&Versioned(Unrelated { value: 1 })).unwrap();
+        let archived = unsafe { archived_root::<Versioned<User>>(&bytes) };
+        assert!(matches!(
+            archived.upgrade(),
+            Err(VersionError::Unsupported {
+                version: 4,
+                current: 3,
+            })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_extensible() {
+        use rkyv::from_archived;
+
+        mod old {
+            use super::*;
+
+            #[derive(Archive, Serialize, Deserialize)]
+            #[archive(extensible)]
+            pub struct Item {
+                pub id: u32,
+                pub name: String,
+            }
+
+            #[derive(Archive, Serialize, Deserialize)]
+            #[archive(extensible)]
+            pub struct Inventory {
+                pub items: Vec<Item>,
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(extensible)]
+        struct Item {
+            id: u32,
+            name: String,
+            tags: Vec<String>,
+            weight: Option<u64>,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(extensible)]
+        struct Inventory {
+            items: Vec<Item>,
+            owner: String,
+        }
+
+        // New readers substitute defaults for fields missing from old archives
+        let value = old::Inventory {
+            items: vec![
+                old::Item {
+                    id: 1,
+                    name: "hammer".to_string(),
+                },
+                old::Item {
+                    id: 2,
+                    name: "saw".to_string(),
+                },
+            ],
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Inventory>(&bytes) };
+        assert!(archived.owner().is_none());
+        let items = archived.items().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].id().map(|&id| from_archived!(id)), Some(2));
+        assert_eq!(items[1].name().unwrap(), "saw");
+        assert!(items[1].tags().is_none());
+        assert!(items[1].weight().is_none());
+
+        let deserialized: Inventory = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(
+            deserialized,
+            Inventory {
+                items: vec![
+                    Item {
+                        id: 1,
+                        name: "hammer".to_string(),
+                        tags: Vec::new(),
+                        weight: None,
+                    },
+                    Item {
+                        id: 2,
+                        name: "saw".to_string(),
+                        tags: Vec::new(),
+                        weight: None,
+                    },
+                ],
+                owner: String::new(),
+            }
+        );
+
+        // Old readers ignore fields added by new writers
+        let value = Inventory {
+            items: vec![Item {
+                id: 3,
+                name: "drill".to_string(),
+                tags: vec!["power".to_string()],
+                weight: Some(1200),
+            }],
+            owner: "workshop".to_string(),
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<old::Inventory>(&bytes) };
+        let items = archived.items().unwrap();
+        assert_eq!(items[0].name().unwrap(), "drill");
+        let deserialized: old::Inventory = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.items[0].id, 3);
+
+        let archived = unsafe { archived_root::<Inventory>(&bytes) };
+        assert_eq!(archived.owner().unwrap(), "workshop");
+        let items = archived.items().unwrap();
+        assert_eq!(items[0].tags().unwrap()[0], "power");
+        assert_eq!(
+            items[0]
+                .weight()
+                .unwrap()
+                .as_ref()
+                .map(|&weight| from_archived!(weight)),
+            Some(1200)
+        );
+        let deserialized: Inventory = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_extensible_padding() {
+        use rkyv::from_archived;
+
+        mod old {
+            use super::*;
+
+            #[derive(Archive, Serialize)]
+            #[archive(extensible)]
+            pub struct Header {
+                pub a: u32,
+                pub b: u8,
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(extensible)]
+        struct Header {
+            a: u32,
+            b: u8,
+            c: u8,
+            d: u16,
+        }
+
+        // The new fields fit in the trailing padding of the old fields, but weren't archived
+        let bytes = rkyv::to_bytes::<_, 256>(&old::Header { a: 1, b: 2 }).unwrap();
+        let archived = unsafe { archived_root::<Header>(&bytes) };
+        assert_eq!(archived.a().map(|&a| from_archived!(a)), Some(1));
+        assert_eq!(archived.b(), Some(&2));
+        assert!(archived.c().is_none());
+        assert!(archived.d().is_none());
+
+        let deserialized: Header = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(
+            deserialized,
+            Header {
+                a: 1,
+                b: 2,
+                c: 0,
+                d: 0,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_skip() {
+        use core::cell::Cell;
+        use rkyv::with::Skip;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Cached {
+            name: String,
+            #[with(Skip)]
+            cache: Cell<Option<u64>>,
+            #[with(Skip)]
+            scratch: Vec<u8>,
+        }
+
+        #[derive(Archive)]
+        struct Uncached {
+            _name: String,
+        }
+
+        assert_eq!(
+            core::mem::size_of::<Archived<Cached>>(),
+            core::mem::size_of::<Archived<Uncached>>()
+        );
+
+        let value = Cached {
+            name: "hello".to_string(),
+            cache: Cell::new(Some(42)),
+            scratch: vec![1, 2, 3],
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Cached>(buf.as_ref()) };
+        assert_eq!(archived.name, "hello");
+
+        let deserialized: Cached = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(
+            deserialized,
+            Cached {
+                name: "hello".to_string(),
+                cache: Cell::new(None),
+                scratch: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_transformed() {
+        use rkyv::with::{Map, Transform, Transformed};
+
+        struct RunLength;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Run {
+            value: u8,
+            count: u8,
+        }
+
+        impl Transform<Vec<u8>> for RunLength {
+            type Output = Vec<Run>;
+
+            fn serialize(field: &Vec<u8>) -> Self::Output {
+                let mut runs = Vec::<Run>::new();
+                for &byte in field.iter() {
+                    match runs.last_mut() {
+                        Some(run) if run.value == byte && run.count < u8::MAX => run.count += 1,
+                        _ => runs.push(Run {
+                            value: byte,
+                            count: 1,
+                        }),
+                    }
+                }
+                runs
+            }
+
+            fn deserialize(output: Self::Output) -> Vec<u8> {
+                output
+                    .into_iter()
+                    .flat_map(|run| vec![run.value; run.count as usize])
+                    .collect()
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Image {
+            #[with(Transformed<RunLength>)]
+            pixels: Vec<u8>,
+            #[with(Map<Transformed<RunLength>>)]
+            mask: Option<Vec<u8>>,
+        }
+
+        let value = Image {
+            pixels: vec![0, 0, 0, 0, 7, 7, 1],
+            mask: Some(vec![1; 300]),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Image>(buf.as_ref()) };
+
+        assert_eq!(archived.pixels.len(), 3);
+        assert_eq!(archived.pixels[0].value, 0);
+        assert_eq!(archived.pixels[0].count, 4);
+        assert_eq!(archived.mask.as_ref().unwrap().len(), 2);
+
+        let deserialized: Image = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_map_btree_map_values() {
+        #[cfg(not(feature = "std"))]
+        use alloc::collections::BTreeMap;
+        use rkyv::{
+            to_archived,
+            with::{AsOwned, Map},
+        };
+        #[cfg(feature = "std")]
+        use std::collections::BTreeMap;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test<'a> {
+            #[with(Map<AsOwned>)]
+            names: BTreeMap<u32, Cow<'a, str>>,
+            #[with(Map<Map<AsOwned>>)]
+            aliases: BTreeMap<String, Vec<Cow<'a, str>>>,
+        }
+
+        let owned = "owned".to_string();
+        let mut names = BTreeMap::new();
+        names.insert(1, Cow::Borrowed("borrowed"));
+        names.insert(2, Cow::Owned(owned));
+        let mut aliases = BTreeMap::new();
+        aliases.insert(
+            "a".to_string(),
+            vec![Cow::Borrowed("b"), Cow::Borrowed("c")],
+        );
+        aliases.insert("d".to_string(), Vec::new());
+        let value = Test { names, aliases };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.names.get(&to_archived!(1u32)).unwrap(), "borrowed");
+        assert_eq!(archived.names.get(&to_archived!(2u32)).unwrap(), "owned");
+        assert_eq!(archived.aliases.get("a").unwrap()[1], "c");
+        assert!(archived.aliases.get("d").unwrap().is_empty());
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_rename_and_reorder() {
+        use core::mem::{align_of, size_of, MaybeUninit};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq), accessors)]
+        #[archive_attr(derive(Debug))]
+        struct Renamed {
+            #[archive(rename = "kind")]
+            r#type: u8,
+            #[archive(rename = "label")]
+            name: String,
+            count: u32,
+        }
+
+        let value = Renamed {
+            r#type: 3,
+            name: "hello".to_string(),
+            count: 10,
+        };
+        test_archive(&value);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Renamed>(buf.as_ref()) };
+        assert_eq!(archived.kind, 3);
+        assert_eq!(archived.label(), "hello");
+        assert_eq!(*archived.count(), 10);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(extensible)]
+        struct Extended {
+            #[archive(rename = "old_name")]
+            name: String,
+        }
+
+        let value = Extended {
+            name: "hello".to_string(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Extended>(buf.as_ref()) };
+        assert_eq!(archived.old_name().unwrap(), "hello");
+        let deserialized: Extended = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug), repr(C))]
+        struct Unordered {
+            a: u8,
+            b: u64,
+            c: u16,
+            d: String,
+            e: u32,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq), reorder)]
+        #[archive_attr(derive(Debug), repr(C))]
+        struct Reordered {
+            a: u8,
+            b: u64,
+            c: u16,
+            d: String,
+            e: u32,
+        }
+
+        assert!(size_of::<Archived<Reordered>>() < size_of::<Archived<Unordered>>());
+        assert_eq!(
+            align_of::<Archived<Reordered>>(),
+            align_of::<Archived<Unordered>>()
+        );
+
+        // Fields without a known alignment come first, then the rest from largest to smallest
+        let archived = MaybeUninit::<Archived<Reordered>>::uninit();
+        let base = archived.as_ptr();
+        let offset = |field: *const u8| field as usize - base as usize;
+        unsafe {
+            assert_eq!(offset(core::ptr::addr_of!((*base).d).cast()), 0);
+            assert!(
+                offset(core::ptr::addr_of!((*base).b).cast())
+                    < offset(core::ptr::addr_of!((*base).e).cast())
+            );
+            assert!(
+                offset(core::ptr::addr_of!((*base).e).cast())
+                    < offset(core::ptr::addr_of!((*base).c).cast())
+            );
+            assert!(
+                offset(core::ptr::addr_of!((*base).c).cast())
+                    < offset(core::ptr::addr_of!((*base).a).cast())
+            );
+        }
+
+        test_archive(&Reordered {
+            a: 1,
+            b: 2,
+            c: 3,
+            d: "four".to_string(),
+            e: 5,
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_archived_visibility() {
+        mod inner {
+            #[cfg(not(feature = "std"))]
+            use alloc::string::String;
+            use rkyv::{archived_root, Archive, Serialize};
+
+            #[derive(Archive, Serialize)]
+            #[archive(vis = "pub")]
+            struct Secret {
+                pub value: u32,
+            }
+
+            #[derive(Archive, Serialize)]
+            #[archive(vis = "pub")]
+            pub(super) struct Internal {
+                pub name: String,
+            }
+
+            pub fn secret_bytes() -> rkyv::AlignedVec {
+                rkyv::to_bytes::<_, 256>(&Secret { value: 42 }).unwrap()
+            }
+
+            pub fn read_secret(bytes: &[u8]) -> &ArchivedSecret {
+                unsafe { archived_root::<Secret>(bytes) }
+            }
+        }
+
+        let bytes = inner::secret_bytes();
+        let archived: &inner::ArchivedSecret = inner::read_secret(&bytes);
+        assert_eq!(archived.value, 42);
+
+        let bytes = rkyv::to_bytes::<_, 256>(&inner::Internal {
+            name: "hello".to_string(),
+        })
+        .unwrap();
+        let archived: &inner::ArchivedInternal =
+            unsafe { archived_root::<inner::Internal>(&bytes) };
+        assert_eq!(archived.name, "hello");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_resolver_name_and_visibility() {
+        mod inner {
+            #[cfg(not(feature = "std"))]
+            use alloc::string::String;
+            use rkyv::{Archive, Fallible, Serialize};
+
+            #[allow(dead_code)]
+            #[derive(Archive, Serialize)]
+            #[archive(resolver = "HttpResolver", resolver_vis = "pub")]
+            struct Http {
+                pub host: String,
+            }
+
+            #[derive(Archive)]
+            #[archive(resolver = "HttpsResolver", resolver_vis = "pub(super)")]
+            pub(super) struct Https {
+                pub host: String,
+            }
+
+            impl<S: Fallible + ?Sized> Serialize<S> for Https
+            where
+                String: Serialize<S>,
+            {
+                fn serialize(&self, serializer: &mut S) -> Result<HttpsResolver, S::Error> {
+                    Ok(HttpsResolver {
+                        host: self.host.serialize(serializer)?,
+                    })
+                }
+            }
+
+            pub fn http_resolver_name() -> &'static str {
+                core::any::type_name::<HttpResolver>()
+            }
+        }
+
+        assert!(inner::http_resolver_name().ends_with("HttpResolver"));
+        let _: Option<inner::HttpsResolver> = None;
+
+        let bytes = rkyv::to_bytes::<_, 256>(&inner::Https {
+            host: "example.com".to_string(),
+        })
+        .unwrap();
+        let archived = unsafe { archived_root::<inner::Https>(&bytes) };
+        assert_eq!(archived.host, "example.com");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_enum_tag_accessors() {
+        #[derive(Archive, Serialize, Deserialize)]
+        enum Message {
+            Ping,
+            Text(String),
+            Move { x: i32, y: i32 },
+            HTTPRequest(u16, String),
+        }
+
+        let value = vec![
+            Message::Ping,
+            Message::Text("hello".to_string()),
+            Message::Move { x: 1, y: -2 },
+            Message::HTTPRequest(200, "ok".to_string()),
+        ];
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Vec<Message>>(buf.as_ref()) };
+
+        let tags = archived.iter().map(|m| m.tag()).collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            [
+                ArchivedMessageTag::Ping,
+                ArchivedMessageTag::Text,
+                ArchivedMessageTag::Move,
+                ArchivedMessageTag::HTTPRequest,
+            ]
+        );
+
+        assert!(archived[0].is_ping());
+        assert!(!archived[0].is_text());
+
+        assert!(archived[1].is_text());
+        assert_eq!(archived[1].as_text().map(|s| s.as_str()), Some("hello"));
+        assert!(archived[0].as_text().is_none());
+
+        assert!(archived[2].is_move());
+        let (x, y) = archived[2].as_move().unwrap();
+        assert_eq!(
+            (rkyv::from_archived!(*x), rkyv::from_archived!(*y)),
+            (1, -2)
+        );
+        assert!(archived[1].as_move().is_none());
+
+        assert!(archived[3].is_http_request());
+        let (status, body) = archived[3].as_http_request().unwrap();
+        assert_eq!(*status, 200);
+        assert_eq!(body, "ok");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_cfg_fields() {
+        // Fields that are compiled out are removed before the derive sees them, so their types
+        // don't need to be archivable
+        #[allow(dead_code)]
+        struct NotArchive;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(accessors)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            a: u32,
+            #[cfg(not(test))]
+            b: NotArchive,
+            #[cfg(test)]
+            c: String,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        enum Event {
+            Tick {
+                #[cfg(not(test))]
+                source: NotArchive,
+                count: u32,
+            },
+        }
+
+        let value = Test {
+            a: 42,
+            c: "hello".to_string(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+        assert_eq!(archived.a(), &42);
+        assert_eq!(archived.c(), "hello");
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        let value = Event::Tick { count: 3 };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Event>(buf.as_ref()) };
+        assert!(archived.is_tick());
+        match archived {
+            ArchivedEvent::Tick { count } => assert_eq!(*count, 3),
+        }
+        let deserialized: Event = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_field_attributes() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Named {
+            #[archive_attr(doc(hidden))]
+            id: u32,
+            #[archive_attr(doc = "The name of the entry")]
+            #[archive_attr(allow(missing_docs))]
+            name: String,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Unnamed(#[archive_attr(doc(hidden))] u32, String);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        enum Entry {
+            Named {
+                #[archive_attr(doc(hidden))]
+                id: u32,
+            },
+            Unnamed(#[archive_attr(doc(hidden))] String),
+        }
+
+        test_archive(&Named {
+            id: 42,
+            name: "hello".to_string(),
+        });
+        test_archive(&Unnamed(42, "hello".to_string()));
+        test_archive(&Entry::Named { id: 42 });
+        test_archive(&Entry::Unnamed("hello".to_string()));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_attributes() {
+        #[derive(Archive, Debug, PartialEq)]
+        #[archive(archived = "ATest", resolver = "RTest", compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            a: i32,
+            b: Option<u32>,
+            c: String,
+            d: Vec<i32>,
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for Test
+        where
+            i32: Serialize<S>,
+            Option<u32>: Serialize<S>,
+            String: Serialize<S>,
+            Vec<i32>: Serialize<S>,
+        {
+            fn serialize(&self, serializer: &mut S) -> Result<RTest, S::Error> {
+                Ok(RTest {
+                    a: self.a.serialize(serializer)?,
+                    b: self.b.serialize(serializer)?,
+                    c: self.c.serialize(serializer)?,
+                    d: self.d.serialize(serializer)?,
+                })
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<Test, D> for ATest
+        where
+            Archived<i32>: Deserialize<i32, D>,
+            Archived<Option<u32>>: Deserialize<Option<u32>, D>,
+            Archived<String>: Deserialize<String, D>,
+            Archived<Vec<i32>>: Deserialize<Vec<i32>, D>,
+        {
+            fn deserialize(&self, deserializer: &mut D) -> Result<Test, D::Error> {
+                Ok(Test {
+                    a: self.a.deserialize(deserializer)?,
+                    b: self.b.deserialize(deserializer)?,
+                    c: self.c.deserialize(deserializer)?,
+                    d: self.d.deserialize(deserializer)?,
+                })
+            }
+        }
+
+        let value = Test {
+            a: 42,
+            b: Some(12),
+            c: "hello world".to_string(),
+            d: vec![1, 2, 3, 4],
+        };
+
+        test_archive(&value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn compare() {
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive(compare(PartialEq, PartialOrd))]
+        pub struct UnitFoo;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive(compare(PartialEq, PartialOrd))]
+        pub struct TupleFoo(i32);
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive(compare(PartialEq, PartialOrd))]
+        pub struct StructFoo {
+            t: i32,
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive(compare(PartialEq, PartialOrd))]
+        pub enum EnumFoo {
+            #[allow(dead_code)]
+            Foo(i32),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn default_type_parameters() {
+        #[derive(Archive, Serialize, Deserialize)]
+        pub struct TupleFoo<T = i32>(T);
+
+        #[derive(Archive, Serialize, Deserialize)]
+        pub struct StructFoo<T = i32> {
+            t: T,
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        pub enum EnumFoo<T = i32> {
+            #[allow(dead_code)]
+            T(T),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_util_bounds() {
+        fn check<T: Serializer>() {}
+
+        check::<BufferSerializer<[u8; 256]>>();
+        check::<BufferSerializer<&mut [u8; 256]>>();
+        check::<BufferSerializer<&mut [u8]>>();
+        check::<BufferSerializer<AlignedBytes<256>>>();
+        check::<BufferSerializer<&mut AlignedBytes<256>>>();
+        check::<AlignedSerializer<AlignedVec>>();
+        check::<AlignedSerializer<&mut AlignedVec>>();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn buffer_serializer_zeroes_padding() {
+        use ::core::mem::size_of;
+
+        #[derive(Archive, Serialize)]
+        pub struct PaddedExample {
+            a: u8,
+            b: u64,
+        }
+        let mut serializer = BufferSerializer::<[u8; 256]>::new([0xccu8; 256]);
+        serializer
+            .serialize_value(&PaddedExample { a: 0u8, b: 0u64 })
+            .unwrap();
+        let bytes = serializer.into_inner();
+        assert!(bytes[0..size_of::<Archived<PaddedExample>>()]
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn buffer_serializer_overflow() {
+        let value = "a string that is too long for the buffer".to_string();
+        let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 32]));
+        let prefix = serializer.serialize_value(&42u32).unwrap();
+        let error = serializer.serialize_value(&value).unwrap_err();
+        assert!(error.required_len() > error.available_len());
+        assert_eq!(error.available_len(), 32);
+        assert!(error
+            .to_string()
+            .contains(&error.required_len().to_string()));
+
+        // The new buffer must hold the bytes that were already written
+        let pos = serializer.pos();
+        assert_eq!(
+            serializer
+                .move_to_buffer([0u8; 2])
+                .map(|_| ())
+                .unwrap_err()
+                .required_len(),
+            pos
+        );
+
+        // The written bytes are kept after a failed move
+        let mut serializer = serializer.move_to_buffer(AlignedBytes([0u8; 256])).unwrap();
+        let pos = serializer.serialize_value(&value).unwrap();
+        assert!(serializer.pos() >= error.required_len());
+
+        let buf = serializer.into_inner();
+        unsafe {
+            assert_eq!(*rkyv::archived_value::<u32>(buf.as_ref(), prefix), 42);
+            assert_eq!(
+                rkyv::archived_value::<String>(buf.as_ref(), pos).as_str(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn const_generics() {
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        pub struct Const<const N: usize>;
+
+        test_archive(&Const::<1>);
+        test_archive(&Const::<2>);
+        test_archive(&Const::<3>);
+
+        #[derive(Archive, Deserialize, Serialize)]
+        pub struct Array<T, const N: usize>([T; N]);
+    }
+
+    #[test]
+    #[cfg(any(
+        not(any(feature = "archive_le", feature = "archive_be")),
+        feature = "arbitrary_enum_discriminant"
+    ))]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn arbitrary_enum_discriminant() {
+        use rkyv::Infallible;
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive_attr(derive(Debug, PartialEq))]
+        #[rustfmt::skip]
+        enum ReallyBigEnum {
+            V00, V01, V02, V03, V04, V05, V06, V07, V08, V09, V0A, V0B, V0C, V0D, V0E, V0F,
+            V10, V11, V12, V13, V14, V15, V16, V17, V18, V19, V1A, V1B, V1C, V1D, V1E, V1F,
+            V20, V21, V22, V23, V24, V25, V26, V27, V28, V29, V2A, V2B, V2C, V2D, V2E, V2F,
+            V30, V31, V32, V33, V34, V35, V36, V37, V38, V39, V3A, V3B, V3C, V3D, V3E, V3F,
+            V40, V41, V42, V43, V44, V45, V46, V47, V48, V49, V4A, V4B, V4C, V4D, V4E, V4F,
+            V50, V51, V52, V53, V54, V55, V56, V57, V58, V59, V5A, V5B, V5C, V5D, V5E, V5F,
+            V60, V61, V62, V63, V64, V65, V66, V67, V68, V69, V6A, V6B, V6C, V6D, V6E, V6F,
+            V70, V71, V72, V73, V74, V75, V76, V77, V78, V79, V7A, V7B, V7C, V7D, V7E, V7F,
+            V80, V81, V82, V83, V84, V85, V86, V87, V88, V89, V8A, V8B, V8C, V8D, V8E, V8F,
+            V90, V91, V92, V93, V94, V95, V96, V97, V98, V99, V9A, V9B, V9C, V9D, V9E, V9F,
+            VA0, VA1, VA2, VA3, VA4, VA5, VA6, VA7, VA8, VA9, VAA, VAB, VAC, VAD, VAE, VAF,
+            VB0, VB1, VB2, VB3, VB4, VB5, VB6, VB7, VB8, VB9, VBA, VBB, VBC, VBD, VBE, VBF,
+            VC0, VC1, VC2, VC3, VC4, VC5, VC6, VC7, VC8, VC9, VCA, VCB, VCC, VCD, VCE, VCF,
+            VD0, VD1, VD2, VD3, VD4, VD5, VD6, VD7, VD8, VD9, VDA, VDB, VDC, VDD, VDE, VDF,
+            VE0, VE1, VE2, VE3, VE4, VE5, VE6, VE7, VE8, VE9, VEA, VEB, VEC, VED, VEE, VEF,
+            VF0, VF1, VF2, VF3, VF4, VF5, VF6, VF7, VF8, VF9, VFA, VFB, VFC, VFD, VFE, VFF,
+            V100,
+        }
+
+        assert_eq!(ReallyBigEnum::V100 as u16, 0x100u16);
+
+        #[cfg(not(any(
+            all(target_endian = "little", feature = "archive_be"),
+            all(target_endian = "big", feature = "archive_le")
+        )))]
+        assert_eq!(ArchivedReallyBigEnum::V100 as u16, 0x100u16);
+        #[cfg(any(
+            all(target_endian = "little", feature = "archive_be"),
+            all(target_endian = "big", feature = "archive_le")
+        ))]
+        assert_eq!(ArchivedReallyBigEnum::V100 as u16, 0x1u16);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&ReallyBigEnum::V100).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+
+        let archived = unsafe { archived_root::<ReallyBigEnum>(buf.as_ref()) };
+        assert_eq!(archived, &ArchivedReallyBigEnum::V100);
+
+        let deserialized: ReallyBigEnum = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, ReallyBigEnum::V100);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[cfg(not(feature = "strict"))]
+    fn repr_rust() {
+        #[derive(Archive)]
+        pub struct Test {
+            a: u8,
+            b: u16,
+            c: u8,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedTest>(), 4);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn repr_transparent() {
+        #[derive(Archive)]
+        #[archive_attr(repr(transparent))]
+        pub struct Test {
+            a: u32,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedTest>(), 4);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn repr_c() {
+        #[derive(Archive)]
+        #[archive_attr(repr(C))]
+        pub struct TestStruct {
+            a: u8,
+            b: u16,
+            c: u8,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedTestStruct>(), 6);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn repr_int() {
+        #[derive(Archive)]
+        #[allow(dead_code)]
+        pub enum InferredRepr {
+            V0,
+            V1,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedInferredRepr>(), 1);
+
+        #[derive(Archive)]
+        #[archive_attr(repr(u16))]
+        #[allow(dead_code)]
+        pub enum ExplicitRepr {
+            V0,
+            V1,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedExplicitRepr>(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn repr_c_packed() {
+        #[derive(Archive)]
+        #[archive_attr(repr(C, packed))]
+        struct CPackedRepr {
+            a: u8,
+            b: u32,
+            c: u8,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedCPackedRepr>(), 6);
+
+        #[derive(Archive)]
+        #[archive_attr(repr(C))]
+        #[archive_attr(repr(packed))]
+        struct CPackedRepr2 {
+            a: u8,
+            b: u32,
+            c: u8,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedCPackedRepr2>(), 6);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn repr_c_align() {
+        #[derive(Archive)]
+        #[archive_attr(repr(C, align(8)))]
+        struct CAlignRepr {
+            a: u8,
+        }
+
+        assert_eq!(core::mem::align_of::<ArchivedCAlignRepr>(), 8);
+
+        #[derive(Archive)]
+        #[archive_attr(repr(C))]
+        #[archive_attr(repr(align(8)))]
+        struct CAlignRepr2 {
+            a: u8,
+        }
+
+        assert_eq!(core::mem::align_of::<ArchivedCAlignRepr>(), 8);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_as() {
+        // Struct
+
+        #[derive(Archive, Serialize, Deserialize, Debug)]
+        #[archive(as = "ExampleStruct<T::Archived>")]
+        #[repr(transparent)]
+        struct ExampleStruct<T> {
+            value: T,
+        }
+
+        impl<T: PartialEq<U>, U> PartialEq<ExampleStruct<U>> for ExampleStruct<T> {
+            fn eq(&self, other: &ExampleStruct<U>) -> bool {
+                self.value.eq(&other.value)
+            }
+        }
+
+        let value = ExampleStruct {
+            value: "hello world".to_string(),
+        };
+
+        test_archive(&value);
+
+        // Tuple struct
+
+        #[derive(Archive, Serialize, Deserialize, Debug)]
+        #[archive(as = "ExampleTupleStruct<T::Archived>")]
+        #[repr(transparent)]
+        struct ExampleTupleStruct<T>(T);
+
+        impl<T: PartialEq<U>, U> PartialEq<ExampleTupleStruct<U>> for ExampleTupleStruct<T> {
+            fn eq(&self, other: &ExampleTupleStruct<U>) -> bool {
+                self.0.eq(&other.0)
+            }
+        }
+
+        let value = ExampleTupleStruct("hello world".to_string());
+
+        test_archive(&value);
+
+        // Unit struct
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(as = "ExampleUnitStruct")]
+        struct ExampleUnitStruct;
+
+        test_archive(&ExampleUnitStruct);
+
+        // Enum
+
+        #[derive(Archive, Serialize, Deserialize, Debug)]
+        #[archive(as = "ExampleEnum<T::Archived>")]
+        #[repr(u8)]
+        enum ExampleEnum<T> {
+            A(T),
+            B,
+        }
+
+        impl<T: PartialEq<U>, U> PartialEq<ExampleEnum<U>> for ExampleEnum<T> {
+            fn eq(&self, other: &ExampleEnum<U>) -> bool {
+                match self {
+                    ExampleEnum::A(value) => {
+                        if let ExampleEnum::A(other) = other {
+                            value.eq(other)
+                        } else {
+                            false
+                        }
+                    }
+                    ExampleEnum::B => {
+                        if let ExampleEnum::B = other {
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                }
+            }
+        }
+
+        let value = ExampleEnum::A("hello world".to_string());
+
+        test_archive(&value);
+    }
+
+    mod with {
+        #[cfg(not(feature = "std"))]
+        use alloc::string::{String, ToString};
+        use core::str::FromStr;
+        use rkyv::{
+            archived_root,
+            ser::serializers::AlignedSerializer,
+            ser::Serializer,
+            with::{ArchiveWith, DeserializeWith, SerializeWith},
+            AlignedVec, Archive, Archived, Deserialize, Fallible, Infallible, Serialize,
+        };
+
+        #[cfg(feature = "wasm")]
         use wasm_bindgen_test::*;
 
-        struct ConvertToString;
+        struct ConvertToString;
+
+        impl<T: ToString> ArchiveWith<T> for ConvertToString {
+            type Archived = <String as Archive>::Archived;
+            type Resolver = <String as Archive>::Resolver;
+
+            unsafe fn resolve_with(
+                value: &T,
+                pos: usize,
+                resolver: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                value.to_string().resolve(pos, resolver, out);
+            }
+        }
+
+        impl<T: ToString, S: Serializer + ?Sized> SerializeWith<T, S> for ConvertToString {
+            fn serialize_with(value: &T, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(value.to_string().serialize(serializer)?)
+            }
+        }
+
+        impl<T: FromStr, D: Fallible + ?Sized> DeserializeWith<Archived<String>, T, D> for ConvertToString
+        where
+            <T as FromStr>::Err: core::fmt::Debug,
+        {
+            fn deserialize_with(value: &Archived<String>, _: &mut D) -> Result<T, D::Error> {
+                Ok(T::from_str(value.as_str()).unwrap())
+            }
+        }
+
+        #[test]
+        #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+        fn with_struct() {
+            #[derive(Archive, Serialize, Deserialize)]
+            struct Test {
+                #[with(ConvertToString)]
+                value: i32,
+                other: i32,
+            }
+
+            let value = Test {
+                value: 10,
+                other: 10,
+            };
+            let mut serializer = AlignedSerializer::new(AlignedVec::new());
+            serializer.serialize_value(&value).unwrap();
+            let result = serializer.into_inner();
+            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+            assert_eq!(archived.value, "10");
+            assert_eq!(archived.other, 10);
+
+            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+            assert_eq!(deserialized.value, 10);
+            assert_eq!(deserialized.other, 10);
+        }
+
+        #[test]
+        #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+        fn with_tuple_struct() {
+            #[derive(Archive, Serialize, Deserialize)]
+            struct Test(#[with(ConvertToString)] i32, i32);
+
+            let value = Test(10, 10);
+            let mut serializer = AlignedSerializer::new(AlignedVec::new());
+            serializer.serialize_value(&value).unwrap();
+            let result = serializer.into_inner();
+            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+            assert_eq!(archived.0, "10");
+            assert_eq!(archived.1, 10);
+
+            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+            assert_eq!(deserialized.0, 10);
+            assert_eq!(deserialized.1, 10);
+        }
+
+        #[test]
+        #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+        fn with_enum() {
+            #[derive(Archive, Serialize, Deserialize)]
+            enum Test {
+                A {
+                    #[with(ConvertToString)]
+                    value: i32,
+                    other: i32,
+                },
+                B(#[with(ConvertToString)] i32, i32),
+            }
+
+            let value = Test::A {
+                value: 10,
+                other: 10,
+            };
+            let mut serializer = AlignedSerializer::new(AlignedVec::new());
+            serializer.serialize_value(&value).unwrap();
+            let result = serializer.into_inner();
+            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+            if let ArchivedTest::A { value, other } = archived {
+                assert_eq!(*value, "10");
+                assert_eq!(*other, 10);
+            } else {
+                panic!("expected variant A");
+            };
+
+            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+            if let Test::A { value, other } = &deserialized {
+                assert_eq!(*value, 10);
+                assert_eq!(*other, 10);
+            } else {
+                panic!("expected variant A");
+            };
+
+            let value = Test::B(10, 10);
+            let mut serializer = AlignedSerializer::new(AlignedVec::new());
+            serializer.serialize_value(&value).unwrap();
+            let result = serializer.into_inner();
+            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+            if let ArchivedTest::B(value, other) = archived {
+                assert_eq!(*value, "10");
+                assert_eq!(*other, 10);
+            } else {
+                panic!("expected variant B");
+            };
+
+            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+            if let Test::B(value, other) = &deserialized {
+                assert_eq!(*value, 10);
+                assert_eq!(*other, 10);
+            } else {
+                panic!("expected variant B");
+            };
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_atomic() {
+        use core::sync::atomic::{AtomicU32, Ordering};
+        use rkyv::with::Atomic;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            #[with(Atomic)]
+            value: AtomicU32,
+        }
+
+        let value = Test {
+            value: AtomicU32::new(42),
+        };
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&value).unwrap();
+        let mut result = serializer.into_inner();
+        // NOTE: with(Atomic) is only sound if the backing memory is mutable, use with caution!
+        let archived = unsafe { archived_root_mut::<Test>(Pin::new(result.as_mut_slice())) };
+
+        assert_eq!(archived.value.load(Ordering::Relaxed), 42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_inline() {
+        use rkyv::with::Inline;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test<'a> {
+            #[with(Inline)]
+            value: &'a i32,
+        }
+
+        let a = 42;
+        let value = Test { value: &a };
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.value, 42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_box() {
+        use rkyv::with::AsBox;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            #[with(AsBox)]
+            value: i32,
+        }
+
+        let value = Test { value: 42 };
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.value.get(), &42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_ref_as_box() {
+        use rkyv::with::RefAsBox;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test<'a> {
+            #[with(RefAsBox)]
+            value: &'a str,
+        }
+
+        let a = "hello world";
+        let value = Test { value: &a };
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.value.as_ref(), "hello world");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_columns() {
+        use rkyv::with::{AsColumns, AsOwned};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(columnar)]
+        struct Row<'a, T> {
+            id: u32,
+            #[archive(rename = "label")]
+            name: String,
+            #[with(AsOwned)]
+            tag: Cow<'a, str>,
+            value: T,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Table<'a> {
+            #[with(AsColumns)]
+            rows: Vec<Row<'a, Option<i64>>>,
+            #[with(AsColumns)]
+            empty: Vec<Row<'a, bool>>,
+        }
+
+        let value = Table {
+            rows: (0..10)
+                .map(|i| Row {
+                    id: i,
+                    name: "row ".to_string() + &i.to_string(),
+                    tag: Cow::Borrowed(if i % 2 == 0 { "even" } else { "odd" }),
+                    value: if i % 3 == 0 { None } else { Some(-(i as i64)) },
+                })
+                .collect(),
+            empty: Vec::new(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Table>(buf.as_ref()) };
+
+        assert_eq!(archived.rows.len(), 10);
+        assert!(!archived.rows.is_empty());
+        assert_eq!(archived.rows.id.as_slice(), (0..10).collect::<Vec<_>>());
+        assert_eq!(archived.rows.label[3], "row 3");
+        assert_eq!(archived.rows.tag[4], "even");
+        assert_eq!(archived.rows.value[1], Some(-1));
+        assert_eq!(archived.rows.value[3], None::<i64>);
+        assert!(archived.empty.is_empty());
+        assert!(archived.empty.id.is_empty());
+
+        let deserialized: Table = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_owned() {
+        use rkyv::with::AsOwned;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test<'a> {
+            #[with(AsOwned)]
+            a: Cow<'a, u32>,
+            #[with(AsOwned)]
+            b: Cow<'a, [u32]>,
+            #[with(AsOwned)]
+            c: Cow<'a, str>,
+        }
+
+        let value = Test {
+            a: Cow::Borrowed(&100),
+            b: Cow::Borrowed(&[1, 2, 3, 4, 5, 6]),
+            c: Cow::Borrowed("hello world"),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.a, 100);
+        assert_eq!(archived.b, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(archived.c, "hello world");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_error_string() {
+        use rkyv::with::AsErrorString;
+        use std::{error::Error, fmt};
+
+        #[derive(Debug)]
+        struct TestError(i32);
+
+        impl fmt::Display for TestError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "test error {}", self.0)
+            }
+        }
+
+        impl Error for TestError {}
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            #[with(AsErrorString)]
+            a: Box<dyn Error>,
+            #[with(AsErrorString)]
+            b: Box<dyn Error + Send + Sync>,
+        }
+
+        let value = Test {
+            a: Box::new(TestError(42)),
+            b: "a much longer error message".into(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.a, "test error 42");
+        assert_eq!(archived.b, "a much longer error message");
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.a.to_string(), "test error 42");
+        assert_eq!(deserialized.b.to_string(), "a much longer error message");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_vec() {
+        #[cfg(not(feature = "std"))]
+        use alloc::collections::{BTreeMap, BTreeSet};
+        use rkyv::{collections::util::Entry, with::AsVec};
+        #[cfg(feature = "std")]
+        use std::collections::{BTreeMap, BTreeSet};
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            #[with(AsVec)]
+            a: BTreeMap<String, String>,
+            #[with(AsVec)]
+            b: BTreeSet<String>,
+            #[with(AsVec)]
+            c: BTreeMap<String, String>,
+        }
+
+        let mut a = BTreeMap::new();
+        a.insert("foo".to_string(), "hello".to_string());
+        a.insert("bar".to_string(), "world".to_string());
+        a.insert("baz".to_string(), "bat".to_string());
+
+        let mut b = BTreeSet::new();
+        b.insert("foo".to_string());
+        b.insert("hello world!".to_string());
+        b.insert("bar".to_string());
+        b.insert("fizzbuzz".to_string());
+
+        let c = BTreeMap::new();
+
+        let value = Test { a, b, c };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.a.len(), 3);
+        assert!(archived
+            .a
+            .iter()
+            .find(|&e| e
+                == &Entry {
+                    key: "foo",
+                    value: "hello"
+                })
+            .is_some());
+        assert!(archived
+            .a
+            .iter()
+            .find(|&e| e
+                == &Entry {
+                    key: "bar",
+                    value: "world"
+                })
+            .is_some());
+        assert!(archived
+            .a
+            .iter()
+            .find(|&e| e
+                == &Entry {
+                    key: "baz",
+                    value: "bat"
+                })
+            .is_some());
 
-        impl<T: ToString> ArchiveWith<T> for ConvertToString {
-            type Archived = <String as Archive>::Archived;
-            type Resolver = <String as Archive>::Resolver;
+        assert_eq!(archived.b.len(), 4);
+        assert!(archived.b.iter().find(|&e| e == "foo").is_some());
+        assert!(archived.b.iter().find(|&e| e == "hello world!").is_some());
+        assert!(archived.b.iter().find(|&e| e == "bar").is_some());
+        assert!(archived.b.iter().find(|&e| e == "fizzbuzz").is_some());
+    }
 
-            unsafe fn resolve_with(
-                value: &T,
-                pos: usize,
-                resolver: Self::Resolver,
-                out: *mut Self::Archived,
-            ) {
-                value.to_string().resolve(pos, resolver, out);
-            }
-        }
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_bit_set() {
+        use rkyv::with::AsBitSet;
 
-        impl<T: ToString, S: Serializer + ?Sized> SerializeWith<T, S> for ConvertToString {
-            fn serialize_with(value: &T, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
-                Ok(value.to_string().serialize(serializer)?)
-            }
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            #[with(AsBitSet)]
+            a: Vec<bool>,
+            #[with(AsBitSet)]
+            b: Vec<bool>,
         }
 
-        impl<T: FromStr, D: Fallible + ?Sized> DeserializeWith<Archived<String>, T, D> for ConvertToString
-        where
-            <T as FromStr>::Err: core::fmt::Debug,
-        {
-            fn deserialize_with(value: &Archived<String>, _: &mut D) -> Result<T, D::Error> {
-                Ok(T::from_str(value.as_str()).unwrap())
-            }
-        }
+        let a = (0..70).map(|i| i % 3 == 0).collect::<Vec<_>>();
+        let value = Test { a, b: Vec::new() };
 
-        #[test]
-        #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-        fn with_struct() {
-            #[derive(Archive, Serialize, Deserialize)]
-            struct Test {
-                #[with(ConvertToString)]
-                value: i32,
-                other: i32,
-            }
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
 
-            let value = Test {
-                value: 10,
-                other: 10,
-            };
-            let mut serializer = AlignedSerializer::new(AlignedVec::new());
-            serializer.serialize_value(&value).unwrap();
-            let result = serializer.into_inner();
-            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        assert_eq!(archived.a.len(), 70);
+        assert_eq!(archived.a.as_raw_blocks().len(), 3);
+        assert_eq!(archived.a.get(0), Some(true));
+        assert_eq!(archived.a.get(67), Some(false));
+        assert_eq!(archived.a.get(69), Some(true));
+        assert_eq!(archived.a.get(70), None);
+        assert_eq!(archived.a.count_ones(), 24);
+        assert_eq!(&archived.a, value.a.as_slice());
+        assert!(archived.b.is_empty());
 
-            assert_eq!(archived.value, "10");
-            assert_eq!(archived.other, 10);
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.a, value.a);
+        assert!(deserialized.b.is_empty());
+    }
 
-            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
-            assert_eq!(deserialized.value, 10);
-            assert_eq!(deserialized.other, 10);
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_delta() {
+        use rkyv::with::AsDelta;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(AsDelta)]
+            sorted: Vec<u64>,
+            #[with(AsDelta)]
+            extremes: Vec<u64>,
+            #[with(AsDelta)]
+            signed: Vec<i32>,
+            #[with(AsDelta)]
+            constant: Vec<u16>,
+            #[with(AsDelta)]
+            empty: Vec<u8>,
         }
 
-        #[test]
-        #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-        fn with_tuple_struct() {
-            #[derive(Archive, Serialize, Deserialize)]
-            struct Test(#[with(ConvertToString)] i32, i32);
+        let value = Test {
+            sorted: (0..200).map(|i| 1_000_000 + i * i).collect(),
+            extremes: vec![0, u64::MAX, 1, u64::MAX - 1, 12345],
+            signed: (-70..70).map(|i| i * 1000 - 7).collect(),
+            constant: vec![42; 65],
+            empty: Vec::new(),
+        };
 
-            let value = Test(10, 10);
-            let mut serializer = AlignedSerializer::new(AlignedVec::new());
-            serializer.serialize_value(&value).unwrap();
-            let result = serializer.into_inner();
-            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
 
-            assert_eq!(archived.0, "10");
-            assert_eq!(archived.1, 10);
+        assert_eq!(archived.sorted.len(), 200);
+        assert_eq!(archived.sorted.get(0), Some(1_000_000));
+        assert_eq!(archived.sorted.get(63), Some(1_003_969));
+        assert_eq!(archived.sorted.get(64), Some(1_004_096));
+        assert_eq!(archived.sorted.get(199), Some(1_039_601));
+        assert_eq!(archived.sorted.get(200), None);
+        assert_eq!(archived.sorted.block_width(0), 12);
+        assert_eq!(archived.sorted.binary_search(1_000_100), Ok(10));
+        assert_eq!(archived.sorted.binary_search(1_000_101), Err(11));
+        assert_eq!(archived.sorted.binary_search(0), Err(0));
+        assert_eq!(archived.sorted.binary_search(u64::MAX), Err(200));
+        assert_eq!(&archived.sorted, value.sorted.as_slice());
+
+        assert_eq!(archived.extremes.block_width(0), 64);
+        assert_eq!(&archived.extremes, value.extremes.as_slice());
+        assert_eq!(&archived.signed, value.signed.as_slice());
+        assert_eq!(archived.signed.binary_search(-7), Ok(70));
+        assert_eq!(archived.signed.iter().next_back(), Some(68_993));
+        assert_eq!(archived.constant.block_width(0), 0);
+        assert_eq!(archived.constant.block_width(1), 0);
+        assert_eq!(&archived.constant, value.constant.as_slice());
+        assert!(archived.empty.is_empty());
+        assert_eq!(archived.empty.iter().next(), None);
 
-            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
-            assert_eq!(deserialized.0, 10);
-            assert_eq!(deserialized.1, 10);
-        }
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
 
-        #[test]
-        #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-        fn with_enum() {
-            #[derive(Archive, Serialize, Deserialize)]
-            enum Test {
-                A {
-                    #[with(ConvertToString)]
-                    value: i32,
-                    other: i32,
-                },
-                B(#[with(ConvertToString)] i32, i32),
-            }
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_inline_string() {
+        use rkyv::with::AsInlineString;
 
-            let value = Test::A {
-                value: 10,
-                other: 10,
-            };
-            let mut serializer = AlignedSerializer::new(AlignedVec::new());
-            serializer.serialize_value(&value).unwrap();
-            let result = serializer.into_inner();
-            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(AsInlineString<8>)]
+            empty: String,
+            #[with(AsInlineString<8>)]
+            full: String,
+            #[with(AsInlineString<8>)]
+            long: String,
+            #[with(AsInlineString<16>)]
+            unicode: String,
+        }
 
-            if let ArchivedTest::A { value, other } = archived {
-                assert_eq!(*value, "10");
-                assert_eq!(*other, 10);
-            } else {
-                panic!("expected variant A");
-            };
+        let value = Test {
+            empty: String::new(),
+            full: "12345678".to_string(),
+            long: "123456789".to_string(),
+            unicode: "héllo wörld".to_string(),
+        };
 
-            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
-            if let Test::A { value, other } = &deserialized {
-                assert_eq!(*value, 10);
-                assert_eq!(*other, 10);
-            } else {
-                panic!("expected variant A");
-            };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
 
-            let value = Test::B(10, 10);
-            let mut serializer = AlignedSerializer::new(AlignedVec::new());
-            serializer.serialize_value(&value).unwrap();
-            let result = serializer.into_inner();
-            let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        assert!(archived.empty.is_inline());
+        assert!(archived.empty.is_empty());
+        assert!(archived.full.is_inline());
+        assert_eq!(archived.full, "12345678");
+        assert!(!archived.long.is_inline());
+        assert_eq!(archived.long, "123456789");
+        assert_eq!(archived.long.len(), 9);
+        assert!(archived.unicode.is_inline());
+        assert_eq!(archived.unicode, "héllo wörld");
 
-            if let ArchivedTest::B(value, other) = archived {
-                assert_eq!(*value, "10");
-                assert_eq!(*other, 10);
-            } else {
-                panic!("expected variant B");
-            };
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
 
-            let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
-            if let Test::B(value, other) = &deserialized {
-                assert_eq!(*value, 10);
-                assert_eq!(*other, 10);
-            } else {
-                panic!("expected variant B");
-            };
+    #[test]
+    #[cfg(feature = "size_16")]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_inline_string_out_of_range() {
+        use rkyv::{
+            rel_ptr::OffsetError, ser::serializers::CompositeSerializerError, with::AsInlineString,
+        };
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            #[with(AsInlineString<8>)]
+            value: String,
         }
+
+        // The bytes end up further away than a 16-bit offset can reach
+        let value = Test {
+            value: "a".repeat(40_000),
+        };
+        let mut serializer = DefaultSerializer::default();
+        assert!(matches!(
+            serializer.serialize_value(&value),
+            Err(CompositeSerializerError::OffsetError(
+                OffsetError::ExceedsStorageRange
+            ))
+        ));
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_atomic() {
-        use core::sync::atomic::{AtomicU32, Ordering};
-        use rkyv::with::Atomic;
+    fn with_nul_terminated() {
+        use rkyv::with::NulTerminated;
 
-        #[derive(Archive, Serialize, Deserialize)]
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
         struct Test {
-            #[with(Atomic)]
-            value: AtomicU32,
+            #[with(NulTerminated)]
+            a: String,
+            #[with(NulTerminated)]
+            empty: String,
+            #[with(NulTerminated)]
+            interior: String,
         }
 
         let value = Test {
-            value: AtomicU32::new(42),
+            a: "hello world".to_string(),
+            empty: String::new(),
+            interior: "a\0b".to_string(),
         };
-        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+
+        let mut serializer = DefaultSerializer::default();
         serializer.serialize_value(&value).unwrap();
-        let mut result = serializer.into_inner();
-        // NOTE: with(Atomic) is only sound if the backing memory is mutable, use with caution!
-        let archived = unsafe { archived_root_mut::<Test>(Pin::new(result.as_mut_slice())) };
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
 
-        assert_eq!(archived.value.load(Ordering::Relaxed), 42);
+        assert_eq!(archived.a, "hello world");
+        assert_eq!(archived.a.as_bytes_with_nul(), b"hello world\0");
+        assert!(archived.empty.is_empty());
+        assert_eq!(archived.empty.as_bytes_with_nul(), b"\0");
+        assert_eq!(archived.interior.len(), 3);
+        assert_eq!(archived.interior.as_bytes_with_nul(), b"a\0b\0");
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_inline() {
-        use rkyv::with::Inline;
+    fn archive_schema() {
+        use rkyv::{
+            schema::{ArchiveSchema, ArchivedValue, Schema},
+            with::AsBox,
+        };
 
-        #[derive(Archive, Serialize, Deserialize)]
-        struct Test<'a> {
-            #[with(Inline)]
-            value: &'a i32,
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        enum Event {
+            Tick,
+            Move { dx: i16, dy: i16 },
+            Say(String, #[with(AsBox)] u32),
         }
 
-        let a = 42;
-        let value = Test { value: &a };
-        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        struct Log<T> {
+            #[archive(rename = "label")]
+            name: String,
+            events: Vec<Event>,
+            last: Option<T>,
+            boxed: Box<[u8; 2]>,
+            flag: bool,
+        }
+
+        let value = Log {
+            name: "session".to_string(),
+            events: vec![
+                Event::Tick,
+                Event::Move { dx: -3, dy: 4 },
+                Event::Say("hi".to_string(), 7),
+            ],
+            last: Some(1.5f64),
+            boxed: Box::new([9, 10]),
+            flag: true,
+        };
+
+        let mut serializer = DefaultSerializer::default();
         serializer.serialize_value(&value).unwrap();
-        let result = serializer.into_inner();
-        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        let result = serializer.into_serializer().into_inner();
+        let root = unsafe { ArchivedValue::root::<Log<f64>>(result.as_slice()) };
+
+        match <Log<f64> as ArchiveSchema>::SCHEMA {
+            Schema::Struct {
+                name, size, fields, ..
+            } => {
+                assert_eq!(name, "ArchivedLog");
+                assert_eq!(size, core::mem::size_of::<Archived<Log<f64>>>());
+                assert_eq!(fields.len(), 5);
+                assert_eq!(fields[0].name, "label");
+            }
+            _ => panic!("expected a struct schema"),
+        }
 
-        assert_eq!(archived.value, 42);
+        assert_eq!(root.field("label").unwrap().as_str(), Some("session"));
+        assert!(root.field("name").is_none());
+        assert_eq!(root.field("flag").unwrap().as_bool(), Some(true));
+        assert_eq!(
+            root.field("last").unwrap().inner().unwrap().as_f64(),
+            Some(1.5)
+        );
+        let boxed = root.field("boxed").unwrap().inner().unwrap();
+        assert_eq!(boxed.len(), Some(2));
+        assert_eq!(boxed.get(1).unwrap().as_u64(), Some(10));
+        assert!(boxed.get(2).is_none());
+
+        let events = root.field("events").unwrap();
+        assert_eq!(events.len(), Some(3));
+
+        let tick = events.get(0).unwrap();
+        assert_eq!(tick.variant().unwrap().name, "Tick");
+        assert_eq!(tick.fields().unwrap().len(), 0);
+
+        let moved = events.get(1).unwrap();
+        assert_eq!(moved.variant().unwrap().name, "Move");
+        assert_eq!(moved.field("dx").unwrap().as_i64(), Some(-3));
+        assert_eq!(moved.field("dy").unwrap().as_i64(), Some(4));
+
+        let say = events.get(2).unwrap();
+        assert_eq!(say.field("0").unwrap().as_str(), Some("hi"));
+        let opaque = say.field("1").unwrap();
+        assert!(matches!(
+            opaque.schema(),
+            Schema::Opaque { name: "u32", .. }
+        ));
+        assert_eq!(opaque.as_u64(), None);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_as_box() {
-        use rkyv::with::AsBox;
+    fn hash_archived_values() {
+        use rkyv::schema::hash_archived;
+
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        struct Record {
+            id: u32,
+            tags: Vec<String>,
+            parent: Option<Box<u64>>,
+            size: usize,
+        }
+
+        fn hash(value: &Record, prefix: &str) -> u64 {
+            let mut serializer = DefaultSerializer::default();
+            // Serializing another value first moves the record to a different position
+            serializer.serialize_value(&prefix.to_string()).unwrap();
+            serializer.serialize_value(value).unwrap();
+            let result = serializer.into_serializer().into_inner();
+            hash_archived::<Record>(unsafe { archived_root::<Record>(result.as_slice()) })
+        }
+
+        let value = Record {
+            id: 1,
+            tags: vec!["alpha".to_string(), "beta is long enough".to_string()],
+            parent: Some(Box::new(7)),
+            size: 3,
+        };
+        let expected = hash(&value, "");
+        assert_eq!(hash(&value, "a prefix that is not inlined"), expected);
+        // The hash is the same on every platform and with every feature
+        assert_eq!(expected, 0x7abb_a473_75b2_6c5f);
+
+        let mut changed = Record {
+            tags: vec!["alpha".to_string(), "beta is long enough".to_string()],
+            parent: Some(Box::new(7)),
+            ..value
+        };
+        changed.id = 2;
+        assert_ne!(hash(&changed, ""), expected);
+        changed.id = 1;
+        changed.parent = None;
+        assert_ne!(hash(&changed, ""), expected);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn diff_archived_values() {
+        use rkyv::diff::{diff, diff_values};
+        use rkyv::schema::ArchivedValue;
 
-        #[derive(Archive, Serialize, Deserialize)]
-        struct Test {
-            #[with(AsBox)]
-            value: i32,
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        enum Shape {
+            Circle { radius: f32 },
+            Polygon(Vec<i32>),
         }
 
-        let value = Test { value: 42 };
-        let mut serializer = AlignedSerializer::new(AlignedVec::new());
-        serializer.serialize_value(&value).unwrap();
-        let result = serializer.into_inner();
-        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        struct Scene {
+            name: String,
+            shapes: Vec<Shape>,
+            focus: Option<Box<Shape>>,
+        }
 
-        assert_eq!(archived.value.get(), &42);
+        let a = Scene {
+            name: "demo".to_string(),
+            shapes: vec![Shape::Circle { radius: 1.0 }, Shape::Polygon(vec![1, 2, 3])],
+            focus: Some(Box::new(Shape::Polygon(vec![4]))),
+        };
+        let b = Scene {
+            name: "demo".to_string(),
+            shapes: vec![Shape::Polygon(vec![]), Shape::Polygon(vec![1, 5, 3])],
+            focus: Some(Box::new(Shape::Polygon(vec![4, 5]))),
+        };
+        let a = rkyv::to_bytes::<_, 256>(&a).unwrap();
+        let b = rkyv::to_bytes::<_, 256>(&b).unwrap();
+        let archived_a = unsafe { archived_root::<Scene>(a.as_slice()) };
+        let archived_b = unsafe { archived_root::<Scene>(b.as_slice()) };
+
+        let differences = diff::<Scene>(archived_a, archived_b);
+        assert_eq!(
+            differences
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            [
+                ".shapes[0]: Circle { radius: 1.0 } != Polygon { 0: [] }",
+                ".shapes[1].0[1]: 2 != 5",
+                ".focus.0: 1 elements != 2 elements",
+            ],
+        );
+        assert_eq!(differences[1].left.as_i64(), Some(2));
+        assert!(diff::<Scene>(archived_a, archived_a).is_empty());
+
+        // Values with different schemas differ at the root
+        let name = unsafe { ArchivedValue::root::<Scene>(a.as_slice()) }
+            .field("name")
+            .unwrap();
+        let shapes = unsafe { ArchivedValue::root::<Scene>(b.as_slice()) }
+            .field("shapes")
+            .unwrap();
+        let differences = diff_values(name, shapes);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].path, ".");
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_ref_as_box() {
-        use rkyv::with::RefAsBox;
+    fn inspect_archive() {
+        use core::mem::{align_of, size_of};
+        use rkyv::inspect::{hex_dump, Inspector};
 
-        #[derive(Archive, Serialize, Deserialize)]
-        struct Test<'a> {
-            #[with(RefAsBox)]
-            value: &'a str,
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        enum Node {
+            Leaf(u8),
+            Branch(Box<u32>, Option<u16>),
         }
 
-        let a = "hello world";
-        let value = Test { value: &a };
-        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        struct Tree {
+            nodes: Vec<Node>,
+        }
+
+        let value = Tree {
+            nodes: vec![Node::Leaf(1), Node::Branch(Box::new(42), None)],
+        };
+
+        let mut serializer = DefaultSerializer::default();
         serializer.serialize_value(&value).unwrap();
-        let result = serializer.into_inner();
-        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        let mut result = serializer.into_serializer().into_inner();
+
+        let inspector = Inspector::new::<Tree>(result.as_slice());
+        let output = inspector.to_string();
+        assert!(output.contains("[0]: ArchivedNode::Leaf"));
+        assert!(output.contains("[1]: ArchivedNode::Branch"));
+        assert!(output.contains("*: u32 = 42"));
+        assert!(output.contains("1: Option<u16> = None"));
+
+        // The boxed `u32` is serialized first and padded out to the alignment of the nodes
+        let boxed_len = size_of::<Archived<u32>>();
+        let node_size = size_of::<Archived<Node>>();
+        let node_align = align_of::<Archived<Node>>();
+        let padding = (node_align - boxed_len % node_align) % node_align;
+        let report = inspector.report();
+        assert_eq!(report.errors(), 0);
+        assert_eq!(
+            report.reachable_len(),
+            boxed_len + 2 * node_size + size_of::<Archived<Tree>>()
+        );
+        assert_eq!(report.reachable_len() + padding, result.len());
+        let unreferenced = report.unreferenced();
+        assert_eq!(unreferenced.iter().map(|r| r.len()).sum::<usize>(), padding);
+
+        let shallow = Inspector::new::<Tree>(result.as_slice()).max_depth(1);
+        assert!(shallow.to_string().contains("<max depth reached>"));
+
+        // Corrupt the tag of the second node, and then the offset of the vec
+        let root = result.len() - size_of::<Archived<Tree>>();
+        let archived = unsafe { archived_root::<Tree>(result.as_slice()) };
+        let nodes = archived.nodes.as_ptr() as usize - result.as_ptr() as usize;
+        result[nodes + node_size] = 0xff;
+        let report = Inspector::new::<Tree>(result.as_slice()).report();
+        assert_eq!(report.errors(), 1);
+        assert!(!report.unreferenced().is_empty());
+
+        for byte in result[root..].iter_mut() {
+            *byte = 0x7f;
+        }
+        let output = Inspector::new::<Tree>(result.as_slice()).to_string();
+        assert!(output.contains("<error: relative pointer target out of bounds"));
 
-        assert_eq!(archived.value.as_ref(), "hello world");
+        assert_eq!(
+            hex_dump(b"rkyv\x00", 0..16).to_string(),
+            "0x0000  72 6b 79 76 00                                    |rkyv.|"
+        );
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_as_owned() {
-        use rkyv::with::AsOwned;
+    fn zero_archive_padding() {
+        use rkyv::inspect::zero_padding;
 
-        #[derive(Archive, Serialize, Deserialize)]
-        struct Test<'a> {
-            #[with(AsOwned)]
-            a: Cow<'a, u32>,
-            #[with(AsOwned)]
-            b: Cow<'a, [u32]>,
-            #[with(AsOwned)]
-            c: Cow<'a, str>,
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        enum Token {
+            Empty,
+            Key { kind: u8, secret: u64 },
         }
 
-        let value = Test {
-            a: Cow::Borrowed(&100),
-            b: Cow::Borrowed(&[1, 2, 3, 4, 5, 6]),
-            c: Cow::Borrowed("hello world"),
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        #[archive_attr(repr(C))]
+        struct Keyring {
+            version: u16,
+            tokens: Vec<Token>,
+        }
+
+        let value = Keyring {
+            version: 3,
+            tokens: vec![
+                Token::Empty,
+                Token::Key {
+                    kind: 1,
+                    secret: u64::MAX,
+                },
+            ],
         };
-        let mut serializer = DefaultSerializer::default();
-        serializer.serialize_value(&value).unwrap();
-        let result = serializer.into_serializer().into_inner();
-        let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        let expected = rkyv::to_bytes_zero_padded::<_, 256>(&value).unwrap();
+        let mut bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        assert_eq!(bytes.as_slice(), expected.as_slice());
+
+        // Fill every padding byte as if it had been copied from memory
+        let regions = zero_padding::<Keyring>(&mut bytes)
+            .padding_regions()
+            .to_vec();
+        assert!(!regions.is_empty());
+        for range in regions.iter() {
+            bytes[range.clone()].fill(0xaa);
+        }
+        assert_ne!(bytes.as_slice(), expected.as_slice());
 
-        assert_eq!(archived.a, 100);
-        assert_eq!(archived.b, [1, 2, 3, 4, 5, 6]);
-        assert_eq!(archived.c, "hello world");
+        let report = zero_padding::<Keyring>(&mut bytes);
+        assert_eq!(report.padding_regions(), regions.as_slice());
+        assert_eq!(report.errors(), 0);
+        assert_eq!(bytes.as_slice(), expected.as_slice());
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_as_vec() {
-        #[cfg(not(feature = "std"))]
-        use alloc::collections::{BTreeMap, BTreeSet};
-        use rkyv::{collections::util::Entry, with::AsVec};
-        #[cfg(feature = "std")]
-        use std::collections::{BTreeMap, BTreeSet};
+    fn with_as_dictionary() {
+        use rkyv::with::AsDictionary;
 
         #[derive(Archive, Serialize, Deserialize)]
         struct Test {
-            #[with(AsVec)]
-            a: BTreeMap<String, String>,
-            #[with(AsVec)]
-            b: BTreeSet<String>,
-            #[with(AsVec)]
-            c: BTreeMap<String, String>,
+            #[with(AsDictionary)]
+            a: Vec<String>,
+            #[with(AsDictionary)]
+            b: Vec<String>,
         }
 
-        let mut a = BTreeMap::new();
-        a.insert("foo".to_string(), "hello".to_string());
-        a.insert("bar".to_string(), "world".to_string());
-        a.insert("baz".to_string(), "bat".to_string());
-
-        let mut b = BTreeSet::new();
-        b.insert("foo".to_string());
-        b.insert("hello world!".to_string());
-        b.insert("bar".to_string());
-        b.insert("fizzbuzz".to_string());
-
-        let c = BTreeMap::new();
-
-        let value = Test { a, b, c };
+        let hosts = ["alpha", "beta", "gamma"];
+        let a = (0..100)
+            .map(|i| hosts[i * i % 3].to_string())
+            .collect::<Vec<_>>();
+        let value = Test { a, b: Vec::new() };
 
         let mut serializer = DefaultSerializer::default();
         serializer.serialize_value(&value).unwrap();
         let result = serializer.into_serializer().into_inner();
         let archived = unsafe { archived_root::<Test>(result.as_slice()) };
 
-        assert_eq!(archived.a.len(), 3);
-        assert!(archived
-            .a
-            .iter()
-            .find(|&e| e
-                == &Entry {
-                    key: "foo",
-                    value: "hello"
-                })
-            .is_some());
-        assert!(archived
-            .a
-            .iter()
-            .find(|&e| e
-                == &Entry {
-                    key: "bar",
-                    value: "world"
-                })
-            .is_some());
-        assert!(archived
-            .a
-            .iter()
-            .find(|&e| e
-                == &Entry {
-                    key: "baz",
-                    value: "bat"
-                })
-            .is_some());
+        assert_eq!(archived.a.len(), 100);
+        assert_eq!(archived.a.dictionary().len(), 2);
+        assert_eq!(archived.a.dictionary()[0], "alpha");
+        assert_eq!(archived.a.dictionary()[1], "beta");
+        assert_eq!(archived.a.get(2), Some("beta"));
+        assert_eq!(archived.a.get(100), None);
+        assert_eq!(archived.a.code(3), Some(0));
+        assert_eq!(archived.a.code_of("beta"), Some(1));
+        assert_eq!(archived.a.code_of("gamma"), None);
+        assert_eq!(&archived.a[99], "alpha");
+        assert_eq!(&archived.a, value.a.as_slice());
+        assert_eq!(archived.a.iter().next_back(), Some("alpha"));
+        assert!(archived.b.is_empty());
+        assert!(archived.b.dictionary().is_empty());
 
-        assert_eq!(archived.b.len(), 4);
-        assert!(archived.b.iter().find(|&e| e == "foo").is_some());
-        assert!(archived.b.iter().find(|&e| e == "hello world!").is_some());
-        assert!(archived.b.iter().find(|&e| e == "bar").is_some());
-        assert!(archived.b.iter().find(|&e| e == "fizzbuzz").is_some());
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.a, value.a);
+        assert!(deserialized.b.is_empty());
     }
 
     #[test]
@@ -1958,6 +4683,68 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_crate_path_facade() {
+        mod facade {
+            pub use ::rkyv as serialization;
+        }
+
+        use facade::serialization::{self, fingerprint::Fingerprint, versioned::Version};
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive(crate = "facade::serialization", fingerprint, version = 2)]
+        struct Record {
+            name: String,
+            #[with(serialization::with::Inline)]
+            parent: &'static u32,
+        }
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive(crate = "facade::serialization", extensible)]
+        struct Settings {
+            volume: u8,
+            theme: String,
+        }
+
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        #[archive(crate = "facade::serialization", compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        #[repr(u8)]
+        enum Status {
+            Active = 3,
+            Retired = 9,
+        }
+
+        assert_eq!(Record::VERSION, 2);
+        assert_ne!(Record::FINGERPRINT, 0);
+
+        static PARENT: u32 = 7;
+        let record = Record {
+            name: "root".to_string(),
+            parent: &PARENT,
+        };
+        let bytes = serialization::to_bytes::<_, 256>(&record).unwrap();
+        let archived = unsafe { archived_root::<Record>(&bytes) };
+        assert_eq!(archived.name, "root");
+        assert_eq!(archived.parent, 7);
+
+        let settings = Settings {
+            volume: 4,
+            theme: "dark".to_string(),
+        };
+        let bytes = serialization::to_bytes::<_, 256>(&settings).unwrap();
+        let archived = unsafe { archived_root::<Settings>(&bytes) };
+        assert_eq!(archived.volume(), Some(&4));
+        let deserialized: Settings = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, settings);
+
+        let bytes = serialization::to_bytes::<_, 256>(&Status::Retired).unwrap();
+        let archived = unsafe { archived_root::<Status>(&bytes) };
+        assert_eq!(*archived, Status::Retired);
+        assert_eq!(Status::Active as u8, 3);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_btree_map() {
@@ -2163,4 +4950,295 @@ mod tests {
         assert_eq!(tracker.max_allocations(), 1);
         assert_ne!(tracker.min_buffer_size(), 0);
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn alloc_scratch_reuses_blocks() {
+        use core::alloc::Layout;
+        use rkyv::ser::{
+            serializers::{
+                AlignedSerializer, AllocScratch, AllocScratchError, CompositeSerializer,
+            },
+            ScratchSpace,
+        };
+
+        let value = (0..200)
+            .map(|i| (0..i % 17).map(|j| (i * j).to_string()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::<AlignedVec>::default(),
+            AllocScratch::default(),
+            Infallible,
+        );
+        serializer.serialize_value(&value).unwrap();
+        let (serializer, scratch, _) = serializer.into_components();
+        let bytes = serializer.into_inner();
+
+        // The largest scratch allocation is for the resolvers of the outer vec, and every block is
+        // at least twice as large as the last, so this bounds the number of blocks allocated
+        let outer = 200 * core::mem::size_of::<usize>();
+        assert!(scratch.capacity() <= 4 * outer + 4096);
+
+        let archived = unsafe { archived_root::<Vec<Vec<String>>>(&bytes) };
+        assert_eq!(archived.len(), value.len());
+        for (archived, value) in archived.iter().zip(value.iter()) {
+            assert_eq!(archived.as_slice(), value.as_slice());
+        }
+
+        let mut scratch = AllocScratch::new();
+        unsafe {
+            let small = Layout::from_size_align(16, 8).unwrap();
+            let large = Layout::from_size_align(10_000, 16).unwrap();
+            let a = scratch.push_scratch(small).unwrap().cast::<u8>();
+            let b = scratch.push_scratch(large).unwrap().cast::<u8>();
+            assert_eq!(b.as_ptr() as usize % 16, 0);
+            assert!(matches!(
+                scratch.pop_scratch(a, small),
+                Err(AllocScratchError::NotPoppedInReverseOrder { .. })
+            ));
+            scratch.pop_scratch(b, large).unwrap();
+            let capacity = scratch.capacity();
+
+            // Popped blocks are reused instead of being freed
+            let c = scratch.push_scratch(large).unwrap().cast::<u8>();
+            assert_eq!(c, b);
+            assert_eq!(scratch.capacity(), capacity);
+            scratch.pop_scratch(c, large).unwrap();
+            scratch.pop_scratch(a, small).unwrap();
+            assert!(matches!(
+                scratch.pop_scratch(a, small),
+                Err(AllocScratchError::NoAllocationsToPop)
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn stats_serializer() {
+        use rkyv::ser::serializers::{AllocSerializer, StatsSerializer};
+
+        let mut serializer = StatsSerializer::new(AllocSerializer::<256>::default());
+        serializer.serialize_value(&42u8).unwrap();
+        serializer.serialize_value(&42u32).unwrap();
+        let stats = serializer.stats();
+        assert_eq!(stats.bytes_written, 8);
+        assert_eq!(stats.padding_bytes, 3);
+        assert_eq!(stats.scratch_allocations, 0);
+        assert_eq!(stats.peak_scratch_bytes, 0);
+
+        let shared = Rc::new("a shared string value".to_string());
+        let value = vec![
+            vec![shared.clone(), shared.clone()],
+            vec![shared.clone()],
+            vec![Rc::new("another string".to_string())],
+        ];
+        let mut serializer = StatsSerializer::new(AllocSerializer::<256>::default());
+        serializer.serialize_value(&value).unwrap();
+        let (serializer, stats) = serializer.into_parts();
+        let bytes = serializer.into_serializer().into_inner();
+        assert_eq!(stats.bytes_written, bytes.len());
+        assert_eq!(stats.shared_values, 2);
+        assert_eq!(stats.scratch_allocations, 4);
+        assert_eq!(stats.peak_scratch_allocations, 2);
+        assert_ne!(stats.peak_scratch_bytes, 0);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn progress_serializer() {
+        #[cfg(not(feature = "std"))]
+        use alloc::format;
+        use core::ops::ControlFlow;
+        use rkyv::ser::serializers::{AllocSerializer, ProgressError, ProgressSerializer};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Chunk {
+            name: String,
+            data: Vec<u32>,
+            shared: Rc<String>,
+        }
+
+        let shared = Rc::new("a shared string value".to_string());
+        let value = (0..16)
+            .map(|i| Chunk {
+                name: format!("chunk {}", i),
+                data: (0..64).collect(),
+                shared: shared.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut reports = Vec::new();
+        let mut serializer =
+            ProgressSerializer::new(AllocSerializer::<256>::default(), 512, |pos| {
+                reports.push(pos);
+                ControlFlow::Continue(())
+            });
+        serializer.serialize_value(&value).unwrap();
+        let bytes = serializer.into_inner().into_serializer().into_inner();
+        assert_eq!(reports.len(), bytes.len() / 512);
+        assert!(reports.windows(2).all(|w| w[1] - w[0] >= 512));
+        let archived = unsafe { archived_root::<Vec<Chunk>>(bytes.as_slice()) };
+        let deserialized: Vec<Chunk> = archived
+            .deserialize(&mut DefaultDeserializer::default())
+            .unwrap();
+        assert_eq!(deserialized, value);
+
+        let mut calls = 0;
+        let mut serializer =
+            ProgressSerializer::new(AllocSerializer::<256>::default(), 512, |_| {
+                calls += 1;
+                if calls == 3 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+        match serializer.serialize_value(&value) {
+            Err(ProgressError::Cancelled { pos }) => assert!(pos >= 3 * 512),
+            result => panic!("expected cancellation, got {:?}", result),
+        }
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_owned_archive() {
+        #[derive(Archive, Serialize)]
+        #[archive(compare(PartialEq))]
+        struct Test {
+            id: u32,
+            values: Vec<u32>,
+        }
+
+        let value = Test {
+            id: 42,
+            values: vec![1, 2, 3],
+        };
+
+        let mut archive = rkyv::to_archive::<_, 256>(&value).unwrap();
+        assert!(*archive == value);
+        assert_eq!(
+            archive.as_bytes(),
+            rkyv::to_bytes::<_, 256>(&value).unwrap().as_slice()
+        );
+
+        let copy = archive.clone();
+        unsafe {
+            archive
+                .pin_mut()
+                .map_unchecked_mut(|t| &mut t.values)
+                .index_pin(1)
+                .set(rkyv::to_archived!(5u32));
+        }
+        assert_eq!(archive.values.as_slice(), &[1, 5, 3]);
+        assert_eq!(copy.values.as_slice(), &[1, 2, 3]);
+
+        let bytes = archive.into_bytes();
+        let archived = unsafe { archived_root::<Test>(bytes.as_ref()) };
+        assert_eq!(archived.id, 42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_unsized_tail() {
+        use rkyv::archived_unsized_root;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(unsized)]
+        struct Packet {
+            id: u32,
+            flags: u8,
+            payload: Vec<u16>,
+        }
+
+        let value = Packet {
+            id: 42,
+            flags: 3,
+            payload: vec![1, 2, 3],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_unsized_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_unsized_root::<Packet>(buf.as_ref()) };
+
+        assert_eq!(archived.id, 42);
+        assert_eq!(archived.flags, 3);
+        assert_eq!(archived.payload, [1, 2, 3]);
+        // The payload follows the header directly instead of through a relative pointer
+        assert_eq!(core::mem::size_of_val(archived), 12);
+        assert_eq!(
+            archived.payload.as_ptr() as usize - archived as *const _ as *const u8 as usize,
+            6
+        );
+
+        let deserialized: Packet = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        let empty = Packet {
+            id: 1,
+            flags: 0,
+            payload: Vec::new(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_unsized_value(&empty).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_unsized_root::<Packet>(buf.as_ref()) };
+        assert_eq!(archived.id, 1);
+        assert!(archived.payload.is_empty());
+        assert_eq!(core::mem::size_of_val(archived), 8);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_unsized_tail_in_box() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(unsized)]
+        struct Named<T> {
+            value: T,
+            #[archive(rename = "label")]
+            name: String,
+        }
+
+        // Unsized structs can only be archived behind a pointer
+        #[allow(clippy::vec_box)]
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            first: Box<Named<i32>>,
+            rest: Vec<Box<Named<i32>>>,
+        }
+
+        let value = Test {
+            first: Box::new(Named {
+                value: 1,
+                name: "first".to_string(),
+            }),
+            rest: vec![
+                Box::new(Named {
+                    value: 2,
+                    name: String::new(),
+                }),
+                Box::new(Named {
+                    value: 3,
+                    name: "third".to_string(),
+                }),
+            ],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.first.value, 1);
+        assert_eq!(&archived.first.label, "first");
+        assert_eq!(archived.rest.len(), 2);
+        assert_eq!(&archived.rest[0].label, "");
+        assert_eq!(archived.rest[1].value, 3);
+        assert_eq!(&archived.rest[1].label, "third");
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
 }
@@ -27,6 +27,101 @@ mod tests {
             .expect_err("serialized to an undersized buffer must fail");
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn buffered_write_serializer() {
+        use rkyv::{
+            ser::serializers::{AllocScratch, BufferedWriteSerializer, CompositeSerializer},
+            Infallible,
+        };
+        use std::io::{self, IoSlice};
+
+        // Writes at most 5 bytes per call, and counts the calls
+        #[derive(Default)]
+        struct SlowWriter {
+            bytes: Vec<u8>,
+            calls: usize,
+        }
+
+        impl io::Write for SlowWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.write_vectored(&[IoSlice::new(buf)])
+            }
+
+            fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+                self.calls += 1;
+                let mut written = 0;
+                for buf in bufs {
+                    let len = usize::min(buf.len(), 5 - written);
+                    self.bytes.extend_from_slice(&buf[..len]);
+                    written += len;
+                }
+                Ok(written)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let value = (0..20)
+            .map(|i| (i, i.to_string().repeat(i)))
+            .collect::<HashMap<_, _>>();
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let expected = serializer.into_serializer().into_inner();
+
+        let mut serializer = CompositeSerializer::new(
+            WriteSerializer::new(SlowWriter::default()),
+            AllocScratch::default(),
+            Infallible,
+        );
+        serializer.serialize_value(&value).unwrap();
+        let unbuffered = serializer.into_serializer().into_inner();
+        assert_eq!(unbuffered.bytes, expected.as_slice());
+
+        let mut serializer = CompositeSerializer::new(
+            BufferedWriteSerializer::with_capacity(64, SlowWriter::default()),
+            AllocScratch::default(),
+            Infallible,
+        );
+        serializer.serialize_value(&value).unwrap();
+        assert_eq!(serializer.pos(), expected.len());
+        let buffered = serializer.into_serializer().into_inner().unwrap();
+        assert_eq!(buffered.bytes, expected.as_slice());
+        assert!(buffered.calls < unbuffered.calls);
+
+        let mut serializer = BufferedWriteSerializer::with_capacity(4, SlowWriter::default());
+        serializer.write(&[1, 2, 3]).unwrap();
+        serializer.pad(10).unwrap();
+        serializer
+            .write_vectored(&[&[4, 5], &[], &[6, 7, 8, 9, 10, 11]])
+            .unwrap();
+        serializer.write(&[12]).unwrap();
+        assert_eq!(serializer.pos(), 22);
+        assert_eq!(serializer.buffered(), 1);
+        let mut expected = vec![1, 2, 3];
+        expected.extend_from_slice(&[0; 10]);
+        expected.extend_from_slice(&[4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        assert_eq!(serializer.into_inner().unwrap().bytes, expected);
+
+        let mut bytes = Vec::new();
+        let mut serializer = BufferedWriteSerializer::new(&mut bytes);
+        serializer.write(&[1, 2, 3]).unwrap();
+        assert_eq!(serializer.buffered(), 3);
+        drop(serializer);
+        assert_eq!(bytes, [1, 2, 3]);
+
+        let mut serializer = WriteSerializer::new(SlowWriter::default());
+        serializer
+            .write_vectored(&[&[1, 2, 3], &[4, 5, 6, 7]])
+            .unwrap();
+        serializer.pad(40).unwrap();
+        assert_eq!(serializer.pos(), 47);
+        assert_eq!(serializer.into_inner().bytes[..7], [1, 2, 3, 4, 5, 6, 7]);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_hash_map() {
@@ -66,6 +161,159 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_hash_map_into_iter() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert("hello".to_string(), 1);
+        hash_map.insert("foo".to_string(), 2);
+        hash_map.insert("baz".to_string(), 3);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<HashMap<String, i32>>(buf.as_ref()) };
+
+        let mut count = 0;
+        for (key, value) in archived_value {
+            assert_eq!(&hash_map[key.as_str()], value);
+            count += 1;
+        }
+        assert_eq!(count, hash_map.len());
+        assert!(archived_value.into_iter().eq(archived_value.iter()));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_hash_map_index() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert("hello".to_string(), 1);
+        hash_map.insert("foo".to_string(), 2);
+        hash_map.insert("baz".to_string(), 3);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<HashMap<String, i32>>(buf.as_ref()) };
+
+        // Indices follow iteration order
+        for (i, (key, value)) in archived_value.iter().enumerate() {
+            assert_eq!(archived_value.get_index(i), Some((key, value)));
+            assert_eq!(archived_value.get_index_of(key.as_str()), Some(i));
+        }
+
+        for key in hash_map.keys() {
+            let index = archived_value.get_index_of(key.as_str()).unwrap();
+            let (archived_key, archived_value) = archived_value.get_index(index).unwrap();
+            assert_eq!(archived_key, key);
+            assert_eq!(archived_value, &hash_map[key]);
+        }
+
+        assert_eq!(archived_value.get_index(hash_map.len()), None);
+        assert_eq!(archived_value.get_index_of("missing"), None);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_empty_hash_map() {
+        let hash_map = HashMap::<String, u32>::new();
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<HashMap<String, u32>>(buf.as_ref()) };
+
+        assert!(archived_value.is_empty());
+        assert_eq!(archived_value.get("missing"), None);
+        assert!(!archived_value.contains_key("missing"));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_hash_map_equivalent_keys() {
+        use rkyv::{collections::util::Equivalent, from_archived, to_archived};
+
+        // Lookups that only rely on `Borrow` keep working
+        #[cfg(not(any(feature = "archive_le", feature = "archive_be")))]
+        fn lookup<'a, K, Q, V>(
+            map: &'a rkyv::collections::ArchivedHashMap<K, V>,
+            key: &Q,
+        ) -> Option<&'a V>
+        where
+            K: core::borrow::Borrow<Q>,
+            Q: core::hash::Hash + Eq + ?Sized,
+        {
+            map.get(key)
+        }
+
+        let mut hash_map = HashMap::new();
+        hash_map.insert("hello".to_string(), 1u64);
+        hash_map.insert("foo".to_string(), 2u64);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<HashMap<String, u64>>(buf.as_ref()) };
+
+        assert_eq!(
+            archived_value.get("hello").map(|&v| from_archived!(v)),
+            Some(1)
+        );
+        assert!(archived_value.contains_key("foo"));
+        assert_eq!(archived_value.get("missing"), None);
+        assert_eq!(from_archived!(archived_value["foo"]), 2);
+        #[cfg(not(any(feature = "archive_le", feature = "archive_be")))]
+        assert_eq!(lookup(archived_value, "foo"), Some(&2));
+
+        let mut hash_map = HashMap::new();
+        hash_map.insert(5u64, 10u32);
+        hash_map.insert(7u64, 20u32);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<HashMap<u64, u32>>(buf.as_ref()) };
+
+        assert_eq!(
+            archived_value.get(&to_archived!(5u64)),
+            Some(&to_archived!(10u32))
+        );
+        assert_eq!(archived_value.get(&to_archived!(6u64)), None);
+        assert_eq!(archived_value[&to_archived!(7u64)], to_archived!(20u32));
+
+        // Native integers are equivalent to archived ones with any endianness
+        assert_eq!(
+            archived_value.get(&5u64).map(|&v| from_archived!(v)),
+            Some(10)
+        );
+        assert!(!archived_value.contains_key(&6u64));
+        assert_eq!(from_archived!(archived_value[&7u64]), 20);
+
+        let mut hash_map = HashMap::new();
+        hash_map.insert(-5i128, 1u8);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<HashMap<i128, u8>>(buf.as_ref()) };
+
+        assert_eq!(archived_value.get(&-5i128), Some(&1));
+        assert!(!archived_value.contains_key(&5i128));
+
+        let mut hash_map = HashMap::new();
+        hash_map.insert('x', vec![1u8, 2, 3]);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived_value = unsafe { archived_root::<HashMap<char, Vec<u8>>>(buf.as_ref()) };
+
+        let (key, value) = archived_value.get_key_value(&'x').unwrap();
+        assert!('x'.equivalent(key));
+        assert!([1u8, 2, 3][..].equivalent(value));
+        assert!(!archived_value.contains_key(&'y'));
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     #[allow(deprecated)]
@@ -213,6 +461,44 @@ mod tests {
         test_archive(&value);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn display_archived() {
+        use std::net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV6};
+
+        #[derive(Archive, Serialize)]
+        struct Entry {
+            name: String,
+            count: u32,
+            ratio: f64,
+            ip: IpAddr,
+            sock: SocketAddr,
+        }
+
+        let value = Entry {
+            name: "server".to_string(),
+            count: 42,
+            ratio: 0.5,
+            ip: IpAddr::V6(Ipv6Addr::LOCALHOST),
+            sock: SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0)),
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = unsafe { archived_root::<Entry>(bytes.as_ref()) };
+
+        assert_eq!(
+            format!(
+                "{} {:>4} {:.2} {} {}",
+                archived.name, archived.count, archived.ratio, archived.ip, archived.sock
+            ),
+            format!(
+                "{} {:>4} {:.2} {} {}",
+                value.name, value.count, value.ratio, value.ip, value.sock
+            ),
+        );
+        assert_eq!(format!("{:<8}|", archived.name), "server  |");
+        assert_eq!(archived.sock.to_string(), "[::1]:8080");
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn c_string() {
@@ -222,61 +508,201 @@ mod tests {
         test_archive(&value);
     }
 
-    // TODO: figure out errors
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_map_hash_map_values() {
+        use rkyv::{
+            to_archived,
+            with::{Inline, Map},
+            Infallible,
+        };
+        use std::borrow::Cow;
 
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn mutex() {
-    //     use rkyv::with::Lock;
-    //     use std::sync::Mutex;
+        #[derive(Archive, Serialize)]
+        struct Refs<'a> {
+            #[with(Map<Inline>)]
+            values: HashMap<u32, &'a String>,
+        }
 
-    //     #[derive(Archive, Serialize, Deserialize)]
-    //     struct Test {
-    //         #[with(Lock)]
-    //         value: Mutex<i32>,
-    //     }
+        let strings = ["zero".to_string(), "one".to_string()];
+        let value = Refs {
+            values: strings
+                .iter()
+                .enumerate()
+                .map(|(i, s)| (i as u32, s))
+                .collect(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Refs>(buf.as_ref()) };
+        assert_eq!(archived.values.len(), 2);
+        assert_eq!(archived.values.get(&to_archived!(1u32)).unwrap(), "one");
 
-    //     let value = Test {
-    //         value: Mutex::new(10),
-    //     };
-    //     let mut serializer = AlignedSerializer::new(AlignedVec::new());
-    //     serializer.serialize_value(&value).unwrap();
-    //     let result = serializer.into_inner();
-    //     let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Owned<'a> {
+            #[with(Map<rkyv::with::AsOwned>)]
+            values: HashMap<String, Cow<'a, str>>,
+        }
 
-    //     assert_eq!(*archived.value, 10);
+        let value = Owned {
+            values: (0..10)
+                .map(|i| (i.to_string(), Cow::Owned(format!("value {}", i))))
+                .collect(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Owned>(buf.as_ref()) };
+        assert_eq!(archived.values.get("3").unwrap(), "value 3");
 
-    //     let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        let deserialized: Owned = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
 
-    //     assert_eq!(*deserialized.value.lock().unwrap(), 10);
-    // }
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_sorted_vec() {
+        use rkyv::{with::AsSortedVec, Infallible};
+        use std::collections::BTreeMap;
 
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn rwlock() {
-    //     use rkyv::with::Lock;
-    //     use std::sync::RwLock;
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(AsSortedVec)]
+            map: HashMap<String, u32>,
+            #[with(AsSortedVec)]
+            set: HashSet<i32>,
+            #[with(AsSortedVec)]
+            tree: BTreeMap<u8, String>,
+            #[with(AsSortedVec)]
+            empty: HashMap<u32, u32>,
+        }
 
-    //     #[derive(Archive, Serialize, Deserialize)]
-    //     struct Test {
-    //         #[with(Lock)]
-    //         value: RwLock<i32>,
-    //     }
+        let value = Test {
+            map: (0..100).map(|i| (format!("key {}", i), i)).collect(),
+            set: (-50..50).map(|i| i * 7 % 31).collect(),
+            tree: (0..10).map(|i| (i, i.to_string())).collect(),
+            empty: HashMap::new(),
+        };
 
-    //     let value = Test {
-    //         value: RwLock::new(10),
-    //     };
-    //     let mut serializer = AlignedSerializer::new(AlignedVec::new());
-    //     serializer.serialize_value(&value).unwrap();
-    //     let result = serializer.into_inner();
-    //     let archived = unsafe { archived_root::<Test>(result.as_slice()) };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.map.len(), 100);
+        assert!(archived.map.windows(2).all(|w| w[0].key < w[1].key));
+        let index = archived
+            .map
+            .binary_search_by(|e| e.key.as_str().cmp("key 42"))
+            .unwrap();
+        assert_eq!(archived.map[index].value, 42);
+
+        assert_eq!(archived.set.len(), value.set.len());
+        assert!(archived.set.windows(2).all(|w| w[0] < w[1]));
+        assert!(archived
+            .set
+            .binary_search(&rkyv::to_archived!(7i32))
+            .is_ok());
+
+        assert!(archived.tree.windows(2).all(|w| w[0].key < w[1].key));
+        assert!(archived.empty.is_empty());
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
 
-    //     assert_eq!(*archived.value, 10);
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn lock() {
+        use core::cell::RefCell;
+        use rkyv::{
+            ser::serializers::AlignedSerializer,
+            with::{Lock, LockError},
+            AlignedVec, Fallible, Infallible,
+        };
+        use std::sync::{Mutex, RwLock};
 
-    //     let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        // `Lock` requires a serializer with an error type that `LockError` converts into
+        struct LockSerializer(AlignedSerializer<AlignedVec>);
 
-    //     assert_eq!(*deserialized.value.read().unwrap(), 10);
-    // }
+        #[derive(Debug)]
+        enum LockSerializerError {
+            Inner,
+            Lock,
+        }
+
+        impl From<LockError> for LockSerializerError {
+            fn from(_: LockError) -> Self {
+                Self::Lock
+            }
+        }
+
+        impl Fallible for LockSerializer {
+            type Error = LockSerializerError;
+        }
+
+        impl Serializer for LockSerializer {
+            fn pos(&self) -> usize {
+                self.0.pos()
+            }
+
+            fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+                self.0.write(bytes).map_err(|_| LockSerializerError::Inner)
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            #[with(Lock)]
+            mutex: Mutex<i32>,
+            #[with(Lock)]
+            rwlock: RwLock<[u8; 4]>,
+            cell: RefCell<u32>,
+        }
+
+        let value = Test {
+            mutex: Mutex::new(10),
+            rwlock: RwLock::new([1, 2, 3, 4]),
+            cell: RefCell::new(20),
+        };
+        let mut serializer = LockSerializer(AlignedSerializer::new(AlignedVec::new()));
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.0.into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_slice()) };
+
+        assert_eq!(*archived.mutex, 10);
+        assert_eq!(*archived.rwlock, [1, 2, 3, 4]);
+        assert_eq!(archived.cell, 20);
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+
+        assert_eq!(*deserialized.mutex.lock().unwrap(), 10);
+        assert_eq!(*deserialized.rwlock.read().unwrap(), [1, 2, 3, 4]);
+        assert_eq!(*deserialized.cell.borrow(), 20);
+
+        // Poisoned locks fail to serialize
+        #[cfg(not(feature = "wasm"))]
+        {
+            let poisoned = Mutex::new(10);
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let _guard = poisoned.lock().unwrap();
+                panic!("poison the mutex");
+            }));
+            let value = Test {
+                mutex: poisoned,
+                rwlock: RwLock::new([0; 4]),
+                cell: RefCell::new(0),
+            };
+            let mut serializer = LockSerializer(AlignedSerializer::new(AlignedVec::new()));
+            assert!(matches!(
+                serializer.serialize_value(&value),
+                Err(LockSerializerError::Lock)
+            ));
+        }
+    }
+
+    // TODO: figure out errors
 
     // #[test]
     // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
@@ -364,4 +790,118 @@ mod tests {
         value.insert(());
         test_archive(&value);
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_unsized_std_types() {
+        use rkyv::{with::RefAsBox, Infallible};
+        use std::{
+            ffi::{CStr, CString, OsStr},
+            path::Path,
+        };
+
+        let c_string = CString::new("hello").unwrap();
+        test_archive_container(&c_string.clone().into_boxed_c_str());
+        test_archive_container(&Box::<OsStr>::from(OsStr::new("hello")));
+        test_archive_container(&Box::<OsStr>::from(OsStr::new("")));
+        test_archive_container(&Box::<Path>::from(Path::new("/usr/lib/librkyv.so")));
+
+        #[derive(Archive, Serialize)]
+        struct Refs<'a> {
+            #[with(RefAsBox)]
+            c_str: &'a CStr,
+            #[with(RefAsBox)]
+            os_str: &'a OsStr,
+            #[with(RefAsBox)]
+            path: &'a Path,
+        }
+
+        let value = Refs {
+            c_str: c_string.as_c_str(),
+            os_str: OsStr::new("os string"),
+            path: Path::new("dir/file.txt"),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Refs>(buf.as_ref()) };
+        assert_eq!(archived.c_str.get(), value.c_str);
+        assert_eq!(archived.os_str.get(), value.os_str);
+        assert_eq!(archived.os_str.to_str(), Some("os string"));
+        assert_eq!(archived.path.get(), value.path);
+        assert_eq!(archived.path.to_path().extension(), Some(OsStr::new("txt")));
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Boxes {
+            os_str: Box<OsStr>,
+            path: Box<Path>,
+        }
+
+        let value = Boxes {
+            os_str: OsStr::new("os string").into(),
+            path: Path::new("dir/file.txt").into(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Boxes>(buf.as_ref()) };
+        assert_eq!(archived.os_str, value.os_str);
+        assert_eq!(archived.path, value.path);
+        let deserialized: Boxes = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_owned_os_str_and_path() {
+        use rkyv::{with::AsOwned, Infallible};
+        use std::{borrow::Cow, ffi::OsStr, path::Path};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test<'a> {
+            #[with(AsOwned)]
+            os_str: Cow<'a, OsStr>,
+            #[with(AsOwned)]
+            path: Cow<'a, Path>,
+        }
+
+        let value = Test {
+            os_str: Cow::Borrowed(OsStr::new("os string")),
+            path: Cow::Borrowed(Path::new("dir/file.txt")),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+        assert_eq!(archived.os_str.to_str(), Some("os string"));
+        assert_eq!(
+            archived.path.to_path().file_name(),
+            Some(OsStr::new("file.txt"))
+        );
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert!(matches!(deserialized.os_str, Cow::Owned(_)));
+        assert!(matches!(deserialized.path, Cow::Owned(_)));
+        assert_eq!(deserialized, value);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn archive_non_utf8_os_str() {
+        use rkyv::Infallible;
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+        let os_str = OsStr::from_bytes(b"invalid \xff utf-8");
+        test_archive_container(&Box::<OsStr>::from(os_str));
+
+        let value = Box::<Path>::from(Path::new(os_str));
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Box<Path>>(buf.as_ref()) };
+        assert_eq!(archived.as_os_str().as_bytes(), os_str.as_bytes());
+        assert_eq!(archived.to_str(), None);
+        let deserialized: Box<Path> = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
 }
@@ -78,10 +78,8 @@ pub mod core {
 
 #[cfg(feature = "alloc")]
 pub mod alloc {
-    const SCRATCH_SIZE: usize = 256;
-
-    pub type DefaultSerializer = rkyv::ser::serializers::AllocSerializer<SCRATCH_SIZE>;
-    pub type DefaultDeserializer = rkyv::de::deserializers::SharedDeserializeMap;
-
-    impl_test_archive!(DefaultSerializer, DefaultDeserializer);
+    pub use rkyv::test_util::{
+        test_archive, test_archive_container, test_archive_ref,
+        TestDeserializer as DefaultDeserializer, TestSerializer as DefaultSerializer,
+    };
 }
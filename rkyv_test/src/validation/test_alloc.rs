@@ -16,7 +16,10 @@ mod tests {
         Deserialize, Infallible, Serialize,
     };
     #[cfg(feature = "std")]
-    use std::{collections::{BTreeMap, BTreeSet}, rc::Rc};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        rc::Rc,
+    };
 
     #[cfg(feature = "wasm")]
     use wasm_bindgen_test::*;
@@ -357,6 +360,31 @@ mod tests {
         ])));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_enum_explicit_discriminants() {
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        #[repr(u8)]
+        enum Test {
+            A(u32) = 5,
+            B(String) = 2,
+        }
+
+        serialize_and_check(&Test::A(42));
+        serialize_and_check(&Test::B("hello world".to_string()));
+
+        let mut bytes = rkyv::to_bytes::<_, 256>(&Test::A(42)).unwrap();
+        let pos = bytes.len() - core::mem::size_of::<rkyv::Archived<Test>>();
+        assert_eq!(bytes[pos], 5);
+        check_archived_value::<Test>(bytes.as_ref(), pos).unwrap();
+        // Tags are checked against the discriminants, not the variant indices
+        bytes[pos] = 0;
+        assert!(check_archived_value::<Test>(bytes.as_ref(), pos).is_err());
+        bytes[pos] = 1;
+        assert!(check_archived_value::<Test>(bytes.as_ref(), pos).is_err());
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn recursive_type() {
@@ -403,6 +431,66 @@ mod tests {
         check_archived_root::<Test>(buf.as_ref()).unwrap();
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_unsized_shared_ptr() {
+        #[cfg(not(feature = "std"))]
+        use alloc::sync::Arc;
+        #[cfg(feature = "std")]
+        use std::sync::Arc;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            a: Arc<str>,
+            b: Arc<str>,
+            c: Rc<[u8]>,
+            d: Arc<[String]>,
+        }
+
+        let shared = Arc::<str>::from("hello world");
+        serialize_and_check(&Test {
+            a: shared.clone(),
+            b: shared,
+            c: Rc::from(&[1, 2, 3][..]),
+            d: Arc::from(vec!["a".to_string(), String::new()]),
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_as_columns() {
+        use rkyv::with::AsColumns;
+
+        #[derive(Archive, Serialize)]
+        #[archive(columnar)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Row {
+            id: u32,
+            name: String,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Table {
+            #[with(AsColumns)]
+            rows: Vec<Row>,
+        }
+
+        serialize_and_check(&Table {
+            rows: vec![
+                Row {
+                    id: 1,
+                    name: "a".to_string(),
+                },
+                Row {
+                    id: 2,
+                    name: "b".to_string(),
+                },
+            ],
+        });
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn check_b_tree() {
@@ -531,6 +619,421 @@ mod tests {
         check_archived_root::<Duration>(&[0xFF, 16]).unwrap_err();
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_chars() {
+        serialize_and_check(&'a');
+        serialize_and_check(&'\u{10ffff}');
+
+        #[cfg(any(
+            all(target_endian = "little", not(feature = "archive_be")),
+            feature = "archive_le"
+        ))]
+        const INVALID_CHARS: [[u8; 4]; 2] = [[0x00, 0xd8, 0x00, 0x00], [0x00, 0x00, 0x11, 0x00]];
+
+        #[cfg(any(
+            all(target_endian = "big", not(feature = "archive_le")),
+            feature = "archive_be"
+        ))]
+        const INVALID_CHARS: [[u8; 4]; 2] = [[0x00, 0x00, 0xd8, 0x00], [0x00, 0x11, 0x00, 0x00]];
+
+        // Surrogates and values past the end of the Unicode range are not valid chars
+        for bytes in INVALID_CHARS.iter() {
+            check_archived_root::<char>(&AlignedBytes(*bytes).0).unwrap_err();
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_nonzero_int128() {
+        use core::num::{NonZeroI128, NonZeroU128};
+
+        serialize_and_check(&NonZeroI128::new(-1).unwrap());
+        serialize_and_check(&NonZeroU128::new(u128::MAX).unwrap());
+
+        check_archived_root::<NonZeroI128>(&AlignedBytes([0u8; 16]).0).unwrap_err();
+        check_archived_root::<NonZeroU128>(&AlignedBytes([0u8; 16]).0).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_ordering() {
+        use core::cmp::Ordering;
+
+        serialize_and_check(&Ordering::Less);
+        serialize_and_check(&Ordering::Greater);
+
+        // Only the three ordering variants are valid
+        check_archived_root::<Ordering>(&[3]).unwrap_err();
+        check_archived_root::<Ordering>(&[0xff]).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_directory() {
+        use rkyv::directory::Directory;
+
+        let mut serializer = DefaultSerializer::default();
+        let mut directory = Directory::new();
+        directory
+            .serialize_root("header", &"hello world".to_string(), &mut serializer)
+            .unwrap();
+        directory
+            .serialize_root("payload", &vec![1u32, 2, 3, 4], &mut serializer)
+            .unwrap();
+        serializer.serialize_value(&directory).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+
+        let archived = check_archived_root::<Directory>(buf.as_ref()).unwrap();
+        let header = archived
+            .check_root::<String>(buf.as_ref(), "header")
+            .unwrap()
+            .unwrap();
+        assert_eq!(header, "hello world");
+        let payload = archived
+            .check_root_at::<Vec<u32>>(buf.as_ref(), 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(payload.as_slice(), [1, 2, 3, 4]);
+        assert!(archived
+            .check_root::<String>(buf.as_ref(), "missing")
+            .is_none());
+        assert!(archived.check_root_at::<String>(buf.as_ref(), 2).is_none());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_bit_set() {
+        use rkyv::with::AsBitSet;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(AsBitSet)]
+            bits: Vec<bool>,
+        }
+
+        serialize_and_check(&Test {
+            bits: vec![true, false, true, true],
+        });
+        serialize_and_check(&Test { bits: Vec::new() });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_delta_vec() {
+        use rkyv::with::AsDelta;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(AsDelta)]
+            values: Vec<i64>,
+        }
+
+        serialize_and_check(&Test {
+            values: (0..100).map(|i| i * 3 - 50).collect(),
+        });
+        serialize_and_check(&Test {
+            values: vec![i64::MIN, i64::MAX],
+        });
+        serialize_and_check(&Test { values: Vec::new() });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_dictionary() {
+        use rkyv::with::AsDictionary;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(AsDictionary)]
+            values: Vec<String>,
+        }
+
+        serialize_and_check(&Test {
+            values: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+        });
+        serialize_and_check(&Test { values: Vec::new() });
+
+        // Point the last code past the end of the dictionary
+        let mut buf = rkyv::to_bytes::<_, 256>(&Test {
+            values: vec!["a".to_string(), "b".to_string()],
+        })
+        .unwrap();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        let code = &archived.values.codes()[1] as *const _ as usize - buf.as_ptr() as usize;
+        buf[code] = 2;
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_inline_string() {
+        use rkyv::with::AsInlineString;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(AsInlineString<8>)]
+            short: String,
+            #[with(AsInlineString<8>)]
+            long: String,
+        }
+
+        let value = Test {
+            short: "abc".to_string(),
+            long: "a string that does not fit".to_string(),
+        };
+        serialize_and_check(&value);
+
+        // Replace the inline string with invalid UTF-8
+        let mut buf = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        let short = archived.short.as_ptr() as usize - buf.as_ptr() as usize;
+        buf[short] = 0xff;
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_nul_terminated() {
+        use rkyv::with::NulTerminated;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(NulTerminated)]
+            value: String,
+        }
+
+        let value = Test {
+            value: "abc".to_string(),
+        };
+        serialize_and_check(&value);
+
+        // Overwrite the trailing NUL
+        let mut buf = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        let nul = archived.value.as_ptr() as usize - buf.as_ptr() as usize + 3;
+        buf[nul] = b'd';
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_sub_archive() {
+        use core::mem::size_of;
+        use rkyv::{string::ArchivedString, sub_archive::SubArchive, vec::ArchivedVec};
+
+        let inner =
+            SubArchive::<Vec<String>>::from_value::<256>(&vec!["a".to_string(), "b".to_string()])
+                .unwrap();
+        let buf = rkyv::to_bytes::<_, 256>(&vec![inner.clone(), inner]).unwrap();
+        let archived = check_archived_root::<Vec<SubArchive<Vec<String>>>>(buf.as_ref()).unwrap();
+        assert_eq!(archived[1].root()[1], "b");
+
+        // Corrupt the root of the inner archive
+        let mut bytes = SubArchive::<Vec<String>>::from_value::<256>(&vec!["c".to_string()])
+            .unwrap()
+            .into_bytes();
+        let root_pos = bytes.len() - size_of::<ArchivedVec<ArchivedString>>();
+        bytes[root_pos..].iter_mut().for_each(|b| *b = 0xff);
+        let corrupt = unsafe { SubArchive::<Vec<String>>::from_bytes_unchecked(bytes) };
+        let buf = rkyv::to_bytes::<_, 256>(&corrupt).unwrap();
+        check_archived_root::<SubArchive<Vec<String>>>(buf.as_ref()).unwrap_err();
+
+        // The inner archive is checked with what's left of the limits of the outer archive
+        use rkyv::validation::validators::{check_archived_root_with_limits, ValidationLimits};
+
+        let inner = SubArchive::<Vec<String>>::from_value::<256>(&vec!["a".repeat(20); 4]).unwrap();
+        let buf = rkyv::to_bytes::<_, 256>(&vec![inner]).unwrap();
+        let check = |limits| {
+            check_archived_root_with_limits::<Vec<SubArchive<Vec<String>>>>(buf.as_ref(), limits)
+        };
+        check(ValidationLimits::default()).unwrap();
+        check(ValidationLimits {
+            max_string_len: 16,
+            ..ValidationLimits::default()
+        })
+        .unwrap_err();
+        // The outer root and its elements take up two levels, and the inner root, its elements and
+        // their strings take up three more
+        check(ValidationLimits {
+            max_subtree_depth: 5,
+            ..ValidationLimits::default()
+        })
+        .unwrap();
+        check(ValidationLimits {
+            max_subtree_depth: 4,
+            ..ValidationLimits::default()
+        })
+        .unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_extensible() {
+        use core::mem::size_of;
+        use rkyv::{to_archived, Archived};
+
+        mod old {
+            use super::*;
+
+            #[derive(Archive, Serialize)]
+            #[archive(extensible)]
+            #[archive_attr(derive(CheckBytes))]
+            pub struct Header {
+                pub a: u32,
+            }
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(extensible)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Header {
+            a: u32,
+            b: bool,
+        }
+
+        let bytes = rkyv::to_bytes::<_, 256>(&old::Header { a: 1 }).unwrap();
+        let archived = check_archived_root::<Header>(bytes.as_ref()).unwrap();
+        assert!(archived.a().is_some());
+        assert!(archived.b().is_none());
+
+        let mut bytes = rkyv::to_bytes::<_, 256>(&Header { a: 1, b: true }).unwrap();
+        check_archived_root::<Header>(bytes.as_ref()).unwrap();
+        check_archived_root::<old::Header>(bytes.as_ref()).unwrap();
+
+        // Fields that are present are checked, and readers without them ignore them
+        bytes[4] = 2;
+        assert!(check_archived_root::<Header>(bytes.as_ref()).is_err());
+        check_archived_root::<old::Header>(bytes.as_ref()).unwrap();
+
+        // Lengths that run past the end of the archive are rejected
+        let root = bytes.len() - size_of::<Archived<Header>>();
+        unsafe {
+            bytes
+                .as_mut_ptr()
+                .add(root)
+                .cast::<Archived<u32>>()
+                .write(to_archived!(1000u32));
+        }
+        assert!(check_archived_root::<old::Header>(bytes.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_arena() {
+        use rkyv::arena::{Arena, ArenaIndex};
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Node {
+            name: String,
+            edges: Vec<ArenaIndex<Node>>,
+        }
+
+        let mut nodes = Arena::new();
+        let a = nodes.push(Node {
+            name: "a".to_string(),
+            edges: Vec::new(),
+        });
+        let b = nodes.push(Node {
+            name: "b".to_string(),
+            edges: vec![a],
+        });
+        nodes[a].edges.push(b);
+        // Out of bounds indices are caught when they're looked up
+        nodes[b].edges.push(ArenaIndex::new(2));
+
+        let buf = rkyv::to_bytes::<_, 256>(&nodes).unwrap();
+        let archived = check_archived_root::<Arena<Node>>(buf.as_ref()).unwrap();
+        assert_eq!(archived[archived[a].edges[0]].name, "b");
+        assert!(archived.get(archived[b].edges[1]).is_none());
+    }
+
+    #[derive(Archive, Serialize)]
+    #[archive(bound(serialize = "__S: Serializer"))]
+    #[archive_attr(derive(CheckBytes, Debug))]
+    #[archive_attr(check_bytes(
+        bound = "__C: ::rkyv::validation::ArchiveContext, <__C as ::rkyv::Fallible>::Error: ::bytecheck::Error"
+    ))]
+    struct List {
+        value: u32,
+        #[omit_bounds]
+        #[archive_attr(omit_bounds)]
+        next: Option<Box<List>>,
+    }
+
+    fn make_list(len: u32) -> List {
+        (1..len).fold(
+            List {
+                value: 0,
+                next: None,
+            },
+            |next, value| List {
+                value,
+                next: Some(Box::new(next)),
+            },
+        )
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_max_depth() {
+        use rkyv::validation::validators::check_archived_root_with_max_depth;
+
+        let buf = rkyv::to_bytes::<_, 256>(&make_list(10)).unwrap();
+        check_archived_root::<List>(buf.as_ref()).unwrap();
+        // The root and each of the nine boxes are one level deep each
+        check_archived_root_with_max_depth::<List>(buf.as_ref(), 10).unwrap();
+        let error = check_archived_root_with_max_depth::<List>(buf.as_ref(), 9).unwrap_err();
+        assert!(error.to_string().contains("maximum subtree depth of 9"));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_pointer_cycle() {
+        use core::mem::size_of;
+        use rkyv::{archived_root, boxed::ArchivedBox, option::ArchivedOption, RawRelPtr};
+
+        let mut buf = rkyv::to_bytes::<_, 256>(&make_list(2)).unwrap();
+        check_archived_root::<List>(buf.as_ref()).unwrap();
+
+        let (root_pos, next_pos, box_offset, tail_next_pos) = unsafe {
+            let base = buf.as_ptr() as usize;
+            let root = archived_root::<List>(buf.as_ref());
+            let next = root.next.as_ref().unwrap();
+            (
+                root as *const ArchivedList as usize - base,
+                &root.next as *const _ as usize - base,
+                next as *const ArchivedBox<ArchivedList> as usize - &root.next as *const _ as usize,
+                &next.next as *const _ as usize - base,
+            )
+        };
+
+        // Point the tail of the list back at the root
+        let size = size_of::<ArchivedOption<ArchivedBox<ArchivedList>>>();
+        buf.as_mut_slice()
+            .copy_within(next_pos..next_pos + size, tail_next_pos);
+        let rel_ptr_pos = tail_next_pos + box_offset;
+        unsafe {
+            RawRelPtr::emplace(
+                rel_ptr_pos,
+                root_pos,
+                buf.as_mut_ptr().add(rel_ptr_pos).cast(),
+            );
+        }
+
+        let root = unsafe { archived_root::<List>(buf.as_ref()) };
+        let tail = root.next.as_ref().unwrap();
+        assert!(core::ptr::eq(&**tail.next.as_ref().unwrap(), root));
+
+        check_archived_root::<List>(buf.as_ref()).unwrap_err();
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn check_invalid_btreemap() {
@@ -555,4 +1058,441 @@ mod tests {
             )
         ));
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_error_report() {
+        #[cfg(not(feature = "std"))]
+        use alloc::format;
+        use rkyv::validation::validators::check_archived_root_with_report;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes, Debug))]
+        struct User {
+            name: String,
+            id: u32,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes, Debug))]
+        struct Database {
+            users: Vec<User>,
+        }
+
+        let value = Database {
+            users: (0..5)
+                .map(|id| User {
+                    name: format!("user number {}", id),
+                    id,
+                })
+                .collect(),
+        };
+        let mut buf = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        check_archived_root_with_report::<Database>(buf.as_ref()).unwrap();
+
+        let name_pos = buf.windows(13).position(|w| w == b"user number 3").unwrap();
+        buf[name_pos] = 0xff;
+
+        let report = check_archived_root_with_report::<Database>(buf.as_ref()).unwrap_err();
+        assert_eq!(report.path(), "root.users[3].name");
+        assert_eq!(report.offset(), Some(name_pos));
+        assert!(report.type_name().ends_with("Database"));
+        assert!(report.message().starts_with("utf8 error"));
+        assert!(report.to_string().contains(&format!(
+            "at byte offset {} in `root.users[3].name`",
+            name_pos
+        )));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_validation_limits() {
+        use rkyv::validation::validators::{check_archived_root_with_limits, ValidationLimits};
+
+        let limits = ValidationLimits {
+            max_collection_len: 100,
+            max_string_len: 16,
+            max_bytes: 256,
+            ..ValidationLimits::default()
+        };
+
+        let bytes = rkyv::to_bytes::<_, 256>(&vec![(); 100]).unwrap();
+        check_archived_root_with_limits::<Vec<()>>(&bytes, limits).unwrap();
+        let bytes = rkyv::to_bytes::<_, 256>(&vec![(); 101]).unwrap();
+        let error = check_archived_root_with_limits::<Vec<()>>(&bytes, limits).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("collection length 101 exceeded the maximum collection length of 100"));
+
+        let map = (0..101).map(|i| (i, ())).collect::<BTreeMap<u8, ()>>();
+        let bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+        let error =
+            check_archived_root_with_limits::<BTreeMap<u8, ()>>(&bytes, limits).unwrap_err();
+        assert!(error.to_string().contains("collection length 101"));
+
+        let bytes = rkyv::to_bytes::<_, 256>(&"a".repeat(16)).unwrap();
+        check_archived_root_with_limits::<String>(&bytes, limits).unwrap();
+        let bytes = rkyv::to_bytes::<_, 256>(&"a".repeat(17)).unwrap();
+        let error = check_archived_root_with_limits::<String>(&bytes, limits).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("string length 17 exceeded the maximum string length of 16"));
+
+        let bytes = rkyv::to_bytes::<_, 256>(&vec![0u32; 60]).unwrap();
+        check_archived_root_with_limits::<Vec<u32>>(&bytes, limits).unwrap();
+        let bytes = rkyv::to_bytes::<_, 256>(&vec![0u32; 70]).unwrap();
+        let error = check_archived_root_with_limits::<Vec<u32>>(&bytes, limits).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("exceeded the maximum total size of 256 bytes"));
+
+        // Limits apply to every collection and string that is pointed to
+        let bytes = rkyv::to_bytes::<_, 256>(&vec![(); 101].into_boxed_slice()).unwrap();
+        let error = check_archived_root_with_limits::<Box<[()]>>(&bytes, limits).unwrap_err();
+        assert!(error.to_string().contains("collection length 101"));
+        let bytes = rkyv::to_bytes::<_, 256>(&Rc::<[()]>::from(vec![(); 101])).unwrap();
+        let error = check_archived_root_with_limits::<Rc<[()]>>(&bytes, limits).unwrap_err();
+        assert!(error.to_string().contains("collection length 101"));
+        let bytes = rkyv::to_bytes::<_, 256>(&Box::<str>::from("a".repeat(17))).unwrap();
+        let error = check_archived_root_with_limits::<Box<str>>(&bytes, limits).unwrap_err();
+        assert!(error.to_string().contains("string length 17"));
+
+        let map = (0..60).map(|i| (i, i)).collect::<BTreeMap<u32, u32>>();
+        let bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+        let error =
+            check_archived_root_with_limits::<BTreeMap<u32, u32>>(&bytes, limits).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("exceeded the maximum total size of 256 bytes"));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_oversized_collection() {
+        use core::mem::size_of;
+        use rkyv::FixedUsize;
+
+        let mut bytes = rkyv::to_bytes::<_, 256>(&vec![0u64; 4]).unwrap();
+        // Claim that the vec has as many elements as its length can hold
+        let len = bytes.len();
+        bytes[len - size_of::<FixedUsize>()..].fill(0xff);
+        let error = check_archived_root::<Vec<u64>>(&bytes).unwrap_err();
+        // Only 64-bit lengths are large enough that the layout of the vec can't be computed
+        if cfg!(feature = "size_64") {
+            assert!(error.to_string().contains("failed to compute layout"));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "size_32"))]
+    fn check_parallel() {
+        use rkyv::validation::validators::{check_archived_root_parallel, ParallelOptions};
+
+        let options = ParallelOptions {
+            threads: 4,
+            min_chunk_len: 16,
+            ..ParallelOptions::default()
+        };
+
+        let value = (0..1000)
+            .map(|i| format!("element number {}", i))
+            .collect::<Vec<_>>();
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = check_archived_root_parallel::<Vec<String>, _>(&bytes, &options).unwrap();
+        assert_eq!(archived.len(), 1000);
+        assert_eq!(archived[999], "element number 999");
+
+        // Too short to split, so it's checked on the calling thread
+        let bytes = rkyv::to_bytes::<_, 256>(&vec!["a".to_string(); 8]).unwrap();
+        check_archived_root_parallel::<Vec<String>, _>(&bytes, &options).unwrap();
+
+        // An invalid element in a later chunk
+        let mut bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let pos = bytes
+            .windows(18)
+            .position(|w| w == b"element number 900")
+            .unwrap();
+        bytes[pos] = 0xff;
+        check_archived_root_parallel::<Vec<String>, _>(&bytes, &options).unwrap_err();
+
+        // The last box points to the value of the first box, which is claimed by an earlier chunk
+        let value = (0..100u32).map(Box::new).collect::<Vec<_>>();
+        let mut bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        check_archived_root_parallel::<Vec<Box<u32>>, _>(&bytes, &options).unwrap();
+        let archived = unsafe { rkyv::archived_root::<Vec<Box<u32>>>(&bytes) };
+        let first = archived[0].as_ref() as *const u32 as usize - bytes.as_ptr() as usize;
+        let last = &archived[99] as *const _ as usize - bytes.as_ptr() as usize;
+        let offset = (first as i32 - last as i32).to_le_bytes();
+        bytes[last..last + 4].copy_from_slice(&offset);
+        check_archived_root::<Vec<Box<u32>>>(&bytes).unwrap_err();
+        check_archived_root_parallel::<Vec<Box<u32>>, _>(&bytes, &options).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_compacted() {
+        use rkyv::{
+            append_to_bytes, archived_value, archived_value_mut,
+            compact::{compact, compact_roots, CompactError},
+            patch_box,
+            schema::ArchiveSchema,
+            Archived,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        #[archive_attr(derive(CheckBytes))]
+        enum Entry {
+            Empty,
+            Text(String),
+            Numbers { values: Vec<u32> },
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(schema)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Record {
+            name: String,
+            entries: Vec<Entry>,
+            detail: Option<Box<[u16; 3]>>,
+        }
+
+        let record = Record {
+            name: "a record with a long name".to_string(),
+            entries: vec![
+                Entry::Empty,
+                Entry::Text("some entry text".to_string()),
+                Entry::Numbers {
+                    values: vec![1, 2, 3],
+                },
+            ],
+            detail: Some(Box::new([4, 5, 6])),
+        };
+        let mut bytes = rkyv::to_bytes::<_, 256>(&record).unwrap();
+        let first_pos = bytes.len() - core::mem::size_of::<Archived<Record>>();
+        let second_pos = append_to_bytes::<_, 256>(&mut bytes, &record).unwrap();
+
+        patch_box::<_, _, 256>(
+            &mut bytes,
+            |bytes| unsafe {
+                archived_value_mut::<Record>(bytes, first_pos)
+                    .map_unchecked_mut(|record| &mut record.detail)
+                    .as_pin_mut()
+                    .unwrap()
+            },
+            &[7u16, 8, 9],
+        )
+        .unwrap();
+        // The patched box points forward
+        assert!(check_archived_value::<Record>(bytes.as_ref(), first_pos).is_err());
+
+        let compacted = compact::<Record>(bytes.as_ref(), first_pos).unwrap();
+        let archived = check_archived_root::<Record>(compacted.as_ref()).unwrap();
+        assert_eq!(archived.name, "a record with a long name");
+        assert_eq!(archived.entries.len(), 3);
+        assert_eq!(
+            archived.detail.as_ref().map(|detail| **detail),
+            Some([7, 8, 9])
+        );
+        // Compacting again doesn't change anything
+        let root_pos = compacted.len() - core::mem::size_of::<Archived<Record>>();
+        assert_eq!(
+            compact::<Record>(compacted.as_ref(), root_pos)
+                .unwrap()
+                .as_slice(),
+            compacted.as_slice()
+        );
+
+        let (compacted, positions) = compact_roots(
+            bytes.as_ref(),
+            &[(first_pos, &Record::SCHEMA), (second_pos, &Record::SCHEMA)],
+        )
+        .unwrap();
+        assert!(compacted.len() < bytes.len());
+        for (&pos, detail) in positions.iter().zip([[7, 8, 9], [4, 5, 6]]) {
+            let archived = check_archived_value::<Record>(compacted.as_ref(), pos).unwrap();
+            assert_eq!(
+                archived.detail.as_ref().map(|detail| **detail),
+                Some(detail)
+            );
+            if let ArchivedEntry::Numbers { values } = &archived.entries[2] {
+                assert_eq!(values.as_slice(), &[1, 2, 3]);
+            } else {
+                panic!("expected numbers");
+            }
+        }
+        let archived = unsafe { archived_value::<Record>(bytes.as_ref(), second_pos) };
+        assert_eq!(archived.entries.len(), 3);
+
+        assert_eq!(
+            compact::<Record>(bytes.as_ref(), bytes.len()).unwrap_err(),
+            CompactError::OutOfBounds { pos: bytes.len() }
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_multiple_roots() {
+        let mut serializer = DefaultSerializer::default();
+        let positions = ["first root value", "second root value", "third root value"]
+            .iter()
+            .map(|s| serializer.serialize_value(&s.to_string()).unwrap())
+            .collect::<Vec<_>>();
+        let mut buf = serializer.into_serializer().into_inner();
+
+        for (&pos, expected) in positions.iter().zip(["first", "second", "third"]) {
+            let value = check_archived_value::<String>(buf.as_ref(), pos).unwrap();
+            assert!(value.starts_with(expected));
+        }
+
+        // Corrupting one value only affects checking that value
+        let second = buf.windows(6).position(|w| w == b"second").unwrap();
+        buf[second] = 0xff;
+        check_archived_value::<String>(buf.as_ref(), positions[0]).unwrap();
+        check_archived_value::<String>(buf.as_ref(), positions[1]).unwrap_err();
+        check_archived_value::<String>(buf.as_ref(), positions[2]).unwrap();
+
+        check_archived_value::<String>(buf.as_ref(), usize::MAX).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_access() {
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes, Debug))]
+        struct Test {
+            id: u32,
+            name: String,
+        }
+
+        let value = Test {
+            id: 42,
+            name: "hello world".to_string(),
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+
+        let archived = rkyv::access::<Test>(&bytes).unwrap();
+        assert_eq!(archived.id, 42);
+        assert_eq!(archived.name, "hello world");
+
+        let pos = bytes.len() - core::mem::size_of::<rkyv::Archived<Test>>();
+        let archived = rkyv::access_pos::<Test>(&bytes, pos).unwrap();
+        assert_eq!(archived.id, 42);
+
+        rkyv::access::<Test>(&bytes[..8]).unwrap_err();
+        rkyv::access_pos::<Test>(&bytes, bytes.len()).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_unsized_tail() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(unsized)]
+        #[archive_attr(derive(CheckBytes, Debug))]
+        struct Record {
+            id: u64,
+            name: String,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(unsized)]
+        #[archive_attr(derive(CheckBytes, Debug))]
+        struct Chunk {
+            kind: u8,
+            data: Box<[u32]>,
+        }
+
+        // Unsized structs can only be archived behind a pointer
+        #[allow(clippy::vec_box)]
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive_attr(derive(CheckBytes, Debug))]
+        struct File {
+            record: Box<Record>,
+            chunks: Vec<Box<Chunk>>,
+        }
+
+        let value = File {
+            record: Box::new(Record {
+                id: 7,
+                name: "header".to_string(),
+            }),
+            chunks: vec![
+                Box::new(Chunk {
+                    kind: 1,
+                    data: vec![1, 2, 3].into_boxed_slice(),
+                }),
+                Box::new(Chunk {
+                    kind: 2,
+                    data: Vec::new().into_boxed_slice(),
+                }),
+            ],
+        };
+
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = check_archived_root::<File>(&bytes).unwrap();
+        assert_eq!(&archived.record.name, "header");
+        assert_eq!(archived.chunks[0].data, [1, 2, 3]);
+        assert_eq!(rkyv::from_bytes::<File>(&bytes).unwrap(), value);
+
+        // Invalid UTF-8 in a str tail
+        let mut bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let name = bytes.windows(6).position(|w| w == b"header").unwrap();
+        bytes[name] = 0xff;
+        check_archived_root::<File>(&bytes).unwrap_err();
+
+        // A tail that runs past the end of the buffer
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        check_archived_root::<File>(&bytes[..bytes.len() - 4]).unwrap_err();
+
+        // The archived tail is chosen by the type of the field, not by its name
+        mod bytes {
+            #[cfg(not(feature = "std"))]
+            use alloc::vec::Vec;
+
+            pub type String = Vec<u8>;
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(unsized)]
+        #[archive_attr(derive(CheckBytes, Debug))]
+        struct Blob {
+            data: bytes::String,
+        }
+
+        let value = Box::new(Blob {
+            data: vec![0xff, 0xfe],
+        });
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = check_archived_root::<Box<Blob>>(&bytes).unwrap();
+        assert_eq!(archived.data, [0xff, 0xfe]);
+        assert_eq!(rkyv::from_bytes::<Box<Blob>>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_value() {
+        use rkyv::value::Value;
+
+        let mut map = BTreeMap::new();
+        map.insert("list".to_string(), Value::Array(vec![Value::Int(1); 4]));
+        map.insert(
+            "name".to_string(),
+            Value::String("a long string value".to_string()),
+        );
+        let value = Value::Array(vec![Value::Map(map), Value::Null, Value::Float(1.5)]);
+
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        let archived = check_archived_root::<Value>(&bytes).unwrap();
+        assert_eq!(*archived, value);
+        assert_eq!(archived[0]["name"].as_str(), Some("a long string value"));
+
+        // An invalid tag
+        let mut bytes = rkyv::to_bytes::<_, 256>(&Value::Bool(true)).unwrap();
+        bytes.as_mut_slice()[0] = 0xff;
+        check_archived_root::<Value>(&bytes).unwrap_err();
+
+        // A truncated archive
+        let bytes = rkyv::to_bytes::<_, 256>(&value).unwrap();
+        check_archived_root::<Value>(&bytes[..bytes.len() - 4]).unwrap_err();
+    }
 }
@@ -25,4 +25,65 @@ mod tests {
         set.insert("baz".to_string());
         serialize_and_check(&set);
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_empty_hashmap() {
+        let map = HashMap::<String, u32>::new();
+        let bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+
+        let archived = rkyv::check_archived_root::<HashMap<String, u32>>(&bytes).unwrap();
+        assert!(archived.is_empty());
+        assert_eq!(archived.get("missing"), None);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_hashmap_invalid_key_position() {
+        use rkyv::{
+            collections::hash_map::validation::HashMapError, validation::CheckArchiveError,
+        };
+
+        let keys = [0xa5a5_0001u32, 0xa5a5_0002, 0xa5a5_0003, 0xa5a5_0004];
+        let map = keys.iter().map(|&k| (k, !k)).collect::<HashMap<_, _>>();
+        let mut bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+        rkyv::check_archived_root::<HashMap<u32, u32>>(&bytes).unwrap();
+
+        // Swap the keys of two entries without moving their values
+        let find = |bytes: &[u8], key: u32| {
+            bytes
+                .windows(4)
+                .position(|w| w == key.to_le_bytes())
+                .unwrap()
+        };
+        let first = find(&bytes, keys[0]);
+        let second = find(&bytes, keys[1]);
+        bytes[first..first + 4].copy_from_slice(&keys[1].to_le_bytes());
+        bytes[second..second + 4].copy_from_slice(&keys[0].to_le_bytes());
+
+        let error = rkyv::check_archived_root::<HashMap<u32, u32>>(&bytes).unwrap_err();
+        assert!(matches!(
+            error,
+            CheckArchiveError::CheckBytesError(HashMapError::InvalidKeyPosition { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_unsized_std_types() {
+        use std::{
+            ffi::{CStr, OsStr},
+            path::Path,
+        };
+
+        serialize_and_check(&Box::<CStr>::from(
+            CStr::from_bytes_with_nul(b"hello\0").unwrap(),
+        ));
+        serialize_and_check(&Box::<OsStr>::from(OsStr::new("hello")));
+        serialize_and_check(&Box::<Path>::from(Path::new("dir/file.txt")));
+        serialize_and_check(&vec![
+            Box::<Path>::from(Path::new("a")),
+            Box::<Path>::from(Path::new("")),
+        ]);
+    }
 }